@@ -538,6 +538,27 @@ impl KarlsenCli {
         Ok((wallet_secret, payment_secret))
     }
 
+    /// Asks the user for the policy secret of the account and approves with it the send
+    /// awaiting a confirmation with `approval_token`.
+    pub(crate) async fn approve_send(
+        &self,
+        account: &Arc<dyn Account>,
+        approval_token: &str,
+    ) -> Result<()> {
+        let policy_secret = Secret::new(
+            self.term()
+                .ask(true, "Enter policy password: ")
+                .await?
+                .trim()
+                .as_bytes()
+                .to_vec(),
+        );
+        self.wallet()
+            .spending_guard()
+            .approve_send(account, approval_token, &policy_secret)?;
+        Ok(())
+    }
+
     pub async fn account(&self) -> Result<Arc<dyn Account>> {
         if let Ok(account) = self.wallet.account() {
             Ok(account)
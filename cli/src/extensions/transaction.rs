@@ -130,6 +130,14 @@ impl TransactionExtension for TransactionRecord {
             "{name} {id} @{block_daa_score} DAA - {kind} {state}"
         )];
 
+        if let Some(payload) = self.payload() {
+            let memo = self
+                .memo()
+                .map(|memo| memo.to_string())
+                .unwrap_or_else(|| payload.to_hex());
+            lines.push(format!("{:>4}Memo: {}", "", style(memo).dim()));
+        }
+
         let suffix = karlsen_suffix(&self.network_id.network_type);
 
         match transaction_data {
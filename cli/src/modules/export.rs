@@ -1,5 +1,8 @@
 use crate::imports::*;
-use karlsen_wallet_core::account::{multisig::MultiSig, Account, MULTISIG_ACCOUNT_KIND};
+use karlsen_wallet_core::account::{
+    bip32::Bip32, multisig::MultiSig, Account, BIP32_ACCOUNT_KIND, MULTISIG_ACCOUNT_KIND,
+};
+use karlsen_wallet_keys::xkey::xpub_prefix;
 
 #[derive(Default, Handler)]
 #[help("Export transactions, a wallet or a private key")]
@@ -15,7 +18,7 @@ impl Export {
         let ctx = ctx.clone().downcast_arc::<KarlsenCli>()?;
 
         if argv.is_empty() || argv.first() == Some(&"help".to_string()) {
-            tprintln!(ctx, "usage: export [mnemonic]");
+            tprintln!(ctx, "usage: export [mnemonic|xpub]");
             return Ok(());
         }
 
@@ -30,11 +33,33 @@ impl Export {
                     export_single_key_account(ctx, account).await
                 }
             }
+            "xpub" => {
+                let account = ctx.account().await?;
+                export_xpub_keys(ctx, account).await
+            }
             _ => Err(format!("Invalid argument: {}", what).into()),
         }
     }
 }
 
+async fn export_xpub_keys(ctx: Arc<KarlsenCli>, account: Arc<dyn Account>) -> Result<()> {
+    let xpub_keys = match account.account_kind().as_ref() {
+        BIP32_ACCOUNT_KIND => account.downcast_arc::<Bip32>()?.xpub_keys().clone(),
+        MULTISIG_ACCOUNT_KIND => account.downcast_arc::<MultiSig>()?.xpub_keys().clone(),
+        _ => {
+            return Err(Error::custom(
+                "xpub keys are not available for this account",
+            ))
+        }
+    };
+
+    let prefix = xpub_prefix(ctx.wallet().network_id()?.network_type());
+    for xpub in xpub_keys.iter() {
+        tprintln!(ctx, "{}", xpub.to_string(Some(prefix)));
+    }
+    Ok(())
+}
+
 async fn export_multisig_account(ctx: Arc<KarlsenCli>, account: Arc<MultiSig>) -> Result<()> {
     match &account.prv_key_data_ids() {
         None => Err(Error::KeyDataNotFound),
@@ -2,7 +2,7 @@ use karlsen_addresses::Version;
 use karlsen_bip32::secp256k1::XOnlyPublicKey;
 use karlsen_wallet_core::{
     account::{BIP32_ACCOUNT_KIND, KEYPAIR_ACCOUNT_KIND},
-    message::{sign_message, verify_message, PersonalMessage},
+    message::{sign_address_message, verify_address_message, verify_message, PersonalMessage},
 };
 
 use crate::imports::*;
@@ -97,25 +97,26 @@ impl Message {
         message: &str,
     ) -> Result<()> {
         let karlsen_address = Address::try_from(karlsen_address)?;
-        if karlsen_address.version != Version::PubKey {
+        if !matches!(
+            karlsen_address.version,
+            Version::PubKey | Version::PubKeyECDSA
+        ) {
             return Err(Error::custom(
-                "Address not supported for message signing. Only supports PubKey addresses",
+                "Address not supported for message signing. Only supports PubKey and PubKeyECDSA addresses",
             ));
         }
 
-        let pm = PersonalMessage(message);
-        let privkey = self.get_address_private_key(&ctx, karlsen_address).await?;
+        let privkey = self
+            .get_address_private_key(&ctx, karlsen_address.clone())
+            .await?;
+        let signature = sign_address_message(message, &privkey, &karlsen_address)?;
+        tprintln!(
+            ctx,
+            "Signature: {}",
+            faster_hex::hex_string(signature.as_slice())
+        );
 
-        let sig_result = sign_message(&pm, &privkey);
-
-        match sig_result {
-            Ok(signature) => {
-                let sig_hex = faster_hex::hex_string(signature.as_slice());
-                tprintln!(ctx, "Signature: {}", sig_hex);
-                Ok(())
-            }
-            Err(_) => Err(Error::custom("Message signing failed")),
-        }
+        Ok(())
     }
 
     async fn verify(
@@ -126,30 +127,29 @@ impl Message {
         message: &str,
     ) -> Result<()> {
         let karlsen_address = Address::try_from(karlsen_address)?;
-        if karlsen_address.version != Version::PubKey {
-            return Err(Error::custom(
-                "Address not supported for message signing. Only supports PubKey addresses",
-            ));
-        }
-
-        let pubkey = XOnlyPublicKey::from_slice(&karlsen_address.payload[0..32]).unwrap();
 
         let mut signature_hex = [0u8; 64];
         faster_hex::hex_decode(signature.as_bytes(), &mut signature_hex)?;
 
-        let pm = PersonalMessage(message);
-        let verify_result = verify_message(&pm, &signature_hex.to_vec(), &pubkey);
+        if verify_address_message(message.as_bytes(), &signature_hex, &karlsen_address).is_ok() {
+            tprintln!(ctx, "Message verified successfully!");
+            return Ok(());
+        }
 
-        match verify_result {
-            Ok(()) => {
-                tprintln!(ctx, "Message verified successfully!");
-            }
-            Err(_) => {
-                return Err(Error::custom("Verification failed"));
+        // Signatures made before the domain separated scheme are personal messages
+        if karlsen_address.version == Version::PubKey {
+            let pubkey = XOnlyPublicKey::from_slice(&karlsen_address.payload[0..32]).unwrap();
+            let pm = PersonalMessage(message);
+            if verify_message(&pm, &signature_hex.to_vec(), &pubkey).is_ok() {
+                tprintln!(
+                    ctx,
+                    "Message verified successfully! (legacy personal message signature)"
+                );
+                return Ok(());
             }
         }
 
-        Ok(())
+        Err(Error::custom("Verification failed"))
     }
 
     async fn get_address_private_key(
@@ -1,7 +1,12 @@
 use crate::imports::*;
-use karlsen_daemon::{locate_binaries, CpuMinerConfig};
+use karlsen_daemon::{locate_binaries, parse_hashrate, CpuMinerConfig, CpuMinerReport};
+use karlsen_rpc_core::GetMinerStatsRequest;
+use workflow_core::time::{Duration, Instant};
 pub use workflow_node::process::Event;
 
+/// Interval between two profitability log lines of the running miner
+const REPORT_INTERVAL: Duration = Duration::from_secs(60);
+
 #[derive(Describe, Debug, Clone, Serialize, Deserialize, Hash, Eq, PartialEq, Ord, PartialOrd)]
 #[serde(rename_all = "lowercase")]
 pub enum MinerSettings {
@@ -41,6 +46,9 @@ pub struct Miner {
     settings: SettingsStore<MinerSettings>,
     mute: Arc<AtomicBool>,
     is_running: Arc<AtomicBool>,
+    /// Latest hashrate logged by the miner, in hashes per second
+    hashrate: Mutex<Option<f64>>,
+    last_report: Mutex<Option<Instant>>,
 }
 
 impl Default for Miner {
@@ -50,6 +58,8 @@ impl Default for Miner {
                 .expect("Failed to create miner settings store"),
             mute: Arc::new(AtomicBool::new(true)),
             is_running: Arc::new(AtomicBool::new(false)),
+            hashrate: Mutex::new(None),
+            last_report: Mutex::new(None),
         }
     }
 }
@@ -93,6 +103,54 @@ impl Miner {
         self.is_running.load(Ordering::SeqCst)
     }
 
+    /// Combines the latest hashrate logged by the miner with the network hashrate and the block
+    /// reward reported by the node, `None` until the miner logs its hashrate
+    async fn report(&self, ctx: &Arc<KarlsenCli>) -> Result<Option<CpuMinerReport>> {
+        let Some(hashrate) = *self.hashrate.lock().unwrap() else {
+            return Ok(None);
+        };
+        let network_id = ctx.wallet().network_id()?;
+        let stats = ctx
+            .wallet()
+            .rpc_api()
+            .get_miner_stats_call(GetMinerStatsRequest {})
+            .await?;
+        Ok(Some(CpuMinerReport::new(
+            network_id.into(),
+            hashrate,
+            stats.network_hashrate,
+            stats.block_reward,
+            stats.daily_power_cost,
+        )))
+    }
+
+    /// Records the hashrate the miner logs, writing a profitability log line every
+    /// [`REPORT_INTERVAL`]
+    async fn handle_output(&self, ctx: &Arc<KarlsenCli>, text: &str) {
+        let Some(hashrate) = text.lines().filter_map(parse_hashrate).last() else {
+            return;
+        };
+        self.hashrate.lock().unwrap().replace(hashrate);
+
+        let now = Instant::now();
+        {
+            let mut last_report = self.last_report.lock().unwrap();
+            if last_report.is_some_and(|time| now.duration_since(time) < REPORT_INTERVAL) {
+                return;
+            }
+            last_report.replace(now);
+        }
+        match self.report(ctx).await {
+            Ok(Some(report)) => ctx.term().writeln(format!("Miner stats: {report}")),
+            Ok(None) => {}
+            Err(err) => ctx.term().writeln(
+                style(format!("Unable to get the miner stats: {err}"))
+                    .yellow()
+                    .to_string(),
+            ),
+        }
+    }
+
     async fn create_config(&self, ctx: &Arc<KarlsenCli>) -> Result<CpuMinerConfig> {
         let location: String = self.settings.get(MinerSettings::Location).ok_or_else(|| {
             Error::Custom(
@@ -182,6 +240,17 @@ impl Miner {
             "status" => {
                 let status = cpu_miner.status().await?;
                 tprintln!(ctx, "{}", status);
+                match self.report(&ctx).await? {
+                    Some(report) => tprintln!(ctx, "{}", report),
+                    None => tprintln!(
+                        ctx,
+                        "{}",
+                        style(
+                            "(the miner has not logged its hashrate yet, its logs must be unmuted)"
+                        )
+                        .dim()
+                    ),
+                }
             }
             "select" => {
                 self.select(ctx).await?;
@@ -216,7 +285,10 @@ impl Miner {
                 ("stop", "Stop the local CPU miner instance"),
                 ("restart", "Restart the local CPU miner instance"),
                 ("kill", "Kill the local CPU miner instance"),
-                ("status", "Get the status of the local CPU miner instance"),
+                (
+                    "status",
+                    "Get the status and the profitability of the local CPU miner instance",
+                ),
                 ("throttle <msec>", "Change CPU miner throttle value"),
             ],
             None,
@@ -265,6 +337,7 @@ impl Miner {
             Event::Exit(_code) => {
                 tprintln!(ctx, "Miner has exited");
                 self.is_running.store(false, Ordering::SeqCst);
+                self.hashrate.lock().unwrap().take();
                 term.refresh_prompt();
             }
             Event::Error(error) => {
@@ -273,6 +346,7 @@ impl Miner {
                 term.refresh_prompt();
             }
             Event::Stdout(text) | Event::Stderr(text) => {
+                self.handle_output(ctx, &text).await;
                 let sanitize = true;
                 if sanitize {
                     let lines = text.split('\n').collect::<Vec<_>>();
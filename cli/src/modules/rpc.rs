@@ -51,10 +51,95 @@ impl Rpc {
                 let result = rpc.get_server_info_call(GetServerInfoRequest {}).await?;
                 self.println(&ctx, result);
             }
+            RpcApiOps::GetServerCapabilities => {
+                let result = rpc
+                    .get_server_capabilities_call(GetServerCapabilitiesRequest {})
+                    .await?;
+                self.println(&ctx, result);
+            }
+            RpcApiOps::GetNetworkInfo => {
+                let result = rpc.get_network_info_call(GetNetworkInfoRequest {}).await?;
+                self.println(&ctx, result);
+            }
+            RpcApiOps::GetDifficultyInfo => {
+                let chain_block_count = match argv.is_empty() {
+                    true => 10,
+                    false => argv.remove(0).parse::<u32>()?,
+                };
+                let result = rpc
+                    .get_difficulty_info_call(GetDifficultyInfoRequest { chain_block_count })
+                    .await?;
+                self.println(&ctx, result);
+            }
+            RpcApiOps::GetDagSlice => {
+                let from_daa_score = match argv.is_empty() {
+                    true => 0,
+                    false => argv.remove(0).parse::<u64>()?,
+                };
+                let count = match argv.is_empty() {
+                    true => 100,
+                    false => argv.remove(0).parse::<u32>()?,
+                };
+                let result = rpc
+                    .get_dag_slice_call(GetDagSliceRequest {
+                        from_daa_score,
+                        count,
+                    })
+                    .await?;
+                self.println(&ctx, result);
+            }
+            RpcApiOps::GetAcceptanceEventsSince => {
+                let cursor = match argv.is_empty() {
+                    true => 0,
+                    false => argv.remove(0).parse::<u64>()?,
+                };
+                let result = rpc
+                    .get_acceptance_events_since_call(GetAcceptanceEventsSinceRequest {
+                        cursor,
+                        max_count: 100,
+                    })
+                    .await?;
+                self.println(&ctx, result);
+            }
+            RpcApiOps::GetBlockFilterHeaders => {
+                if argv.is_empty() {
+                    return Err(Error::custom("Missing start hash argument"));
+                }
+                let start_hash = RpcHash::from_hex(argv.remove(0).as_str())?;
+                let limit = match argv.is_empty() {
+                    true => 100,
+                    false => argv.remove(0).parse::<u32>()?,
+                };
+                let result = rpc
+                    .get_block_filter_headers_call(GetBlockFilterHeadersRequest {
+                        start_hash,
+                        limit,
+                    })
+                    .await?;
+                self.println(&ctx, result);
+            }
+            RpcApiOps::GetBlockFilters => {
+                if argv.is_empty() {
+                    return Err(Error::custom("Missing start hash argument"));
+                }
+                let start_hash = RpcHash::from_hex(argv.remove(0).as_str())?;
+                let limit = match argv.is_empty() {
+                    true => 100,
+                    false => argv.remove(0).parse::<u32>()?,
+                };
+                let result = rpc
+                    .get_block_filters_call(GetBlockFiltersRequest { start_hash, limit })
+                    .await?;
+                self.println(&ctx, result);
+            }
             RpcApiOps::GetSyncStatus => {
                 let result = rpc.get_sync_status_call(GetSyncStatusRequest {}).await?;
                 self.println(&ctx, result);
             }
+            RpcApiOps::GetMinerStats => {
+                let result = rpc.get_miner_stats_call(GetMinerStatsRequest {}).await?;
+                self.println(&ctx, result);
+            }
             RpcApiOps::GetCurrentNetwork => {
                 let result = rpc
                     .get_current_network_call(GetCurrentNetworkRequest {})
@@ -69,6 +154,41 @@ impl Rpc {
             //     let result = rpc.get_block_template_call(GetBlockTemplateRequest {  }).await?;
             //     self.println(&ctx, result);
             // }
+            // RpcApiOps::GetTemplateDiff => {
+            //     let result = rpc.get_template_diff_call(GetTemplateDiffRequest {  }).await?;
+            //     self.println(&ctx, result);
+            // }
+            RpcApiOps::GetMetadata => {
+                if argv.is_empty() {
+                    return Err(Error::custom("Missing namespace argument"));
+                }
+                let namespace = argv.remove(0);
+                let result = rpc
+                    .get_metadata_call(GetMetadataRequest {
+                        namespace,
+                        keys: argv,
+                    })
+                    .await?;
+                self.println(&ctx, result);
+            }
+            // RpcApiOps::UpdateMetadata => {
+            //     let result = rpc.update_metadata_call(UpdateMetadataRequest {  }).await?;
+            //     self.println(&ctx, result);
+            // }
+            RpcApiOps::GetAddressCluster => {
+                if argv.is_empty() {
+                    return Err(Error::custom("Missing address argument"));
+                }
+                let address = Address::try_from(argv.remove(0).as_str())?;
+                let limit = match argv.is_empty() {
+                    true => 100,
+                    false => argv.remove(0).parse::<u32>()?,
+                };
+                let result = rpc
+                    .get_address_cluster_call(GetAddressClusterRequest { address, limit })
+                    .await?;
+                self.println(&ctx, result);
+            }
             RpcApiOps::GetPeerAddresses => {
                 let result = rpc
                     .get_peer_addresses_call(GetPeerAddressesRequest {})
@@ -86,10 +206,7 @@ impl Rpc {
             RpcApiOps::GetMempoolEntries => {
                 // TODO
                 let result = rpc
-                    .get_mempool_entries_call(GetMempoolEntriesRequest {
-                        include_orphan_pool: true,
-                        filter_transaction_pool: true,
-                    })
+                    .get_mempool_entries_call(GetMempoolEntriesRequest::new(true, true))
                     .await?;
                 self.println(&ctx, result);
             }
@@ -129,10 +246,33 @@ impl Rpc {
                     .get_block_call(GetBlockRequest {
                         hash,
                         include_transactions: true,
+                        resolve_previous_outputs: false,
                     })
                     .await?;
                 self.println(&ctx, result);
             }
+            RpcApiOps::GetTransactionStatus => {
+                if argv.is_empty() {
+                    return Err(Error::custom("Missing transaction id argument"));
+                }
+                let transaction_id = argv.remove(0);
+                let transaction_id = RpcHash::from_hex(transaction_id.as_str())?;
+                let result = rpc
+                    .get_transaction_status_call(GetTransactionStatusRequest { transaction_id })
+                    .await?;
+                self.println(&ctx, result);
+            }
+            RpcApiOps::GetUnconfirmedTxRisk => {
+                if argv.is_empty() {
+                    return Err(Error::custom("Missing transaction id argument"));
+                }
+                let transaction_id = argv.remove(0);
+                let transaction_id = RpcHash::from_hex(transaction_id.as_str())?;
+                let result = rpc
+                    .get_unconfirmed_tx_risk_call(GetUnconfirmedTxRiskRequest { transaction_id })
+                    .await?;
+                self.println(&ctx, result);
+            }
             // RpcApiOps::GetSubnetwork => {
             //     let result = rpc.get_subnetwork_call(GetSubnetworkRequest {  }).await?;
             //     self.println(&ctx, result);
@@ -176,7 +316,7 @@ impl Rpc {
                     .map(|s| Address::try_from(s.as_str()))
                     .collect::<std::result::Result<Vec<_>, _>>()?;
                 let result = rpc
-                    .get_utxos_by_addresses_call(GetUtxosByAddressesRequest { addresses })
+                    .get_utxos_by_addresses_call(GetUtxosByAddressesRequest::new(addresses))
                     .await?;
                 self.println(&ctx, result);
             }
@@ -208,6 +348,25 @@ impl Rpc {
                     .await?;
                 self.println(&ctx, result);
             }
+            RpcApiOps::GetBalanceByAddressesAt => {
+                if argv.len() < 2 {
+                    return Err(Error::custom(
+                        "Please specify a DAA score followed by at least one address",
+                    ));
+                }
+                let daa_score = argv.remove(0).parse::<u64>()?;
+                let addresses = argv
+                    .iter()
+                    .map(|s| Address::try_from(s.as_str()))
+                    .collect::<std::result::Result<Vec<_>, _>>()?;
+                let result = rpc
+                    .get_balance_by_addresses_at_call(GetBalanceByAddressesAtRequest {
+                        addresses,
+                        daa_score,
+                    })
+                    .await?;
+                self.println(&ctx, result);
+            }
             RpcApiOps::GetSinkBlueScore => {
                 let result = rpc
                     .get_sink_blue_score_call(GetSinkBlueScoreRequest {})
@@ -253,6 +412,7 @@ impl Rpc {
                         addresses,
                         include_orphan_pool,
                         filter_transaction_pool,
+                        resolve_previous_outputs: false,
                     })
                     .await?;
                 self.println(&ctx, result);
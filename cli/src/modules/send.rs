@@ -1,4 +1,5 @@
 use crate::imports::*;
+use karlsen_wallet_core::tx::validate_memo;
 
 #[derive(Default, Handler)]
 #[help("Send a Karlsen transaction to a public address")]
@@ -16,26 +17,64 @@ impl Send {
 
         let account = ctx.wallet().account()?;
 
+        // --dry-run builds the transactions without signing nor submitting them
+        let dry_run = argv.iter().any(|arg| arg == "--dry-run");
+        // --approve=<token> confirms a send requiring an approval under the account spending policy,
+        // once approved with the policy secret
+        let approval_token = argv
+            .iter()
+            .find_map(|arg| arg.strip_prefix("--approve="))
+            .map(String::from);
+        let argv = argv
+            .into_iter()
+            .filter(|arg| arg != "--dry-run" && !arg.starts_with("--approve="))
+            .collect::<Vec<_>>();
+
         if argv.len() < 2 {
-            tprintln!(ctx, "usage: send <address> <amount> <priority fee>");
+            tprintln!(
+                ctx,
+                "usage: send <address> <amount> <priority fee> [<memo>] [--dry-run] [--approve=<token>]"
+            );
             return Ok(());
         }
 
         let address = Address::try_from(argv.first().unwrap().as_str())?;
         let amount_sompi = try_parse_required_nonzero_karlsen_as_sompi_u64(argv.get(1))?;
         let priority_fee_sompi = try_parse_optional_karlsen_as_sompi_i64(argv.get(2))?.unwrap_or(0);
+        let payload = argv.get(3).map(|memo| memo.as_bytes().to_vec());
+        if let Some(payload) = payload.as_ref() {
+            validate_memo(payload)?;
+        }
         let outputs = PaymentOutputs::from((address.clone(), amount_sompi));
         let abortable = Abortable::default();
+
+        if dry_run {
+            let summary = account
+                .estimate(
+                    outputs.into(),
+                    priority_fee_sompi.into(),
+                    payload,
+                    &abortable,
+                )
+                .await?;
+            tprintln!(ctx, "Dry run - {summary}");
+            return Ok(());
+        }
+
         let (wallet_secret, payment_secret) = ctx.ask_wallet_secret(Some(&account)).await?;
+        if let Some(approval_token) = approval_token.as_deref() {
+            ctx.approve_send(&account, approval_token).await?;
+        }
 
         // let ctx_ = ctx.clone();
         let (summary, _ids) = account
             .send(
                 outputs.into(),
                 priority_fee_sompi.into(),
-                None,
+                payload,
                 wallet_secret,
                 payment_secret,
+                approval_token,
                 &abortable,
                 Some(Arc::new(move |_ptx| {
                     // tprintln!(ctx_, "Sending transaction: {}", ptx.id());
@@ -15,8 +15,22 @@ impl Transfer {
 
         let account = ctx.wallet().account()?;
 
+        // --approve=<token> confirms a transfer requiring an approval under the account spending
+        // policy, once approved with the policy secret
+        let approval_token = argv
+            .iter()
+            .find_map(|arg| arg.strip_prefix("--approve="))
+            .map(String::from);
+        let argv = argv
+            .into_iter()
+            .filter(|arg| !arg.starts_with("--approve="))
+            .collect::<Vec<_>>();
+
         if argv.len() < 2 {
-            tprintln!(ctx, "usage: transfer <account> <amount> <priority fee>");
+            tprintln!(
+                ctx,
+                "usage: transfer <account> <amount> <priority fee> [--approve=<token>]"
+            );
             return Ok(());
         }
 
@@ -29,6 +43,9 @@ impl Transfer {
         let priority_fee_sompi = try_parse_optional_karlsen_as_sompi_i64(argv.get(2))?.unwrap_or(0);
         let target_address = target_account.receive_address()?;
         let (wallet_secret, payment_secret) = ctx.ask_wallet_secret(Some(&account)).await?;
+        if let Some(approval_token) = approval_token.as_deref() {
+            ctx.approve_send(&account, approval_token).await?;
+        }
 
         let abortable = Abortable::default();
         let outputs = PaymentOutputs::from((target_address.clone(), amount_sompi));
@@ -41,6 +58,7 @@ impl Transfer {
                 None,
                 wallet_secret,
                 payment_secret,
+                approval_token,
                 &abortable,
                 Some(Arc::new(move |_ptx| {
                     // tprintln!(ctx_, "Sending transaction: {}", ptx.id());
@@ -3,7 +3,7 @@ use std::{
     collections::{HashMap, HashSet},
     net::{IpAddr, SocketAddr, ToSocketAddrs},
     sync::Arc,
-    time::{Duration, SystemTime},
+    time::{Duration, Instant, SystemTime},
 };
 
 use duration_string::DurationString;
@@ -12,7 +12,7 @@ use itertools::Itertools;
 use karlsen_addressmanager::{AddressManager, NetAddress};
 use karlsen_core::{debug, info, warn};
 use karlsen_p2p_lib::{common::ProtocolError, ConnectionError, Peer};
-use karlsen_utils::triggers::SingleTrigger;
+use karlsen_utils::{networking::PrefixBucket, triggers::SingleTrigger};
 use parking_lot::Mutex as ParkingLotMutex;
 use rand::{seq::SliceRandom, thread_rng};
 use tokio::{
@@ -24,6 +24,13 @@ use tokio::{
     time::{interval, MissedTickBehavior},
 };
 
+mod quality;
+
+use quality::{select_eviction, OutboundCandidate};
+
+/// Minimum interval between two evictions of low quality outbound peers
+const OUTBOUND_CHURN_INTERVAL: Duration = Duration::from_secs(20 * 60);
+
 pub struct ConnectionManager {
     p2p_adaptor: Arc<karlsen_p2p_lib::Adaptor>,
     outbound_target: usize,
@@ -34,6 +41,7 @@ pub struct ConnectionManager {
     connection_requests: TokioMutex<HashMap<SocketAddr, ConnectionRequest>>,
     force_next_iteration: UnboundedSender<()>,
     shutdown_signal: SingleTrigger,
+    last_outbound_churn: ParkingLotMutex<Instant>,
 }
 
 #[derive(Clone, Debug)]
@@ -71,6 +79,7 @@ impl ConnectionManager {
             connection_requests: Default::default(),
             force_next_iteration: tx,
             shutdown_signal: SingleTrigger::new(),
+            last_outbound_churn: ParkingLotMutex::new(Instant::now()),
             dns_seeders,
             default_port,
         });
@@ -108,6 +117,7 @@ impl ConnectionManager {
         self.handle_connection_requests(&peer_by_address).await;
         self.handle_outbound_connections(&peer_by_address).await;
         self.handle_inbound_connections(&peer_by_address).await;
+        self.handle_outbound_churn(&peer_by_address).await;
     }
 
     pub async fn add_connection_request(&self, address: SocketAddr, is_permanent: bool) {
@@ -191,10 +201,19 @@ impl ConnectionManager {
         }
 
         let mut missing_connections = self.outbound_target - active_outbound.len();
-        let mut addr_iter = self
+        // Addresses sharing a prefix bucket with an outbound peer are only tried once all the
+        // others were, so a single network operator hardly fills several outbound slots
+        let mut used_buckets: HashSet<PrefixBucket> = active_outbound
+            .iter()
+            .map(|address| address.prefix_bucket())
+            .collect();
+        let (mut addresses, crowded): (Vec<_>, Vec<_>) = self
             .address_manager
             .lock()
-            .iterate_prioritized_random_addresses(active_outbound);
+            .iterate_prioritized_random_addresses(active_outbound)
+            .partition(|address| used_buckets.insert(address.prefix_bucket()));
+        addresses.extend(crowded);
+        let mut addr_iter = addresses.into_iter();
 
         let mut progressing = true;
         let mut connecting = true;
@@ -267,6 +286,41 @@ impl ConnectionManager {
         }
     }
 
+    /// Evicts a low quality outbound peer once in a while when all the outbound slots are
+    /// taken, freeing its slot for a fresh address. See [`quality`] for the selection rules.
+    async fn handle_outbound_churn(self: &Arc<Self>, peer_by_address: &HashMap<SocketAddr, Peer>) {
+        if self.last_outbound_churn.lock().elapsed() < OUTBOUND_CHURN_INTERVAL {
+            return;
+        }
+        let outbound = peer_by_address
+            .values()
+            .filter(|peer| peer.is_outbound())
+            .collect_vec();
+        if outbound.len() < self.outbound_target {
+            return;
+        }
+
+        // Peers requested explicitly are never evicted
+        let requests = self.connection_requests.lock().await;
+        let candidates = outbound
+            .into_iter()
+            .filter(|peer| !requests.contains_key(&peer.net_address()))
+            .map(OutboundCandidate::from)
+            .collect_vec();
+        drop(requests);
+
+        let Some(key) = select_eviction(&candidates, self.outbound_target / 2) else {
+            return;
+        };
+        *self.last_outbound_churn.lock() = Instant::now();
+        debug!(
+            "Disconnecting from outbound peer {} because of its low quality score",
+            key
+        );
+        self.p2p_adaptor.terminate(key).await;
+        let _ = self.force_next_iteration.send(());
+    }
+
     async fn handle_inbound_connections(
         self: &Arc<Self>,
         peer_by_address: &HashMap<SocketAddr, Peer>,
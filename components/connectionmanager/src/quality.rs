@@ -0,0 +1,145 @@
+//! Quality scoring of the outbound peers.
+//!
+//! Peers earn score by doing useful work for the node, ie. being the first to announce new
+//! blocks and relaying valid transactions, per hour of connection and discounted by their
+//! ping latency. Once the outbound slots are full, the connection manager periodically evicts
+//! a low scored peer so its slot goes to a fresh address while the best peers stay connected.
+//!
+//! Announcing blocks fast is however cheap for an attacker running many nodes, so scores alone
+//! must not decide which peers stay connected. Only the best peer of a prefix bucket can be
+//! protected by its score, and the eviction targets the most crowded bucket first, so an
+//! attacker needs addresses in many distinct buckets in order to eclipse the node.
+
+use itertools::Itertools;
+use karlsen_p2p_lib::{Peer, PeerKey};
+use karlsen_utils::networking::{IpAddress, PrefixBucket};
+use std::{collections::HashMap, time::Duration};
+
+/// Minimum connection time before a peer is scored, and thus before it may be evicted
+pub(crate) const MIN_EVALUATION_TIME: Duration = Duration::from_secs(10 * 60);
+
+/// Weight of a new block first announced by a peer, relative to a relayed transaction
+const BLOCK_WEIGHT: f64 = 10.0;
+
+/// Latency at which the score of a peer is halved, in milliseconds
+const LATENCY_HALVING_MILLIS: f64 = 1_000.0;
+
+/// Score of a peer: its weighted useful work per hour of connection, discounted by its latency
+pub(crate) fn score(peer: &Peer) -> f64 {
+    let quality = peer.quality();
+    let work =
+        quality.blocks_first_announced as f64 * BLOCK_WEIGHT + quality.transactions_relayed as f64;
+    let hours = Duration::from_millis(peer.time_connected())
+        .max(MIN_EVALUATION_TIME)
+        .as_secs_f64()
+        / 3_600.0;
+    work / hours / (1.0 + peer.last_ping_duration() as f64 / LATENCY_HALVING_MILLIS)
+}
+
+/// An outbound peer subject to eviction
+#[derive(Debug, Clone)]
+pub(crate) struct OutboundCandidate {
+    key: PeerKey,
+    bucket: PrefixBucket,
+    score: f64,
+    evaluated: bool,
+}
+
+impl OutboundCandidate {
+    pub(crate) fn new(key: PeerKey, bucket: PrefixBucket, score: f64, evaluated: bool) -> Self {
+        Self {
+            key,
+            bucket,
+            score,
+            evaluated,
+        }
+    }
+}
+
+impl From<&Peer> for OutboundCandidate {
+    fn from(peer: &Peer) -> Self {
+        Self::new(
+            peer.key(),
+            IpAddress::from(peer.net_address().ip()).prefix_bucket(),
+            score(peer),
+            Duration::from_millis(peer.time_connected()) >= MIN_EVALUATION_TIME,
+        )
+    }
+}
+
+/// Selects the outbound peer to evict, if any.
+///
+/// Up to `protected_count` evaluated peers with the highest scores are protected, counting at
+/// most one per prefix bucket. Among the other evaluated peers, the one from the bucket holding
+/// the most outbound peers is evicted, the lowest score breaking ties.
+pub(crate) fn select_eviction(
+    candidates: &[OutboundCandidate],
+    protected_count: usize,
+) -> Option<PeerKey> {
+    let mut bucket_sizes: HashMap<PrefixBucket, usize> = HashMap::new();
+    for candidate in candidates {
+        *bucket_sizes.entry(candidate.bucket).or_default() += 1;
+    }
+
+    let mut protected_buckets = Vec::with_capacity(protected_count);
+    let mut protected_keys = Vec::with_capacity(protected_count);
+    for candidate in candidates
+        .iter()
+        .filter(|candidate| candidate.evaluated)
+        .sorted_by(|a, b| b.score.total_cmp(&a.score))
+    {
+        if protected_keys.len() >= protected_count {
+            break;
+        }
+        if !protected_buckets.contains(&candidate.bucket) {
+            protected_buckets.push(candidate.bucket);
+            protected_keys.push(candidate.key);
+        }
+    }
+
+    candidates
+        .iter()
+        .filter(|candidate| candidate.evaluated && !protected_keys.contains(&candidate.key))
+        .min_by(|a, b| {
+            bucket_sizes[&b.bucket]
+                .cmp(&bucket_sizes[&a.bucket])
+                .then(a.score.total_cmp(&b.score))
+        })
+        .map(|candidate| candidate.key)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use karlsen_utils::networking::PeerId;
+    use std::net::{IpAddr, Ipv4Addr};
+
+    fn candidate(id: u8, ip: [u8; 4], score: f64, evaluated: bool) -> OutboundCandidate {
+        let ip = IpAddress::from(IpAddr::V4(Ipv4Addr::from(ip)));
+        let key = PeerKey::new(PeerId::from_slice(&[id; 16]).unwrap(), ip);
+        OutboundCandidate::new(key, ip.prefix_bucket(), score, evaluated)
+    }
+
+    #[test]
+    fn test_select_eviction() {
+        let a = candidate(1, [1, 1, 0, 1], 100.0, true);
+        let b = candidate(2, [2, 2, 0, 1], 50.0, true);
+        let c = candidate(3, [3, 3, 0, 1], 10.0, true);
+        let d = candidate(4, [4, 4, 0, 1], 0.0, false);
+
+        // The worst evaluated peer goes, never the peers still under evaluation
+        let candidates = vec![a.clone(), b.clone(), c.clone(), d.clone()];
+        assert_eq!(select_eviction(&candidates, 2), Some(c.key));
+        assert_eq!(select_eviction(&candidates, 3), None);
+
+        // Peers sharing the bucket of `a` protect a single slot and are evicted first,
+        // even when scoring better than the peers of singleton buckets
+        let a2 = candidate(5, [1, 1, 7, 7], 90.0, true);
+        let a3 = candidate(6, [1, 1, 8, 8], 80.0, true);
+        let candidates = vec![a.clone(), a2.clone(), a3.clone(), b.clone(), c.clone()];
+        assert_eq!(select_eviction(&candidates, 2), Some(a3.key));
+        assert_eq!(select_eviction(&[a, a2.clone(), b, c], 2), Some(a2.key));
+
+        assert_eq!(select_eviction(&[d], 0), None);
+    }
+}
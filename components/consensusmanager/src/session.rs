@@ -4,15 +4,21 @@
 
 use karlsen_consensus_core::{
     acceptance_data::AcceptanceData,
-    api::{BlockCount, BlockValidationFutures, ConsensusApi, ConsensusStats, DynConsensus},
+    api::{
+        BlockCount, BlockValidationFutures, ConsensusApi, ConsensusReadView, ConsensusStats,
+        DynConsensus, PowPermit,
+    },
     block::Block,
     blockstatus::BlockStatus,
     daa_score_timestamp::DaaScoreTimestamp,
+    dag_slice::DagSlice,
+    difficulty::DifficultyInfo,
     errors::consensus::ConsensusResult,
     header::Header,
     pruning::{PruningPointProof, PruningPointTrustedData, PruningPointsList},
     trusted::{ExternalGhostdagData, TrustedBlock},
-    tx::{MutableTransaction, Transaction, TransactionOutpoint, UtxoEntry},
+    tx::{MutableTransaction, ScriptPublicKeys, Transaction, TransactionOutpoint, UtxoEntry},
+    utxo::{balance_rollback::BalanceRollback, utxo_diff::UtxoDiff},
     BlockHashSet, BlueWorkType, ChainPath, Hash,
 };
 use karlsen_utils::sync::rwlock::*;
@@ -189,6 +195,15 @@ impl ConsensusSessionOwned {
         self.consensus.validate_and_insert_block(block)
     }
 
+    pub fn validate_and_insert_block_with_pow_permit(
+        &self,
+        block: Block,
+        pow_permit: PowPermit,
+    ) -> BlockValidationFutures {
+        self.consensus
+            .validate_and_insert_block_with_pow_permit(block, pow_permit)
+    }
+
     pub fn validate_and_insert_block_batch(&self, mut batch: Vec<Block>) -> BlockProcessingBatch {
         // Sort by blue work in order to ensure topological order
         batch.sort_by(|a, b| a.header.blue_work.partial_cmp(&b.header.blue_work).unwrap());
@@ -305,6 +320,19 @@ impl ConsensusSessionOwned {
             .await
     }
 
+    /// Runs `f` on a [`ConsensusReadView`] of the current virtual state. Reads combining the selected
+    /// chain, the acceptance data and the virtual UTXO set, such as whether a block is a chain block
+    /// along with the transactions it accepted, should go through a single view to be consistent.
+    pub async fn async_with_read_view<F, R>(&self, f: F) -> R
+    where
+        F: FnOnce(&dyn ConsensusReadView) -> R + Send + 'static,
+        R: Send + 'static,
+    {
+        self.clone()
+            .spawn_blocking(move |c| f(c.read_view().as_ref()))
+            .await
+    }
+
     pub async fn async_get_virtual_utxos(
         &self,
         from_outpoint: Option<TransactionOutpoint>,
@@ -316,6 +344,15 @@ impl ConsensusSessionOwned {
             .await
     }
 
+    pub async fn async_get_virtual_utxo_entries(
+        &self,
+        outpoints: Vec<TransactionOutpoint>,
+    ) -> Vec<Option<UtxoEntry>> {
+        self.clone()
+            .spawn_blocking(move |c| c.get_virtual_utxo_entries(&outpoints))
+            .await
+    }
+
     pub async fn async_get_tips(&self) -> Vec<Hash> {
         self.clone().spawn_blocking(|c| c.get_tips()).await
     }
@@ -480,6 +517,12 @@ impl ConsensusSessionOwned {
             .await
     }
 
+    pub async fn async_get_block_utxo_diff(&self, hash: Hash) -> ConsensusResult<Arc<UtxoDiff>> {
+        self.clone()
+            .spawn_blocking(move |c| c.get_block_utxo_diff(hash))
+            .await
+    }
+
     pub async fn async_is_chain_block(&self, hash: Hash) -> ConsensusResult<bool> {
         self.clone()
             .spawn_blocking(move |c| c.is_chain_block(hash))
@@ -545,6 +588,38 @@ impl ConsensusSessionOwned {
             .await
     }
 
+    pub async fn async_get_balance_rollback(
+        &self,
+        daa_score: u64,
+        script_public_keys: ScriptPublicKeys,
+        max_chain_blocks: usize,
+    ) -> ConsensusResult<BalanceRollback> {
+        self.clone()
+            .spawn_blocking(move |c| {
+                c.get_balance_rollback(daa_score, script_public_keys, max_chain_blocks)
+            })
+            .await
+    }
+
+    pub async fn async_get_difficulty_info(
+        &self,
+        chain_block_count: usize,
+    ) -> ConsensusResult<DifficultyInfo> {
+        self.clone()
+            .spawn_blocking(move |c| c.get_difficulty_info(chain_block_count))
+            .await
+    }
+
+    pub async fn async_get_dag_slice(
+        &self,
+        from_daa_score: u64,
+        count: usize,
+    ) -> ConsensusResult<DagSlice> {
+        self.clone()
+            .spawn_blocking(move |c| c.get_dag_slice(from_daa_score, count))
+            .await
+    }
+
     pub async fn async_validate_pruning_points(&self) -> ConsensusResult<()> {
         self.clone()
             .spawn_blocking(move |c| c.validate_pruning_points())
@@ -0,0 +1,190 @@
+use crate::{
+    errors::{SnapshotError, SnapshotResult},
+    manifest::{SnapshotManifest, SnapshotSegment},
+    producer::is_data_file_name,
+    server::MAX_RANGE_LENGTH,
+    IDENT, MANIFEST_FILE,
+};
+use bincode::Options;
+use karlsen_consensus_core::{
+    header::Header,
+    network::NetworkId,
+    pruning::PruningPointProof,
+    tx::{TransactionOutpoint, UtxoEntry},
+};
+use karlsen_core::{debug, warn};
+use reqwest::{header::RANGE, Client, StatusCode};
+use secp256k1::XOnlyPublicKey;
+use serde::de::DeserializeOwned;
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+/// Attempts made to fetch a segment before giving up on the snapshot
+const FETCH_ATTEMPTS: usize = 3;
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// A snapshot server the node bootstraps from during IBD, in place of downloading the pruning
+/// point proof and UTXO set from its peers.
+///
+/// Every fetched segment is checked against its hash in the signed manifest. The proof and the
+/// UTXO set are then validated by the consensus exactly like the ones received from peers.
+pub struct SnapshotSource {
+    url: String,
+    network_id: NetworkId,
+    publisher: Option<XOnlyPublicKey>,
+    client: Client,
+    /// Set once a snapshot of the source failed, the node then syncing from its peers only
+    disabled: AtomicBool,
+}
+
+impl SnapshotSource {
+    /// Creates a source for the snapshots served at `url`, optionally only accepting the ones
+    /// signed by `publisher`
+    pub fn new(
+        url: &str,
+        network_id: NetworkId,
+        publisher: Option<XOnlyPublicKey>,
+    ) -> SnapshotResult<Arc<Self>> {
+        Ok(Arc::new(Self {
+            url: url.trim_end_matches('/').to_string(),
+            network_id,
+            publisher,
+            client: Client::builder().timeout(REQUEST_TIMEOUT).build()?,
+            disabled: AtomicBool::new(false),
+        }))
+    }
+
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    pub fn is_disabled(&self) -> bool {
+        self.disabled.load(Ordering::Relaxed)
+    }
+
+    /// Stops bootstrapping from the source for the lifetime of the node
+    pub fn disable(&self, reason: &SnapshotError) {
+        warn!(
+            "[{IDENT}] no longer bootstrapping from {}: {}",
+            self.url, reason
+        );
+        self.disabled.store(true, Ordering::Relaxed);
+    }
+
+    /// Fetches the manifest of the latest snapshot and checks its signature and network
+    pub async fn fetch_manifest(&self) -> SnapshotResult<SnapshotManifest> {
+        let response = self
+            .client
+            .get(format!("{}/{}", self.url, MANIFEST_FILE))
+            .send()
+            .await?;
+        if response.status() != StatusCode::OK {
+            return Err(SnapshotError::HttpStatus(response.status().as_u16()));
+        }
+        let manifest: SnapshotManifest = serde_json::from_slice(&response.bytes().await?)?;
+        manifest.verify(self.publisher.as_ref())?;
+        if !is_data_file_name(&manifest.data_file) {
+            return Err(SnapshotError::InvalidSnapshot(format!(
+                "invalid data file name {}",
+                manifest.data_file
+            )));
+        }
+        if manifest.network_id != self.network_id.to_string() {
+            return Err(SnapshotError::NetworkMismatch(
+                manifest.network_id,
+                self.network_id.to_string(),
+            ));
+        }
+        debug!(
+            "[{IDENT}] fetched the manifest of the snapshot of {} with {} UTXO entries",
+            manifest.pruning_point, manifest.utxo_count
+        );
+        Ok(manifest)
+    }
+
+    /// Fetches the pruning point proof of the snapshot, recomputing the hashes of its headers
+    pub async fn fetch_proof(
+        &self,
+        manifest: &SnapshotManifest,
+    ) -> SnapshotResult<PruningPointProof> {
+        let levels: Vec<Vec<Header>> = self.fetch_segment(manifest, &manifest.proof).await?;
+        Ok(levels
+            .into_iter()
+            .map(|level| {
+                level
+                    .into_iter()
+                    .map(|mut header| {
+                        header.finalize();
+                        Arc::new(header)
+                    })
+                    .collect()
+            })
+            .collect())
+    }
+
+    /// Fetches the chunk `index` of the UTXO set of the snapshot
+    pub async fn fetch_utxo_chunk(
+        &self,
+        manifest: &SnapshotManifest,
+        index: usize,
+    ) -> SnapshotResult<Vec<(TransactionOutpoint, UtxoEntry)>> {
+        self.fetch_segment(manifest, &manifest.utxo_set[index])
+            .await
+    }
+
+    async fn fetch_segment<T: DeserializeOwned>(
+        &self,
+        manifest: &SnapshotManifest,
+        segment: &SnapshotSegment,
+    ) -> SnapshotResult<T> {
+        if segment.length > MAX_RANGE_LENGTH {
+            return Err(SnapshotError::InvalidSnapshot(format!(
+                "segment {} exceeds the max length",
+                segment.range()
+            )));
+        }
+        let mut attempt = 1;
+        let data = loop {
+            match self.fetch_range(manifest, segment).await {
+                Ok(data) => break data,
+                Err(err) if attempt < FETCH_ATTEMPTS => {
+                    debug!("[{IDENT}] retrying to fetch {}: {}", segment.range(), err);
+                    attempt += 1;
+                }
+                Err(err) => return Err(err),
+            }
+        };
+        if !segment.verify(&data) {
+            return Err(SnapshotError::SegmentHashMismatch(segment.range()));
+        }
+        // Matches the encoding of `bincode::serialize`, the allocations being bounded by the
+        // length of the segment
+        Ok(bincode::DefaultOptions::new()
+            .with_fixint_encoding()
+            .allow_trailing_bytes()
+            .with_limit(segment.length)
+            .deserialize(&data)?)
+    }
+
+    async fn fetch_range(
+        &self,
+        manifest: &SnapshotManifest,
+        segment: &SnapshotSegment,
+    ) -> SnapshotResult<Vec<u8>> {
+        let response = self
+            .client
+            .get(format!("{}/{}", self.url, manifest.data_file))
+            .header(RANGE, segment.range())
+            .send()
+            .await?;
+        if response.status() != StatusCode::PARTIAL_CONTENT {
+            return Err(SnapshotError::HttpStatus(response.status().as_u16()));
+        }
+        Ok(response.bytes().await?.to_vec())
+    }
+}
@@ -0,0 +1,56 @@
+use crate::IDENT;
+use karlsen_consensus_core::errors::consensus::ConsensusError;
+use karlsen_hashes::Hash;
+use thiserror::Error;
+
+/// Errors originating from the snapshot producer, server and client.
+#[derive(Error, Debug)]
+pub enum SnapshotError {
+    #[error("[{IDENT}]: {0}")]
+    IoError(#[from] std::io::Error),
+
+    #[error("[{IDENT}]: {0}")]
+    HttpError(#[from] reqwest::Error),
+
+    #[error("[{IDENT}]: {0}")]
+    ServerError(#[from] hyper::Error),
+
+    #[error("[{IDENT}]: server responded with status {0}")]
+    HttpStatus(u16),
+
+    #[error("[{IDENT}]: {0}")]
+    JsonError(#[from] serde_json::Error),
+
+    #[error("[{IDENT}]: {0}")]
+    EncodingError(#[from] bincode::Error),
+
+    #[error("[{IDENT}]: {0}")]
+    ConsensusError(#[from] ConsensusError),
+
+    #[error("[{IDENT}]: {0}")]
+    KeyError(#[from] secp256k1::Error),
+
+    #[error("[{IDENT}]: manifest version {0} is not supported")]
+    UnsupportedVersion(u16),
+
+    #[error("[{IDENT}]: the manifest signature is invalid")]
+    InvalidSignature,
+
+    #[error("[{IDENT}]: the manifest is signed by {0} instead of the expected publisher")]
+    UnexpectedPublisher(String),
+
+    #[error("[{IDENT}]: the snapshot is for network {0} instead of {1}")]
+    NetworkMismatch(String, String),
+
+    #[error("[{IDENT}]: segment {0} does not match its hash in the manifest")]
+    SegmentHashMismatch(String),
+
+    #[error("[{IDENT}]: the snapshot is for pruning point {0} instead of {1}")]
+    PruningPointMismatch(Hash, Hash),
+
+    #[error("[{IDENT}]: {0}")]
+    InvalidSnapshot(String),
+}
+
+/// Results originating from the snapshot producer, server and client.
+pub type SnapshotResult<T> = Result<T, SnapshotError>;
@@ -0,0 +1,30 @@
+//!
+//! Serving and fetching of the pruning point snapshots used to bootstrap nodes over HTTP.
+//!
+//! A node serving snapshots periodically writes its pruning point proof and the UTXO set of its
+//! pruning point to a data file, split into segments listed along with their hashes in a
+//! manifest signed by the node. Bootstrapping nodes download the manifest, then fetch every
+//! segment with an HTTP range request and check it against its hash before handing it to the
+//! consensus, which fully validates the proof and the UTXO set commitment as it does for the
+//! data received from peers.
+//!
+
+pub mod client;
+pub mod errors;
+pub mod manifest;
+pub mod producer;
+pub mod server;
+pub mod service;
+
+pub use client::SnapshotSource;
+pub use errors::SnapshotError;
+pub use manifest::{parse_public_key, SnapshotManifest, SnapshotSegment};
+pub use producer::SnapshotProducer;
+pub use service::SnapshotService;
+
+/// Name of the snapshot signed manifest, in JSON
+pub const MANIFEST_FILE: &str = "manifest.json";
+/// Name of the file holding the secret key signing the manifests
+pub const KEY_FILE: &str = "snapshot.key";
+
+const IDENT: &str = "Snapshot";
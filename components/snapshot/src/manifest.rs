@@ -0,0 +1,186 @@
+use crate::errors::{SnapshotError, SnapshotResult};
+use karlsen_hashes::{Hash, SnapshotManifestHash, SnapshotSegmentHash};
+use secp256k1::{schnorr::Signature, Keypair, Message, XOnlyPublicKey};
+use serde::{Deserialize, Serialize};
+
+/// Version of the manifest format and of the encoding of the segments
+pub const MANIFEST_VERSION: u16 = 1;
+
+/// A byte range of the snapshot data file
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SnapshotSegment {
+    pub offset: u64,
+    pub length: u64,
+    pub hash: Hash,
+}
+
+impl SnapshotSegment {
+    pub fn new(offset: u64, data: &[u8]) -> Self {
+        Self {
+            offset,
+            length: data.len() as u64,
+            hash: Self::hash_data(data),
+        }
+    }
+
+    pub fn verify(&self, data: &[u8]) -> bool {
+        data.len() as u64 == self.length && Self::hash_data(data) == self.hash
+    }
+
+    /// Value of the HTTP `Range` header requesting the segment
+    pub fn range(&self) -> String {
+        format!(
+            "bytes={}-{}",
+            self.offset,
+            (self.offset + self.length).saturating_sub(1)
+        )
+    }
+
+    fn hash_data(data: &[u8]) -> Hash {
+        let mut hasher = SnapshotSegmentHash::new();
+        hasher.write(data);
+        hasher.finalize()
+    }
+}
+
+/// Describes a snapshot of the pruning point of a node: the segment of the data file holding
+/// the pruning point proof and the segments holding the UTXO set, in chunks of entries.
+///
+/// The manifest is signed by the serving node with a Schnorr key of its own, so the clients can
+/// pin the publisher they trust. The signature does not replace the consensus validation of the
+/// snapshot, it only guards the clients from downloading data substituted on the way.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SnapshotManifest {
+    pub version: u16,
+    pub network_id: String,
+    pub pruning_point: Hash,
+    /// UTXO commitment of the header of the pruning point
+    pub utxo_commitment: Hash,
+    /// Creation time, in milliseconds since the unix epoch
+    pub created_at: u64,
+    /// Name of the data file, served next to the manifest
+    pub data_file: String,
+    pub proof: SnapshotSegment,
+    pub utxo_set: Vec<SnapshotSegment>,
+    pub utxo_count: u64,
+    /// Hex encoded x-only public key of the publisher
+    pub public_key: String,
+    /// Hex encoded Schnorr signature of the manifest by the publisher
+    pub signature: String,
+}
+
+impl SnapshotManifest {
+    /// Hash of all the fields of the manifest but its signature
+    pub fn signing_hash(&self) -> Hash {
+        let mut hasher = SnapshotManifestHash::new();
+        hasher.write(self.version.to_le_bytes());
+        hasher.write((self.network_id.len() as u64).to_le_bytes());
+        hasher.write(self.network_id.as_bytes());
+        hasher.write(self.pruning_point);
+        hasher.write(self.utxo_commitment);
+        hasher.write(self.created_at.to_le_bytes());
+        hasher.write((self.data_file.len() as u64).to_le_bytes());
+        hasher.write(self.data_file.as_bytes());
+        for segment in std::iter::once(&self.proof).chain(self.utxo_set.iter()) {
+            hasher.write(segment.offset.to_le_bytes());
+            hasher.write(segment.length.to_le_bytes());
+            hasher.write(segment.hash);
+        }
+        hasher.write(self.utxo_count.to_le_bytes());
+        hasher.write(self.public_key.as_bytes());
+        hasher.finalize()
+    }
+
+    /// Sets the public key of `keypair` as the publisher and signs the manifest
+    pub fn sign(&mut self, keypair: &Keypair) {
+        self.public_key = faster_hex::hex_string(&keypair.x_only_public_key().0.serialize());
+        let message =
+            Message::from_digest_slice(self.signing_hash().as_bytes().as_slice()).unwrap();
+        self.signature = faster_hex::hex_string(keypair.sign_schnorr(message).as_ref());
+    }
+
+    /// Checks the signature of the manifest and, if some is expected, its publisher
+    pub fn verify(&self, expected_publisher: Option<&XOnlyPublicKey>) -> SnapshotResult<()> {
+        if self.version != MANIFEST_VERSION {
+            return Err(SnapshotError::UnsupportedVersion(self.version));
+        }
+        let public_key = parse_public_key(&self.public_key)?;
+        if expected_publisher.is_some_and(|expected| *expected != public_key) {
+            return Err(SnapshotError::UnexpectedPublisher(self.public_key.clone()));
+        }
+        let signature = Signature::from_slice(&decode_hex(&self.signature)?)?;
+        let message =
+            Message::from_digest_slice(self.signing_hash().as_bytes().as_slice()).unwrap();
+        signature
+            .verify(&message, &public_key)
+            .map_err(|_| SnapshotError::InvalidSignature)
+    }
+}
+
+/// Parses a hex encoded x-only public key, as found in the manifests
+pub fn parse_public_key(hex: &str) -> SnapshotResult<XOnlyPublicKey> {
+    Ok(XOnlyPublicKey::from_slice(&decode_hex(hex)?)?)
+}
+
+fn decode_hex(hex: &str) -> SnapshotResult<Vec<u8>> {
+    let mut bytes = vec![0u8; hex.len() / 2];
+    faster_hex::hex_decode(hex.as_bytes(), &mut bytes)
+        .map_err(|err| SnapshotError::InvalidSnapshot(err.to_string()))?;
+    Ok(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_manifest_signature() {
+        let proof = b"proof".to_vec();
+        let chunk = b"utxos".to_vec();
+        let mut manifest = SnapshotManifest {
+            version: MANIFEST_VERSION,
+            network_id: "karlsen-mainnet".to_string(),
+            pruning_point: Hash::from_u64_word(1),
+            utxo_commitment: Hash::from_u64_word(2),
+            created_at: 3,
+            data_file: "snapshot.bin".to_string(),
+            proof: SnapshotSegment::new(0, &proof),
+            utxo_set: vec![SnapshotSegment::new(proof.len() as u64, &chunk)],
+            utxo_count: 1,
+            public_key: String::new(),
+            signature: String::new(),
+        };
+        assert_eq!(manifest.utxo_set[0].range(), "bytes=5-9");
+        assert!(manifest.utxo_set[0].verify(&chunk));
+        assert!(!manifest.utxo_set[0].verify(b"utxoz"));
+
+        let keypair = Keypair::new(secp256k1::SECP256K1, &mut rand::thread_rng());
+        manifest.sign(&keypair);
+        let publisher = keypair.x_only_public_key().0;
+        manifest.verify(None).unwrap();
+        manifest.verify(Some(&publisher)).unwrap();
+
+        // Pinning another publisher fails
+        let other = Keypair::new(secp256k1::SECP256K1, &mut rand::thread_rng());
+        assert!(matches!(
+            manifest.verify(Some(&other.x_only_public_key().0)),
+            Err(SnapshotError::UnexpectedPublisher(_))
+        ));
+
+        // Any change to the signed fields invalidates the signature
+        let mut tampered = manifest.clone();
+        tampered.utxo_set[0].hash = Hash::from_u64_word(4);
+        assert!(matches!(
+            tampered.verify(None),
+            Err(SnapshotError::InvalidSignature)
+        ));
+
+        // The manifest survives its JSON encoding
+        let json = serde_json::to_string(&manifest).unwrap();
+        let decoded: SnapshotManifest = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded, manifest);
+        decoded.verify(Some(&publisher)).unwrap();
+    }
+}
@@ -0,0 +1,190 @@
+use crate::{
+    errors::SnapshotResult,
+    manifest::{SnapshotManifest, SnapshotSegment, MANIFEST_VERSION},
+    IDENT, KEY_FILE, MANIFEST_FILE,
+};
+use karlsen_consensus_core::{config::params::Params, network::NetworkId};
+use karlsen_consensusmanager::ConsensusManager;
+use karlsen_core::{info, time::unix_now, trace};
+use karlsen_hashes::Hash;
+use secp256k1::{Keypair, XOnlyPublicKey};
+use std::{
+    fs::{self, File},
+    io::{BufWriter, Write},
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+/// Count of UTXO entries per segment of the data file
+pub const UTXO_CHUNK_SIZE: usize = 10_000;
+
+/// Writes the snapshots of the pruning point of the node to a directory.
+///
+/// Every snapshot gets its own data file, named after its pruning point, and the manifest is
+/// replaced last so the server never describes a data file still being written. The data file of
+/// the previous snapshot is kept until the next one is written, letting the downloads in progress
+/// complete.
+pub struct SnapshotProducer {
+    consensus_manager: Arc<ConsensusManager>,
+    network_id: NetworkId,
+    genesis_hash: Hash,
+    dir: PathBuf,
+    keypair: Keypair,
+}
+
+impl SnapshotProducer {
+    /// Creates a producer writing to `dir`, signing the manifests with the key found in the
+    /// directory or with a new key if there is none
+    pub fn new(
+        consensus_manager: Arc<ConsensusManager>,
+        params: &Params,
+        dir: PathBuf,
+    ) -> SnapshotResult<Self> {
+        fs::create_dir_all(&dir)?;
+        let key_path = dir.join(KEY_FILE);
+        let keypair = match fs::read(&key_path) {
+            Ok(secret) => Keypair::from_seckey_slice(secp256k1::SECP256K1, &secret)?,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+                let keypair = Keypair::new(secp256k1::SECP256K1, &mut rand::thread_rng());
+                fs::write(&key_path, keypair.secret_bytes())?;
+                keypair
+            }
+            Err(err) => return Err(err.into()),
+        };
+        Ok(Self {
+            consensus_manager,
+            network_id: params.net,
+            genesis_hash: params.genesis.hash,
+            dir,
+            keypair,
+        })
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// The key signing the manifests, which clients may pin
+    pub fn public_key(&self) -> XOnlyPublicKey {
+        self.keypair.x_only_public_key().0
+    }
+
+    /// Returns the manifest of the latest snapshot, if any
+    pub fn manifest(&self) -> SnapshotResult<Option<SnapshotManifest>> {
+        match fs::read(self.dir.join(MANIFEST_FILE)) {
+            Ok(json) => Ok(Some(serde_json::from_slice(&json)?)),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    /// Writes a snapshot of the current pruning point, unless the latest snapshot is already of
+    /// it. Returns whether a snapshot was written.
+    ///
+    /// Blocking, the UTXO set of the pruning point being read in full.
+    pub fn build(&self) -> SnapshotResult<bool> {
+        let previous = self.manifest()?;
+        let consensus = self.consensus_manager.consensus();
+        let session = futures::executor::block_on(consensus.session_blocking());
+        let pruning_point = session.pruning_point();
+        if pruning_point == self.genesis_hash
+            || previous
+                .as_ref()
+                .is_some_and(|manifest| manifest.pruning_point == pruning_point)
+        {
+            return Ok(false);
+        }
+        info!("[{IDENT}] writing the snapshot of the pruning point {pruning_point}");
+        let header = session.get_header(pruning_point)?;
+        let proof = session.get_pruning_point_proof();
+        drop(session);
+
+        let data_file = data_file_name(pruning_point);
+        let data_path = self.dir.join(&data_file);
+        let mut writer = BufWriter::new(File::create(&data_path)?);
+        let mut offset = 0u64;
+        let mut write_segment = |data: Vec<u8>| -> SnapshotResult<SnapshotSegment> {
+            writer.write_all(&data)?;
+            let segment = SnapshotSegment::new(offset, &data);
+            offset += segment.length;
+            Ok(segment)
+        };
+
+        let proof_segment = write_segment(bincode::serialize(proof.as_ref())?)?;
+        let mut utxo_segments = Vec::new();
+        let mut utxo_count = 0u64;
+        let mut from_outpoint = None;
+        loop {
+            // A session per chunk, so pruning is not held back by the whole build
+            let session = futures::executor::block_on(consensus.session_blocking());
+            let chunk = session.get_pruning_point_utxos(
+                pruning_point,
+                from_outpoint,
+                UTXO_CHUNK_SIZE,
+                from_outpoint.is_some(),
+            )?;
+            drop(session);
+            if chunk.is_empty() {
+                break;
+            }
+            utxo_count += chunk.len() as u64;
+            from_outpoint = Some(chunk.last().unwrap().0);
+            utxo_segments.push(write_segment(bincode::serialize(&chunk)?)?);
+            trace!("[{IDENT}] wrote {utxo_count} UTXO entries");
+            if chunk.len() < UTXO_CHUNK_SIZE {
+                break;
+            }
+        }
+        writer
+            .into_inner()
+            .map_err(|err| err.into_error())?
+            .sync_all()?;
+
+        let mut manifest = SnapshotManifest {
+            version: MANIFEST_VERSION,
+            network_id: self.network_id.to_string(),
+            pruning_point,
+            utxo_commitment: header.utxo_commitment,
+            created_at: unix_now(),
+            data_file,
+            proof: proof_segment,
+            utxo_set: utxo_segments,
+            utxo_count,
+            public_key: String::new(),
+            signature: String::new(),
+        };
+        manifest.sign(&self.keypair);
+        let manifest_path = self.dir.join(MANIFEST_FILE);
+        let temp_path = manifest_path.with_extension("tmp");
+        fs::write(&temp_path, serde_json::to_vec_pretty(&manifest)?)?;
+        fs::rename(&temp_path, &manifest_path)?;
+        info!(
+            "[{IDENT}] wrote the snapshot of the pruning point {} with {} UTXO entries",
+            pruning_point, utxo_count
+        );
+
+        // Only the data files of the new and the previous snapshots are kept
+        let keep = [
+            Some(manifest.data_file),
+            previous.map(|manifest| manifest.data_file),
+        ];
+        for entry in fs::read_dir(&self.dir)? {
+            let name = entry?.file_name().to_string_lossy().to_string();
+            if is_data_file_name(&name) && !keep.iter().flatten().any(|kept| *kept == name) {
+                fs::remove_file(self.dir.join(name))?;
+            }
+        }
+        Ok(true)
+    }
+}
+
+fn data_file_name(pruning_point: Hash) -> String {
+    format!("snapshot-{pruning_point}.bin")
+}
+
+/// Data file names have no path separator, so the server serves no other file
+pub(crate) fn is_data_file_name(name: &str) -> bool {
+    name.strip_prefix("snapshot-")
+        .and_then(|name| name.strip_suffix(".bin"))
+        .is_some_and(|hash| hash.len() == 64 && hash.chars().all(|c| c.is_ascii_hexdigit()))
+}
@@ -0,0 +1,149 @@
+use crate::{errors::SnapshotResult, producer::is_data_file_name, IDENT, MANIFEST_FILE};
+use hyper::{
+    header::{ACCEPT_RANGES, CONTENT_RANGE, CONTENT_TYPE, RANGE},
+    service::{make_service_fn, service_fn},
+    Body, Method, Request, Response, Server, StatusCode,
+};
+use karlsen_core::{debug, info};
+use std::{convert::Infallible, future::Future, net::SocketAddr, path::PathBuf, sync::Arc};
+use tokio::{
+    fs::File,
+    io::{AsyncReadExt, AsyncSeekExt},
+};
+
+/// Max length of the range served by a single request
+pub const MAX_RANGE_LENGTH: u64 = 64 * 1024 * 1024;
+
+/// Serves the manifest and the data files of the snapshots found in `dir` over HTTP until
+/// `shutdown` completes.
+///
+/// The data files are only served by byte ranges, so a client can fetch and verify them segment
+/// by segment, and resume an interrupted download.
+pub async fn serve(
+    address: SocketAddr,
+    dir: PathBuf,
+    shutdown: impl Future<Output = ()>,
+) -> SnapshotResult<()> {
+    let dir = Arc::new(dir);
+    let make_service = make_service_fn(move |_| {
+        let dir = dir.clone();
+        async move { Ok::<_, Infallible>(service_fn(move |request| handle(dir.clone(), request))) }
+    });
+    let server = Server::try_bind(&address)?.serve(make_service);
+    info!(
+        "[{IDENT}] serving snapshots on http://{}",
+        server.local_addr()
+    );
+    server.with_graceful_shutdown(shutdown).await?;
+    Ok(())
+}
+
+async fn handle(dir: Arc<PathBuf>, request: Request<Body>) -> Result<Response<Body>, Infallible> {
+    if request.method() != Method::GET {
+        return Ok(status(
+            StatusCode::METHOD_NOT_ALLOWED,
+            "only GET is supported",
+        ));
+    }
+    let name = request.uri().path().trim_start_matches('/');
+    debug!("[{IDENT}] GET /{name}");
+    if name == MANIFEST_FILE {
+        return Ok(match tokio::fs::read(dir.join(MANIFEST_FILE)).await {
+            Ok(json) => Response::builder()
+                .header(CONTENT_TYPE, "application/json")
+                .body(Body::from(json))
+                .unwrap(),
+            Err(_) => status(StatusCode::NOT_FOUND, "no snapshot is available yet"),
+        });
+    }
+    if !is_data_file_name(name) {
+        return Ok(status(StatusCode::NOT_FOUND, "not found"));
+    }
+    let Some(range) = request
+        .headers()
+        .get(RANGE)
+        .and_then(|value| value.to_str().ok())
+    else {
+        return Ok(status(
+            StatusCode::BAD_REQUEST,
+            "data files are only served by byte ranges",
+        ));
+    };
+    let Ok(mut file) = File::open(dir.join(name)).await else {
+        return Ok(status(
+            StatusCode::NOT_FOUND,
+            "the snapshot is no longer available",
+        ));
+    };
+    let Ok(file_length) = file.metadata().await.map(|metadata| metadata.len()) else {
+        return Ok(status(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "unreadable data file",
+        ));
+    };
+    let Some((start, end)) = parse_range(range, file_length) else {
+        return Ok(Response::builder()
+            .status(StatusCode::RANGE_NOT_SATISFIABLE)
+            .header(CONTENT_RANGE, format!("bytes */{file_length}"))
+            .body(Body::empty())
+            .unwrap());
+    };
+    let mut data = vec![0u8; (end + 1 - start) as usize];
+    if file.seek(std::io::SeekFrom::Start(start)).await.is_err()
+        || file.read_exact(&mut data).await.is_err()
+    {
+        return Ok(status(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "unreadable data file",
+        ));
+    }
+    Ok(Response::builder()
+        .status(StatusCode::PARTIAL_CONTENT)
+        .header(ACCEPT_RANGES, "bytes")
+        .header(CONTENT_RANGE, format!("bytes {start}-{end}/{file_length}"))
+        .header(CONTENT_TYPE, "application/octet-stream")
+        .body(Body::from(data))
+        .unwrap())
+}
+
+fn status(status: StatusCode, message: &'static str) -> Response<Body> {
+    Response::builder()
+        .status(status)
+        .body(Body::from(message))
+        .unwrap()
+}
+
+/// Parses a single `bytes=start-end` or `bytes=start-` range within a file of `file_length`
+/// bytes, returning its inclusive bounds
+fn parse_range(range: &str, file_length: u64) -> Option<(u64, u64)> {
+    let (start, end) = range.strip_prefix("bytes=")?.split_once('-')?;
+    let start = start.trim().parse::<u64>().ok()?;
+    let end = match end.trim() {
+        "" => file_length.checked_sub(1)?,
+        end => end.parse::<u64>().ok()?.min(file_length.checked_sub(1)?),
+    };
+    (start <= end && end - start < MAX_RANGE_LENGTH).then_some((start, end))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_range() {
+        assert_eq!(parse_range("bytes=0-9", 100), Some((0, 9)));
+        assert_eq!(parse_range("bytes=90-", 100), Some((90, 99)));
+        // The end is clamped to the file
+        assert_eq!(parse_range("bytes=90-200", 100), Some((90, 99)));
+        assert_eq!(parse_range("bytes=100-", 100), None);
+        assert_eq!(parse_range("bytes=9-0", 100), None);
+        assert_eq!(parse_range("bytes=-10", 100), None);
+        assert_eq!(parse_range("bytes=0-1,4-5", 100), None);
+        assert_eq!(parse_range("items=0-9", 100), None);
+        assert_eq!(parse_range("bytes=0-", 0), None);
+        assert_eq!(
+            parse_range(&format!("bytes=0-{MAX_RANGE_LENGTH}"), u64::MAX),
+            None
+        );
+    }
+}
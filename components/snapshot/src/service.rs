@@ -0,0 +1,81 @@
+use crate::{producer::SnapshotProducer, server::serve, IDENT};
+use karlsen_consensusmanager::spawn_blocking;
+use karlsen_core::{
+    task::service::{AsyncService, AsyncServiceError, AsyncServiceFuture},
+    trace, warn,
+};
+use karlsen_utils::triggers::SingleTrigger;
+use std::{net::SocketAddr, sync::Arc, time::Duration};
+
+const SNAPSHOT_SERVICE: &str = IDENT;
+
+/// Interval between the checks for a new pruning point to snapshot
+const BUILD_INTERVAL: Duration = Duration::from_secs(600);
+
+/// Keeps a snapshot of the current pruning point written by the [`SnapshotProducer`] and serves
+/// it over HTTP
+pub struct SnapshotService {
+    producer: Arc<SnapshotProducer>,
+    listen: SocketAddr,
+    shutdown: SingleTrigger,
+}
+
+impl SnapshotService {
+    pub fn new(producer: Arc<SnapshotProducer>, listen: SocketAddr) -> Self {
+        Self {
+            producer,
+            listen,
+            shutdown: SingleTrigger::default(),
+        }
+    }
+
+    async fn build(&self) {
+        let producer = self.producer.clone();
+        match spawn_blocking(move || producer.build()).await {
+            Ok(Err(err)) => warn!("Error while writing the pruning point snapshot: {}", err),
+            Err(err) => warn!("The pruning point snapshot writing panicked: {}", err),
+            Ok(Ok(_)) => {}
+        }
+    }
+}
+
+impl AsyncService for SnapshotService {
+    fn ident(self: Arc<Self>) -> &'static str {
+        SNAPSHOT_SERVICE
+    }
+
+    fn start(self: Arc<Self>) -> AsyncServiceFuture {
+        trace!("{} starting", SNAPSHOT_SERVICE);
+        let shutdown_signal = self.shutdown.listener.clone();
+        Box::pin(async move {
+            let server = serve(
+                self.listen,
+                self.producer.dir().to_path_buf(),
+                shutdown_signal.clone(),
+            );
+            let builder = async {
+                loop {
+                    self.build().await;
+                    tokio::time::sleep(BUILD_INTERVAL).await;
+                }
+            };
+            tokio::select! {
+                _ = shutdown_signal => Ok(()),
+                result = server => result.map_err(|err| AsyncServiceError::Service(err.to_string())),
+                _ = builder => Ok(()),
+            }
+        })
+    }
+
+    fn signal_exit(self: Arc<Self>) {
+        trace!("sending an exit signal to {}", SNAPSHOT_SERVICE);
+        self.shutdown.trigger.trigger();
+    }
+
+    fn stop(self: Arc<Self>) -> AsyncServiceFuture {
+        Box::pin(async move {
+            trace!("{} stopped", SNAPSHOT_SERVICE);
+            Ok(())
+        })
+    }
+}
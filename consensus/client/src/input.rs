@@ -27,7 +27,10 @@ export interface ITransactionInput {
  * 
  * @category Node RPC
  */
-export interface ITransactionInputVerboseData { }
+export interface ITransactionInputVerboseData {
+    /** Output spent by the input, set when requested and found by the node */
+    previousOutput?: ITransactionOutput;
+}
 
 "#;
 
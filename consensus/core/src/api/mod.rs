@@ -10,6 +10,8 @@ use crate::{
     blockstatus::BlockStatus,
     coinbase::MinerData,
     daa_score_timestamp::DaaScoreTimestamp,
+    dag_slice::DagSlice,
+    difficulty::DifficultyInfo,
     errors::{
         block::{BlockProcessResult, RuleError},
         coinbase::CoinbaseResult,
@@ -20,7 +22,8 @@ use crate::{
     header::Header,
     pruning::{PruningPointProof, PruningPointTrustedData, PruningPointsList},
     trusted::{ExternalGhostdagData, TrustedBlock},
-    tx::{MutableTransaction, Transaction, TransactionOutpoint, UtxoEntry},
+    tx::{MutableTransaction, ScriptPublicKeys, Transaction, TransactionOutpoint, UtxoEntry},
+    utxo::{balance_rollback::BalanceRollback, utxo_diff::UtxoDiff},
     BlockHashSet, BlueWorkType, ChainPath,
 };
 use karlsen_hashes::Hash;
@@ -32,6 +35,10 @@ pub mod stats;
 
 pub type BlockValidationFuture = BoxFuture<'static, BlockProcessResult<BlockStatus>>;
 
+/// A reservation of room for a block awaiting proof of work verification, dropped by consensus
+/// once the proof of work of the block was checked or the block was rejected before that
+pub type PowPermit = Box<dyn Send + Sync>;
+
 /// A struct returned by consensus for block validation processing calls
 pub struct BlockValidationFutures {
     /// A future triggered when block processing is completed (header and body processing)
@@ -43,6 +50,30 @@ pub struct BlockValidationFutures {
     pub virtual_state_task: BlockValidationFuture,
 }
 
+/// Reads of the consensus state as of a single committed virtual state, backed by a DB snapshot.
+/// The sink, the selected chain leading to it and the virtual UTXO set are mutually consistent,
+/// while new virtual states keep being committed as long as the view is alive.
+pub trait ConsensusReadView {
+    /// Returns the sink of the virtual state of the view
+    fn get_sink(&self) -> Hash;
+
+    /// Returns whether `hash` is on the selected chain of the view
+    fn is_chain_block(&self, hash: Hash) -> ConsensusResult<bool>;
+
+    /// Returns the chain changes between `hash` and the sink of the view
+    fn get_virtual_chain_from_block(&self, hash: Hash) -> ConsensusResult<ChainPath>;
+
+    fn get_blocks_acceptance_data(
+        &self,
+        hashes: &[Hash],
+    ) -> ConsensusResult<Vec<Arc<AcceptanceData>>>;
+
+    /// Returns the entries of the virtual UTXO set of the view spent by `outpoints`, or `None`
+    /// for the outpoints not in the set
+    fn get_virtual_utxo_entries(&self, outpoints: &[TransactionOutpoint])
+        -> Vec<Option<UtxoEntry>>;
+}
+
 /// Abstracts the consensus external API
 #[allow(unused_variables)]
 pub trait ConsensusApi: Send + Sync {
@@ -59,6 +90,16 @@ pub trait ConsensusApi: Send + Sync {
         unimplemented!()
     }
 
+    /// Same as [`Self::validate_and_insert_block`], additionally holding `pow_permit` until the
+    /// proof of work of the block was checked
+    fn validate_and_insert_block_with_pow_permit(
+        &self,
+        block: Block,
+        pow_permit: PowPermit,
+    ) -> BlockValidationFutures {
+        unimplemented!()
+    }
+
     fn validate_and_insert_trusted_block(&self, tb: TrustedBlock) -> BlockValidationFutures {
         unimplemented!()
     }
@@ -159,6 +200,12 @@ pub trait ConsensusApi: Send + Sync {
         unimplemented!()
     }
 
+    /// Returns a view of the current virtual state. The view should be short lived, since it pins
+    /// the DB data it reads.
+    fn read_view(&self) -> Box<dyn ConsensusReadView + '_> {
+        unimplemented!()
+    }
+
     fn get_chain_block_samples(&self) -> Vec<DaaScoreTimestamp> {
         unimplemented!()
     }
@@ -180,6 +227,15 @@ pub trait ConsensusApi: Send + Sync {
         unimplemented!()
     }
 
+    /// Returns the entries of the virtual UTXO set spent by `outpoints`, or `None` for the
+    /// outpoints not in the set
+    fn get_virtual_utxo_entries(
+        &self,
+        outpoints: &[TransactionOutpoint],
+    ) -> Vec<Option<UtxoEntry>> {
+        unimplemented!()
+    }
+
     fn get_tips(&self) -> Vec<Hash> {
         unimplemented!()
     }
@@ -334,6 +390,12 @@ pub trait ConsensusApi: Send + Sync {
         unimplemented!()
     }
 
+    /// Returns the UTXO diff of a chain block relative to its selected parent, ie. the outputs
+    /// created and spent by the transactions the block accepted
+    fn get_block_utxo_diff(&self, hash: Hash) -> ConsensusResult<Arc<UtxoDiff>> {
+        unimplemented!()
+    }
+
     fn is_chain_block(&self, hash: Hash) -> ConsensusResult<bool> {
         unimplemented!()
     }
@@ -378,6 +440,33 @@ pub trait ConsensusApi: Send + Sync {
         unimplemented!()
     }
 
+    /// Returns the changes of the balances of `script_public_keys` between the UTXO set of the
+    /// highest selected chain block with a DAA score not above `daa_score` and the virtual UTXO set,
+    /// rolling back at most `max_chain_blocks` chain blocks
+    fn get_balance_rollback(
+        &self,
+        daa_score: u64,
+        script_public_keys: ScriptPublicKeys,
+        max_chain_blocks: usize,
+    ) -> ConsensusResult<BalanceRollback> {
+        unimplemented!()
+    }
+
+    /// Returns the difficulty adjustment state of the virtual block along with the difficulty
+    /// of the `chain_block_count` most recent selected chain blocks
+    fn get_difficulty_info(&self, chain_block_count: usize) -> ConsensusResult<DifficultyInfo> {
+        unimplemented!()
+    }
+
+    /// Returns the blocks merged by the selected chain blocks from the first one reaching
+    /// `from_daa_score` on, walking the chain forward until `count` blocks are collected. The
+    /// mergeset of a chain block is never split. If the walk reaches the sink, the slice ends with
+    /// the blocks merged by the virtual block only, which the following slices return again once
+    /// a chain block merges them.
+    fn get_dag_slice(&self, from_daa_score: u64, count: usize) -> ConsensusResult<DagSlice> {
+        unimplemented!()
+    }
+
     fn validate_pruning_points(&self) -> ConsensusResult<()> {
         unimplemented!()
     }
@@ -1,6 +1,14 @@
-use crate::tx::{ScriptPublicKey, Transaction};
+use crate::tx::{ScriptPublicKey, ScriptVec, Transaction};
 use serde::{Deserialize, Serialize};
 
+/// Length of the coinbase payload fields preceding the script public key of the miner: the blue
+/// score, the subsidy, and the version and length of the script public key
+pub const COINBASE_PAYLOAD_HEADER_LEN: usize = 8 + 8 + 2 + 1;
+
+/// Max length of the miner tag embedded by a node in the coinbase payloads of its templates, in
+/// bytes. Along with the node version, it fits in the payload whatever the paying script.
+pub const MAX_MINER_TAG_LEN: usize = 24;
+
 #[derive(PartialEq, Eq, Debug, Clone)]
 pub struct MinerData<T: AsRef<[u8]> = Vec<u8>> {
     pub script_public_key: ScriptPublicKey,
@@ -16,6 +24,22 @@ impl<T: AsRef<[u8]>> MinerData<T> {
     }
 }
 
+impl<'a> MinerData<&'a [u8]> {
+    /// Reads the miner data of a coinbase payload, without checking the payload against the
+    /// consensus limits. Returns `None` if the payload is too short to hold its script public key.
+    pub fn from_coinbase_payload(payload: &'a [u8]) -> Option<Self> {
+        let header = payload.get(..COINBASE_PAYLOAD_HEADER_LEN)?;
+        let version = u16::from_le_bytes([header[16], header[17]]);
+        let script_len = header[18] as usize;
+        let script =
+            payload.get(COINBASE_PAYLOAD_HEADER_LEN..COINBASE_PAYLOAD_HEADER_LEN + script_len)?;
+        Some(Self {
+            script_public_key: ScriptPublicKey::new(version, ScriptVec::from_slice(script)),
+            extra_data: &payload[COINBASE_PAYLOAD_HEADER_LEN + script_len..],
+        })
+    }
+}
+
 #[derive(PartialEq, Eq, Debug)]
 pub struct CoinbaseData<T: AsRef<[u8]> = Vec<u8>> {
     pub blue_score: u64,
@@ -0,0 +1,55 @@
+//! Checkpoints embedded in the node for the public networks.
+//!
+//! A checkpoint pins a selected chain block buried deep enough to never be reorganized. Blocks
+//! more than a finality depth above a known checkpoint must have it in their past, and the latest
+//! checkpoint is the default assume-valid block, under which the scripts are not verified during
+//! sync (see [`Config::assume_valid`](super::Config::assume_valid)).
+//!
+//! The lists below are deliberately left empty: checkpoint hashes can only be taken from a synced
+//! node of the network and are not fabricated in code. Until entries are added, no checkpoint is
+//! enforced and the default assume-valid block is unset, so every script is verified.
+
+use crate::BlueWorkType;
+use karlsen_hashes::Hash;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Checkpoint {
+    pub hash: Hash,
+    pub daa_score: u64,
+    /// Blue work of the block, bounding from below the work of the chains honoring the checkpoint
+    pub blue_work: BlueWorkType,
+}
+
+impl Checkpoint {
+    pub const fn new(hash: Hash, daa_score: u64, blue_work: BlueWorkType) -> Self {
+        Self {
+            hash,
+            daa_score,
+            blue_work,
+        }
+    }
+}
+
+/// Checkpoints of mainnet, ordered by DAA score.
+///
+/// Entries are appended at release time from a synced node, picking selected chain blocks at
+/// least a pruning depth below the sink. Empty on purpose until such blocks are picked.
+pub const MAINNET_CHECKPOINTS: &[Checkpoint] = &[];
+
+/// Checkpoints of testnet-1, ordered by DAA score. Empty on purpose, as for mainnet.
+pub const TESTNET_CHECKPOINTS: &[Checkpoint] = &[];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_checkpoints_ordering() {
+        for checkpoints in [MAINNET_CHECKPOINTS, TESTNET_CHECKPOINTS] {
+            for pair in checkpoints.windows(2) {
+                assert!(pair[0].daa_score < pair[1].daa_score);
+                assert!(pair[0].blue_work < pair[1].blue_work);
+            }
+        }
+    }
+}
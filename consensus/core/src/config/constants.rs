@@ -129,6 +129,7 @@ pub mod perf {
     const BASELINE_BLOCK_DATA_CACHE_SIZE: usize = 200;
     const BASELINE_BLOCK_WINDOW_CACHE_SIZE: usize = 2000;
     const BASELINE_UTXOSET_CACHE_SIZE: usize = 10_000;
+    const BASELINE_POW_VERIFICATION_QUEUE_SIZE: usize = 4096;
 
     #[derive(Clone, Debug)]
     pub struct PerfParams {
@@ -159,6 +160,17 @@ pub mod perf {
         /// Defaults to 0 which indicates using system default
         /// which is typically the number of logical CPU cores
         pub virtual_processor_num_threads: usize,
+
+        /// Threads verifying the proof of work of headers. Defaults to 0 which indicates using
+        /// half of the logical CPU cores, leaving the others to the async runtime
+        pub pow_verification_num_threads: usize,
+
+        //
+        // Queues
+        //
+        /// Maximum count of headers received from peers and awaiting verification. Peer flows
+        /// wait for room in the queue before submitting more headers
+        pub pow_verification_queue_size: usize,
     }
 
     pub const PERF_PARAMS: PerfParams = PerfParams {
@@ -168,6 +180,8 @@ pub mod perf {
         block_window_cache_size: BASELINE_BLOCK_WINDOW_CACHE_SIZE,
         block_processors_num_threads: 0,
         virtual_processor_num_threads: 0,
+        pow_verification_num_threads: 0,
+        pow_verification_queue_size: BASELINE_POW_VERIFICATION_QUEUE_SIZE,
     };
 
     impl PerfParams {
@@ -175,6 +189,14 @@ pub mod perf {
             // Allow caching up to 10x over the baseline
             self.block_data_cache_size *= consensus_params.bps().clamp(1, 10) as usize;
         }
+
+        /// Returns the count of PoW verification threads, resolving the default
+        pub fn pow_verification_threads(&self) -> usize {
+            match self.pow_verification_num_threads {
+                0 => (std::thread::available_parallelism().map_or(1, |n| n.get()) / 2).max(1),
+                n => n,
+            }
+        }
     }
 }
 
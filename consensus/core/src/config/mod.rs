@@ -1,8 +1,10 @@
 pub mod bps;
+pub mod checkpoints;
 pub mod constants;
 pub mod genesis;
 pub mod params;
 
+use karlsen_hashes::Hash;
 use karlsen_utils::networking::{ContextualNetAddress, NetAddress};
 
 #[cfg(feature = "devnet-prealloc")]
@@ -35,6 +37,10 @@ pub struct Config {
     /// Indicates whether this node is an archival node
     pub is_archival: bool,
 
+    /// Blue score depth below the sink from which the block bodies and the acceptance data are
+    /// migrated to the cold DB, if the node has one (the finality depth being the minimum)
+    pub cold_storage_depth: u64,
+
     /// Enable various sanity checks which might be compute-intensive (mostly performed during pruning)
     pub enable_sanity_checks: bool,
 
@@ -61,6 +67,18 @@ pub struct Config {
 
     pub block_template_cache_lifetime: Option<u64>,
 
+    /// Short text embedded in the coinbase payload of the block templates built by the node
+    pub miner_tag: Option<String>,
+
+    /// Count of recently added blocks retained for RPC clients resuming the BlockAdded
+    /// event stream (0 disables the journal)
+    pub block_added_journal_size: usize,
+
+    /// Block under which the transaction scripts are assumed valid and not verified, while the
+    /// proof of work, the structure and the UTXO commitments of the blocks still are. Defaults to
+    /// the latest checkpoint of the network (`None` verifies every script)
+    pub assume_valid: Option<Hash>,
+
     #[cfg(feature = "devnet-prealloc")]
     pub initial_utxo_set: Arc<UtxoCollection>,
 
@@ -76,11 +94,13 @@ impl Config {
     }
 
     pub fn with_perf(params: Params, perf: PerfParams) -> Self {
+        let assume_valid = params.checkpoints.last().map(|checkpoint| checkpoint.hash);
         Self {
             params,
             perf,
             process_genesis: true,
             is_archival: false,
+            cold_storage_depth: 0,
             enable_sanity_checks: false,
             utxoindex: false,
             unsafe_rpc: false,
@@ -90,6 +110,9 @@ impl Config {
             externalip: None,
             p2p_listen_address: ContextualNetAddress::unspecified(),
             block_template_cache_lifetime: None,
+            miner_tag: None,
+            block_added_journal_size: 0,
+            assume_valid,
 
             #[cfg(feature = "devnet-prealloc")]
             initial_utxo_set: Default::default(),
@@ -1,5 +1,6 @@
 pub use super::{
     bps::{Bps, Testnet11Bps},
+    checkpoints::{Checkpoint, MAINNET_CHECKPOINTS, TESTNET_CHECKPOINTS},
     constants::consensus::*,
     genesis::{
         GenesisBlock, DEVNET_GENESIS, GENESIS, SIMNET_GENESIS, TESTNET11_GENESIS, TESTNET_GENESIS,
@@ -94,6 +95,9 @@ pub struct Params {
     pub max_block_level: BlockLevel,
     pub pruning_proof_m: u64,
     pub hf_daa_score: u64,
+
+    /// Hard-coded selected chain blocks of the network, ordered by DAA score
+    pub checkpoints: &'static [Checkpoint],
 }
 
 fn unix_now() -> u64 {
@@ -357,6 +361,7 @@ pub const MAINNET_PARAMS: Params = Params {
     max_block_level: 225,
     pruning_proof_m: 1000,
     hf_daa_score: 26962009, // HF DAAscore to switch to khashv2 (Fri Sep 13 01:37:00 PM UTC 2024)
+    checkpoints: MAINNET_CHECKPOINTS,
 };
 
 pub const TESTNET_PARAMS: Params = Params {
@@ -414,6 +419,7 @@ pub const TESTNET_PARAMS: Params = Params {
     max_block_level: 250,
     pruning_proof_m: 1000,
     hf_daa_score: 43200, // HF DAAscore to switch to khashv2 (12 hours after testnet launch)
+    checkpoints: TESTNET_CHECKPOINTS,
 };
 
 pub const TESTNET11_PARAMS: Params = Params {
@@ -466,6 +472,7 @@ pub const TESTNET11_PARAMS: Params = Params {
     skip_proof_of_work: false,
     max_block_level: 250,
     hf_daa_score: 0,
+    checkpoints: &[],
 };
 
 pub const SIMNET_PARAMS: Params = Params {
@@ -519,6 +526,7 @@ pub const SIMNET_PARAMS: Params = Params {
     skip_proof_of_work: true, // For simnet only, PoW can be simulated by default
     max_block_level: 250,
     hf_daa_score: 3600,
+    checkpoints: &[],
 };
 
 pub const DEVNET_PARAMS: Params = Params {
@@ -576,4 +584,5 @@ pub const DEVNET_PARAMS: Params = Params {
     max_block_level: 250,
     pruning_proof_m: 1000,
     hf_daa_score: 3600,
+    checkpoints: &[],
 };
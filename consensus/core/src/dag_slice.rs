@@ -0,0 +1,28 @@
+use karlsen_hashes::Hash;
+use serde::{Deserialize, Serialize};
+
+/// A block of a slice of the DAG along with its relations to the other blocks, as needed for
+/// drawing the DAG
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DagSliceBlock {
+    pub hash: Hash,
+    pub parents: Vec<Hash>,
+    pub daa_score: u64,
+    pub blue_score: u64,
+    pub timestamp: u64,
+    /// Whether the block is blue in the mergeset of its merging block
+    pub is_blue: bool,
+    pub is_chain_block: bool,
+    /// The selected chain block merging this block, none for the blocks merged by the virtual
+    /// block only, whose color may still change
+    pub merging_block: Option<Hash>,
+}
+
+/// A slice of the DAG made of the mergesets of consecutive selected chain blocks
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DagSlice {
+    /// Blocks ordered by DAA score
+    pub blocks: Vec<DagSliceBlock>,
+    /// DAA score to request the following slice from
+    pub next_daa_score: u64,
+}
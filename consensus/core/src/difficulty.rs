@@ -0,0 +1,114 @@
+use karlsen_hashes::Hash;
+use karlsen_math::{Uint256, Uint320};
+use serde::{Deserialize, Serialize};
+
+/// Difficulty data of a single block
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BlockDifficulty {
+    pub hash: Hash,
+    pub daa_score: u64,
+    pub timestamp: u64,
+    pub bits: u32,
+}
+
+/// Summary of the blocks composing a difficulty adjustment (DAA) window
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DifficultyWindowSummary {
+    /// Count of blocks in the window
+    pub size: u64,
+    /// The window holds one out of every `sample_rate` blocks
+    pub sample_rate: u64,
+    pub oldest_timestamp: u64,
+    pub newest_timestamp: u64,
+    /// Bits of the block with the highest target (lowest difficulty)
+    pub easiest_bits: u32,
+    /// Bits of the block with the lowest target (highest difficulty)
+    pub hardest_bits: u32,
+    /// Average target of the window blocks, the basis of the next difficulty adjustment
+    pub average_target: Uint256,
+}
+
+impl DifficultyWindowSummary {
+    pub fn new(sample_rate: u64, blocks: impl IntoIterator<Item = (u64, u32)>) -> Self {
+        let mut summary = Self {
+            size: 0,
+            sample_rate,
+            oldest_timestamp: u64::MAX,
+            newest_timestamp: 0,
+            easiest_bits: 0,
+            hardest_bits: 0,
+            average_target: Uint256::ZERO,
+        };
+        let mut easiest_target = Uint256::ZERO;
+        let mut hardest_target = Uint256::MAX;
+        let mut targets_sum = Uint320::ZERO;
+        for (timestamp, bits) in blocks {
+            let target = Uint256::from_compact_target_bits(bits);
+            summary.size += 1;
+            summary.oldest_timestamp = summary.oldest_timestamp.min(timestamp);
+            summary.newest_timestamp = summary.newest_timestamp.max(timestamp);
+            if target >= easiest_target {
+                easiest_target = target;
+                summary.easiest_bits = bits;
+            }
+            if target <= hardest_target {
+                hardest_target = target;
+                summary.hardest_bits = bits;
+            }
+            targets_sum = targets_sum + Uint320::from(target);
+        }
+        if summary.size == 0 {
+            summary.oldest_timestamp = 0;
+        } else {
+            summary.average_target = Uint256::try_from(targets_sum / summary.size)
+                .expect("the average of Uint256 values fits a Uint256");
+        }
+        summary
+    }
+
+    /// Returns the time span between the oldest and newest blocks of the window in milliseconds
+    pub fn time_span(&self) -> u64 {
+        self.newest_timestamp.saturating_sub(self.oldest_timestamp)
+    }
+}
+
+/// Difficulty adjustment state of the virtual block
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DifficultyInfo {
+    /// DAA score of the virtual block
+    pub daa_score: u64,
+    /// Difficulty bits expected for the next block, ie. the virtual bits
+    pub next_bits: u32,
+    /// Summary of the virtual DAA window
+    pub window: DifficultyWindowSummary,
+    /// Difficulty of the most recent selected chain blocks, starting from the sink
+    pub chain_blocks: Vec<BlockDifficulty>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_window_summary() {
+        let summary = DifficultyWindowSummary::new(
+            4,
+            [
+                (1_000, 0x1e7fffff),
+                (3_000, 0x1d7fffff),
+                (2_000, 0x1e7fffff),
+            ],
+        );
+        assert_eq!(summary.size, 3);
+        assert_eq!(summary.time_span(), 2_000);
+        assert_eq!(summary.easiest_bits, 0x1e7fffff);
+        assert_eq!(summary.hardest_bits, 0x1d7fffff);
+        let easy = Uint256::from_compact_target_bits(0x1e7fffff);
+        let hard = Uint256::from_compact_target_bits(0x1d7fffff);
+        assert_eq!(summary.average_target, (easy + easy + hard) / 3);
+
+        let empty = DifficultyWindowSummary::new(1, []);
+        assert_eq!(empty.size, 0);
+        assert_eq!(empty.time_span(), 0);
+    }
+}
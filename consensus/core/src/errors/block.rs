@@ -90,6 +90,11 @@ pub enum RuleError {
     #[error("block is violating bounded merge depth")]
     ViolatingBoundedMergeDepth,
 
+    #[error(
+        "block is more than a finality depth above checkpoint {0} but does not have it in its past"
+    )]
+    ViolatingCheckpoint(Hash),
+
     #[error("invalid merkle root: header indicates {0} but calculated value is {1}")]
     BadMerkleRoot(Hash, Hash),
 
@@ -14,9 +14,37 @@ pub enum ConfigError {
     #[error("Configuration: --ram-scale cannot be set above 10.0")]
     RamScaleTooHigh,
 
+    #[error("Configuration: --storage-mass-factor must be positive")]
+    StorageMassFactorNotPositive,
+
+    #[error(
+        "Configuration: --max-standard-tx-mass cannot be set above the maximum block mass of {0}"
+    )]
+    MaxStandardTxMassTooHigh(u64),
+
     #[error("Configuration: --max-tracked-addresses cannot be set above {0}")]
     MaxTrackedAddressesTooHigh(usize),
 
+    #[error("Configuration: --assume-valid must be a block hash or 0, got {0}")]
+    InvalidAssumeValid(String),
+
+    #[error("Configuration: --miner-tag must have 1 to {0} bytes and no control characters")]
+    InvalidMinerTag(usize),
+
+    #[error("Configuration: --miner-power-watts and --power-price must be set together, to finite non-negative values")]
+    InvalidPowerCost,
+
+    #[error("Configuration: --work-binding-secret must have at least {0} bytes")]
+    WorkBindingSecretTooShort(usize),
+
+    #[error(
+        "Configuration: --snapshot-publisher must be a hex encoded x-only public key, got {0}"
+    )]
+    InvalidSnapshotPublisher(String),
+
+    #[error("Configuration: --snapshot-publisher requires --snapshot-url")]
+    SnapshotPublisherWithoutUrl,
+
     #[cfg(feature = "devnet-prealloc")]
     #[error("Cannot preallocate UTXOs on any network except devnet")]
     PreallocUtxosOnNonDevnet,
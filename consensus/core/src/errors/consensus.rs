@@ -20,6 +20,12 @@ pub enum ConsensusError {
     #[error("got unexpected pruning point")]
     UnexpectedPruningPoint,
 
+    #[error("the UTXO history at DAA score {0} is pruned")]
+    UtxoHistoryPruned(u64),
+
+    #[error("the UTXO history at DAA score {0} is more than {1} chain blocks deep")]
+    UtxoHistoryTooDeep(u64, usize),
+
     #[error("pruning point is not at sufficient depth from virtual, cannot obtain its final anticone at this stage")]
     PruningPointInsufficientDepth,
 
@@ -1,4 +1,4 @@
-use crate::{hashing, BlueWorkType};
+use crate::{hashing, BlockLevel, BlueWorkType};
 use borsh::{BorshDeserialize, BorshSerialize};
 use karlsen_hashes::Hash;
 use serde::{Deserialize, Serialize};
@@ -72,6 +72,18 @@ impl Header {
         }
     }
 
+    /// Returns the parents at `level`, the genesis standing for the levels above the header ones
+    pub fn parents_at_level<'a>(&'a self, level: BlockLevel, genesis_hash: &'a Hash) -> &'a [Hash] {
+        if self.parents_by_level.is_empty() {
+            // If is genesis
+            &[]
+        } else if self.parents_by_level.len() > level as usize {
+            &self.parents_by_level[level as usize][..]
+        } else {
+            std::slice::from_ref(genesis_hash)
+        }
+    }
+
     /// WARNING: To be used for test purposes only
     pub fn from_precomputed_hash(hash: Hash, parents: Vec<Hash>) -> Header {
         Header {
@@ -0,0 +1,69 @@
+//!
+//! Header validation rules which only depend on the header itself and the network parameters.
+//!
+//! These rules are shared by the consensus header processor and the header-only (SPV)
+//! validation, so both reject the same headers with the same errors.
+//!
+
+use crate::{
+    blockhash::BlockHashExtensions,
+    constants,
+    errors::block::{BlockProcessResult, RuleError},
+    header::Header,
+};
+use karlsen_core::time::unix_now;
+
+pub fn check_header_version(header: &Header, hf_daa_score: u64) -> BlockProcessResult<()> {
+    let expected = if header.daa_score >= hf_daa_score {
+        constants::BLOCK_VERSION_KHASHV2
+    } else {
+        constants::BLOCK_VERSION_KHASHV1
+    };
+    if header.version != expected {
+        return Err(RuleError::WrongBlockVersion(header.version, expected));
+    }
+    Ok(())
+}
+
+/// Checks the header timestamp is not further into the future than `timestamp_deviation_tolerance` seconds
+pub fn check_block_timestamp_in_isolation(
+    header: &Header,
+    timestamp_deviation_tolerance: u64,
+) -> BlockProcessResult<()> {
+    // Timestamp deviation tolerance is in seconds so we multiply by 1000 to get milliseconds (without BPS dependency)
+    let max_block_time = unix_now() + timestamp_deviation_tolerance * 1000;
+    if header.timestamp > max_block_time {
+        return Err(RuleError::TimeTooFarIntoTheFuture(
+            header.timestamp,
+            max_block_time,
+        ));
+    }
+    Ok(())
+}
+
+pub fn check_parents_limit(header: &Header, max_block_parents: u8) -> BlockProcessResult<()> {
+    if header.direct_parents().is_empty() {
+        return Err(RuleError::NoParents);
+    }
+
+    if header.direct_parents().len() > max_block_parents as usize {
+        return Err(RuleError::TooManyParents(
+            header.direct_parents().len(),
+            max_block_parents as usize,
+        ));
+    }
+
+    Ok(())
+}
+
+pub fn check_parents_not_origin(header: &Header) -> BlockProcessResult<()> {
+    if header
+        .direct_parents()
+        .iter()
+        .any(|&parent| parent.is_origin())
+    {
+        return Err(RuleError::OriginParent);
+    }
+
+    Ok(())
+}
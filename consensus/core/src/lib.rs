@@ -16,9 +16,12 @@ pub mod coinbase;
 pub mod config;
 pub mod constants;
 pub mod daa_score_timestamp;
+pub mod dag_slice;
+pub mod difficulty;
 pub mod errors;
 pub mod hashing;
 pub mod header;
+pub mod header_validation;
 pub mod mass;
 pub mod merkle;
 pub mod muhash;
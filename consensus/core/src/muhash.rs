@@ -8,6 +8,14 @@ use karlsen_muhash::MuHash;
 pub trait MuHashExtensions {
     fn add_transaction(&mut self, tx: &impl VerifiableTransaction, block_daa_score: u64);
     fn add_utxo(&mut self, outpoint: &TransactionOutpoint, entry: &UtxoEntry);
+
+    /// Equivalent to calling `add_transaction` for every transaction, the UTXO elements being
+    /// hashed and multiplied as a batch
+    fn add_transactions<'a, T: VerifiableTransaction + 'a>(
+        &mut self,
+        txs: impl IntoIterator<Item = &'a T>,
+        block_daa_score: u64,
+    );
 }
 
 impl MuHashExtensions for MuHash {
@@ -35,6 +43,48 @@ impl MuHashExtensions for MuHash {
         write_utxo(&mut writer, entry, outpoint);
         writer.finalize();
     }
+
+    fn add_transactions<'a, T: VerifiableTransaction + 'a>(
+        &mut self,
+        txs: impl IntoIterator<Item = &'a T>,
+        block_daa_score: u64,
+    ) {
+        let mut removed = Vec::new();
+        let mut added = Vec::new();
+        for tx in txs {
+            let tx_id = tx.id();
+            for (input, entry) in tx.populated_inputs() {
+                let mut writer = ElementWriter::default();
+                write_utxo(&mut writer, entry, &input.previous_outpoint);
+                removed.push(writer.0);
+            }
+            for (i, output) in tx.outputs().iter().enumerate() {
+                let outpoint = TransactionOutpoint::new(tx_id, i as u32);
+                let entry = UtxoEntry::new(
+                    output.value,
+                    output.script_public_key.clone(),
+                    block_daa_score,
+                    tx.is_coinbase(),
+                );
+                let mut writer = ElementWriter::default();
+                write_utxo(&mut writer, &entry, &outpoint);
+                added.push(writer.0);
+            }
+        }
+        self.update_batch(&added, &removed);
+    }
+}
+
+/// Collects the serialized data of a single element, hashing it as a whole being equivalent
+/// to hashing it through an element builder
+#[derive(Default)]
+struct ElementWriter(Vec<u8>);
+
+impl HasherBase for ElementWriter {
+    fn update<A: AsRef<[u8]>>(&mut self, data: A) -> &mut Self {
+        self.0.extend_from_slice(data.as_ref());
+        self
+    }
 }
 
 fn write_utxo(writer: &mut impl HasherBase, entry: &UtxoEntry, outpoint: &TransactionOutpoint) {
@@ -49,3 +99,52 @@ fn write_utxo(writer: &mut impl HasherBase, entry: &UtxoEntry, outpoint: &Transa
         .update(entry.script_public_key.version().to_le_bytes())
         .write_var_bytes(entry.script_public_key.script());
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        subnets::SUBNETWORK_ID_NATIVE,
+        tx::{
+            PopulatedTransaction, ScriptPublicKey, Transaction, TransactionInput, TransactionOutput,
+        },
+    };
+    use karlsen_hashes::Hash;
+
+    #[test]
+    fn test_add_transactions() {
+        let spk = ScriptPublicKey::from_vec(0, vec![1, 2, 3]);
+        let txs = (0..3u64)
+            .map(|i| {
+                Transaction::new(
+                    0,
+                    vec![TransactionInput::new(
+                        TransactionOutpoint::new(Hash::from_u64_word(i), 0),
+                        vec![],
+                        0,
+                        0,
+                    )],
+                    vec![TransactionOutput::new(i * 100, spk.clone())],
+                    0,
+                    SUBNETWORK_ID_NATIVE,
+                    0,
+                    vec![],
+                )
+            })
+            .collect::<Vec<_>>();
+        let populated = txs
+            .iter()
+            .map(|tx| {
+                PopulatedTransaction::new(tx, vec![UtxoEntry::new(500, spk.clone(), 1, false)])
+            })
+            .collect::<Vec<_>>();
+
+        let mut serial = MuHash::new();
+        populated
+            .iter()
+            .for_each(|tx| serial.add_transaction(tx, 10));
+        let mut batched = MuHash::new();
+        batched.add_transactions(populated.iter(), 10);
+        assert_eq!(batched.finalize(), serial.finalize());
+    }
+}
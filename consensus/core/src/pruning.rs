@@ -1,6 +1,8 @@
 use crate::{
+    errors::pruning::{PruningImportError, PruningImportResult},
     header::Header,
     trusted::{TrustedGhostdagData, TrustedHeader},
+    BlockLevel,
 };
 use karlsen_hashes::Hash;
 use std::sync::Arc;
@@ -19,3 +21,55 @@ pub struct PruningPointTrustedData {
     /// Union of GHOSTDAG data required to verify blocks in the future of the pruning point
     pub ghostdag_blocks: Vec<TrustedGhostdagData>,
 }
+
+/// Checks the proof has a level per block level and returns the header of the pruning point it proves.
+///
+/// The checks of this module are shared by the consensus proof validation and the header-only (SPV) one.
+pub fn proof_pruning_point_header(
+    proof: &PruningPointProof,
+    max_block_level: BlockLevel,
+) -> PruningImportResult<&Arc<Header>> {
+    if proof.len() != max_block_level as usize + 1 {
+        return Err(PruningImportError::ProofNotEnoughLevels(
+            max_block_level as usize + 1,
+        ));
+    }
+    proof[0]
+        .last()
+        .ok_or(PruningImportError::PruningProofNotEnoughHeaders)
+}
+
+/// Checks a proof header belongs to `level`, ie. its own block level is at least as high
+pub fn check_proof_header_level(
+    header: &Header,
+    header_level: BlockLevel,
+    level: BlockLevel,
+) -> PruningImportResult<()> {
+    if header_level < level {
+        return Err(PruningImportError::PruningProofWrongBlockLevel(
+            header.hash,
+            header_level,
+            level,
+        ));
+    }
+    Ok(())
+}
+
+/// Checks the selected tip of a proof level is the pruning point or one of its parents at that level
+pub fn check_proof_selected_tip(
+    selected_tip: Hash,
+    pruning_point_header: &Header,
+    level: BlockLevel,
+    genesis_hash: &Hash,
+) -> PruningImportResult<()> {
+    if selected_tip != pruning_point_header.hash
+        && !pruning_point_header
+            .parents_at_level(level, genesis_hash)
+            .contains(&selected_tip)
+    {
+        return Err(
+            PruningImportError::PruningProofMissesBlocksBelowPruningPoint(selected_tip, level),
+        );
+    }
+    Ok(())
+}
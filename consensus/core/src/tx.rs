@@ -70,6 +70,8 @@ pub type TransactionIndexType = u32;
     Eq,
     Hash,
     PartialEq,
+    PartialOrd,
+    Ord,
     Debug,
     Copy,
     Clone,
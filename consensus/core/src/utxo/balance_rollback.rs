@@ -0,0 +1,110 @@
+//! Historical balances of script public keys.
+//!
+//! The UTXO index only holds the virtual UTXO set. The balance of a script as of a past selected
+//! chain block is obtained by rolling back the current balance along the UTXO diffs of the chain
+//! blocks above it and of the virtual, which are only retained above the pruning point on
+//! pruned nodes.
+
+use super::utxo_diff::UtxoDiff;
+use crate::{
+    tx::{ScriptPublicKey, ScriptPublicKeys},
+    BlockHashSet,
+};
+use karlsen_hashes::Hash;
+use std::collections::HashMap;
+
+/// Changes of the balances of a set of scripts between the UTXO set of a past selected chain
+/// block and the virtual UTXO set
+#[derive(Debug, Clone, Default)]
+pub struct BalanceRollback {
+    /// Chain block the balances are rolled back to
+    pub chain_block: Hash,
+    /// DAA score of the chain block
+    pub daa_score: u64,
+    /// Parents of the virtual the balances are rolled back from
+    pub virtual_parents: BlockHashSet,
+    /// Sum of the values of the entries added since the chain block, by script
+    pub added: HashMap<ScriptPublicKey, u64>,
+    /// Sum of the values of the entries removed since the chain block, by script
+    pub removed: HashMap<ScriptPublicKey, u64>,
+}
+
+impl BalanceRollback {
+    pub fn new(virtual_parents: BlockHashSet) -> Self {
+        Self {
+            virtual_parents,
+            ..Default::default()
+        }
+    }
+
+    /// Accounts the entries of `diff` paying to one of `script_public_keys`
+    pub fn add_diff(&mut self, diff: &UtxoDiff, script_public_keys: &ScriptPublicKeys) {
+        for entry in diff.add.values() {
+            if script_public_keys.contains(&entry.script_public_key) {
+                *self
+                    .added
+                    .entry(entry.script_public_key.clone())
+                    .or_default() += entry.amount;
+            }
+        }
+        for entry in diff.remove.values() {
+            if script_public_keys.contains(&entry.script_public_key) {
+                *self
+                    .removed
+                    .entry(entry.script_public_key.clone())
+                    .or_default() += entry.amount;
+            }
+        }
+    }
+
+    /// Returns the balance of `script_public_key` as of the chain block given its current
+    /// balance in the virtual UTXO set
+    pub fn rollback(&self, script_public_key: &ScriptPublicKey, current: u64) -> u64 {
+        let added = self
+            .added
+            .get(script_public_key)
+            .copied()
+            .unwrap_or_default();
+        let removed = self
+            .removed
+            .get(script_public_key)
+            .copied()
+            .unwrap_or_default();
+        (current + removed).saturating_sub(added)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tx::{TransactionOutpoint, UtxoEntry};
+
+    #[test]
+    fn test_balance_rollback() {
+        let spk = |byte: u8| ScriptPublicKey::from_vec(0, vec![byte; 34]);
+        let outpoint = |word: u64| TransactionOutpoint::new(Hash::from_u64_word(word), 0);
+        let entry = |byte: u8, amount: u64| UtxoEntry::new(amount, spk(byte), 0, false);
+        let tracked: ScriptPublicKeys = [spk(1), spk(2)].into_iter().collect();
+
+        // Older diff: script 1 receives 50 and script 3 (not tracked) receives 70
+        let mut older = UtxoDiff::default();
+        older.add.insert(outpoint(1), entry(1, 50));
+        older.add.insert(outpoint(2), entry(3, 70));
+        // Newer diff: script 1 spends a 100 entry older than the rollback target and the 50
+        // entry received above, sending 120 to script 2
+        let mut newer = UtxoDiff::default();
+        newer.remove.insert(outpoint(0), entry(1, 100));
+        newer.remove.insert(outpoint(1), entry(1, 50));
+        newer.add.insert(outpoint(3), entry(2, 120));
+
+        let mut rollback = BalanceRollback::new(Default::default());
+        rollback.add_diff(&newer, &tracked);
+        rollback.add_diff(&older, &tracked);
+        assert!(!rollback.added.contains_key(&spk(3)));
+
+        // Script 1 held 100 before the older diff, then 150, then 0
+        assert_eq!(rollback.rollback(&spk(1), 0), 100);
+        assert_eq!(rollback.rollback(&spk(2), 120), 0);
+        assert_eq!(rollback.rollback(&spk(3), 70), 70);
+    }
+}
@@ -1,3 +1,4 @@
+pub mod balance_rollback;
 pub mod utxo_collection;
 pub mod utxo_diff;
 pub mod utxo_error;
@@ -1,6 +1,7 @@
 // public for benchmarks
 #[doc(hidden)]
 pub mod matrix;
+mod share;
 #[cfg(feature = "wasm32-sdk")]
 pub mod wasm;
 #[doc(hidden)]
@@ -9,8 +10,11 @@ pub mod xoshiro;
 use std::cmp::max;
 
 use crate::matrix::Matrix;
-use karlsen_consensus_core::{constants, hashing, header::Header, BlockLevel};
-//use karlsen_consensus_core::errors::block::RuleError;
+pub use crate::share::{
+    difficulty_1_target, target_from_share_difficulty, ShareOutcome, ShareValidator,
+};
+use karlsen_consensus_core::errors::block::{BlockProcessResult, RuleError};
+use karlsen_consensus_core::{constants, hashing, header::Header, BlockLevel, BlueWorkType};
 //use karlsen_hashes::Pow;
 use karlsen_hashes::{PowB3Hash, PowFishHash};
 use karlsen_math::Uint256;
@@ -109,6 +113,39 @@ pub fn calc_block_level(header: &Header, max_block_level: BlockLevel) -> BlockLe
 
     let state = State::new(header);
     let (_, pow) = state.check_pow(header.nonce);
+    calc_level_from_pow(pow, max_block_level)
+}
+
+/// Checks the header pow against its declared bits and returns the block level as computed from the pow.
+/// With `skip_proof_of_work`, only the level is computed.
+pub fn check_pow_and_calc_block_level(
+    header: &Header,
+    max_block_level: BlockLevel,
+    skip_proof_of_work: bool,
+) -> BlockProcessResult<BlockLevel> {
+    let (passed, pow) = State::new(header).check_pow(header.nonce);
+    if passed || skip_proof_of_work {
+        Ok(calc_level_from_pow(pow, max_block_level))
+    } else {
+        Err(RuleError::InvalidPoW)
+    }
+}
+
+/// Returns the level of a block given the value of its pow hash
+pub fn calc_level_from_pow(pow: Uint256, max_block_level: BlockLevel) -> BlockLevel {
     let signed_block_level = max_block_level as i64 - pow.bits() as i64;
     max(signed_block_level, 0) as BlockLevel
 }
+
+/// Returns the expected amount of work required to mine a block with the given difficulty `bits`
+pub fn calc_work(bits: u32) -> BlueWorkType {
+    let target = Uint256::from_compact_target_bits(bits);
+    // Source: https://github.com/bitcoin/bitcoin/blob/2e34374bf3e12b37b0c66824a6c998073cdfab01/src/chain.cpp#L131
+    // We need to compute 2**256 / (bnTarget+1), but we can't represent 2**256
+    // as it's too large for an arith_uint256. However, as 2**256 is at least as large
+    // as bnTarget+1, it is equal to ((2**256 - bnTarget - 1) / (bnTarget+1)) + 1,
+    // or ~bnTarget / (bnTarget+1) + 1.
+
+    let res = (!target / (target + 1)) + 1;
+    res.try_into().expect("Work should not exceed 2**192")
+}
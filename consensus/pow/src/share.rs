@@ -0,0 +1,171 @@
+//!
+//! Share validation for mining pools.
+//!
+//! A pool accepts shares meeting its own, easier, share target and must submit the ones also
+//! meeting the network target as blocks. A solution meeting the network target is always
+//! reported as a block, even if the share difficulty is set above the network difficulty.
+//!
+
+use crate::State;
+use karlsen_consensus_core::header::Header;
+use karlsen_math::Uint256;
+
+/// The target of a share of difficulty 1, as used by the stratum protocol: 0xffff * 2^208
+pub fn difficulty_1_target() -> Uint256 {
+    Uint256::from_u64(0xffff) << 208
+}
+
+/// Fractional bits kept when converting a share difficulty into a target
+const DIFFICULTY_FRACTION_BITS: u32 = 32;
+
+/// Returns the target of a pool share difficulty, ie. `difficulty_1_target() / difficulty`,
+/// or `None` if the difficulty is not a positive finite number or is too small to be represented
+pub fn target_from_share_difficulty(difficulty: f64) -> Option<Uint256> {
+    if !difficulty.is_finite() || difficulty <= 0.0 {
+        return None;
+    }
+    // The float to integer conversion saturates for huge difficulties, yielding the smallest target
+    let scaled_difficulty = (difficulty * (1u64 << DIFFICULTY_FRACTION_BITS) as f64) as u128;
+    if scaled_difficulty == 0 {
+        return None;
+    }
+    Some(
+        (difficulty_1_target() << DIFFICULTY_FRACTION_BITS) / Uint256::from_u128(scaled_difficulty),
+    )
+}
+
+/// Result of a share check
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ShareOutcome {
+    /// The pow does not meet the share target
+    Rejected,
+    /// The pow meets the share target only
+    Share,
+    /// The pow meets the network target, so the solution must be submitted as a block
+    Block,
+}
+
+impl ShareOutcome {
+    pub fn is_accepted(&self) -> bool {
+        !matches!(self, ShareOutcome::Rejected)
+    }
+}
+
+/// Checks the nonces submitted for a block template against a pool share target.
+///
+/// FishHash computations rely on a light cache shared by the whole process, so validators are
+/// cheap to create per template. The cache can be built ahead of the first share with
+/// [`karlsen_hashes::PowFishHash::initialize_light_cache`].
+pub struct ShareValidator {
+    state: State,
+    share_target: Uint256,
+}
+
+impl ShareValidator {
+    /// Returns `None` if `share_difficulty` is not a valid share difficulty
+    pub fn new(header: &Header, share_difficulty: f64) -> Option<Self> {
+        let share_target = target_from_share_difficulty(share_difficulty)?;
+        Some(Self::with_share_target(header, share_target))
+    }
+
+    pub fn with_share_target(header: &Header, share_target: Uint256) -> Self {
+        Self {
+            state: State::new(header),
+            share_target,
+        }
+    }
+
+    pub fn share_target(&self) -> Uint256 {
+        self.share_target
+    }
+
+    pub fn network_target(&self) -> Uint256 {
+        self.state.target
+    }
+
+    /// Checks a nonce and returns the outcome along with the pow value
+    pub fn check(&self, nonce: u64) -> (ShareOutcome, Uint256) {
+        let (meets_network_target, pow) = self.state.check_pow(nonce);
+        let outcome = if meets_network_target {
+            ShareOutcome::Block
+        } else if pow <= self.share_target {
+            ShareOutcome::Share
+        } else {
+            ShareOutcome::Rejected
+        };
+        (outcome, pow)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use karlsen_consensus_core::constants::BLOCK_VERSION_KHASHV1;
+    use karlsen_hashes::ZERO_HASH;
+
+    fn header(bits: u32) -> Header {
+        Header::new_finalized(
+            BLOCK_VERSION_KHASHV1,
+            vec![vec![ZERO_HASH]],
+            ZERO_HASH,
+            ZERO_HASH,
+            ZERO_HASH,
+            1_000,
+            bits,
+            0,
+            0,
+            0.into(),
+            0,
+            ZERO_HASH,
+        )
+    }
+
+    #[test]
+    fn test_target_from_share_difficulty() {
+        assert_eq!(
+            target_from_share_difficulty(1.0),
+            Some(difficulty_1_target())
+        );
+        assert_eq!(
+            target_from_share_difficulty(2.0),
+            Some(difficulty_1_target() >> 1)
+        );
+        assert_eq!(
+            target_from_share_difficulty(0.5),
+            Some(difficulty_1_target() << 1)
+        );
+        assert_eq!(
+            target_from_share_difficulty(65536.0),
+            Some(Uint256::from_u64(0xffff) << 192)
+        );
+        assert_eq!(target_from_share_difficulty(0.0), None);
+        assert_eq!(target_from_share_difficulty(-1.0), None);
+        assert_eq!(target_from_share_difficulty(f64::NAN), None);
+        assert_eq!(target_from_share_difficulty(f64::INFINITY), None);
+    }
+
+    #[test]
+    fn test_share_validator() {
+        // A very hard network target and the easiest share target
+        let validator =
+            ShareValidator::new(&header(0x1b00ffff), 1.0 / (1u64 << 32) as f64).unwrap();
+        assert!(validator.share_target() > validator.network_target());
+        let (outcome, pow) = (0..4)
+            .map(|nonce| validator.check(nonce))
+            .find(|(outcome, _)| outcome.is_accepted())
+            .unwrap();
+        assert_eq!(outcome, ShareOutcome::Share);
+        assert!(pow <= validator.share_target());
+        assert!(ShareValidator::new(&header(0x1b00ffff), 1.0 / (1u64 << 33) as f64).is_none());
+
+        // No nonce meets a zero share target, however full solutions are still reported
+        let validator = ShareValidator::with_share_target(&header(0x1b00ffff), Uint256::ZERO);
+        assert_eq!(validator.check(1).0, ShareOutcome::Rejected);
+        let validator = ShareValidator::with_share_target(&header(0x207fffff), Uint256::ZERO);
+        let outcomes = (0..64)
+            .map(|nonce| validator.check(nonce).0)
+            .collect::<Vec<_>>();
+        assert!(outcomes.contains(&ShareOutcome::Block));
+        assert!(!outcomes.contains(&ShareOutcome::Share));
+    }
+}
@@ -0,0 +1,45 @@
+use karlsen_consensus_core::{
+    errors::{block::RuleError, pruning::PruningImportError},
+    BlueWorkType,
+};
+use karlsen_hashes::Hash;
+use thiserror::Error;
+
+#[derive(Error, Debug, Clone)]
+pub enum SpvError {
+    /// A header rule shared with the consensus header processor
+    #[error(transparent)]
+    Rule(#[from] RuleError),
+
+    /// A pruning point proof rule shared with the consensus proof validation
+    #[error(transparent)]
+    Proof(#[from] PruningImportError),
+
+    #[error("block {0} difficulty bits {1:#x} exceed the network maximal target")]
+    TargetAboveMax(Hash, u32),
+
+    #[error("block {0} is missing the following parents: {1:?}")]
+    MissingParents(Hash, Vec<Hash>),
+
+    #[error(
+        "block {0} blue score {1} is out of the range [{2}, {3}] expected from its selected parent"
+    )]
+    BlueScoreOutOfRange(Hash, u64, u64, u64),
+
+    #[error(
+        "block {0} blue work {1} is out of the range [{2}, {3}] expected from its selected parent"
+    )]
+    BlueWorkOutOfRange(Hash, BlueWorkType, BlueWorkType, BlueWorkType),
+
+    #[error(
+        "block {0} DAA score {1} is out of the range [{2}, {3}] expected from its selected parent"
+    )]
+    DaaScoreOutOfRange(Hash, u64, u64, u64),
+
+    #[error(
+        "the proof pruning point blue work {0} is not higher than the current best blue work {1}"
+    )]
+    ProofInsufficientBlueWork(BlueWorkType, BlueWorkType),
+}
+
+pub type SpvResult<T> = std::result::Result<T, SpvError>;
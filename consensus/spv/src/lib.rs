@@ -0,0 +1,37 @@
+//!
+//! Header-only (SPV) validation and sync.
+//!
+//! This crate validates block headers received from untrusted peers without
+//! the block bodies nor the consensus databases, so light clients can follow
+//! the best chain of the network and its blue score.
+//!
+//! A [`HeaderSync`] is bootstrapped either from the genesis or from a pruning
+//! point proof and then fed headers in topological order.
+//!
+//! Every header is checked for:
+//! - its version, timestamp, parents count and proof of work in isolation, using
+//!   the consensus rules of `karlsen_consensus_core::header_validation`
+//! - a difficulty target not exceeding the network maximum
+//! - blue score and DAA score in the range allowed by its selected parent
+//! - blue work accumulating the work of its selected parent and of at most `ghostdag_k`
+//!   other blues, none of them having more work than the header itself
+//! - difficulty bits matching the ones computed by the consensus from its DAA window,
+//!   recomputed from the mergesets of the known headers
+//!
+//! Some consensus rules need the GHOSTDAG coloring and are *not* verified here: the exact
+//! blue score/work and DAA score, the past median time, the bounded merge depth and the
+//! header pruning point. A peer can therefore feed headers with a blue work inflated
+//! within the above bounds, which requires mining them at the expected difficulty.
+//! The difficulty is not checked for the headers whose DAA window reaches below the
+//! pruning point the sync was bootstrapped from.
+//!
+
+pub mod error;
+pub mod proof;
+pub mod store;
+pub mod sync;
+pub mod validator;
+mod window;
+
+pub use error::{SpvError, SpvResult};
+pub use sync::HeaderSync;
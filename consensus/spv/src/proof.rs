@@ -0,0 +1,91 @@
+use crate::{error::SpvResult, store::HeaderStore, validator::HeaderValidator};
+use karlsen_consensus_core::{
+    errors::pruning::PruningImportError,
+    header::Header,
+    pruning::{
+        check_proof_header_level, check_proof_selected_tip, proof_pruning_point_header,
+        PruningPointProof,
+    },
+};
+use karlsen_hashes::Hash;
+use std::{collections::HashSet, sync::Arc};
+
+/// Validates the structure of a pruning point proof received from an untrusted peer and
+/// returns the header of the pruning point it proves.
+///
+/// Every header is checked in isolation (including its proof of work) and must have a level at
+/// least as high as the proof level it belongs to. At every level, all headers but the first must
+/// have a parent earlier in the same level and the selected tip must be either the pruning point
+/// or one of its parents.
+///
+/// The proof shape, header level and selected tip checks are the ones of the consensus validation
+/// and fail with the same [`PruningImportError`]s. Unlike the consensus validation, the GHOSTDAG
+/// of every level is not recomputed, so the proofs of two peers can only be compared by the blue
+/// work of their pruning points.
+pub fn validate_pruning_point_proof(
+    validator: &HeaderValidator,
+    proof: &PruningPointProof,
+) -> SpvResult<Arc<Header>> {
+    let max_block_level = validator.params().max_block_level;
+    let proof_pp_header = proof_pruning_point_header(proof, max_block_level)?.clone();
+
+    for level in (0..=max_block_level).rev() {
+        let mut known = HashSet::with_capacity(proof[level as usize].len());
+        let mut selected_tip: Option<&Arc<Header>> = None;
+        for (i, header) in proof[level as usize].iter().enumerate() {
+            // The genesis has no parents and hence cannot be validated in isolation
+            let header_level = if header.hash == validator.genesis_hash() {
+                max_block_level
+            } else {
+                validator.validate_header_in_isolation(header)?
+            };
+            check_proof_header_level(header, header_level, level)?;
+
+            // Only the first block at each level is allowed to have no known parents
+            if i != 0
+                && !validator
+                    .parents_at_level(header, level)
+                    .iter()
+                    .any(|parent| known.contains(parent))
+            {
+                return Err(PruningImportError::PruningProofHeaderWithNoKnownParents(
+                    header.hash,
+                    level,
+                )
+                .into());
+            }
+
+            if !known.insert(header.hash) {
+                return Err(PruningImportError::PruningProofDuplicateHeaderAtLevel(
+                    header.hash,
+                    level,
+                )
+                .into());
+            }
+
+            if selected_tip.map_or(true, |tip| HeaderStore::is_better(header, tip)) {
+                selected_tip = Some(header);
+            }
+        }
+
+        if let Some(selected_tip) = selected_tip {
+            check_proof_selected_tip(
+                selected_tip.hash,
+                &proof_pp_header,
+                level,
+                &validator.genesis_hash(),
+            )?;
+        }
+    }
+
+    Ok(proof_pp_header)
+}
+
+/// Returns the unique headers of a proof
+pub fn proof_headers(proof: &PruningPointProof) -> impl Iterator<Item = &Arc<Header>> {
+    let mut seen = HashSet::<Hash>::new();
+    proof
+        .iter()
+        .flatten()
+        .filter(move |header| seen.insert(header.hash))
+}
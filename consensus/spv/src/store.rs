@@ -0,0 +1,80 @@
+use karlsen_consensus_core::header::Header;
+use karlsen_hashes::Hash;
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+};
+
+/// In-memory store of the validated headers, tracking the DAG tips and the best header.
+///
+/// Uses the default hasher since the headers come from untrusted peers.
+#[derive(Default)]
+pub struct HeaderStore {
+    headers: HashMap<Hash, Arc<Header>>,
+    tips: HashSet<Hash>,
+    best: Option<Arc<Header>>,
+}
+
+impl HeaderStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn get(&self, hash: &Hash) -> Option<&Arc<Header>> {
+        self.headers.get(hash)
+    }
+
+    pub fn has(&self, hash: &Hash) -> bool {
+        self.headers.contains_key(hash)
+    }
+
+    /// Inserts a header, returning false if it was already known
+    pub fn insert(&mut self, header: Arc<Header>) -> bool {
+        if self.headers.contains_key(&header.hash) {
+            return false;
+        }
+        header.direct_parents().iter().for_each(|parent| {
+            self.tips.remove(parent);
+        });
+        self.tips.insert(header.hash);
+        if self
+            .best
+            .as_ref()
+            .map_or(true, |best| Self::is_better(&header, best))
+        {
+            self.best = Some(header.clone());
+        }
+        self.headers.insert(header.hash, header);
+        true
+    }
+
+    /// Returns the header having the highest blue work, ties being broken by the highest hash
+    /// like the GHOSTDAG selected parent
+    pub fn best(&self) -> Option<&Arc<Header>> {
+        self.best.as_ref()
+    }
+
+    /// Returns the headers having no known children
+    pub fn tips(&self) -> &HashSet<Hash> {
+        &self.tips
+    }
+
+    pub fn len(&self) -> usize {
+        self.headers.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.headers.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.headers.clear();
+        self.tips.clear();
+        self.best = None;
+    }
+
+    /// Returns true if `a` would be selected over `b` as a selected parent
+    pub fn is_better(a: &Header, b: &Header) -> bool {
+        (a.blue_work, a.hash) > (b.blue_work, b.hash)
+    }
+}
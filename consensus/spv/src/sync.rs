@@ -0,0 +1,333 @@
+use crate::{
+    error::{SpvError, SpvResult},
+    proof::{proof_headers, validate_pruning_point_proof},
+    store::HeaderStore,
+    validator::HeaderValidator,
+    window::difficulty_window,
+};
+use karlsen_consensus_core::{config::params::Params, header::Header, pruning::PruningPointProof};
+use karlsen_hashes::Hash;
+use parking_lot::{RwLock, RwLockUpgradableReadGuard};
+use std::sync::Arc;
+
+/// Syncs and validates the headers of the DAG received from untrusted peers.
+///
+/// The sync starts from the network genesis and can jump ahead to a pruning point with
+/// [`HeaderSync::apply_pruning_point_proof`]. Headers must then be added in topological order,
+/// ie. after all their parents.
+pub struct HeaderSync {
+    validator: HeaderValidator,
+    store: RwLock<HeaderStore>,
+}
+
+impl HeaderSync {
+    pub fn new(params: Params) -> Self {
+        let mut store = HeaderStore::new();
+        store.insert(Arc::new(Header::from(&params.genesis)));
+        Self {
+            validator: HeaderValidator::new(params),
+            store: RwLock::new(store),
+        }
+    }
+
+    pub fn validator(&self) -> &HeaderValidator {
+        &self.validator
+    }
+
+    /// Validates a pruning point proof and, if its pruning point has more blue work than the
+    /// current best header, replaces the known headers with the ones of the proof
+    pub fn apply_pruning_point_proof(&self, proof: &PruningPointProof) -> SpvResult<Hash> {
+        let pruning_point = validate_pruning_point_proof(&self.validator, proof)?;
+        let mut store = self.store.write();
+        if let Some(best) = store.best() {
+            if pruning_point.blue_work <= best.blue_work {
+                return Err(SpvError::ProofInsufficientBlueWork(
+                    pruning_point.blue_work,
+                    best.blue_work,
+                ));
+            }
+        }
+        store.clear();
+        proof_headers(proof).for_each(|header| {
+            store.insert(header.clone());
+        });
+        Ok(pruning_point.hash)
+    }
+
+    /// Adds a header from the anticone of the pruning point, as received along with the pruning
+    /// point proof. Such headers are only validated in isolation since their parents are
+    /// usually below the pruning point and thus unknown.
+    pub fn add_trusted_header(&self, header: Arc<Header>) -> SpvResult<bool> {
+        self.validator.validate_header_in_isolation(&header)?;
+        Ok(self.store.write().insert(header))
+    }
+
+    /// Validates and adds a header whose parents are all known. Returns false if the header was
+    /// already known.
+    ///
+    /// The difficulty is checked against the difficulty window recomputed from the known headers,
+    /// unless this window reaches below the pruning point the sync was bootstrapped from.
+    pub fn add_header(&self, header: Arc<Header>) -> SpvResult<bool> {
+        if self.store.read().has(&header.hash) {
+            return Ok(false);
+        }
+        self.validator.validate_header_in_isolation(&header)?;
+
+        // Readers are not blocked while the difficulty window is recomputed
+        let store = self.store.upgradable_read();
+        let mut selected_parent: Option<&Arc<Header>> = None;
+        let mut missing_parents = Vec::new();
+        for parent in header.direct_parents() {
+            match store.get(parent) {
+                Some(parent) => {
+                    if selected_parent.map_or(true, |sp| HeaderStore::is_better(parent, sp)) {
+                        selected_parent = Some(parent);
+                    }
+                }
+                None => missing_parents.push(*parent),
+            }
+        }
+        if !missing_parents.is_empty() {
+            return Err(SpvError::MissingParents(header.hash, missing_parents));
+        }
+        let selected_parent = selected_parent.expect("parents count was checked in isolation");
+        self.validator
+            .validate_header_against_selected_parent(&header, selected_parent)?;
+        if let Some(window) =
+            difficulty_window(&store, self.validator.params(), &header, selected_parent)?
+        {
+            self.validator
+                .validate_difficulty(&header, selected_parent, &window)?;
+        }
+
+        Ok(RwLockUpgradableReadGuard::upgrade(store).insert(header))
+    }
+
+    /// Validates and adds a batch of headers sorted in topological order, stopping at the first
+    /// invalid one. Returns the count of newly added headers.
+    pub fn add_headers(&self, headers: impl IntoIterator<Item = Arc<Header>>) -> SpvResult<usize> {
+        let mut count = 0;
+        for header in headers {
+            if self.add_header(header)? {
+                count += 1;
+            }
+        }
+        Ok(count)
+    }
+
+    pub fn has_header(&self, hash: &Hash) -> bool {
+        self.store.read().has(hash)
+    }
+
+    pub fn get_header(&self, hash: &Hash) -> Option<Arc<Header>> {
+        self.store.read().get(hash).cloned()
+    }
+
+    /// Returns the best known header, ie. the one a full node would select as the virtual selected parent
+    pub fn best_header(&self) -> Arc<Header> {
+        self.store
+            .read()
+            .best()
+            .cloned()
+            .expect("the store is never empty")
+    }
+
+    /// Returns the blue score of the best known header
+    pub fn best_blue_score(&self) -> u64 {
+        self.best_header().blue_score
+    }
+
+    /// Returns the hashes of the known headers having no children, ie. the virtual parents candidates
+    pub fn tips(&self) -> Vec<Hash> {
+        self.store.read().tips().iter().copied().collect()
+    }
+
+    pub fn header_count(&self) -> usize {
+        self.store.read().len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use karlsen_consensus_core::{
+        config::params::SIMNET_PARAMS,
+        constants,
+        errors::{block::RuleError, pruning::PruningImportError},
+        BlueWorkType,
+    };
+    use karlsen_hashes::ZERO_HASH;
+    use karlsen_pow::calc_work;
+
+    fn header(parents: &[&Arc<Header>], nonce: u64) -> Arc<Header> {
+        let selected_parent = parents
+            .iter()
+            .copied()
+            .reduce(|a, b| if HeaderStore::is_better(a, b) { a } else { b })
+            .unwrap();
+        Arc::new(Header::new_finalized(
+            constants::BLOCK_VERSION_KHASHV1,
+            vec![parents.iter().map(|x| x.hash).collect()],
+            ZERO_HASH,
+            ZERO_HASH,
+            ZERO_HASH,
+            selected_parent.timestamp + 1_000,
+            SIMNET_PARAMS.genesis.bits,
+            nonce,
+            selected_parent.daa_score + parents.len() as u64,
+            selected_parent.blue_work
+                + parents
+                    .iter()
+                    .map(|x| calc_work(x.bits))
+                    .fold(BlueWorkType::from(0u64), |a, b| a + b),
+            selected_parent.blue_score + parents.len() as u64,
+            ZERO_HASH,
+        ))
+    }
+
+    #[test]
+    fn test_header_sync() {
+        let sync = HeaderSync::new(SIMNET_PARAMS);
+        let genesis = sync.best_header();
+        assert_eq!(genesis.hash, SIMNET_PARAMS.genesis.hash);
+
+        let a = header(&[&genesis], 1);
+        let b = header(&[&genesis], 2);
+        let c = header(&[&a, &b], 3);
+        assert_eq!(
+            sync.add_headers([a.clone(), b.clone(), c.clone()]).unwrap(),
+            3
+        );
+        assert!(!sync.add_header(c.clone()).unwrap());
+        assert_eq!(sync.best_header().hash, c.hash);
+        assert_eq!(sync.best_blue_score(), 3);
+        assert_eq!(sync.tips(), vec![c.hash]);
+
+        // Unknown parent
+        let orphan = header(&[&header(&[&c], 4)], 5);
+        assert!(matches!(
+            sync.add_header(orphan),
+            Err(SpvError::MissingParents(_, _))
+        ));
+
+        // Blue score not following the selected parent
+        let mut invalid = (*header(&[&c], 6)).clone();
+        invalid.blue_score = c.blue_score;
+        invalid.finalize();
+        assert!(matches!(
+            sync.add_header(Arc::new(invalid)),
+            Err(SpvError::BlueScoreOutOfRange(_, _, _, _))
+        ));
+
+        // Blue work not accumulating the selected parent work
+        let mut invalid = (*header(&[&c], 7)).clone();
+        invalid.blue_work = c.blue_work;
+        invalid.finalize();
+        assert!(matches!(
+            sync.add_header(Arc::new(invalid)),
+            Err(SpvError::BlueWorkOutOfRange(_, _, _, _))
+        ));
+
+        // Blue work inflated beyond the work of the mergeset blues
+        let mut invalid = (*header(&[&c], 10)).clone();
+        invalid.blue_work = c.blue_work + calc_work(c.bits) * (SIMNET_PARAMS.ghostdag_k as u64 + 2);
+        invalid.finalize();
+        assert!(matches!(
+            sync.add_header(Arc::new(invalid)),
+            Err(SpvError::BlueWorkOutOfRange(_, _, _, _))
+        ));
+
+        // Difficulty not matching the one of the DAA window
+        let mut invalid = (*header(&[&c], 11)).clone();
+        invalid.bits = SIMNET_PARAMS.genesis.bits - 1;
+        invalid.finalize();
+        assert!(matches!(
+            sync.add_header(Arc::new(invalid)),
+            Err(SpvError::Rule(RuleError::UnexpectedDifficulty(_, _)))
+        ));
+
+        // Difficulty easier than the network maximum
+        let mut invalid = (*header(&[&c], 8)).clone();
+        invalid.bits = 0x2100ffff;
+        invalid.finalize();
+        assert!(matches!(
+            sync.add_header(Arc::new(invalid)),
+            Err(SpvError::TargetAboveMax(_, _))
+        ));
+
+        // Isolation rules are the consensus ones
+        let mut invalid = (*header(&[&c], 9)).clone();
+        invalid.version = constants::BLOCK_VERSION_KHASHV2;
+        invalid.finalize();
+        assert!(matches!(
+            sync.add_header(Arc::new(invalid)),
+            Err(SpvError::Rule(RuleError::WrongBlockVersion(_, _)))
+        ));
+        assert_eq!(sync.header_count(), 4);
+    }
+
+    #[test]
+    fn test_apply_pruning_point_proof() {
+        let genesis = Arc::new(Header::from(&SIMNET_PARAMS.genesis));
+        let a = header(&[&genesis], 1);
+        let b = header(&[&a], 2);
+        let levels = SIMNET_PARAMS.max_block_level as usize + 1;
+        let mut proof: PruningPointProof = vec![vec![genesis.clone()]; levels];
+        proof[0] = vec![genesis.clone(), a.clone(), b.clone()];
+
+        let sync = HeaderSync::new(SIMNET_PARAMS);
+        assert_eq!(sync.apply_pruning_point_proof(&proof).unwrap(), b.hash);
+        assert_eq!(sync.best_header().hash, b.hash);
+        assert_eq!(sync.header_count(), 3);
+        assert!(sync.add_header(header(&[&b], 3)).is_ok());
+
+        // A proof not improving on the current best header is rejected
+        assert!(matches!(
+            sync.apply_pruning_point_proof(&proof),
+            Err(SpvError::ProofInsufficientBlueWork(_, _))
+        ));
+
+        // The selected tip of a level must be the pruning point or one of its parents
+        let sync = HeaderSync::new(SIMNET_PARAMS);
+        let mut invalid = proof.clone();
+        invalid[0] = vec![
+            genesis.clone(),
+            a.clone(),
+            b.clone(),
+            header(&[&b], 4),
+            header(&[&a], 5),
+        ];
+        assert!(matches!(
+            sync.apply_pruning_point_proof(&invalid),
+            Err(SpvError::Proof(
+                PruningImportError::PruningProofMissesBlocksBelowPruningPoint(_, 0)
+            ))
+        ));
+
+        // Headers must follow a parent of the same level
+        let mut invalid = proof.clone();
+        invalid[0].swap(1, 2);
+        assert!(matches!(
+            sync.apply_pruning_point_proof(&invalid),
+            Err(SpvError::Proof(
+                PruningImportError::PruningProofHeaderWithNoKnownParents(_, 0)
+            ))
+        ));
+
+        let mut invalid = proof.clone();
+        invalid.pop();
+        assert!(matches!(
+            sync.apply_pruning_point_proof(&invalid),
+            Err(SpvError::Proof(PruningImportError::ProofNotEnoughLevels(_)))
+        ));
+
+        let mut invalid = proof.clone();
+        invalid[0].push(a.clone());
+        assert!(matches!(
+            sync.apply_pruning_point_proof(&invalid),
+            Err(SpvError::Proof(
+                PruningImportError::PruningProofDuplicateHeaderAtLevel(_, 0)
+            ))
+        ));
+    }
+}
@@ -0,0 +1,169 @@
+use crate::error::{SpvError, SpvResult};
+use karlsen_consensus_core::{
+    config::params::Params,
+    errors::block::RuleError,
+    header::Header,
+    header_validation::{
+        check_block_timestamp_in_isolation, check_header_version, check_parents_limit,
+        check_parents_not_origin,
+    },
+    BlockLevel,
+};
+use karlsen_hashes::Hash;
+use karlsen_math::{Uint256, Uint320};
+use karlsen_pow::{calc_work, check_pow_and_calc_block_level};
+use std::{cmp::max, sync::Arc};
+
+/// Stateless header validation rules shared by the header sync and the pruning proof validation
+#[derive(Clone)]
+pub struct HeaderValidator {
+    params: Params,
+}
+
+impl HeaderValidator {
+    pub fn new(params: Params) -> Self {
+        Self { params }
+    }
+
+    pub fn params(&self) -> &Params {
+        &self.params
+    }
+
+    pub fn genesis_hash(&self) -> Hash {
+        self.params.genesis.hash
+    }
+
+    /// Validates the header in isolation including the pow check against the header declared bits.
+    /// Returns the block level as computed from the pow
+    pub fn validate_header_in_isolation(&self, header: &Header) -> SpvResult<BlockLevel> {
+        check_header_version(header, self.params.hf_daa_score)?;
+        check_block_timestamp_in_isolation(
+            header,
+            self.params.timestamp_deviation_tolerance(header.daa_score),
+        )?;
+        check_parents_limit(header, self.params.max_block_parents)?;
+        check_parents_not_origin(header)?;
+        self.check_target(header)?;
+        Ok(check_pow_and_calc_block_level(
+            header,
+            self.params.max_block_level,
+            self.params.skip_proof_of_work,
+        )?)
+    }
+
+    /// Validates the header scores against its selected parent, ie. the parent with the highest blue work
+    pub fn validate_header_against_selected_parent(
+        &self,
+        header: &Header,
+        selected_parent: &Header,
+    ) -> SpvResult<()> {
+        // The block mergeset has at least the selected parent and at most `mergeset_size_limit`
+        // blocks, of which at most `k + 1` are blue
+        let min_blue_score = selected_parent.blue_score + 1;
+        let max_blue_score = selected_parent.blue_score + self.params.ghostdag_k as u64 + 1;
+        if header.blue_score < min_blue_score || header.blue_score > max_blue_score {
+            return Err(SpvError::BlueScoreOutOfRange(
+                header.hash,
+                header.blue_score,
+                min_blue_score,
+                max_blue_score,
+            ));
+        }
+
+        // The blues of the mergeset are the selected parent and `blue_score - 1` other blocks,
+        // which are mined within the DAA window of the header and so with at most its own work
+        let min_blue_work = selected_parent.blue_work + calc_work(selected_parent.bits);
+        let max_blue_work = min_blue_work
+            + calc_work(header.bits) * (header.blue_score - selected_parent.blue_score - 1);
+        if header.blue_work < min_blue_work || header.blue_work > max_blue_work {
+            return Err(SpvError::BlueWorkOutOfRange(
+                header.hash,
+                header.blue_work,
+                min_blue_work,
+                max_blue_work,
+            ));
+        }
+
+        let min_daa_score = selected_parent.daa_score + 1;
+        let max_daa_score = selected_parent.daa_score + self.params.mergeset_size_limit;
+        if header.daa_score < min_daa_score || header.daa_score > max_daa_score {
+            return Err(SpvError::DaaScoreOutOfRange(
+                header.hash,
+                header.daa_score,
+                min_daa_score,
+                max_daa_score,
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Checks the header difficulty against the one computed by the consensus from its
+    /// difficulty `window`
+    pub fn validate_difficulty(
+        &self,
+        header: &Header,
+        selected_parent: &Header,
+        window: &[Arc<Header>],
+    ) -> SpvResult<()> {
+        let hf_window_end =
+            self.params.hf_daa_score + self.params.legacy_difficulty_window_size as u64;
+        let expected_bits =
+            if (self.params.hf_daa_score..=hf_window_end).contains(&header.daa_score) {
+                // The difficulty is reset to the genesis one for a window after the hard fork
+                self.params.genesis.bits
+            } else {
+                self.calc_difficulty_bits(selected_parent.daa_score, window)
+            };
+        if header.bits != expected_bits {
+            return Err(RuleError::UnexpectedDifficulty(header.bits, expected_bits).into());
+        }
+        Ok(())
+    }
+
+    /// Returns the parents of `header` at `level`, the genesis standing for the levels above the header ones
+    pub fn parents_at_level<'a>(&'a self, header: &'a Header, level: BlockLevel) -> &'a [Hash] {
+        header.parents_at_level(level, &self.params.genesis.hash)
+    }
+
+    /// Computes the difficulty bits of a window like the consensus difficulty managers
+    fn calc_difficulty_bits(&self, selected_parent_daa_score: u64, window: &[Arc<Header>]) -> u32 {
+        // Until there are enough blocks for a valid calculation the difficulty remains constant
+        if window.len() < self.params.min_difficulty_window_len {
+            return self.params.genesis.bits;
+        }
+        let (min_index, min_block) = window
+            .iter()
+            .enumerate()
+            .min_by_key(|(_, x)| (x.timestamp, x.blue_work, x.hash))
+            .unwrap();
+        let max_ts = window.iter().map(|x| x.timestamp).max().unwrap();
+
+        // The block of minimal timestamp is excluded to average the target of the internal window
+        let targets_count = window.len() as u64 - 1;
+        let targets_sum: Uint320 = window
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| *i != min_index)
+            .map(|(_, x)| Uint320::from(Uint256::from_compact_target_bits(x.bits)))
+            .sum();
+        let average_target = targets_sum / targets_count;
+        let measured_duration = max(max_ts - min_block.timestamp, 1);
+        let expected_duration = self.params.target_time_per_block(selected_parent_daa_score)
+            * self
+                .params
+                .difficulty_sample_rate(selected_parent_daa_score)
+            * targets_count;
+        let new_target = average_target * measured_duration / expected_duration;
+        Uint256::try_from(new_target.min(self.params.max_difficulty_target.into()))
+            .expect("max target < Uint256::MAX")
+            .compact_target_bits()
+    }
+
+    fn check_target(&self, header: &Header) -> SpvResult<()> {
+        if Uint256::from_compact_target_bits(header.bits) > self.params.max_difficulty_target {
+            return Err(SpvError::TargetAboveMax(header.hash, header.bits));
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,246 @@
+use crate::{error::SpvResult, store::HeaderStore};
+use karlsen_consensus_core::{
+    config::params::Params, errors::block::RuleError, header::Header, BlueWorkType,
+};
+use karlsen_hashes::Hash;
+use std::{
+    cmp::{Ordering, Reverse},
+    collections::{BinaryHeap, HashSet},
+    iter::once,
+    sync::Arc,
+};
+
+/// A header ordered like the consensus `SortableBlock`, ie. by blue work then by hash
+#[derive(Clone)]
+struct SortableHeader(Arc<Header>);
+
+impl SortableHeader {
+    fn key(&self) -> (BlueWorkType, Hash) {
+        (self.0.blue_work, self.0.hash)
+    }
+}
+
+impl PartialEq for SortableHeader {
+    fn eq(&self, other: &Self) -> bool {
+        self.key() == other.key()
+    }
+}
+
+impl Eq for SortableHeader {}
+
+impl PartialOrd for SortableHeader {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for SortableHeader {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.key().cmp(&other.key())
+    }
+}
+
+/// Explores the past of a block in descending blue work order, as far as needed to tell
+/// whether a block belongs to it.
+///
+/// The blue work of a block being strictly higher than the one of its parents, a block is known
+/// to be out of the past of the root once all the past blocks of higher blue work are expanded.
+struct PastExplorer<'a> {
+    store: &'a HeaderStore,
+    frontier: BinaryHeap<SortableHeader>,
+    visited: HashSet<Hash>,
+}
+
+impl<'a> PastExplorer<'a> {
+    fn new(store: &'a HeaderStore, root: &Header) -> Option<Self> {
+        let mut explorer = Self {
+            store,
+            frontier: BinaryHeap::new(),
+            visited: HashSet::new(),
+        };
+        explorer.expand(root)?;
+        Some(explorer)
+    }
+
+    /// Returns whether `block` is in the past of the root, `None` if some of this past is unknown
+    fn contains(&mut self, block: &Header) -> Option<bool> {
+        while let Some(top) = self.frontier.peek() {
+            if top.0.blue_work <= block.blue_work {
+                break;
+            }
+            let top = self.frontier.pop().unwrap();
+            self.expand(&top.0)?;
+        }
+        Some(self.visited.contains(&block.hash))
+    }
+
+    fn expand(&mut self, block: &Header) -> Option<()> {
+        for parent in block.direct_parents() {
+            if self.visited.insert(*parent) {
+                self.frontier
+                    .push(SortableHeader(self.store.get(parent)?.clone()));
+            }
+        }
+        Some(())
+    }
+}
+
+/// Keeps the `size` blocks of highest blue work pushed into it
+struct BoundedWindow {
+    heap: BinaryHeap<Reverse<SortableHeader>>,
+    size: usize,
+}
+
+impl BoundedWindow {
+    fn new(size: usize) -> Self {
+        Self {
+            heap: BinaryHeap::with_capacity(size),
+            size,
+        }
+    }
+
+    fn is_full(&self) -> bool {
+        self.heap.len() == self.size
+    }
+
+    fn can_push(&self, block: &SortableHeader) -> bool {
+        !self.is_full() || self.heap.peek().is_some_and(|min| *block > min.0)
+    }
+
+    fn try_push(&mut self, block: SortableHeader) -> bool {
+        if !self.can_push(&block) {
+            return false;
+        }
+        if self.is_full() {
+            self.heap.pop();
+        }
+        self.heap.push(Reverse(block));
+        true
+    }
+
+    fn into_headers(self) -> Vec<Arc<Header>> {
+        self.heap.into_iter().map(|Reverse(x)| x.0).collect()
+    }
+}
+
+/// Returns the selected parent of a known block, or `None` if some of its parents are unknown
+fn find_selected_parent(store: &HeaderStore, block: &Header) -> Option<Arc<Header>> {
+    let mut selected_parent: Option<&Arc<Header>> = None;
+    for parent in block.direct_parents() {
+        let parent = store.get(parent)?;
+        if selected_parent.map_or(true, |sp| HeaderStore::is_better(parent, sp)) {
+            selected_parent = Some(parent);
+        }
+    }
+    selected_parent.cloned()
+}
+
+/// Returns the mergeset of a block with `parents`, without its selected parent and sorted by
+/// descending blue work, or `None` if some of the blocks involved are unknown
+fn mergeset_without_selected_parent(
+    store: &HeaderStore,
+    params: &Params,
+    parents: &[Hash],
+    selected_parent: &Header,
+) -> SpvResult<Option<Vec<Arc<Header>>>> {
+    let Some(mut past) = PastExplorer::new(store, selected_parent) else {
+        return Ok(None);
+    };
+    let mut mergeset = Vec::new();
+    let mut seen = HashSet::new();
+    let mut queue: Vec<Hash> = parents.to_vec();
+    while let Some(current) = queue.pop() {
+        if current == selected_parent.hash || !seen.insert(current) {
+            continue;
+        }
+        let Some(block) = store.get(&current) else {
+            return Ok(None);
+        };
+        match past.contains(block) {
+            Some(true) => continue,
+            Some(false) => {}
+            None => return Ok(None),
+        }
+        // The mergeset size includes the selected parent
+        let mergeset_size = mergeset.len() as u64 + 2;
+        if mergeset_size > params.mergeset_size_limit {
+            return Err(
+                RuleError::MergeSetTooBig(mergeset_size, params.mergeset_size_limit).into(),
+            );
+        }
+        queue.extend(block.direct_parents().iter().copied());
+        mergeset.push(block.clone());
+    }
+    mergeset.sort_by_cached_key(|x| Reverse((x.blue_work, x.hash)));
+    Ok(Some(mergeset))
+}
+
+/// Recomputes the difficulty window of `header` from the known headers, following the sampled
+/// and full window managers of the consensus.
+///
+/// Returns `None` if the window reaches unknown headers, ie. the headers below the pruning point
+/// the sync was bootstrapped from.
+pub(crate) fn difficulty_window(
+    store: &HeaderStore,
+    params: &Params,
+    header: &Header,
+    selected_parent: &Arc<Header>,
+) -> SpvResult<Option<Vec<Arc<Header>>>> {
+    let window_size = params.difficulty_window_size(selected_parent.daa_score);
+    let sample_rate = params.difficulty_sample_rate(selected_parent.daa_score);
+    let sampling = selected_parent.daa_score >= params.sampling_activation_daa_score;
+    let mut window = BoundedWindow::new(window_size);
+    if window_size == 0 {
+        return Ok(Some(vec![]));
+    }
+
+    // Walks down the selected chain, pushing the mergeset of every chain block
+    let mut parents = header.direct_parents().to_vec();
+    let mut blue_score = header.blue_score;
+    let mut current_selected_parent = selected_parent.clone();
+    loop {
+        // The genesis does not enter the window due to having a fixed timestamp
+        if current_selected_parent.hash == params.genesis.hash {
+            break;
+        }
+        // The past of the selected parent has an even lower blue work
+        if !window.can_push(&SortableHeader(current_selected_parent.clone())) {
+            break;
+        }
+        let Some(mergeset) =
+            mergeset_without_selected_parent(store, params, &parents, &current_selected_parent)?
+        else {
+            return Ok(None);
+        };
+        let blocks = once(current_selected_parent.clone()).chain(mergeset);
+        if sampling {
+            // Blocks below the DAA window neither count in the DAA score nor get sampled
+            let lowest_daa_blue_score = blue_score.saturating_sub(window_size as u64 * sample_rate);
+            let mut index = 0;
+            for block in blocks.filter(|x| x.blue_score >= lowest_daa_blue_score) {
+                index += 1;
+                if (current_selected_parent.daa_score + index) % sample_rate == 0 {
+                    window.try_push(SortableHeader(block));
+                }
+            }
+        } else {
+            for block in blocks {
+                if !window.try_push(SortableHeader(block)) {
+                    break;
+                }
+            }
+        }
+
+        match find_selected_parent(store, &current_selected_parent) {
+            Some(next) => {
+                parents = current_selected_parent.direct_parents().to_vec();
+                blue_score = current_selected_parent.blue_score;
+                current_selected_parent = next;
+            }
+            // A window filled before reaching the unknown headers is complete
+            None if window.is_full() => break,
+            None => return Ok(None),
+        }
+    }
+    Ok(Some(window.into_headers()))
+}
@@ -20,7 +20,7 @@ use karlsen_utils::mem_size::MemSizeEstimator;
 use parking_lot::RwLock;
 use rocksdb::WriteBatch;
 use serde::{Deserialize, Serialize};
-use std::{collections::HashMap, error::Error, fs, path::PathBuf, sync::Arc};
+use std::{collections::HashMap, error::Error, fs, iter::once, path::PathBuf, sync::Arc};
 
 #[derive(Serialize, Deserialize, Clone)]
 pub struct ConsensusEntry {
@@ -69,6 +69,10 @@ pub struct MultiConsensusMetadata {
 }
 
 const LATEST_DB_VERSION: u32 = 3;
+
+/// Key of the property marking that the data migrated to the cold DB is missing from the main DB
+const COLD_DB_REQUIRED_KEY: &[u8] = b"cold-db-required";
+
 impl Default for MultiConsensusMetadata {
     fn default() -> Self {
         Self {
@@ -251,6 +255,25 @@ impl MultiConsensusManagementStore {
         }
     }
 
+    /// Whether the consensus data was set up with a cold DB, which then holds part of the data
+    pub fn is_cold_db_required(&self) -> StoreResult<bool> {
+        match self.metadata.read() {
+            Ok(data) => Ok(data.props.contains_key(COLD_DB_REQUIRED_KEY)),
+            Err(StoreError::KeyNotFound(_)) => Ok(false),
+            Err(err) => Err(err),
+        }
+    }
+
+    pub fn set_cold_db_required(&mut self) {
+        let mut metadata = self.metadata.read().unwrap();
+        if !metadata.props.contains_key(COLD_DB_REQUIRED_KEY) {
+            metadata.props.insert(COLD_DB_REQUIRED_KEY.to_vec(), vec![]);
+            self.metadata
+                .write(DirectDbWriter::new(&self.db), &metadata)
+                .unwrap();
+        }
+    }
+
     pub fn should_upgrade(&self) -> StoreResult<bool> {
         match self.metadata.read() {
             Ok(data) => Ok(data.version != LATEST_DB_VERSION),
@@ -264,6 +287,8 @@ pub struct Factory {
     management_store: Arc<RwLock<MultiConsensusManagementStore>>,
     config: Config,
     db_root_dir: PathBuf,
+    /// Root directory of the cold DBs receiving the ancient block bodies and acceptance data, if any
+    cold_db_root_dir: Option<PathBuf>,
     db_parallelism: usize,
     notification_root: Arc<ConsensusNotificationRoot>,
     counters: Arc<ProcessingCounters>,
@@ -276,6 +301,7 @@ impl Factory {
         management_db: Arc<DB>,
         config: &Config,
         db_root_dir: PathBuf,
+        cold_db_root_dir: Option<PathBuf>,
         db_parallelism: usize,
         notification_root: Arc<ConsensusNotificationRoot>,
         counters: Arc<ProcessingCounters>,
@@ -293,10 +319,14 @@ impl Factory {
         management_store
             .write()
             .set_is_archival_node(config.is_archival);
+        if cold_db_root_dir.is_some() {
+            management_store.write().set_cold_db_required();
+        }
         let factory = Self {
             management_store,
             config,
             db_root_dir,
+            cold_db_root_dir,
             db_parallelism,
             notification_root,
             counters,
@@ -306,6 +336,46 @@ impl Factory {
         factory.delete_inactive_consensus_entries();
         factory
     }
+
+    /// Opens the DB of the consensus stored in `directory_name`, along with its cold DB if configured
+    fn open_consensus_dbs(&self, directory_name: &str) -> (Arc<DB>, Option<Arc<DB>>) {
+        // Active and staging consensuses should have equal budgets, a quarter of which goes to the cold DB if any
+        let files_limit = self.fd_budget / 2;
+        let cold_files_limit = match self.cold_db_root_dir {
+            Some(_) => files_limit / 4,
+            None => 0,
+        };
+        let db = karlsen_database::prelude::ConnBuilder::default()
+            .with_db_path(self.db_root_dir.join(directory_name))
+            .with_parallelism(self.db_parallelism)
+            .with_files_limit(files_limit - cold_files_limit)
+            .build()
+            .unwrap();
+        let cold_db = self.cold_db_root_dir.as_ref().map(|cold_db_root_dir| {
+            karlsen_database::prelude::ConnBuilder::default()
+                .with_db_path(cold_db_root_dir.join(directory_name))
+                .with_parallelism(self.db_parallelism)
+                .with_files_limit(cold_files_limit)
+                .build()
+                .unwrap()
+        });
+        (db, cold_db)
+    }
+
+    /// Deletes the directories of the consensus stored in `directory_name`, returning the first error
+    fn delete_consensus_dirs(&self, directory_name: &str) -> std::io::Result<()> {
+        let dirs = once(self.db_root_dir.join(directory_name)).chain(
+            self.cold_db_root_dir
+                .as_ref()
+                .map(|cold_db_root_dir| cold_db_root_dir.join(directory_name)),
+        );
+        for dir in dirs {
+            if dir.exists() {
+                fs::remove_dir_all(dir)?;
+            }
+        }
+        Ok(())
+    }
 }
 
 impl ConsensusFactory for Factory {
@@ -332,17 +402,12 @@ impl ConsensusFactory for Factory {
             }
         };
 
-        let dir = self.db_root_dir.join(entry.directory_name.clone());
-        let db = karlsen_database::prelude::ConnBuilder::default()
-            .with_db_path(dir)
-            .with_parallelism(self.db_parallelism)
-            .with_files_limit(self.fd_budget / 2) // active and staging consensuses should have equal budgets
-            .build()
-            .unwrap();
+        let (db, cold_db) = self.open_consensus_dbs(&entry.directory_name);
 
         let session_lock = SessionLock::new();
         let consensus = Arc::new(Consensus::new(
             db.clone(),
+            cold_db,
             Arc::new(config),
             session_lock.clone(),
             self.notification_root.clone(),
@@ -380,17 +445,12 @@ impl ConsensusFactory for Factory {
             .write()
             .new_staging_consensus_entry()
             .unwrap();
-        let dir = self.db_root_dir.join(entry.directory_name);
-        let db = karlsen_database::prelude::ConnBuilder::default()
-            .with_db_path(dir)
-            .with_parallelism(self.db_parallelism)
-            .with_files_limit(self.fd_budget / 2) // active and staging consensuses should have equal budgets
-            .build()
-            .unwrap();
+        let (db, cold_db) = self.open_consensus_dbs(&entry.directory_name);
 
         let session_lock = SessionLock::new();
         let consensus = Arc::new(Consensus::new(
             db.clone(),
+            cold_db,
             Arc::new(self.config.to_builder().skip_adding_genesis().build()),
             session_lock.clone(),
             self.notification_root.clone(),
@@ -423,17 +483,12 @@ impl ConsensusFactory for Factory {
             .iterate_inactive_entries()
             .filter_map(|entry_result| {
                 let entry = entry_result.unwrap();
-                let dir = self.db_root_dir.join(entry.directory_name.clone());
-                if dir.exists() {
-                    match fs::remove_dir_all(dir) {
-                        Ok(_) => Some(entry),
-                        Err(e) => {
-                            warn!("Error deleting consensus entry {}: {}", entry.key, e);
-                            None
-                        }
+                match self.delete_consensus_dirs(&entry.directory_name) {
+                    Ok(_) => Some(entry),
+                    Err(e) => {
+                        warn!("Error deleting consensus entry {}: {}", entry.key, e);
+                        None
                     }
-                } else {
-                    Some(entry)
                 }
             })
             .collect_vec();
@@ -446,8 +501,7 @@ impl ConsensusFactory for Factory {
     fn delete_staging_entry(&self) {
         let mut write_guard = self.management_store.write();
         if let Some(entry) = write_guard.staging_consensus_entry() {
-            let dir = self.db_root_dir.join(entry.directory_name.clone());
-            match fs::remove_dir_all(dir) {
+            match self.delete_consensus_dirs(&entry.directory_name) {
                 Ok(_) => {
                     write_guard.delete_entry(entry).unwrap();
                 }
@@ -1,6 +1,7 @@
 pub mod cache_policy_builder;
 pub mod ctl;
 pub mod factory;
+mod read_view;
 pub mod services;
 pub mod storage;
 pub mod test_consensus;
@@ -24,14 +25,17 @@ use crate::{
             relations::RelationsStoreReader,
             statuses::StatusesStoreReader,
             tips::TipsStoreReader,
+            utxo_diffs::UtxoDiffsStoreReader,
             utxo_set::{UtxoSetStore, UtxoSetStoreReader},
             DB,
         },
     },
     pipeline::{
         body_processor::BlockBodyProcessor,
+        cold_storage_processor::processor::{ColdStorageProcessingMessage, ColdStorageProcessor},
         deps_manager::{
-            BlockProcessingMessage, BlockResultSender, BlockTask, VirtualStateProcessingMessage,
+            BlockProcessingMessage, BlockResultSender, BlockTask, PowPermitSlot,
+            VirtualStateProcessingMessage,
         },
         header_processor::HeaderProcessor,
         pruning_processor::processor::{PruningProcessingMessage, PruningProcessor},
@@ -42,7 +46,10 @@ use crate::{
 };
 use karlsen_consensus_core::{
     acceptance_data::AcceptanceData,
-    api::{stats::BlockCount, BlockValidationFutures, ConsensusApi, ConsensusStats},
+    api::{
+        stats::BlockCount, BlockValidationFutures, ConsensusApi, ConsensusReadView, ConsensusStats,
+        PowPermit,
+    },
     block::{
         Block, BlockTemplate, TemplateBuildMode, TemplateTransactionSelector, VirtualStateApproxId,
     },
@@ -50,6 +57,8 @@ use karlsen_consensus_core::{
     blockstatus::BlockStatus,
     coinbase::MinerData,
     daa_score_timestamp::DaaScoreTimestamp,
+    dag_slice::{DagSlice, DagSliceBlock},
+    difficulty::{BlockDifficulty, DifficultyInfo, DifficultyWindowSummary},
     errors::{
         coinbase::CoinbaseResult,
         consensus::{ConsensusError, ConsensusResult},
@@ -61,7 +70,8 @@ use karlsen_consensus_core::{
     network::NetworkType,
     pruning::{PruningPointProof, PruningPointTrustedData, PruningPointsList},
     trusted::{ExternalGhostdagData, TrustedBlock},
-    tx::{MutableTransaction, Transaction, TransactionOutpoint, UtxoEntry},
+    tx::{MutableTransaction, ScriptPublicKeys, Transaction, TransactionOutpoint, UtxoEntry},
+    utxo::{balance_rollback::BalanceRollback, utxo_diff::UtxoDiff},
     BlockHashSet, BlueWorkType, ChainPath,
 };
 use karlsen_consensus_notify::root::ConsensusNotificationRoot;
@@ -108,6 +118,7 @@ pub struct Consensus {
     pub(super) body_processor: Arc<BlockBodyProcessor>,
     pub(super) virtual_processor: Arc<VirtualStateProcessor>,
     pub(super) pruning_processor: Arc<PruningProcessor>,
+    pub(super) cold_storage_processor: Option<Arc<ColdStorageProcessor>>,
 
     // Storage
     pub(super) storage: Arc<ConsensusStorage>,
@@ -145,6 +156,7 @@ impl Deref for Consensus {
 impl Consensus {
     pub fn new(
         db: Arc<DB>,
+        cold_db: Option<Arc<DB>>,
         config: Arc<Config>,
         pruning_lock: SessionLock,
         notification_root: Arc<ConsensusNotificationRoot>,
@@ -160,7 +172,7 @@ impl Consensus {
         // Storage layer
         //
 
-        let storage = ConsensusStorage::new(db.clone(), config.clone());
+        let storage = ConsensusStorage::new(db.clone(), cold_db.clone(), config.clone());
 
         //
         // Services and managers
@@ -194,6 +206,10 @@ impl Consensus {
             CrossbeamSender<PruningProcessingMessage>,
             CrossbeamReceiver<PruningProcessingMessage>,
         ) = bounded_crossbeam(2);
+        let (cold_storage_sender, cold_storage_receiver): (
+            CrossbeamSender<ColdStorageProcessingMessage>,
+            CrossbeamReceiver<ColdStorageProcessingMessage>,
+        ) = bounded_crossbeam(2);
 
         //
         // Thread-pools
@@ -217,6 +233,15 @@ impl Consensus {
                 .build()
                 .unwrap(),
         );
+        // Proof of work verification is CPU heavy, so it runs on a dedicated pool of bounded size. Bursts of headers
+        // then never occupy more cores than this pool has, leaving the others to the async runtime
+        let pow_pool = Arc::new(
+            rayon::ThreadPoolBuilder::new()
+                .num_threads(perf_params.pow_verification_threads())
+                .thread_name(|i| format!("pow-pool-{i}"))
+                .build()
+                .unwrap(),
+        );
 
         //
         // Pipeline processors
@@ -226,6 +251,7 @@ impl Consensus {
             receiver,
             body_sender,
             block_processors_pool.clone(),
+            pow_pool,
             params,
             db.clone(),
             &storage,
@@ -263,6 +289,7 @@ impl Consensus {
             pruning_receiver.clone(),
             virtual_pool,
             params,
+            config.assume_valid,
             db.clone(),
             &storage,
             &services,
@@ -273,6 +300,7 @@ impl Consensus {
 
         let pruning_processor = Arc::new(PruningProcessor::new(
             pruning_receiver,
+            cold_db.is_some().then_some(cold_storage_sender),
             db.clone(),
             &storage,
             &services,
@@ -281,6 +309,18 @@ impl Consensus {
             is_consensus_exiting.clone(),
         ));
 
+        let cold_storage_processor = cold_db.map(|cold_db| {
+            Arc::new(ColdStorageProcessor::new(
+                cold_storage_receiver,
+                db.clone(),
+                cold_db,
+                &storage,
+                pruning_lock.clone(),
+                &config,
+                is_consensus_exiting.clone(),
+            ))
+        });
+
         // Ensure the relations stores are initialized
         header_processor.init();
         // Ensure that some pruning point is registered
@@ -300,6 +340,7 @@ impl Consensus {
             body_processor,
             virtual_processor,
             pruning_processor,
+            cold_storage_processor,
             storage,
             services,
             pruning_lock,
@@ -318,7 +359,7 @@ impl Consensus {
         let virtual_processor = self.virtual_processor.clone();
         let pruning_processor = self.pruning_processor.clone();
 
-        vec![
+        let mut handles = vec![
             thread::Builder::new()
                 .name("header-processor".to_string())
                 .spawn(move || header_processor.worker())
@@ -335,7 +376,16 @@ impl Consensus {
                 .name("pruning-processor".to_string())
                 .spawn(move || pruning_processor.worker())
                 .unwrap(),
-        ]
+        ];
+        if let Some(cold_storage_processor) = self.cold_storage_processor.clone() {
+            handles.push(
+                thread::Builder::new()
+                    .name("cold-storage-processor".to_string())
+                    .spawn(move || cold_storage_processor.worker())
+                    .unwrap(),
+            );
+        }
+        handles
     }
 
     /// Acquires a consensus session, blocking data-pruning from occurring until released
@@ -459,7 +509,26 @@ impl ConsensusApi for Consensus {
 
     fn validate_and_insert_block(&self, block: Block) -> BlockValidationFutures {
         let (block_task, virtual_state_task) =
-            self.validate_and_insert_block_impl(BlockTask::Ordinary { block });
+            self.validate_and_insert_block_impl(BlockTask::Ordinary {
+                block,
+                pow_permit: Default::default(),
+            });
+        BlockValidationFutures {
+            block_task: Box::pin(block_task),
+            virtual_state_task: Box::pin(virtual_state_task),
+        }
+    }
+
+    fn validate_and_insert_block_with_pow_permit(
+        &self,
+        block: Block,
+        pow_permit: PowPermit,
+    ) -> BlockValidationFutures {
+        let (block_task, virtual_state_task) =
+            self.validate_and_insert_block_impl(BlockTask::Ordinary {
+                block,
+                pow_permit: PowPermitSlot::new(pow_permit),
+            });
         BlockValidationFutures {
             block_task: Box::pin(block_task),
             virtual_state_task: Box::pin(virtual_state_task),
@@ -630,6 +699,10 @@ impl ConsensusApi for Consensus {
             .calculate_chain_path(hash, self.get_sink()))
     }
 
+    fn read_view(&self) -> Box<dyn ConsensusReadView + '_> {
+        Box::new(read_view::ReadView::new(self))
+    }
+
     /// Returns a Vec of header samples since genesis
     /// ordered by ascending daa_score, first entry is genesis
     fn get_chain_block_samples(&self) -> Vec<DaaScoreTimestamp> {
@@ -784,6 +857,23 @@ impl ConsensusApi for Consensus {
         iter.map(|item| item.unwrap()).collect()
     }
 
+    fn get_virtual_utxo_entries(
+        &self,
+        outpoints: &[TransactionOutpoint],
+    ) -> Vec<Option<UtxoEntry>> {
+        let virtual_stores = self.virtual_stores.read();
+        outpoints
+            .iter()
+            .map(|outpoint| {
+                virtual_stores
+                    .utxo_set
+                    .get(outpoint)
+                    .unwrap_option()
+                    .map(|entry| UtxoEntry::clone(&entry))
+            })
+            .collect()
+    }
+
     fn get_tips(&self) -> Vec<Hash> {
         self.body_tips_store
             .read()
@@ -1131,6 +1221,13 @@ impl ConsensusApi for Consensus {
             .collect::<ConsensusResult<Vec<_>>>()
     }
 
+    fn get_block_utxo_diff(&self, hash: Hash) -> ConsensusResult<Arc<UtxoDiff>> {
+        self.utxo_diffs_store
+            .get(hash)
+            .unwrap_option()
+            .ok_or(ConsensusError::MissingData(hash))
+    }
+
     fn is_chain_block(&self, hash: Hash) -> ConsensusResult<bool> {
         self.is_chain_ancestor_of(hash, self.get_sink())
     }
@@ -1236,6 +1333,236 @@ impl ConsensusApi for Consensus {
         }
     }
 
+    fn get_balance_rollback(
+        &self,
+        daa_score: u64,
+        script_public_keys: ScriptPublicKeys,
+        max_chain_blocks: usize,
+    ) -> ConsensusResult<BalanceRollback> {
+        // Keeps the UTXO diffs of the chain from being pruned while walking it
+        let _guard = self.pruning_lock.blocking_read();
+        let virtual_state = self.lkg_virtual_state.load();
+        let mut rollback = BalanceRollback::new(virtual_state.parents.iter().copied().collect());
+        rollback.add_diff(&virtual_state.utxo_diff, &script_public_keys);
+
+        // Walk down the selected chain from the sink, rolling back the diffs of the chain blocks
+        // above the requested DAA score
+        let mut hash = virtual_state.ghostdag_data.selected_parent;
+        for _ in 0..=max_chain_blocks {
+            let header = self
+                .headers_store
+                .get_compact_header_data(hash)
+                .unwrap_option()
+                .ok_or(ConsensusError::UtxoHistoryPruned(daa_score))?;
+            if header.daa_score <= daa_score {
+                rollback.chain_block = hash;
+                rollback.daa_score = header.daa_score;
+                return Ok(rollback);
+            }
+            if hash == self.config.genesis.hash {
+                return Err(ConsensusError::UtxoHistoryPruned(daa_score));
+            }
+            let utxo_diff = self
+                .utxo_diffs_store
+                .get(hash)
+                .unwrap_option()
+                .ok_or(ConsensusError::UtxoHistoryPruned(daa_score))?;
+            rollback.add_diff(&utxo_diff, &script_public_keys);
+            hash = self
+                .ghostdag_primary_store
+                .get_selected_parent(hash)
+                .unwrap_option()
+                .ok_or(ConsensusError::UtxoHistoryPruned(daa_score))?;
+        }
+        Err(ConsensusError::UtxoHistoryTooDeep(
+            daa_score,
+            max_chain_blocks,
+        ))
+    }
+
+    fn get_difficulty_info(&self, chain_block_count: usize) -> ConsensusResult<DifficultyInfo> {
+        let _guard = self.pruning_lock.blocking_read();
+        let virtual_state = self.lkg_virtual_state.load();
+        let ghostdag_data = &virtual_state.ghostdag_data;
+
+        let daa_window = match self.services.window_manager.block_daa_window(ghostdag_data) {
+            Ok(w) => w,
+            Err(RuleError::InsufficientDaaWindowSize(s)) => {
+                return Err(DifficultyError::InsufficientWindowData(s).into())
+            }
+            Err(e) => panic!("unexpected error: {e}"),
+        };
+        let sample_rate = self
+            .services
+            .window_manager
+            .sample_rate(ghostdag_data, WindowType::SampledDifficultyWindow);
+        let window = DifficultyWindowSummary::new(
+            sample_rate,
+            daa_window.window.iter().map(|block| {
+                let header = self
+                    .headers_store
+                    .get_compact_header_data(block.0.hash)
+                    .unwrap();
+                (header.timestamp, header.bits)
+            }),
+        );
+
+        // Walk down the selected chain from the sink, stopping at the first block with missing data
+        // (ie. below the pruning point)
+        let mut chain_blocks = Vec::with_capacity(chain_block_count);
+        let mut hash = ghostdag_data.selected_parent;
+        while chain_blocks.len() < chain_block_count && !hash.is_origin() {
+            let Some(header) = self
+                .headers_store
+                .get_compact_header_data(hash)
+                .unwrap_option()
+            else {
+                break;
+            };
+            chain_blocks.push(BlockDifficulty {
+                hash,
+                daa_score: header.daa_score,
+                timestamp: header.timestamp,
+                bits: header.bits,
+            });
+            if hash == self.config.genesis.hash {
+                break;
+            }
+            match self
+                .ghostdag_primary_store
+                .get_selected_parent(hash)
+                .unwrap_option()
+            {
+                Some(selected_parent) => hash = selected_parent,
+                None => break,
+            }
+        }
+
+        Ok(DifficultyInfo {
+            daa_score: virtual_state.daa_score,
+            next_bits: virtual_state.bits,
+            window,
+            chain_blocks,
+        })
+    }
+
+    fn get_dag_slice(&self, from_daa_score: u64, count: usize) -> ConsensusResult<DagSlice> {
+        let _guard = self.pruning_lock.blocking_read();
+        let virtual_state = self.lkg_virtual_state.load();
+        let pruning_point = self.pruning_point();
+
+        // Every block is merged by a single selected chain block, or by the virtual block only.
+        // Locate the first chain block reaching the requested DAA score, then take the chain
+        // blocks following it. Every mergeset holds at least the selected parent, so `count`
+        // chain blocks are enough.
+        let (chain_hashes, reaches_tip) = {
+            let sc_read = self.storage.selected_chain_store.read();
+            let (tip_index, _) = sc_read.get_tip().unwrap();
+            let low_index = sc_read
+                .get_by_hash(pruning_point)
+                .unwrap_option()
+                .unwrap_or(tip_index);
+            // Chain DAA scores strictly increase along the chain
+            let (mut low, mut high) = (low_index, tip_index + 1);
+            while low < high {
+                let mid = low + (high - low) / 2;
+                let hash = sc_read.get_by_index(mid).unwrap();
+                if self.headers_store.get_daa_score(hash).unwrap() < from_daa_score {
+                    low = mid + 1;
+                } else {
+                    high = mid;
+                }
+            }
+            let chain_hashes = (low..=tip_index)
+                .take(count)
+                .map(|index| sc_read.get_by_index(index).unwrap())
+                .collect_vec();
+            let reaches_tip = low + chain_hashes.len() as u64 > tip_index;
+            (chain_hashes, reaches_tip)
+        };
+
+        // Walk the chain forward, collecting whole mergesets until `count` blocks are collected
+        let mut merged = Vec::new();
+        let mut chain = BlockHashSet::new();
+        let mut collect_mergeset = |ghostdag_data: &GhostdagData, merging_block: Option<Hash>| {
+            let blues = ghostdag_data
+                .mergeset_blues
+                .iter()
+                .map(|&hash| (hash, true));
+            let reds = ghostdag_data
+                .mergeset_reds
+                .iter()
+                .map(|&hash| (hash, false));
+            merged.extend(
+                blues
+                    .chain(reds)
+                    .map(|(hash, is_blue)| (hash, is_blue, merging_block)),
+            );
+            // The selected parent of a chain block is the chain block it merges
+            chain.insert(ghostdag_data.selected_parent);
+        };
+        let mut next_daa_score = from_daa_score;
+        let mut walked = 0;
+        for &hash in chain_hashes.iter() {
+            if merged.len() >= count {
+                break;
+            }
+            let Some(ghostdag_data) = self.ghostdag_primary_store.get_data(hash).unwrap_option()
+            else {
+                break;
+            };
+            collect_mergeset(&ghostdag_data, Some(hash));
+            next_daa_score = self.headers_store.get_daa_score(hash).unwrap() + 1;
+            walked += 1;
+        }
+        // Past the sink, the blocks are merged by the virtual block only
+        if reaches_tip
+            && walked == chain_hashes.len()
+            && merged.len() < count
+            && chain_hashes.last().map_or(true, |&sink| {
+                sink == virtual_state.ghostdag_data.selected_parent
+            })
+        {
+            collect_mergeset(&virtual_state.ghostdag_data, None);
+        }
+
+        let mut blocks = merged
+            .into_iter()
+            .filter_map(|(hash, is_blue, merging_block)| {
+                let header = self
+                    .headers_store
+                    .get_compact_header_data(hash)
+                    .unwrap_option()?;
+                Some((hash, header, is_blue, merging_block))
+            })
+            .collect_vec();
+        blocks.sort_by_key(|(hash, header, ..)| (header.daa_score, *hash));
+
+        let blocks = blocks
+            .into_iter()
+            .map(|(hash, header, is_blue, merging_block)| DagSliceBlock {
+                hash,
+                parents: self
+                    .services
+                    .relations_service
+                    .get_parents(hash)
+                    .unwrap_option()
+                    .map(|parents| parents.iter().copied().collect())
+                    .unwrap_or_default(),
+                daa_score: header.daa_score,
+                blue_score: header.blue_score,
+                timestamp: header.timestamp,
+                is_blue,
+                is_chain_block: chain.contains(&hash),
+                merging_block,
+            })
+            .collect();
+        Ok(DagSlice {
+            blocks,
+            next_daa_score,
+        })
+    }
+
     fn are_pruning_points_violating_finality(&self, pp_list: PruningPointsList) -> bool {
         self.virtual_processor
             .are_pruning_points_violating_finality(pp_list)
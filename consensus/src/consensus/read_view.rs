@@ -0,0 +1,107 @@
+use super::Consensus;
+use crate::model::{
+    services::reachability::ReachabilityService, stores::acceptance_data::AcceptanceDataStoreReader,
+};
+use karlsen_consensus_core::{
+    acceptance_data::AcceptanceData,
+    api::ConsensusReadView,
+    errors::consensus::{ConsensusError, ConsensusResult},
+    tx::{TransactionOutpoint, UtxoEntry},
+    ChainPath,
+};
+use karlsen_database::prelude::{DbSnapshot, StoreResultExtensions};
+use karlsen_hashes::Hash;
+use std::sync::Arc;
+
+/// A [`ConsensusReadView`] reading the virtual stores through a DB snapshot.
+///
+/// The virtual state and the virtual UTXO set are committed in a single batch, so the snapshot
+/// sees both before or after a commit. The DAG relations used to derive the selected chain from
+/// the sink and the acceptance data of chain candidates never change once written, so they are
+/// read from the stores as usual.
+pub(super) struct ReadView<'a> {
+    consensus: &'a Consensus,
+    snapshot: DbSnapshot<'a>,
+    sink: Hash,
+}
+
+impl<'a> ReadView<'a> {
+    pub(super) fn new(consensus: &'a Consensus) -> Self {
+        let snapshot = consensus.db.snapshot();
+        // The last known good virtual state is updated before the batch committing it is written,
+        // so the sink is read from the snapshot instead
+        let sink = consensus
+            .virtual_stores
+            .read()
+            .state
+            .get_at(&snapshot)
+            .unwrap()
+            .ghostdag_data
+            .selected_parent;
+        Self {
+            consensus,
+            snapshot,
+            sink,
+        }
+    }
+}
+
+impl ConsensusReadView for ReadView<'_> {
+    fn get_sink(&self) -> Hash {
+        self.sink
+    }
+
+    fn is_chain_block(&self, hash: Hash) -> ConsensusResult<bool> {
+        let _guard = self.consensus.pruning_lock.blocking_read();
+        self.consensus.validate_block_exists(hash)?;
+        Ok(self
+            .consensus
+            .services
+            .reachability_service
+            .is_chain_ancestor_of(hash, self.sink))
+    }
+
+    fn get_virtual_chain_from_block(&self, hash: Hash) -> ConsensusResult<ChainPath> {
+        let _guard = self.consensus.pruning_lock.blocking_read();
+        self.consensus.validate_block_exists(hash)?;
+        Ok(self
+            .consensus
+            .services
+            .dag_traversal_manager
+            .calculate_chain_path(hash, self.sink))
+    }
+
+    fn get_blocks_acceptance_data(
+        &self,
+        hashes: &[Hash],
+    ) -> ConsensusResult<Vec<Arc<AcceptanceData>>> {
+        hashes
+            .iter()
+            .copied()
+            .map(|hash| {
+                self.consensus
+                    .acceptance_data_store
+                    .get(hash)
+                    .unwrap_option()
+                    .ok_or(ConsensusError::MissingData(hash))
+            })
+            .collect()
+    }
+
+    fn get_virtual_utxo_entries(
+        &self,
+        outpoints: &[TransactionOutpoint],
+    ) -> Vec<Option<UtxoEntry>> {
+        let virtual_stores = self.consensus.virtual_stores.read();
+        outpoints
+            .iter()
+            .map(|outpoint| {
+                virtual_stores
+                    .utxo_set
+                    .get_at(&self.snapshot, outpoint)
+                    .unwrap_option()
+                    .map(|entry| UtxoEntry::clone(&entry))
+            })
+            .collect()
+    }
+}
@@ -30,8 +30,9 @@ use crate::{
 use super::cache_policy_builder::CachePolicyBuilder as PolicyBuilder;
 use itertools::Itertools;
 use karlsen_consensus_core::{blockstatus::BlockStatus, BlockHashSet};
-use karlsen_database::registry::DatabaseStorePrefixes;
+use karlsen_database::{prelude::CacheMemoryConsumer, registry::DatabaseStorePrefixes};
 use karlsen_hashes::Hash;
+use karlsen_utils::mem_budget::{memory_budget, MemoryConsumer};
 use parking_lot::RwLock;
 use std::{mem::size_of, ops::DerefMut, sync::Arc};
 
@@ -73,10 +74,15 @@ pub struct ConsensusStorage {
     /// The "last known good" virtual state. To be used by any logic which does not want to wait
     /// for a possible virtual state write to complete but can rather settle with the last known state
     pub lkg_virtual_state: LkgVirtualState,
+
+    /// Caches registered to the process memory budget, which only holds weak references
+    _memory_consumers: Vec<Arc<dyn MemoryConsumer>>,
 }
 
 impl ConsensusStorage {
-    pub fn new(db: Arc<DB>, config: Arc<Config>) -> Arc<Self> {
+    /// Creates the stores over `db`, the block bodies and the acceptance data falling through to
+    /// `cold_db`, if any, for the data migrated out of `db`
+    pub fn new(db: Arc<DB>, cold_db: Option<Arc<DB>>, config: Arc<Config>) -> Arc<Self> {
         let scale_factor = config.ram_scale;
         let scaled = |s| (s as f64 * scale_factor) as usize;
 
@@ -262,8 +268,9 @@ impl ConsensusStorage {
         )));
 
         // Txs
-        let block_transactions_store = Arc::new(DbBlockTransactionsStore::new(
+        let block_transactions_store = Arc::new(DbBlockTransactionsStore::with_cold_db(
             db.clone(),
+            cold_db.clone(),
             transactions_builder.build(),
         ));
         let utxo_diffs_store = Arc::new(DbUtxoDiffsStore::new(
@@ -274,8 +281,9 @@ impl ConsensusStorage {
             db.clone(),
             block_data_builder.build(),
         ));
-        let acceptance_data_store = Arc::new(DbAcceptanceDataStore::new(
+        let acceptance_data_store = Arc::new(DbAcceptanceDataStore::with_cold_db(
             db.clone(),
+            cold_db,
             acceptance_data_builder.build(),
         ));
 
@@ -299,6 +307,44 @@ impl ConsensusStorage {
             utxo_set_builder.build(),
         )));
 
+        // Memory budget, the block windows being capped to their share and the UTXO caches
+        // only shrunk under pressure
+        let memory_consumers: Vec<(&'static str, Option<usize>, Arc<dyn MemoryConsumer>)> = vec![
+            (
+                "difficulty window cache",
+                Some(block_window_budget),
+                Arc::new(CacheMemoryConsumer::new(
+                    (*block_window_cache_for_difficulty).clone(),
+                    difficulty_window_bytes,
+                )),
+            ),
+            (
+                "median time window cache",
+                Some(block_window_budget),
+                Arc::new(CacheMemoryConsumer::new(
+                    (*block_window_cache_for_past_median_time).clone(),
+                    median_window_bytes,
+                )),
+            ),
+            (
+                "UTXO diffs cache",
+                None,
+                Arc::new(utxo_diffs_store.memory_consumer()),
+            ),
+            (
+                "virtual UTXO set cache",
+                None,
+                Arc::new(virtual_stores.read().utxo_set.memory_consumer()),
+            ),
+        ];
+        let memory_consumers = memory_consumers
+            .into_iter()
+            .map(|(name, cap, consumer)| {
+                memory_budget().register(name, cap, consumer.clone());
+                consumer
+            })
+            .collect();
+
         // Ensure that reachability stores are initialized
         reachability::init(reachability_store.write().deref_mut()).unwrap();
         relations::init(reachability_relations_store.write().deref_mut());
@@ -328,6 +374,7 @@ impl ConsensusStorage {
             block_window_cache_for_difficulty,
             block_window_cache_for_past_median_time,
             lkg_virtual_state,
+            _memory_consumers: memory_consumers,
         })
     }
 }
@@ -33,8 +33,9 @@ use crate::{
     },
     params::Params,
     pipeline::{
-        body_processor::BlockBodyProcessor, virtual_processor::VirtualStateProcessor,
-        ProcessingCounters,
+        body_processor::BlockBodyProcessor,
+        cold_storage_processor::processor::ColdStorageProcessor,
+        virtual_processor::VirtualStateProcessor, ProcessingCounters,
     },
     test_helpers::header_from_precomputed_hash,
 };
@@ -47,6 +48,7 @@ pub struct TestConsensus {
     consensus: Arc<Consensus>,
     block_builder: TestBlockBuilder,
     db_lifetime: DbLifetime,
+    cold_db_lifetime: DbLifetime,
 }
 
 impl TestConsensus {
@@ -61,6 +63,7 @@ impl TestConsensus {
         let tx_script_cache_counters = Default::default();
         let consensus = Arc::new(Consensus::new(
             db,
+            None,
             Arc::new(config.clone()),
             Default::default(),
             notification_root,
@@ -75,6 +78,7 @@ impl TestConsensus {
             consensus,
             block_builder,
             db_lifetime: Default::default(),
+            cold_db_lifetime: Default::default(),
         }
     }
 
@@ -93,6 +97,7 @@ impl TestConsensus {
         let tx_script_cache_counters = Default::default();
         let consensus = Arc::new(Consensus::new(
             db,
+            None,
             Arc::new(config.clone()),
             Default::default(),
             notification_root,
@@ -107,6 +112,7 @@ impl TestConsensus {
             block_builder,
             params: config.params.clone(),
             db_lifetime,
+            cold_db_lifetime: Default::default(),
         }
     }
 
@@ -119,6 +125,7 @@ impl TestConsensus {
         let tx_script_cache_counters = Default::default();
         let consensus = Arc::new(Consensus::new(
             db,
+            None,
             Arc::new(config.clone()),
             Default::default(),
             notification_root,
@@ -133,10 +140,41 @@ impl TestConsensus {
             block_builder,
             params: config.params.clone(),
             db_lifetime,
+            cold_db_lifetime: Default::default(),
         }
     }
 
     /// Clone the inner consensus Arc. For general usage of the underlying consensus simply deref
+    /// Creates a test consensus instance based on `config` with temp main and cold DBs and no notifier
+    pub fn with_cold_db(config: &Config) -> Self {
+        let (db_lifetime, db) = create_temp_db!(ConnBuilder::default().with_files_limit(10));
+        let (cold_db_lifetime, cold_db) =
+            create_temp_db!(ConnBuilder::default().with_files_limit(10));
+        let (dummy_notification_sender, _) = async_channel::unbounded();
+        let notification_root = Arc::new(ConsensusNotificationRoot::new(dummy_notification_sender));
+        let counters = Default::default();
+        let tx_script_cache_counters = Default::default();
+        let consensus = Arc::new(Consensus::new(
+            db,
+            Some(cold_db),
+            Arc::new(config.clone()),
+            Default::default(),
+            notification_root,
+            counters,
+            tx_script_cache_counters,
+            0,
+        ));
+        let block_builder = TestBlockBuilder::new(consensus.virtual_processor.clone());
+
+        Self {
+            consensus,
+            block_builder,
+            params: config.params.clone(),
+            db_lifetime,
+            cold_db_lifetime,
+        }
+    }
+
     pub fn consensus_clone(&self) -> Arc<Consensus> {
         self.consensus.clone()
     }
@@ -318,6 +356,11 @@ impl TestConsensus {
     pub fn ghostdag_manager(&self) -> &DbGhostdagManager {
         &self.consensus.services.ghostdag_primary_manager
     }
+
+    /// The cold storage processor, for instances created with a cold DB
+    pub fn cold_storage_processor(&self) -> &Arc<ColdStorageProcessor> {
+        self.consensus.cold_storage_processor.as_ref().unwrap()
+    }
 }
 
 impl std::ops::Deref for TestConsensus {
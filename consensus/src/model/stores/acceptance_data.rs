@@ -1,9 +1,11 @@
+use super::cold_storage::DbColdStorageStore;
 use karlsen_consensus_core::acceptance_data::AcceptanceData;
 use karlsen_consensus_core::acceptance_data::AcceptedTxEntry;
 use karlsen_consensus_core::acceptance_data::MergesetBlockAcceptanceData;
 use karlsen_consensus_core::BlockHasher;
 use karlsen_database::prelude::CachePolicy;
 use karlsen_database::prelude::StoreError;
+use karlsen_database::prelude::StoreResultExtensions;
 use karlsen_database::prelude::DB;
 use karlsen_database::prelude::{BatchDbWriter, CachedDbAccess, DirectDbWriter};
 use karlsen_database::registry::DatabaseStorePrefixes;
@@ -46,10 +48,23 @@ impl MemSizeEstimator for AcceptanceDataEntry {
 pub struct DbAcceptanceDataStore {
     db: Arc<DB>,
     access: CachedDbAccess<Hash, AcceptanceDataEntry, BlockHasher>,
+    /// The cold DB holding the acceptance data migrated out of the main DB, along with its access and
+    /// the queue of its deletions
+    cold: Option<(
+        Arc<DB>,
+        CachedDbAccess<Hash, AcceptanceDataEntry, BlockHasher>,
+        DbColdStorageStore,
+    )>,
 }
 
 impl DbAcceptanceDataStore {
     pub fn new(db: Arc<DB>, cache_policy: CachePolicy) -> Self {
+        Self::with_cold_db(db, None, cache_policy)
+    }
+
+    /// Creates a store whose reads fall through to `cold_db` for the acceptance data missing from `db`
+    pub fn with_cold_db(db: Arc<DB>, cold_db: Option<Arc<DB>>, cache_policy: CachePolicy) -> Self {
+        let cold_storage_store = DbColdStorageStore::new(Arc::clone(&db));
         Self {
             db: Arc::clone(&db),
             access: CachedDbAccess::new(
@@ -57,11 +72,34 @@ impl DbAcceptanceDataStore {
                 cache_policy,
                 DatabaseStorePrefixes::AcceptanceData.into(),
             ),
+            // Cold data is rarely read, so it is not cached
+            cold: cold_db.map(|cold_db| {
+                let access = CachedDbAccess::new(
+                    Arc::clone(&cold_db),
+                    CachePolicy::Empty,
+                    DatabaseStorePrefixes::AcceptanceData.into(),
+                );
+                (cold_db, access, cold_storage_store)
+            }),
         }
     }
 
     pub fn clone_with_new_cache(&self, cache_policy: CachePolicy) -> Self {
-        Self::new(Arc::clone(&self.db), cache_policy)
+        Self::with_cold_db(
+            Arc::clone(&self.db),
+            self.cold
+                .as_ref()
+                .map(|(cold_db, _, _)| Arc::clone(cold_db)),
+            cache_policy,
+        )
+    }
+
+    pub fn has(&self, hash: Hash) -> Result<bool, StoreError> {
+        Ok(self.access.has(hash)?
+            || match self.cold.as_ref() {
+                Some((_, cold_access, _)) => cold_access.has(hash)?,
+                None => false,
+            })
     }
 
     pub fn insert_batch(
@@ -70,7 +108,7 @@ impl DbAcceptanceDataStore {
         hash: Hash,
         acceptance_data: Arc<AcceptanceData>,
     ) -> Result<(), StoreError> {
-        if self.access.has(hash)? {
+        if self.has(hash)? {
             return Err(StoreError::HashAlreadyExists(hash));
         }
         self.access.write(
@@ -82,19 +120,60 @@ impl DbAcceptanceDataStore {
     }
 
     pub fn delete_batch(&self, batch: &mut WriteBatch, hash: Hash) -> Result<(), StoreError> {
+        // The batch only applies to the main DB, so the deletion of the cold copy is queued within it
+        if let Some((_, _, cold_storage_store)) = self.cold.as_ref() {
+            cold_storage_store.queue_deletion(BatchDbWriter::new(batch), hash)?;
+        }
         self.access.delete(BatchDbWriter::new(batch), hash)
     }
+
+    /// Deletes the cold copy of the acceptance data of `hash` within `cold_batch`, carrying out a queued deletion
+    pub fn delete_cold_batch(
+        &self,
+        cold_batch: &mut WriteBatch,
+        hash: Hash,
+    ) -> Result<(), StoreError> {
+        match self.cold.as_ref() {
+            Some((_, cold_access, _)) => cold_access.delete(BatchDbWriter::new(cold_batch), hash),
+            None => Ok(()),
+        }
+    }
+
+    /// Moves the acceptance data of `hash` to the cold DB, writing it to `cold_batch` and deleting it
+    /// from the main DB within `batch`. Returns false if the main DB holds no such data or there is no
+    /// cold DB. The cold batch must be committed first, so the data is never missing from both DBs.
+    pub fn migrate_batch(
+        &self,
+        batch: &mut WriteBatch,
+        cold_batch: &mut WriteBatch,
+        hash: Hash,
+    ) -> Result<bool, StoreError> {
+        let Some((_, cold_access, _)) = self.cold.as_ref() else {
+            return Ok(false);
+        };
+        let Some(entry) = self.access.read(hash).optional()? else {
+            return Ok(false);
+        };
+        cold_access.write(BatchDbWriter::new(cold_batch), hash, entry)?;
+        self.access.delete(BatchDbWriter::new(batch), hash)?;
+        Ok(true)
+    }
 }
 
 impl AcceptanceDataStoreReader for DbAcceptanceDataStore {
     fn get(&self, hash: Hash) -> Result<Arc<AcceptanceData>, StoreError> {
-        Ok(self.access.read(hash)?.0)
+        match (self.access.read(hash), self.cold.as_ref()) {
+            (Err(StoreError::KeyNotFound(_)), Some((_, cold_access, _))) => {
+                Ok(cold_access.read(hash)?.0)
+            }
+            (result, _) => Ok(result?.0),
+        }
     }
 }
 
 impl AcceptanceDataStore for DbAcceptanceDataStore {
     fn insert(&self, hash: Hash, acceptance_data: Arc<AcceptanceData>) -> Result<(), StoreError> {
-        if self.access.has(hash)? {
+        if self.has(hash)? {
             return Err(StoreError::HashAlreadyExists(hash));
         }
         self.access.write(
@@ -106,6 +185,9 @@ impl AcceptanceDataStore for DbAcceptanceDataStore {
     }
 
     fn delete(&self, hash: Hash) -> Result<(), StoreError> {
+        if let Some((_, _, cold_storage_store)) = self.cold.as_ref() {
+            cold_storage_store.queue_deletion(DirectDbWriter::new(&self.db), hash)?;
+        }
         self.access.delete(DirectDbWriter::new(&self.db), hash)
     }
 }
@@ -1,7 +1,9 @@
+use super::cold_storage::DbColdStorageStore;
 use karlsen_consensus_core::tx::{TransactionInput, TransactionOutput};
 use karlsen_consensus_core::{tx::Transaction, BlockHasher};
 use karlsen_database::prelude::CachePolicy;
 use karlsen_database::prelude::StoreError;
+use karlsen_database::prelude::StoreResultExtensions;
 use karlsen_database::prelude::DB;
 use karlsen_database::prelude::{BatchDbWriter, CachedDbAccess, DirectDbWriter};
 use karlsen_database::registry::DatabaseStorePrefixes;
@@ -49,10 +51,23 @@ impl MemSizeEstimator for BlockBody {
 pub struct DbBlockTransactionsStore {
     db: Arc<DB>,
     access: CachedDbAccess<Hash, BlockBody, BlockHasher>,
+    /// The cold DB holding the block bodies migrated out of the main DB, along with its access and
+    /// the queue of its deletions
+    cold: Option<(
+        Arc<DB>,
+        CachedDbAccess<Hash, BlockBody, BlockHasher>,
+        DbColdStorageStore,
+    )>,
 }
 
 impl DbBlockTransactionsStore {
     pub fn new(db: Arc<DB>, cache_policy: CachePolicy) -> Self {
+        Self::with_cold_db(db, None, cache_policy)
+    }
+
+    /// Creates a store whose reads fall through to `cold_db` for the block bodies missing from `db`
+    pub fn with_cold_db(db: Arc<DB>, cold_db: Option<Arc<DB>>, cache_policy: CachePolicy) -> Self {
+        let cold_storage_store = DbColdStorageStore::new(Arc::clone(&db));
         Self {
             db: Arc::clone(&db),
             access: CachedDbAccess::new(
@@ -60,15 +75,34 @@ impl DbBlockTransactionsStore {
                 cache_policy,
                 DatabaseStorePrefixes::BlockTransactions.into(),
             ),
+            // Cold data is rarely read, so it is not cached
+            cold: cold_db.map(|cold_db| {
+                let access = CachedDbAccess::new(
+                    Arc::clone(&cold_db),
+                    CachePolicy::Empty,
+                    DatabaseStorePrefixes::BlockTransactions.into(),
+                );
+                (cold_db, access, cold_storage_store)
+            }),
         }
     }
 
     pub fn clone_with_new_cache(&self, cache_policy: CachePolicy) -> Self {
-        Self::new(Arc::clone(&self.db), cache_policy)
+        Self::with_cold_db(
+            Arc::clone(&self.db),
+            self.cold
+                .as_ref()
+                .map(|(cold_db, _, _)| Arc::clone(cold_db)),
+            cache_policy,
+        )
     }
 
     pub fn has(&self, hash: Hash) -> Result<bool, StoreError> {
-        self.access.has(hash)
+        Ok(self.access.has(hash)?
+            || match self.cold.as_ref() {
+                Some((_, cold_access, _)) => cold_access.has(hash)?,
+                None => false,
+            })
     }
 
     pub fn insert_batch(
@@ -77,7 +111,7 @@ impl DbBlockTransactionsStore {
         hash: Hash,
         transactions: Arc<Vec<Transaction>>,
     ) -> Result<(), StoreError> {
-        if self.access.has(hash)? {
+        if self.has(hash)? {
             return Err(StoreError::HashAlreadyExists(hash));
         }
         self.access
@@ -86,19 +120,60 @@ impl DbBlockTransactionsStore {
     }
 
     pub fn delete_batch(&self, batch: &mut WriteBatch, hash: Hash) -> Result<(), StoreError> {
+        // The batch only applies to the main DB, so the deletion of the cold copy is queued within it
+        if let Some((_, _, cold_storage_store)) = self.cold.as_ref() {
+            cold_storage_store.queue_deletion(BatchDbWriter::new(batch), hash)?;
+        }
         self.access.delete(BatchDbWriter::new(batch), hash)
     }
+
+    /// Deletes the cold copy of the body of `hash` within `cold_batch`, carrying out a queued deletion
+    pub fn delete_cold_batch(
+        &self,
+        cold_batch: &mut WriteBatch,
+        hash: Hash,
+    ) -> Result<(), StoreError> {
+        match self.cold.as_ref() {
+            Some((_, cold_access, _)) => cold_access.delete(BatchDbWriter::new(cold_batch), hash),
+            None => Ok(()),
+        }
+    }
+
+    /// Moves the body of `hash` to the cold DB, writing it to `cold_batch` and deleting it from the
+    /// main DB within `batch`. Returns false if the main DB holds no such body or there is no cold DB.
+    /// The cold batch must be committed first, so the body is never missing from both DBs.
+    pub fn migrate_batch(
+        &self,
+        batch: &mut WriteBatch,
+        cold_batch: &mut WriteBatch,
+        hash: Hash,
+    ) -> Result<bool, StoreError> {
+        let Some((_, cold_access, _)) = self.cold.as_ref() else {
+            return Ok(false);
+        };
+        let Some(entry) = self.access.read(hash).optional()? else {
+            return Ok(false);
+        };
+        cold_access.write(BatchDbWriter::new(cold_batch), hash, entry)?;
+        self.access.delete(BatchDbWriter::new(batch), hash)?;
+        Ok(true)
+    }
 }
 
 impl BlockTransactionsStoreReader for DbBlockTransactionsStore {
     fn get(&self, hash: Hash) -> Result<Arc<Vec<Transaction>>, StoreError> {
-        Ok(self.access.read(hash)?.0)
+        match (self.access.read(hash), self.cold.as_ref()) {
+            (Err(StoreError::KeyNotFound(_)), Some((_, cold_access, _))) => {
+                Ok(cold_access.read(hash)?.0)
+            }
+            (result, _) => Ok(result?.0),
+        }
     }
 }
 
 impl BlockTransactionsStore for DbBlockTransactionsStore {
     fn insert(&self, hash: Hash, transactions: Arc<Vec<Transaction>>) -> Result<(), StoreError> {
-        if self.access.has(hash)? {
+        if self.has(hash)? {
             return Err(StoreError::HashAlreadyExists(hash));
         }
         self.access
@@ -107,6 +182,74 @@ impl BlockTransactionsStore for DbBlockTransactionsStore {
     }
 
     fn delete(&self, hash: Hash) -> Result<(), StoreError> {
+        if let Some((_, _, cold_storage_store)) = self.cold.as_ref() {
+            cold_storage_store.queue_deletion(DirectDbWriter::new(&self.db), hash)?;
+        }
         self.access.delete(DirectDbWriter::new(&self.db), hash)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::stores::cold_storage::ColdStorageStoreReader;
+    use karlsen_database::{create_temp_db, prelude::ConnBuilder};
+
+    #[test]
+    fn test_cold_db_migration() {
+        let (_lifetime, db) = create_temp_db!(ConnBuilder::default().with_files_limit(10));
+        let (_cold_lifetime, cold_db) =
+            create_temp_db!(ConnBuilder::default().with_files_limit(10));
+        let store = DbBlockTransactionsStore::with_cold_db(
+            db.clone(),
+            Some(cold_db.clone()),
+            CachePolicy::Count(10),
+        );
+        let body = Arc::new(vec![]);
+        store.insert(1.into(), body.clone()).unwrap();
+        store.insert(2.into(), body.clone()).unwrap();
+
+        let mut batch = WriteBatch::default();
+        let mut cold_batch = WriteBatch::default();
+        assert!(store
+            .migrate_batch(&mut batch, &mut cold_batch, 1.into())
+            .unwrap());
+        assert!(!store
+            .migrate_batch(&mut batch, &mut cold_batch, 3.into())
+            .unwrap());
+        cold_db.write(cold_batch).unwrap();
+        db.write(batch).unwrap();
+
+        // Reads fall through to the cold DB, while the main DB only holds the recent body
+        let hot_store = DbBlockTransactionsStore::new(db.clone(), CachePolicy::Empty);
+        assert!(!hot_store.has(1.into()).unwrap());
+        assert!(hot_store.has(2.into()).unwrap());
+        assert!(store.has(1.into()).unwrap());
+        assert_eq!(store.get(1.into()).unwrap().len(), 0);
+        assert!(matches!(
+            store.insert(1.into(), body),
+            Err(StoreError::HashAlreadyExists(_))
+        ));
+
+        // Deleting the body only queues the deletion of its cold copy
+        store.delete(1.into()).unwrap();
+        let cold_storage_store = DbColdStorageStore::new(db.clone());
+        let queued = cold_storage_store.queued_deletions(10).unwrap();
+        assert_eq!(queued, vec![1.into()]);
+        assert!(store.has(1.into()).unwrap());
+
+        let mut batch = WriteBatch::default();
+        let mut cold_batch = WriteBatch::default();
+        store.delete_cold_batch(&mut cold_batch, 1.into()).unwrap();
+        cold_storage_store
+            .dequeue_deletions(&mut batch, &queued)
+            .unwrap();
+        cold_db.write(cold_batch).unwrap();
+        db.write(batch).unwrap();
+        assert!(matches!(
+            store.get(1.into()),
+            Err(StoreError::KeyNotFound(_))
+        ));
+        assert!(cold_storage_store.queued_deletions(10).unwrap().is_empty());
+    }
+}
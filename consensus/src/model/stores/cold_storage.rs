@@ -0,0 +1,81 @@
+use std::sync::Arc;
+
+use karlsen_consensus_core::BlockHasher;
+use karlsen_database::prelude::DB;
+use karlsen_database::prelude::{
+    BatchDbWriter, CachePolicy, CachedDbAccess, CachedDbItem, DbWriter,
+};
+use karlsen_database::prelude::{StoreError, StoreResult};
+use karlsen_database::registry::DatabaseStorePrefixes;
+use karlsen_hashes::Hash;
+use rocksdb::WriteBatch;
+
+/// Reader API for `ColdStorageStore`.
+pub trait ColdStorageStoreReader {
+    /// Returns the selected chain index up to which (excluded) the data of the chain blocks and
+    /// of their mergesets was migrated to the cold DB
+    fn migration_index(&self) -> StoreResult<u64>;
+
+    /// Returns up to `limit` blocks whose cold data is queued for deletion
+    fn queued_deletions(&self, limit: usize) -> StoreResult<Vec<Hash>>;
+}
+
+/// A DB + cache implementation of `ColdStorageStoreReader`, tracking the progress of the
+/// migration to the cold DB. The progress is kept in the main DB, along with the data it refers to.
+///
+/// Deleting data from the main DB also deletes its cold copy, which cannot be part of the same
+/// atomic batch. The deletion is thus queued within the main DB batch instead, and carried out
+/// later by the cold storage processor, which is the only writer of the cold DB.
+#[derive(Clone)]
+pub struct DbColdStorageStore {
+    access: CachedDbItem<u64>,
+    deletions: CachedDbAccess<Hash, (), BlockHasher>,
+}
+
+impl DbColdStorageStore {
+    pub fn new(db: Arc<DB>) -> Self {
+        Self {
+            access: CachedDbItem::new(
+                db.clone(),
+                DatabaseStorePrefixes::ColdStorageMigrationIndex.into(),
+            ),
+            deletions: CachedDbAccess::new(
+                db,
+                CachePolicy::Empty,
+                DatabaseStorePrefixes::ColdStorageDeletions.into(),
+            ),
+        }
+    }
+
+    pub fn set_migration_index(&mut self, batch: &mut WriteBatch, index: u64) -> StoreResult<()> {
+        self.access.write(BatchDbWriter::new(batch), &index)
+    }
+
+    /// Queues the deletion of the cold data of `hash`
+    pub fn queue_deletion(&self, writer: impl DbWriter, hash: Hash) -> StoreResult<()> {
+        self.deletions.write(writer, hash, ())
+    }
+
+    /// Removes `hashes` from the deletion queue, once their cold data was deleted
+    pub fn dequeue_deletions(&self, batch: &mut WriteBatch, hashes: &[Hash]) -> StoreResult<()> {
+        self.deletions
+            .delete_many(BatchDbWriter::new(batch), &mut hashes.iter().copied())
+    }
+}
+
+impl ColdStorageStoreReader for DbColdStorageStore {
+    fn migration_index(&self) -> StoreResult<u64> {
+        self.access.read()
+    }
+
+    fn queued_deletions(&self, limit: usize) -> StoreResult<Vec<Hash>> {
+        self.deletions
+            .iterator()
+            .take(limit)
+            .map(|item| match item {
+                Ok((key, _)) => Ok(Hash::from_slice(&key)),
+                Err(err) => Err(StoreError::DataInconsistency(err.to_string())),
+            })
+            .collect()
+    }
+}
@@ -1,4 +1,4 @@
-use std::sync::Arc;
+use std::{mem::size_of, sync::Arc};
 
 use karlsen_consensus_core::{utxo::utxo_diff::UtxoDiff, BlockHasher};
 use karlsen_database::prelude::CachePolicy;
@@ -7,6 +7,7 @@ use karlsen_database::prelude::DB;
 use karlsen_database::prelude::{BatchDbWriter, CachedDbAccess, DirectDbWriter};
 use karlsen_database::registry::DatabaseStorePrefixes;
 use karlsen_hashes::Hash;
+use karlsen_utils::mem_budget::MemoryConsumer;
 use rocksdb::WriteBatch;
 
 /// Store for holding the UTXO difference (delta) of a block relative to its selected parent.
@@ -43,6 +44,12 @@ impl DbUtxoDiffsStore {
         Self::new(Arc::clone(&self.db), cache_policy)
     }
 
+    /// The cache of the store as a consumer of the process memory budget
+    pub fn memory_consumer(&self) -> impl MemoryConsumer {
+        // The cache is tracked in bytes so the item size is not used
+        self.access.cache_memory_consumer(size_of::<UtxoDiff>())
+    }
+
     pub fn insert_batch(
         &self,
         batch: &mut WriteBatch,
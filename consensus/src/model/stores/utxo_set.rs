@@ -6,12 +6,13 @@ use karlsen_consensus_core::{
     },
 };
 use karlsen_database::prelude::StoreResultExtensions;
-use karlsen_database::prelude::DB;
 use karlsen_database::prelude::{BatchDbWriter, CachedDbAccess, DirectDbWriter};
 use karlsen_database::prelude::{CachePolicy, StoreError};
+use karlsen_database::prelude::{DbSnapshot, DB};
 use karlsen_hashes::Hash;
+use karlsen_utils::mem_budget::MemoryConsumer;
 use rocksdb::WriteBatch;
-use std::{error::Error, fmt::Display, sync::Arc};
+use std::{error::Error, fmt::Display, mem::size_of, sync::Arc};
 
 type UtxoCollectionIterator<'a> =
     Box<dyn Iterator<Item = Result<(TransactionOutpoint, UtxoEntry), Box<dyn Error>>> + 'a>;
@@ -120,6 +121,21 @@ impl DbUtxoSetStore {
         Self::new(Arc::clone(&self.db), cache_policy, self.prefix.clone())
     }
 
+    /// The cache of the store as a consumer of the process memory budget
+    pub fn memory_consumer(&self) -> impl MemoryConsumer {
+        // The cache is tracked in bytes so the item size is not used
+        self.access.cache_memory_consumer(size_of::<UtxoEntry>())
+    }
+
+    /// Returns the entry of `outpoint` as of `snapshot`
+    pub fn get_at(
+        &self,
+        snapshot: &DbSnapshot,
+        outpoint: &TransactionOutpoint,
+    ) -> Result<Arc<UtxoEntry>, StoreError> {
+        self.access.read_at(snapshot, (*outpoint).into())
+    }
+
     /// See comment at [`UtxoSetStore::write_diff`]
     pub fn write_diff_batch(
         &mut self,
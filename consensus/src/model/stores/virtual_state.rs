@@ -11,7 +11,7 @@ use karlsen_database::prelude::{
     BatchDbWriter, CachedDbItem, DirectDbWriter, StoreResultExtensions,
 };
 use karlsen_database::prelude::{CachePolicy, StoreResult};
-use karlsen_database::prelude::{StoreError, DB};
+use karlsen_database::prelude::{DbSnapshot, StoreError, DB};
 use karlsen_database::registry::DatabaseStorePrefixes;
 use karlsen_hashes::Hash;
 use karlsen_muhash::MuHash;
@@ -194,6 +194,11 @@ impl DbVirtualStateStore {
         Self::new(self.db.clone(), self.lkg_virtual_state.clone())
     }
 
+    /// Returns the virtual state as of `snapshot`
+    pub fn get_at(&self, snapshot: &DbSnapshot) -> StoreResult<Arc<VirtualState>> {
+        self.access.read_at(snapshot)
+    }
+
     pub fn is_initialized(&self) -> StoreResult<bool> {
         match self.access.read() {
             Ok(_) => Ok(true),
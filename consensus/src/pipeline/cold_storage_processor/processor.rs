@@ -0,0 +1,379 @@
+//!
+//! Migration of the ancient block bodies and acceptance data to the cold DB.
+//!
+//! The data of a chain block and the bodies of its mergeset are migrated together once the
+//! chain block is deep enough in the past of the sink, so the migration progress is a single
+//! index of the selected chain. The depth is at least the finality depth, so the migrated part
+//! of the chain never reorgs. Each batch is first committed to the cold DB, then deleted from
+//! the main DB along with the progress update, so the data is never missing from both DBs and
+//! an interrupted batch is migrated again on the next run. Reads fall through to the cold DB,
+//! so the migration is transparent to the rest of the consensus. Data pruning queues the
+//! deletion of the cold copies in the main DB, and the processor carries them out after each
+//! batch, being the only writer of the cold DB.
+//!
+
+use crate::{
+    consensus::storage::ConsensusStorage,
+    model::stores::{
+        cold_storage::{ColdStorageStoreReader, DbColdStorageStore},
+        ghostdag::{CompactGhostdagData, GhostdagStoreReader},
+        pruning::PruningStoreReader,
+        selected_chain::SelectedChainStoreReader,
+    },
+};
+use crossbeam_channel::Receiver as CrossbeamReceiver;
+use karlsen_consensus_core::config::Config;
+use karlsen_consensusmanager::SessionLock;
+use karlsen_core::{debug, info};
+use karlsen_database::prelude::{StoreResultExtensions, DB};
+use karlsen_hashes::Hash;
+use parking_lot::RwLock;
+use rocksdb::WriteBatch;
+use std::{
+    cmp::max,
+    ops::Deref,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
+
+/// Maximum count of chain blocks whose data is migrated in a single batch
+const MIGRATION_BATCH_SIZE: u64 = 64;
+
+/// Maximum count of queued deletions carried out in a single batch
+const DELETION_BATCH_SIZE: usize = 1024;
+
+pub enum ColdStorageProcessingMessage {
+    Process {
+        sink_ghostdag_data: CompactGhostdagData,
+    },
+}
+
+/// A processor dedicated for moving the block bodies and the acceptance data deep in the past of
+/// the sink to the cold DB, keeping only recent data on the main storage
+pub struct ColdStorageProcessor {
+    // Channels
+    receiver: CrossbeamReceiver<ColdStorageProcessingMessage>,
+
+    // DB
+    db: Arc<DB>,
+    cold_db: Arc<DB>,
+
+    // Storage
+    storage: Arc<ConsensusStorage>,
+    cold_storage_store: RwLock<DbColdStorageStore>,
+
+    // Pruning lock
+    pruning_lock: SessionLock,
+
+    /// Blue score depth below the sink from which chain blocks are migrated
+    migration_depth: u64,
+
+    // Signals
+    is_consensus_exiting: Arc<AtomicBool>,
+}
+
+impl Deref for ColdStorageProcessor {
+    type Target = ConsensusStorage;
+
+    fn deref(&self) -> &Self::Target {
+        &self.storage
+    }
+}
+
+impl ColdStorageProcessor {
+    pub fn new(
+        receiver: CrossbeamReceiver<ColdStorageProcessingMessage>,
+        db: Arc<DB>,
+        cold_db: Arc<DB>,
+        storage: &Arc<ConsensusStorage>,
+        pruning_lock: SessionLock,
+        config: &Config,
+        is_consensus_exiting: Arc<AtomicBool>,
+    ) -> Self {
+        Self {
+            receiver,
+            cold_storage_store: RwLock::new(DbColdStorageStore::new(db.clone())),
+            db,
+            cold_db,
+            storage: storage.clone(),
+            pruning_lock,
+            migration_depth: max(config.cold_storage_depth, config.params.finality_depth),
+            is_consensus_exiting,
+        }
+    }
+
+    pub fn worker(self: &Arc<Self>) {
+        while let Ok(ColdStorageProcessingMessage::Process {
+            mut sink_ghostdag_data,
+        }) = self.receiver.recv()
+        {
+            // Only the latest sink matters, since the migration catches up with it at once
+            while let Ok(ColdStorageProcessingMessage::Process {
+                sink_ghostdag_data: latest,
+            }) = self.receiver.try_recv()
+            {
+                sink_ghostdag_data = latest;
+            }
+            if !self.migrate(sink_ghostdag_data.blue_score) {
+                info!("Interrupted while migrating data to the cold storage: Process is exiting");
+                return;
+            }
+        }
+    }
+
+    /// Migrates the data of the chain blocks deeper than the migration depth below the sink.
+    /// Returns false if interrupted by the consensus exiting.
+    fn migrate(&self, sink_blue_score: u64) -> bool {
+        // The processor is the only writer of the cold DB, the lock only serializes its rounds
+        let mut cold_storage_write = self.cold_storage_store.write();
+        let Some(max_blue_score) = sink_blue_score.checked_sub(self.migration_depth) else {
+            self.delete_queued(&cold_storage_write);
+            return true;
+        };
+        let mut migrated = 0;
+        loop {
+            if self.is_consensus_exiting.load(Ordering::Relaxed) {
+                return false;
+            }
+
+            // The blocks are collected under the locks, which are released before any I/O. Data
+            // pruning may then delete some of them, which are skipped by the migration or have
+            // their cold copy deleted along with the queued deletions below.
+            let start_index = cold_storage_write
+                .migration_index()
+                .unwrap_option()
+                .unwrap_or_default();
+            let (index, blocks, is_complete) = self.collect_batch(start_index, max_blue_score);
+
+            if index != start_index {
+                let mut batch = WriteBatch::default();
+                let mut cold_batch = WriteBatch::default();
+                for (chain_block, mergeset) in blocks {
+                    self.acceptance_data_store
+                        .migrate_batch(&mut batch, &mut cold_batch, chain_block)
+                        .unwrap();
+                    for block in mergeset {
+                        self.block_transactions_store
+                            .migrate_batch(&mut batch, &mut cold_batch, block)
+                            .unwrap();
+                    }
+                }
+                // The cold data is made durable before being deleted from the main DB
+                self.cold_db.write(cold_batch).unwrap();
+                self.cold_db.flush_wal(true).unwrap();
+                cold_storage_write
+                    .set_migration_index(&mut batch, index)
+                    .unwrap();
+                self.db.write(batch).unwrap();
+                migrated += index - start_index;
+            }
+            self.delete_queued(&cold_storage_write);
+
+            if is_complete {
+                if migrated > 0 {
+                    debug!(
+                        "Migrated the data of {} chain blocks to the cold storage",
+                        migrated
+                    );
+                }
+                return true;
+            }
+        }
+    }
+
+    /// Collects the chain blocks of the next batch starting at `start_index`, along with their
+    /// mergesets. Returns the index following the batch and whether the migration is complete.
+    fn collect_batch(
+        &self,
+        start_index: u64,
+        max_blue_score: u64,
+    ) -> (u64, Vec<(Hash, Vec<Hash>)>, bool) {
+        // Data pruning deletes the very same blocks, so it is blocked while they are collected
+        let _prune_guard = self.pruning_lock.blocking_read();
+        let selected_chain_read = self.selected_chain_store.read();
+        let (tip_index, _) = selected_chain_read.get_tip().unwrap();
+        let mut index = start_index;
+        let mut blocks = Vec::new();
+        while index - start_index < MIGRATION_BATCH_SIZE {
+            if index > tip_index {
+                return (index, blocks, true);
+            }
+            let Some(chain_block) = selected_chain_read.get_by_index(index).unwrap_option() else {
+                // The chain below the pruning point was pruned along with its data
+                let pruning_point = self.pruning_point_store.read().pruning_point().unwrap();
+                index = selected_chain_read.get_by_hash(pruning_point).unwrap();
+                continue;
+            };
+            if self
+                .ghostdag_primary_store
+                .get_blue_score(chain_block)
+                .unwrap()
+                > max_blue_score
+            {
+                return (index, blocks, true);
+            }
+            // The selected parent is part of the mergeset, so the body of a chain block is
+            // migrated along with the next chain block
+            let ghostdag_data = self.ghostdag_primary_store.get_data(chain_block).unwrap();
+            blocks.push((chain_block, ghostdag_data.unordered_mergeset().collect()));
+            index += 1;
+        }
+        (index, blocks, false)
+    }
+
+    /// Deletes the cold copies of the data deleted from the main DB, as queued by the stores
+    fn delete_queued(&self, cold_storage_store: &DbColdStorageStore) {
+        loop {
+            let hashes = cold_storage_store
+                .queued_deletions(DELETION_BATCH_SIZE)
+                .unwrap();
+            if hashes.is_empty() {
+                return;
+            }
+            let mut cold_batch = WriteBatch::default();
+            for &hash in hashes.iter() {
+                self.block_transactions_store
+                    .delete_cold_batch(&mut cold_batch, hash)
+                    .unwrap();
+                self.acceptance_data_store
+                    .delete_cold_batch(&mut cold_batch, hash)
+                    .unwrap();
+            }
+            // The deletions stay queued until carried out durably
+            self.cold_db.write(cold_batch).unwrap();
+            self.cold_db.flush_wal(true).unwrap();
+            let mut batch = WriteBatch::default();
+            cold_storage_store
+                .dequeue_deletions(&mut batch, &hashes)
+                .unwrap();
+            self.db.write(batch).unwrap();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        consensus::test_consensus::TestConsensus,
+        model::stores::{
+            acceptance_data::{
+                AcceptanceDataStore, AcceptanceDataStoreReader, DbAcceptanceDataStore,
+            },
+            block_transactions::{
+                BlockTransactionsStore, BlockTransactionsStoreReader, DbBlockTransactionsStore,
+            },
+            cold_storage::ColdStorageStoreReader,
+            ghostdag::GhostdagStoreReader,
+            selected_chain::SelectedChainStoreReader,
+        },
+    };
+    use karlsen_consensus_core::{
+        api::ConsensusApi,
+        config::{params::MAINNET_PARAMS, ConfigBuilder},
+    };
+    use karlsen_database::prelude::{CachePolicy, StoreError};
+    use karlsen_hashes::Hash;
+
+    const FINALITY_DEPTH: u64 = 10;
+
+    #[tokio::test]
+    async fn test_migration() {
+        let config = ConfigBuilder::new(MAINNET_PARAMS)
+            .skip_proof_of_work()
+            .edit_consensus_params(|p| p.finality_depth = FINALITY_DEPTH)
+            .build();
+        let consensus = TestConsensus::with_cold_db(&config);
+        let wait_handles = consensus.init();
+
+        let chain: Vec<Hash> = (1..=3 * FINALITY_DEPTH).map(Hash::from_u64_word).collect();
+        let mut parent = consensus.params().genesis.hash;
+        for &hash in chain.iter() {
+            consensus
+                .add_utxo_valid_block_with_parents(hash, vec![parent], vec![])
+                .await
+                .unwrap();
+            parent = hash;
+        }
+
+        // The worker may have migrated part of the data already, the rounds being serialized
+        let processor = consensus.cold_storage_processor();
+        let sink_blue_score = processor
+            .ghostdag_primary_store
+            .get_blue_score(consensus.get_sink())
+            .unwrap();
+        assert!(processor.migrate(sink_blue_score));
+
+        // The data is gone from the main DB, yet still readable through the stores. The body of
+        // the deepest chain block which is not migrated migrates along with the next chain block.
+        let max_blue_score = sink_blue_score - FINALITY_DEPTH;
+        let hot_acceptance_data_store =
+            DbAcceptanceDataStore::new(processor.db.clone(), CachePolicy::Empty);
+        let hot_block_transactions_store =
+            DbBlockTransactionsStore::new(processor.db.clone(), CachePolicy::Empty);
+        for &hash in chain.iter() {
+            let blue_score = processor
+                .ghostdag_primary_store
+                .get_blue_score(hash)
+                .unwrap();
+            assert_eq!(
+                hot_acceptance_data_store.has(hash).unwrap(),
+                blue_score > max_blue_score
+            );
+            assert_eq!(
+                hot_block_transactions_store.has(hash).unwrap(),
+                blue_score >= max_blue_score
+            );
+            processor.acceptance_data_store.get(hash).unwrap();
+            processor.block_transactions_store.get(hash).unwrap();
+        }
+        let last_migrated = *chain
+            .iter()
+            .find(|&&hash| {
+                processor
+                    .ghostdag_primary_store
+                    .get_blue_score(hash)
+                    .unwrap()
+                    == max_blue_score
+            })
+            .unwrap();
+        assert_eq!(
+            processor
+                .cold_storage_store
+                .read()
+                .migration_index()
+                .unwrap(),
+            processor
+                .selected_chain_store
+                .read()
+                .get_by_hash(last_migrated)
+                .unwrap()
+                + 1
+        );
+
+        // Deleting migrated data only queues the deletion of the cold copy, which the next round
+        // carries out
+        let deleted = chain[0];
+        processor.acceptance_data_store.delete(deleted).unwrap();
+        processor.block_transactions_store.delete(deleted).unwrap();
+        processor.block_transactions_store.get(deleted).unwrap();
+        assert!(processor.migrate(sink_blue_score));
+        assert!(matches!(
+            processor.acceptance_data_store.get(deleted),
+            Err(StoreError::KeyNotFound(_))
+        ));
+        assert!(matches!(
+            processor.block_transactions_store.get(deleted),
+            Err(StoreError::KeyNotFound(_))
+        ));
+        assert!(processor
+            .cold_storage_store
+            .read()
+            .queued_deletions(10)
+            .unwrap()
+            .is_empty());
+
+        consensus.shutdown(wait_handles);
+    }
+}
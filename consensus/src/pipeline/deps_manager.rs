@@ -1,5 +1,5 @@
 use crate::errors::BlockProcessResult;
-use karlsen_consensus_core::{block::Block, blockstatus::BlockStatus};
+use karlsen_consensus_core::{api::PowPermit, block::Block, blockstatus::BlockStatus};
 use karlsen_hashes::Hash;
 use parking_lot::{Condvar, Mutex};
 use std::collections::{
@@ -40,9 +40,26 @@ impl VirtualStateProcessingMessage {
     }
 }
 
+/// Holds the proof of work permit of a task until the header processor releases it
+#[derive(Default)]
+pub struct PowPermitSlot(Mutex<Option<PowPermit>>);
+
+impl PowPermitSlot {
+    pub fn new(pow_permit: PowPermit) -> Self {
+        Self(Mutex::new(Some(pow_permit)))
+    }
+
+    pub fn release(&self) {
+        self.0.lock().take();
+    }
+}
+
 pub enum BlockTask {
     /// Ordinary block processing task, requiring full validation. The block might be header-only
-    Ordinary { block: Block },
+    Ordinary {
+        block: Block,
+        pow_permit: PowPermitSlot,
+    },
 
     /// Trusted block processing task, only requiring partial validation.
     /// Trusted blocks arrive as part of the pruning proof; the block might be header-only.
@@ -52,11 +69,18 @@ pub enum BlockTask {
 impl BlockTask {
     pub fn block(&self) -> &Block {
         match self {
-            BlockTask::Ordinary { block } => block,
+            BlockTask::Ordinary { block, .. } => block,
             BlockTask::Trusted { block } => block,
         }
     }
 
+    /// Releases the proof of work permit of the task, if any
+    pub fn release_pow_permit(&self) {
+        if let BlockTask::Ordinary { pow_permit, .. } = self {
+            pow_permit.release();
+        }
+    }
+
     pub fn is_ordinary(&self) -> bool {
         matches!(self, BlockTask::Ordinary { .. })
     }
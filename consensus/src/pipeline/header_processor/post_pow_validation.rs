@@ -1,6 +1,7 @@
 use super::{HeaderProcessingContext, HeaderProcessor};
 use crate::errors::{BlockProcessResult, RuleError, TwoDimVecDisplay};
 use crate::model::services::reachability::ReachabilityService;
+use crate::processes::reachability::ReachabilityError;
 use crate::processes::window::WindowManager;
 use karlsen_consensus_core::header::Header;
 use karlsen_hashes::Hash;
@@ -14,6 +15,7 @@ impl HeaderProcessor {
     ) -> BlockProcessResult<()> {
         self.check_blue_score(ctx, header)?;
         self.check_blue_work(ctx, header)?;
+        self.check_checkpoints(header)?;
         self.check_median_timestamp(ctx, header)?;
         self.check_merge_size_limit(ctx)?;
         self.check_bounded_merge_depth(ctx)?;
@@ -82,6 +84,39 @@ impl HeaderProcessor {
         Ok(())
     }
 
+    /// Rejects the headers more than a finality depth above a checkpoint which do not have it in
+    /// their past. Only the latest such checkpoint present in the reachability data is checked,
+    /// since it has the older ones in its past. The blue work of the checkpoint is checked even
+    /// if the checkpoint is not known yet, since a chain holding it accumulates more work.
+    fn check_checkpoints(&self, header: &Header) -> BlockProcessResult<()> {
+        for checkpoint in self.checkpoints.iter().rev() {
+            if header.daa_score < checkpoint.daa_score + self.finality_depth {
+                continue;
+            }
+            if header.blue_work <= checkpoint.blue_work {
+                return Err(RuleError::ViolatingCheckpoint(checkpoint.hash));
+            }
+            let in_past = header
+                .direct_parents()
+                .iter()
+                .try_fold(false, |in_past, &parent| {
+                    Ok::<_, ReachabilityError>(
+                        in_past
+                            || self
+                                .reachability_service
+                                .is_dag_ancestor_of_result(checkpoint.hash, parent)?,
+                    )
+                });
+            match in_past {
+                Ok(true) => return Ok(()),
+                Ok(false) => return Err(RuleError::ViolatingCheckpoint(checkpoint.hash)),
+                // The checkpoint is not known (yet or anymore), fall back to an older one
+                Err(_) => continue,
+            }
+        }
+        Ok(())
+    }
+
     pub fn check_indirect_parents(
         &self,
         ctx: &mut HeaderProcessingContext,
@@ -171,3 +206,115 @@ impl HeaderProcessor {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{consensus::test_consensus::TestConsensus, errors::RuleError};
+    use karlsen_consensus_core::{
+        api::ConsensusApi,
+        config::{checkpoints::Checkpoint, params::MAINNET_PARAMS, ConfigBuilder},
+        BlueWorkType,
+    };
+    use karlsen_hashes::Hash;
+
+    const FINALITY_DEPTH: u64 = 10;
+
+    fn consensus_with_checkpoint(checkpoint: Hash, blue_work: BlueWorkType) -> TestConsensus {
+        let genesis_daa_score = MAINNET_PARAMS.genesis.daa_score;
+        let checkpoints: &'static [Checkpoint] = Box::leak(Box::new([Checkpoint::new(
+            checkpoint,
+            genesis_daa_score + 3,
+            blue_work,
+        )]));
+        let config = ConfigBuilder::new(MAINNET_PARAMS)
+            .skip_proof_of_work()
+            .edit_consensus_params(|p| {
+                p.finality_depth = FINALITY_DEPTH;
+                p.checkpoints = checkpoints;
+            })
+            .build();
+        TestConsensus::new(&config)
+    }
+
+    /// Adds a chain of `len` blocks with hashes starting at `first` on top of `parent`, returning
+    /// the result of the header validation of the last block
+    async fn add_chain(
+        consensus: &TestConsensus,
+        parent: Hash,
+        first: u64,
+        len: u64,
+    ) -> Result<(), RuleError> {
+        let mut parent = parent;
+        for hash in (first..first + len).map(Hash::from_u64_word) {
+            let block = consensus.build_block_with_parents(hash, vec![parent]);
+            consensus
+                .validate_and_insert_block(block.to_immutable())
+                .block_task
+                .await?;
+            parent = hash;
+        }
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_checkpoint_mismatch() {
+        let checkpoint = Hash::from_u64_word(3);
+        let consensus = consensus_with_checkpoint(checkpoint, 0.into());
+        let wait_handles = consensus.init();
+        let genesis = consensus.params().genesis.hash;
+
+        // The chain holding the checkpoint grows past a finality depth above it
+        add_chain(&consensus, genesis, 1, 3 + FINALITY_DEPTH + 2)
+            .await
+            .unwrap();
+
+        // A side chain missing the checkpoint is accepted up to a finality depth above it only
+        add_chain(&consensus, genesis, 101, 2 + FINALITY_DEPTH)
+            .await
+            .unwrap();
+        let block = consensus.build_block_with_parents(
+            Hash::from_u64_word(103 + FINALITY_DEPTH),
+            vec![Hash::from_u64_word(102 + FINALITY_DEPTH)],
+        );
+        assert!(matches!(
+            consensus
+                .validate_and_insert_block(block.to_immutable())
+                .block_task
+                .await,
+            Err(RuleError::ViolatingCheckpoint(hash)) if hash == checkpoint
+        ));
+
+        consensus.shutdown(wait_handles);
+    }
+
+    #[tokio::test]
+    async fn test_unknown_checkpoint() {
+        // The checkpoint is not in the DAG, so only its blue work is checked
+        let checkpoint = Hash::from_u64_word(1000);
+        let consensus = consensus_with_checkpoint(checkpoint, 0.into());
+        let wait_handles = consensus.init();
+        let genesis = consensus.params().genesis.hash;
+        add_chain(&consensus, genesis, 1, 3 + FINALITY_DEPTH + 2)
+            .await
+            .unwrap();
+        consensus.shutdown(wait_handles);
+
+        // A chain with less blue work than the checkpoint cannot hold it
+        let consensus = consensus_with_checkpoint(checkpoint, BlueWorkType::MAX);
+        let wait_handles = consensus.init();
+        add_chain(&consensus, genesis, 1, 2 + FINALITY_DEPTH)
+            .await
+            .unwrap();
+        assert!(matches!(
+            add_chain(
+                &consensus,
+                Hash::from_u64_word(2 + FINALITY_DEPTH),
+                3 + FINALITY_DEPTH,
+                1
+            )
+            .await,
+            Err(RuleError::ViolatingCheckpoint(hash)) if hash == checkpoint
+        ));
+        consensus.shutdown(wait_handles);
+    }
+}
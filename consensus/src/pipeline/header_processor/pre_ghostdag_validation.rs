@@ -1,15 +1,15 @@
 use super::*;
-use crate::constants;
 use crate::errors::{BlockProcessResult, RuleError};
 use crate::model::services::reachability::ReachabilityService;
 use crate::model::stores::statuses::StatusesStoreReader;
-use karlsen_consensus_core::blockhash::BlockHashExtensions;
 use karlsen_consensus_core::blockstatus::BlockStatus::StatusInvalid;
 use karlsen_consensus_core::header::Header;
+use karlsen_consensus_core::header_validation::{
+    check_block_timestamp_in_isolation, check_header_version, check_parents_limit,
+    check_parents_not_origin,
+};
 use karlsen_consensus_core::BlockLevel;
-use karlsen_core::time::unix_now;
 use karlsen_database::prelude::StoreResultExtensions;
-use std::cmp::max;
 
 impl HeaderProcessor {
     /// Validates the header in isolation including pow check against header declared bits.
@@ -25,10 +25,10 @@ impl HeaderProcessor {
         println!("header hash : {:?}", header.hash);
         println!("header hash_merkle_root : {:?}", header.hash_merkle_root);
         */
-        self.check_header_version(header, hf_daa_score)?;
-        self.check_block_timestamp_in_isolation(header)?;
-        self.check_parents_limit(header)?;
-        Self::check_parents_not_origin(header)?;
+        check_header_version(header, hf_daa_score)?;
+        check_block_timestamp_in_isolation(header, self.timestamp_deviation_tolerance)?;
+        check_parents_limit(header, self.max_block_parents)?;
+        check_parents_not_origin(header)?;
         self.check_pow_and_calc_block_level(header)
     }
 
@@ -38,63 +38,6 @@ impl HeaderProcessor {
         Ok(())
     }
 
-    // TODO : setup dual block version managment
-    fn check_header_version(&self, header: &Header, hf_daa_score: u64) -> BlockProcessResult<()> {
-        if header.daa_score >= hf_daa_score && header.version != constants::BLOCK_VERSION_KHASHV2 {
-            return Err(RuleError::WrongBlockVersion(
-                header.version,
-                constants::BLOCK_VERSION_KHASHV2,
-            ));
-        } else if header.daa_score < hf_daa_score
-            && header.version != constants::BLOCK_VERSION_KHASHV1
-        {
-            return Err(RuleError::WrongBlockVersion(
-                header.version,
-                constants::BLOCK_VERSION_KHASHV1,
-            ));
-        }
-        Ok(())
-    }
-
-    fn check_block_timestamp_in_isolation(&self, header: &Header) -> BlockProcessResult<()> {
-        // Timestamp deviation tolerance is in seconds so we multiply by 1000 to get milliseconds (without BPS dependency)
-        let max_block_time = unix_now() + self.timestamp_deviation_tolerance * 1000;
-        if header.timestamp > max_block_time {
-            return Err(RuleError::TimeTooFarIntoTheFuture(
-                header.timestamp,
-                max_block_time,
-            ));
-        }
-        Ok(())
-    }
-
-    fn check_parents_limit(&self, header: &Header) -> BlockProcessResult<()> {
-        if header.direct_parents().is_empty() {
-            return Err(RuleError::NoParents);
-        }
-
-        if header.direct_parents().len() > self.max_block_parents as usize {
-            return Err(RuleError::TooManyParents(
-                header.direct_parents().len(),
-                self.max_block_parents as usize,
-            ));
-        }
-
-        Ok(())
-    }
-
-    fn check_parents_not_origin(header: &Header) -> BlockProcessResult<()> {
-        if header
-            .direct_parents()
-            .iter()
-            .any(|&parent| parent.is_origin())
-        {
-            return Err(RuleError::OriginParent);
-        }
-
-        Ok(())
-    }
-
     fn check_parents_exist(&self, header: &Header) -> BlockProcessResult<()> {
         let mut missing_parents = Vec::new();
         for parent in header.direct_parents() {
@@ -133,13 +76,80 @@ impl HeaderProcessor {
     }
 
     fn check_pow_and_calc_block_level(&self, header: &Header) -> BlockProcessResult<BlockLevel> {
-        let state = karlsen_pow::State::new(header);
-        let (passed, pow) = state.check_pow(header.nonce);
-        if passed || self.skip_proof_of_work {
-            let signed_block_level = self.max_block_level as i64 - pow.bits() as i64;
-            Ok(max(signed_block_level, 0) as BlockLevel)
-        } else {
-            Err(RuleError::InvalidPoW)
+        // The calling thread keeps processing other blocks while the pow pool is busy
+        self.pow_pool.install(|| {
+            karlsen_pow::check_pow_and_calc_block_level(
+                header,
+                self.max_block_level,
+                self.skip_proof_of_work,
+            )
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::consensus::{test_consensus::TestConsensus, Consensus};
+    use karlsen_consensus_core::{
+        api::ConsensusApi,
+        blockstatus::BlockStatus,
+        config::{params::MAINNET_PARAMS, ConfigBuilder},
+    };
+    use karlsen_hashes::Hash;
+    use std::sync::{Arc, Mutex};
+
+    /// A proof of work permit recording the status of its block when released
+    struct StatusProbe {
+        consensus: Arc<Consensus>,
+        hash: Hash,
+        released_at: Arc<Mutex<Option<Option<BlockStatus>>>>,
+    }
+
+    impl Drop for StatusProbe {
+        fn drop(&mut self) {
+            *self.released_at.lock().unwrap() = Some(self.consensus.get_block_status(self.hash));
         }
     }
+
+    #[tokio::test]
+    async fn test_pow_permit_release() {
+        let config = ConfigBuilder::new(MAINNET_PARAMS)
+            .skip_proof_of_work()
+            .build();
+        let consensus = TestConsensus::new(&config);
+        let wait_handles = consensus.init();
+        let submit = |hash: Hash, version: Option<u16>| {
+            let mut block = consensus.build_block_with_parents(hash, vec![config.genesis.hash]);
+            if let Some(version) = version {
+                block.header.version = version;
+            }
+            let released_at = Arc::new(Mutex::new(None));
+            let probe = StatusProbe {
+                consensus: consensus.consensus_clone(),
+                hash,
+                released_at: released_at.clone(),
+            };
+            let futures = consensus
+                .validate_and_insert_block_with_pow_permit(block.to_immutable(), Box::new(probe));
+            (futures.block_task, released_at)
+        };
+
+        // The permit is released once the proof of work was checked, before the header is committed
+        let (block_task, released_at) = submit(Hash::from_u64_word(1), None);
+        assert_eq!(block_task.await.unwrap(), BlockStatus::StatusHeaderOnly);
+        assert_eq!(*released_at.lock().unwrap(), Some(None));
+
+        // Known blocks and blocks rejected before the proof of work check release it as well
+        let (block_task, released_at) = submit(Hash::from_u64_word(1), None);
+        assert_eq!(block_task.await.unwrap(), BlockStatus::StatusHeaderOnly);
+        assert_eq!(
+            *released_at.lock().unwrap(),
+            Some(Some(BlockStatus::StatusHeaderOnly))
+        );
+        let (block_task, released_at) = submit(Hash::from_u64_word(2), Some(u16::MAX));
+        assert!(block_task.await.is_err());
+        assert_eq!(*released_at.lock().unwrap(), Some(None));
+
+        consensus.shutdown(wait_handles);
+    }
 }
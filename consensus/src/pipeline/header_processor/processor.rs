@@ -27,7 +27,7 @@ use crate::{
     },
     params::Params,
     pipeline::deps_manager::{
-        BlockProcessingMessage, BlockTask, BlockTaskDependencyManager, TaskId,
+        BlockProcessingMessage, BlockTask, BlockTaskDependencyManager, PowPermitSlot, TaskId,
     },
     processes::{
         ghostdag::ordering::SortableBlock, reachability::inquirer as reachability,
@@ -39,7 +39,7 @@ use itertools::Itertools;
 use karlsen_consensus_core::{
     blockhash::{BlockHashes, ORIGIN},
     blockstatus::BlockStatus::{self, StatusHeaderOnly, StatusInvalid},
-    config::genesis::GenesisBlock,
+    config::{checkpoints::Checkpoint, genesis::GenesisBlock},
     header::Header,
     BlockHashSet, BlockLevel,
 };
@@ -115,8 +115,9 @@ pub struct HeaderProcessor {
     receiver: Receiver<BlockProcessingMessage>,
     body_sender: Sender<BlockProcessingMessage>,
 
-    // Thread pool
+    // Thread pools
     pub(super) thread_pool: Arc<ThreadPool>,
+    pub(super) pow_pool: Arc<ThreadPool>,
 
     // Config
     pub(super) genesis: GenesisBlock,
@@ -128,6 +129,8 @@ pub struct HeaderProcessor {
     pub(super) max_block_level: BlockLevel,
     pub(super) hf_daa_score: u64,
     pub(super) difficulty_window_size: usize,
+    pub(super) finality_depth: u64,
+    pub(super) checkpoints: &'static [Checkpoint],
 
     // DB
     db: Arc<DB>,
@@ -170,6 +173,7 @@ impl HeaderProcessor {
         receiver: Receiver<BlockProcessingMessage>,
         body_sender: Sender<BlockProcessingMessage>,
         thread_pool: Arc<ThreadPool>,
+        pow_pool: Arc<ThreadPool>,
         params: &Params,
         db: Arc<DB>,
         storage: &Arc<ConsensusStorage>,
@@ -181,6 +185,7 @@ impl HeaderProcessor {
             receiver,
             body_sender,
             thread_pool,
+            pow_pool,
             genesis: params.genesis.clone(),
             db,
 
@@ -219,6 +224,8 @@ impl HeaderProcessor {
             max_block_level: params.max_block_level,
             hf_daa_score: params.hf_daa_score,
             difficulty_window_size: params.legacy_difficulty_window_size,
+            finality_depth: params.finality_depth,
+            checkpoints: params.checkpoints,
         }
     }
 
@@ -257,6 +264,8 @@ impl HeaderProcessor {
     fn queue_block(self: &Arc<HeaderProcessor>, task_id: TaskId) {
         if let Some(task) = self.task_manager.try_begin(task_id) {
             let res = self.process_header(&task);
+            // Known and rejected blocks may never reach the proof of work check
+            task.release_pow_permit();
 
             let dependent_tasks = self.task_manager.end(
                 task,
@@ -301,8 +310,8 @@ impl HeaderProcessor {
 
         // Validate the header depending on task type
         match task {
-            BlockTask::Ordinary { .. } => {
-                let ctx = self.validate_header(header)?;
+            BlockTask::Ordinary { pow_permit, .. } => {
+                let ctx = self.validate_header(header, pow_permit)?;
                 self.commit_header(ctx, header);
             }
             BlockTask::Trusted { .. } => {
@@ -321,8 +330,15 @@ impl HeaderProcessor {
     }
 
     /// Runs full ordinary header validation
-    fn validate_header(&self, header: &Arc<Header>) -> BlockProcessResult<HeaderProcessingContext> {
-        let block_level = self.validate_header_in_isolation(header, self.hf_daa_score)?;
+    fn validate_header(
+        &self,
+        header: &Arc<Header>,
+        pow_permit: &PowPermitSlot,
+    ) -> BlockProcessResult<HeaderProcessingContext> {
+        let block_level = self.validate_header_in_isolation(header, self.hf_daa_score);
+        // The permit only covers the proof of work check
+        pow_permit.release();
+        let block_level = block_level?;
         self.validate_parent_relations(header)?;
         let mut ctx = self.build_processing_context(header, block_level);
         self.ghostdag(&mut ctx);
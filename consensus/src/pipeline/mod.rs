@@ -1,4 +1,5 @@
 pub mod body_processor;
+pub mod cold_storage_processor;
 pub mod deps_manager;
 pub mod header_processor;
 pub mod monitor;
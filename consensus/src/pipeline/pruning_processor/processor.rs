@@ -22,11 +22,12 @@ use crate::{
             utxo_diffs::UtxoDiffsStoreReader,
         },
     },
+    pipeline::cold_storage_processor::processor::ColdStorageProcessingMessage,
     processes::{
         pruning_proof::PruningProofManager, reachability::inquirer as reachability, relations,
     },
 };
-use crossbeam_channel::Receiver as CrossbeamReceiver;
+use crossbeam_channel::{Receiver as CrossbeamReceiver, Sender as CrossbeamSender};
 use itertools::Itertools;
 use karlsen_consensus_core::{
     blockhash::ORIGIN,
@@ -66,6 +67,7 @@ pub enum PruningProcessingMessage {
 pub struct PruningProcessor {
     // Channels
     receiver: CrossbeamReceiver<PruningProcessingMessage>,
+    cold_storage_sender: Option<CrossbeamSender<ColdStorageProcessingMessage>>,
 
     // DB
     db: Arc<DB>,
@@ -100,6 +102,7 @@ impl Deref for PruningProcessor {
 impl PruningProcessor {
     pub fn new(
         receiver: CrossbeamReceiver<PruningProcessingMessage>,
+        cold_storage_sender: Option<CrossbeamSender<ColdStorageProcessingMessage>>,
         db: Arc<DB>,
         storage: &Arc<ConsensusStorage>,
         services: &Arc<ConsensusServices>,
@@ -109,6 +112,7 @@ impl PruningProcessor {
     ) -> Self {
         Self {
             receiver,
+            cold_storage_sender,
             db,
             storage: storage.clone(),
             reachability_service: services.reachability_service.clone(),
@@ -131,11 +135,21 @@ impl PruningProcessor {
         // in order to make sure the node is already connected and receiving blocks before we start background recovery operations
         self.recover_pruning_workflows_if_needed();
         self.advance_pruning_point_and_candidate_if_possible(sink_ghostdag_data);
+        self.notify_cold_storage(sink_ghostdag_data);
 
         while let Ok(PruningProcessingMessage::Process { sink_ghostdag_data }) =
             self.receiver.recv()
         {
             self.advance_pruning_point_and_candidate_if_possible(sink_ghostdag_data);
+            self.notify_cold_storage(sink_ghostdag_data);
+        }
+    }
+
+    /// Forwards the new sink to the cold storage processor, if any. The latter catches up with the
+    /// latest sink it gets, so a message is simply dropped if the channel is full.
+    fn notify_cold_storage(&self, sink_ghostdag_data: CompactGhostdagData) {
+        if let Some(sender) = self.cold_storage_sender.as_ref() {
+            let _ = sender.try_send(ColdStorageProcessingMessage::Process { sink_ghostdag_data });
         }
     }
 
@@ -125,6 +125,7 @@ pub struct VirtualStateProcessor {
     pub(super) pruning_depth: u64,
     pub(super) hf_daa_score: u64,
     pub(super) difficulty_window_size: usize,
+    pub(super) assume_valid: Option<Hash>,
 
     // Stores
     pub(super) statuses_store: Arc<RwLock<DbStatusesStore>>,
@@ -182,6 +183,7 @@ impl VirtualStateProcessor {
         pruning_receiver: CrossbeamReceiver<PruningProcessingMessage>,
         thread_pool: Arc<ThreadPool>,
         params: &Params,
+        assume_valid: Option<Hash>,
         db: Arc<DB>,
         storage: &Arc<ConsensusStorage>,
         services: &Arc<ConsensusServices>,
@@ -201,6 +203,7 @@ impl VirtualStateProcessor {
             pruning_depth: params.pruning_depth,
             hf_daa_score: params.hf_daa_score,
             difficulty_window_size: params.legacy_difficulty_window_size,
+            assume_valid,
 
             db,
             statuses_store: storage.statuses_store.clone(),
@@ -500,11 +503,18 @@ impl VirtualStateProcessor {
                         selected_parent_multiset_hash,
                     );
 
-                    self.calculate_utxo_state(&mut ctx, &selected_parent_utxo_view, pov_daa_score);
+                    let assumed_valid = self.is_assumed_valid(current);
+                    self.calculate_utxo_state(
+                        &mut ctx,
+                        &selected_parent_utxo_view,
+                        pov_daa_score,
+                        assumed_valid,
+                    );
                     let res = self.verify_expected_utxo_state(
                         &mut ctx,
                         &selected_parent_utxo_view,
                         &header,
+                        assumed_valid,
                     );
 
                     if let Err(rule_error) = res {
@@ -625,6 +635,7 @@ impl VirtualStateProcessor {
             &mut ctx,
             &selected_parent_utxo_view,
             virtual_daa_window.daa_score,
+            false,
         );
 
         // Update the accumulated diff
@@ -8,7 +8,7 @@ use karlsen_consensus_core::{
     blockstatus::BlockStatus,
     coinbase::MinerData,
     config::{params::MAINNET_PARAMS, ConfigBuilder},
-    tx::{ScriptPublicKey, ScriptVec, Transaction},
+    tx::{ScriptPublicKey, ScriptVec, Transaction, TransactionOutpoint},
     BlockHashSet,
 };
 use karlsen_hashes::Hash;
@@ -376,6 +376,94 @@ async fn double_search_disqualified_test() {
     ctx.assert_tips_num(1);
 }
 
+#[tokio::test]
+async fn assume_valid_test() {
+    let assume_valid = Hash::from_u64_word(3);
+    let config = ConfigBuilder::new(MAINNET_PARAMS)
+        .skip_proof_of_work()
+        .apply_args(|config| config.assume_valid = Some(assume_valid))
+        .build();
+    let consensus = TestConsensus::new(&config);
+    let wait_handles = consensus.init();
+
+    // Blocks are assumed valid up to the assume-valid block, which is unknown until synced
+    assert!(!consensus
+        .virtual_processor()
+        .is_assumed_valid(config.genesis.hash));
+    let mut parent = config.genesis.hash;
+    for hash in (1..=5).map(Hash::from_u64_word) {
+        let status = consensus
+            .add_utxo_valid_block_with_parents(hash, vec![parent], vec![])
+            .await
+            .unwrap();
+        // Skipping the script checks keeps verifying the UTXO state of the blocks
+        assert_eq!(status, BlockStatus::StatusUTXOValid);
+        parent = hash;
+    }
+    for (hash, assumed_valid) in [
+        (config.genesis.hash, true),
+        (Hash::from_u64_word(1), true),
+        (assume_valid, true),
+        (Hash::from_u64_word(4), false),
+        (Hash::from_u64_word(5), false),
+    ] {
+        assert_eq!(
+            consensus.virtual_processor().is_assumed_valid(hash),
+            assumed_valid,
+            "block {hash}"
+        );
+    }
+
+    consensus.shutdown(wait_handles);
+}
+
+#[tokio::test]
+async fn read_view_test() {
+    let config = ConfigBuilder::new(MAINNET_PARAMS)
+        .skip_proof_of_work()
+        .build();
+    let consensus = TestConsensus::new(&config);
+    let wait_handles = consensus.init();
+
+    let (first, second) = (Hash::from_u64_word(1), Hash::from_u64_word(2));
+    consensus
+        .add_utxo_valid_block_with_parents(first, vec![config.genesis.hash], vec![])
+        .await
+        .unwrap();
+    let view = consensus.read_view();
+
+    // Virtual processing goes on while the view is alive
+    consensus
+        .add_utxo_valid_block_with_parents(second, vec![first], vec![])
+        .await
+        .unwrap();
+    assert_eq!(consensus.get_sink(), second);
+    let coinbase = consensus.get_block(second).unwrap().transactions[0].id();
+    let outpoint = TransactionOutpoint::new(coinbase, 0);
+    assert!(consensus.get_virtual_utxo_entries(&[outpoint])[0].is_some());
+
+    // The view keeps reading the virtual state it was taken at
+    assert_eq!(view.get_sink(), first);
+    assert!(view.is_chain_block(first).unwrap());
+    assert!(!view.is_chain_block(second).unwrap());
+    assert_eq!(
+        view.get_virtual_chain_from_block(config.genesis.hash)
+            .unwrap()
+            .added,
+        vec![first]
+    );
+    assert_eq!(view.get_virtual_utxo_entries(&[outpoint]), vec![None]);
+    drop(view);
+
+    let view = consensus.read_view();
+    assert_eq!(view.get_sink(), second);
+    assert!(view.is_chain_block(second).unwrap());
+    assert!(view.get_virtual_utxo_entries(&[outpoint])[0].is_some());
+    drop(view);
+
+    consensus.shutdown(wait_handles);
+}
+
 fn new_miner_data() -> MinerData {
     let secp = secp256k1::Secp256k1::new();
     let mut rng = rand::thread_rng();
@@ -7,9 +7,12 @@ use crate::{
             InvalidTransactionsInUtxoContext,
         },
     },
-    model::stores::{
-        block_transactions::BlockTransactionsStoreReader, daa::DaaStoreReader,
-        ghostdag::GhostdagData,
+    model::{
+        services::reachability::ReachabilityService,
+        stores::{
+            block_transactions::BlockTransactionsStoreReader, daa::DaaStoreReader,
+            ghostdag::GhostdagData,
+        },
     },
     processes::{
         mass::Kip9Version,
@@ -76,12 +79,26 @@ impl<'a> UtxoProcessingContext<'a> {
 }
 
 impl VirtualStateProcessor {
-    /// Calculates UTXO state and transaction acceptance data relative to the selected parent state
+    /// Returns true if the scripts of the transactions of `block` and of its mergeset are assumed
+    /// valid, that is if the block is in the past of the assume-valid block (inclusive). The UTXO
+    /// commitment of the block is still verified.
+    pub(super) fn is_assumed_valid(&self, block: Hash) -> bool {
+        // The assume-valid block may not be known yet, until its header is synced
+        self.assume_valid.is_some_and(|assume_valid| {
+            self.reachability_service
+                .is_dag_ancestor_of_result(block, assume_valid)
+                .unwrap_or(false)
+        })
+    }
+
+    /// Calculates UTXO state and transaction acceptance data relative to the selected parent state.
+    /// Script checks are skipped if the chain block is `assumed_valid`.
     pub(super) fn calculate_utxo_state<V: UtxoView + Sync>(
         &self,
         ctx: &mut UtxoProcessingContext,
         selected_parent_utxo_view: &V,
         pov_daa_score: u64,
+        assumed_valid: bool,
     ) {
         let selected_parent_transactions = self
             .block_transactions_store
@@ -116,7 +133,7 @@ impl VirtualStateProcessor {
 
             // No need to fully validate selected parent transactions since selected parent txs were already validated
             // as part of selected parent UTXO state verification with the exact same UTXO context.
-            let validation_flags = if is_selected_parent {
+            let validation_flags = if is_selected_parent || assumed_valid {
                 TxValidationFlags::SkipScriptChecks
             } else {
                 TxValidationFlags::Full
@@ -128,13 +145,16 @@ impl VirtualStateProcessor {
                 validation_flags,
             );
 
+            // Large blocks make the UTXO commitment update a hot spot, so it is done as a parallel batch
+            ctx.multiset_hash.add_transactions(
+                validated_transactions.iter().map(|(tx, _)| tx),
+                pov_daa_score,
+            );
             let mut block_fee = 0u64;
             for (validated_tx, _) in validated_transactions.iter() {
                 ctx.mergeset_diff
                     .add_transaction(validated_tx, pov_daa_score)
                     .unwrap();
-                ctx.multiset_hash
-                    .add_transaction(validated_tx, pov_daa_score);
                 ctx.accepted_tx_ids.push(validated_tx.id());
                 block_fee += validated_tx.calculated_fee;
             }
@@ -200,6 +220,7 @@ impl VirtualStateProcessor {
         ctx: &mut UtxoProcessingContext,
         selected_parent_utxo_view: &V,
         header: &Header,
+        assumed_valid: bool,
     ) -> BlockProcessResult<()> {
         // Verify header UTXO commitment
         let expected_commitment = ctx.multiset_hash.finalize();
@@ -243,11 +264,16 @@ impl VirtualStateProcessor {
 
         // Verify all transactions are valid in context
         let current_utxo_view = selected_parent_utxo_view.compose(&ctx.mergeset_diff);
+        let validation_flags = if assumed_valid {
+            TxValidationFlags::SkipScriptChecks
+        } else {
+            TxValidationFlags::Full
+        };
         let validated_transactions = self.validate_transactions_in_parallel(
             &txs,
             &current_utxo_view,
             header.daa_score,
-            TxValidationFlags::Full,
+            validation_flags,
         );
         if validated_transactions.len() < txs.len() - 1 {
             // Some non-coinbase transactions are invalid
@@ -235,14 +235,7 @@ impl<T: HeaderStoreReader, U: ReachabilityStoreReader, V: RelationsStoreReader>
     }
 
     pub fn parents_at_level<'a>(&'a self, header: &'a Header, level: u8) -> &'a [Hash] {
-        if header.parents_by_level.is_empty() {
-            // If is genesis
-            &[]
-        } else if header.parents_by_level.len() > level as usize {
-            &header.parents_by_level[level as usize][..]
-        } else {
-            std::slice::from_ref(&self.genesis_hash)
-        }
+        header.parents_at_level(level, &self.genesis_hash)
     }
 }
 
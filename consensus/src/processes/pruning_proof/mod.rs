@@ -21,7 +21,10 @@ use karlsen_consensus_core::{
         pruning::{PruningImportError, PruningImportResult},
     },
     header::Header,
-    pruning::{PruningPointProof, PruningPointTrustedData},
+    pruning::{
+        check_proof_header_level, check_proof_selected_tip, proof_pruning_point_header,
+        PruningPointProof, PruningPointTrustedData,
+    },
     trusted::{TrustedBlock, TrustedGhostdagData, TrustedHeader},
     BlockHashMap, BlockHashSet, BlockLevel, HashMapCustomHasher, KType,
 };
@@ -486,17 +489,8 @@ impl PruningProofManager {
         &self,
         proof: &PruningPointProof,
     ) -> PruningImportResult<()> {
-        if proof.len() != self.max_block_level as usize + 1 {
-            return Err(PruningImportError::ProofNotEnoughLevels(
-                self.max_block_level as usize + 1,
-            ));
-        }
-        if proof[0].is_empty() {
-            return Err(PruningImportError::PruningProofNotEnoughHeaders);
-        }
-
+        let proof_pp_header = proof_pruning_point_header(proof, self.max_block_level)?;
         let headers_estimate = self.estimate_proof_unique_size(proof);
-        let proof_pp_header = proof[0].last().expect("checked if empty");
         let proof_pp = proof_pp_header.hash;
         let proof_pp_level = calc_block_level(proof_pp_header, self.max_block_level);
         let (db_lifetime, db) =
@@ -582,20 +576,10 @@ impl PruningProofManager {
             let mut selected_tip = None;
             for (i, header) in proof[level as usize].iter().enumerate() {
                 let header_level = calc_block_level(header, self.max_block_level);
-                /* TODO analyse & fix that */
-                if header_level < level {
-                    //headers_store.insert(header.hash, header.clone(), level).unwrap_or_exists();
-                    //println!("block {0} level is {1} when it's expected to be at least {2}", header.hash, header_level, level);
-                    return Err(PruningImportError::PruningProofWrongBlockLevel(
-                        header.hash,
-                        header_level,
-                        level,
-                    ));
-                } else {
-                    headers_store
-                        .insert(header.hash, header.clone(), header_level)
-                        .unwrap_or_exists();
-                }
+                check_proof_header_level(header, header_level, level)?;
+                headers_store
+                    .insert(header.hash, header.clone(), header_level)
+                    .unwrap_or_exists();
 
                 let parents = self
                     .parents_manager
@@ -682,19 +666,12 @@ impl PruningProofManager {
                 }
             }
 
-            if selected_tip.unwrap() != proof_pp
-                && !self
-                    .parents_manager
-                    .parents_at_level(proof_pp_header, level)
-                    .contains(&selected_tip.unwrap())
-            {
-                return Err(
-                    PruningImportError::PruningProofMissesBlocksBelowPruningPoint(
-                        selected_tip.unwrap(),
-                        level,
-                    ),
-                );
-            }
+            check_proof_selected_tip(
+                selected_tip.unwrap(),
+                proof_pp_header,
+                level,
+                &self.genesis_hash,
+            )?;
 
             selected_tip_by_level[level_idx] = selected_tip;
         }
@@ -0,0 +1,262 @@
+use crate::topology::{NodeInfo, Topology};
+use async_trait::async_trait;
+use futures::{stream::FuturesUnordered, StreamExt};
+use karlsen_core::{debug, info, time::unix_now};
+use karlsen_p2p_lib::{
+    common::ProtocolError,
+    convert::model::version::Version,
+    make_message,
+    pb::{karlsend_message::Payload, AddressesMessage, RequestAddressesMessage},
+    Adaptor, ConnectionInitializer, Hub, KarlsendHandshake, KarlsendMessagePayloadType, Router,
+};
+use karlsen_utils::networking::{IpAddress, NetAddress, PeerId};
+use parking_lot::Mutex;
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    net::SocketAddr,
+    sync::Arc,
+    time::Duration,
+};
+use tokio::time::timeout;
+use uuid::Uuid;
+
+const PROTOCOL_VERSION: u32 = 6;
+
+/// Maximum count of gossiped addresses accepted from a single node
+const MAX_ADDRESSES_RECEIVE: usize = 1000;
+
+/// What a crawled node reported about itself and its address book
+#[derive(Default)]
+struct PeerReport {
+    version: Option<Version>,
+    addresses: Vec<NetAddress>,
+}
+
+/// Connection initializer performing the handshake, requesting the address book of the peer
+/// and recording both. No flow is registered, the connection is terminated right after.
+pub struct CrawlerInitializer {
+    node_id: PeerId,
+    network_name: String,
+    addresses_timeout: Duration,
+    reports: Mutex<HashMap<SocketAddr, PeerReport>>,
+}
+
+impl CrawlerInitializer {
+    pub fn new(network_name: String, addresses_timeout: Duration) -> Self {
+        Self {
+            node_id: Uuid::new_v4().into(),
+            network_name,
+            addresses_timeout,
+            reports: Default::default(),
+        }
+    }
+
+    fn take_report(&self, address: &SocketAddr) -> Option<PeerReport> {
+        self.reports.lock().remove(address)
+    }
+
+    async fn request_addresses(&self, router: &Arc<Router>) -> Result<(), ProtocolError> {
+        // Once ready, the peer starts its own flows, so all messages it may send unsolicited
+        // are routed here in order to keep the connection open
+        let mut incoming_route = router.subscribe(vec![
+            KarlsendMessagePayloadType::Addresses,
+            KarlsendMessagePayloadType::RequestAddresses,
+            KarlsendMessagePayloadType::Ping,
+            KarlsendMessagePayloadType::InvRelayBlock,
+            KarlsendMessagePayloadType::InvTransactions,
+        ]);
+        router
+            .enqueue(make_message!(
+                Payload::RequestAddresses,
+                RequestAddressesMessage {
+                    include_all_subnetworks: false,
+                    subnetwork_id: None
+                }
+            ))
+            .await?;
+
+        loop {
+            let msg = match timeout(self.addresses_timeout, incoming_route.recv()).await {
+                Ok(Some(msg)) => msg,
+                Ok(None) => return Err(ProtocolError::ConnectionClosed),
+                Err(_) => return Err(ProtocolError::Timeout(self.addresses_timeout)),
+            };
+            match msg.payload {
+                Some(Payload::Addresses(msg)) => {
+                    let address_list: Vec<(IpAddress, u16)> = msg.try_into()?;
+                    if address_list.len() > MAX_ADDRESSES_RECEIVE {
+                        return Err(ProtocolError::OtherOwned(format!(
+                            "address count {} exceeded {}",
+                            address_list.len(),
+                            MAX_ADDRESSES_RECEIVE
+                        )));
+                    }
+                    let addresses = address_list
+                        .into_iter()
+                        .map(|(ip, port)| NetAddress::new(ip, port))
+                        .collect();
+                    self.reports
+                        .lock()
+                        .entry(router.net_address())
+                        .or_default()
+                        .addresses = addresses;
+                    return Ok(());
+                }
+                Some(Payload::RequestAddresses(_)) => {
+                    // The crawler has no address to share
+                    router
+                        .enqueue(make_message!(
+                            Payload::Addresses,
+                            AddressesMessage {
+                                address_list: vec![]
+                            }
+                        ))
+                        .await?;
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl ConnectionInitializer for CrawlerInitializer {
+    async fn initialize_connection(&self, router: Arc<Router>) -> Result<(), ProtocolError> {
+        let mut handshake = KarlsendHandshake::new(&router);
+
+        // We start the router receive loop only after we registered to handshake routes
+        router.start();
+
+        let mut self_version_message = Version::new(
+            None,
+            self.node_id,
+            self.network_name.clone(),
+            None,
+            PROTOCOL_VERSION,
+        );
+        self_version_message.add_user_agent(env!("CARGO_PKG_NAME"), env!("CARGO_PKG_VERSION"), &[]);
+
+        let peer_version_message = handshake.handshake(self_version_message.into()).await?;
+        let peer_version: Version = peer_version_message.try_into()?;
+        if peer_version.network != self.network_name {
+            return Err(ProtocolError::WrongNetwork(
+                self.network_name.clone(),
+                peer_version.network,
+            ));
+        }
+        // The version is recorded right away so a node failing to share its addresses
+        // is still reported as reachable
+        self.reports
+            .lock()
+            .entry(router.net_address())
+            .or_default()
+            .version = Some(peer_version);
+
+        handshake.exchange_ready_messages().await?;
+        self.request_addresses(&router).await
+    }
+}
+
+/// Walks the address gossip graph breadth first, starting from the seed addresses
+pub struct Crawler {
+    adaptor: Arc<Adaptor>,
+    initializer: Arc<CrawlerInitializer>,
+    network_name: String,
+    concurrency: usize,
+    max_nodes: usize,
+    include_private: bool,
+}
+
+impl Crawler {
+    pub fn new(
+        network_name: String,
+        addresses_timeout: Duration,
+        concurrency: usize,
+        max_nodes: usize,
+        include_private: bool,
+    ) -> Self {
+        let initializer = Arc::new(CrawlerInitializer::new(
+            network_name.clone(),
+            addresses_timeout,
+        ));
+        let adaptor = Adaptor::client_only(Hub::new(), initializer.clone(), Default::default());
+        Self {
+            adaptor,
+            initializer,
+            network_name,
+            concurrency: concurrency.max(1),
+            max_nodes,
+            include_private,
+        }
+    }
+
+    /// Crawls the network until no new address is discovered or until `max_nodes` nodes were visited
+    pub async fn crawl(&self, seeds: Vec<SocketAddr>) -> Topology {
+        let started = unix_now();
+        let mut queue = VecDeque::new();
+        let mut visited = HashSet::new();
+        for seed in seeds {
+            if visited.insert(seed) {
+                queue.push_back(seed);
+            }
+        }
+
+        let mut nodes = Vec::new();
+        let mut pending = FuturesUnordered::new();
+        loop {
+            while pending.len() < self.concurrency && nodes.len() + pending.len() < self.max_nodes {
+                let Some(address) = queue.pop_front() else {
+                    break;
+                };
+                pending.push(self.visit(address));
+            }
+            let Some(node) = pending.next().await else {
+                break;
+            };
+            for &address in node.peers.iter() {
+                if (self.include_private || IpAddress::from(address.ip()).is_publicly_routable())
+                    && visited.insert(address)
+                {
+                    queue.push_back(address);
+                }
+            }
+            nodes.push(node);
+            if nodes.len() % 100 == 0 {
+                info!(
+                    "Visited {} nodes, {} addresses left in queue",
+                    nodes.len(),
+                    queue.len()
+                );
+            }
+        }
+        self.adaptor.close().await;
+
+        nodes.sort_by_key(|node| node.address);
+        Topology {
+            network: self.network_name.clone(),
+            started,
+            finished: unix_now(),
+            nodes,
+        }
+    }
+
+    async fn visit(&self, address: SocketAddr) -> NodeInfo {
+        debug!("Crawling {}", address);
+        let result = self.adaptor.connect_peer(address.to_string()).await;
+        if let Ok(peer_key) = result.as_ref() {
+            self.adaptor.terminate(*peer_key).await;
+        }
+        let report = self.initializer.take_report(&address).unwrap_or_default();
+        let mut node = NodeInfo::new(address);
+        if let Some(version) = report.version {
+            node.reachable = true;
+            node.protocol_version = Some(version.protocol_version);
+            node.user_agent = Some(version.user_agent);
+            node.services = Some(version.services);
+            node.advertised_address = version.address.map(SocketAddr::from);
+        }
+        node.peers = report.addresses.into_iter().map(SocketAddr::from).collect();
+        node.error = result.err().map(|err| err.to_string());
+        node
+    }
+}
@@ -0,0 +1,173 @@
+use clap::{Arg, ArgAction, Command};
+use karlsen_consensus_core::{config::params::Params, network::NetworkId};
+use karlsen_core::{info, karlsend_env::version, warn};
+use std::{
+    net::{SocketAddr, ToSocketAddrs},
+    str::FromStr,
+    time::Duration,
+};
+
+mod crawler;
+mod topology;
+
+use crawler::Crawler;
+
+pub struct Args {
+    pub network: NetworkId,
+    pub seeds: Vec<String>,
+    pub concurrency: usize,
+    pub max_nodes: usize,
+    pub timeout: u64,
+    pub include_private: bool,
+    pub json: Option<String>,
+    pub dot: Option<String>,
+}
+
+impl Args {
+    fn parse() -> Self {
+        let m = cli().get_matches();
+        Args {
+            network: m.get_one::<NetworkId>("network").cloned().unwrap(),
+            seeds: m
+                .get_many::<String>("seed")
+                .map(|seeds| seeds.cloned().collect())
+                .unwrap_or_default(),
+            concurrency: m.get_one::<usize>("concurrency").cloned().unwrap(),
+            max_nodes: m.get_one::<usize>("max-nodes").cloned().unwrap(),
+            timeout: m.get_one::<u64>("timeout").cloned().unwrap(),
+            include_private: m
+                .get_one::<bool>("include-private")
+                .cloned()
+                .unwrap_or(false),
+            json: m.get_one::<String>("json").cloned(),
+            dot: m.get_one::<String>("dot").cloned(),
+        }
+    }
+}
+
+pub fn cli() -> Command {
+    Command::new("karlsen-crawler")
+        .about(format!("{} (karlsen-crawler) v{}", env!("CARGO_PKG_DESCRIPTION"), version()))
+        .version(env!("CARGO_PKG_VERSION"))
+        .arg(
+            Arg::new("network")
+                .long("network")
+                .short('n')
+                .value_name("network")
+                .default_value("mainnet")
+                .value_parser(|s: &str| NetworkId::from_str(s).map_err(|err| err.to_string()))
+                .help("Network to crawl (mainnet, testnet-<suffix>, devnet or simnet)"),
+        )
+        .arg(
+            Arg::new("seed")
+                .long("seed")
+                .short('s')
+                .value_name("host[:port]")
+                .action(ArgAction::Append)
+                .help("Address to start crawling from, can be repeated. Defaults to the DNS seeders of the network."),
+        )
+        .arg(
+            Arg::new("concurrency")
+                .long("concurrency")
+                .short('c')
+                .default_value("32")
+                .value_parser(clap::value_parser!(usize))
+                .help("Maximum number of nodes crawled concurrently"),
+        )
+        .arg(
+            Arg::new("max-nodes")
+                .long("max-nodes")
+                .default_value("10000")
+                .value_parser(clap::value_parser!(usize))
+                .help("Maximum number of nodes to visit"),
+        )
+        .arg(
+            Arg::new("timeout")
+                .long("timeout")
+                .default_value("30")
+                .value_parser(clap::value_parser!(u64))
+                .help("Seconds to wait for the address list of a node"),
+        )
+        .arg(
+            Arg::new("include-private")
+                .long("include-private")
+                .action(ArgAction::SetTrue)
+                .help("Also crawl gossiped addresses which are not publicly routable"),
+        )
+        .arg(Arg::new("json").long("json").value_name("path").help("Write the topology as JSON to this file"))
+        .arg(Arg::new("dot").long("dot").value_name("path").help("Write the topology as a Graphviz DOT graph to this file"))
+}
+
+/// Resolves `host[:port]` addresses, using the default P2P port of the network when omitted
+fn resolve(address: &str, default_port: u16) -> Vec<SocketAddr> {
+    let resolved = match address.to_socket_addrs() {
+        Ok(addrs) => Ok(addrs),
+        Err(_) => (address, default_port).to_socket_addrs(),
+    };
+    match resolved {
+        Ok(addrs) => addrs.collect(),
+        Err(err) => {
+            warn!("Error resolving {}: {}", address, err);
+            vec![]
+        }
+    }
+}
+
+#[tokio::main]
+async fn main() {
+    karlsen_core::log::init_logger(None, "info");
+    let args = Args::parse();
+    let params = Params::from(args.network);
+
+    let seeds = if args.seeds.is_empty() {
+        params
+            .dns_seeders
+            .iter()
+            .flat_map(|seeder| {
+                info!("Querying DNS seeder {}", seeder);
+                resolve(seeder, params.default_p2p_port())
+            })
+            .collect::<Vec<_>>()
+    } else {
+        args.seeds
+            .iter()
+            .flat_map(|seed| resolve(seed, params.default_p2p_port()))
+            .collect::<Vec<_>>()
+    };
+    if seeds.is_empty() {
+        warn!("No address to start crawling from");
+        return;
+    }
+    info!(
+        "Crawling {} from {} seed addresses",
+        params.network_name(),
+        seeds.len()
+    );
+
+    let crawler = Crawler::new(
+        params.network_name(),
+        Duration::from_secs(args.timeout),
+        args.concurrency,
+        args.max_nodes,
+        args.include_private,
+    );
+    let topology = crawler.crawl(seeds).await;
+    info!(
+        "Visited {} nodes, {} of which are reachable",
+        topology.nodes.len(),
+        topology.reachable_count()
+    );
+
+    let json = topology
+        .to_json()
+        .expect("the topology is always serializable");
+    match args.json {
+        Some(path) => std::fs::write(&path, json).unwrap(),
+        None if args.dot.is_none() => println!("{}", json),
+        None => {}
+    }
+    if let Some(path) = args.dot {
+        let dot = topology.to_dot().expect("writing to a string never fails");
+        std::fs::write(&path, dot).unwrap();
+    }
+}
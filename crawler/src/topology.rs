@@ -0,0 +1,146 @@
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashSet,
+    fmt::{self, Write},
+    net::SocketAddr,
+};
+
+/// A node visited by the crawler
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NodeInfo {
+    /// Address the node was dialed at
+    pub address: SocketAddr,
+    /// True if the handshake with the node completed
+    pub reachable: bool,
+    pub protocol_version: Option<u32>,
+    pub user_agent: Option<String>,
+    /// Services advertised in the version message of the node
+    pub services: Option<u64>,
+    /// Address the node advertises for itself, if any
+    pub advertised_address: Option<SocketAddr>,
+    /// Addresses gossiped by the node
+    pub peers: Vec<SocketAddr>,
+    /// Connection or protocol error, if any
+    pub error: Option<String>,
+}
+
+impl NodeInfo {
+    pub fn new(address: SocketAddr) -> Self {
+        Self {
+            address,
+            reachable: false,
+            protocol_version: None,
+            user_agent: None,
+            services: None,
+            advertised_address: None,
+            peers: vec![],
+            error: None,
+        }
+    }
+}
+
+/// Result of a crawl: the visited nodes and, through their gossiped addresses, the edges of the
+/// address gossip graph
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Topology {
+    pub network: String,
+    /// Unix time in milliseconds
+    pub started: u64,
+    /// Unix time in milliseconds
+    pub finished: u64,
+    pub nodes: Vec<NodeInfo>,
+}
+
+impl Topology {
+    pub fn reachable_count(&self) -> usize {
+        self.nodes.iter().filter(|node| node.reachable).count()
+    }
+
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string_pretty(self)
+    }
+
+    /// Exports the gossip graph in the Graphviz DOT format. Reachable nodes are filled, and
+    /// addresses which were gossiped but not visited are drawn dashed.
+    pub fn to_dot(&self) -> Result<String, fmt::Error> {
+        let mut dot = String::new();
+        writeln!(dot, "digraph \"{}\" {{", self.network)?;
+        writeln!(dot, "    node [shape=box, fontsize=10];")?;
+
+        let visited: HashSet<_> = self.nodes.iter().map(|node| node.address).collect();
+        for node in self.nodes.iter() {
+            let mut label = node.address.to_string();
+            if let Some(user_agent) = node.user_agent.as_ref() {
+                write!(label, "\\n{}", user_agent.replace('"', "\\\""))?;
+            }
+            if let Some(protocol_version) = node.protocol_version {
+                write!(label, "\\nprotocol v{}", protocol_version)?;
+            }
+            let style = if node.reachable { "filled" } else { "solid" };
+            writeln!(
+                dot,
+                "    \"{}\" [label=\"{}\", style={}];",
+                node.address, label, style
+            )?;
+        }
+
+        let mut unvisited = HashSet::new();
+        for node in self.nodes.iter() {
+            for peer in node.peers.iter() {
+                if !visited.contains(peer) && unvisited.insert(*peer) {
+                    writeln!(dot, "    \"{}\" [style=dashed];", peer)?;
+                }
+                writeln!(dot, "    \"{}\" -> \"{}\";", node.address, peer)?;
+            }
+        }
+        writeln!(dot, "}}")?;
+        Ok(dot)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_topology_export() {
+        let a: SocketAddr = "1.1.1.1:42111".parse().unwrap();
+        let b: SocketAddr = "2.2.2.2:42111".parse().unwrap();
+        let c: SocketAddr = "[2001:db8::1]:42111".parse().unwrap();
+        let topology = Topology {
+            network: "karlsen-mainnet".to_string(),
+            started: 1,
+            finished: 2,
+            nodes: vec![
+                NodeInfo {
+                    reachable: true,
+                    protocol_version: Some(6),
+                    user_agent: Some("/karlsend:2.1.0/".to_string()),
+                    services: Some(0),
+                    peers: vec![b, c],
+                    ..NodeInfo::new(a)
+                },
+                NodeInfo {
+                    error: Some("connection refused".to_string()),
+                    ..NodeInfo::new(b)
+                },
+            ],
+        };
+        assert_eq!(topology.reachable_count(), 1);
+
+        let json = topology.to_json().unwrap();
+        assert_eq!(serde_json::from_str::<Topology>(&json).unwrap(), topology);
+
+        let dot = topology.to_dot().unwrap();
+        assert!(dot.starts_with("digraph \"karlsen-mainnet\" {"));
+        assert!(dot.contains(
+            "\"1.1.1.1:42111\" [label=\"1.1.1.1:42111\\n/karlsend:2.1.0/\\nprotocol v6\", style=filled];"
+        ));
+        assert!(dot.contains("\"2.2.2.2:42111\" [label=\"2.2.2.2:42111\", style=solid];"));
+        assert!(dot.contains("\"[2001:db8::1]:42111\" [style=dashed];"));
+        assert!(dot.contains("\"1.1.1.1:42111\" -> \"[2001:db8::1]:42111\";"));
+        assert_eq!(dot.matches("->").count(), 2);
+    }
+}
@@ -30,6 +30,12 @@ blake2b_hasher! {
     struct MuHashElementHash => b"MuHashElement",
     struct MuHashFinalizeHash => b"MuHashFinalize",
     struct PersonalMessageSigningHash => b"PersonalMessageSigningHash",
+    struct AddressMessageSigningHash => b"KarlsenSignedMessage",
+    struct BlockFilterHash => b"BlockFilterHash",
+    struct BlockFilterHeaderHash => b"BlockFilterHeaderHash",
+    struct SnapshotSegmentHash => b"SnapshotSegmentHash",
+    struct SnapshotManifestHash => b"SnapshotManifestHash",
+    struct WorkBindingHash => b"WorkBindingHash",
 }
 
 sha256_hasher! {
@@ -28,7 +28,7 @@ const LIGHT_CACHE_ROUNDS: i32 = 3;
 
 const LIGHT_CACHE_NUM_ITEMS: u32 = 1179641;
 const FULL_DATASET_NUM_ITEMS: u32 = 37748717;
-const SEED: Hash256 = Hash256([
+const SEED: Hash256 = Hash256::from_le_bytes([
     0xeb, 0x01, 0x63, 0xae, 0xf2, 0xab, 0x1c, 0x5a, 0x66, 0x31, 0x0c, 0x1c, 0x14, 0xd6, 0x0f, 0x42,
     0x55, 0xa9, 0xb3, 0x9b, 0x0e, 0xdf, 0x26, 0x53, 0x98, 0x44, 0xf1, 0x17, 0xad, 0x67, 0x21, 0x19,
 ]);
@@ -36,125 +36,147 @@ const SEED: Hash256 = Hash256([
 const SIZE_U32: usize = std::mem::size_of::<u32>();
 const SIZE_U64: usize = std::mem::size_of::<u64>();
 
+/// Hash containers of the FishHash kernel. The data is held as little-endian `u64` words so the
+/// kernel accesses it with no byte slicing, bytes being only used at the edges (ie. keccak).
 pub trait HashData {
     fn new() -> Self;
     fn from_hash(hash: &Hash) -> Self;
-    fn as_bytes(&self) -> &[u8];
-    fn as_bytes_mut(&mut self) -> &mut [u8];
+    fn words(&self) -> &[u64];
+    fn words_mut(&mut self) -> &mut [u64];
 
+    #[inline(always)]
     fn get_as_u32(&self, index: usize) -> u32 {
-        u32::from_le_bytes(
-            self.as_bytes()[index * SIZE_U32..index * SIZE_U32 + SIZE_U32]
-                .try_into()
-                .unwrap(),
-        )
+        (self.words()[index / 2] >> ((index % 2) * 32)) as u32
     }
 
+    #[inline(always)]
     fn set_as_u32(&mut self, index: usize, value: u32) {
-        self.as_bytes_mut()[index * SIZE_U32..index * SIZE_U32 + SIZE_U32]
-            .copy_from_slice(&value.to_le_bytes())
+        let shift = (index % 2) * 32;
+        let word = &mut self.words_mut()[index / 2];
+        *word = (*word & !(0xffff_ffffu64 << shift)) | ((value as u64) << shift);
     }
 
+    #[inline(always)]
     fn get_as_u64(&self, index: usize) -> u64 {
-        u64::from_le_bytes(
-            self.as_bytes()[index * SIZE_U64..index * SIZE_U64 + SIZE_U64]
-                .try_into()
-                .unwrap(),
-        )
+        self.words()[index]
     }
 
+    #[inline(always)]
     fn set_as_u64(&mut self, index: usize, value: u64) {
-        self.as_bytes_mut()[index * SIZE_U64..index * SIZE_U64 + SIZE_U64]
-            .copy_from_slice(&value.to_le_bytes())
+        self.words_mut()[index] = value
     }
 }
 
-#[derive(Debug)]
-pub struct Hash256([u8; 32]);
+macro_rules! hash_data {
+    ($name:ident, $words:literal, $align:literal) => {
+        #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+        #[repr(C, align($align))]
+        pub struct $name([u64; $words]);
+
+        // Not every container uses every byte adapter
+        #[allow(dead_code)]
+        impl $name {
+            pub const BYTES: usize = $words * SIZE_U64;
+
+            pub const fn from_le_bytes(bytes: [u8; $words * SIZE_U64]) -> Self {
+                let mut words = [0u64; $words];
+                let mut i = 0;
+                while i < $words {
+                    let mut word = [0u8; SIZE_U64];
+                    let mut j = 0;
+                    while j < SIZE_U64 {
+                        word[j] = bytes[i * SIZE_U64 + j];
+                        j += 1;
+                    }
+                    words[i] = u64::from_le_bytes(word);
+                    i += 1;
+                }
+                Self(words)
+            }
 
-impl HashData for Hash256 {
-    fn new() -> Self {
-        Self([0; 32])
-    }
+            pub fn to_le_bytes(&self) -> [u8; $words * SIZE_U64] {
+                let mut bytes = [0u8; $words * SIZE_U64];
+                for (chunk, word) in bytes.chunks_exact_mut(SIZE_U64).zip(self.0.iter()) {
+                    chunk.copy_from_slice(&word.to_le_bytes());
+                }
+                bytes
+            }
+        }
 
-    fn from_hash(hash: &Hash) -> Self {
-        Self(hash.0)
-    }
+        impl HashData for $name {
+            #[inline(always)]
+            fn new() -> Self {
+                Self([0; $words])
+            }
 
-    fn as_bytes(&self) -> &[u8] {
-        &self.0
-    }
+            /// Zero padded if wider than a hash
+            fn from_hash(hash: &Hash) -> Self {
+                let mut result = Self::new();
+                result
+                    .0
+                    .iter_mut()
+                    .zip(hash.iter_le_u64())
+                    .for_each(|(word, hash_word)| *word = hash_word);
+                result
+            }
 
-    fn as_bytes_mut(&mut self) -> &mut [u8] {
-        &mut self.0
-    }
-}
+            #[inline(always)]
+            fn words(&self) -> &[u64] {
+                &self.0
+            }
 
-#[derive(Clone, Copy, Debug)]
-pub struct Hash512([u8; 64]);
+            #[inline(always)]
+            fn words_mut(&mut self) -> &mut [u64] {
+                &mut self.0
+            }
+        }
+    };
+}
 
-impl HashData for Hash512 {
-    fn new() -> Self {
-        Self([0; 64])
-    }
+hash_data!(Hash256, 4, 32);
+hash_data!(Hash512, 8, 64);
+hash_data!(Hash1024, 16, 64);
 
-    //Todo check if filled with 0
-    fn from_hash(hash: &Hash) -> Self {
-        let mut result = Self::new();
-        let (first_half, _) = result.0.split_at_mut(hash.0.len());
-        first_half.copy_from_slice(&hash.0);
-        result
-    }
-
-    fn as_bytes(&self) -> &[u8] {
-        &self.0
+impl Hash512 {
+    /// Sets this hash to the keccak-512 of its own bytes.
+    ///
+    /// The 64 bytes fit in a single block of the 72 bytes rate, so the words are absorbed
+    /// as lanes of the state and the padding is applied directly to the next lanes.
+    #[inline]
+    fn keccak_in_place(&mut self) {
+        let mut state = [0u64; 25];
+        state[..8].copy_from_slice(&self.0);
+        // Keccak padding: 0x01 after the message, 0x80 on the last byte of the rate
+        state[8] = 0x01 | (0x80 << 56);
+        tiny_keccak::keccakf(&mut state);
+        self.0.copy_from_slice(&state[..8]);
     }
 
-    fn as_bytes_mut(&mut self) -> &mut [u8] {
-        &mut self.0
+    /// Returns the keccak-512 of `data`
+    fn keccak(data: &[u8]) -> Self {
+        let mut bytes = [0u8; Self::BYTES];
+        PowFishHash::keccak(&mut bytes, data);
+        Self::from_le_bytes(bytes)
     }
 }
 
 impl BitXor<&Hash512> for &Hash512 {
     type Output = Hash512;
 
+    #[inline(always)]
     fn bitxor(self, rhs: &Hash512) -> Self::Output {
         let mut hash = Hash512::new();
 
-        for i in 0..64 {
-            hash.0[i] = self.0[i] ^ rhs.0[i]
+        for ((word, a), b) in hash.0.iter_mut().zip(self.0.iter()).zip(rhs.0.iter()) {
+            *word = a ^ b
         }
 
         hash
     }
 }
 
-#[derive(Copy, Clone, Debug)]
-pub struct Hash1024([u8; 128]);
-
-impl HashData for Hash1024 {
-    fn new() -> Self {
-        Self([0; 128])
-    }
-
-    //Todo check if filled with 0
-    fn from_hash(hash: &Hash) -> Self {
-        let mut result = Self::new();
-        let (first_half, _) = result.0.split_at_mut(hash.0.len());
-        first_half.copy_from_slice(&hash.0);
-        result
-    }
-
-    fn as_bytes(&self) -> &[u8] {
-        &self.0
-    }
-
-    fn as_bytes_mut(&mut self) -> &mut [u8] {
-        &mut self.0
-    }
-}
-
 impl Hash1024 {
+    #[inline(always)]
     fn from_512s(first: &Hash512, second: &Hash512) -> Self {
         let mut hash = Self::new();
         let (first_half, second_half) = hash.0.split_at_mut(first.0.len());
@@ -163,16 +185,6 @@ impl Hash1024 {
 
         hash
     }
-
-    /*fn from_256s(first: &Hash256, second: &Hash256) -> Self {
-        //filled with 0 by default
-        let mut hash = Self::new();
-        let (first_half, second_half) = hash.0.split_at_mut(first.0.len() * 2);
-        first_half.copy_from_slice(&first.0);
-        second_half.copy_from_slice(&second.0);
-
-        hash
-    }*/
 }
 
 #[derive(Clone)]
@@ -212,8 +224,7 @@ impl Context {
     }*/
 
     fn build_light_cache(cache: &mut [Hash512]) {
-        let mut item: Hash512 = Hash512::new();
-        PowFishHash::keccak(&mut item.0, &SEED.0);
+        let mut item = Hash512::keccak(&SEED.to_le_bytes());
         cache[0] = item;
 
         for cache_item in cache
@@ -221,7 +232,7 @@ impl Context {
             .take(LIGHT_CACHE_NUM_ITEMS as usize)
             .skip(1)
         {
-            PowFishHash::keccak_in_place(&mut item.0);
+            item.keccak_in_place();
             *cache_item = item;
         }
 
@@ -236,13 +247,19 @@ impl Context {
                     (LIGHT_CACHE_NUM_ITEMS.wrapping_add(i.wrapping_sub(1))) % LIGHT_CACHE_NUM_ITEMS;
 
                 let x = &cache[v as usize] ^ &cache[w as usize];
-                PowFishHash::keccak(&mut cache[i as usize].0, &x.0);
+                cache[i as usize] = Hash512::keccak(&x.to_le_bytes());
             }
         }
     }
 }
 
 impl PowFishHash {
+    /// Builds the light cache shared by every FishHash computation of the process, which
+    /// otherwise happens lazily on the first computation
+    pub fn initialize_light_cache() {
+        lazy_static::initialize(&LIGHT_CACHE);
+    }
+
     #[inline]
     //pub fn fishhash_kernel(context: &mut Context, seed: &Hash512) -> Hash256 {
     pub fn fishhash_kernel(seed: &Hash) -> Hash {
@@ -314,7 +331,7 @@ impl PowFishHash {
             mix_hash.set_as_u32(i / 4, h3);
         }
 
-        Hash::from_bytes(mix_hash.0)
+        Hash::from_le_u64(mix_hash.0)
     }
 
     #[inline]
@@ -387,7 +404,7 @@ impl PowFishHash {
             mix_hash.set_as_u32(i / 4, h3);
         }
 
-        Hash::from_bytes(mix_hash.0)
+        Hash::from_le_u64(mix_hash.0)
     }
 
     pub fn keccak(out: &mut [u8], data: &[u8]) {
@@ -396,25 +413,32 @@ impl PowFishHash {
         hasher.finalize(out);
     }
 
-    fn keccak_in_place(data: &mut [u8]) {
-        //TODO remove tiny_keccak with asm keccak
-        let mut hasher = tiny_keccak::Keccak::v512();
-        hasher.update(data);
-        hasher.finalize(data);
-    }
-
     fn fnv1(u: u32, v: u32) -> u32 {
         u.wrapping_mul(FNV_PRIME) ^ v
     }
 
-    fn fnv1_512(u: Hash512, v: Hash512) -> Hash512 {
-        let mut r = Hash512::new();
+    /// FNV1 of both u32 lanes of a word at once
+    #[inline(always)]
+    fn fnv1_u32x2(u: u64, v: u64) -> u64 {
+        const LOW_LANE: u64 = 0xffff_ffff;
+        // The high lane product is computed with the low lane cleared, so no carry crosses the lanes
+        let high = (u & !LOW_LANE).wrapping_mul(FNV_PRIME as u64);
+        let low = u.wrapping_mul(FNV_PRIME as u64) & LOW_LANE;
+        (high | low) ^ v
+    }
 
-        for i in 0..r.0.len() / SIZE_U32 {
-            r.set_as_u32(i, PowFishHash::fnv1(u.get_as_u32(i), v.get_as_u32(i)));
+    /// FNV1 of two independent 512 bits lanes, interleaved so their dependency chains overlap
+    #[inline(always)]
+    fn fnv1_512_x2(u0: &mut Hash512, v0: &Hash512, u1: &mut Hash512, v1: &Hash512) {
+        for (((a0, b0), a1), b1) in
+            u0.0.iter_mut()
+                .zip(v0.0.iter())
+                .zip(u1.0.iter_mut())
+                .zip(v1.0.iter())
+        {
+            *a0 = PowFishHash::fnv1_u32x2(*a0, *b0);
+            *a1 = PowFishHash::fnv1_u32x2(*a1, *b1);
         }
-
-        r
     }
 
     fn calculate_dataset_item_1024(light_cache: &[Hash512], index: usize) -> Hash1024 {
@@ -430,19 +454,23 @@ impl PowFishHash {
         mix0.set_as_u32(0, mix0_seed);
         mix1.set_as_u32(0, mix1_seed);
 
-        PowFishHash::keccak_in_place(&mut mix0.0);
-        PowFishHash::keccak_in_place(&mut mix1.0);
+        mix0.keccak_in_place();
+        mix1.keccak_in_place();
 
         let num_words: u32 = (std::mem::size_of_val(&mix0) / SIZE_U32) as u32;
         for j in 0..FULL_DATASET_ITEM_PARENTS {
             let t0 = PowFishHash::fnv1(seed0 ^ j, mix0.get_as_u32((j % num_words) as usize));
             let t1 = PowFishHash::fnv1(seed1 ^ j, mix1.get_as_u32((j % num_words) as usize));
-            mix0 = PowFishHash::fnv1_512(mix0, light_cache[(t0 % LIGHT_CACHE_NUM_ITEMS) as usize]);
-            mix1 = PowFishHash::fnv1_512(mix1, light_cache[(t1 % LIGHT_CACHE_NUM_ITEMS) as usize]);
+            PowFishHash::fnv1_512_x2(
+                &mut mix0,
+                &light_cache[(t0 % LIGHT_CACHE_NUM_ITEMS) as usize],
+                &mut mix1,
+                &light_cache[(t1 % LIGHT_CACHE_NUM_ITEMS) as usize],
+            );
         }
 
-        PowFishHash::keccak_in_place(&mut mix0.0);
-        PowFishHash::keccak_in_place(&mut mix1.0);
+        mix0.keccak_in_place();
+        mix1.keccak_in_place();
 
         Hash1024::from_512s(&mix0, &mix1)
     }
@@ -578,7 +606,10 @@ mod keccak256 {
 #[cfg(test)]
 mod tests {
 
-    use super::{KHeavyHash, PowHash};
+    use super::{
+        Hash1024, Hash512, HashData, KHeavyHash, PowFishHash, PowHash, FULL_DATASET_ITEM_PARENTS,
+        FULL_DATASET_NUM_ITEMS, LIGHT_CACHE_NUM_ITEMS, SIZE_U32,
+    };
     use crate::Hash;
     use sha3::digest::{ExtendableOutput, Update, XofReader};
     use sha3::{CShake256, CShake256Core};
@@ -618,6 +649,154 @@ mod tests {
         assert_eq!(Hash(hash2), hash1);
     }
 
+    /// Light cache filled with splitmix64 words, which is much faster to build than the actual one
+    fn splitmix_light_cache() -> Vec<Hash512> {
+        let mut state = 0x9e3779b97f4a7c15u64;
+        let mut next = || {
+            state = state.wrapping_add(0x9e3779b97f4a7c15);
+            let mut z = state;
+            z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+            z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+            z ^ (z >> 31)
+        };
+        (0..LIGHT_CACHE_NUM_ITEMS)
+            .map(|_| {
+                let mut item = Hash512::new();
+                item.words_mut().iter_mut().for_each(|word| *word = next());
+                item
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_fishhash_dataset_item_known_answers() {
+        // Computed by the byte array containers preceding the u64 words
+        let light_cache = splitmix_light_cache();
+        let expected = [
+            (0, "310e4f91dede251e4f6f4687538f93cc3222615927cb9097cefa453311ff1c1ed2f2da95042a993fde68582387ac4e2e09a18790714d3ff335b15fbb143c4e78b8ed29855b1a4ad414d65a40f9c14b48b574b82fdae829f6bf27f824bb8f644a2ddd7504de89f43d7f0efb31c72d34efed1ebbb892ce1c9bcd5e1c8d3eb7330f"),
+            (42, "6ef910bdf213d6ceae736658d68b1be6728a992f4401d1c5c8e42819d78f609e6e6def18552ce4e15b7cbc445110963593d91da0adac4f6a37a8a7e8c0f1048b39832031fa698b0bf181dda4134f992e040cc9c85f6f1ae3330fd1ef93cbf7e4db05bf47223f1b57093d46ab727e9988565b1e4ad4c7bddb2f81fca544518cf6"),
+            (FULL_DATASET_NUM_ITEMS as usize - 1, "57566399123eaefd18d0ee5033ed8d409d4e30dbcaaf87a0f521caa5da807dd114e9fe72292fb05a2f130b434b2616224f1e10fdd3d0440db26dbce9e9cc744d426f6903aaeb379e49e561ec4db389206e28d11a329d9ab27d47d2f9808e7e9facceebbaeadf22989522de44b45940ae784ff2c26f612574639854684cb31cf8"),
+        ];
+        for (index, item) in expected {
+            assert_eq!(
+                faster_hex::hex_string(
+                    &PowFishHash::calculate_dataset_item_1024(&light_cache, index).to_le_bytes()
+                ),
+                item
+            );
+        }
+    }
+
+    #[test]
+    #[ignore = "builds the actual light cache, taking minutes in debug builds"]
+    fn test_fishhash_kernel_known_answers() {
+        // Computed by the byte array containers preceding the u64 words
+        let expected = [
+            (
+                Hash([42; 32]),
+                "89d5b03567d7da76d42de9da26d53a6fab61efc4c34efb04ab5dd696c7c3cfb5",
+                "e1aac4350ee31275dc863c8bdc2a47b0bd0b840aab6f6531be87be0f26bda6c2",
+            ),
+            (
+                Hash([0; 32]),
+                "e76e4db67f17d4fb22f6c501c808065ed1678d4f68a52919d620c376b1820bf9",
+                "dd60f37dcba9c805449f8fd1374721b60b7bb2c8b5e3aecb26201a61689998d6",
+            ),
+        ];
+        for (seed, fishhash, fishhashplus) in expected {
+            assert_eq!(PowFishHash::fishhash_kernel(&seed).to_string(), fishhash);
+            assert_eq!(
+                PowFishHash::fishhashplus_kernel(&seed).to_string(),
+                fishhashplus
+            );
+        }
+    }
+
+    #[test]
+    fn test_hash_data_words() {
+        let bytes: [u8; 64] = std::array::from_fn(|i| i as u8);
+        let mut hash = Hash512::from_le_bytes(bytes);
+        assert_eq!(hash.to_le_bytes(), bytes);
+        assert_eq!(std::mem::align_of::<Hash1024>(), 64);
+
+        // Accessors follow the little-endian byte layout
+        assert_eq!(hash.get_as_u32(1), u32::from_le_bytes([4, 5, 6, 7]));
+        assert_eq!(
+            hash.get_as_u64(1),
+            u64::from_le_bytes([8, 9, 10, 11, 12, 13, 14, 15])
+        );
+        hash.set_as_u32(3, 0xdeadbeef);
+        assert_eq!(hash.to_le_bytes()[12..16], 0xdeadbeefu32.to_le_bytes());
+        assert_eq!(hash.get_as_u32(2), u32::from_le_bytes([8, 9, 10, 11]));
+
+        let hash1024 = Hash1024::from_512s(&hash, &Hash512::from_hash(&Hash([42; 32])));
+        assert_eq!(hash1024.to_le_bytes()[..64], hash.to_le_bytes());
+        assert_eq!(hash1024.to_le_bytes()[64..96], [42; 32]);
+        assert_eq!(hash1024.to_le_bytes()[96..], [0; 32]);
+    }
+
+    /// The dataset item calculation processing both lanes one after the other, u32 by u32
+    fn calculate_dataset_item_1024_reference(light_cache: &[Hash512], index: usize) -> Hash1024 {
+        fn fnv1_512(u: Hash512, v: Hash512) -> Hash512 {
+            let mut r = Hash512::new();
+            for i in 0..Hash512::BYTES / SIZE_U32 {
+                r.set_as_u32(i, PowFishHash::fnv1(u.get_as_u32(i), v.get_as_u32(i)));
+            }
+            r
+        }
+
+        let seed0 = (index * 2) as u32;
+        let seed1 = seed0 + 1;
+        let mut mix0 = light_cache[(seed0 % LIGHT_CACHE_NUM_ITEMS) as usize];
+        let mut mix1 = light_cache[(seed1 % LIGHT_CACHE_NUM_ITEMS) as usize];
+        mix0.set_as_u32(0, mix0.get_as_u32(0) ^ seed0);
+        mix1.set_as_u32(0, mix1.get_as_u32(0) ^ seed1);
+        mix0.keccak_in_place();
+        mix1.keccak_in_place();
+
+        let num_words = (Hash512::BYTES / SIZE_U32) as u32;
+        for j in 0..FULL_DATASET_ITEM_PARENTS {
+            let t0 = PowFishHash::fnv1(seed0 ^ j, mix0.get_as_u32((j % num_words) as usize));
+            let t1 = PowFishHash::fnv1(seed1 ^ j, mix1.get_as_u32((j % num_words) as usize));
+            mix0 = fnv1_512(mix0, light_cache[(t0 % LIGHT_CACHE_NUM_ITEMS) as usize]);
+            mix1 = fnv1_512(mix1, light_cache[(t1 % LIGHT_CACHE_NUM_ITEMS) as usize]);
+        }
+        mix0.keccak_in_place();
+        mix1.keccak_in_place();
+
+        Hash1024::from_512s(&mix0, &mix1)
+    }
+
+    #[test]
+    fn test_dual_lane_dataset_item() {
+        let light_cache = splitmix_light_cache();
+        // The cache words double as the random inputs
+        let mut words = light_cache
+            .iter()
+            .flat_map(|item| item.words().iter().copied());
+        let mut next = || words.next().unwrap();
+
+        for _ in 0..64 {
+            let (u, v) = (next(), next());
+            assert_eq!(
+                PowFishHash::fnv1_u32x2(u, v),
+                ((PowFishHash::fnv1((u >> 32) as u32, (v >> 32) as u32) as u64) << 32)
+                    | PowFishHash::fnv1(u as u32, v as u32) as u64
+            );
+        }
+
+        let last = FULL_DATASET_NUM_ITEMS as usize - 1;
+        let random = (0..16)
+            .map(|_| (next() % FULL_DATASET_NUM_ITEMS as u64) as usize)
+            .collect::<Vec<_>>();
+        for index in [0, 1, 42, last].into_iter().chain(random) {
+            assert_eq!(
+                PowFishHash::calculate_dataset_item_1024(&light_cache, index),
+                calculate_dataset_item_1024_reference(&light_cache, index)
+            );
+        }
+    }
+
     #[test]
     fn test_heavy_hash() {
         let val = Hash([42; 32]);
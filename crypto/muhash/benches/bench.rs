@@ -45,6 +45,22 @@ fn bench_muhash(c: &mut Criterion) {
         black_box(muhash);
     });
 
+    c.bench_function("MuHash::add_elements 1000", |b| {
+        let elements: Vec<[u8; 100]> = (0..1000)
+            .map(|_| {
+                let mut element = [0u8; 100];
+                rng.fill_bytes(&mut element);
+                element
+            })
+            .collect();
+        let mut muhash = MuHash::new();
+        b.iter(|| {
+            black_box(&elements);
+            muhash.add_elements(&elements);
+        });
+        black_box(muhash);
+    });
+
     c.bench_function("MuHash::clone", |b| {
         b.iter(|| {
             black_box(&mut rand_set);
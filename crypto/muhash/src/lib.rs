@@ -15,6 +15,8 @@ use std::fmt::Display;
 
 pub const HASH_SIZE: usize = 32;
 pub const SERIALIZED_MUHASH_SIZE: usize = ELEMENT_BYTE_SIZE;
+/// The size of a serialized intermediate (non normalized) state, ie. numerator and denominator
+pub const SERIALIZED_MUHASH_STATE_SIZE: usize = 2 * ELEMENT_BYTE_SIZE;
 // The hash of `NewMuHash().Finalize()`
 pub const EMPTY_MUHASH: Hash = Hash::from_bytes([
     0x54, 0x4e, 0xb3, 0x14, 0x2c, 0x0, 0xf, 0xa, 0xd2, 0xc7, 0x6a, 0xc4, 0x1f, 0x42, 0x22, 0xab,
@@ -24,6 +26,9 @@ pub const EMPTY_MUHASH: Hash = Hash::from_bytes([
 pub(crate) const ELEMENT_BIT_SIZE: usize = 3072;
 pub(crate) const ELEMENT_BYTE_SIZE: usize = ELEMENT_BIT_SIZE / 8;
 
+/// Batches are split in chunks of this many elements, each chunk product being computed by a single thread
+const BATCH_CHUNK_SIZE: usize = 32;
+
 /// MuHash is a type used to create a Multiplicative Hash
 /// which is a rolling(homomorphic) hash that you can add and remove elements from
 /// and receive the same resulting hash as-if you never hashed them.
@@ -84,6 +89,29 @@ impl MuHash {
         MuHashElementBuilder::new(&mut self.denominator)
     }
 
+    /// Hashes and adds a batch of elements. With the `rayon` feature, the elements are hashed and
+    /// their partial products are computed in parallel.
+    pub fn add_elements<T: AsRef<[u8]> + Sync>(&mut self, elements: &[T]) {
+        self.numerator *= elements_product(elements);
+    }
+
+    /// Hashes and removes a batch of elements. With the `rayon` feature, the elements are hashed and
+    /// their partial products are computed in parallel.
+    pub fn remove_elements<T: AsRef<[u8]> + Sync>(&mut self, elements: &[T]) {
+        self.denominator *= elements_product(elements);
+    }
+
+    /// Adds and removes batches of elements, equivalent to calling [`MuHash::add_elements`]
+    /// and [`MuHash::remove_elements`]
+    pub fn update_batch<A, R>(&mut self, added: &[A], removed: &[R])
+    where
+        A: AsRef<[u8]> + Sync,
+        R: AsRef<[u8]> + Sync,
+    {
+        self.add_elements(added);
+        self.remove_elements(removed);
+    }
+
     #[inline]
     // will add the MuHash together. Equivalent to manually adding all the data elements
     // from one set to the other.
@@ -110,6 +138,30 @@ impl MuHash {
         self.numerator.to_le_bytes()
     }
 
+    /// Serializes the intermediate state without normalizing it, hence with no costly modular inversion.
+    /// Unlike [`MuHash::serialize`], the result is not unique for a given set of elements.
+    pub fn serialize_state(&self) -> [u8; SERIALIZED_MUHASH_STATE_SIZE] {
+        let mut data = [0u8; SERIALIZED_MUHASH_STATE_SIZE];
+        data[..ELEMENT_BYTE_SIZE].copy_from_slice(&self.numerator.to_le_bytes());
+        data[ELEMENT_BYTE_SIZE..].copy_from_slice(&self.denominator.to_le_bytes());
+        data
+    }
+
+    pub fn deserialize_state(
+        data: &[u8; SERIALIZED_MUHASH_STATE_SIZE],
+    ) -> Result<Self, OverflowError> {
+        let numerator = U3072::from_le_bytes(data[..ELEMENT_BYTE_SIZE].try_into().unwrap());
+        let denominator = U3072::from_le_bytes(data[ELEMENT_BYTE_SIZE..].try_into().unwrap());
+        if numerator.is_overflow() || denominator.is_overflow() {
+            Err(OverflowError)
+        } else {
+            Ok(Self {
+                numerator,
+                denominator,
+            })
+        }
+    }
+
     #[inline]
     pub fn deserialize(data: [u8; SERIALIZED_MUHASH_SIZE]) -> Result<Self, OverflowError> {
         let numerator = U3072::from_le_bytes(data);
@@ -188,6 +240,30 @@ fn data_to_element(data: &[u8]) -> U3072 {
     U3072::from_le_bytes(bytes)
 }
 
+fn chunk_product<T: AsRef<[u8]>>(chunk: &[T]) -> U3072 {
+    chunk.iter().fold(U3072::one(), |mut product, data| {
+        product *= data_to_element(data.as_ref());
+        product
+    })
+}
+
+fn elements_product<T: AsRef<[u8]> + Sync>(elements: &[T]) -> U3072 {
+    if elements.len() <= BATCH_CHUNK_SIZE {
+        return chunk_product(elements);
+    }
+    cfg_if::cfg_if! {
+        if #[cfg(feature = "rayon")] {
+            use rayon::prelude::*;
+            elements.par_chunks(BATCH_CHUNK_SIZE).map(chunk_product).reduce(U3072::one, |mut a, b| {
+                a *= b;
+                a
+            })
+        } else {
+            chunk_product(elements)
+        }
+    }
+}
+
 impl Default for MuHash {
     #[inline]
     fn default() -> Self {
@@ -198,11 +274,50 @@ impl Default for MuHash {
 #[cfg(test)]
 mod tests {
     use crate::OverflowError;
-    use crate::{MuHash, EMPTY_MUHASH, U3072};
+    use crate::{MuHash, EMPTY_MUHASH, SERIALIZED_MUHASH_STATE_SIZE, U3072};
     use karlsen_hashes::Hash;
     use rand::{Rng, SeedableRng};
     use rand_chacha::ChaCha8Rng;
 
+    #[test]
+    fn test_batch_updates() {
+        let mut rng = ChaCha8Rng::seed_from_u64(42);
+        let elements: Vec<[u8; 32]> = (0..100).map(|_| rng.gen()).collect();
+
+        let mut serial = MuHash::new();
+        elements[..70].iter().for_each(|x| serial.add_element(x));
+        elements[60..].iter().for_each(|x| serial.remove_element(x));
+
+        let mut batched = MuHash::new();
+        batched.update_batch(&elements[..70], &elements[60..]);
+        assert_eq!(batched.finalize(), serial.finalize());
+
+        // Partial products of batches processed apart combine into the same set
+        let mut first = MuHash::new();
+        first.add_elements(&elements[..40]);
+        let mut second = MuHash::new();
+        second.add_elements(&elements[40..70]);
+        second.remove_elements(&elements[60..]);
+        first.combine(&second);
+        assert_eq!(first.finalize(), serial.finalize());
+    }
+
+    #[test]
+    fn test_serialize_state() {
+        let mut set = MuHash::new();
+        set.add_element(&[1, 2, 3]);
+        set.remove_element(&[4, 5, 6]);
+
+        let mut deserialized = MuHash::deserialize_state(&set.serialize_state()).unwrap();
+        assert_eq!(deserialized.finalize(), set.finalize());
+
+        let overflow = [0xffu8; SERIALIZED_MUHASH_STATE_SIZE];
+        assert_eq!(
+            MuHash::deserialize_state(&overflow).unwrap_err(),
+            OverflowError
+        );
+    }
+
     struct TestVector {
         data: &'static [u8],
         multiset_hash: Hash,
@@ -482,7 +482,7 @@ mod tests {
     }
 
     // Otherwise this test it too long
-    #[cfg(feature = "rayon")]
+    #[cfg(all(feature = "rayon", not(debug_assertions)))]
     #[test]
     fn exhuastive_test_div_overflow() {
         use super::PRIME_DIFF;
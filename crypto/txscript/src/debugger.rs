@@ -0,0 +1,173 @@
+//!
+//! Step-wise script evaluation for script authors.
+//!
+//! The engine records its stacks after every opcode it goes through, including the ones
+//! skipped by a false conditional branch, so a failing script can be inspected without
+//! instrumenting the engine itself.
+//!
+
+use crate::{caches::Cache, opcodes::opcode_name, TxScriptEngine};
+use karlsen_consensus_core::{
+    hashing::sighash::SigHashReusedValues,
+    tx::{PopulatedTransaction, VerifiableTransaction},
+};
+use karlsen_txscript_errors::TxScriptError;
+
+/// Engine state after the evaluation of a single opcode
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ScriptStep {
+    /// Index of the evaluated script: 0 for the signature script, 1 for the script public key
+    /// and 2 for the redeem script of a P2SH output
+    pub script_index: usize,
+    /// Index of the opcode in its script
+    pub opcode_index: usize,
+    pub opcode: u8,
+    /// Data pushed by the opcode, if any
+    pub data: Vec<u8>,
+    /// False if the opcode was skipped by a conditional branch
+    pub executed: bool,
+    /// Data stack after the opcode, from bottom to top
+    pub stack: Vec<Vec<u8>>,
+    /// Alt stack after the opcode, from bottom to top
+    pub alt_stack: Vec<Vec<u8>>,
+    /// Error raised by the opcode, always the last step of a trace
+    pub error: Option<String>,
+}
+
+impl ScriptStep {
+    pub fn opcode_name(&self) -> &'static str {
+        opcode_name(self.opcode)
+    }
+}
+
+/// Steps of a script evaluation along with its final result.
+///
+/// Errors raised outside of an opcode (ie. when parsing a script or when checking the final
+/// stack) are only reported in `result`.
+#[derive(Clone, Debug)]
+pub struct ScriptTrace {
+    pub steps: Vec<ScriptStep>,
+    pub result: Result<(), TxScriptError>,
+}
+
+impl ScriptTrace {
+    pub fn is_success(&self) -> bool {
+        self.result.is_ok()
+    }
+}
+
+/// Evaluates the signature script of the transaction input at `input_index` against the script
+/// public key of the UTXO entry it spends, exactly as the consensus would
+pub fn debug_transaction_input(tx: &impl VerifiableTransaction, input_index: usize) -> ScriptTrace {
+    if input_index >= tx.tx().inputs.len() {
+        return ScriptTrace {
+            steps: vec![],
+            result: Err(TxScriptError::InvalidIndex(
+                input_index,
+                tx.tx().inputs.len(),
+            )),
+        };
+    }
+    let (input, utxo_entry) = tx.populated_input(input_index);
+    let sig_cache = Cache::new(0);
+    let mut reused_values = SigHashReusedValues::new();
+    match TxScriptEngine::from_transaction_input(
+        tx,
+        input,
+        input_index,
+        utxo_entry,
+        &mut reused_values,
+        &sig_cache,
+    ) {
+        Ok(engine) => run(engine.with_trace()),
+        Err(err) => ScriptTrace {
+            steps: vec![],
+            result: Err(err),
+        },
+    }
+}
+
+/// Evaluates a standalone script with no transaction context, hence signature checks fail
+pub fn debug_script(script: &[u8]) -> ScriptTrace {
+    let sig_cache = Cache::new(0);
+    let mut reused_values = SigHashReusedValues::new();
+    let engine =
+        TxScriptEngine::<PopulatedTransaction>::from_script(script, &mut reused_values, &sig_cache);
+    run(engine.with_trace())
+}
+
+fn run<T: VerifiableTransaction>(mut engine: TxScriptEngine<T>) -> ScriptTrace {
+    let result = engine.execute();
+    ScriptTrace {
+        steps: engine.take_trace(),
+        result,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        opcodes::codes::{OpDup, OpElse, OpEndIf, OpEqual, OpFalse, OpIf, OpTrue, OpVerify},
+        test_helpers::{create_transaction, op_true_script},
+    };
+    use karlsen_consensus_core::{
+        constants::TX_VERSION,
+        subnets::SUBNETWORK_ID_NATIVE,
+        tx::{Transaction, TransactionOutput, UtxoEntry},
+    };
+
+    #[test]
+    fn test_debug_script() {
+        let trace = debug_script(&[OpTrue, OpDup, OpEqual]);
+        assert!(trace.is_success());
+        assert_eq!(trace.steps.len(), 3);
+        assert_eq!(trace.steps[1].opcode_name(), "OpDup");
+        assert_eq!(trace.steps[1].stack, vec![vec![1], vec![1]]);
+        assert_eq!(trace.steps[2].stack, vec![vec![1]]);
+
+        // Opcodes of a false branch are recorded but not executed
+        let trace = debug_script(&[OpFalse, OpIf, OpFalse, OpElse, OpTrue, OpEndIf]);
+        assert!(trace.is_success());
+        let executed = trace.steps.iter().map(|x| x.executed).collect::<Vec<_>>();
+        assert_eq!(executed, vec![true, true, false, true, true, true]);
+
+        // The failing opcode is the last step
+        let trace = debug_script(&[OpTrue, OpFalse, OpVerify, OpTrue]);
+        assert_eq!(trace.result, Err(TxScriptError::VerifyError));
+        assert_eq!(trace.steps.len(), 3);
+        assert_eq!(trace.steps[2].opcode, OpVerify);
+        assert!(trace.steps[2].error.is_some());
+    }
+
+    #[test]
+    fn test_debug_transaction_input() {
+        let (script_public_key, redeem_script) = op_true_script();
+        let tx_to_spend = Transaction::new(
+            TX_VERSION,
+            vec![],
+            vec![TransactionOutput::new(1_000, script_public_key.clone())],
+            0,
+            SUBNETWORK_ID_NATIVE,
+            0,
+            vec![],
+        );
+        let tx = create_transaction(&tx_to_spend, 10);
+        let entry = UtxoEntry::new(1_000, script_public_key, 0, false);
+        let populated = PopulatedTransaction::new(&tx, vec![entry]);
+
+        let trace = debug_transaction_input(&populated, 0);
+        assert!(trace.is_success());
+        // Signature script push, P2SH hash check and finally the redeem script
+        let scripts = trace
+            .steps
+            .iter()
+            .map(|x| x.script_index)
+            .collect::<Vec<_>>();
+        assert_eq!(scripts, vec![0, 1, 1, 1, 2]);
+        assert_eq!(trace.steps[0].data, redeem_script);
+
+        let trace = debug_transaction_input(&populated, 1);
+        assert_eq!(trace.result, Err(TxScriptError::InvalidIndex(1, 1)));
+    }
+}
@@ -3,6 +3,8 @@ extern crate core;
 
 pub mod caches;
 mod data_stack;
+pub mod debugger;
+pub mod message;
 pub mod opcodes;
 pub mod script_builder;
 pub mod script_class;
@@ -10,6 +12,7 @@ pub mod standard;
 
 use crate::caches::Cache;
 use crate::data_stack::{DataStack, Stack};
+use crate::debugger::ScriptStep;
 use crate::opcodes::{deserialize_next_opcode, OpCodeImplementation};
 use itertools::Itertools;
 use karlsen_consensus_core::hashing::sighash::{
@@ -90,6 +93,10 @@ pub struct TxScriptEngine<'a, T: VerifiableTransaction> {
     cond_stack: Vec<OpCond>, // Following if stacks, and whether it is running
 
     num_ops: i32,
+
+    // Step-wise execution record, only collected when tracing is enabled
+    trace: Option<Vec<ScriptStep>>,
+    script_index: usize,
 }
 
 fn parse_script<T: VerifiableTransaction>(
@@ -188,6 +195,8 @@ impl<'a, T: VerifiableTransaction> TxScriptEngine<'a, T> {
             sig_cache,
             cond_stack: vec![],
             num_ops: 0,
+            trace: None,
+            script_index: 0,
         }
     }
 
@@ -218,6 +227,8 @@ impl<'a, T: VerifiableTransaction> TxScriptEngine<'a, T> {
                 sig_cache,
                 cond_stack: Default::default(),
                 num_ops: 0,
+                trace: None,
+                script_index: 0,
             }),
             false => Err(TxScriptError::InvalidIndex(input_idx, tx.tx().inputs.len())),
         }
@@ -236,9 +247,22 @@ impl<'a, T: VerifiableTransaction> TxScriptEngine<'a, T> {
             sig_cache,
             cond_stack: Default::default(),
             num_ops: 0,
+            trace: None,
+            script_index: 0,
         }
     }
 
+    /// Enables the recording of the engine state after every opcode, see [`debugger`]
+    pub fn with_trace(mut self) -> Self {
+        self.trace = Some(vec![]);
+        self
+    }
+
+    /// Returns the steps recorded so far, leaving an empty trace in place if tracing is enabled
+    pub fn take_trace(&mut self) -> Vec<ScriptStep> {
+        self.trace.as_mut().map(std::mem::take).unwrap_or_default()
+    }
+
     #[inline]
     pub fn is_executing(&self) -> bool {
         return self.cond_stack.is_empty()
@@ -278,31 +302,37 @@ impl<'a, T: VerifiableTransaction> TxScriptEngine<'a, T> {
         script: &[u8],
         verify_only_push: bool,
     ) -> Result<(), TxScriptError> {
-        let script_result = parse_script(script).try_for_each(|opcode| {
-            let opcode = opcode?;
-            if opcode.is_disabled() {
-                return Err(TxScriptError::OpcodeDisabled(format!("{:?}", opcode)));
-            }
+        let script_result = parse_script(script)
+            .enumerate()
+            .try_for_each(|(index, opcode)| {
+                let opcode = opcode?;
+                if opcode.is_disabled() {
+                    return Err(TxScriptError::OpcodeDisabled(format!("{:?}", opcode)));
+                }
 
-            if opcode.always_illegal() {
-                return Err(TxScriptError::OpcodeReserved(format!("{:?}", opcode)));
-            }
+                if opcode.always_illegal() {
+                    return Err(TxScriptError::OpcodeReserved(format!("{:?}", opcode)));
+                }
 
-            if verify_only_push && !opcode.is_push_opcode() {
-                return Err(TxScriptError::SignatureScriptNotPushOnly);
-            }
+                if verify_only_push && !opcode.is_push_opcode() {
+                    return Err(TxScriptError::SignatureScriptNotPushOnly);
+                }
 
-            self.execute_opcode(opcode)?;
+                if self.trace.is_some() {
+                    self.execute_traced_opcode(index, opcode)?;
+                } else {
+                    self.execute_opcode(opcode)?;
+                }
 
-            let combined_size = self.astack.len() + self.dstack.len();
-            if combined_size > MAX_STACK_SIZE {
-                return Err(TxScriptError::StackSizeExceeded(
-                    combined_size,
-                    MAX_STACK_SIZE,
-                ));
-            }
-            Ok(())
-        });
+                let combined_size = self.astack.len() + self.dstack.len();
+                if combined_size > MAX_STACK_SIZE {
+                    return Err(TxScriptError::StackSizeExceeded(
+                        combined_size,
+                        MAX_STACK_SIZE,
+                    ));
+                }
+                Ok(())
+            });
 
         // Moving between scripts - we can't be inside an if
         if script_result.is_ok() && !self.cond_stack.is_empty() {
@@ -316,6 +346,31 @@ impl<'a, T: VerifiableTransaction> TxScriptEngine<'a, T> {
         script_result
     }
 
+    fn execute_traced_opcode(
+        &mut self,
+        opcode_index: usize,
+        opcode: Box<dyn OpCodeImplementation<T>>,
+    ) -> Result<(), TxScriptError> {
+        let opcode_value = opcode.value();
+        let data = opcode.get_data().to_vec();
+        let executed = self.is_executing() || opcode.is_conditional();
+        let result = self.execute_opcode(opcode);
+        let step = ScriptStep {
+            script_index: self.script_index,
+            opcode_index,
+            opcode: opcode_value,
+            data,
+            executed,
+            stack: self.dstack.clone(),
+            alt_stack: self.astack.clone(),
+            error: result.as_ref().err().map(|err| err.to_string()),
+        };
+        if let Some(trace) = self.trace.as_mut() {
+            trace.push(step);
+        }
+        result
+    }
+
     pub fn execute(&mut self) -> Result<(), TxScriptError> {
         let (scripts, is_p2sh) = match &self.script_source {
             ScriptSource::TxInput {
@@ -378,6 +433,7 @@ impl<'a, T: VerifiableTransaction> TxScriptEngine<'a, T> {
                 if is_p2sh && idx == 1 {
                     saved_stack = Some(self.dstack.clone());
                 }
+                self.script_index = idx;
                 self.execute_script(s, verify_only_push)
             })?;
 
@@ -385,6 +441,7 @@ impl<'a, T: VerifiableTransaction> TxScriptEngine<'a, T> {
             self.check_error_condition(false)?;
             self.dstack = saved_stack.ok_or(TxScriptError::EmptyStack)?;
             let script = self.dstack.pop().ok_or(TxScriptError::EmptyStack)?;
+            self.script_index = scripts.len();
             self.execute_script(script.as_slice(), false)?
         }
 
@@ -0,0 +1,127 @@
+//!
+//! Verification of messages signed with the key of an address.
+//!
+//! The message is hashed under the `KarlsenSignedMessage` domain, so a signature over a message
+//! can never be replayed as the signature of a transaction or of any other hashed structure.
+//! PubKey addresses sign with Schnorr and PubKeyECDSA addresses with ECDSA, both as 64 bytes.
+//!
+
+use karlsen_addresses::{Address, Version};
+use karlsen_hashes::{AddressMessageSigningHash, Hash, Hasher};
+use thiserror::Error;
+
+#[derive(Error, PartialEq, Eq, Debug, Clone)]
+pub enum Error {
+    #[error("address version {0} does not support message signing")]
+    UnsupportedAddress(Version),
+    #[error("invalid signature length {0}, expected 64 bytes")]
+    SignatureLength(usize),
+    #[error("the address does not hold a valid public key: {0}")]
+    InvalidPublicKey(secp256k1::Error),
+    #[error("the signature does not match the message and the address")]
+    InvalidSignature,
+}
+
+/// Returns the hash signed by the key of an address for proving its ownership with `message`
+pub fn calc_address_message_hash(message: &[u8]) -> Hash {
+    AddressMessageSigningHash::hash(message)
+}
+
+/// Verifies that `signature` signs `message` with the key of `address`.
+///
+/// This needs no wallet, letting any party holding the message, the signature and the address
+/// check a proof of ownership of the address.
+pub fn verify_address_message(
+    message: &[u8],
+    signature: &[u8],
+    address: &Address,
+) -> Result<(), Error> {
+    if signature.len() != 64 {
+        return Err(Error::SignatureLength(signature.len()));
+    }
+    let hash = calc_address_message_hash(message);
+    let msg = secp256k1::Message::from_digest_slice(hash.as_bytes().as_slice()).unwrap();
+    match address.version {
+        Version::PubKey => {
+            let pub_key = secp256k1::XOnlyPublicKey::from_slice(address.payload.as_slice())
+                .map_err(Error::InvalidPublicKey)?;
+            let sig = secp256k1::schnorr::Signature::from_slice(signature)
+                .map_err(|_| Error::InvalidSignature)?;
+            sig.verify(&msg, &pub_key)
+                .map_err(|_| Error::InvalidSignature)
+        }
+        Version::PubKeyECDSA => {
+            let pub_key = secp256k1::PublicKey::from_slice(address.payload.as_slice())
+                .map_err(Error::InvalidPublicKey)?;
+            let sig = secp256k1::ecdsa::Signature::from_compact(signature)
+                .map_err(|_| Error::InvalidSignature)?;
+            sig.verify(&msg, &pub_key)
+                .map_err(|_| Error::InvalidSignature)
+        }
+        version => Err(Error::UnsupportedAddress(version)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use karlsen_addresses::Prefix;
+    use karlsen_hashes::PersonalMessageSigningHash;
+
+    fn message(hash: Hash) -> secp256k1::Message {
+        secp256k1::Message::from_digest_slice(hash.as_bytes().as_slice()).unwrap()
+    }
+
+    #[test]
+    fn test_verify_address_message() {
+        let text = b"I own this address";
+        let keypair =
+            secp256k1::Keypair::from_seckey_slice(secp256k1::SECP256K1, &[3u8; 32]).unwrap();
+        let hash = calc_address_message_hash(text);
+
+        // Schnorr over a PubKey address
+        let address = Address::new(
+            Prefix::Mainnet,
+            Version::PubKey,
+            &keypair.x_only_public_key().0.serialize(),
+        );
+        let signature = keypair.sign_schnorr(message(hash));
+        assert_eq!(
+            verify_address_message(text, signature.as_ref(), &address),
+            Ok(())
+        );
+        assert_eq!(
+            verify_address_message(b"I own another address", signature.as_ref(), &address),
+            Err(Error::InvalidSignature)
+        );
+        assert_eq!(
+            verify_address_message(text, &signature.as_ref()[..63], &address),
+            Err(Error::SignatureLength(63))
+        );
+
+        // The domain separates from the signatures of personal messages
+        let personal = keypair.sign_schnorr(message(PersonalMessageSigningHash::hash(text)));
+        assert_eq!(
+            verify_address_message(text, personal.as_ref(), &address),
+            Err(Error::InvalidSignature)
+        );
+
+        // ECDSA over a PubKeyECDSA address
+        let address = Address::new(
+            Prefix::Mainnet,
+            Version::PubKeyECDSA,
+            &keypair.public_key().serialize(),
+        );
+        let signature = secp256k1::SECP256K1
+            .sign_ecdsa(&message(hash), &keypair.secret_key())
+            .serialize_compact();
+        assert_eq!(verify_address_message(text, &signature, &address), Ok(()));
+
+        // Script hash addresses hold no key
+        let address = Address::new(Prefix::Mainnet, Version::ScriptHash, &[0u8; 32]);
+        assert_eq!(
+            verify_address_message(text, &signature, &address),
+            Err(Error::UnsupportedAddress(Version::ScriptHash))
+        );
+    }
+}
@@ -135,6 +135,15 @@ macro_rules! opcode_list {
             }
         }
 
+        /// Returns the name of an opcode, aliases resolving to their canonical name
+        pub fn opcode_name(opcode: u8) -> &'static str {
+            match opcode {
+                $(
+                    $num => stringify!($name),
+                )*
+            }
+        }
+
         #[cfg(test)]
         use crate::script_builder::{ScriptBuilder, ScriptBuilderResult};
 
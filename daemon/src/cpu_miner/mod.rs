@@ -1,4 +1,5 @@
 pub mod native;
+pub mod stats;
 pub mod wasm;
 
 use crate::imports::*;
@@ -0,0 +1,122 @@
+//! Profitability reports of the CPU miner.
+//!
+//! The miner logs the hashrate it measures. The hashrate is parsed from its output and combined
+//! with the network hashrate and block reward the node reports through `GetMinerStats`.
+
+use crate::imports::*;
+use karlsen_consensus_core::{config::params::Params, constants::SOMPI_PER_KARLSEN};
+use std::fmt::{self, Display, Formatter};
+
+const SECONDS_PER_DAY: f64 = 24.0 * 60.0 * 60.0;
+
+/// Parses the hashrate, in hashes per second, of a hashrate log line of the miner such as
+/// `Current hashrate is 1.25 Mhash/s`
+pub fn parse_hashrate(line: &str) -> Option<f64> {
+    let line = line.to_ascii_lowercase();
+    let rest = &line[line.find("hashrate")? + "hashrate".len()..];
+    let rest = &rest[rest.find(|c: char| c.is_ascii_digit())?..];
+    let end = rest
+        .find(|c: char| !c.is_ascii_digit() && c != '.')
+        .unwrap_or(rest.len());
+    let value: f64 = rest[..end].parse().ok()?;
+    let multiplier = match rest[end..].trim_start().chars().next()? {
+        'h' => 1.0,
+        'k' => 1e3,
+        'm' => 1e6,
+        'g' => 1e9,
+        't' => 1e12,
+        'p' => 1e15,
+        _ => return None,
+    };
+    Some(value * multiplier)
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct CpuMinerReport {
+    /// Hashrate measured by the miner, in hashes per second
+    pub hashrate: f64,
+    /// Network hashrate implied by the difficulty of the latest template, in hashes per second
+    pub network_hashrate: f64,
+    /// Share of the network hashrate held by the miner, in [0, 1]
+    pub network_share: f64,
+    /// Expected time between two blocks found by the miner, `None` while it has no hashrate
+    pub expected_time_to_block: Option<Duration>,
+    /// Expected coinbase value earned per day, in sompi
+    pub expected_daily_reward: u64,
+    /// Daily cost of the power drawn by the miner, if configured on the node
+    pub daily_power_cost: Option<f64>,
+    /// Power cost of a mined KLS, if configured on the node
+    pub cost_per_kls: Option<f64>,
+}
+
+impl CpuMinerReport {
+    pub fn new(
+        network: NetworkType,
+        hashrate: f64,
+        network_hashrate: f64,
+        block_reward: u64,
+        daily_power_cost: Option<f64>,
+    ) -> Self {
+        let target_time_per_block = Params::from(network).target_time_per_block as f64 / 1000.0;
+        let network_share = match network_hashrate > 0.0 {
+            true => (hashrate / network_hashrate).min(1.0),
+            false => 0.0,
+        };
+        let expected_time_to_block = (network_share > 0.0)
+            .then(|| Duration::from_secs_f64(target_time_per_block / network_share));
+        let expected_daily_reward = match expected_time_to_block {
+            Some(time) => (SECONDS_PER_DAY / time.as_secs_f64() * block_reward as f64) as u64,
+            None => 0,
+        };
+        let cost_per_kls = daily_power_cost
+            .filter(|_| expected_daily_reward > 0)
+            .map(|cost| cost / (expected_daily_reward as f64 / SOMPI_PER_KARLSEN as f64));
+        Self {
+            hashrate,
+            network_hashrate,
+            network_share,
+            expected_time_to_block,
+            expected_daily_reward,
+            daily_power_cost,
+            cost_per_kls,
+        }
+    }
+}
+
+impl Display for CpuMinerReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "hashrate {} ({:.6}% of {})",
+            format_hashrate(self.hashrate),
+            self.network_share * 100.0,
+            format_hashrate(self.network_hashrate)
+        )?;
+        if let Some(time) = self.expected_time_to_block {
+            write!(
+                f,
+                ", a block expected every {:.1}h, {:.2} KLS/day",
+                time.as_secs_f64() / 3600.0,
+                self.expected_daily_reward as f64 / SOMPI_PER_KARLSEN as f64
+            )?;
+        }
+        if let Some(cost) = self.daily_power_cost {
+            write!(f, ", power cost {cost:.2}/day")?;
+        }
+        if let Some(cost) = self.cost_per_kls {
+            write!(f, " ({cost:.6}/KLS)")?;
+        }
+        Ok(())
+    }
+}
+
+fn format_hashrate(hashrate: f64) -> String {
+    const UNITS: [&str; 7] = ["H/s", "KH/s", "MH/s", "GH/s", "TH/s", "PH/s", "EH/s"];
+    let mut value = hashrate;
+    let mut unit = 0;
+    while value >= 1000.0 && unit < UNITS.len() - 1 {
+        value /= 1000.0;
+        unit += 1;
+    }
+    format!("{value:.2} {}", UNITS[unit])
+}
@@ -8,7 +8,10 @@ use std::fmt::Display;
 
 use crate::imports::*;
 pub use crate::result::Result;
-pub use cpu_miner::{CpuMiner, CpuMinerConfig, CpuMinerCtl};
+pub use cpu_miner::{
+    stats::{parse_hashrate, CpuMinerReport},
+    CpuMiner, CpuMinerConfig, CpuMinerCtl,
+};
 pub use karlsend::{Karlsend, KarlsendConfig, KarlsendCtl};
 use workflow_core::runtime;
 use workflow_node::process::Event as ProcessEvent;
@@ -1,4 +1,8 @@
-use crate::{cache::CachePolicy, db::DB, errors::StoreError};
+use crate::{
+    cache::{CacheMemoryConsumer, CachePolicy},
+    db::{DbSnapshot, DB},
+    errors::StoreError,
+};
 
 use super::prelude::{Cache, DbKey, DbWriter};
 use karlsen_utils::mem_size::MemSizeEstimator;
@@ -36,6 +40,11 @@ where
         }
     }
 
+    /// Exposes the cache of this access to the process memory budget, see [`CacheMemoryConsumer`]
+    pub fn cache_memory_consumer(&self, item_bytes: usize) -> CacheMemoryConsumer<TKey, TData, S> {
+        CacheMemoryConsumer::new(self.cache.clone(), item_bytes)
+    }
+
     pub fn read_from_cache(&self, key: TKey) -> Option<TData>
     where
         TKey: Copy + AsRef<[u8]>,
@@ -70,6 +79,20 @@ where
         }
     }
 
+    /// Reads `key` as of `snapshot`. The cache is bypassed, since it may hold data written after
+    /// the snapshot was taken.
+    pub fn read_at(&self, snapshot: &DbSnapshot, key: TKey) -> Result<TData, StoreError>
+    where
+        TKey: Clone + AsRef<[u8]> + ToString,
+        TData: DeserializeOwned,
+    {
+        let db_key = DbKey::new(&self.prefix, key);
+        match snapshot.get_pinned(&db_key)? {
+            Some(slice) => Ok(bincode::deserialize(&slice)?),
+            None => Err(StoreError::KeyNotFound(db_key)),
+        }
+    }
+
     pub fn iterator(&self) -> impl Iterator<Item = Result<(Box<[u8]>, TData), Box<dyn Error>>> + '_
     where
         TKey: Clone + AsRef<[u8]>,
@@ -272,4 +295,32 @@ mod tests {
         db.write(batch).unwrap();
         assert_eq!(0, access.iterator().count());
     }
+
+    #[test]
+    fn test_read_at() {
+        let (_lifetime, db) = create_temp_db!(ConnBuilder::default().with_files_limit(10));
+        let access =
+            CachedDbAccess::<Hash, u64>::new(db.clone(), CachePolicy::Count(2), vec![1, 2]);
+        let (first, second) = (Hash::from_u64_word(1), Hash::from_u64_word(2));
+        access.write(DirectDbWriter::new(&db), first, 1).unwrap();
+
+        // Writes following the snapshot are not visible through it, even once cached
+        let snapshot = db.snapshot();
+        let mut batch = WriteBatch::default();
+        access
+            .write(BatchDbWriter::new(&mut batch), first, 10)
+            .unwrap();
+        access
+            .write(BatchDbWriter::new(&mut batch), second, 20)
+            .unwrap();
+        db.write(batch).unwrap();
+        assert_eq!(access.read(first).unwrap(), 10);
+        assert_eq!(access.read_at(&snapshot, first).unwrap(), 1);
+        assert!(matches!(
+            access.read_at(&snapshot, second),
+            Err(StoreError::KeyNotFound(_))
+        ));
+        drop(snapshot);
+        assert_eq!(access.read_at(&db.snapshot(), second).unwrap(), 20);
+    }
 }
@@ -1,5 +1,8 @@
 use indexmap::IndexMap;
-use karlsen_utils::mem_size::{MemMode, MemSizeEstimator};
+use karlsen_utils::{
+    mem_budget::MemoryConsumer,
+    mem_size::{MemMode, MemSizeEstimator},
+};
 use parking_lot::RwLock;
 use rand::Rng;
 use std::{collections::hash_map::RandomState, hash::BuildHasher, sync::Arc};
@@ -149,6 +152,20 @@ where
             tracked_size: 0,
         }
     }
+
+    /// Evicts random items until the cache holds no more than `max_items` items
+    fn evict_to(&mut self, policy: &CachePolicyInner, max_items: usize) {
+        while self.map.len() > max_items {
+            if let Some((_, v)) = self
+                .map
+                .swap_remove_index(rand::thread_rng().gen_range(0..self.map.len()))
+            {
+                if policy.tracked {
+                    self.tracked_size -= v.estimate_size(policy.mem_mode);
+                }
+            }
+        }
+    }
 }
 
 #[derive(Clone)]
@@ -231,6 +248,22 @@ where
         }
     }
 
+    pub fn len(&self) -> usize {
+        self.inner.read().map.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Evicts random items until the cache holds no more than `max_items` items
+    pub fn evict_to(&self, max_items: usize) {
+        if self.policy.max_size == 0 {
+            return;
+        }
+        self.inner.write().evict_to(&self.policy, max_items);
+    }
+
     pub fn remove_all(&self) {
         if self.policy.max_size == 0 {
             return;
@@ -242,3 +275,80 @@ where
         }
     }
 }
+
+/// Exposes a cache to the process memory budget.
+///
+/// The usage of caches tracked in bytes is known, for the others it is estimated from
+/// the average size of an item, `item_bytes`.
+pub struct CacheMemoryConsumer<TKey, TData, S = RandomState>
+where
+    TKey: Clone + std::hash::Hash + Eq + Send + Sync,
+    TData: Clone + Send + Sync + MemSizeEstimator,
+{
+    cache: Cache<TKey, TData, S>,
+    item_bytes: usize,
+}
+
+impl<TKey, TData, S> CacheMemoryConsumer<TKey, TData, S>
+where
+    TKey: Clone + std::hash::Hash + Eq + Send + Sync,
+    TData: Clone + Send + Sync + MemSizeEstimator,
+    S: BuildHasher + Default,
+{
+    pub fn new(cache: Cache<TKey, TData, S>, item_bytes: usize) -> Self {
+        Self {
+            cache,
+            item_bytes: item_bytes.max(1),
+        }
+    }
+
+    fn tracks_bytes(&self) -> bool {
+        self.cache.policy.tracked && matches!(self.cache.policy.mem_mode, MemMode::Bytes)
+    }
+}
+
+impl<TKey, TData, S> MemoryConsumer for CacheMemoryConsumer<TKey, TData, S>
+where
+    TKey: Clone + std::hash::Hash + Eq + Send + Sync,
+    TData: Clone + Send + Sync + MemSizeEstimator,
+    S: BuildHasher + Default + Send + Sync,
+{
+    fn memory_usage(&self) -> usize {
+        let inner = self.cache.inner.read();
+        if self.tracks_bytes() {
+            inner.tracked_size
+        } else {
+            inner.map.len() * self.item_bytes
+        }
+    }
+
+    fn shrink_to(&self, target: usize) {
+        if self.tracks_bytes() {
+            let mut inner = self.cache.inner.write();
+            let policy = &self.cache.policy;
+            while inner.tracked_size > target && !inner.map.is_empty() {
+                let len = inner.map.len();
+                inner.evict_to(policy, len - 1);
+            }
+        } else {
+            self.cache.evict_to(target / self.item_bytes);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cache_memory_consumer() {
+        let cache = Cache::<u64, u64>::new(CachePolicy::Count(100));
+        (0..100).for_each(|i| cache.insert(i, i));
+        let consumer = CacheMemoryConsumer::new(cache.clone(), 8);
+        assert_eq!(consumer.memory_usage(), 800);
+
+        consumer.shrink_to(300);
+        assert_eq!(cache.len(), 37);
+        assert_eq!(consumer.memory_usage(), 296);
+    }
+}
@@ -36,6 +36,10 @@ impl Deref for DB {
     }
 }
 
+/// A point-in-time read view of the DB. Writes committed after the snapshot was taken are not
+/// visible through it, and batches are either fully visible or not at all.
+pub type DbSnapshot<'a> = rocksdb::SnapshotWithThreadMode<'a, DBWithThreadMode<MultiThreaded>>;
+
 /// Deletes an existing DB if it exists
 pub fn delete_db(db_dir: PathBuf) {
     if !db_dir.exists() {
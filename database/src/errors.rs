@@ -30,6 +30,9 @@ pub type StoreResult<T> = std::result::Result<T, StoreError>;
 pub trait StoreResultExtensions<T> {
     /// Unwrap or assert that the error is key not fund in which case `None` is returned
     fn unwrap_option(self) -> Option<T>;
+
+    /// Maps a key not found error to `None`, leaving other errors to the caller
+    fn optional(self) -> StoreResult<Option<T>>;
 }
 
 impl<T> StoreResultExtensions<T> for StoreResult<T> {
@@ -40,6 +43,14 @@ impl<T> StoreResultExtensions<T> for StoreResult<T> {
             Err(err) => panic!("Unexpected store error: {err:?}"),
         }
     }
+
+    fn optional(self) -> StoreResult<Option<T>> {
+        match self {
+            Ok(value) => Ok(Some(value)),
+            Err(StoreError::KeyNotFound(_)) => Ok(None),
+            Err(err) => Err(err),
+        }
+    }
 }
 
 pub trait StoreResultEmptyTuple {
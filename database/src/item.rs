@@ -1,5 +1,5 @@
 use crate::{
-    db::DB,
+    db::{DbSnapshot, DB},
     errors::StoreError,
     prelude::{DbSetAccess, ReadLock},
 };
@@ -46,6 +46,17 @@ impl<T> CachedDbItem<T> {
         }
     }
 
+    /// Reads the item as of `snapshot`, bypassing the cached item
+    pub fn read_at(&self, snapshot: &DbSnapshot) -> Result<T, StoreError>
+    where
+        T: DeserializeOwned,
+    {
+        match snapshot.get_pinned(&self.key)? {
+            Some(slice) => Ok(bincode::deserialize(&slice)?),
+            None => Err(StoreError::KeyNotFound(DbKey::prefix_only(&self.key))),
+        }
+    }
+
     pub fn write(&mut self, mut writer: impl DbWriter, item: &T) -> Result<(), StoreError>
     where
         T: Clone + Serialize,
@@ -14,11 +14,11 @@ pub mod prelude {
     use crate::{db, errors};
 
     pub use super::access::CachedDbAccess;
-    pub use super::cache::{Cache, CachePolicy};
+    pub use super::cache::{Cache, CacheMemoryConsumer, CachePolicy};
     pub use super::item::{CachedDbItem, CachedDbSetItem};
     pub use super::key::DbKey;
     pub use super::set_access::{CachedDbSetAccess, DbSetAccess, ReadLock};
     pub use super::writer::{BatchDbWriter, DbWriter, DirectDbWriter, DirectWriter, MemoryWriter};
-    pub use db::{delete_db, ConnBuilder, DB};
+    pub use db::{delete_db, ConnBuilder, DbSnapshot, DB};
     pub use errors::{StoreError, StoreResult, StoreResultEmptyTuple, StoreResultExtensions};
 }
@@ -36,11 +36,15 @@ pub enum DatabaseStorePrefixes {
     UtxoMultisets = 26,
     VirtualUtxoset = 27,
     VirtualState = 28,
+    ColdStorageMigrationIndex = 29,
 
     // ---- Decomposed reachability stores ----
     ReachabilityTreeChildren = 30,
     ReachabilityFutureCoveringSet = 31,
 
+    // ---- Cold storage ----
+    ColdStorageDeletions = 32,
+
     // ---- Metadata ----
     MultiConsensusMetadata = 124,
     ConsensusEntries = 125,
@@ -53,6 +57,16 @@ pub enum DatabaseStorePrefixes {
     UtxoIndex = 192,
     UtxoIndexTips = 193,
     CirculatingSupply = 194,
+    BlockFilters = 195,
+    BlockFilterChain = 196,
+    BlockFilterTip = 197,
+    AcceptanceEvents = 198,
+    AcceptanceJournalTip = 199,
+    ExternalMetadata = 200,
+    ClusterIds = 201,
+    ClusterMembers = 202,
+    ClusterSizes = 203,
+    ClusterIndexTip = 204,
 
     // ---- Separator ----
     /// Reserved as a separator
@@ -0,0 +1,18 @@
+use thiserror::Error;
+
+use crate::IDENT;
+use karlsen_consensus_core::errors::consensus::ConsensusError;
+use karlsen_database::prelude::StoreError;
+
+/// Errors originating from the [`AcceptanceJournal`](crate::AcceptanceJournal).
+#[derive(Error, Debug)]
+pub enum AcceptanceJournalError {
+    #[error("[{IDENT}]: {0}")]
+    StoreAccessError(#[from] StoreError),
+
+    #[error("[{IDENT}]: {0}")]
+    ConsensusError(#[from] ConsensusError),
+}
+
+/// Results originating from the [`AcceptanceJournal`](crate::AcceptanceJournal).
+pub type AcceptanceJournalResult<T> = Result<T, AcceptanceJournalError>;
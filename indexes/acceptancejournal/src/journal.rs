@@ -0,0 +1,228 @@
+use crate::{
+    errors::AcceptanceJournalResult,
+    stores::{AcceptanceEvent, AcceptanceJournalTip, Store},
+    IDENT,
+};
+use karlsen_consensus_core::acceptance_data::AcceptanceData;
+use karlsen_consensusmanager::{ConsensusManager, ConsensusResetHandler, ConsensusSessionBlocking};
+use karlsen_core::{info, time::unix_now, trace};
+use karlsen_database::prelude::DB;
+use parking_lot::Mutex;
+use rocksdb::WriteBatch;
+use std::{
+    fmt::Debug,
+    sync::{Arc, Weak},
+};
+
+/// Count of chain blocks journaled per consensus session and DB write
+const SYNC_CHUNK_SIZE: usize = 256;
+
+/// Outcome of a journal read
+#[derive(Debug, Default)]
+pub struct AcceptanceJournalRead {
+    /// Events following the cursor, in journal order
+    pub events: Vec<AcceptanceEvent>,
+    /// Events following the cursor were evicted from the journal, so some acceptance changes
+    /// were missed before the first returned event
+    pub gap: bool,
+    /// More events are available after the last returned one
+    pub has_more: bool,
+}
+
+/// Journals the chain blocks added to and removed from the selected chain along with the
+/// transactions they accepted, retaining the latest `capacity` events on disk.
+///
+/// The journal starts at the sink at the time it is first synced. Every event gets a sequence
+/// number which serves as the cursor of the readers, and which keeps increasing across restarts,
+/// consensus resets and resyncs so a stale cursor is always detected as a gap.
+pub struct AcceptanceJournal {
+    consensus_manager: Arc<ConsensusManager>,
+    store: Mutex<Store>,
+    capacity: u64,
+    /// Serializes the syncs and resets of the journal
+    sync_lock: Mutex<()>,
+}
+
+impl AcceptanceJournal {
+    pub fn new(
+        consensus_manager: Arc<ConsensusManager>,
+        db: Arc<DB>,
+        capacity: usize,
+    ) -> Arc<Self> {
+        assert!(
+            capacity > 0,
+            "the acceptance journal capacity must be positive"
+        );
+        let journal = Arc::new(Self {
+            consensus_manager: consensus_manager.clone(),
+            store: Mutex::new(Store::new(db)),
+            capacity: capacity as u64,
+            sync_lock: Mutex::new(()),
+        });
+        consensus_manager.register_consensus_reset_handler(Arc::new(
+            AcceptanceJournalConsensusResetHandler::new(Arc::downgrade(&journal)),
+        ));
+        journal
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity as usize
+    }
+
+    pub fn get_tip(&self) -> AcceptanceJournalResult<AcceptanceJournalTip> {
+        Ok(self.store.lock().get_tip()?)
+    }
+
+    /// Reads up to `max_count` events following the event of sequence `cursor`, where a zero
+    /// `cursor` reads from the oldest retained event
+    pub fn read(
+        &self,
+        cursor: u64,
+        max_count: usize,
+    ) -> AcceptanceJournalResult<AcceptanceJournalRead> {
+        let store = self.store.lock();
+        let tip = store.get_tip()?;
+        let gap = cursor != 0 && cursor + 1 < tip.first_sequence;
+        let start = (cursor + 1).max(tip.first_sequence);
+        let end = tip
+            .last_sequence
+            .min(start.saturating_add(max_count as u64).saturating_sub(1));
+        let mut events = Vec::with_capacity((end + 1).saturating_sub(start) as usize);
+        for sequence in start..=end {
+            match store.get_event(sequence)? {
+                Some(event) => events.push(event),
+                None => break,
+            }
+        }
+        let has_more = tip.last_sequence >= start + events.len() as u64;
+        Ok(AcceptanceJournalRead {
+            events,
+            gap,
+            has_more,
+        })
+    }
+
+    /// Catches up with the selected chain of the consensus, journaling the chain blocks it
+    /// removed and added since the last sync
+    pub fn sync(&self) -> AcceptanceJournalResult<()> {
+        let _guard = self.sync_lock.lock();
+        let consensus = self.consensus_manager.consensus();
+        let session = futures::executor::block_on(consensus.session_blocking());
+
+        let mut tip = self.store.lock().get_tip()?;
+        let Some(chain_block) = tip.chain_block else {
+            return self.anchor(&session, tip);
+        };
+        let chain_path = match session.get_virtual_chain_from_block(chain_block) {
+            Ok(chain_path) => chain_path,
+            Err(_) => {
+                // The journaled chain fell below the pruning point while the node was not running
+                info!(
+                    "The acceptance journal is behind the pruning point, restarting it at the sink"
+                );
+                self.store.lock().clear()?;
+                let tip = self.store.lock().get_tip()?;
+                return self.anchor(&session, tip);
+            }
+        };
+        drop(session);
+
+        // Removals come first and are journaled along with the additions, as a single reorg
+        let removed = chain_path.removed.iter().map(|&hash| (hash, true));
+        let added = chain_path.added.iter().map(|&hash| (hash, false));
+        let blocks = removed.chain(added).collect::<Vec<_>>();
+        for chunk in blocks.chunks(SYNC_CHUNK_SIZE) {
+            let session = futures::executor::block_on(consensus.session_blocking());
+            let mut store = self.store.lock();
+            let mut batch = WriteBatch::default();
+            let recorded_time = unix_now();
+            for &(hash, removed) in chunk {
+                let header = session.get_header(hash)?;
+                let acceptance_data = match session.get_block_acceptance_data(hash) {
+                    Ok(acceptance_data) => acceptance_data,
+                    // Acceptance data of former chain blocks may be gone
+                    Err(_) if removed => Arc::new(AcceptanceData::new()),
+                    Err(err) => return Err(err.into()),
+                };
+                // Once a block is removed, the journal stands on its selected parent
+                tip.chain_block = Some(match removed {
+                    true => session.get_ghostdag_data(hash)?.selected_parent,
+                    false => hash,
+                });
+                tip.last_sequence += 1;
+                store.insert_batch(
+                    &mut batch,
+                    AcceptanceEvent {
+                        sequence: tip.last_sequence,
+                        chain_block_hash: hash,
+                        removed,
+                        blue_score: header.blue_score,
+                        timestamp: header.timestamp,
+                        recorded_time,
+                        accepted_transaction_ids: acceptance_data
+                            .iter()
+                            .flat_map(|mergeset_block| {
+                                mergeset_block
+                                    .accepted_transactions
+                                    .iter()
+                                    .map(|entry| entry.transaction_id)
+                            })
+                            .collect(),
+                    },
+                )?;
+            }
+            while tip.len() > self.capacity {
+                store.delete_batch(&mut batch, tip.first_sequence)?;
+                tip.first_sequence += 1;
+            }
+            store.commit(batch, &tip)?;
+            trace!("[{IDENT}] journaled {} chain changes", chunk.len());
+        }
+        Ok(())
+    }
+
+    /// Anchors the journal at the current sink, from which chain changes start being journaled
+    fn anchor(
+        &self,
+        session: &ConsensusSessionBlocking<'_>,
+        mut tip: AcceptanceJournalTip,
+    ) -> AcceptanceJournalResult<()> {
+        let sink = session.get_sink();
+        info!("Starting the acceptance journal at the sink {sink}");
+        tip.chain_block = Some(sink);
+        self.store.lock().commit(WriteBatch::default(), &tip)?;
+        Ok(())
+    }
+
+    fn reset(&self) -> AcceptanceJournalResult<()> {
+        let _guard = self.sync_lock.lock();
+        Ok(self.store.lock().clear()?)
+    }
+}
+
+impl Debug for AcceptanceJournal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AcceptanceJournal")
+            .field("capacity", &self.capacity)
+            .finish()
+    }
+}
+
+struct AcceptanceJournalConsensusResetHandler {
+    journal: Weak<AcceptanceJournal>,
+}
+
+impl AcceptanceJournalConsensusResetHandler {
+    fn new(journal: Weak<AcceptanceJournal>) -> Self {
+        Self { journal }
+    }
+}
+
+impl ConsensusResetHandler for AcceptanceJournalConsensusResetHandler {
+    fn handle_consensus_reset(&self) {
+        // The journal is anchored again at the next sync
+        if let Some(journal) = self.journal.upgrade() {
+            journal.reset().unwrap();
+        }
+    }
+}
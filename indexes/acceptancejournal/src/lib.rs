@@ -0,0 +1,14 @@
+//!
+//! Bounded on-disk journal of the chain acceptance events, letting integrators (eg. exchanges)
+//! resync after a long downtime from a cursor instead of replaying the virtual chain.
+//!
+
+pub mod errors;
+mod journal;
+pub mod service;
+pub mod stores;
+
+pub use crate::journal::{AcceptanceJournal, AcceptanceJournalRead};
+pub use crate::stores::AcceptanceEvent;
+
+const IDENT: &str = "acceptancejournal";
@@ -0,0 +1,106 @@
+use crate::{journal::AcceptanceJournal, IDENT};
+use karlsen_consensus_notify::{
+    connection::ConsensusChannelConnection, notification::Notification as ConsensusNotification,
+    notifier::ConsensusNotifier,
+};
+use karlsen_consensusmanager::spawn_blocking;
+use karlsen_core::{
+    task::service::{AsyncService, AsyncServiceFuture},
+    trace, warn,
+};
+use karlsen_notify::{
+    connection::ChannelType,
+    listener::ListenerLifespan,
+    scope::VirtualChainChangedScope,
+    subscription::{MutationPolicies, UtxosChangedMutationPolicy},
+};
+use karlsen_utils::{channel::Channel, triggers::SingleTrigger};
+use std::sync::Arc;
+
+const ACCEPTANCE_JOURNAL_SERVICE: &str = IDENT;
+
+/// Keeps the [`AcceptanceJournal`] in sync with the selected chain by syncing it on every
+/// virtual chain change notified by the consensus
+pub struct AcceptanceJournalService {
+    journal: Arc<AcceptanceJournal>,
+    channel: Channel<ConsensusNotification>,
+    shutdown: SingleTrigger,
+}
+
+impl AcceptanceJournalService {
+    pub fn new(
+        consensus_notifier: &Arc<ConsensusNotifier>,
+        journal: Arc<AcceptanceJournal>,
+    ) -> Self {
+        let channel = Channel::<ConsensusNotification>::default();
+        let listener_id = consensus_notifier.register_new_listener(
+            ConsensusChannelConnection::new(
+                ACCEPTANCE_JOURNAL_SERVICE,
+                channel.sender(),
+                ChannelType::Closable,
+            ),
+            ListenerLifespan::Static(MutationPolicies::new(UtxosChangedMutationPolicy::Wildcard)),
+        );
+        consensus_notifier
+            .try_start_notify(listener_id, VirtualChainChangedScope::new(false).into())
+            .expect("the subscription always succeeds");
+        Self {
+            journal,
+            channel,
+            shutdown: SingleTrigger::default(),
+        }
+    }
+
+    pub fn journal(&self) -> Arc<AcceptanceJournal> {
+        self.journal.clone()
+    }
+
+    async fn sync(&self) {
+        let journal = self.journal.clone();
+        match spawn_blocking(move || journal.sync()).await {
+            Ok(Err(err)) => warn!("Error while syncing the acceptance journal: {}", err),
+            Err(err) => warn!("The acceptance journal sync panicked: {}", err),
+            Ok(Ok(())) => {}
+        }
+    }
+}
+
+impl AsyncService for AcceptanceJournalService {
+    fn ident(self: Arc<Self>) -> &'static str {
+        ACCEPTANCE_JOURNAL_SERVICE
+    }
+
+    fn start(self: Arc<Self>) -> AsyncServiceFuture {
+        trace!("{} starting", ACCEPTANCE_JOURNAL_SERVICE);
+        let shutdown_signal = self.shutdown.listener.clone();
+        let receiver = self.channel.receiver();
+        Box::pin(async move {
+            // Catch up with the chain changes which occurred while the node was down
+            self.sync().await;
+            tokio::select! {
+                _ = shutdown_signal => {}
+                _ = async {
+                    // The channel gets closed when the consensus notifier stops
+                    while receiver.recv().await.is_ok() {
+                        // A single sync covers all the pending notifications
+                        while receiver.try_recv().is_ok() {}
+                        self.sync().await;
+                    }
+                } => {}
+            }
+            Ok(())
+        })
+    }
+
+    fn signal_exit(self: Arc<Self>) {
+        trace!("sending an exit signal to {}", ACCEPTANCE_JOURNAL_SERVICE);
+        self.shutdown.trigger.trigger();
+    }
+
+    fn stop(self: Arc<Self>) -> AsyncServiceFuture {
+        Box::pin(async move {
+            trace!("{} stopped", ACCEPTANCE_JOURNAL_SERVICE);
+            Ok(())
+        })
+    }
+}
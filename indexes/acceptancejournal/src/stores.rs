@@ -0,0 +1,198 @@
+use karlsen_consensus_core::tx::TransactionId;
+use karlsen_database::{
+    prelude::{
+        BatchDbWriter, CachePolicy, CachedDbAccess, CachedDbItem, DirectDbWriter, StoreError,
+        StoreResult, StoreResultExtensions, DB,
+    },
+    registry::DatabaseStorePrefixes,
+};
+use karlsen_hashes::Hash;
+use karlsen_utils::mem_size::MemSizeEstimator;
+use rocksdb::WriteBatch;
+use serde::{Deserialize, Serialize};
+use std::{fmt::Display, sync::Arc};
+
+/// A chain block added to or removed from the selected chain, with the transactions it accepted
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AcceptanceEvent {
+    /// Position of the event in the journal, strictly increasing from 1
+    pub sequence: u64,
+    pub chain_block_hash: Hash,
+    /// The block left the selected chain, so its accepted transactions are no longer accepted
+    /// by it. The transaction ids of a removal are the ones of the matching addition.
+    pub removed: bool,
+    pub blue_score: u64,
+    /// Timestamp of the chain block
+    pub timestamp: u64,
+    /// Time the node recorded the event, in milliseconds since the unix epoch
+    pub recorded_time: u64,
+    pub accepted_transaction_ids: Vec<TransactionId>,
+}
+
+impl MemSizeEstimator for AcceptanceEvent {}
+
+/// Bounds of the journal and the chain block it is synced to
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AcceptanceJournalTip {
+    /// Sequence of the oldest retained event
+    pub first_sequence: u64,
+    /// Sequence of the latest event, `first_sequence - 1` if the journal is empty
+    pub last_sequence: u64,
+    /// The last chain block journaled, `None` until the journal gets anchored to the chain
+    pub chain_block: Option<Hash>,
+}
+
+impl AcceptanceJournalTip {
+    pub fn len(&self) -> u64 {
+        self.last_sequence + 1 - self.first_sequence
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl Default for AcceptanceJournalTip {
+    fn default() -> Self {
+        Self {
+            first_sequence: 1,
+            last_sequence: 0,
+            chain_block: None,
+        }
+    }
+}
+
+/// Big endian sequence, so the keys iterate in journal order
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+struct SequenceKey([u8; 8]);
+
+impl From<u64> for SequenceKey {
+    fn from(sequence: u64) -> Self {
+        Self(sequence.to_be_bytes())
+    }
+}
+
+impl AsRef<[u8]> for SequenceKey {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl Display for SequenceKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", u64::from_be_bytes(self.0))
+    }
+}
+
+#[derive(Clone)]
+pub struct Store {
+    db: Arc<DB>,
+    events: CachedDbAccess<SequenceKey, AcceptanceEvent>,
+    tip: CachedDbItem<AcceptanceJournalTip>,
+}
+
+impl Store {
+    pub fn new(db: Arc<DB>) -> Self {
+        Self {
+            db: db.clone(),
+            events: CachedDbAccess::new(
+                db.clone(),
+                CachePolicy::Empty,
+                DatabaseStorePrefixes::AcceptanceEvents.into(),
+            ),
+            tip: CachedDbItem::new(db, DatabaseStorePrefixes::AcceptanceJournalTip.into()),
+        }
+    }
+
+    pub fn get_event(&self, sequence: u64) -> StoreResult<Option<AcceptanceEvent>> {
+        self.events.read(sequence.into()).optional()
+    }
+
+    pub fn get_tip(&self) -> StoreResult<AcceptanceJournalTip> {
+        Ok(self.tip.read().optional()?.unwrap_or_default())
+    }
+
+    pub fn insert_batch(&self, batch: &mut WriteBatch, event: AcceptanceEvent) -> StoreResult<()> {
+        self.events
+            .write(BatchDbWriter::new(batch), event.sequence.into(), event)
+    }
+
+    pub fn delete_batch(&self, batch: &mut WriteBatch, sequence: u64) -> StoreResult<()> {
+        self.events
+            .delete(BatchDbWriter::new(batch), sequence.into())
+    }
+
+    /// Sets the tip in `batch` and commits it
+    pub fn commit(&mut self, mut batch: WriteBatch, tip: &AcceptanceJournalTip) -> StoreResult<()> {
+        self.tip.write(BatchDbWriter::new(&mut batch), tip)?;
+        self.db.write(batch)?;
+        Ok(())
+    }
+
+    /// Removes all events and detaches the journal from the chain, keeping the sequence going so
+    /// the cursors held by the clients fall into a gap
+    pub fn clear(&mut self) -> Result<(), StoreError> {
+        let tip = self.get_tip()?;
+        self.events.delete_all(DirectDbWriter::new(&self.db))?;
+        self.tip.write(
+            DirectDbWriter::new(&self.db),
+            &AcceptanceJournalTip {
+                first_sequence: tip.last_sequence + 1,
+                last_sequence: tip.last_sequence,
+                chain_block: None,
+            },
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use karlsen_database::{create_temp_db, prelude::ConnBuilder};
+
+    #[test]
+    fn test_acceptance_journal_store() {
+        let (_lifetime, db) = create_temp_db!(ConnBuilder::default().with_files_limit(10));
+        let mut store = Store::new(db);
+        let mut tip = store.get_tip().unwrap();
+        assert!(tip.is_empty());
+        assert_eq!(tip.chain_block, None);
+
+        let mut batch = WriteBatch::default();
+        for word in 1..=3 {
+            let event = AcceptanceEvent {
+                sequence: word,
+                chain_block_hash: Hash::from_u64_word(word),
+                removed: false,
+                blue_score: word,
+                timestamp: word,
+                recorded_time: word,
+                accepted_transaction_ids: vec![Hash::from_u64_word(100 + word)],
+            };
+            tip.last_sequence = event.sequence;
+            tip.chain_block = Some(event.chain_block_hash);
+            store.insert_batch(&mut batch, event).unwrap();
+        }
+        store.commit(batch, &tip).unwrap();
+        assert_eq!(store.get_tip().unwrap().len(), 3);
+        assert_eq!(
+            store.get_event(2).unwrap().unwrap().chain_block_hash,
+            Hash::from_u64_word(2)
+        );
+
+        let mut batch = WriteBatch::default();
+        store.delete_batch(&mut batch, 1).unwrap();
+        tip.first_sequence = 2;
+        store.commit(batch, &tip).unwrap();
+        assert_eq!(store.get_event(1).unwrap(), None);
+        assert_eq!(store.get_tip().unwrap().len(), 2);
+
+        // Clearing keeps the sequence going
+        store.clear().unwrap();
+        let tip = store.get_tip().unwrap();
+        assert!(tip.is_empty());
+        assert_eq!(tip.first_sequence, 4);
+        assert_eq!(tip.chain_block, None);
+        assert_eq!(store.get_event(3).unwrap(), None);
+    }
+}
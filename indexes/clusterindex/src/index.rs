@@ -0,0 +1,305 @@
+use crate::{
+    errors::AddressClusterIndexResult,
+    stores::{ClusterIndexTip, Store},
+    IDENT,
+};
+use karlsen_consensus_core::{
+    tx::{ScriptPublicKey, Transaction, TransactionOutpoint},
+    utxo::utxo_diff::UtxoDiff,
+};
+use karlsen_consensusmanager::{ConsensusManager, ConsensusResetHandler};
+use karlsen_core::{info, trace, warn};
+use karlsen_database::prelude::DB;
+use parking_lot::Mutex;
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Debug,
+    sync::{Arc, Weak},
+};
+
+/// Count of chain blocks indexed per consensus session
+const SYNC_CHUNK_SIZE: usize = 256;
+
+/// Depth below the sink, in chain blocks, at which chain blocks get indexed. Joined clusters cannot
+/// be split again, so only blocks unlikely to leave the selected chain are indexed.
+pub const CONFIRMATION_DEPTH: usize = 100;
+
+/// The cluster of an address
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AddressCluster {
+    /// Count of script public keys in the cluster, 1 if the key was never spent along with others
+    pub size: u64,
+    /// Members of the cluster, up to the requested limit
+    pub members: Vec<ScriptPublicKey>,
+}
+
+/// Clusters the script public keys spent by the transactions accepted by the selected chain,
+/// joining the keys of the inputs of every transaction (the common-input-ownership heuristic).
+///
+/// The index follows the selected chain of the consensus from the pruning point at the time it
+/// was first synced, lagging [`CONFIRMATION_DEPTH`] chain blocks behind the sink. A reorg deeper
+/// than that rebuilds the index from the pruning point. Clustering therefore only covers the
+/// pruning window: links made by transactions accepted below the pruning point the index was
+/// built from are missing.
+pub struct AddressClusterIndex {
+    consensus_manager: Arc<ConsensusManager>,
+    store: Mutex<Store>,
+    /// Serializes the syncs and resets of the index
+    sync_lock: Mutex<()>,
+}
+
+impl AddressClusterIndex {
+    pub fn new(consensus_manager: Arc<ConsensusManager>, db: Arc<DB>) -> Arc<Self> {
+        let index = Arc::new(Self {
+            consensus_manager: consensus_manager.clone(),
+            store: Mutex::new(Store::new(db)),
+            sync_lock: Mutex::new(()),
+        });
+        consensus_manager.register_consensus_reset_handler(Arc::new(
+            AddressClusterIndexConsensusResetHandler::new(Arc::downgrade(&index)),
+        ));
+        index
+    }
+
+    /// Returns the highest indexed chain block, if any
+    pub fn get_tip(&self) -> AddressClusterIndexResult<Option<ClusterIndexTip>> {
+        Ok(self.store.lock().get_tip()?)
+    }
+
+    /// Returns the cluster of `script_public_key` with up to `limit` of its members
+    pub fn get_cluster(
+        &self,
+        script_public_key: &ScriptPublicKey,
+        limit: usize,
+    ) -> AddressClusterIndexResult<AddressCluster> {
+        let store = self.store.lock();
+        Ok(match store.get_cluster_id(script_public_key)? {
+            Some(id) => AddressCluster {
+                size: store.get_cluster_size(id)?,
+                members: store.get_cluster_members(id, limit)?,
+            },
+            None => AddressCluster {
+                size: 1,
+                members: [script_public_key.clone()]
+                    .into_iter()
+                    .take(limit)
+                    .collect(),
+            },
+        })
+    }
+
+    /// Catches up with the selected chain of the consensus, indexing the chain blocks confirmed
+    /// since the last sync
+    pub fn sync(&self) -> AddressClusterIndexResult<()> {
+        let _guard = self.sync_lock.lock();
+        self.sync_chain()
+    }
+
+    fn sync_chain(&self) -> AddressClusterIndexResult<()> {
+        let consensus = self.consensus_manager.consensus();
+
+        let mut tip = match self.store.lock().get_tip()? {
+            Some(tip) => tip,
+            None => self.init()?,
+        };
+        let session = futures::executor::block_on(consensus.session_blocking());
+        let chain_path = match session.get_virtual_chain_from_block(tip.chain_block) {
+            Ok(chain_path) => chain_path,
+            Err(_) => {
+                // The tip fell below the pruning point while the index was not running
+                drop(session);
+                info!("The address cluster index is behind the pruning point, rebuilding it");
+                self.store.lock().delete_all()?;
+                return self.sync_chain();
+            }
+        };
+        drop(session);
+
+        if !chain_path.removed.is_empty() {
+            warn!(
+                "A reorg of {} chain blocks reached the address cluster index, rebuilding it",
+                chain_path.removed.len()
+            );
+            self.store.lock().delete_all()?;
+            return self.sync_chain();
+        }
+
+        let confirmed = chain_path.added.len().saturating_sub(CONFIRMATION_DEPTH);
+        for chunk in chain_path.added[..confirmed].chunks(SYNC_CHUNK_SIZE) {
+            let session = futures::executor::block_on(consensus.session_blocking());
+            let mut store = self.store.lock();
+            for hash in chunk.iter().copied() {
+                let acceptance_data = session.get_block_acceptance_data(hash)?;
+                let utxo_diff = session.get_block_utxo_diff(hash)?;
+                let blocks = acceptance_data
+                    .iter()
+                    .map(|mergeset_block| session.get_block(mergeset_block.block_hash))
+                    .collect::<Result<Vec<_>, _>>()?;
+                let transactions = acceptance_data
+                    .iter()
+                    .zip(blocks.iter())
+                    .flat_map(|(mergeset_block, block)| {
+                        mergeset_block
+                            .accepted_transactions
+                            .iter()
+                            .map(|entry| &block.transactions[entry.index_within_block as usize])
+                    })
+                    .collect::<Vec<_>>();
+                for script_public_keys in input_script_public_keys(&transactions, &utxo_diff) {
+                    store.join(&script_public_keys, &mut tip)?;
+                }
+                tip.chain_block = hash;
+            }
+            store.set_tip(&tip)?;
+            trace!("[{IDENT}] indexed {} chain blocks", chunk.len());
+        }
+        Ok(())
+    }
+
+    /// Anchors the empty index at the current pruning point
+    fn init(&self) -> AddressClusterIndexResult<ClusterIndexTip> {
+        let consensus = self.consensus_manager.consensus();
+        let session = futures::executor::block_on(consensus.session_blocking());
+        let pruning_point = session.pruning_point();
+        info!("Syncing the address cluster index from the pruning point {pruning_point}");
+        Ok(self.store.lock().init(pruning_point)?)
+    }
+
+    fn reset(&self) -> AddressClusterIndexResult<()> {
+        let _guard = self.sync_lock.lock();
+        Ok(self.store.lock().delete_all()?)
+    }
+}
+
+/// Returns the script public keys of the inputs of every accepted transaction spending several
+/// outputs. The UTXO diff of a chain block is netted over its mergeset, so an output created and
+/// spent by accepted transactions is missing from it and is resolved from the transactions.
+fn input_script_public_keys(
+    transactions: &[&Transaction],
+    utxo_diff: &UtxoDiff,
+) -> Vec<HashSet<ScriptPublicKey>> {
+    let created: HashMap<TransactionOutpoint, &ScriptPublicKey> = transactions
+        .iter()
+        .flat_map(|transaction| {
+            let id = transaction.id();
+            transaction
+                .outputs
+                .iter()
+                .enumerate()
+                .map(move |(index, output)| {
+                    (
+                        TransactionOutpoint::new(id, index as u32),
+                        &output.script_public_key,
+                    )
+                })
+        })
+        .collect();
+    transactions
+        .iter()
+        .filter(|transaction| transaction.inputs.len() >= 2)
+        .map(|transaction| {
+            transaction
+                .inputs
+                .iter()
+                .filter_map(|input| {
+                    created.get(&input.previous_outpoint).copied().or_else(|| {
+                        utxo_diff
+                            .remove
+                            .get(&input.previous_outpoint)
+                            .map(|entry| &entry.script_public_key)
+                    })
+                })
+                .cloned()
+                .collect()
+        })
+        .collect()
+}
+
+impl Debug for AddressClusterIndex {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AddressClusterIndex").finish()
+    }
+}
+
+struct AddressClusterIndexConsensusResetHandler {
+    index: Weak<AddressClusterIndex>,
+}
+
+impl AddressClusterIndexConsensusResetHandler {
+    fn new(index: Weak<AddressClusterIndex>) -> Self {
+        Self { index }
+    }
+}
+
+impl ConsensusResetHandler for AddressClusterIndexConsensusResetHandler {
+    fn handle_consensus_reset(&self) {
+        // The index is anchored again at the next sync
+        if let Some(index) = self.index.upgrade() {
+            index.reset().unwrap();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use karlsen_consensus_core::{
+        subnets::SUBNETWORK_ID_NATIVE,
+        tx::{TransactionInput, TransactionOutput, UtxoEntry},
+    };
+    use karlsen_hashes::Hash;
+
+    #[test]
+    fn test_input_script_public_keys() {
+        let script_public_key = |byte: u8| ScriptPublicKey::from_vec(0, vec![byte; 34]);
+        let transaction = |previous_outpoints: Vec<TransactionOutpoint>, bytes: &[u8]| {
+            Transaction::new(
+                0,
+                previous_outpoints
+                    .into_iter()
+                    .map(|previous_outpoint| TransactionInput::new(previous_outpoint, vec![], 0, 1))
+                    .collect(),
+                bytes
+                    .iter()
+                    .map(|&byte| TransactionOutput::new(10, script_public_key(byte)))
+                    .collect(),
+                0,
+                SUBNETWORK_ID_NATIVE,
+                0,
+                vec![],
+            )
+        };
+        let funding = TransactionOutpoint::new(Hash::from_u64_word(1), 0);
+        let other_funding = TransactionOutpoint::new(Hash::from_u64_word(2), 0);
+        let first = transaction(vec![funding], &[3, 4]);
+        let second = transaction(
+            vec![
+                TransactionOutpoint::new(first.id(), 0),
+                TransactionOutpoint::new(first.id(), 1),
+                other_funding,
+            ],
+            &[5],
+        );
+
+        // Both outputs of the first transaction are spent within the mergeset, so the diff nets
+        // them out and only the funding outputs remain on its remove side
+        let mut utxo_diff = UtxoDiff::default();
+        utxo_diff
+            .remove
+            .insert(funding, UtxoEntry::new(10, script_public_key(1), 0, false));
+        utxo_diff.remove.insert(
+            other_funding,
+            UtxoEntry::new(10, script_public_key(2), 0, false),
+        );
+
+        // The single input transaction joins nothing, the second one joins its three keys
+        let clusters = input_script_public_keys(&[&first, &second], &utxo_diff);
+        assert_eq!(
+            clusters,
+            vec![[2, 3, 4]
+                .into_iter()
+                .map(script_public_key)
+                .collect::<HashSet<_>>()]
+        );
+    }
+}
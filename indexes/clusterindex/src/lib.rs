@@ -0,0 +1,13 @@
+//!
+//! Opt-in analytics index clustering the addresses of the selected chain by the common-input-ownership
+//! heuristic: the addresses spent together by a transaction are assumed to belong to the same owner.
+//!
+
+pub mod errors;
+mod index;
+pub mod service;
+pub mod stores;
+
+pub use crate::index::{AddressCluster, AddressClusterIndex, CONFIRMATION_DEPTH};
+
+const IDENT: &str = "clusterindex";
@@ -0,0 +1,106 @@
+use crate::{index::AddressClusterIndex, IDENT};
+use karlsen_consensus_notify::{
+    connection::ConsensusChannelConnection, notification::Notification as ConsensusNotification,
+    notifier::ConsensusNotifier,
+};
+use karlsen_consensusmanager::spawn_blocking;
+use karlsen_core::{
+    task::service::{AsyncService, AsyncServiceFuture},
+    trace, warn,
+};
+use karlsen_notify::{
+    connection::ChannelType,
+    listener::ListenerLifespan,
+    scope::VirtualChainChangedScope,
+    subscription::{MutationPolicies, UtxosChangedMutationPolicy},
+};
+use karlsen_utils::{channel::Channel, triggers::SingleTrigger};
+use std::sync::Arc;
+
+const CLUSTER_INDEX_SERVICE: &str = IDENT;
+
+/// Keeps the [`AddressClusterIndex`] in sync with the selected chain by syncing it on every
+/// virtual chain change notified by the consensus
+pub struct AddressClusterIndexService {
+    index: Arc<AddressClusterIndex>,
+    channel: Channel<ConsensusNotification>,
+    shutdown: SingleTrigger,
+}
+
+impl AddressClusterIndexService {
+    pub fn new(
+        consensus_notifier: &Arc<ConsensusNotifier>,
+        index: Arc<AddressClusterIndex>,
+    ) -> Self {
+        let channel = Channel::<ConsensusNotification>::default();
+        let listener_id = consensus_notifier.register_new_listener(
+            ConsensusChannelConnection::new(
+                CLUSTER_INDEX_SERVICE,
+                channel.sender(),
+                ChannelType::Closable,
+            ),
+            ListenerLifespan::Static(MutationPolicies::new(UtxosChangedMutationPolicy::Wildcard)),
+        );
+        consensus_notifier
+            .try_start_notify(listener_id, VirtualChainChangedScope::new(false).into())
+            .expect("the subscription always succeeds");
+        Self {
+            index,
+            channel,
+            shutdown: SingleTrigger::default(),
+        }
+    }
+
+    pub fn index(&self) -> Arc<AddressClusterIndex> {
+        self.index.clone()
+    }
+
+    async fn sync(&self) {
+        let index = self.index.clone();
+        match spawn_blocking(move || index.sync()).await {
+            Ok(Err(err)) => warn!("Error while syncing the address cluster index: {}", err),
+            Err(err) => warn!("The address cluster index sync panicked: {}", err),
+            Ok(Ok(())) => {}
+        }
+    }
+}
+
+impl AsyncService for AddressClusterIndexService {
+    fn ident(self: Arc<Self>) -> &'static str {
+        CLUSTER_INDEX_SERVICE
+    }
+
+    fn start(self: Arc<Self>) -> AsyncServiceFuture {
+        trace!("{} starting", CLUSTER_INDEX_SERVICE);
+        let shutdown_signal = self.shutdown.listener.clone();
+        let receiver = self.channel.receiver();
+        Box::pin(async move {
+            // Catch up with the chain changes which occurred while the node was down
+            self.sync().await;
+            tokio::select! {
+                _ = shutdown_signal => {}
+                _ = async {
+                    // The channel gets closed when the consensus notifier stops
+                    while receiver.recv().await.is_ok() {
+                        // A single sync covers all the pending notifications
+                        while receiver.try_recv().is_ok() {}
+                        self.sync().await;
+                    }
+                } => {}
+            }
+            Ok(())
+        })
+    }
+
+    fn signal_exit(self: Arc<Self>) {
+        trace!("sending an exit signal to {}", CLUSTER_INDEX_SERVICE);
+        self.shutdown.trigger.trigger();
+    }
+
+    fn stop(self: Arc<Self>) -> AsyncServiceFuture {
+        Box::pin(async move {
+            trace!("{} stopped", CLUSTER_INDEX_SERVICE);
+            Ok(())
+        })
+    }
+}
@@ -0,0 +1,284 @@
+use karlsen_consensus_core::tx::ScriptPublicKey;
+use karlsen_database::{
+    prelude::{
+        BatchDbWriter, CachePolicy, CachedDbAccess, CachedDbItem, DbSetAccess, DbWriter,
+        DirectDbWriter, StoreError, StoreResult, StoreResultExtensions, DB,
+    },
+    registry::DatabaseStorePrefixes,
+};
+use karlsen_hashes::Hash;
+use karlsen_utils::hex::ToHex;
+use rocksdb::WriteBatch;
+use serde::{Deserialize, Serialize};
+use std::{cmp::Reverse, collections::HashSet, fmt::Display, sync::Arc};
+
+/// The highest indexed chain block, along with the id of the next created cluster
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ClusterIndexTip {
+    pub chain_block: Hash,
+    pub next_cluster_id: u64,
+}
+
+/// The version in little endian followed by the script
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+struct ScriptPublicKeyKey(Vec<u8>);
+
+impl From<&ScriptPublicKey> for ScriptPublicKeyKey {
+    fn from(script_public_key: &ScriptPublicKey) -> Self {
+        let script = script_public_key.script();
+        let mut bytes = Vec::with_capacity(std::mem::size_of::<u16>() + script.len());
+        bytes.extend_from_slice(&script_public_key.version().to_le_bytes());
+        bytes.extend_from_slice(script);
+        Self(bytes)
+    }
+}
+
+impl AsRef<[u8]> for ScriptPublicKeyKey {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl Display for ScriptPublicKeyKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0.to_hex())
+    }
+}
+
+/// Big endian cluster id, the fixed length letting the members of a cluster be iterated by prefix
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+struct ClusterKey([u8; 8]);
+
+impl From<u64> for ClusterKey {
+    fn from(id: u64) -> Self {
+        Self(id.to_be_bytes())
+    }
+}
+
+impl AsRef<[u8]> for ClusterKey {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl Display for ClusterKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", u64::from_be_bytes(self.0))
+    }
+}
+
+/// Clusters of script public keys, kept as a disjoint set where every key points directly at its
+/// cluster. Joining clusters moves the members of the smaller ones into the largest, so every key
+/// is moved a logarithmic count of times.
+///
+/// Every join is written atomically along with the next cluster id, and joining keys which already
+/// share a cluster changes nothing, so re-applying the joins of a chain block is harmless.
+#[derive(Clone)]
+pub struct Store {
+    db: Arc<DB>,
+    cluster_ids: CachedDbAccess<ScriptPublicKeyKey, u64>,
+    members: DbSetAccess<ClusterKey, ScriptPublicKey>,
+    sizes: CachedDbAccess<ClusterKey, u64>,
+    tip: CachedDbItem<ClusterIndexTip>,
+}
+
+impl Store {
+    pub fn new(db: Arc<DB>) -> Self {
+        Self {
+            db: db.clone(),
+            cluster_ids: CachedDbAccess::new(
+                db.clone(),
+                CachePolicy::Empty,
+                DatabaseStorePrefixes::ClusterIds.into(),
+            ),
+            members: DbSetAccess::new(db.clone(), DatabaseStorePrefixes::ClusterMembers.into()),
+            sizes: CachedDbAccess::new(
+                db.clone(),
+                CachePolicy::Empty,
+                DatabaseStorePrefixes::ClusterSizes.into(),
+            ),
+            tip: CachedDbItem::new(db, DatabaseStorePrefixes::ClusterIndexTip.into()),
+        }
+    }
+
+    pub fn get_tip(&self) -> StoreResult<Option<ClusterIndexTip>> {
+        self.tip.read().optional()
+    }
+
+    pub fn get_cluster_id(&self, script_public_key: &ScriptPublicKey) -> StoreResult<Option<u64>> {
+        self.cluster_ids.read(script_public_key.into()).optional()
+    }
+
+    pub fn get_cluster_size(&self, cluster_id: u64) -> StoreResult<u64> {
+        Ok(self
+            .sizes
+            .read(cluster_id.into())
+            .optional()?
+            .unwrap_or_default())
+    }
+
+    /// Returns up to `limit` members of the cluster `cluster_id`
+    pub fn get_cluster_members(
+        &self,
+        cluster_id: u64,
+        limit: usize,
+    ) -> StoreResult<Vec<ScriptPublicKey>> {
+        self.members
+            .bucket_iterator(cluster_id.into())
+            .take(limit)
+            .collect()
+    }
+
+    /// Anchors an empty index at `chain_block`
+    pub fn init(&mut self, chain_block: Hash) -> StoreResult<ClusterIndexTip> {
+        let tip = ClusterIndexTip {
+            chain_block,
+            next_cluster_id: 0,
+        };
+        self.tip.write(DirectDbWriter::new(&self.db), &tip)?;
+        Ok(tip)
+    }
+
+    /// Joins the clusters of `script_public_keys` into a single cluster, creating it if none of
+    /// the keys is clustered yet
+    pub fn join(
+        &mut self,
+        script_public_keys: &HashSet<ScriptPublicKey>,
+        tip: &mut ClusterIndexTip,
+    ) -> StoreResult<()> {
+        let mut clusters: Vec<(u64, u64)> = Vec::new();
+        let mut unclustered = Vec::new();
+        for script_public_key in script_public_keys {
+            match self.get_cluster_id(script_public_key)? {
+                Some(id) if clusters.iter().any(|&(cluster, _)| cluster == id) => {}
+                Some(id) => clusters.push((id, self.get_cluster_size(id)?)),
+                None => unclustered.push(script_public_key),
+            }
+        }
+        if clusters.len() + unclustered.len() < 2 {
+            return Ok(());
+        }
+
+        let mut batch = WriteBatch::default();
+        clusters.sort_by_key(|&(id, size)| (Reverse(size), id));
+        let (target, mut size) = match clusters.first() {
+            Some(&cluster) => cluster,
+            None => {
+                tip.next_cluster_id += 1;
+                (tip.next_cluster_id - 1, 0)
+            }
+        };
+        for &(id, _) in clusters.iter().skip(1) {
+            for member in self.get_cluster_members(id, usize::MAX)? {
+                self.cluster_ids
+                    .write(BatchDbWriter::new(&mut batch), (&member).into(), target)?;
+                self.members
+                    .write(BatchDbWriter::new(&mut batch), target.into(), member)?;
+                size += 1;
+            }
+            self.members
+                .delete_bucket(BatchDbWriter::new(&mut batch), id.into())?;
+            self.sizes
+                .delete(BatchDbWriter::new(&mut batch), id.into())?;
+        }
+        for script_public_key in unclustered {
+            self.cluster_ids.write(
+                BatchDbWriter::new(&mut batch),
+                script_public_key.into(),
+                target,
+            )?;
+            self.members.write(
+                BatchDbWriter::new(&mut batch),
+                target.into(),
+                script_public_key.clone(),
+            )?;
+            size += 1;
+        }
+        self.sizes
+            .write(BatchDbWriter::new(&mut batch), target.into(), size)?;
+        self.tip.write(BatchDbWriter::new(&mut batch), tip)?;
+        self.db.write(batch)?;
+        Ok(())
+    }
+
+    pub fn set_tip(&mut self, tip: &ClusterIndexTip) -> StoreResult<()> {
+        self.tip.write(DirectDbWriter::new(&self.db), tip)
+    }
+
+    /// Removes all entries from the index
+    pub fn delete_all(&mut self) -> Result<(), StoreError> {
+        self.tip.remove(DirectDbWriter::new(&self.db))?;
+        self.cluster_ids.delete_all(DirectDbWriter::new(&self.db))?;
+        self.sizes.delete_all(DirectDbWriter::new(&self.db))?;
+        let (from, to) = rocksdb::PrefixRange(self.members.prefix()).into_bounds();
+        DirectDbWriter::new(&self.db).delete_range(from.unwrap(), to.unwrap())?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use karlsen_database::{create_temp_db, prelude::ConnBuilder};
+
+    fn keys(words: &[u8]) -> HashSet<ScriptPublicKey> {
+        words
+            .iter()
+            .map(|&word| ScriptPublicKey::from_vec(0, vec![word; 34]))
+            .collect()
+    }
+
+    fn cluster_of(store: &Store, word: u8) -> Option<(u64, u64, HashSet<ScriptPublicKey>)> {
+        let id = store
+            .get_cluster_id(&ScriptPublicKey::from_vec(0, vec![word; 34]))
+            .unwrap()?;
+        Some((
+            id,
+            store.get_cluster_size(id).unwrap(),
+            store
+                .get_cluster_members(id, usize::MAX)
+                .unwrap()
+                .into_iter()
+                .collect(),
+        ))
+    }
+
+    #[test]
+    fn test_cluster_store() {
+        let (_lifetime, db) = create_temp_db!(ConnBuilder::default().with_files_limit(10));
+        let mut store = Store::new(db);
+        assert_eq!(store.get_tip().unwrap(), None);
+        let mut tip = store.init(Hash::from_u64_word(1)).unwrap();
+
+        // A single key makes no cluster
+        store.join(&keys(&[1]), &mut tip).unwrap();
+        assert_eq!(cluster_of(&store, 1), None);
+
+        store.join(&keys(&[1, 2]), &mut tip).unwrap();
+        store.join(&keys(&[3, 4, 5]), &mut tip).unwrap();
+        assert_eq!(tip.next_cluster_id, 2);
+        assert_eq!(cluster_of(&store, 2), Some((0, 2, keys(&[1, 2]))));
+        assert_eq!(cluster_of(&store, 4), Some((1, 3, keys(&[3, 4, 5]))));
+
+        // The smaller cluster is moved into the larger one, along with the new key
+        store.join(&keys(&[1, 5, 6]), &mut tip).unwrap();
+        assert_eq!(tip.next_cluster_id, 2);
+        let joined = Some((1, 6, keys(&[1, 2, 3, 4, 5, 6])));
+        for word in 1..=6 {
+            assert_eq!(cluster_of(&store, word), joined);
+        }
+        assert_eq!(store.get_cluster_size(0).unwrap(), 0);
+        assert!(store.get_cluster_members(0, usize::MAX).unwrap().is_empty());
+        assert_eq!(store.get_cluster_members(1, 4).unwrap().len(), 4);
+
+        // Joining keys of the same cluster changes nothing
+        store.join(&keys(&[2, 3]), &mut tip).unwrap();
+        assert_eq!(cluster_of(&store, 2), joined);
+        assert_eq!(store.get_tip().unwrap(), Some(tip));
+
+        store.delete_all().unwrap();
+        assert_eq!(store.get_tip().unwrap(), None);
+        assert_eq!(cluster_of(&store, 1), None);
+        assert!(store.get_cluster_members(1, usize::MAX).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,21 @@
+use thiserror::Error;
+
+use crate::IDENT;
+use karlsen_consensus_core::errors::consensus::ConsensusError;
+use karlsen_database::prelude::StoreError;
+
+/// Errors originating from the [`BlockFilterIndex`](crate::BlockFilterIndex).
+#[derive(Error, Debug)]
+pub enum BlockFilterIndexError {
+    #[error("[{IDENT}]: {0}")]
+    StoreAccessError(#[from] StoreError),
+
+    #[error("[{IDENT}]: {0}")]
+    ConsensusError(#[from] ConsensusError),
+
+    #[error("[{IDENT}]: block {0} is not an indexed chain block")]
+    BlockNotIndexed(karlsen_hashes::Hash),
+}
+
+/// Results originating from the [`BlockFilterIndex`](crate::BlockFilterIndex).
+pub type BlockFilterIndexResult<T> = Result<T, BlockFilterIndexError>;
@@ -0,0 +1,531 @@
+//!
+//! Golomb-coded set filters of the script public keys touched by a chain block, following
+//! BIP158 (basic filter type).
+//!
+//! The elements of the filter of a chain block are the script public keys of the outputs
+//! created and spent by the transactions the block accepted. A filter is matched by hashing
+//! the candidate elements with the same block-keyed SipHash, so a wallet can find out which
+//! blocks concern its addresses without revealing them to the node.
+//!
+
+use karlsen_consensus_core::{
+    tx::{ScriptPublicKey, Transaction, TransactionOutpoint},
+    utxo::utxo_diff::UtxoDiff,
+};
+use karlsen_hashes::{BlockFilterHash, BlockFilterHeaderHash, Hash, Hasher, HasherBase};
+use karlsen_utils::mem_size::MemSizeEstimator;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+
+/// Bit length of the remainder of the Golomb-Rice coding
+pub const FILTER_P: u8 = 19;
+
+/// Inverse of the false positive rate
+pub const FILTER_M: u64 = 784_931;
+
+/// Returns the filter element of a script public key: the version in little endian followed
+/// by the script
+pub fn filter_element(script_public_key: &ScriptPublicKey) -> Vec<u8> {
+    let script = script_public_key.script();
+    let mut element = Vec::with_capacity(std::mem::size_of::<u16>() + script.len());
+    element.extend_from_slice(&script_public_key.version().to_le_bytes());
+    element.extend_from_slice(script);
+    element
+}
+
+/// A serialized Golomb-coded set: the element count as a compact size followed by the
+/// Golomb-Rice coded deltas of the sorted element hashes
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BlockFilter(Vec<u8>);
+
+impl BlockFilter {
+    /// Builds the filter of the block `block_hash` out of `elements`. Empty and duplicate
+    /// elements are ignored.
+    pub fn new<'a>(block_hash: Hash, elements: impl IntoIterator<Item = &'a [u8]>) -> Self {
+        let elements: HashSet<&[u8]> = elements
+            .into_iter()
+            .filter(|element| !element.is_empty())
+            .collect();
+        let n = elements.len() as u64;
+        let range = filter_range(n);
+        let key = SipKey::from(block_hash);
+        let mut values = elements
+            .into_iter()
+            .map(|element| key.hash_to_range(element, range))
+            .collect::<Vec<_>>();
+        values.sort_unstable();
+
+        let mut writer = BitWriter::default();
+        write_compact_size(&mut writer.bytes, n);
+        let mut last = 0;
+        for value in values {
+            let delta = value - last;
+            writer.write_unary(delta >> FILTER_P);
+            writer.write_bits(delta, FILTER_P);
+            last = value;
+        }
+        Self(writer.bytes)
+    }
+
+    /// Builds the filter of a chain block out of the transactions it accepted and its UTXO
+    /// diff. The diff is netted over the mergeset, so an output created and spent by accepted
+    /// transactions appears in neither of its sides: such outputs are taken from the
+    /// transactions, and only the scripts of the outputs the block spent from its selected
+    /// parent UTXO set are looked up in the diff.
+    pub fn from_accepted_transactions<'a>(
+        block_hash: Hash,
+        transactions: impl IntoIterator<Item = &'a Transaction>,
+        utxo_diff: &UtxoDiff,
+    ) -> Self {
+        let transactions = transactions.into_iter().collect::<Vec<_>>();
+        let created: HashMap<TransactionOutpoint, &ScriptPublicKey> = transactions
+            .iter()
+            .flat_map(|transaction| {
+                let id = transaction.id();
+                transaction
+                    .outputs
+                    .iter()
+                    .enumerate()
+                    .map(move |(index, output)| {
+                        (
+                            TransactionOutpoint::new(id, index as u32),
+                            &output.script_public_key,
+                        )
+                    })
+            })
+            .collect();
+        let spent = transactions
+            .iter()
+            .flat_map(|transaction| transaction.inputs.iter())
+            .filter_map(|input| {
+                created.get(&input.previous_outpoint).copied().or_else(|| {
+                    utxo_diff
+                        .remove
+                        .get(&input.previous_outpoint)
+                        .map(|entry| &entry.script_public_key)
+                })
+            });
+        let elements = created
+            .values()
+            .copied()
+            .chain(spent)
+            .map(filter_element)
+            .collect::<Vec<_>>();
+        Self::new(
+            block_hash,
+            elements.iter().map(|element| element.as_slice()),
+        )
+    }
+
+    pub fn from_bytes(bytes: Vec<u8>) -> Self {
+        Self(bytes)
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+
+    /// Number of elements in the filter, `None` if the filter is malformed
+    pub fn element_count(&self) -> Option<u64> {
+        read_compact_size(&self.0).map(|(n, _)| n)
+    }
+
+    pub fn hash(&self) -> Hash {
+        BlockFilterHash::hash(&self.0)
+    }
+
+    /// Returns the filter header committing to this filter and to all the filters of the
+    /// selected chain below it through `prev_header`
+    pub fn header(&self, prev_header: Hash) -> Hash {
+        let mut hasher = BlockFilterHeaderHash::new();
+        hasher.update(self.hash()).update(prev_header);
+        hasher.finalize()
+    }
+
+    /// Returns the header the filter headers of the chain blocks pointing at `pruning_point`
+    /// chain from. Filter headers restart from it at every move of the pruning point, so
+    /// they only commit to chain blocks which every node keeps the body of, and two nodes
+    /// agree on them whatever point their index was synced from.
+    pub fn anchor_header(pruning_point: Hash) -> Hash {
+        let mut hasher = BlockFilterHeaderHash::new();
+        hasher.update(Hash::default()).update(pruning_point);
+        hasher.finalize()
+    }
+
+    /// Returns true if any of `elements` probably belongs to the filter of the block
+    /// `block_hash`. False positives occur at a rate of 1/[`FILTER_M`] per element, false
+    /// negatives never do. A malformed filter matches nothing.
+    pub fn match_any<'a>(
+        &self,
+        block_hash: Hash,
+        elements: impl IntoIterator<Item = &'a [u8]>,
+    ) -> bool {
+        let Some((n, offset)) = read_compact_size(&self.0) else {
+            return false;
+        };
+        if n == 0 {
+            return false;
+        }
+        let range = filter_range(n);
+        let key = SipKey::from(block_hash);
+        let mut queries = elements
+            .into_iter()
+            .filter(|element| !element.is_empty())
+            .map(|element| key.hash_to_range(element, range))
+            .collect::<Vec<_>>();
+        if queries.is_empty() {
+            return false;
+        }
+        queries.sort_unstable();
+
+        let mut reader = BitReader::new(&self.0[offset..]);
+        let mut queries = queries.into_iter().peekable();
+        let mut value = 0;
+        for _ in 0..n {
+            let (Some(quotient), Some(remainder)) =
+                (reader.read_unary(), reader.read_bits(FILTER_P))
+            else {
+                return false;
+            };
+            value += (quotient << FILTER_P) + remainder;
+            while let Some(&query) = queries.peek() {
+                match query.cmp(&value) {
+                    std::cmp::Ordering::Less => {
+                        queries.next();
+                    }
+                    std::cmp::Ordering::Equal => return true,
+                    std::cmp::Ordering::Greater => break,
+                }
+            }
+            if queries.peek().is_none() {
+                return false;
+            }
+        }
+        false
+    }
+}
+
+impl MemSizeEstimator for BlockFilter {}
+
+/// Returns the range the element hashes of a filter of `n` elements are mapped to. The element
+/// count of a filter read from the network is arbitrary, so the product saturates instead of
+/// wrapping around.
+fn filter_range(n: u64) -> u64 {
+    n.saturating_mul(FILTER_M)
+}
+
+/// SipHash-2-4 key derived from the first 16 bytes of the block hash
+struct SipKey(u64, u64);
+
+impl From<Hash> for SipKey {
+    fn from(block_hash: Hash) -> Self {
+        let bytes = block_hash.as_bytes();
+        Self(
+            u64::from_le_bytes(bytes[..8].try_into().unwrap()),
+            u64::from_le_bytes(bytes[8..16].try_into().unwrap()),
+        )
+    }
+}
+
+impl SipKey {
+    /// Maps the hash of `data` uniformly into `[0, range)`
+    fn hash_to_range(&self, data: &[u8], range: u64) -> u64 {
+        ((siphash24(self.0, self.1, data) as u128 * range as u128) >> 64) as u64
+    }
+}
+
+fn siphash24(k0: u64, k1: u64, data: &[u8]) -> u64 {
+    let mut v = [
+        k0 ^ 0x736f6d6570736575,
+        k1 ^ 0x646f72616e646f6d,
+        k0 ^ 0x6c7967656e657261,
+        k1 ^ 0x7465646279746573,
+    ];
+    let chunks = data.chunks_exact(8);
+    let tail = chunks.remainder();
+    for chunk in chunks {
+        let m = u64::from_le_bytes(chunk.try_into().unwrap());
+        v[3] ^= m;
+        sip_round(&mut v);
+        sip_round(&mut v);
+        v[0] ^= m;
+    }
+    let mut last = (data.len() as u64 & 0xff) << 56;
+    for (i, byte) in tail.iter().enumerate() {
+        last |= (*byte as u64) << (8 * i);
+    }
+    v[3] ^= last;
+    sip_round(&mut v);
+    sip_round(&mut v);
+    v[0] ^= last;
+    v[2] ^= 0xff;
+    for _ in 0..4 {
+        sip_round(&mut v);
+    }
+    v[0] ^ v[1] ^ v[2] ^ v[3]
+}
+
+#[inline(always)]
+fn sip_round(v: &mut [u64; 4]) {
+    v[0] = v[0].wrapping_add(v[1]);
+    v[1] = v[1].rotate_left(13) ^ v[0];
+    v[0] = v[0].rotate_left(32);
+    v[2] = v[2].wrapping_add(v[3]);
+    v[3] = v[3].rotate_left(16) ^ v[2];
+    v[0] = v[0].wrapping_add(v[3]);
+    v[3] = v[3].rotate_left(21) ^ v[0];
+    v[2] = v[2].wrapping_add(v[1]);
+    v[1] = v[1].rotate_left(17) ^ v[2];
+    v[2] = v[2].rotate_left(32);
+}
+
+fn write_compact_size(bytes: &mut Vec<u8>, n: u64) {
+    match n {
+        0..=0xfc => bytes.push(n as u8),
+        0xfd..=0xffff => {
+            bytes.push(0xfd);
+            bytes.extend_from_slice(&(n as u16).to_le_bytes());
+        }
+        0x10000..=0xffff_ffff => {
+            bytes.push(0xfe);
+            bytes.extend_from_slice(&(n as u32).to_le_bytes());
+        }
+        _ => {
+            bytes.push(0xff);
+            bytes.extend_from_slice(&n.to_le_bytes());
+        }
+    }
+}
+
+/// Reads a compact size, returning the value and the count of bytes it spans
+fn read_compact_size(bytes: &[u8]) -> Option<(u64, usize)> {
+    let read = |len: usize| -> Option<u64> {
+        let slice = bytes.get(1..1 + len)?;
+        let mut buf = [0u8; 8];
+        buf[..len].copy_from_slice(slice);
+        Some(u64::from_le_bytes(buf))
+    };
+    match *bytes.first()? {
+        0xfd => Some((read(2)?, 3)),
+        0xfe => Some((read(4)?, 5)),
+        0xff => Some((read(8)?, 9)),
+        n => Some((n as u64, 1)),
+    }
+}
+
+/// Writes bits most significant first
+#[derive(Default)]
+struct BitWriter {
+    bytes: Vec<u8>,
+    /// Count of free bits in the last byte
+    free: u8,
+}
+
+impl BitWriter {
+    fn write_bit(&mut self, bit: bool) {
+        if self.free == 0 {
+            self.bytes.push(0);
+            self.free = 8;
+        }
+        self.free -= 1;
+        if bit {
+            *self.bytes.last_mut().unwrap() |= 1 << self.free;
+        }
+    }
+
+    fn write_bits(&mut self, value: u64, count: u8) {
+        for i in (0..count).rev() {
+            self.write_bit((value >> i) & 1 == 1);
+        }
+    }
+
+    fn write_unary(&mut self, value: u64) {
+        for _ in 0..value {
+            self.write_bit(true);
+        }
+        self.write_bit(false);
+    }
+}
+
+struct BitReader<'a> {
+    bytes: &'a [u8],
+    position: usize,
+}
+
+impl<'a> BitReader<'a> {
+    fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes, position: 0 }
+    }
+
+    fn read_bit(&mut self) -> Option<bool> {
+        let byte = *self.bytes.get(self.position / 8)?;
+        let bit = (byte >> (7 - self.position % 8)) & 1 == 1;
+        self.position += 1;
+        Some(bit)
+    }
+
+    fn read_bits(&mut self, count: u8) -> Option<u64> {
+        let mut value = 0;
+        for _ in 0..count {
+            value = (value << 1) | self.read_bit()? as u64;
+        }
+        Some(value)
+    }
+
+    fn read_unary(&mut self) -> Option<u64> {
+        let mut value = 0;
+        while self.read_bit()? {
+            value += 1;
+        }
+        Some(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use karlsen_consensus_core::{
+        subnets::SUBNETWORK_ID_NATIVE,
+        tx::{TransactionInput, TransactionOutput, UtxoEntry},
+    };
+
+    #[test]
+    fn test_siphash24() {
+        // Reference vectors of the SipHash paper, with the key 00 01 .. 0f
+        let (k0, k1) = (0x0706050403020100, 0x0f0e0d0c0b0a0908);
+        assert_eq!(siphash24(k0, k1, &[]), 0x726fdb47dd0e0e31);
+        assert_eq!(siphash24(k0, k1, &[0]), 0x74f839c593dc67fd);
+        assert_eq!(
+            siphash24(k0, k1, &(0..8).collect::<Vec<u8>>()),
+            0x93f5f5799a932462
+        );
+        assert_eq!(
+            siphash24(k0, k1, &(0..15).collect::<Vec<u8>>()),
+            0xa129ca6149be45e5
+        );
+    }
+
+    #[test]
+    fn test_compact_size() {
+        for n in [0, 0xfc, 0xfd, 0xffff, 0x10000, 0xffff_ffff, 0x1_0000_0000] {
+            let mut bytes = vec![];
+            write_compact_size(&mut bytes, n);
+            assert_eq!(read_compact_size(&bytes), Some((n, bytes.len())));
+        }
+        assert_eq!(read_compact_size(&[0xfd, 0x01]), None);
+    }
+
+    #[test]
+    fn test_block_filter() {
+        let block_hash = Hash::from_slice(&[42; 32]);
+        let elements = (0u32..500)
+            .map(|i| i.to_le_bytes().repeat(8))
+            .collect::<Vec<_>>();
+        let filter = BlockFilter::new(
+            block_hash,
+            elements
+                .iter()
+                .map(|element| element.as_slice())
+                .chain([&[][..]]),
+        );
+        assert_eq!(filter.element_count(), Some(500));
+
+        // Every element matches, alone or among others
+        for element in elements.iter() {
+            assert!(filter.match_any(block_hash, [element.as_slice()]));
+        }
+        let absent = (1000u32..2000)
+            .map(|i| i.to_le_bytes().repeat(8))
+            .collect::<Vec<_>>();
+        assert!(filter.match_any(
+            block_hash,
+            absent
+                .iter()
+                .map(|element| element.as_slice())
+                .chain([elements[250].as_slice()])
+        ));
+
+        // With a false positive rate of 1/784931, none of the absent elements is expected to match
+        assert!(!filter.match_any(block_hash, absent.iter().map(|element| element.as_slice())));
+
+        // The filter is keyed by the block hash
+        assert_ne!(
+            BlockFilter::new(
+                Hash::from_slice(&[43; 32]),
+                elements.iter().map(|element| element.as_slice())
+            ),
+            filter
+        );
+
+        // Empty filters match nothing
+        let empty = BlockFilter::new(block_hash, []);
+        assert_eq!(empty.as_bytes(), &[0]);
+        assert_eq!(empty.element_count(), Some(0));
+        assert!(!empty.match_any(block_hash, [elements[0].as_slice()]));
+
+        // Headers chain the filters
+        assert_ne!(
+            filter.header(Hash::default()),
+            filter.header(empty.header(Hash::default()))
+        );
+    }
+
+    #[test]
+    fn test_filter_of_accepted_transactions() {
+        let script_public_key = |byte: u8| ScriptPublicKey::from_vec(0, vec![byte; 34]);
+        let transaction = |previous_outpoint: TransactionOutpoint, byte: u8| {
+            Transaction::new(
+                0,
+                vec![TransactionInput::new(previous_outpoint, vec![], 0, 1)],
+                vec![TransactionOutput::new(10, script_public_key(byte))],
+                0,
+                SUBNETWORK_ID_NATIVE,
+                0,
+                vec![],
+            )
+        };
+        let funding = TransactionOutpoint::new(Hash::from_u64_word(1), 0);
+        let first = transaction(funding, 2);
+        let second = transaction(TransactionOutpoint::new(first.id(), 0), 3);
+
+        // The output of the first transaction is spent within the mergeset, so the diff nets it out
+        let mut utxo_diff = UtxoDiff::default();
+        utxo_diff
+            .remove
+            .insert(funding, UtxoEntry::new(10, script_public_key(1), 0, false));
+        utxo_diff.add.insert(
+            TransactionOutpoint::new(second.id(), 0),
+            UtxoEntry::new(10, script_public_key(3), 1, false),
+        );
+
+        let block_hash = Hash::from_u64_word(42);
+        let filter =
+            BlockFilter::from_accepted_transactions(block_hash, [&first, &second], &utxo_diff);
+        assert_eq!(filter.element_count(), Some(3));
+        for byte in 1..=3 {
+            let element = filter_element(&script_public_key(byte));
+            assert!(filter.match_any(block_hash, [element.as_slice()]));
+        }
+        let element = filter_element(&script_public_key(4));
+        assert!(!filter.match_any(block_hash, [element.as_slice()]));
+    }
+
+    #[test]
+    fn test_malformed_filter() {
+        // An element count overflowing the hash range matches nothing instead of panicking
+        let mut bytes = vec![];
+        write_compact_size(&mut bytes, u64::MAX);
+        bytes.extend_from_slice(&[0xff; 8]);
+        let filter = BlockFilter::from_bytes(bytes);
+        assert_eq!(filter.element_count(), Some(u64::MAX));
+        assert!(!filter.match_any(Hash::default(), [&[1u8][..]]));
+    }
+
+    #[test]
+    fn test_anchor_header() {
+        let filter = BlockFilter::new(Hash::default(), [&[1u8][..]]);
+        let anchor = BlockFilter::anchor_header(Hash::from_u64_word(1));
+        assert_ne!(anchor, BlockFilter::anchor_header(Hash::from_u64_word(2)));
+        assert_ne!(filter.header(anchor), filter.header(Hash::default()));
+    }
+}
@@ -0,0 +1,261 @@
+use crate::{
+    errors::{BlockFilterIndexError, BlockFilterIndexResult},
+    filter::BlockFilter,
+    stores::{BlockFilterEntry, BlockFilterTip, Store},
+    IDENT,
+};
+use karlsen_consensus_core::{api::ConsensusApi, errors::consensus::ConsensusResult};
+use karlsen_consensusmanager::{ConsensusManager, ConsensusResetHandler};
+use karlsen_core::{info, trace};
+use karlsen_database::prelude::DB;
+use karlsen_hashes::Hash;
+use parking_lot::Mutex;
+use rocksdb::WriteBatch;
+use std::{
+    fmt::Debug,
+    sync::{Arc, Weak},
+};
+
+/// Count of chain blocks indexed per consensus session and DB write
+const SYNC_CHUNK_SIZE: usize = 256;
+
+/// Indexes a BIP158-style compact filter per chain block, along with a filter header
+/// committing to the filters of the selected chain up to the block.
+///
+/// The index follows the selected chain of the consensus from the pruning point at the time
+/// it was first synced. Filter headers chain from the pruning point of each block header and
+/// restart when it moves (see [`BlockFilter::anchor_header`]), so the index only starts
+/// serving filters once the pruning point moved after it was synced, and from then on all
+/// the nodes agree on the filter headers.
+pub struct BlockFilterIndex {
+    consensus_manager: Arc<ConsensusManager>,
+    store: Mutex<Store>,
+    /// Serializes the syncs and resets of the index
+    sync_lock: Mutex<()>,
+}
+
+impl BlockFilterIndex {
+    pub fn new(consensus_manager: Arc<ConsensusManager>, db: Arc<DB>) -> Arc<Self> {
+        let index = Arc::new(Self {
+            consensus_manager: consensus_manager.clone(),
+            store: Mutex::new(Store::new(db)),
+            sync_lock: Mutex::new(()),
+        });
+        consensus_manager.register_consensus_reset_handler(Arc::new(
+            BlockFilterIndexConsensusResetHandler::new(Arc::downgrade(&index)),
+        ));
+        index
+    }
+
+    /// Returns the highest indexed chain block, if any
+    pub fn get_tip(&self) -> BlockFilterIndexResult<Option<BlockFilterTip>> {
+        Ok(self.store.lock().get_tip()?)
+    }
+
+    pub fn get_filter(&self, hash: Hash) -> BlockFilterIndexResult<BlockFilterEntry> {
+        self.store
+            .lock()
+            .get_entry(hash)?
+            .ok_or(BlockFilterIndexError::BlockNotIndexed(hash))
+    }
+
+    /// Returns up to `limit` entries of the indexed chain, starting at `start_hash` and
+    /// ascending the selected chain
+    pub fn get_filters(
+        &self,
+        start_hash: Hash,
+        limit: usize,
+    ) -> BlockFilterIndexResult<Vec<(Hash, BlockFilterEntry)>> {
+        let store = self.store.lock();
+        let Some(start) = store.get_entry(start_hash)? else {
+            return Err(BlockFilterIndexError::BlockNotIndexed(start_hash));
+        };
+        let mut chain_index = start.chain_index;
+        let mut entries = Vec::with_capacity(limit.min(SYNC_CHUNK_SIZE));
+        entries.push((start_hash, start));
+        while entries.len() < limit {
+            chain_index += 1;
+            let Some(hash) = store.get_chain_block(chain_index)? else {
+                break;
+            };
+            let Some(entry) = store.get_entry(hash)? else {
+                break;
+            };
+            entries.push((hash, entry));
+        }
+        entries.truncate(limit);
+        Ok(entries)
+    }
+
+    /// Catches up with the selected chain of the consensus, indexing the blocks it added and
+    /// dropping the ones it removed since the last sync.
+    pub fn sync(&self) -> BlockFilterIndexResult<()> {
+        let _guard = self.sync_lock.lock();
+        self.sync_chain()
+    }
+
+    fn sync_chain(&self) -> BlockFilterIndexResult<()> {
+        let consensus = self.consensus_manager.consensus();
+
+        let mut tip = match self.store.lock().get_tip()? {
+            Some(tip) => tip,
+            None => self.init()?,
+        };
+        let session = futures::executor::block_on(consensus.session_blocking());
+        let chain_path = match session.get_virtual_chain_from_block(tip.hash) {
+            Ok(chain_path) => chain_path,
+            Err(_) => {
+                // The tip fell below the pruning point while the index was not running
+                drop(session);
+                info!("The block filter index is behind the pruning point, resyncing it");
+                self.store.lock().delete_all()?;
+                tip = self.init()?;
+                let session = futures::executor::block_on(consensus.session_blocking());
+                session.get_virtual_chain_from_block(tip.hash)?
+            }
+        };
+        drop(session);
+
+        if !chain_path.removed.is_empty() {
+            if chain_path.removed.len() as u64 >= tip.chain_index {
+                // The anchor is no longer a chain block, which only happens on a consensus reset
+                self.store.lock().delete_all()?;
+                return self.sync_chain();
+            }
+            let mut store = self.store.lock();
+            let mut batch = WriteBatch::default();
+            for (i, hash) in chain_path.removed.iter().copied().enumerate() {
+                store.delete_batch(&mut batch, hash, tip.chain_index - i as u64)?;
+            }
+            let chain_index = tip.chain_index - chain_path.removed.len() as u64;
+            let hash = store
+                .get_chain_block(chain_index)?
+                .expect("the chain of the index is contiguous");
+            let header = match chain_index {
+                0 => Hash::default(),
+                _ => {
+                    store
+                        .get_entry(hash)?
+                        .expect("every chain block above the anchor has a filter")
+                        .header
+                }
+            };
+            tip = BlockFilterTip {
+                chain_index,
+                hash,
+                header,
+            };
+            store.commit(batch, &tip)?;
+            trace!(
+                "[{IDENT}] removed {} chain blocks",
+                chain_path.removed.len()
+            );
+        }
+
+        for chunk in chain_path.added.chunks(SYNC_CHUNK_SIZE) {
+            let session = futures::executor::block_on(consensus.session_blocking());
+            let mut prev_pruning_point = session.get_header(tip.hash)?.pruning_point;
+            let mut batch = WriteBatch::default();
+            let mut store = self.store.lock();
+            for hash in chunk.iter().copied() {
+                let pruning_point = session.get_header(hash)?.pruning_point;
+                let moved = pruning_point != prev_pruning_point;
+                prev_pruning_point = pruning_point;
+                let prev_header = match (moved, tip.chain_index) {
+                    (true, _) => BlockFilter::anchor_header(pruning_point),
+                    (false, 0) => {
+                        // The filter header of this block chains from blocks below the anchor,
+                        // so the anchor moves up until the pruning point does
+                        store.anchor_batch(&mut batch, hash)?;
+                        tip = BlockFilterTip {
+                            chain_index: 0,
+                            hash,
+                            header: Hash::default(),
+                        };
+                        continue;
+                    }
+                    (false, _) => tip.header,
+                };
+                let filter = block_filter(&*session, hash)?;
+                let entry = BlockFilterEntry {
+                    chain_index: tip.chain_index + 1,
+                    header: filter.header(prev_header),
+                    filter,
+                };
+                tip = BlockFilterTip {
+                    chain_index: entry.chain_index,
+                    hash,
+                    header: entry.header,
+                };
+                store.insert_batch(&mut batch, hash, entry)?;
+            }
+            store.commit(batch, &tip)?;
+            trace!("[{IDENT}] indexed {} chain blocks", chunk.len());
+        }
+        Ok(())
+    }
+
+    /// Anchors the empty index at the current pruning point
+    fn init(&self) -> BlockFilterIndexResult<BlockFilterTip> {
+        let consensus = self.consensus_manager.consensus();
+        let session = futures::executor::block_on(consensus.session_blocking());
+        let pruning_point = session.pruning_point();
+        info!("Syncing the block filter index from the pruning point {pruning_point}");
+        Ok(self.store.lock().init(pruning_point)?)
+    }
+
+    fn reset(&self) -> BlockFilterIndexResult<()> {
+        let _guard = self.sync_lock.lock();
+        Ok(self.store.lock().delete_all()?)
+    }
+}
+
+/// Builds the filter of the chain block `hash` out of the transactions it accepted
+fn block_filter(consensus: &dyn ConsensusApi, hash: Hash) -> BlockFilterIndexResult<BlockFilter> {
+    let acceptance_data = consensus.get_block_acceptance_data(hash)?;
+    let utxo_diff = consensus.get_block_utxo_diff(hash)?;
+    let blocks = acceptance_data
+        .iter()
+        .map(|mergeset_block| consensus.get_block(mergeset_block.block_hash))
+        .collect::<ConsensusResult<Vec<_>>>()?;
+    let transactions =
+        acceptance_data
+            .iter()
+            .zip(blocks.iter())
+            .flat_map(|(mergeset_block, block)| {
+                mergeset_block
+                    .accepted_transactions
+                    .iter()
+                    .map(|entry| &block.transactions[entry.index_within_block as usize])
+            });
+    Ok(BlockFilter::from_accepted_transactions(
+        hash,
+        transactions,
+        &utxo_diff,
+    ))
+}
+
+impl Debug for BlockFilterIndex {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BlockFilterIndex").finish()
+    }
+}
+
+struct BlockFilterIndexConsensusResetHandler {
+    index: Weak<BlockFilterIndex>,
+}
+
+impl BlockFilterIndexConsensusResetHandler {
+    fn new(index: Weak<BlockFilterIndex>) -> Self {
+        Self { index }
+    }
+}
+
+impl ConsensusResetHandler for BlockFilterIndexConsensusResetHandler {
+    fn handle_consensus_reset(&self) {
+        // The index is anchored again at the next sync
+        if let Some(index) = self.index.upgrade() {
+            index.reset().unwrap();
+        }
+    }
+}
@@ -0,0 +1,15 @@
+//!
+//! Index of BIP158-style compact block filters, letting light wallets find the chain blocks
+//! relevant to their addresses without disclosing them to the node.
+//!
+
+pub mod errors;
+pub mod filter;
+mod index;
+pub mod service;
+pub mod stores;
+
+pub use crate::filter::{filter_element, BlockFilter};
+pub use crate::index::BlockFilterIndex;
+
+const IDENT: &str = "blockfilterindex";
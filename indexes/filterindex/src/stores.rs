@@ -0,0 +1,214 @@
+use crate::filter::BlockFilter;
+use karlsen_database::{
+    prelude::{
+        BatchDbWriter, CachePolicy, CachedDbAccess, CachedDbItem, DirectDbWriter, StoreError,
+        StoreResult, StoreResultExtensions, DB,
+    },
+    registry::DatabaseStorePrefixes,
+};
+use karlsen_hashes::Hash;
+use karlsen_utils::mem_size::MemSizeEstimator;
+use rocksdb::WriteBatch;
+use serde::{Deserialize, Serialize};
+use std::{fmt::Display, sync::Arc};
+
+/// The filter of a chain block along with its position in the indexed chain and its header
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BlockFilterEntry {
+    pub chain_index: u64,
+    pub filter: BlockFilter,
+    pub header: Hash,
+}
+
+impl MemSizeEstimator for BlockFilterEntry {}
+
+/// The highest indexed chain block. The chain index 0 is the anchor block the index chains
+/// from, which has no filter and a zero header.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BlockFilterTip {
+    pub chain_index: u64,
+    pub hash: Hash,
+    pub header: Hash,
+}
+
+/// Big endian chain index, so the keys iterate in chain order
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+struct ChainIndexKey([u8; 8]);
+
+impl From<u64> for ChainIndexKey {
+    fn from(index: u64) -> Self {
+        Self(index.to_be_bytes())
+    }
+}
+
+impl AsRef<[u8]> for ChainIndexKey {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl Display for ChainIndexKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", u64::from_be_bytes(self.0))
+    }
+}
+
+#[derive(Clone)]
+pub struct Store {
+    db: Arc<DB>,
+    filters: CachedDbAccess<Hash, BlockFilterEntry>,
+    chain: CachedDbAccess<ChainIndexKey, Hash>,
+    tip: CachedDbItem<BlockFilterTip>,
+}
+
+impl Store {
+    pub fn new(db: Arc<DB>) -> Self {
+        Self {
+            db: db.clone(),
+            filters: CachedDbAccess::new(
+                db.clone(),
+                CachePolicy::Empty,
+                DatabaseStorePrefixes::BlockFilters.into(),
+            ),
+            chain: CachedDbAccess::new(
+                db.clone(),
+                CachePolicy::Empty,
+                DatabaseStorePrefixes::BlockFilterChain.into(),
+            ),
+            tip: CachedDbItem::new(db, DatabaseStorePrefixes::BlockFilterTip.into()),
+        }
+    }
+
+    pub fn get_entry(&self, hash: Hash) -> StoreResult<Option<BlockFilterEntry>> {
+        self.filters.read(hash).optional()
+    }
+
+    pub fn get_chain_block(&self, chain_index: u64) -> StoreResult<Option<Hash>> {
+        self.chain.read(chain_index.into()).optional()
+    }
+
+    pub fn get_tip(&self) -> StoreResult<Option<BlockFilterTip>> {
+        self.tip.read().optional()
+    }
+
+    /// Anchors an empty index at `hash`
+    pub fn init(&mut self, hash: Hash) -> StoreResult<BlockFilterTip> {
+        let tip = BlockFilterTip {
+            chain_index: 0,
+            hash,
+            header: Hash::default(),
+        };
+        let mut batch = WriteBatch::default();
+        self.chain
+            .write(BatchDbWriter::new(&mut batch), 0.into(), hash)?;
+        self.tip.write(BatchDbWriter::new(&mut batch), &tip)?;
+        self.db.write(batch)?;
+        Ok(tip)
+    }
+
+    /// Moves the anchor of an index with no entries up to `hash`
+    pub fn anchor_batch(&self, batch: &mut WriteBatch, hash: Hash) -> StoreResult<()> {
+        self.chain.write(BatchDbWriter::new(batch), 0.into(), hash)
+    }
+
+    pub fn insert_batch(
+        &self,
+        batch: &mut WriteBatch,
+        hash: Hash,
+        entry: BlockFilterEntry,
+    ) -> StoreResult<()> {
+        self.chain
+            .write(BatchDbWriter::new(batch), entry.chain_index.into(), hash)?;
+        self.filters.write(BatchDbWriter::new(batch), hash, entry)
+    }
+
+    pub fn delete_batch(
+        &self,
+        batch: &mut WriteBatch,
+        hash: Hash,
+        chain_index: u64,
+    ) -> StoreResult<()> {
+        self.chain
+            .delete(BatchDbWriter::new(batch), chain_index.into())?;
+        self.filters.delete(BatchDbWriter::new(batch), hash)
+    }
+
+    /// Sets the tip in `batch` and commits it
+    pub fn commit(&mut self, mut batch: WriteBatch, tip: &BlockFilterTip) -> StoreResult<()> {
+        self.tip.write(BatchDbWriter::new(&mut batch), tip)?;
+        self.db.write(batch)?;
+        Ok(())
+    }
+
+    /// Removes all entries from the index
+    pub fn delete_all(&mut self) -> Result<(), StoreError> {
+        self.tip.remove(DirectDbWriter::new(&self.db))?;
+        self.filters.delete_all(DirectDbWriter::new(&self.db))?;
+        self.chain.delete_all(DirectDbWriter::new(&self.db))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use karlsen_database::{create_temp_db, prelude::ConnBuilder};
+
+    #[test]
+    fn test_block_filter_store() {
+        let (_lifetime, db) = create_temp_db!(ConnBuilder::default().with_files_limit(10));
+        let mut store = Store::new(db);
+        assert_eq!(store.get_tip().unwrap(), None);
+
+        let anchor = Hash::from_u64_word(1);
+        let tip = store.init(anchor).unwrap();
+        assert_eq!(store.get_chain_block(0).unwrap(), Some(anchor));
+
+        let mut batch = WriteBatch::default();
+        let mut tip = tip;
+        for word in 2..5 {
+            let hash = Hash::from_u64_word(word);
+            let filter = BlockFilter::new(hash, [&word.to_le_bytes()[..]]);
+            let entry = BlockFilterEntry {
+                chain_index: tip.chain_index + 1,
+                header: filter.header(tip.header),
+                filter,
+            };
+            tip = BlockFilterTip {
+                chain_index: entry.chain_index,
+                hash,
+                header: entry.header,
+            };
+            store.insert_batch(&mut batch, hash, entry).unwrap();
+        }
+        store.commit(batch, &tip).unwrap();
+        assert_eq!(store.get_tip().unwrap(), Some(tip));
+        assert_eq!(
+            store.get_chain_block(2).unwrap(),
+            Some(Hash::from_u64_word(3))
+        );
+        let entry = store.get_entry(Hash::from_u64_word(4)).unwrap().unwrap();
+        assert_eq!(entry.chain_index, 3);
+        assert_eq!(entry.header, tip.header);
+
+        let mut batch = WriteBatch::default();
+        store
+            .delete_batch(&mut batch, Hash::from_u64_word(4), 3)
+            .unwrap();
+        let tip = BlockFilterTip {
+            chain_index: 2,
+            hash: Hash::from_u64_word(3),
+            header: store
+                .get_entry(Hash::from_u64_word(3))
+                .unwrap()
+                .unwrap()
+                .header,
+        };
+        store.commit(batch, &tip).unwrap();
+        assert_eq!(store.get_entry(Hash::from_u64_word(4)).unwrap(), None);
+        assert_eq!(store.get_chain_block(3).unwrap(), None);
+
+        store.delete_all().unwrap();
+        assert_eq!(store.get_tip().unwrap(), None);
+        assert_eq!(store.get_chain_block(0).unwrap(), None);
+    }
+}
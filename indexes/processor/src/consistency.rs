@@ -0,0 +1,111 @@
+use karlsen_core::{
+    debug,
+    task::{
+        service::{AsyncService, AsyncServiceFuture},
+        tick::{TickReason, TickService},
+    },
+    trace, warn,
+};
+use karlsen_utxoindex::{api::UtxoIndexProxy, model::UtxoIndexConsistencyCursor};
+use std::{sync::Arc, time::Duration};
+
+const CONSISTENCY_SERVICE: &str = "utxoindex-consistency";
+
+/// Interval between two consistency checks
+const CHECK_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Count of entries sampled per check on each side, keeping a check short enough not to delay
+/// the utxoindex updates waiting for the lock
+const SAMPLE_SIZE: usize = 1_000;
+
+/// Incrementally cross-checks the utxoindex against the consensus virtual UTXO set in the
+/// background, repairing the divergent entries found.
+///
+/// Silent drift of the utxoindex otherwise shows as wrong balances that users only notice when
+/// spending. The whole set is covered in turn, a sample per tick.
+pub struct UtxoIndexConsistencyService {
+    utxoindex: UtxoIndexProxy,
+    tick_service: Arc<TickService>,
+}
+
+impl UtxoIndexConsistencyService {
+    pub fn new(utxoindex: UtxoIndexProxy, tick_service: Arc<TickService>) -> Self {
+        Self {
+            utxoindex,
+            tick_service,
+        }
+    }
+
+    async fn worker(&self) {
+        let mut cursor = UtxoIndexConsistencyCursor::default();
+        loop {
+            if let TickReason::Shutdown = self.tick_service.tick(CHECK_INTERVAL).await {
+                break;
+            }
+
+            let divergences = match self
+                .utxoindex
+                .clone()
+                .check_consistency(cursor.clone(), SAMPLE_SIZE)
+                .await
+            {
+                Ok((next_cursor, Some(divergences))) => {
+                    cursor = next_cursor;
+                    divergences
+                }
+                // Skipped while the utxoindex catches up with the virtual state
+                Ok((_, None)) => continue,
+                Err(err) => {
+                    warn!("Error while checking the utxoindex consistency: {}", err);
+                    continue;
+                }
+            };
+            if cursor.consensus_outpoint.is_none() {
+                debug!(
+                    "[{}] completed a pass over the UTXO set",
+                    CONSISTENCY_SERVICE
+                );
+            }
+            if divergences.is_empty() {
+                continue;
+            }
+
+            let divergent_count = divergences.len();
+            match self.utxoindex.clone().repair(divergences).await {
+                Ok(repaired_count) => warn!(
+                    "The utxoindex diverged from the UTXO set on {} entries, {} repaired",
+                    divergent_count, repaired_count
+                ),
+                Err(err) => warn!(
+                    "The utxoindex diverged from the UTXO set on {} entries but the repair failed: {}",
+                    divergent_count, err
+                ),
+            }
+        }
+    }
+}
+
+impl AsyncService for UtxoIndexConsistencyService {
+    fn ident(self: Arc<Self>) -> &'static str {
+        CONSISTENCY_SERVICE
+    }
+
+    fn start(self: Arc<Self>) -> AsyncServiceFuture {
+        trace!("{} starting", CONSISTENCY_SERVICE);
+        Box::pin(async move {
+            self.worker().await;
+            Ok(())
+        })
+    }
+
+    fn signal_exit(self: Arc<Self>) {
+        trace!("sending an exit signal to {}", CONSISTENCY_SERVICE);
+    }
+
+    fn stop(self: Arc<Self>) -> AsyncServiceFuture {
+        Box::pin(async move {
+            trace!("{} stopped", CONSISTENCY_SERVICE);
+            Ok(())
+        })
+    }
+}
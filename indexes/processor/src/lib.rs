@@ -1,3 +1,4 @@
+pub mod consistency;
 pub mod errors;
 pub mod processor;
 pub mod service;
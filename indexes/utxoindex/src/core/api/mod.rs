@@ -1,5 +1,5 @@
 use karlsen_consensus_core::{
-    tx::{ScriptPublicKeys, TransactionOutpoint},
+    tx::{ScriptPublicKey, ScriptPublicKeys, TransactionOutpoint},
     utxo::utxo_diff::UtxoDiff,
     BlockHashSet,
 };
@@ -12,7 +12,10 @@ use std::{collections::HashSet, fmt::Debug, sync::Arc};
 
 use crate::{
     errors::UtxoIndexResult,
-    model::{UtxoChanges, UtxoSetByScriptPublicKey},
+    model::{
+        CompactUtxoEntry, UtxoChanges, UtxoIndexConsistencyCounters, UtxoIndexConsistencyCursor,
+        UtxoIndexDivergence, UtxoSetByScriptPublicKey,
+    },
 };
 
 ///Utxoindex API targeted at retrieval calls.
@@ -30,6 +33,17 @@ pub trait UtxoIndexApi: Send + Sync + Debug {
         script_public_keys: ScriptPublicKeys,
     ) -> StoreResult<UtxoSetByScriptPublicKey>;
 
+    /// Retrieve up to `limit` utxos of `script_public_keys`, listed key by key in the given order, each utxo
+    /// along with the position of its key. The listing starts after the utxo `from` if specified.
+    ///
+    /// Note: Use a read lock when accessing this method
+    fn get_utxos_by_script_public_keys_from(
+        &self,
+        script_public_keys: &[ScriptPublicKey],
+        from: Option<(usize, TransactionOutpoint)>,
+        limit: usize,
+    ) -> StoreResult<Vec<(usize, TransactionOutpoint, CompactUtxoEntry)>>;
+
     fn get_balance_by_script_public_keys(
         &self,
         script_public_keys: ScriptPublicKeys,
@@ -63,6 +77,27 @@ pub trait UtxoIndexApi: Send + Sync + Debug {
     ///
     /// Note: Use a write lock when accessing this method
     fn resync(&mut self) -> UtxoIndexResult<()>;
+
+    /// Cross-checks up to `sample_size` consensus virtual UTXO entries against the utxoindex, and
+    /// as many indexed entries against consensus, from the position of `cursor` which is then
+    /// advanced. Returns `None` if the check was skipped since the utxoindex is not synced with
+    /// the virtual state at the time.
+    ///
+    /// Note: Use a read lock when accessing this method
+    fn check_consistency(
+        &self,
+        cursor: &mut UtxoIndexConsistencyCursor,
+        sample_size: usize,
+    ) -> UtxoIndexResult<Option<Vec<UtxoIndexDivergence>>>;
+
+    /// Repairs the given divergences from the current consensus virtual UTXO set, returning the
+    /// count of divergences actually repaired.
+    ///
+    /// Note: Use a write lock when accessing this method
+    fn repair(&mut self, divergences: Vec<UtxoIndexDivergence>) -> UtxoIndexResult<usize>;
+
+    /// Retrieve the counters of the consistency checks.
+    fn consistency_counters(&self) -> Arc<UtxoIndexConsistencyCounters>;
 }
 
 /// Async proxy for the UTXO index
@@ -95,6 +130,21 @@ impl UtxoIndexProxy {
         .unwrap()
     }
 
+    pub async fn get_utxos_by_script_public_keys_from(
+        self,
+        script_public_keys: Arc<Vec<ScriptPublicKey>>,
+        from: Option<(usize, TransactionOutpoint)>,
+        limit: usize,
+    ) -> StoreResult<Vec<(usize, TransactionOutpoint, CompactUtxoEntry)>> {
+        spawn_blocking(move || {
+            self.inner
+                .read()
+                .get_utxos_by_script_public_keys_from(&script_public_keys, from, limit)
+        })
+        .await
+        .unwrap()
+    }
+
     pub async fn get_balance_by_script_public_keys(
         self,
         script_public_keys: ScriptPublicKeys,
@@ -108,6 +158,49 @@ impl UtxoIndexProxy {
         .unwrap()
     }
 
+    /// Retrieves the balances along with the tips of the utxoindex under a single read lock,
+    /// so the balances are known to be those of the virtual UTXO set with these parents.
+    pub async fn get_balance_by_script_public_keys_with_tips(
+        self,
+        script_public_keys: ScriptPublicKeys,
+    ) -> StoreResult<(BalanceByScriptPublicKey, Arc<BlockHashSet>)> {
+        spawn_blocking(move || {
+            let inner = self.inner.read();
+            Ok((
+                inner.get_balance_by_script_public_keys(script_public_keys)?,
+                inner.get_utxo_index_tips()?,
+            ))
+        })
+        .await
+        .unwrap()
+    }
+
+    pub fn consistency_counters(&self) -> Arc<UtxoIndexConsistencyCounters> {
+        self.inner.read().consistency_counters()
+    }
+
+    pub async fn check_consistency(
+        self,
+        mut cursor: UtxoIndexConsistencyCursor,
+        sample_size: usize,
+    ) -> UtxoIndexResult<(UtxoIndexConsistencyCursor, Option<Vec<UtxoIndexDivergence>>)> {
+        spawn_blocking(move || {
+            let divergences = self
+                .inner
+                .read()
+                .check_consistency(&mut cursor, sample_size)?;
+            Ok((cursor, divergences))
+        })
+        .await
+        .unwrap()
+    }
+
+    pub async fn repair(self, divergences: Vec<UtxoIndexDivergence>) -> UtxoIndexResult<usize> {
+        spawn_blocking(move || self.inner.write().repair(divergences))
+            .await
+            .unwrap()
+    }
+
     pub async fn update(
         self,
         utxo_diff: Arc<UtxoDiff>,
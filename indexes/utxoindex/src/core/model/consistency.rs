@@ -0,0 +1,72 @@
+use karlsen_consensus_core::tx::{ScriptPublicKey, TransactionOutpoint};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Kind of a divergence between the utxoindex and the consensus virtual UTXO set
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UtxoIndexDivergenceKind {
+    /// The entry is in the consensus UTXO set but not indexed
+    Missing,
+    /// The entry is indexed but is not in the consensus UTXO set, or not under this script
+    Stale,
+    /// The entry is indexed with an amount, DAA score or coinbase flag differing from consensus
+    Mismatch,
+}
+
+/// An entry of the utxoindex found diverging from the consensus virtual UTXO set
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UtxoIndexDivergence {
+    pub kind: UtxoIndexDivergenceKind,
+    pub script_public_key: ScriptPublicKey,
+    pub outpoint: TransactionOutpoint,
+}
+
+impl UtxoIndexDivergence {
+    pub fn new(
+        kind: UtxoIndexDivergenceKind,
+        script_public_key: ScriptPublicKey,
+        outpoint: TransactionOutpoint,
+    ) -> Self {
+        Self {
+            kind,
+            script_public_key,
+            outpoint,
+        }
+    }
+}
+
+/// Position of an incremental consistency check, which walks the consensus UTXO set by outpoint
+/// and the utxoindex by script public key, wrapping around at the end of each
+#[derive(Debug, Clone, Default)]
+pub struct UtxoIndexConsistencyCursor {
+    /// Last consensus entry checked against the utxoindex
+    pub consensus_outpoint: Option<TransactionOutpoint>,
+    /// Last utxoindex entry checked against consensus
+    pub indexed_entry: Option<(ScriptPublicKey, TransactionOutpoint)>,
+}
+
+#[derive(Default)]
+pub struct UtxoIndexConsistencyCounters {
+    pub checked_counts: AtomicU64,
+    pub divergent_counts: AtomicU64,
+    pub repaired_counts: AtomicU64,
+    pub skipped_cycle_counts: AtomicU64,
+}
+
+impl UtxoIndexConsistencyCounters {
+    pub fn snapshot(&self) -> UtxoIndexConsistencyCountersSnapshot {
+        UtxoIndexConsistencyCountersSnapshot {
+            checked_counts: self.checked_counts.load(Ordering::Relaxed),
+            divergent_counts: self.divergent_counts.load(Ordering::Relaxed),
+            repaired_counts: self.repaired_counts.load(Ordering::Relaxed),
+            skipped_cycle_counts: self.skipped_cycle_counts.load(Ordering::Relaxed),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct UtxoIndexConsistencyCountersSnapshot {
+    pub checked_counts: u64,
+    pub divergent_counts: u64,
+    pub repaired_counts: u64,
+    pub skipped_cycle_counts: u64,
+}
@@ -1,3 +1,4 @@
+mod consistency;
 mod supply;
 
-pub use {karlsen_index_core::indexed_utxos::*, supply::*};
+pub use {consistency::*, karlsen_index_core::indexed_utxos::*, supply::*};
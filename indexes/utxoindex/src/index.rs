@@ -1,22 +1,30 @@
 use crate::{
     api::UtxoIndexApi,
     errors::{UtxoIndexError, UtxoIndexResult},
-    model::{CirculatingSupply, UtxoChanges, UtxoSetByScriptPublicKey},
+    model::{
+        CirculatingSupply, CompactUtxoEntry, UtxoChanges, UtxoIndexConsistencyCounters,
+        UtxoIndexConsistencyCursor, UtxoIndexDivergence, UtxoIndexDivergenceKind,
+        UtxoSetByScriptPublicKey,
+    },
     stores::store_manager::Store,
     update_container::UtxoIndexChanges,
     IDENT,
 };
-use karlsen_consensus_core::{tx::ScriptPublicKeys, utxo::utxo_diff::UtxoDiff, BlockHashSet};
+use karlsen_consensus_core::{
+    tx::{ScriptPublicKey, ScriptPublicKeys, TransactionOutpoint},
+    utxo::utxo_diff::UtxoDiff,
+    BlockHashSet,
+};
 use karlsen_consensusmanager::{ConsensusManager, ConsensusResetHandler};
 use karlsen_core::{info, trace};
-use karlsen_database::prelude::{StoreError, StoreResult, DB};
+use karlsen_database::prelude::{StoreError, StoreResult, StoreResultExtensions, DB};
 use karlsen_hashes::Hash;
 use karlsen_index_core::indexed_utxos::BalanceByScriptPublicKey;
 use karlsen_utils::arc::ArcExtensions;
 use parking_lot::RwLock;
 use std::{
     fmt::Debug,
-    sync::{Arc, Weak},
+    sync::{atomic::Ordering, Arc, Weak},
 };
 
 const RESYNC_CHUNK_SIZE: usize = 2048; //Increased from 1k (used in go-karlsend), for quicker resets, while still having a low memory footprint.
@@ -27,6 +35,7 @@ const RESYNC_CHUNK_SIZE: usize = 2048; //Increased from 1k (used in go-karlsend)
 pub struct UtxoIndex {
     consensus_manager: Arc<ConsensusManager>,
     store: Store,
+    consistency_counters: Arc<UtxoIndexConsistencyCounters>,
 }
 
 impl UtxoIndex {
@@ -38,6 +47,7 @@ impl UtxoIndex {
         let mut utxoindex = Self {
             consensus_manager: consensus_manager.clone(),
             store: Store::new(db),
+            consistency_counters: Default::default(),
         };
         if !utxoindex.is_synced()? {
             utxoindex.resync()?;
@@ -73,6 +83,38 @@ impl UtxoIndexApi for UtxoIndex {
             .get_utxos_by_script_public_key(script_public_keys)
     }
 
+    fn get_utxos_by_script_public_keys_from(
+        &self,
+        script_public_keys: &[ScriptPublicKey],
+        from: Option<(usize, TransactionOutpoint)>,
+        limit: usize,
+    ) -> StoreResult<Vec<(usize, TransactionOutpoint, CompactUtxoEntry)>> {
+        trace!(
+            "[{0}] retrieving up to {1} utxos from {2} script public keys",
+            IDENT,
+            limit,
+            script_public_keys.len()
+        );
+
+        let (start, mut after) = match from {
+            Some((position, outpoint)) => (position, Some(outpoint)),
+            None => (0, None),
+        };
+        let mut entries = Vec::new();
+        for (position, script_public_key) in script_public_keys.iter().enumerate().skip(start) {
+            if entries.len() >= limit {
+                break;
+            }
+            entries.extend(
+                self.store
+                    .seek_utxo_entries_of(script_public_key, after.take(), limit - entries.len())?
+                    .into_iter()
+                    .map(|(outpoint, entry)| (position, outpoint, entry)),
+            );
+        }
+        Ok(entries)
+    }
+
     /// Retrieve utxos by script public keys from the utxoindex db.
     fn get_balance_by_script_public_keys(
         &self,
@@ -241,6 +283,196 @@ impl UtxoIndexApi for UtxoIndex {
         Ok(())
     }
 
+    /// Cross-checks a sample of entries between the consensus virtual UTXO set and the utxoindex:
+    /// 1) consensus entries are looked up in the utxoindex, revealing missing and mismatching entries.
+    /// 2) indexed entries are looked up in consensus, revealing stale entries.
+    ///
+    /// **Note:** Both sides legitimately differ while the utxoindex lags behind the virtual state, so the check is
+    /// skipped unless the utxoindex tips equal the virtual parents both before and after sampling.
+    fn check_consistency(
+        &self,
+        cursor: &mut UtxoIndexConsistencyCursor,
+        sample_size: usize,
+    ) -> UtxoIndexResult<Option<Vec<UtxoIndexDivergence>>> {
+        trace!(
+            "[{0}] checking consistency of {1} entries...",
+            IDENT,
+            sample_size
+        );
+
+        let consensus = self.consensus_manager.consensus();
+        let session = futures::executor::block_on(consensus.session_blocking());
+
+        let virtual_parents = session.get_virtual_parents();
+        if self
+            .store
+            .get_tips()
+            .optional()?
+            .is_none_or(|tips| *tips != virtual_parents)
+        {
+            trace!("[{0}] consistency check skipped while syncing", IDENT);
+            self.consistency_counters
+                .skipped_cycle_counts
+                .fetch_add(1, Ordering::Relaxed);
+            return Ok(None);
+        }
+
+        let mut divergences = Vec::new();
+
+        let consensus_entries = session.get_virtual_utxos(
+            cursor.consensus_outpoint,
+            sample_size,
+            cursor.consensus_outpoint.is_some(),
+        );
+        for (outpoint, entry) in consensus_entries.iter() {
+            match self
+                .store
+                .get_utxo_entry(&entry.script_public_key, outpoint)?
+            {
+                None => divergences.push(UtxoIndexDivergence::new(
+                    UtxoIndexDivergenceKind::Missing,
+                    entry.script_public_key.clone(),
+                    *outpoint,
+                )),
+                Some(indexed) if indexed != CompactUtxoEntry::from(entry.clone()) => divergences
+                    .push(UtxoIndexDivergence::new(
+                        UtxoIndexDivergenceKind::Mismatch,
+                        entry.script_public_key.clone(),
+                        *outpoint,
+                    )),
+                Some(_) => {}
+            }
+        }
+
+        let indexed_entries = self
+            .store
+            .seek_utxo_entries(cursor.indexed_entry.clone(), sample_size)?;
+        let outpoints: Vec<TransactionOutpoint> = indexed_entries
+            .iter()
+            .map(|(_, outpoint, _)| *outpoint)
+            .collect();
+        for ((script_public_key, outpoint, _), entry) in indexed_entries
+            .iter()
+            .zip(session.get_virtual_utxo_entries(&outpoints))
+        {
+            if !entry.is_some_and(|entry| entry.script_public_key == *script_public_key) {
+                divergences.push(UtxoIndexDivergence::new(
+                    UtxoIndexDivergenceKind::Stale,
+                    script_public_key.clone(),
+                    *outpoint,
+                ));
+            }
+        }
+
+        if session.get_virtual_parents() != virtual_parents {
+            trace!("[{0}] consistency check skipped while syncing", IDENT);
+            self.consistency_counters
+                .skipped_cycle_counts
+                .fetch_add(1, Ordering::Relaxed);
+            return Ok(None);
+        }
+
+        // Wrap around once the end of either side is reached
+        cursor.consensus_outpoint = consensus_entries
+            .last()
+            .filter(|_| consensus_entries.len() == sample_size)
+            .map(|(outpoint, _)| *outpoint);
+        cursor.indexed_entry = indexed_entries
+            .last()
+            .filter(|_| indexed_entries.len() == sample_size)
+            .map(|(script_public_key, outpoint, _)| (script_public_key.clone(), *outpoint));
+
+        self.consistency_counters.checked_counts.fetch_add(
+            (consensus_entries.len() + indexed_entries.len()) as u64,
+            Ordering::Relaxed,
+        );
+        self.consistency_counters
+            .divergent_counts
+            .fetch_add(divergences.len() as u64, Ordering::Relaxed);
+        Ok(Some(divergences))
+    }
+
+    /// Repairs the given divergences by re-reading both the consensus and the indexed entry of each, removing the
+    /// indexed entry unless it matches consensus and indexing the consensus entry if missing.
+    ///
+    /// **Notes:**
+    /// 1) Nothing is repaired unless the utxoindex is synced, since the divergences may then be pending updates.
+    /// 2) The circulating supply is left as is, since it accounts for the emission rather than the indexed entries.
+    fn repair(&mut self, divergences: Vec<UtxoIndexDivergence>) -> UtxoIndexResult<usize> {
+        trace!(
+            "[{0}] repairing {1} divergences...",
+            IDENT,
+            divergences.len()
+        );
+
+        let consensus = self.consensus_manager.consensus();
+        let session = futures::executor::block_on(consensus.session_blocking());
+
+        if self
+            .store
+            .get_tips()
+            .optional()?
+            .is_none_or(|tips| *tips != session.get_virtual_parents())
+        {
+            return Ok(0);
+        }
+
+        let outpoints: Vec<TransactionOutpoint> = divergences
+            .iter()
+            .map(|divergence| divergence.outpoint)
+            .collect();
+        let mut to_remove = UtxoSetByScriptPublicKey::new();
+        let mut to_add = UtxoSetByScriptPublicKey::new();
+        let mut repaired = 0;
+        for (divergence, entry) in divergences
+            .into_iter()
+            .zip(session.get_virtual_utxo_entries(&outpoints))
+        {
+            let mut is_repaired = false;
+            let expected = entry
+                .as_ref()
+                .filter(|entry| entry.script_public_key == divergence.script_public_key)
+                .map(|entry| CompactUtxoEntry::from(entry.clone()));
+            let indexed = self
+                .store
+                .get_utxo_entry(&divergence.script_public_key, &divergence.outpoint)?;
+            if let Some(indexed) = indexed.filter(|indexed| Some(*indexed) != expected) {
+                to_remove
+                    .entry(divergence.script_public_key.clone())
+                    .or_default()
+                    .insert(divergence.outpoint, indexed);
+                is_repaired = true;
+            }
+            if let Some(entry) = entry {
+                let compact_entry = CompactUtxoEntry::from(entry.clone());
+                if self
+                    .store
+                    .get_utxo_entry(&entry.script_public_key, &divergence.outpoint)?
+                    != Some(compact_entry)
+                {
+                    to_add
+                        .entry(entry.script_public_key)
+                        .or_default()
+                        .insert(divergence.outpoint, compact_entry);
+                    is_repaired = true;
+                }
+            }
+            if is_repaired {
+                repaired += 1;
+            }
+        }
+
+        self.store.update_utxo_state(&to_add, &to_remove, false)?;
+        self.consistency_counters
+            .repaired_counts
+            .fetch_add(repaired as u64, Ordering::Relaxed);
+        Ok(repaired)
+    }
+
+    fn consistency_counters(&self) -> Arc<UtxoIndexConsistencyCounters> {
+        self.consistency_counters.clone()
+    }
+
     // This can have a big memory footprint, so it should be used only for tests.
     fn get_all_outpoints(
         &self,
@@ -277,8 +509,13 @@ impl ConsensusResetHandler for UtxoIndexConsensusResetHandler {
 #[cfg(test)]
 mod tests {
     use crate::{
-        api::UtxoIndexApi, model::CirculatingSupply,
-        testutils::virtual_change_emulator::VirtualChangeEmulator, UtxoIndex,
+        api::UtxoIndexApi,
+        model::{
+            CirculatingSupply, CompactUtxoCollection, CompactUtxoEntry, UtxoIndexConsistencyCursor,
+            UtxoIndexDivergenceKind, UtxoSetByScriptPublicKey,
+        },
+        testutils::virtual_change_emulator::VirtualChangeEmulator,
+        UtxoIndex,
     };
     use karlsen_consensus::{
         config::Config,
@@ -291,12 +528,14 @@ mod tests {
     };
     use karlsen_consensus_core::{
         api::ConsensusApi,
+        tx::TransactionOutpoint,
         utxo::{utxo_collection::UtxoCollection, utxo_diff::UtxoDiff},
     };
     use karlsen_consensusmanager::ConsensusManager;
     use karlsen_core::info;
     use karlsen_database::create_temp_db;
     use karlsen_database::prelude::ConnBuilder;
+    use karlsen_hashes::Hash;
     use std::{collections::HashSet, sync::Arc, time::Instant};
 
     /// TODO: use proper Simnet when implemented.
@@ -526,4 +765,243 @@ mod tests {
         drop(utxoindex);
         drop(tc);
     }
+
+    #[test]
+    fn test_utxoindex_consistency() {
+        karlsen_core::log::try_init_logger("INFO");
+
+        let mut virtual_change_emulator = VirtualChangeEmulator::new();
+        let (_utxoindex_db_lifetime, utxoindex_db) =
+            create_temp_db!(ConnBuilder::default().with_files_limit(10));
+        let config = Config::new(DEVNET_PARAMS);
+        let tc = Arc::new(TestConsensus::new(&config));
+        let consensus_manager = Arc::new(ConsensusManager::from_consensus(tc.consensus_clone()));
+        let utxoindex = UtxoIndex::new(consensus_manager, utxoindex_db).unwrap();
+
+        virtual_change_emulator.fill_utxo_collection(1_000, 20);
+        let test_consensus_virtual_state = Arc::new(VirtualState {
+            parents: Vec::from_iter(virtual_change_emulator.tips.clone()),
+            utxo_diff: UtxoDiff::new(
+                virtual_change_emulator.utxo_collection.clone(),
+                UtxoCollection::new(),
+            ),
+            ..Default::default()
+        });
+        tc.virtual_stores
+            .write()
+            .utxo_set
+            .write_diff(&test_consensus_virtual_state.utxo_diff)
+            .expect("expected write diff");
+        tc.virtual_stores
+            .write()
+            .state
+            .set(test_consensus_virtual_state)
+            .expect("setting of state");
+        utxoindex.write().resync().expect("expected resync");
+
+        // Walk the whole set in small samples: a synced utxoindex has no divergence
+        let mut cursor = UtxoIndexConsistencyCursor::default();
+        let mut samples = 0;
+        loop {
+            let divergences = utxoindex
+                .read()
+                .check_consistency(&mut cursor, 100)
+                .expect("expected check")
+                .expect("expected a synced utxoindex");
+            assert!(divergences.is_empty());
+            samples += 1;
+            if cursor.consensus_outpoint.is_none() && cursor.indexed_entry.is_none() {
+                break;
+            }
+        }
+        assert_eq!(samples, 11);
+        assert_eq!(
+            utxoindex
+                .read()
+                .consistency_counters()
+                .snapshot()
+                .checked_counts,
+            2_000
+        );
+
+        // Drift the utxoindex: drop an entry, alter another one and index a spent one
+        let mut entries = tc.get_virtual_utxos(None, 2, false).into_iter();
+        let (missing_outpoint, missing_entry) = entries.next().unwrap();
+        let (mismatch_outpoint, mismatch_entry) = entries.next().unwrap();
+        let stale_outpoint = TransactionOutpoint::new(Hash::from_u64_word(1), 0);
+        let stale_script_public_key = missing_entry.script_public_key.clone();
+        let collection = |outpoint: TransactionOutpoint, entry: CompactUtxoEntry| {
+            CompactUtxoCollection::from_iter([(outpoint, entry)])
+        };
+        let to_remove = UtxoSetByScriptPublicKey::from_iter([(
+            missing_entry.script_public_key.clone(),
+            collection(missing_outpoint, missing_entry.clone().into()),
+        )]);
+        let to_add = UtxoSetByScriptPublicKey::from_iter([
+            (
+                mismatch_entry.script_public_key.clone(),
+                collection(
+                    mismatch_outpoint,
+                    CompactUtxoEntry::new(mismatch_entry.amount + 1, 0, false),
+                ),
+            ),
+            (
+                stale_script_public_key.clone(),
+                collection(stale_outpoint, CompactUtxoEntry::new(1, 0, false)),
+            ),
+        ]);
+        utxoindex
+            .write()
+            .store
+            .update_utxo_state(&to_add, &to_remove, false)
+            .unwrap();
+
+        let divergences = utxoindex
+            .read()
+            .check_consistency(&mut UtxoIndexConsistencyCursor::default(), usize::MAX)
+            .unwrap()
+            .unwrap();
+        let kind_of = |outpoint| {
+            divergences
+                .iter()
+                .find(|divergence| divergence.outpoint == outpoint)
+                .map(|divergence| divergence.kind)
+        };
+        assert_eq!(divergences.len(), 3);
+        assert_eq!(
+            kind_of(missing_outpoint),
+            Some(UtxoIndexDivergenceKind::Missing)
+        );
+        assert_eq!(
+            kind_of(mismatch_outpoint),
+            Some(UtxoIndexDivergenceKind::Mismatch)
+        );
+        assert_eq!(
+            kind_of(stale_outpoint),
+            Some(UtxoIndexDivergenceKind::Stale)
+        );
+
+        // Repairing twice is a no-op the second time
+        assert_eq!(utxoindex.write().repair(divergences.clone()).unwrap(), 3);
+        assert_eq!(utxoindex.write().repair(divergences).unwrap(), 0);
+        assert!(utxoindex
+            .read()
+            .check_consistency(&mut UtxoIndexConsistencyCursor::default(), usize::MAX)
+            .unwrap()
+            .unwrap()
+            .is_empty());
+        let counters = utxoindex.read().consistency_counters().snapshot();
+        assert_eq!(counters.divergent_counts, 3);
+        assert_eq!(counters.repaired_counts, 3);
+
+        // A lagging utxoindex is not checked
+        tc.virtual_stores
+            .write()
+            .state
+            .set(Arc::new(VirtualState {
+                parents: vec![Hash::from_u64_word(2)],
+                ..Default::default()
+            }))
+            .unwrap();
+        assert!(utxoindex
+            .read()
+            .check_consistency(&mut UtxoIndexConsistencyCursor::default(), usize::MAX)
+            .unwrap()
+            .is_none());
+
+        drop(utxoindex);
+        drop(tc);
+    }
+
+    #[test]
+    fn test_utxoindex_paging() {
+        karlsen_core::log::try_init_logger("INFO");
+
+        let mut virtual_change_emulator = VirtualChangeEmulator::new();
+        let (_utxoindex_db_lifetime, utxoindex_db) =
+            create_temp_db!(ConnBuilder::default().with_files_limit(10));
+        let config = Config::new(DEVNET_PARAMS);
+        let tc = Arc::new(TestConsensus::new(&config));
+        let consensus_manager = Arc::new(ConsensusManager::from_consensus(tc.consensus_clone()));
+        let utxoindex = UtxoIndex::new(consensus_manager, utxoindex_db).unwrap();
+
+        virtual_change_emulator.fill_utxo_collection(500, 20);
+        let test_consensus_virtual_state = Arc::new(VirtualState {
+            parents: Vec::from_iter(virtual_change_emulator.tips.clone()),
+            utxo_diff: UtxoDiff::new(
+                virtual_change_emulator.utxo_collection.clone(),
+                UtxoCollection::new(),
+            ),
+            ..Default::default()
+        });
+        tc.virtual_stores
+            .write()
+            .utxo_set
+            .write_diff(&test_consensus_virtual_state.utxo_diff)
+            .expect("expected write diff");
+        tc.virtual_stores
+            .write()
+            .state
+            .set(test_consensus_virtual_state)
+            .expect("setting of state");
+        utxoindex.write().resync().expect("expected resync");
+
+        let script_public_keys = virtual_change_emulator
+            .utxo_collection
+            .values()
+            .map(|entry| entry.script_public_key.clone())
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .collect::<Vec<_>>();
+
+        // Pages follow each other without gaps nor overlaps, key by key
+        let mut listed = Vec::new();
+        let mut from = None;
+        loop {
+            let page = utxoindex
+                .read()
+                .get_utxos_by_script_public_keys_from(&script_public_keys, from, 7)
+                .unwrap();
+            assert!(page.len() <= 7);
+            assert!(page.windows(2).all(|pair| pair[0].0 <= pair[1].0));
+            let Some(&(position, outpoint, _)) = page.last() else {
+                break;
+            };
+            from = Some((position, outpoint));
+            listed.extend(page.into_iter().map(|(_, outpoint, _)| outpoint));
+        }
+        assert_eq!(listed.len(), 500);
+        assert_eq!(
+            HashSet::<TransactionOutpoint>::from_iter(listed.iter().copied()),
+            HashSet::from_iter(virtual_change_emulator.utxo_collection.keys().copied())
+        );
+
+        // A page resumes after its cursor even once the entry of the cursor is spent
+        let (_, cursor_outpoint, cursor_entry) = utxoindex
+            .read()
+            .get_utxos_by_script_public_keys_from(&script_public_keys, None, 3)
+            .unwrap()
+            .remove(1);
+        let spent = UtxoSetByScriptPublicKey::from_iter([(
+            script_public_keys[0].clone(),
+            CompactUtxoCollection::from_iter([(cursor_outpoint, cursor_entry)]),
+        )]);
+        utxoindex
+            .write()
+            .store
+            .update_utxo_state(&UtxoSetByScriptPublicKey::new(), &spent, false)
+            .unwrap();
+        let resumed = utxoindex
+            .read()
+            .get_utxos_by_script_public_keys_from(
+                &script_public_keys,
+                Some((0, cursor_outpoint)),
+                1,
+            )
+            .unwrap();
+        assert_eq!(resumed[0].1, listed[2]);
+
+        drop(utxoindex);
+        drop(tc);
+    }
 }
@@ -5,7 +5,9 @@ use karlsen_consensus_core::tx::{
     TransactionOutpoint,
 };
 use karlsen_core::debug;
-use karlsen_database::prelude::{CachePolicy, CachedDbAccess, DirectDbWriter, StoreResult, DB};
+use karlsen_database::prelude::{
+    CachePolicy, CachedDbAccess, DirectDbWriter, StoreResult, StoreResultExtensions, DB,
+};
 use karlsen_database::registry::DatabaseStorePrefixes;
 use karlsen_hashes::Hash;
 use karlsen_index_core::indexed_utxos::BalanceByScriptPublicKey;
@@ -132,6 +134,12 @@ impl UtxoEntryFullAccessKey {
                 .unwrap(),
         ))
     }
+
+    pub fn extract_script_public_key(&self) -> ScriptPublicKey {
+        ScriptPublicKey::from(ScriptPublicKeyBucket(
+            self.0[..(self.0.len() - TRANSACTION_OUTPOINT_KEY_SIZE)].to_vec(),
+        ))
+    }
 }
 
 impl AsRef<[u8]> for UtxoEntryFullAccessKey {
@@ -153,6 +161,29 @@ pub trait UtxoSetByScriptPublicKeyStoreReader {
         script_public_keys: ScriptPublicKeys,
     ) -> StoreResult<BalanceByScriptPublicKey>;
     fn get_all_outpoints(&self) -> StoreResult<HashSet<TransactionOutpoint>>; // This can have a big memory footprint, so it should be used only for tests.
+
+    /// Get the [CompactUtxoEntry] of `outpoint` indexed under `script_public_key`, if any.
+    fn get_utxo_entry(
+        &self,
+        script_public_key: &ScriptPublicKey,
+        outpoint: &TransactionOutpoint,
+    ) -> StoreResult<Option<CompactUtxoEntry>>;
+
+    /// Get up to `limit` entries in key order, starting after the entry `from` if specified.
+    fn seek_utxo_entries(
+        &self,
+        from: Option<(ScriptPublicKey, TransactionOutpoint)>,
+        limit: usize,
+    ) -> StoreResult<Vec<(ScriptPublicKey, TransactionOutpoint, CompactUtxoEntry)>>;
+
+    /// Get up to `limit` entries of `script_public_key` in key order, starting after the entry of the outpoint
+    /// `after` if specified, whether or not it still exists.
+    fn seek_utxo_entries_of(
+        &self,
+        script_public_key: &ScriptPublicKey,
+        after: Option<TransactionOutpoint>,
+        limit: usize,
+    ) -> StoreResult<Vec<(TransactionOutpoint, CompactUtxoEntry)>>;
 }
 
 pub trait UtxoSetByScriptPublicKeyStore: UtxoSetByScriptPublicKeyStoreReader {
@@ -258,6 +289,78 @@ impl UtxoSetByScriptPublicKeyStoreReader for DbUtxoSetByScriptPublicKeyStore {
             UtxoEntryFullAccessKey(Arc::new(res.unwrap().0.to_vec())).extract_outpoint()
         })))
     }
+
+    fn get_utxo_entry(
+        &self,
+        script_public_key: &ScriptPublicKey,
+        outpoint: &TransactionOutpoint,
+    ) -> StoreResult<Option<CompactUtxoEntry>> {
+        self.access
+            .read(UtxoEntryFullAccessKey::new(
+                ScriptPublicKeyBucket::from(script_public_key),
+                TransactionOutpointKey::from(outpoint),
+            ))
+            .optional()
+    }
+
+    fn seek_utxo_entries(
+        &self,
+        from: Option<(ScriptPublicKey, TransactionOutpoint)>,
+        limit: usize,
+    ) -> StoreResult<Vec<(ScriptPublicKey, TransactionOutpoint, CompactUtxoEntry)>> {
+        let skip_first = from.is_some();
+        let seek_from = from.map(|(script_public_key, outpoint)| {
+            UtxoEntryFullAccessKey::new(
+                ScriptPublicKeyBucket::from(&script_public_key),
+                TransactionOutpointKey::from(&outpoint),
+            )
+        });
+        Ok(self
+            .access
+            .seek_iterator(None, seek_from, limit, skip_first)
+            .map(|res| {
+                let (key, entry) = res.unwrap();
+                let key = UtxoEntryFullAccessKey(Arc::new(key.to_vec()));
+                (
+                    key.extract_script_public_key(),
+                    key.extract_outpoint(),
+                    entry,
+                )
+            })
+            .collect())
+    }
+
+    fn seek_utxo_entries_of(
+        &self,
+        script_public_key: &ScriptPublicKey,
+        after: Option<TransactionOutpoint>,
+        limit: usize,
+    ) -> StoreResult<Vec<(TransactionOutpoint, CompactUtxoEntry)>> {
+        let script_public_key_bucket = ScriptPublicKeyBucket::from(script_public_key);
+        let seek_from = after.map(|outpoint| {
+            UtxoEntryFullAccessKey::new(
+                script_public_key_bucket.clone(),
+                TransactionOutpointKey::from(&outpoint),
+            )
+        });
+        // The entry `after` is only skipped if it is still there
+        Ok(self
+            .access
+            .seek_iterator(
+                Some(script_public_key_bucket.as_ref()),
+                seek_from,
+                limit.saturating_add(1),
+                false,
+            )
+            .map(|res| {
+                let (key, entry) = res.unwrap();
+                let key = TransactionOutpointKey(key.as_ref().try_into().unwrap());
+                (TransactionOutpoint::from(key), entry)
+            })
+            .filter(|(outpoint, _)| Some(outpoint) != after.as_ref())
+            .take(limit)
+            .collect())
+    }
 }
 
 impl UtxoSetByScriptPublicKeyStore for DbUtxoSetByScriptPublicKeyStore {
@@ -1,7 +1,7 @@
 use std::{collections::HashSet, sync::Arc};
 
 use karlsen_consensus_core::{
-    tx::{ScriptPublicKeys, TransactionOutpoint},
+    tx::{ScriptPublicKey, ScriptPublicKeys, TransactionOutpoint},
     BlockHashSet,
 };
 use karlsen_core::trace;
@@ -9,7 +9,7 @@ use karlsen_database::prelude::{CachePolicy, StoreResult, DB};
 use karlsen_index_core::indexed_utxos::BalanceByScriptPublicKey;
 
 use crate::{
-    model::UtxoSetByScriptPublicKey,
+    model::{CompactUtxoEntry, UtxoSetByScriptPublicKey},
     stores::{
         indexed_utxos::{
             DbUtxoSetByScriptPublicKeyStore, UtxoSetByScriptPublicKeyStore,
@@ -61,6 +61,34 @@ impl Store {
         self.utxos_by_script_public_key_store.get_all_outpoints()
     }
 
+    pub fn get_utxo_entry(
+        &self,
+        script_public_key: &ScriptPublicKey,
+        outpoint: &TransactionOutpoint,
+    ) -> StoreResult<Option<CompactUtxoEntry>> {
+        self.utxos_by_script_public_key_store
+            .get_utxo_entry(script_public_key, outpoint)
+    }
+
+    pub fn seek_utxo_entries(
+        &self,
+        from: Option<(ScriptPublicKey, TransactionOutpoint)>,
+        limit: usize,
+    ) -> StoreResult<Vec<(ScriptPublicKey, TransactionOutpoint, CompactUtxoEntry)>> {
+        self.utxos_by_script_public_key_store
+            .seek_utxo_entries(from, limit)
+    }
+
+    pub fn seek_utxo_entries_of(
+        &self,
+        script_public_key: &ScriptPublicKey,
+        after: Option<TransactionOutpoint>,
+        limit: usize,
+    ) -> StoreResult<Vec<(TransactionOutpoint, CompactUtxoEntry)>> {
+        self.utxos_by_script_public_key_store
+            .seek_utxo_entries_of(script_public_key, after, limit)
+    }
+
     pub fn update_utxo_state(
         &mut self,
         to_add: &UtxoSetByScriptPublicKey,
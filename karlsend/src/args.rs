@@ -4,12 +4,18 @@ use karlsen_consensus_core::{
     network::{NetworkId, NetworkType},
 };
 use karlsen_core::karlsend_env::version;
-use karlsen_notify::address::tracker::Tracker;
+use karlsen_hashes::Hash;
+use karlsen_mining::mempool::config::RelayPolicy;
+use karlsen_notify::{
+    address::tracker::Tracker,
+    queue::{EventPriority, OverflowPolicy, QueueSettings, DEFAULT_QUEUE_CAPACITY},
+};
+use karlsen_rpc_service::policy::parse_method;
 use karlsen_utils::networking::ContextualNetAddress;
 use karlsen_wrpc_server::address::WrpcNetAddress;
 use serde::Deserialize;
 use serde_with::{serde_as, DisplayFromStr};
-use std::{ffi::OsString, fs};
+use std::{ffi::OsString, fs, net::SocketAddr, str::FromStr};
 use toml::from_str;
 
 #[cfg(feature = "devnet-prealloc")]
@@ -36,12 +42,29 @@ pub struct Args {
     pub rpclisten_borsh: Option<WrpcNetAddress>,
     #[serde_as(as = "Option<DisplayFromStr>")]
     pub rpclisten_json: Option<WrpcNetAddress>,
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    pub rpclisten_public: Option<ContextualNetAddress>,
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    pub rpclisten_public_borsh: Option<WrpcNetAddress>,
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    pub rpclisten_public_json: Option<WrpcNetAddress>,
+    /// Requests per second allowed to each client IP of the public RPC listeners, 0 for no limit
+    pub rpc_public_rate_limit: u32,
+    /// Methods allowed on the public RPC listeners on top of the read-only ones
+    pub rpc_public_allow: Vec<String>,
     #[serde(rename = "unsaferpc")]
     pub unsafe_rpc: bool,
     pub wrpc_verbose: bool,
     #[serde(rename = "loglevel")]
     pub log_level: String,
     pub async_threads: usize,
+    pub processor_threads: usize,
+    pub virtual_threads: usize,
+    pub pow_threads: usize,
+    pub rpc_workers: usize,
+    pub rpc_max_response_size: usize,
+    pub rpc_max_previous_outputs_reads: usize,
+    pub clock_skew_threshold: u64,
     #[serde(rename = "connect")]
     #[serde_as(as = "Vec<DisplayFromStr>")]
     pub connect_peers: Vec<ContextualNetAddress>,
@@ -53,6 +76,9 @@ pub struct Args {
     #[serde(rename = "uacomment")]
     pub user_agent_comments: Vec<String>,
     pub utxoindex: bool,
+    pub blockfilterindex: bool,
+    /// Count of chain acceptance events retained by the acceptance journal, 0 disabling it
+    pub acceptance_journal_size: usize,
     pub reset_db: bool,
     #[serde(rename = "outpeers")]
     pub outbound_target: usize,
@@ -69,13 +95,47 @@ pub struct Args {
     pub devnet: bool,
     pub simnet: bool,
     pub archival: bool,
+    /// Directory receiving the block bodies and acceptance data older than `cold_storage_age`
+    #[serde(rename = "colddatadir")]
+    pub cold_datadir: Option<String>,
+    /// Age in hours of the data migrated to `cold_datadir`
+    pub cold_storage_age: u64,
     pub sanity: bool,
+    /// Hash of the block under which the scripts are not verified, `0` to verify every script.
+    /// Defaults to the latest checkpoint of the network.
+    pub assume_valid: Option<String>,
     pub yes: bool,
     #[serde_as(as = "Option<DisplayFromStr>")]
     pub externalip: Option<ContextualNetAddress>,
     pub perf_metrics: bool,
     pub perf_metrics_interval_sec: u64,
     pub block_template_cache_lifetime: Option<u64>,
+    /// Text embedded in the coinbase payload of the block templates built by the node
+    pub miner_tag: Option<String>,
+    /// Power drawn by the miners, in watts, for the mining profitability stats
+    pub miner_power_watts: Option<f64>,
+    /// Price of a kWh, for the mining profitability stats
+    pub power_price: Option<f64>,
+    /// Secret shared with the miners, binding the block templates to their work
+    pub work_binding_secret: Option<String>,
+    pub block_journal_size: usize,
+    pub wrpc_durable_retention: usize,
+    /// Count of notifications queued for a wRPC connection
+    #[serde_as(as = "Vec<DisplayFromStr>")]
+    pub rpc_notification_queue: Vec<WrpcListenerSetting<usize>>,
+    /// Handling of a wRPC connection whose notification queue is full
+    #[serde_as(as = "Vec<DisplayFromStr>")]
+    pub rpc_notification_overflow: Vec<WrpcListenerSetting<OverflowPolicy>>,
+    /// Priorities of the notifications queued for a wRPC connection, by event type
+    #[serde_as(as = "Vec<DisplayFromStr>")]
+    pub rpc_notification_priority: Vec<WrpcListenerSetting<EventPriority>>,
+    /// Address serving the pruning point snapshots of the node over HTTP
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    pub snapshot_listen: Option<SocketAddr>,
+    /// URL of a snapshot server to bootstrap from during IBD
+    pub snapshot_url: Option<String>,
+    /// Hex encoded public key of the only publisher whose snapshots are accepted
+    pub snapshot_publisher: Option<String>,
 
     #[cfg(feature = "devnet-prealloc")]
     pub num_prealloc_utxos: Option<u64>,
@@ -84,12 +144,25 @@ pub struct Args {
     #[cfg(feature = "devnet-prealloc")]
     pub prealloc_amount: u64,
 
+    /// Enables the address cluster index serving GetAddressCluster
+    #[cfg(feature = "analytics")]
+    pub addressclusterindex: bool,
+
     pub disable_upnp: bool,
     #[serde(rename = "nodnsseed")]
     pub disable_dns_seeding: bool,
     #[serde(rename = "nogrpc")]
     pub disable_grpc: bool,
     pub ram_scale: f64,
+    /// Fee rate in sompi/kg defining dust outputs under the relay policy
+    #[serde(rename = "dustrelayfee")]
+    pub dust_relay_fee: u64,
+    pub storage_mass_factor: f64,
+    pub max_standard_tx_mass: u64,
+    pub max_standard_sig_ops: u64,
+    /// Memory limit of the process in megabytes, 0 disabling the memory budget
+    pub memory_limit: usize,
+    pub webhooks_config: Option<String>,
 }
 
 impl Default for Args {
@@ -99,9 +172,25 @@ impl Default for Args {
             no_log_files: false,
             rpclisten_borsh: None,
             rpclisten_json: None,
+            rpclisten_public: None,
+            rpclisten_public_borsh: None,
+            rpclisten_public_json: None,
+            rpc_public_rate_limit: 20,
+            rpc_public_allow: vec![],
             unsafe_rpc: false,
             async_threads: num_cpus::get(),
+            processor_threads: 0,
+            virtual_threads: 0,
+            pow_threads: 0,
+            // Leave most of the cores to the block processing on small hosts
+            rpc_workers: (num_cpus::get() / 4).max(1),
+            // The maximum message size of the wRPC server
+            rpc_max_response_size: 128 * 1024 * 1024,
+            rpc_max_previous_outputs_reads: 100_000,
+            clock_skew_threshold: 30,
             utxoindex: false,
+            blockfilterindex: false,
+            acceptance_journal_size: 0,
             reset_db: false,
             outbound_target: 8,
             inbound_limit: 128,
@@ -114,7 +203,10 @@ impl Default for Args {
             devnet: false,
             simnet: false,
             archival: false,
+            cold_datadir: None,
+            cold_storage_age: 30 * 24,
             sanity: false,
+            assume_valid: None,
             logdir: None,
             rpclisten: None,
             wrpc_verbose: false,
@@ -128,6 +220,18 @@ impl Default for Args {
             perf_metrics_interval_sec: 10,
             externalip: None,
             block_template_cache_lifetime: None,
+            miner_tag: None,
+            miner_power_watts: None,
+            power_price: None,
+            work_binding_secret: None,
+            block_journal_size: 0,
+            wrpc_durable_retention: 0,
+            rpc_notification_queue: vec![],
+            rpc_notification_overflow: vec![],
+            rpc_notification_priority: vec![],
+            snapshot_listen: None,
+            snapshot_url: None,
+            snapshot_publisher: None,
 
             #[cfg(feature = "devnet-prealloc")]
             num_prealloc_utxos: None,
@@ -136,10 +240,19 @@ impl Default for Args {
             #[cfg(feature = "devnet-prealloc")]
             prealloc_amount: 1_000_000,
 
+            #[cfg(feature = "analytics")]
+            addressclusterindex: false,
+
             disable_upnp: false,
             disable_dns_seeding: false,
             disable_grpc: false,
             ram_scale: 1.0,
+            dust_relay_fee: RelayPolicy::default().dust_relay_transaction_fee,
+            storage_mass_factor: RelayPolicy::default().storage_mass_factor,
+            max_standard_tx_mass: RelayPolicy::default().maximum_standard_transaction_mass,
+            max_standard_sig_ops: RelayPolicy::default().maximum_standard_p2sh_sig_ops,
+            memory_limit: 0,
+            webhooks_config: None,
         }
     }
 }
@@ -152,17 +265,29 @@ impl Args {
         config.enable_unsynced_mining = self.enable_unsynced_mining;
         config.enable_mainnet_mining = self.enable_mainnet_mining;
         config.is_archival = self.archival;
+        if self.cold_datadir.is_some() {
+            config.cold_storage_depth = self.cold_storage_age * 60 * 60 * config.params.bps();
+        }
         // TODO: change to `config.enable_sanity_checks = self.sanity` when we reach stable versions
         config.enable_sanity_checks = true;
         config
             .user_agent_comments
             .clone_from(&self.user_agent_comments);
         config.block_template_cache_lifetime = self.block_template_cache_lifetime;
+        config.miner_tag.clone_from(&self.miner_tag);
+        config.block_added_journal_size = self.block_journal_size;
+        config.perf.block_processors_num_threads = self.processor_threads;
+        config.perf.virtual_processor_num_threads = self.virtual_threads;
+        config.perf.pow_verification_num_threads = self.pow_threads;
         config.p2p_listen_address = self.listen.unwrap_or(ContextualNetAddress::unspecified());
         config.externalip = self
             .externalip
             .map(|v| v.normalize(config.default_p2p_port()));
         config.ram_scale = self.ram_scale;
+        if let Some(assume_valid) = self.assume_valid.as_deref() {
+            config.assume_valid =
+                parse_assume_valid(assume_valid).expect("checked by the arguments validation");
+        }
 
         #[cfg(feature = "devnet-prealloc")]
         if let Some(num_prealloc_utxos) = self.num_prealloc_utxos {
@@ -170,6 +295,34 @@ impl Args {
         }
     }
 
+    /// Returns the relay policy of the mempool, which is distinct from the consensus rules
+    pub fn relay_policy(&self) -> RelayPolicy {
+        RelayPolicy {
+            dust_relay_transaction_fee: self.dust_relay_fee,
+            storage_mass_factor: self.storage_mass_factor,
+            maximum_standard_transaction_mass: self.max_standard_tx_mass,
+            maximum_standard_p2sh_sig_ops: self.max_standard_sig_ops,
+        }
+    }
+
+    /// Returns the settings of the notification queues of the connections of a wRPC listener
+    pub fn notification_queue(&self, listener: WrpcListener) -> QueueSettings {
+        let capacity = WrpcListenerSetting::values(&self.rpc_notification_queue, listener)
+            .last()
+            .unwrap_or(DEFAULT_QUEUE_CAPACITY);
+        let overflow = WrpcListenerSetting::values(&self.rpc_notification_overflow, listener)
+            .last()
+            .unwrap_or_default();
+        WrpcListenerSetting::values(&self.rpc_notification_priority, listener).fold(
+            QueueSettings::new(capacity.max(1), overflow),
+            |settings,
+             EventPriority {
+                 event_type,
+                 priority,
+             }| { settings.with_priority(event_type, priority) },
+        )
+    }
+
     #[cfg(feature = "devnet-prealloc")]
     pub fn generate_prealloc_utxos(
         &self,
@@ -208,6 +361,76 @@ impl Args {
     }
 }
 
+/// wRPC listener, as named by the settings scoped to it
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WrpcListener {
+    Borsh,
+    Json,
+    PublicBorsh,
+    PublicJson,
+}
+
+impl FromStr for WrpcListener {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "borsh" => Ok(WrpcListener::Borsh),
+            "json" => Ok(WrpcListener::Json),
+            "public-borsh" => Ok(WrpcListener::PublicBorsh),
+            "public-json" => Ok(WrpcListener::PublicJson),
+            _ => Err(format!(
+                "invalid wRPC listener `{s}`, expected one of: borsh, json, public-borsh, public-json"
+            )),
+        }
+    }
+}
+
+/// Setting of the wRPC listener it names, or of all of them, parsed from `[listener:]value`
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct WrpcListenerSetting<T> {
+    pub listener: Option<WrpcListener>,
+    pub value: T,
+}
+
+impl<T: Copy> WrpcListenerSetting<T> {
+    /// Returns the values applying to `listener`, the ones scoped to it last
+    fn values(settings: &[Self], listener: WrpcListener) -> impl Iterator<Item = T> + '_ {
+        let unscoped = settings.iter().filter(|setting| setting.listener.is_none());
+        let scoped = settings
+            .iter()
+            .filter(move |setting| setting.listener == Some(listener));
+        unscoped.chain(scoped).map(|setting| setting.value)
+    }
+}
+
+impl<T> FromStr for WrpcListenerSetting<T>
+where
+    T: FromStr,
+    T::Err: std::fmt::Display,
+{
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (listener, value) = match s.split_once(':') {
+            Some((listener, value)) => (Some(listener.parse()?), value),
+            None => (None, s),
+        };
+        Ok(Self {
+            listener,
+            value: value.parse().map_err(|err: T::Err| err.to_string())?,
+        })
+    }
+}
+
+/// Parses the value of `--assume-valid`, where `0` disables the assumption
+pub fn parse_assume_valid(value: &str) -> Result<Option<Hash>, <Hash as FromStr>::Err> {
+    match value {
+        "0" => Ok(None),
+        hash => Hash::from_str(hash).map(Some),
+    }
+}
+
 pub fn cli() -> Command {
     let defaults: Args = Default::default();
 
@@ -228,6 +451,55 @@ pub fn cli() -> Command {
                 .value_parser(clap::value_parser!(usize))
                 .help(format!("Specify number of async threads (default: {}).", defaults.async_threads)),
         )
+        .arg(
+            Arg::new("processor-threads")
+                .long("processor-threads")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(usize))
+                .help("Number of threads of the block validation pool (default: 0, one per logical CPU core)."),
+        )
+        .arg(
+            Arg::new("virtual-threads")
+                .long("virtual-threads")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(usize))
+                .help("Number of threads of the virtual processor pool (default: 0, one per logical CPU core)."),
+        )
+        .arg(
+            Arg::new("pow-threads")
+                .long("pow-threads")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(usize))
+                .help("Number of threads verifying the proof of work of headers (default: 0, half of the logical CPU cores)."),
+        )
+        .arg(
+            Arg::new("rpc-workers")
+                .long("rpc-workers")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(usize))
+                .help(format!("Number of threads running the heavy RPC queries apart from block processing, 0 to share the node threads (default: {}).", defaults.rpc_workers)),
+        )
+        .arg(
+            Arg::new("rpc-max-response-size")
+                .long("rpc-max-response-size")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(usize))
+                .help(format!("Maximum size in bytes of the RPC responses listing UTXOs or mempool entries, larger ones being split in pages or rejected, 0 for unlimited (default: {}).", defaults.rpc_max_response_size)),
+        )
+        .arg(
+            Arg::new("rpc-max-previous-outputs-reads")
+                .long("rpc-max-previous-outputs-reads")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(usize))
+                .help(format!("Maximum count of consensus reads an RPC request may spend resolving the outputs spent by the blocks it returns (default: {}).", defaults.rpc_max_previous_outputs_reads)),
+        )
+        .arg(
+            Arg::new("clock-skew-threshold")
+                .long("clock-skew-threshold")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(u64))
+                .help(format!("Skew of the local clock against the network, in seconds, above which a warning is logged, 0 to disable (default: {}).", defaults.clock_skew_threshold)),
+        )
         .arg(
             Arg::new("log_level")
                 .short('d')
@@ -267,6 +539,45 @@ pub fn cli() -> Command {
                 .value_parser(clap::value_parser!(WrpcNetAddress))
                 .help("Interface:port to listen for wRPC JSON connections (default port: 44110, testnet: 44210)."),
         )
+        .arg(
+            Arg::new("rpclisten-public")
+                .long("rpclisten-public")
+                .value_name("IP[:PORT]")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(ContextualNetAddress))
+                .help("Interface:port of a public gRPC listener, restricted to the read-only methods and rate limited."),
+        )
+        .arg(
+            Arg::new("rpclisten-public-borsh")
+                .long("rpclisten-public-borsh")
+                .value_name("IP[:PORT]")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(WrpcNetAddress))
+                .help("Interface:port of a public wRPC Borsh listener, restricted to the read-only methods and rate limited."),
+        )
+        .arg(
+            Arg::new("rpclisten-public-json")
+                .long("rpclisten-public-json")
+                .value_name("IP[:PORT]")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(WrpcNetAddress))
+                .help("Interface:port of a public wRPC JSON listener, restricted to the read-only methods and rate limited."),
+        )
+        .arg(
+            Arg::new("rpc-public-rate-limit")
+                .long("rpc-public-rate-limit")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(u32))
+                .help(format!("Requests per second allowed to each client IP of the public RPC listeners, 0 for no limit (default: {}).", defaults.rpc_public_rate_limit)),
+        )
+        .arg(
+            Arg::new("rpc-public-allow")
+                .long("rpc-public-allow")
+                .value_name("METHOD")
+                .action(ArgAction::Append)
+                .require_equals(true)
+                .help("RPC method to allow on the public RPC listeners on top of the read-only ones (ie. submitTransaction)."),
+        )
         .arg(arg!(--unsaferpc "Enable RPC commands which affect the state of the node"))
         .arg(
             Arg::new("connect-peers")
@@ -328,6 +639,14 @@ pub fn cli() -> Command {
                 .help("Allow mainnet mining (currently enabled by default while the flag is kept for backwards compatibility)"),
         )
         .arg(arg!(--utxoindex "Enable the UTXO index"))
+        .arg(arg!(--blockfilterindex "Enable the compact block filter index serving the filters of the chain blocks to light wallets"))
+        .arg(
+            Arg::new("acceptance-journal-size")
+                .long("acceptance-journal-size")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(usize))
+                .help("Enable the acceptance journal, retaining on disk this many of the latest chain acceptance events for resyncing clients (default: 0, disabled)."),
+        )
         .arg(
             Arg::new("max-tracked-addresses")
                 .long("max-tracked-addresses")
@@ -349,7 +668,22 @@ Setting to 0 prevents the preallocation and sets the maximum to {}, leading to 0
         .arg(arg!(--devnet "Use the development test network"))
         .arg(arg!(--simnet "Use the simulation test network"))
         .arg(arg!(--archival "Run as an archival node: avoids deleting old block data when moving the pruning point (Warning: heavy disk usage)"))
+        .arg(arg!(--colddatadir <COLD_DATA_DIR> "Directory to move the block bodies and acceptance data older than --cold-storage-age to, typically on slower storage. They are still served from there."))
+        .arg(
+            Arg::new("cold-storage-age")
+                .long("cold-storage-age")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(u64))
+                .help(format!("Age in hours of the data moved to --colddatadir, never less than the finality depth (default: {}).", defaults.cold_storage_age)),
+        )
         .arg(arg!(--sanity "Enable various sanity checks which might be compute-intensive (mostly performed during pruning)"))
+        .arg(
+            Arg::new("assume-valid")
+                .long("assume-valid")
+                .value_name("HASH")
+                .require_equals(true)
+                .help("Skip the script verification of the blocks in the past of this block during sync, still verifying their proof of work, structure and UTXO commitments. Set to 0 to verify every script (default: the latest checkpoint of the network)."),
+        )
         .arg(arg!(--yes "Answer yes to all interactive console questions"))
         .arg(
             Arg::new("user_agent_comments")
@@ -375,6 +709,47 @@ Setting to 0 prevents the preallocation and sets the maximum to {}, leading to 0
                 .value_parser(clap::value_parser!(u64))
                 .help("Interval in seconds for performance metrics collection."),
         )
+        .arg(
+            Arg::new("block-journal-size")
+                .long("block-journal-size")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(usize))
+                .help("Number of recently added blocks retained for RPC clients resuming the block added event stream (default: 0, disabled)."),
+        )
+        .arg(
+            Arg::new("wrpc-durable-retention")
+                .long("wrpc-durable-retention")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(usize))
+                .help("Number of notifications journaled per durable wRPC subscription for replay on reconnection (default: 0, disabled)."),
+        )
+        .arg(
+            Arg::new("rpc-notification-queue")
+                .long("rpc-notification-queue")
+                .value_name("[LISTENER:]COUNT")
+                .action(ArgAction::Append)
+                .require_equals(true)
+                .value_parser(clap::value_parser!(WrpcListenerSetting<usize>))
+                .help(format!("Number of notifications queued for a slow wRPC client before applying the overflow policy, for the wRPC listeners or the one prefixed: borsh, json, public-borsh or public-json (default: {DEFAULT_QUEUE_CAPACITY}).")),
+        )
+        .arg(
+            Arg::new("rpc-notification-overflow")
+                .long("rpc-notification-overflow")
+                .value_name("[LISTENER:]POLICY")
+                .action(ArgAction::Append)
+                .require_equals(true)
+                .value_parser(clap::value_parser!(WrpcListenerSetting<OverflowPolicy>))
+                .help("Handling of a wRPC client whose notification queue is full, for the wRPC listeners or the one prefixed: drop-oldest, dropping the oldest notifications of the lowest priority and sending a gap marker, or disconnect (default: drop-oldest)."),
+        )
+        .arg(
+            Arg::new("rpc-notification-priority")
+                .long("rpc-notification-priority")
+                .value_name("[LISTENER:]EVENT=PRIORITY")
+                .action(ArgAction::Append)
+                .require_equals(true)
+                .value_parser(clap::value_parser!(WrpcListenerSetting<EventPriority>))
+                .help("Priority of the notifications of an event type (ie. utxos-changed=high), for the wRPC listeners or the one prefixed: low, normal or high. The priorities only reorder the notifications of a client whose queue is more than half full."),
+        )
         .arg(arg!(--"disable-upnp" "Disable upnp"))
         .arg(arg!(--"nodnsseed" "Disable DNS seeding for peers"))
         .arg(arg!(--"nogrpc" "Disable gRPC server"))
@@ -386,8 +761,111 @@ Setting to 0 prevents the preallocation and sets the maximum to {}, leading to 0
                 .help("Apply a scale factor to memory allocation bounds. Nodes with limited RAM (~4-8GB) should set this to ~0.3-0.5 respectively. Nodes with 
 a large RAM (~64GB) can set this value to ~3.0-4.0 and gain superior performance especially for syncing peers faster"),
         )
+        .arg(
+            Arg::new("dustrelayfee")
+                .long("dustrelayfee")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(u64))
+                .help(format!("Relay policy: fee rate in sompi/kg defining dust outputs, ie. outputs whose spending costs more than a third of their value at this rate (default: {}).", defaults.dust_relay_fee)),
+        )
+        .arg(
+            Arg::new("storage-mass-factor")
+                .long("storage-mass-factor")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(f64))
+                .help(format!("Relay policy: weight of the storage mass of transactions, above 1 to reject transactions bloating the UTXO set beyond what consensus allows (default: {}).", defaults.storage_mass_factor)),
+        )
+        .arg(
+            Arg::new("max-standard-tx-mass")
+                .long("max-standard-tx-mass")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(u64))
+                .help(format!("Relay policy: maximum mass of a standard transaction (default: {}).", defaults.max_standard_tx_mass)),
+        )
+        .arg(
+            Arg::new("max-standard-sig-ops")
+                .long("max-standard-sig-ops")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(u64))
+                .help(format!("Relay policy: maximum number of signature operations of a standard pay-to-script-hash input (default: {}).", defaults.max_standard_sig_ops)),
+        )
+        .arg(
+            Arg::new("memory-limit")
+                .long("memory-limit")
+                .value_name("MB")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(usize))
+                .help("Memory limit in megabytes above which caches and pools are shrunk. Nodes with limited RAM can set it to ~75% of the system memory (default: 0, disabled)."),
+        )
+        .arg(
+            Arg::new("webhooks-config")
+                .long("webhooks-config")
+                .value_name("FILE")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(String))
+                .help("Path to a TOML file configuring webhook endpoints notified of node events (block added, transaction accepted, deep reorg)."),
+        )
+        .arg(
+            Arg::new("miner-tag")
+                .long("miner-tag")
+                .value_name("TAG")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(String))
+                .help("Short UTF-8 text, up to 24 bytes, embedded in the coinbase payload of the block templates built by the node."),
+        )
+        .arg(
+            Arg::new("miner-power-watts")
+                .long("miner-power-watts")
+                .value_name("WATTS")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(f64))
+                .help("Power drawn by the miners of the node, in watts. Along with --power-price, reports the power cost of the mined blocks."),
+        )
+        .arg(
+            Arg::new("power-price")
+                .long("power-price")
+                .value_name("PRICE")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(f64))
+                .help("Price of a kWh, in any currency. Along with --miner-power-watts, reports the power cost of the mined blocks."),
+        )
+        .arg(
+            Arg::new("work-binding-secret")
+                .long("work-binding-secret")
+                .value_name("SECRET")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(String))
+                .help("Secret of at least 16 bytes shared with the miners. Binds the block templates to the pay address and worker requesting them, and rejects the submitted blocks not carrying such a binding."),
+        )
+        .arg(
+            Arg::new("snapshot-listen")
+                .long("snapshot-listen")
+                .value_name("IP:PORT")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(SocketAddr))
+                .help("Interface:port serving the pruning point snapshots of the node over HTTP, for other nodes to bootstrap from."),
+        )
+        .arg(
+            Arg::new("snapshot-url")
+                .long("snapshot-url")
+                .value_name("URL")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(String))
+                .help("URL of a snapshot server to fetch the pruning point proof and UTXO set from during IBD, falling back to peers if the snapshot fails."),
+        )
+        .arg(
+            Arg::new("snapshot-publisher")
+                .long("snapshot-publisher")
+                .value_name("PUBKEY")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(String))
+                .help("Hex encoded public key of the only snapshot publisher trusted by --snapshot-url (default: any publisher)."),
+        )
         ;
 
+    #[cfg(feature = "analytics")]
+    let cmd = cmd.arg(arg!(--addressclusterindex "Enable the address cluster index, grouping the addresses spent together by transactions (analytics only, the index grows with the chain)"));
+
     #[cfg(feature = "devnet-prealloc")]
     let cmd = cmd
         .arg(
@@ -460,6 +938,28 @@ impl Args {
                 .get_one::<WrpcNetAddress>("rpclisten-json")
                 .cloned()
                 .or(defaults.rpclisten_json),
+            rpclisten_public: m
+                .get_one::<ContextualNetAddress>("rpclisten-public")
+                .cloned()
+                .or(defaults.rpclisten_public),
+            rpclisten_public_borsh: m
+                .get_one::<WrpcNetAddress>("rpclisten-public-borsh")
+                .cloned()
+                .or(defaults.rpclisten_public_borsh),
+            rpclisten_public_json: m
+                .get_one::<WrpcNetAddress>("rpclisten-public-json")
+                .cloned()
+                .or(defaults.rpclisten_public_json),
+            rpc_public_rate_limit: arg_match_unwrap_or::<u32>(
+                &m,
+                "rpc-public-rate-limit",
+                defaults.rpc_public_rate_limit,
+            ),
+            rpc_public_allow: arg_match_many_unwrap_or::<String>(
+                &m,
+                "rpc-public-allow",
+                defaults.rpc_public_allow,
+            ),
             unsafe_rpc: arg_match_unwrap_or::<bool>(&m, "unsaferpc", defaults.unsafe_rpc),
             wrpc_verbose: false,
             log_level: arg_match_unwrap_or::<String>(&m, "log_level", defaults.log_level),
@@ -468,6 +968,33 @@ impl Args {
                 "async_threads",
                 defaults.async_threads,
             ),
+            processor_threads: arg_match_unwrap_or::<usize>(
+                &m,
+                "processor-threads",
+                defaults.processor_threads,
+            ),
+            virtual_threads: arg_match_unwrap_or::<usize>(
+                &m,
+                "virtual-threads",
+                defaults.virtual_threads,
+            ),
+            pow_threads: arg_match_unwrap_or::<usize>(&m, "pow-threads", defaults.pow_threads),
+            rpc_workers: arg_match_unwrap_or::<usize>(&m, "rpc-workers", defaults.rpc_workers),
+            rpc_max_response_size: arg_match_unwrap_or::<usize>(
+                &m,
+                "rpc-max-response-size",
+                defaults.rpc_max_response_size,
+            ),
+            rpc_max_previous_outputs_reads: arg_match_unwrap_or::<usize>(
+                &m,
+                "rpc-max-previous-outputs-reads",
+                defaults.rpc_max_previous_outputs_reads,
+            ),
+            clock_skew_threshold: arg_match_unwrap_or::<u64>(
+                &m,
+                "clock-skew-threshold",
+                defaults.clock_skew_threshold,
+            ),
             connect_peers: arg_match_many_unwrap_or::<ContextualNetAddress>(
                 &m,
                 "connect-peers",
@@ -506,12 +1033,35 @@ impl Args {
                 defaults.enable_mainnet_mining,
             ),
             utxoindex: arg_match_unwrap_or::<bool>(&m, "utxoindex", defaults.utxoindex),
+            blockfilterindex: arg_match_unwrap_or::<bool>(
+                &m,
+                "blockfilterindex",
+                defaults.blockfilterindex,
+            ),
+            acceptance_journal_size: arg_match_unwrap_or::<usize>(
+                &m,
+                "acceptance-journal-size",
+                defaults.acceptance_journal_size,
+            ),
             testnet: arg_match_unwrap_or::<bool>(&m, "testnet", defaults.testnet),
             testnet_suffix: arg_match_unwrap_or::<u32>(&m, "netsuffix", defaults.testnet_suffix),
             devnet: arg_match_unwrap_or::<bool>(&m, "devnet", defaults.devnet),
             simnet: arg_match_unwrap_or::<bool>(&m, "simnet", defaults.simnet),
             archival: arg_match_unwrap_or::<bool>(&m, "archival", defaults.archival),
+            cold_datadir: m
+                .get_one::<String>("colddatadir")
+                .cloned()
+                .or(defaults.cold_datadir),
+            cold_storage_age: arg_match_unwrap_or::<u64>(
+                &m,
+                "cold-storage-age",
+                defaults.cold_storage_age,
+            ),
             sanity: arg_match_unwrap_or::<bool>(&m, "sanity", defaults.sanity),
+            assume_valid: m
+                .get_one::<String>("assume-valid")
+                .cloned()
+                .or(defaults.assume_valid),
             yes: arg_match_unwrap_or::<bool>(&m, "yes", defaults.yes),
             user_agent_comments: arg_match_many_unwrap_or::<String>(
                 &m,
@@ -527,6 +1077,49 @@ impl Args {
             ),
             // Note: currently used programmatically by benchmarks and not exposed to CLI users
             block_template_cache_lifetime: defaults.block_template_cache_lifetime,
+            miner_tag: m
+                .get_one::<String>("miner-tag")
+                .cloned()
+                .or(defaults.miner_tag),
+            miner_power_watts: m
+                .get_one::<f64>("miner-power-watts")
+                .cloned()
+                .or(defaults.miner_power_watts),
+            power_price: m
+                .get_one::<f64>("power-price")
+                .cloned()
+                .or(defaults.power_price),
+            work_binding_secret: m
+                .get_one::<String>("work-binding-secret")
+                .cloned()
+                .or(defaults.work_binding_secret),
+            block_journal_size: arg_match_unwrap_or::<usize>(
+                &m,
+                "block-journal-size",
+                defaults.block_journal_size,
+            ),
+            wrpc_durable_retention: arg_match_unwrap_or::<usize>(
+                &m,
+                "wrpc-durable-retention",
+                defaults.wrpc_durable_retention,
+            ),
+            rpc_notification_queue: arg_match_many_unwrap_or::<WrpcListenerSetting<usize>>(
+                &m,
+                "rpc-notification-queue",
+                defaults.rpc_notification_queue,
+            ),
+            rpc_notification_overflow: arg_match_many_unwrap_or::<
+                WrpcListenerSetting<OverflowPolicy>,
+            >(
+                &m,
+                "rpc-notification-overflow",
+                defaults.rpc_notification_overflow,
+            ),
+            rpc_notification_priority: arg_match_many_unwrap_or::<WrpcListenerSetting<EventPriority>>(
+                &m,
+                "rpc-notification-priority",
+                defaults.rpc_notification_priority,
+            ),
             disable_upnp: arg_match_unwrap_or::<bool>(&m, "disable-upnp", defaults.disable_upnp),
             disable_dns_seeding: arg_match_unwrap_or::<bool>(
                 &m,
@@ -535,6 +1128,39 @@ impl Args {
             ),
             disable_grpc: arg_match_unwrap_or::<bool>(&m, "nogrpc", defaults.disable_grpc),
             ram_scale: arg_match_unwrap_or::<f64>(&m, "ram-scale", defaults.ram_scale),
+            dust_relay_fee: arg_match_unwrap_or::<u64>(&m, "dustrelayfee", defaults.dust_relay_fee),
+            storage_mass_factor: arg_match_unwrap_or::<f64>(
+                &m,
+                "storage-mass-factor",
+                defaults.storage_mass_factor,
+            ),
+            max_standard_tx_mass: arg_match_unwrap_or::<u64>(
+                &m,
+                "max-standard-tx-mass",
+                defaults.max_standard_tx_mass,
+            ),
+            max_standard_sig_ops: arg_match_unwrap_or::<u64>(
+                &m,
+                "max-standard-sig-ops",
+                defaults.max_standard_sig_ops,
+            ),
+            memory_limit: arg_match_unwrap_or::<usize>(&m, "memory-limit", defaults.memory_limit),
+            webhooks_config: m
+                .get_one::<String>("webhooks-config")
+                .cloned()
+                .or(defaults.webhooks_config),
+            snapshot_listen: m
+                .get_one::<SocketAddr>("snapshot-listen")
+                .cloned()
+                .or(defaults.snapshot_listen),
+            snapshot_url: m
+                .get_one::<String>("snapshot-url")
+                .cloned()
+                .or(defaults.snapshot_url),
+            snapshot_publisher: m
+                .get_one::<String>("snapshot-publisher")
+                .cloned()
+                .or(defaults.snapshot_publisher),
 
             #[cfg(feature = "devnet-prealloc")]
             num_prealloc_utxos: m.get_one::<u64>("num-prealloc-utxos").cloned(),
@@ -546,8 +1172,26 @@ impl Args {
                 "prealloc-amount",
                 defaults.prealloc_amount,
             ),
+
+            #[cfg(feature = "analytics")]
+            addressclusterindex: arg_match_unwrap_or::<bool>(
+                &m,
+                "addressclusterindex",
+                defaults.addressclusterindex,
+            ),
         };
 
+        if let Some(method) = args
+            .rpc_public_allow
+            .iter()
+            .find(|method| parse_method(method).is_none())
+        {
+            return Err(clap::Error::raw(
+                clap::error::ErrorKind::ValueValidation,
+                format!("Unknown RPC method {} in --rpc-public-allow", method),
+            ));
+        }
+
         if arg_match_unwrap_or::<bool>(&m, "enable-mainnet-mining", false) {
             println!("\nNOTE: The flag --enable-mainnet-mining is deprecated and defaults to true also w/o explicit setting\n")
         }
@@ -2,18 +2,26 @@ use std::{fs, path::PathBuf, process::exit, sync::Arc, time::Duration};
 
 use async_channel::unbounded;
 use karlsen_consensus_core::{
-    config::ConfigBuilder,
+    coinbase::MAX_MINER_TAG_LEN,
+    config::{params::Params, ConfigBuilder},
     errors::config::{ConfigError, ConfigResult},
 };
 use karlsen_consensus_notify::{root::ConsensusNotificationRoot, service::NotifyService};
-use karlsen_core::{core::Core, info, trace};
+use karlsen_core::{core::Core, info, trace, warn};
 use karlsen_core::{karlsend_env::version, task::tick::TickService};
 use karlsen_database::prelude::CachePolicy;
 use karlsen_grpc_server::service::GrpcService;
 use karlsen_notify::{address::tracker::Tracker, subscription::context::SubscriptionContext};
-use karlsen_rpc_service::service::RpcCoreService;
+use karlsen_rpc_core::RpcWorkBinding;
+use karlsen_rpc_service::{
+    config::RpcCoreConfig,
+    metadata::MetadataStore,
+    miner_stats::PowerCost,
+    policy::{parse_method, RpcPolicy},
+    service::RpcCoreService,
+};
 use karlsen_txscript::caches::TxScriptCacheCounters;
-use karlsen_utils::networking::ContextualNetAddress;
+use karlsen_utils::{mem_budget::memory_budget, networking::ContextualNetAddress};
 use karlsen_utils_tower::counters::TowerConnectionCounters;
 
 use karlsen_addressmanager::AddressManager;
@@ -26,7 +34,7 @@ use karlsen_consensus::{
 };
 use karlsen_consensusmanager::ConsensusManager;
 use karlsen_core::task::runtime::AsyncRuntime;
-use karlsen_index_processor::service::IndexService;
+use karlsen_index_processor::{consistency::UtxoIndexConsistencyService, service::IndexService};
 use karlsen_mining::{
     manager::{MiningManager, MiningManagerProxy},
     monitor::MiningMonitor,
@@ -34,8 +42,15 @@ use karlsen_mining::{
 };
 use karlsen_p2p_flows::{flow_context::FlowContext, service::P2pService};
 
+use karlsen_acceptancejournal::{service::AcceptanceJournalService, AcceptanceJournal};
+#[cfg(feature = "analytics")]
+use karlsen_clusterindex::{service::AddressClusterIndexService, AddressClusterIndex};
+use karlsen_filterindex::{service::BlockFilterIndexService, BlockFilterIndex};
 use karlsen_perf_monitor::{builder::Builder as PerfMonitorBuilder, counters::CountersSnapshot};
+use karlsen_snapshot::{parse_public_key, SnapshotProducer, SnapshotService, SnapshotSource};
 use karlsen_utxoindex::{api::UtxoIndexProxy, UtxoIndex};
+use karlsen_webhook::{config::WebhookConfig, service::WebhookService};
+use karlsen_wrpc_server::durable::{DurableSubscriptions, DURABLE_SUBSCRIPTIONS_FILE};
 use karlsen_wrpc_server::service::{
     Options as WrpcServerOptions, WebSocketCounters as WrpcServerCounters, WrpcEncoding,
     WrpcService,
@@ -50,13 +65,18 @@ pub const DESIRED_DAEMON_SOFT_FD_LIMIT: u64 = 8 * 1024;
 /// this value may impact the database performance).
 pub const MINIMUM_DAEMON_SOFT_FD_LIMIT: u64 = 4 * 1024;
 
-use crate::args::Args;
+use crate::args::{parse_assume_valid, Args, WrpcListener};
 
 const DEFAULT_DATA_DIR: &str = "datadir";
 const CONSENSUS_DB: &str = "consensus";
 const UTXOINDEX_DB: &str = "utxoindex";
+const BLOCKFILTERINDEX_DB: &str = "blockfilterindex";
+const ACCEPTANCEJOURNAL_DB: &str = "acceptancejournal";
+#[cfg(feature = "analytics")]
+const ADDRESSCLUSTERINDEX_DB: &str = "addressclusterindex";
 const META_DB: &str = "meta";
 const META_DB_FILE_LIMIT: i32 = 5;
+const SNAPSHOT_DIR: &str = "snapshot";
 const DEFAULT_LOG_DIR: &str = "logs";
 
 fn get_home_dir() -> PathBuf {
@@ -98,11 +118,54 @@ pub fn validate_args(args: &Args) -> ConfigResult<()> {
     if args.ram_scale > 10.0 {
         return Err(ConfigError::RamScaleTooHigh);
     }
+    if args.storage_mass_factor <= 0.0 {
+        return Err(ConfigError::StorageMassFactorNotPositive);
+    }
+    let max_block_mass = Params::from(args.network()).max_block_mass;
+    if args.max_standard_tx_mass > max_block_mass {
+        return Err(ConfigError::MaxStandardTxMassTooHigh(max_block_mass));
+    }
     if args.max_tracked_addresses > Tracker::MAX_ADDRESS_UPPER_BOUND {
         return Err(ConfigError::MaxTrackedAddressesTooHigh(
             Tracker::MAX_ADDRESS_UPPER_BOUND,
         ));
     }
+    if let Some(assume_valid) = args.assume_valid.as_deref() {
+        if parse_assume_valid(assume_valid).is_err() {
+            return Err(ConfigError::InvalidAssumeValid(assume_valid.to_owned()));
+        }
+    }
+    if let Some(miner_tag) = args.miner_tag.as_deref() {
+        if miner_tag.is_empty()
+            || miner_tag.len() > MAX_MINER_TAG_LEN
+            || miner_tag.chars().any(char::is_control)
+        {
+            return Err(ConfigError::InvalidMinerTag(MAX_MINER_TAG_LEN));
+        }
+    }
+    match (args.miner_power_watts, args.power_price) {
+        (None, None) => {}
+        (Some(watts), Some(price))
+            if watts.is_finite() && watts >= 0.0 && price.is_finite() && price >= 0.0 => {}
+        _ => return Err(ConfigError::InvalidPowerCost),
+    }
+    if args
+        .work_binding_secret
+        .as_ref()
+        .is_some_and(|secret| secret.len() < RpcWorkBinding::MIN_SECRET_LEN)
+    {
+        return Err(ConfigError::WorkBindingSecretTooShort(
+            RpcWorkBinding::MIN_SECRET_LEN,
+        ));
+    }
+    if let Some(publisher) = args.snapshot_publisher.as_deref() {
+        if args.snapshot_url.is_none() {
+            return Err(ConfigError::SnapshotPublisherWithoutUrl);
+        }
+        if parse_public_key(publisher).is_err() {
+            return Err(ConfigError::InvalidSnapshotPublisher(publisher.to_owned()));
+        }
+    }
     Ok(())
 }
 
@@ -238,12 +301,47 @@ pub fn create_core_with_runtime(
     } else {
         0
     };
+    let filter_files_limit = if args.blockfilterindex {
+        let filter_files_limit = fd_remaining * 5 / 100;
+        fd_remaining -= filter_files_limit;
+        filter_files_limit
+    } else {
+        0
+    };
+    let journal_files_limit = if args.acceptance_journal_size > 0 {
+        let journal_files_limit = fd_remaining * 5 / 100;
+        fd_remaining -= journal_files_limit;
+        journal_files_limit
+    } else {
+        0
+    };
+    #[cfg(feature = "analytics")]
+    let cluster_files_limit = if args.addressclusterindex {
+        let cluster_files_limit = fd_remaining * 5 / 100;
+        fd_remaining -= cluster_files_limit;
+        cluster_files_limit
+    } else {
+        0
+    };
     // Make sure args forms a valid set of properties
     if let Err(err) = validate_args(args) {
         println!("{}", err);
         exit(1);
     }
 
+    // Load the webhooks config early so any error in it is reported before the node starts
+    let webhook_config = args.webhooks_config.as_ref().map(|path| {
+        match WebhookConfig::load(path)
+            .and_then(|config| config.validate(network.into()).map(|_| config))
+        {
+            Ok(config) => config,
+            Err(err) => {
+                println!("{}", err);
+                exit(1);
+            }
+        }
+    });
+
     let config = Arc::new(
         ConfigBuilder::new(network.into())
             .adjust_perf_params_to_consensus_params()
@@ -270,9 +368,28 @@ pub fn create_core_with_runtime(
             info!("Logs to console only");
         }
     }
+    if let Some(assume_valid) = config.assume_valid {
+        info!(
+            "Skipping the script verification of the blocks in the past of {}",
+            assume_valid
+        );
+    }
 
     let consensus_db_dir = db_dir.join(CONSENSUS_DB);
+    // The cold data directory mirrors the layout of the main one
+    let cold_db_dir = args.cold_datadir.as_ref().map(|cold_datadir| {
+        PathBuf::from(cold_datadir)
+            .join(network.to_prefixed())
+            .join(DEFAULT_DATA_DIR)
+    });
+    let cold_consensus_db_dir = cold_db_dir
+        .as_ref()
+        .map(|cold_db_dir| cold_db_dir.join(CONSENSUS_DB));
     let utxoindex_db_dir = db_dir.join(UTXOINDEX_DB);
+    let blockfilterindex_db_dir = db_dir.join(BLOCKFILTERINDEX_DB);
+    let acceptancejournal_db_dir = db_dir.join(ACCEPTANCEJOURNAL_DB);
+    #[cfg(feature = "analytics")]
+    let addressclusterindex_db_dir = db_dir.join(ADDRESSCLUSTERINDEX_DB);
     let meta_db_dir = db_dir.join(META_DB);
 
     let mut is_db_reset_needed = args.reset_db;
@@ -284,14 +401,47 @@ do you confirm? (answer y/n or pass --yes to the Karlsend command line to confir
         get_user_approval_or_exit(msg, args.yes);
         info!("Deleting databases");
         fs::remove_dir_all(&db_dir).unwrap();
+        if let Some(cold_db_dir) = cold_db_dir.as_ref().filter(|dir| dir.exists()) {
+            fs::remove_dir_all(cold_db_dir).unwrap();
+        }
     }
 
     fs::create_dir_all(consensus_db_dir.as_path()).unwrap();
+    if let Some(cold_consensus_db_dir) = cold_consensus_db_dir.as_ref() {
+        info!(
+            "Cold data directory {}, receiving the block data older than {} hours",
+            cold_consensus_db_dir.display(),
+            args.cold_storage_age
+        );
+        fs::create_dir_all(cold_consensus_db_dir.as_path()).unwrap();
+    }
     fs::create_dir_all(meta_db_dir.as_path()).unwrap();
     if args.utxoindex {
         info!("Utxoindex Data directory {}", utxoindex_db_dir.display());
         fs::create_dir_all(utxoindex_db_dir.as_path()).unwrap();
     }
+    if args.blockfilterindex {
+        info!(
+            "Block filter index Data directory {}",
+            blockfilterindex_db_dir.display()
+        );
+        fs::create_dir_all(blockfilterindex_db_dir.as_path()).unwrap();
+    }
+    if args.acceptance_journal_size > 0 {
+        info!(
+            "Acceptance journal Data directory {}",
+            acceptancejournal_db_dir.display()
+        );
+        fs::create_dir_all(acceptancejournal_db_dir.as_path()).unwrap();
+    }
+    #[cfg(feature = "analytics")]
+    if args.addressclusterindex {
+        info!(
+            "Address cluster index Data directory {}",
+            addressclusterindex_db_dir.display()
+        );
+        fs::create_dir_all(addressclusterindex_db_dir.as_path()).unwrap();
+    }
 
     // DB used for addresses store and for multi-consensus management
     let mut meta_db = karlsen_database::prelude::ConnBuilder::default()
@@ -362,14 +512,30 @@ do you confirm? (answer y/n or pass --yes to the Karlsend command line to confir
 
         // Delete
         fs::remove_dir_all(db_dir.clone()).unwrap();
+        if let Some(cold_db_dir) = cold_db_dir.as_ref().filter(|dir| dir.exists()) {
+            fs::remove_dir_all(cold_db_dir).unwrap();
+        }
 
         // Recreate the empty folders
         fs::create_dir_all(consensus_db_dir.as_path()).unwrap();
+        if let Some(cold_consensus_db_dir) = cold_consensus_db_dir.as_ref() {
+            fs::create_dir_all(cold_consensus_db_dir.as_path()).unwrap();
+        }
         fs::create_dir_all(meta_db_dir.as_path()).unwrap();
 
         if args.utxoindex {
             fs::create_dir_all(utxoindex_db_dir.as_path()).unwrap();
         }
+        if args.blockfilterindex {
+            fs::create_dir_all(blockfilterindex_db_dir.as_path()).unwrap();
+        }
+        if args.acceptance_journal_size > 0 {
+            fs::create_dir_all(acceptancejournal_db_dir.as_path()).unwrap();
+        }
+        #[cfg(feature = "analytics")]
+        if args.addressclusterindex {
+            fs::create_dir_all(addressclusterindex_db_dir.as_path()).unwrap();
+        }
 
         // Reopen the DB
         meta_db = karlsen_database::prelude::ConnBuilder::default()
@@ -387,6 +553,15 @@ do you confirm? (answer y/n or pass --yes to the Karlsend command line to confir
         get_user_approval_or_exit("--archival is set to false although the node was previously archival. Proceeding may delete archived data. Do you confirm? (y/n)", args.yes);
     }
 
+    if args.cold_datadir.is_none()
+        && MultiConsensusManagementStore::new(meta_db.clone())
+            .is_cold_db_required()
+            .unwrap()
+    {
+        println!("--colddatadir is not set although the node previously migrated data to a cold DB, which is required to read it. Restart with the same --colddatadir, or with --reset-db to start over, exiting..");
+        exit(1);
+    }
+
     let connect_peers = args
         .connect_peers
         .iter()
@@ -429,7 +604,8 @@ do you confirm? (answer y/n or pass --yes to the Karlsend command line to confir
     } else {
         None
     };
-    let subscription_context = SubscriptionContext::with_options(max_tracked_addresses);
+    let subscription_context =
+        SubscriptionContext::with_address_prefix(max_tracked_addresses, network.into());
     let notification_root = Arc::new(ConsensusNotificationRoot::with_context(
         notification_send,
         subscription_context.clone(),
@@ -448,6 +624,7 @@ do you confirm? (answer y/n or pass --yes to the Karlsend command line to confir
         meta_db.clone(),
         &config,
         consensus_db_dir,
+        cold_consensus_db_dir,
         consensus_db_parallelism,
         notification_root.clone(),
         processing_counters.clone(),
@@ -463,29 +640,49 @@ do you confirm? (answer y/n or pass --yes to the Karlsend command line to confir
     let perf_monitor_builder = PerfMonitorBuilder::new()
         .with_fetch_interval(Duration::from_secs(args.perf_metrics_interval_sec))
         .with_tick_service(tick_service.clone());
-    let perf_monitor = if args.perf_metrics {
-        let cb = move |counters: CountersSnapshot| {
-            trace!(
-                "[{}] {}",
-                karlsen_perf_monitor::SERVICE_NAME,
-                counters.to_process_metrics_display()
-            );
-            trace!(
-                "[{}] {}",
-                karlsen_perf_monitor::SERVICE_NAME,
-                counters.to_io_metrics_display()
+    // The memory budget, if enabled, is enforced on every perf monitor fetch
+    memory_budget().set_limit(args.memory_limit * 1_000_000);
+    if args.memory_limit > 0 {
+        info!("Memory limit: {} MB", args.memory_limit);
+    }
+    let perf_metrics = args.perf_metrics;
+    let cb = move |counters: CountersSnapshot| {
+        let released = memory_budget().enforce(counters.resident_set_size as usize);
+        if released > 0 {
+            info!(
+                "Released ~{} MB of caches and pools under memory pressure",
+                released / 1_000_000
             );
-            #[cfg(feature = "heap")]
+        }
+        if !perf_metrics {
+            return;
+        }
+        trace!(
+            "[{}] {}",
+            karlsen_perf_monitor::SERVICE_NAME,
+            counters.to_process_metrics_display()
+        );
+        trace!(
+            "[{}] {}",
+            karlsen_perf_monitor::SERVICE_NAME,
+            counters.to_io_metrics_display()
+        );
+        for usage in memory_budget().snapshot() {
             trace!(
-                "[{}] heap stats: {:?}",
+                "[{}] memory usage of {}: {} MB",
                 karlsen_perf_monitor::SERVICE_NAME,
-                dhat::HeapStats::get()
+                usage.name,
+                usage.usage / 1_000_000
             );
-        };
-        Arc::new(perf_monitor_builder.with_fetch_cb(cb).build())
-    } else {
-        Arc::new(perf_monitor_builder.build())
+        }
+        #[cfg(feature = "heap")]
+        trace!(
+            "[{}] heap stats: {:?}",
+            karlsen_perf_monitor::SERVICE_NAME,
+            dhat::HeapStats::get()
+        );
     };
+    let perf_monitor = Arc::new(perf_monitor_builder.with_fetch_cb(cb).build());
 
     let notify_service = Arc::new(NotifyService::new(
         notification_root.clone(),
@@ -510,7 +707,71 @@ do you confirm? (answer y/n or pass --yes to the Karlsend command line to confir
     } else {
         None
     };
+    let utxoindex_consistency_service = index_service.as_ref().map(|index_service| {
+        Arc::new(UtxoIndexConsistencyService::new(
+            index_service.utxoindex().unwrap(),
+            tick_service.clone(),
+        ))
+    });
+    let block_filter_index_service: Option<Arc<BlockFilterIndexService>> = if args.blockfilterindex
+    {
+        let blockfilterindex_db = karlsen_database::prelude::ConnBuilder::default()
+            .with_db_path(blockfilterindex_db_dir)
+            .with_files_limit(filter_files_limit)
+            .build()
+            .unwrap();
+        let blockfilterindex =
+            BlockFilterIndex::new(consensus_manager.clone(), blockfilterindex_db);
+        Some(Arc::new(BlockFilterIndexService::new(
+            &notify_service.notifier(),
+            blockfilterindex,
+        )))
+    } else {
+        None
+    };
+    let acceptance_journal_service: Option<Arc<AcceptanceJournalService>> =
+        if args.acceptance_journal_size > 0 {
+            let acceptancejournal_db = karlsen_database::prelude::ConnBuilder::default()
+                .with_db_path(acceptancejournal_db_dir)
+                .with_files_limit(journal_files_limit)
+                .build()
+                .unwrap();
+            let acceptance_journal = AcceptanceJournal::new(
+                consensus_manager.clone(),
+                acceptancejournal_db,
+                args.acceptance_journal_size,
+            );
+            Some(Arc::new(AcceptanceJournalService::new(
+                &notify_service.notifier(),
+                acceptance_journal,
+            )))
+        } else {
+            None
+        };
+    #[cfg(feature = "analytics")]
+    let address_cluster_index_service: Option<Arc<AddressClusterIndexService>> =
+        if args.addressclusterindex {
+            let addressclusterindex_db = karlsen_database::prelude::ConnBuilder::default()
+                .with_db_path(addressclusterindex_db_dir)
+                .with_files_limit(cluster_files_limit)
+                .build()
+                .unwrap();
+            let address_cluster_index =
+                AddressClusterIndex::new(consensus_manager.clone(), addressclusterindex_db);
+            Some(Arc::new(AddressClusterIndexService::new(
+                &notify_service.notifier(),
+                address_cluster_index,
+            )))
+        } else {
+            None
+        };
+    #[cfg(feature = "analytics")]
+    let address_cluster_index = address_cluster_index_service.as_ref().map(|x| x.index());
+    #[cfg(not(feature = "analytics"))]
+    let address_cluster_index = None;
 
+    // The metadata of the external indexers lives in the meta DB, surviving consensus resets
+    let metadata_store = Arc::new(MetadataStore::new(meta_db.clone()));
     let (address_manager, port_mapping_extender_svc) =
         AddressManager::new(config.clone(), meta_db, tick_service.clone());
 
@@ -525,6 +786,7 @@ do you confirm? (answer y/n or pass --yes to the Karlsend command line to confir
             false,
             config.max_block_mass,
             config.ram_scale,
+            &args.relay_policy(),
             config.block_template_cache_lifetime,
             mining_counters,
         )));
@@ -537,6 +799,34 @@ do you confirm? (answer y/n or pass --yes to the Karlsend command line to confir
         tick_service.clone(),
         notification_root,
     ));
+    flow_context.set_clock_skew_threshold(args.clock_skew_threshold);
+    if let Some(snapshot_url) = args.snapshot_url.as_deref() {
+        let publisher = args.snapshot_publisher.as_deref().map(|publisher| {
+            parse_public_key(publisher).expect("checked by the arguments validation")
+        });
+        match SnapshotSource::new(snapshot_url, network, publisher) {
+            Ok(snapshot_source) => {
+                info!(
+                    "IBD bootstraps from the snapshots served at {}",
+                    snapshot_url
+                );
+                flow_context.set_snapshot_source(snapshot_source);
+            }
+            Err(err) => warn!(
+                "Cannot bootstrap from the snapshots served at {}: {}",
+                snapshot_url, err
+            ),
+        }
+    }
+    // The snapshots are kept next to the data directory, so a database reset does not discard
+    // the ones served to other nodes
+    let snapshot_service = args.snapshot_listen.map(|snapshot_listen| {
+        let snapshot_dir = app_dir.join(network.to_prefixed()).join(SNAPSHOT_DIR);
+        let producer = SnapshotProducer::new(consensus_manager.clone(), &config, snapshot_dir)
+            .unwrap_or_else(|err| panic!("Cannot create the snapshot directory: {err}"));
+        info!("Snapshot publisher key: {}", producer.public_key());
+        Arc::new(SnapshotService::new(Arc::new(producer), snapshot_listen))
+    });
     let p2p_service = Arc::new(P2pService::new(
         flow_context.clone(),
         connect_peers,
@@ -549,7 +839,23 @@ do you confirm? (answer y/n or pass --yes to the Karlsend command line to confir
         p2p_tower_counters.clone(),
     ));
 
-    let rpc_core_service = Arc::new(RpcCoreService::new(
+    let rpc_config = RpcCoreConfig {
+        workers: args.rpc_workers,
+        power_cost: args
+            .miner_power_watts
+            .zip(args.power_price)
+            .map(|(watts, price_per_kwh)| PowerCost {
+                watts,
+                price_per_kwh,
+            }),
+        max_response_size: args.rpc_max_response_size,
+        max_previous_outputs_reads: args.rpc_max_previous_outputs_reads,
+        work_binding_secret: args
+            .work_binding_secret
+            .as_ref()
+            .map(|secret| secret.as_bytes().to_vec()),
+    };
+    let rpc_core_service = RpcCoreService::new(
         consensus_manager.clone(),
         notify_service.notifier(),
         index_service.as_ref().map(|x| x.notifier()),
@@ -557,7 +863,12 @@ do you confirm? (answer y/n or pass --yes to the Karlsend command line to confir
         flow_context,
         subscription_context,
         index_service.as_ref().map(|x| x.utxoindex().unwrap()),
+        block_filter_index_service.as_ref().map(|x| x.index()),
+        acceptance_journal_service.as_ref().map(|x| x.journal()),
+        address_cluster_index,
+        metadata_store,
         config.clone(),
+        rpc_config,
         core.clone(),
         processing_counters,
         wrpc_borsh_counters.clone(),
@@ -565,20 +876,52 @@ do you confirm? (answer y/n or pass --yes to the Karlsend command line to confir
         perf_monitor.clone(),
         p2p_tower_counters.clone(),
         grpc_tower_counters.clone(),
+    );
+    // The listeners configured by --rpclisten* serve the full API while the public ones are
+    // restricted to the read-only methods and rate limited
+    let full_rpc_policy = Arc::new(RpcPolicy::unrestricted());
+    let public_rpc_policy = Arc::new(RpcPolicy::public(
+        args.rpc_public_rate_limit,
+        &args
+            .rpc_public_allow
+            .iter()
+            .filter_map(|method| parse_method(method))
+            .collect::<Vec<_>>(),
     ));
     let grpc_service_broadcasters: usize = 3; // TODO: add a command line argument or derive from other arg/config/host-related fields
-    let grpc_service = if !args.disable_grpc {
-        Some(Arc::new(GrpcService::new(
-            grpc_server_addr,
-            config,
-            rpc_core_service.clone(),
-            args.rpc_max_clients,
-            grpc_service_broadcasters,
-            grpc_tower_counters,
-        )))
+    let grpc_services = if !args.disable_grpc {
+        let public_grpc_server_addr = args
+            .rpclisten_public
+            .map(|address| address.normalize(config.default_rpc_port()));
+        [
+            Some((grpc_server_addr, full_rpc_policy.clone())),
+            public_grpc_server_addr.map(|address| (address, public_rpc_policy.clone())),
+        ]
+        .into_iter()
+        .flatten()
+        .map(|(address, policy)| {
+            Arc::new(GrpcService::new(
+                address,
+                config.clone(),
+                rpc_core_service.clone(),
+                args.rpc_max_clients,
+                grpc_service_broadcasters,
+                grpc_tower_counters.clone(),
+                policy,
+            ))
+        })
+        .collect()
     } else {
-        None
+        vec![]
     };
+    let webhook_service = webhook_config.map(|webhook_config| {
+        Arc::new(WebhookService::new(
+            webhook_config,
+            rpc_core_service.clone(),
+            consensus_manager.clone(),
+            network.to_string(),
+        ))
+    });
 
     // Create an async runtime and register the top-level async services
     let async_runtime = Arc::new(AsyncRuntime::new(args.async_threads));
@@ -587,49 +930,103 @@ do you confirm? (answer y/n or pass --yes to the Karlsend command line to confir
     if let Some(index_service) = index_service {
         async_runtime.register(index_service)
     };
+    if let Some(utxoindex_consistency_service) = utxoindex_consistency_service {
+        async_runtime.register(utxoindex_consistency_service)
+    };
+    if let Some(block_filter_index_service) = block_filter_index_service {
+        async_runtime.register(block_filter_index_service)
+    };
+    if let Some(acceptance_journal_service) = acceptance_journal_service {
+        async_runtime.register(acceptance_journal_service)
+    };
+    #[cfg(feature = "analytics")]
+    if let Some(address_cluster_index_service) = address_cluster_index_service {
+        async_runtime.register(address_cluster_index_service)
+    };
     if let Some(port_mapping_extender_svc) = port_mapping_extender_svc {
         async_runtime.register(Arc::new(port_mapping_extender_svc))
     };
     async_runtime.register(rpc_core_service.clone());
-    if let Some(grpc_service) = grpc_service {
+    for grpc_service in grpc_services {
         async_runtime.register(grpc_service)
     }
+    if let Some(webhook_service) = webhook_service {
+        async_runtime.register(webhook_service)
+    }
+    if let Some(snapshot_service) = snapshot_service {
+        async_runtime.register(snapshot_service)
+    }
     async_runtime.register(p2p_service);
     async_runtime.register(consensus_monitor);
     async_runtime.register(mining_monitor);
     async_runtime.register(perf_monitor);
+    let durable_subscriptions = (args.wrpc_durable_retention > 0
+        && (args.rpclisten_borsh.is_some() || args.rpclisten_json.is_some()))
+    .then(|| {
+        Arc::new(DurableSubscriptions::new(
+            rpc_core_service.clone(),
+            args.wrpc_durable_retention,
+            Some(db_dir.join(DURABLE_SUBSCRIPTIONS_FILE)),
+        ))
+    });
+    if let Some(durable_subscriptions) = durable_subscriptions.clone() {
+        async_runtime.register(durable_subscriptions)
+    }
     let wrpc_service_tasks: usize = 2; // num_cpus::get() / 2;
                                        // Register wRPC servers based on command line arguments
     [
         (
             args.rpclisten_borsh.clone(),
+            WrpcListener::Borsh,
             WrpcEncoding::Borsh,
-            wrpc_borsh_counters,
+            wrpc_borsh_counters.clone(),
+            full_rpc_policy.clone(),
         ),
         (
             args.rpclisten_json.clone(),
+            WrpcListener::Json,
+            WrpcEncoding::SerdeJson,
+            wrpc_json_counters.clone(),
+            full_rpc_policy,
+        ),
+        (
+            args.rpclisten_public_borsh.clone(),
+            WrpcListener::PublicBorsh,
+            WrpcEncoding::Borsh,
+            wrpc_borsh_counters,
+            public_rpc_policy.clone(),
+        ),
+        (
+            args.rpclisten_public_json.clone(),
+            WrpcListener::PublicJson,
             WrpcEncoding::SerdeJson,
             wrpc_json_counters,
+            public_rpc_policy,
         ),
     ]
     .into_iter()
-    .filter_map(|(listen_address, encoding, wrpc_server_counters)| {
-        listen_address.map(|listen_address| {
-            Arc::new(WrpcService::new(
-                wrpc_service_tasks,
-                Some(rpc_core_service.clone()),
-                &encoding,
-                wrpc_server_counters,
-                WrpcServerOptions {
-                    listen_address: listen_address
-                        .to_address(&network.network_type, &encoding)
-                        .to_string(), // TODO: use a normalized ContextualNetAddress instead of a String
-                    verbose: args.wrpc_verbose,
-                    ..WrpcServerOptions::default()
-                },
-            ))
-        })
-    })
+    .filter_map(
+        |(listen_address, listener, encoding, wrpc_server_counters, policy)| {
+            listen_address.map(|listen_address| {
+                Arc::new(WrpcService::new(
+                    wrpc_service_tasks,
+                    Some(rpc_core_service.clone()),
+                    &encoding,
+                    wrpc_server_counters,
+                    WrpcServerOptions {
+                        listen_address: listen_address
+                            .to_address(&network.network_type, &encoding)
+                            .to_string(), // TODO: use a normalized ContextualNetAddress instead of a String
+                        verbose: args.wrpc_verbose,
+                        durable_subscriptions: durable_subscriptions.clone(),
+                        policy,
+                        notification_queue: args.notification_queue(listener),
+                        ..WrpcServerOptions::default()
+                    },
+                ))
+            })
+        },
+    )
     .for_each(|server| async_runtime.register(server));
 
     // Consensus must start first in order to init genesis in stores
@@ -108,6 +108,9 @@ pub enum NonStandardError {
     #[error("transaction mass in context (including storage mass) of {1} is larger than max allowed size of {2}")]
     RejectContextualMass(TransactionId, u64, u64),
 
+    #[error("transaction storage mass of {1} weighted by the relay policy factor of {2} is larger than max allowed size of {3}")]
+    RejectStorageMass(TransactionId, u64, f64, u64),
+
     #[error("transaction input #{1}: signature script size of {2} bytes is larger than the maximum allowed size of {3} bytes")]
     RejectSignatureScriptSize(TransactionId, usize, u64, u64),
 
@@ -117,8 +120,10 @@ pub enum NonStandardError {
     #[error("transaction output #{1}: non-standard script form")]
     RejectOutputScriptClass(TransactionId, usize),
 
-    #[error("transaction output #{1}: payment of {2} is dust")]
-    RejectDust(TransactionId, usize, u64),
+    #[error(
+        "transaction output #{1}: payment of {2} is dust, the relay policy requires at least {3}"
+    )]
+    RejectDust(TransactionId, usize, u64, u64),
 
     #[error("transaction input {1}: non-standard script form")]
     RejectInputScriptClass(TransactionId, usize),
@@ -127,7 +132,7 @@ pub enum NonStandardError {
     RejectInsufficientFee(TransactionId, u64, u64),
 
     #[error("transaction input #{1} has {2} signature operations which is more than the allowed max amount of {3}")]
-    RejectSignatureCount(TransactionId, usize, u8, u8),
+    RejectSignatureCount(TransactionId, usize, u64, u64),
 }
 
 impl NonStandardError {
@@ -136,10 +141,11 @@ impl NonStandardError {
             NonStandardError::RejectVersion(id, _, _, _) => id,
             NonStandardError::RejectMass(id, _, _) => id,
             NonStandardError::RejectContextualMass(id, _, _) => id,
+            NonStandardError::RejectStorageMass(id, _, _, _) => id,
             NonStandardError::RejectSignatureScriptSize(id, _, _, _) => id,
             NonStandardError::RejectScriptPublicKeyVersion(id, _) => id,
             NonStandardError::RejectOutputScriptClass(id, _) => id,
-            NonStandardError::RejectDust(id, _, _) => id,
+            NonStandardError::RejectDust(id, _, _, _) => id,
             NonStandardError::RejectInputScriptClass(id, _) => id,
             NonStandardError::RejectInsufficientFee(id, _, _) => id,
             NonStandardError::RejectSignatureCount(id, _, _, _) => id,
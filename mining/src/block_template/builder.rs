@@ -1,7 +1,5 @@
-use super::{errors::BuilderResult, policy::Policy};
-use crate::{
-    block_template::selector::TransactionsSelector, model::candidate_tx::CandidateTransaction,
-};
+use super::{errors::BuilderResult, provider::TemplateProvider};
+use crate::model::candidate_tx::CandidateTransaction;
 use karlsen_consensus_core::{
     api::ConsensusApi,
     block::{BlockTemplate, TemplateBuildMode},
@@ -13,15 +11,19 @@ use karlsen_core::{
     debug,
     time::{unix_now, Stopwatch},
 };
+use std::sync::Arc;
 
 pub(crate) struct BlockTemplateBuilder {
-    policy: Policy,
+    max_block_mass: u64,
+    provider: Arc<dyn TemplateProvider>,
 }
 
 impl BlockTemplateBuilder {
-    pub(crate) fn new(max_block_mass: u64) -> Self {
-        let policy = Policy::new(max_block_mass);
-        Self { policy }
+    pub(crate) fn new(max_block_mass: u64, provider: Arc<dyn TemplateProvider>) -> Self {
+        Self {
+            max_block_mass,
+            provider,
+        }
     }
 
     /// BuildBlockTemplate creates a block template for a miner to consume
@@ -96,10 +98,13 @@ impl BlockTemplateBuilder {
     ) -> BuilderResult<BlockTemplate> {
         let _sw = Stopwatch::<20>::with_threshold("build_block_template op");
         debug!(
-            "Considering {} transactions for a new block template",
-            transactions.len()
+            "Considering {} transactions for a new block template with the {} selection policy",
+            transactions.len(),
+            self.provider.name()
         );
-        let selector = Box::new(TransactionsSelector::new(self.policy.clone(), transactions));
+        let selector = self
+            .provider
+            .selector(self.max_block_mass, miner_data, transactions);
         Ok(consensus.build_block_template(miner_data.clone(), selector, build_mode)?)
     }
 
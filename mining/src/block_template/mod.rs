@@ -2,4 +2,5 @@ pub(crate) mod builder;
 pub(crate) mod errors;
 mod model;
 pub(crate) mod policy;
+pub mod provider;
 pub(crate) mod selector;
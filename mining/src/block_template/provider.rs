@@ -0,0 +1,89 @@
+use super::{policy::Policy, selector::TransactionsSelector};
+use crate::model::candidate_tx::CandidateTransaction;
+use karlsen_consensus_core::{block::TemplateTransactionSelector, coinbase::MinerData};
+use std::sync::Arc;
+
+/// Provides the transaction selection policy of the block templates.
+///
+/// The mining manager hands the mempool candidate transactions to its provider on every
+/// template build, so operators can plug their own policy with
+/// [`MiningManager::set_template_provider`](crate::manager::MiningManager::set_template_provider)
+/// rather than forking the template code.
+pub trait TemplateProvider: Send + Sync {
+    /// Name of the policy, for logging purposes
+    fn name(&self) -> &'static str;
+
+    /// Returns a selector of the transactions of a template paying to `miner_data`, the
+    /// block mass of the selected transactions being bounded by `max_block_mass`
+    fn selector(
+        &self,
+        max_block_mass: u64,
+        miner_data: &MinerData,
+        transactions: Vec<CandidateTransaction>,
+    ) -> Box<dyn TemplateTransactionSelector>;
+}
+
+/// The default policy, selecting transactions randomly with a probability
+/// growing with their fee rate
+#[derive(Default)]
+pub struct DefaultTemplateProvider;
+
+impl TemplateProvider for DefaultTemplateProvider {
+    fn name(&self) -> &'static str {
+        "default"
+    }
+
+    fn selector(
+        &self,
+        max_block_mass: u64,
+        _miner_data: &MinerData,
+        transactions: Vec<CandidateTransaction>,
+    ) -> Box<dyn TemplateTransactionSelector> {
+        Box::new(TransactionsSelector::new(
+            Policy::new(max_block_mass),
+            transactions,
+        ))
+    }
+}
+
+/// A policy filtering the candidate transactions before handing them to another policy,
+/// ie. for excluding some script classes or rate limiting some addresses
+pub struct FilteredTemplateProvider<F> {
+    name: &'static str,
+    filter: F,
+    inner: Arc<dyn TemplateProvider>,
+}
+
+impl<F> FilteredTemplateProvider<F>
+where
+    F: Fn(&MinerData, &CandidateTransaction) -> bool + Send + Sync,
+{
+    /// Creates a provider keeping the candidate transactions for which `filter` returns true
+    pub fn new(name: &'static str, filter: F, inner: Arc<dyn TemplateProvider>) -> Self {
+        Self {
+            name,
+            filter,
+            inner,
+        }
+    }
+}
+
+impl<F> TemplateProvider for FilteredTemplateProvider<F>
+where
+    F: Fn(&MinerData, &CandidateTransaction) -> bool + Send + Sync,
+{
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    fn selector(
+        &self,
+        max_block_mass: u64,
+        miner_data: &MinerData,
+        mut transactions: Vec<CandidateTransaction>,
+    ) -> Box<dyn TemplateTransactionSelector> {
+        transactions.retain(|tx| (self.filter)(miner_data, tx));
+        self.inner
+            .selector(max_block_mass, miner_data, transactions)
+    }
+}
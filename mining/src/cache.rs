@@ -63,7 +63,6 @@ impl BlockTemplateCache {
         }
     }
 
-    #[cfg(test)]
     pub(crate) fn clear(&self) {
         self.inner.lock().clear();
     }
@@ -5,7 +5,7 @@ use std::{
 
 use mempool::tx::Priority;
 
-mod block_template;
+pub mod block_template;
 pub(crate) mod cache;
 pub mod errors;
 pub mod manager;
@@ -1,9 +1,13 @@
 use crate::{
-    block_template::{builder::BlockTemplateBuilder, errors::BuilderError},
+    block_template::{
+        builder::BlockTemplateBuilder,
+        errors::BuilderError,
+        provider::{DefaultTemplateProvider, TemplateProvider},
+    },
     cache::BlockTemplateCache,
     errors::MiningManagerResult,
     mempool::{
-        config::Config,
+        config::{Config, RelayPolicy},
         model::tx::{MempoolTransaction, TxRemovalReason},
         populate_entries_and_try_validate::{
             populate_mempool_transactions_in_parallel, validate_mempool_transaction,
@@ -14,6 +18,7 @@ use crate::{
     },
     model::{
         candidate_tx::CandidateTransaction,
+        double_spend::{DoubleSpend, DoubleSpendReporter},
         owner_txs::{GroupedOwnerTransactions, ScriptPublicKeySet},
         topological_sort::IntoIterTopologically,
         tx_query::TransactionQuery,
@@ -40,6 +45,8 @@ pub struct MiningManager {
     block_template_cache: BlockTemplateCache,
     mempool: RwLock<Mempool>,
     counters: Arc<MiningCounters>,
+    template_provider: RwLock<Arc<dyn TemplateProvider>>,
+    double_spend_reporter: DoubleSpendReporter,
 }
 
 impl MiningManager {
@@ -63,6 +70,7 @@ impl MiningManager {
         relay_non_std_transactions: bool,
         max_block_mass: u64,
         ram_scale: f64,
+        relay_policy: &RelayPolicy,
         cache_lifetime: Option<u64>,
         counters: Arc<MiningCounters>,
     ) -> Self {
@@ -71,7 +79,8 @@ impl MiningManager {
             relay_non_std_transactions,
             max_block_mass,
         )
-        .apply_ram_scale(ram_scale);
+        .apply_ram_scale(ram_scale)
+        .apply_relay_policy(relay_policy);
         Self::with_config(config, cache_lifetime, counters)
     }
 
@@ -88,6 +97,60 @@ impl MiningManager {
             block_template_cache,
             mempool,
             counters,
+            template_provider: RwLock::new(Arc::new(DefaultTemplateProvider)),
+            double_spend_reporter: DoubleSpendReporter::default(),
+        }
+    }
+
+    /// Replaces the transaction selection policy of the block templates. The cached
+    /// template, if any, is discarded.
+    pub fn set_template_provider(&self, provider: Arc<dyn TemplateProvider>) {
+        info!(
+            "Block templates transaction selection policy: {}",
+            provider.name()
+        );
+        *self.template_provider.write() = provider;
+        self.block_template_cache.clear();
+    }
+
+    pub fn template_provider(&self) -> Arc<dyn TemplateProvider> {
+        self.template_provider.read().clone()
+    }
+
+    /// Sets the subscriber of the double spends detected among the transactions submitted to the
+    /// mempool, relayed by peers or accepted by new blocks. Double spends are not detected until
+    /// a subscriber is set.
+    pub fn set_double_spend_sender(&self, sender: UnboundedSender<DoubleSpend>) {
+        self.double_spend_reporter.set_sender(sender);
+    }
+
+    /// Reports the conflicts of an incoming transaction with the mempool and the recently
+    /// accepted transactions
+    fn report_double_spends(&self, mempool: &Mempool, transaction: &Transaction) {
+        if self.double_spend_reporter.is_enabled() {
+            self.double_spend_reporter
+                .report(mempool.find_double_spends(transaction));
+        }
+    }
+
+    /// Reports the conflicts of a transaction the mempool rejected before validating it because
+    /// it double spends mempool transactions, provided the transaction is otherwise valid, so
+    /// that an invalid transaction cannot raise a double spend alert
+    fn report_validated_double_spends(
+        &self,
+        consensus: &dyn ConsensusApi,
+        transaction: MutableTransaction,
+    ) {
+        let pre_validation = self
+            .mempool
+            .read()
+            .pre_validate_and_populate_double_spend(consensus, transaction);
+        let Ok(mut transaction) = pre_validation else {
+            return;
+        };
+        // no lock on mempool
+        if validate_mempool_transaction(consensus, &mut transaction).is_ok() {
+            self.report_double_spends(&self.mempool.read(), &transaction.tx);
         }
     }
 
@@ -130,8 +193,10 @@ impl MiningManager {
             attempts += 1;
 
             let transactions = self.block_candidate_transactions();
-            let block_template_builder =
-                BlockTemplateBuilder::new(self.config.maximum_mass_per_block);
+            let block_template_builder = BlockTemplateBuilder::new(
+                self.config.maximum_mass_per_block,
+                self.template_provider(),
+            );
             let build_mode = if attempts < self.config.maximum_build_block_template_attempts {
                 TemplateBuildMode::Standard
             } else {
@@ -239,7 +304,7 @@ impl MiningManager {
 
     #[cfg(test)]
     pub(crate) fn block_template_builder(&self) -> BlockTemplateBuilder {
-        BlockTemplateBuilder::new(self.config.maximum_mass_per_block)
+        BlockTemplateBuilder::new(self.config.maximum_mass_per_block, self.template_provider())
     }
 
     /// validate_and_insert_transaction validates the given transaction, and
@@ -270,22 +335,41 @@ impl MiningManager {
         priority: Priority,
         orphan: Orphan,
     ) -> MiningManagerResult<Vec<Arc<Transaction>>> {
+        let candidate = self
+            .double_spend_reporter
+            .is_enabled()
+            .then(|| transaction.clone());
         // read lock on mempool
-        let mut transaction = self
+        let pre_validation = self
             .mempool
             .read()
-            .pre_validate_and_populate_transaction(consensus, transaction)?;
+            .pre_validate_and_populate_transaction(consensus, transaction);
+        let mut transaction = match (pre_validation, candidate) {
+            (Err(err @ RuleError::RejectDoubleSpendInMempool(..)), Some(candidate)) => {
+                self.report_validated_double_spends(consensus, candidate);
+                return Err(err.into());
+            }
+            (pre_validation, _) => pre_validation?,
+        };
         // no lock on mempool
         let validation_result = validate_mempool_transaction(consensus, &mut transaction);
+        let validated = validation_result.is_ok();
         // write lock on mempool
         let mut mempool = self.mempool.write();
-        if let Some(accepted_transaction) = mempool.post_validate_and_insert_transaction(
+        let tx = transaction.tx.clone();
+        let insert_result = mempool.post_validate_and_insert_transaction(
             consensus,
             validation_result,
             transaction,
             priority,
             orphan,
-        )? {
+        );
+        if let (true, Err(RuleError::RejectDoubleSpendInMempool(..))) = (validated, &insert_result)
+        {
+            // The conflicting transaction got inserted while this one was being validated
+            self.report_double_spends(&mempool, &tx);
+        }
+        if let Some(accepted_transaction) = insert_result? {
             let unorphaned_transactions = mempool
                 .get_unorphaned_transactions_after_accepted_transaction(&accepted_transaction);
             drop(mempool);
@@ -308,6 +392,35 @@ impl MiningManager {
         }
     }
 
+    /// Runs the full mempool validation of a transaction without inserting it into the mempool.
+    ///
+    /// Returns the transaction, populated with its UTXO entries, fee and compute mass as far as
+    /// the validation went, along with the validation outcome.
+    pub fn test_mempool_accept(
+        &self,
+        consensus: &dyn ConsensusApi,
+        transaction: MutableTransaction,
+    ) -> (MutableTransaction, MiningManagerResult<()>) {
+        // read lock on mempool
+        let pre_validation_result = self
+            .mempool
+            .read()
+            .pre_validate_and_populate_transaction(consensus, transaction.clone());
+        let mut transaction = match pre_validation_result {
+            Ok(transaction) => transaction,
+            Err(err) => return (transaction, Err(err.into())),
+        };
+        // no lock on mempool
+        let validation_result = validate_mempool_transaction(consensus, &mut transaction);
+        // read lock on mempool
+        let result = self
+            .mempool
+            .read()
+            .post_validate_transaction(validation_result, &transaction)
+            .map_err(MiningManagerError::from);
+        (transaction, result)
+    }
+
     fn validate_and_insert_unorphaned_transactions(
         &self,
         consensus: &dyn ConsensusApi,
@@ -414,10 +527,12 @@ impl MiningManager {
         // read lock on mempool
         // Here, we simply log and drop all erroneous transactions since the caller doesn't care about those anyway
         let mut transactions = Vec::with_capacity(sorted_transactions.len());
+        let mut double_spends = vec![];
         for chunk in &sorted_transactions.chunks(TRANSACTION_CHUNK_SIZE) {
             let mempool = self.mempool.read();
             let txs = chunk.filter_map(|tx| {
                 let transaction_id = tx.id();
+                let candidate = self.double_spend_reporter.is_enabled().then(|| tx.clone());
                 match mempool.pre_validate_and_populate_transaction(consensus, tx) {
                     Ok(tx) => Some(tx),
                     Err(RuleError::RejectAlreadyAccepted(transaction_id)) => {
@@ -443,6 +558,11 @@ impl MiningManager {
                             "Failed to pre validate transaction {0} due to rule error: {1}",
                             transaction_id, err
                         );
+                        if let (RuleError::RejectDoubleSpendInMempool(..), Some(candidate)) =
+                            (&err, candidate)
+                        {
+                            double_spends.push(candidate);
+                        }
                         insert_results.push(Err(MiningManagerError::MempoolError(err)));
                         None
                     }
@@ -450,6 +570,9 @@ impl MiningManager {
             });
             transactions.extend(txs);
         }
+        for candidate in double_spends {
+            self.report_validated_double_spends(consensus, candidate);
+        }
 
         // no lock on mempool
         // We process the transactions by chunks of max block mass to prevent locking the virtual processor for too long.
@@ -481,6 +604,8 @@ impl MiningManager {
             let mut mempool = self.mempool.write();
             let txs = chunk.flat_map(|(transaction, validation_result)| {
                 let transaction_id = transaction.id();
+                let tx = transaction.tx.clone();
+                let validated = validation_result.is_ok();
                 match mempool.post_validate_and_insert_transaction(
                     consensus,
                     validation_result,
@@ -504,6 +629,11 @@ impl MiningManager {
                             "Failed to post validate transaction {0} due to rule error: {1}",
                             transaction_id, err
                         );
+                        if let (true, RuleError::RejectDoubleSpendInMempool(..)) = (validated, &err)
+                        {
+                            // The conflicting transaction got inserted while this one was being validated
+                            self.report_double_spends(&mempool, &tx);
+                        }
                         insert_results.push(Err(MiningManagerError::MempoolError(err)));
                         vec![]
                     }
@@ -597,6 +727,22 @@ impl MiningManager {
         (transactions, orphans)
     }
 
+    /// Returns up to `limit` transactions following `after` in id order, sorted by id, so the
+    /// mempool can be listed page by page without being cloned whole.
+    pub fn get_transactions_after(
+        &self,
+        query: TransactionQuery,
+        after: Option<TransactionId>,
+        limit: usize,
+    ) -> Vec<MutableTransaction> {
+        let mempool = self.mempool.read();
+        mempool
+            .get_transaction_ids_after(query, after, limit)
+            .iter()
+            .filter_map(|transaction_id| mempool.get_transaction(transaction_id, query))
+            .collect()
+    }
+
     /// get_transactions_by_addresses returns the sending and receiving transactions for
     /// a set of addresses.
     ///
@@ -627,10 +773,25 @@ impl MiningManager {
         // problem of the internal implementation and unrelated to the caller
 
         // write lock on mempool
-        let unorphaned_transactions = self
-            .mempool
-            .write()
-            .handle_new_block_transactions(block_daa_score, block_transactions)?;
+        let mut mempool = self.mempool.write();
+        if self.double_spend_reporter.is_enabled() {
+            // Mempool transactions conflicting with the block transactions are about to be removed
+            for transaction in block_transactions.iter().skip(1) {
+                let transaction_id = transaction.id();
+                let double_spends = mempool
+                    .find_double_spends(transaction)
+                    .into_iter()
+                    .map(|mut double_spend| {
+                        double_spend.accepted_transaction_id = Some(transaction_id);
+                        double_spend
+                    })
+                    .collect();
+                self.double_spend_reporter.report(double_spends);
+            }
+        }
+        let unorphaned_transactions =
+            mempool.handle_new_block_transactions(block_daa_score, block_transactions)?;
+        drop(mempool);
 
         // alternate no & write lock on mempool
         let accepted_transactions =
@@ -931,6 +1092,23 @@ impl MiningManager {
         self.mempool.read().has_accepted_transaction(transaction_id)
     }
 
+    /// Returns the fee rate of a mempool transaction along with the percentage of the mempool
+    /// transactions paying a lower fee rate
+    pub fn get_fee_rate_percentile(&self, transaction_id: &TransactionId) -> Option<(f64, f64)> {
+        self.mempool.read().fee_rate_percentile(transaction_id)
+    }
+
+    /// Returns the transactions detected double spending some outpoints of `transaction_id`.
+    ///
+    /// Only the recent conflicts are remembered, and none are detected until a double spend
+    /// subscriber is set.
+    pub fn get_conflicting_transactions(
+        &self,
+        transaction_id: &TransactionId,
+    ) -> Vec<TransactionId> {
+        self.double_spend_reporter.conflicts_of(transaction_id)
+    }
+
     pub fn unaccepted_transactions(&self, transactions: Vec<TransactionId>) -> Vec<TransactionId> {
         self.mempool.read().unaccepted_transactions(transactions)
     }
@@ -982,6 +1160,24 @@ impl MiningManagerProxy {
             .await
     }
 
+    /// Runs the full mempool validation of a transaction without inserting it into the mempool.
+    ///
+    /// Returns the transaction, populated with its UTXO entries, fee and compute mass as far as
+    /// the validation went, along with the validation outcome.
+    pub async fn test_mempool_accept(
+        self,
+        consensus: &ConsensusProxy,
+        transaction: Transaction,
+    ) -> (MutableTransaction, MiningManagerResult<()>) {
+        consensus
+            .clone()
+            .spawn_blocking(move |c| {
+                self.inner
+                    .test_mempool_accept(c, MutableTransaction::from_tx(transaction))
+            })
+            .await
+    }
+
     /// Validates a batch of transactions, handling iteratively only the independent ones, and
     /// adds those to the set of known transactions that have not yet been added to any block.
     ///
@@ -1078,6 +1274,17 @@ impl MiningManagerProxy {
             .unwrap()
     }
 
+    pub async fn get_transactions_after(
+        self,
+        query: TransactionQuery,
+        after: Option<TransactionId>,
+        limit: usize,
+    ) -> Vec<MutableTransaction> {
+        spawn_blocking(move || self.inner.get_transactions_after(query, after, limit))
+            .await
+            .unwrap()
+    }
+
     /// get_transactions_by_addresses returns the sending and receiving transactions for
     /// a set of addresses.
     ///
@@ -1109,6 +1316,27 @@ impl MiningManagerProxy {
             .unwrap()
     }
 
+    /// Returns the fee rate of a mempool transaction along with the percentage of the mempool
+    /// transactions paying a lower fee rate
+    pub async fn get_fee_rate_percentile(
+        self,
+        transaction_id: TransactionId,
+    ) -> Option<(f64, f64)> {
+        spawn_blocking(move || self.inner.get_fee_rate_percentile(&transaction_id))
+            .await
+            .unwrap()
+    }
+
+    /// Returns the transactions recently detected double spending some outpoints of `transaction_id`
+    pub async fn get_conflicting_transactions(
+        self,
+        transaction_id: TransactionId,
+    ) -> Vec<TransactionId> {
+        spawn_blocking(move || self.inner.get_conflicting_transactions(&transaction_id))
+            .await
+            .unwrap()
+    }
+
     /// Returns a vector of unaccepted transactions.
     /// For more details, see [`Self::has_accepted_transaction()`].
     pub async fn unaccepted_transactions(
@@ -1131,6 +1359,10 @@ impl MiningManagerProxy {
             .unwrap()
     }
 
+    pub fn set_double_spend_sender(&self, sender: UnboundedSender<DoubleSpend>) {
+        self.inner.set_double_spend_sender(sender);
+    }
+
     pub fn snapshot(&self) -> MempoolCountersSnapshot {
         self.inner.counters.snapshot()
     }
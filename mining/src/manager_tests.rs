@@ -1,7 +1,10 @@
 #[cfg(test)]
 mod tests {
     use crate::{
-        block_template::builder::BlockTemplateBuilder,
+        block_template::{
+            builder::BlockTemplateBuilder,
+            provider::{DefaultTemplateProvider, FilteredTemplateProvider},
+        },
         errors::{MiningManagerError, MiningManagerResult},
         manager::MiningManager,
         mempool::{
@@ -9,7 +12,10 @@ mod tests {
             errors::RuleError,
             tx::{Orphan, Priority},
         },
-        model::{candidate_tx::CandidateTransaction, tx_query::TransactionQuery},
+        model::{
+            candidate_tx::CandidateTransaction, double_spend::DoubleSpend,
+            tx_query::TransactionQuery,
+        },
         testutils::consensus_mock::ConsensusMock,
         MiningCounters,
     };
@@ -214,6 +220,102 @@ mod tests {
         }
     }
 
+    // test_mempool_accept verifies that a dry-run validation reports the outcome of a submission
+    // without altering the mempool.
+    #[test]
+    fn test_mempool_accept() {
+        let consensus = Arc::new(ConsensusMock::new());
+        let counters = Arc::new(MiningCounters::default());
+        let mining_manager =
+            MiningManager::new(TARGET_TIME_PER_BLOCK, false, MAX_BLOCK_MASS, None, counters);
+
+        let transaction = create_transaction_with_utxo_entry(0, 0);
+        let (validated, result) =
+            mining_manager.test_mempool_accept(consensus.as_ref(), transaction.clone());
+        assert!(
+            result.is_ok(),
+            "a valid transaction should pass the mempool acceptance test"
+        );
+        assert!(validated.calculated_fee.is_some());
+        assert!(validated.calculated_compute_mass.is_some());
+        assert_eq!(
+            mining_manager.transaction_count(TransactionQuery::All),
+            0,
+            "the mempool acceptance test should not insert the transaction"
+        );
+
+        let result = mining_manager.validate_and_insert_mutable_transaction(
+            consensus.as_ref(),
+            transaction.clone(),
+            Priority::Low,
+            Orphan::Allowed,
+        );
+        assert!(result.is_ok());
+
+        // The same transaction is now a duplicate
+        let (_, result) = mining_manager.test_mempool_accept(consensus.as_ref(), transaction);
+        assert!(matches!(
+            result,
+            Err(MiningManagerError::MempoolError(
+                RuleError::RejectDuplicate(_)
+            ))
+        ));
+
+        // A transaction with unknown outpoints would only qualify as an orphan
+        let parent_tx = create_transaction_without_input(vec![500 * SOMPI_PER_KARLSEN]);
+        let orphan = MutableTransaction::from_tx(create_transaction(&parent_tx, 1000));
+        let (_, result) = mining_manager.test_mempool_accept(consensus.as_ref(), orphan);
+        assert!(matches!(
+            result,
+            Err(MiningManagerError::MempoolError(
+                RuleError::RejectMissingOutpoint
+            ))
+        ));
+        assert_eq!(mining_manager.transaction_count(TransactionQuery::All), 1);
+    }
+
+    // test_get_transactions_after verifies that the mempool is listed page by page in id order.
+    #[test]
+    fn test_get_transactions_after() {
+        let consensus = Arc::new(ConsensusMock::new());
+        let counters = Arc::new(MiningCounters::default());
+        let mining_manager =
+            MiningManager::new(TARGET_TIME_PER_BLOCK, false, MAX_BLOCK_MASS, None, counters);
+
+        let mut transaction_ids = (0..10)
+            .map(|i| {
+                let transaction = create_transaction_with_utxo_entry(i, 0);
+                let transaction_id = transaction.id();
+                mining_manager
+                    .validate_and_insert_mutable_transaction(
+                        consensus.as_ref(),
+                        transaction,
+                        Priority::Low,
+                        Orphan::Allowed,
+                    )
+                    .unwrap();
+                transaction_id
+            })
+            .collect::<Vec<_>>();
+        transaction_ids.sort_unstable();
+
+        let mut listed = Vec::new();
+        let mut after = None;
+        loop {
+            let page = mining_manager.get_transactions_after(TransactionQuery::All, after, 3);
+            if page.is_empty() {
+                break;
+            }
+            assert!(page.len() <= 3);
+            after = page.last().map(|transaction| transaction.id());
+            listed.extend(page.iter().map(|transaction| transaction.id()));
+        }
+        assert_eq!(listed, transaction_ids);
+        assert!(mining_manager
+            .get_transactions_after(TransactionQuery::OrphansOnly, None, 3)
+            .is_empty());
+    }
+
     // test_double_spend_in_mempool verifies that an attempt to insert a transaction double-spending
     // another transaction already in the mempool will result in raising an appropriate error.
     #[test]
@@ -392,6 +494,152 @@ mod tests {
         );
     }
 
+    // test_double_spend_reporting verifies that the conflicts with the mempool and with the block transactions are
+    // reported once per pair of transactions.
+    #[test]
+    fn test_double_spend_reporting() {
+        let consensus = Arc::new(ConsensusMock::new());
+        let counters = Arc::new(MiningCounters::default());
+        let mining_manager =
+            MiningManager::new(TARGET_TIME_PER_BLOCK, false, MAX_BLOCK_MASS, None, counters);
+        let (sender, mut receiver) = unbounded_channel();
+        mining_manager.set_double_spend_sender(sender);
+
+        let transaction = create_transaction_with_utxo_entry(0, 0);
+        let outpoint = transaction.tx.inputs[0].previous_outpoint;
+        let result = mining_manager.validate_and_insert_mutable_transaction(
+            consensus.as_ref(),
+            transaction.clone(),
+            Priority::Low,
+            Orphan::Allowed,
+        );
+        assert!(result.is_ok());
+        assert_eq!(receiver.try_recv(), Err(TryRecvError::Empty));
+
+        let mut double_spend = transaction.clone();
+        Arc::make_mut(&mut double_spend.tx).outputs[0].value -= 1;
+        Arc::make_mut(&mut double_spend.tx).finalize();
+        for _ in 0..2 {
+            let result = mining_manager.validate_and_insert_mutable_transaction(
+                consensus.as_ref(),
+                double_spend.clone(),
+                Priority::Low,
+                Orphan::Allowed,
+            );
+            assert!(result.is_err());
+        }
+        assert_eq!(
+            receiver.try_recv(),
+            Ok(DoubleSpend::new(
+                double_spend.id(),
+                transaction.id(),
+                vec![outpoint],
+                None
+            ))
+        );
+        // The relayed again transaction is not reported twice
+        assert_eq!(receiver.try_recv(), Err(TryRecvError::Empty));
+
+        // A block accepting another spender of the outpoint evicts the mempool transaction
+        let mut block_transaction = create_transaction_with_utxo_entry(1, 0);
+        Arc::make_mut(&mut block_transaction.tx).inputs[0].previous_outpoint = outpoint;
+        Arc::make_mut(&mut block_transaction.tx).finalize();
+        let block_transactions =
+            build_block_transactions(std::iter::once(block_transaction.tx.as_ref()));
+        let result = mining_manager.handle_new_block_transactions(
+            consensus.as_ref(),
+            2,
+            &block_transactions,
+        );
+        assert!(result.is_ok());
+        assert_eq!(
+            receiver.try_recv(),
+            Ok(DoubleSpend::new(
+                block_transaction.id(),
+                transaction.id(),
+                vec![outpoint],
+                Some(block_transaction.id())
+            ))
+        );
+        assert_eq!(
+            mining_manager.get_conflicting_transactions(&transaction.id()),
+            vec![double_spend.id(), block_transaction.id()]
+        );
+    }
+
+    // test_invalid_double_spend_not_reported verifies that a transaction conflicting with the mempool is only reported
+    // once it passed the consensus validation, so that anyone cannot raise alerts with invalid transactions.
+    #[test]
+    fn test_invalid_double_spend_not_reported() {
+        let consensus = Arc::new(ConsensusMock::new());
+        let counters = Arc::new(MiningCounters::default());
+        let mining_manager =
+            MiningManager::new(TARGET_TIME_PER_BLOCK, false, MAX_BLOCK_MASS, None, counters);
+        let (sender, mut receiver) = unbounded_channel();
+        mining_manager.set_double_spend_sender(sender);
+
+        let transaction = create_transaction_with_utxo_entry(0, 0);
+        let outpoint = transaction.tx.inputs[0].previous_outpoint;
+        let result = mining_manager.validate_and_insert_mutable_transaction(
+            consensus.as_ref(),
+            transaction.clone(),
+            Priority::Low,
+            Orphan::Allowed,
+        );
+        assert!(result.is_ok());
+
+        let conflicting_transaction = |value_change: u64| {
+            let mut double_spend = transaction.clone();
+            Arc::make_mut(&mut double_spend.tx).outputs[0].value -= value_change;
+            Arc::make_mut(&mut double_spend.tx).finalize();
+            double_spend
+        };
+
+        // An invalid conflicting transaction is rejected without any report, whether submitted alone or in a batch
+        let invalid_double_spend = conflicting_transaction(1);
+        consensus.set_status(invalid_double_spend.id(), Err(TxRuleError::TxHasGas));
+        let result = mining_manager.validate_and_insert_mutable_transaction(
+            consensus.as_ref(),
+            invalid_double_spend.clone(),
+            Priority::Low,
+            Orphan::Allowed,
+        );
+        assert!(matches!(
+            result,
+            Err(MiningManagerError::MempoolError(
+                RuleError::RejectDoubleSpendInMempool(..)
+            ))
+        ));
+        let results = mining_manager.validate_and_insert_transaction_batch(
+            consensus.as_ref(),
+            vec![invalid_double_spend.tx.as_ref().clone()],
+            Priority::Low,
+            Orphan::Allowed,
+        );
+        assert!(results[0].is_err());
+        assert_eq!(receiver.try_recv(), Err(TryRecvError::Empty));
+
+        // A valid one is reported
+        let valid_double_spend = conflicting_transaction(2);
+        let results = mining_manager.validate_and_insert_transaction_batch(
+            consensus.as_ref(),
+            vec![valid_double_spend.tx.as_ref().clone()],
+            Priority::Low,
+            Orphan::Allowed,
+        );
+        assert!(results[0].is_err());
+        assert_eq!(
+            receiver.try_recv(),
+            Ok(DoubleSpend::new(
+                valid_double_spend.id(),
+                transaction.id(),
+                vec![outpoint],
+                None
+            ))
+        );
+        assert_eq!(receiver.try_recv(), Err(TryRecvError::Empty));
+    }
+
     // test_orphan_transactions verifies that a transaction could be a part of a new block template only if it's not an orphan.
     #[test]
     fn test_orphan_transactions() {
@@ -918,6 +1166,47 @@ mod tests {
         assert!(orphan_txs.is_empty(), "orphan pool should be empty");
     }
 
+    // test_template_provider verifies that a custom transaction selection policy is used by the block templates
+    #[test]
+    fn test_template_provider() {
+        let consensus = Arc::new(ConsensusMock::new());
+        let counters = Arc::new(MiningCounters::default());
+        let mining_manager =
+            MiningManager::new(TARGET_TIME_PER_BLOCK, false, MAX_BLOCK_MASS, None, counters);
+
+        let transactions = (0..2)
+            .map(|i| create_transaction_with_utxo_entry(i, 0))
+            .collect::<Vec<_>>();
+        for transaction in transactions.iter() {
+            let result = mining_manager.validate_and_insert_mutable_transaction(
+                consensus.as_ref(),
+                transaction.clone(),
+                Priority::Low,
+                Orphan::Allowed,
+            );
+            assert!(result.is_ok());
+        }
+
+        let excluded = transactions[0].id();
+        mining_manager.set_template_provider(Arc::new(FilteredTemplateProvider::new(
+            "exclude",
+            move |_, tx: &CandidateTransaction| tx.tx.id() != excluded,
+            Arc::new(DefaultTemplateProvider),
+        )));
+        assert_eq!(mining_manager.template_provider().name(), "exclude");
+
+        let miner_data = get_miner_data(Prefix::Testnet);
+        let template = mining_manager
+            .get_block_template(consensus.as_ref(), &miner_data)
+            .unwrap();
+        assert!(template.block.transactions.len() <= 2);
+        assert!(template
+            .block
+            .transactions
+            .iter()
+            .all(|tx| tx.id() != excluded));
+    }
+
     // test_modify_block_template verifies that modifying a block template changes coinbase data correctly.
     #[test]
     fn test_modify_block_template() {
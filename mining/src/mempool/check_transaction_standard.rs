@@ -9,10 +9,6 @@ use karlsen_consensus_core::{
 };
 use karlsen_txscript::{get_sig_op_count, is_unspendable, script_class::ScriptClass};
 
-/// MAX_STANDARD_P2SH_SIG_OPS is the maximum number of signature operations
-/// that are considered standard in a pay-to-script-hash script.
-const MAX_STANDARD_P2SH_SIG_OPS: u8 = 15;
-
 /// MAXIMUM_STANDARD_SIGNATURE_SCRIPT_SIZE is the maximum size allowed for a
 /// transaction input signature script to be considered standard. This
 /// value allows for a 15-of-15 CHECKMULTISIG pay-to-script-hash with
@@ -32,10 +28,6 @@ const MAX_STANDARD_P2SH_SIG_OPS: u8 = 15;
 /// (1 + 15*74 + 3) + (15*34 + 3) + 23 = 1650
 const MAXIMUM_STANDARD_SIGNATURE_SCRIPT_SIZE: u64 = 1650;
 
-/// MAXIMUM_STANDARD_TRANSACTION_MASS is the maximum mass allowed for transactions that
-/// are considered standard and will therefore be relayed and considered for mining.
-const MAXIMUM_STANDARD_TRANSACTION_MASS: u64 = 100_000;
-
 impl Mempool {
     pub(crate) fn check_transaction_standard_in_isolation(
         &self,
@@ -63,11 +55,13 @@ impl Mempool {
         // almost as much to process as the sender fees, limit the maximum
         // size of a transaction. This also helps mitigate CPU exhaustion
         // attacks.
-        if transaction.calculated_compute_mass.unwrap() > MAXIMUM_STANDARD_TRANSACTION_MASS {
+        if transaction.calculated_compute_mass.unwrap()
+            > self.config.maximum_standard_transaction_mass
+        {
             return Err(NonStandardError::RejectMass(
                 transaction_id,
                 transaction.calculated_compute_mass.unwrap(),
-                MAXIMUM_STANDARD_TRANSACTION_MASS,
+                self.config.maximum_standard_transaction_mass,
             ));
         }
 
@@ -106,6 +100,7 @@ impl Mempool {
                     transaction_id,
                     i,
                     output.value,
+                    self.dust_threshold(output).unwrap_or(u64::MAX),
                 ));
             }
         }
@@ -114,21 +109,29 @@ impl Mempool {
     }
 
     /// is_transaction_output_dust returns whether or not the passed transaction output
-    /// amount is considered dust or not based on the configured minimum transaction
-    /// relay fee.
+    /// amount is considered dust or not based on the configured dust relay fee.
     ///
-    /// Dust is defined in terms of the minimum transaction relay fee. In particular,
-    /// if the cost to the network to spend coins is more than 1/3 of the minimum
-    /// transaction relay fee, it is considered dust.
+    /// Dust is defined in terms of the dust relay fee of the relay policy, which defaults to
+    /// the minimum transaction relay fee. In particular, if the cost to the network to spend
+    /// coins is more than 1/3 of the dust relay fee, it is considered dust.
     ///
     /// It is exposed by [MiningManager] for use by transaction generators and wallets.
     pub(crate) fn is_transaction_output_dust(
         &self,
         transaction_output: &TransactionOutput,
     ) -> bool {
+        match self.dust_threshold(transaction_output) {
+            Some(threshold) => transaction_output.value < threshold,
+            None => true,
+        }
+    }
+
+    /// dust_threshold returns the minimum value of the passed transaction output for it not to
+    /// be considered dust, or `None` if the output is unspendable and thus always dust.
+    fn dust_threshold(&self, transaction_output: &TransactionOutput) -> Option<u64> {
         // Unspendable outputs are considered dust.
         if is_unspendable::<PopulatedTransaction>(transaction_output.script_public_key.script()) {
-            return true;
+            return None;
         }
 
         // The total serialized size consists of the output and the associated
@@ -152,29 +155,22 @@ impl Mempool {
             mass::transaction_output_estimated_serialized_size(transaction_output) + 148;
 
         // The output is considered dust if the cost to the network to spend the
-        // coins is more than 1/3 of the minimum free transaction relay fee.
-        // mp.config.MinimumRelayTransactionFee is in sompi/KB, so multiply
-        // by 1000 to convert to bytes.
+        // coins is more than 1/3 of the dust relay fee. The fee is in sompi/kg,
+        // so divide by 1000 to convert to grams.
         //
         // Using the typical values for a pay-to-pubkey transaction from
-        // the breakdown above and the default minimum free transaction relay
-        // fee of 1000, this equates to values less than 546 sompi being
-        // considered dust.
+        // the breakdown above and the default dust relay fee of 1000, this
+        // equates to values less than 546 sompi being considered dust.
         //
-        // The following is equivalent to (value/total_serialized_size) * (1/3) * 1000
-        // without needing to do floating point math.
+        // The following is the smallest value such that
+        // (value/total_serialized_size) * (1/3) * 1000 >= fee, without needing
+        // to do floating point math.
         //
-        // Since the multiplication may overflow a u64, 2 separate calculation paths
-        // are considered to avoid overflowing.
-        match transaction_output.value.checked_mul(1000) {
-            Some(value_1000) => {
-                value_1000 / (3 * total_serialized_size) < self.config.minimum_relay_transaction_fee
-            }
-            None => {
-                (transaction_output.value as u128 * 1000 / (3 * total_serialized_size as u128))
-                    < self.config.minimum_relay_transaction_fee as u128
-            }
-        }
+        // Since the multiplication may overflow a u64, it is done in u128.
+        let threshold =
+            (3 * total_serialized_size as u128 * self.config.dust_relay_transaction_fee as u128)
+                .div_ceil(1000);
+        Some(threshold.min(u64::MAX as u128) as u64)
     }
 
     /// check_transaction_standard_in_context performs a series of checks on a transaction's
@@ -191,11 +187,24 @@ impl Mempool {
         let transaction_id = transaction.id();
         let contextual_mass = transaction.tx.mass();
         assert!(contextual_mass > 0, "expected to be set by consensus");
-        if contextual_mass > MAXIMUM_STANDARD_TRANSACTION_MASS {
+        // The contextual mass only exceeds the compute mass when the storage mass dominates,
+        // in which case the storage mass is weighted by the factor of the relay policy
+        if contextual_mass > transaction.calculated_compute_mass.unwrap() {
+            let weighted_storage_mass =
+                (contextual_mass as f64 * self.config.storage_mass_factor) as u64;
+            if weighted_storage_mass > self.config.maximum_standard_transaction_mass {
+                return Err(NonStandardError::RejectStorageMass(
+                    transaction_id,
+                    contextual_mass,
+                    self.config.storage_mass_factor,
+                    self.config.maximum_standard_transaction_mass,
+                ));
+            }
+        } else if contextual_mass > self.config.maximum_standard_transaction_mass {
             return Err(NonStandardError::RejectContextualMass(
                 transaction_id,
                 contextual_mass,
-                MAXIMUM_STANDARD_TRANSACTION_MASS,
+                self.config.maximum_standard_transaction_mass,
             ));
         }
 
@@ -211,17 +220,16 @@ impl Mempool {
                 ScriptClass::PubKey => {}
                 ScriptClass::PubKeyECDSA => {}
                 ScriptClass::ScriptHash => {
-                    get_sig_op_count::<PopulatedTransaction>(
+                    let num_sig_ops = get_sig_op_count::<PopulatedTransaction>(
                         &input.signature_script,
                         &entry.script_public_key,
                     );
-                    let num_sig_ops = 1;
-                    if num_sig_ops > MAX_STANDARD_P2SH_SIG_OPS {
+                    if num_sig_ops > self.config.maximum_standard_p2sh_sig_ops {
                         return Err(NonStandardError::RejectSignatureCount(
                             transaction_id,
                             i,
                             num_sig_ops,
-                            MAX_STANDARD_P2SH_SIG_OPS,
+                            self.config.maximum_standard_p2sh_sig_ops,
                         ));
                     }
                 }
@@ -268,7 +276,10 @@ impl Mempool {
 mod tests {
     use super::*;
     use crate::{
-        mempool::config::{Config, DEFAULT_MINIMUM_RELAY_TRANSACTION_FEE},
+        mempool::config::{
+            Config, RelayPolicy, DEFAULT_MAXIMUM_STANDARD_TRANSACTION_MASS,
+            DEFAULT_MINIMUM_RELAY_TRANSACTION_FEE,
+        },
         MiningCounters,
     };
     use karlsen_addresses::{Address, Prefix, Version};
@@ -279,7 +290,7 @@ mod tests {
         subnets::SUBNETWORK_ID_NATIVE,
         tx::{
             ScriptPublicKey, ScriptVec, Transaction, TransactionInput, TransactionOutpoint,
-            TransactionOutput,
+            TransactionOutput, UtxoEntry,
         },
     };
     use karlsen_txscript::{
@@ -315,7 +326,7 @@ mod tests {
             },
             Test {
                 name: "max standard tx size with default minimum relay fee",
-                size: MAXIMUM_STANDARD_TRANSACTION_MASS,
+                size: DEFAULT_MAXIMUM_STANDARD_TRANSACTION_MASS,
                 minimum_relay_transaction_fee: DEFAULT_MINIMUM_RELAY_TRANSACTION_FEE,
                 want: 100000,
             },
@@ -390,7 +401,7 @@ mod tests {
         struct Test {
             name: &'static str,
             tx_out: TransactionOutput,
-            minimum_relay_transaction_fee: u64,
+            dust_relay_transaction_fee: u64,
             is_dust: bool,
         }
 
@@ -399,33 +410,33 @@ mod tests {
             Test {
                 name: "zero value with zero relay fee",
                 tx_out: TransactionOutput::new(0, script_public_key.clone()),
-                minimum_relay_transaction_fee: 0,
+                dust_relay_transaction_fee: 0,
                 is_dust: false,
             },
             // Zero value is dust with any relay fee"
             Test {
                 name: "zero value with very small tx fee",
                 tx_out: TransactionOutput::new(0, script_public_key.clone()),
-                minimum_relay_transaction_fee: 1,
+                dust_relay_transaction_fee: 1,
                 is_dust: true,
             },
             Test {
                 name: "36 byte public key script with value 605",
                 tx_out: TransactionOutput::new(605, script_public_key.clone()),
-                minimum_relay_transaction_fee: 1000,
+                dust_relay_transaction_fee: 1000,
                 is_dust: true,
             },
             Test {
                 name: "36 byte public key script with value 606",
                 tx_out: TransactionOutput::new(606, script_public_key.clone()),
-                minimum_relay_transaction_fee: 1000,
+                dust_relay_transaction_fee: 1000,
                 is_dust: false,
             },
             // Maximum allowed value is never dust.
             Test {
                 name: "max sompi amount is never dust",
                 tx_out: TransactionOutput::new(MAX_SOMPI, script_public_key.clone()),
-                minimum_relay_transaction_fee: 1000,
+                dust_relay_transaction_fee: 1000,
                 is_dust: false,
             },
             // Maximum uint64 value causes NO overflow.
@@ -433,14 +444,14 @@ mod tests {
             Test {
                 name: "maximum uint64 value",
                 tx_out: TransactionOutput::new(u64::MAX, script_public_key),
-                minimum_relay_transaction_fee: u64::MAX,
+                dust_relay_transaction_fee: u64::MAX,
                 is_dust: false,
             },
             // Unspendable script_public_key due to an invalid public key script.
             Test {
                 name: "unspendable script_public_key",
                 tx_out: TransactionOutput::new(5000, invalid_script_public_key),
-                minimum_relay_transaction_fee: 0,
+                dust_relay_transaction_fee: 0,
                 is_dust: true,
             },
         ];
@@ -452,7 +463,7 @@ mod tests {
                     false,
                     params.max_block_mass,
                 );
-                config.minimum_relay_transaction_fee = test.minimum_relay_transaction_fee;
+                config.dust_relay_transaction_fee = test.dust_relay_transaction_fee;
                 let counters = Arc::new(MiningCounters::default());
                 let mempool = Mempool::new(Arc::new(config), counters);
 
@@ -539,7 +550,8 @@ mod tests {
                                 MAX_SCRIPT_PUBLIC_KEY_VERSION,
                                 ScriptVec::from_vec(vec![
                                     0u8;
-                                    MAXIMUM_STANDARD_TRANSACTION_MASS as usize
+                                    DEFAULT_MAXIMUM_STANDARD_TRANSACTION_MASS
+                                        as usize
                                         + 1
                                 ]),
                             ),
@@ -679,4 +691,84 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_relay_policy() {
+        let addr = Address::new(Prefix::Testnet, Version::PubKey, &[1u8; 32]);
+        let script_public_key = karlsen_txscript::pay_to_address_script(&addr);
+        let input = TransactionInput::new(
+            TransactionOutpoint::new(karlsen_hashes::Hash::from_u64_word(1), 1),
+            vec![0u8; 65],
+            MAX_TX_IN_SEQUENCE_NUM,
+            1,
+        );
+        let tx = Transaction::new(
+            TX_VERSION,
+            vec![input],
+            vec![TransactionOutput::new(1_000, script_public_key.clone())],
+            0,
+            SUBNETWORK_ID_NATIVE,
+            0,
+            vec![],
+        );
+        let mut mtx = MutableTransaction::from_tx(tx);
+        mtx.calculated_compute_mass = Some(2_000);
+        mtx.calculated_fee = Some(SOMPI_PER_KARLSEN);
+        mtx.entries = vec![Some(UtxoEntry::new(
+            2 * SOMPI_PER_KARLSEN,
+            script_public_key,
+            0,
+            false,
+        ))];
+        // The small output makes the storage mass dominate the compute mass
+        mtx.tx.set_mass(60_000);
+
+        let params: Params = NetworkType::Mainnet.into();
+        let build_mempool = |relay_policy: &RelayPolicy| {
+            let config =
+                Config::build_default(params.target_time_per_block, false, params.max_block_mass)
+                    .apply_relay_policy(relay_policy);
+            Mempool::new(Arc::new(config), Arc::new(MiningCounters::default()))
+        };
+
+        // The default policy accepts the transaction
+        let mempool = build_mempool(&RelayPolicy::default());
+        assert!(mempool
+            .check_transaction_standard_in_isolation(&mtx)
+            .is_ok());
+        assert!(mempool.check_transaction_standard_in_context(&mtx).is_ok());
+
+        // A higher dust relay fee turns the output into dust
+        let mempool = build_mempool(&RelayPolicy {
+            dust_relay_transaction_fee: 10_000,
+            ..Default::default()
+        });
+        assert!(matches!(
+            mempool.check_transaction_standard_in_isolation(&mtx),
+            Err(NonStandardError::RejectDust(_, 0, 1_000, threshold)) if threshold > 1_000
+        ));
+
+        // A lower maximum standard mass rejects the transaction in isolation
+        let mempool = build_mempool(&RelayPolicy {
+            maximum_standard_transaction_mass: 1_000,
+            ..Default::default()
+        });
+        assert!(matches!(
+            mempool.check_transaction_standard_in_isolation(&mtx),
+            Err(NonStandardError::RejectMass(_, 2_000, 1_000))
+        ));
+
+        // A storage mass factor doubling the storage mass rejects the transaction in context
+        let mempool = build_mempool(&RelayPolicy {
+            storage_mass_factor: 2.0,
+            ..Default::default()
+        });
+        assert!(mempool
+            .check_transaction_standard_in_isolation(&mtx)
+            .is_ok());
+        assert!(matches!(
+            mempool.check_transaction_standard_in_context(&mtx),
+            Err(NonStandardError::RejectStorageMass(_, 60_000, _, 100_000))
+        ));
+    }
 }
@@ -27,6 +27,42 @@ pub(crate) const DEFAULT_MINIMUM_RELAY_TRANSACTION_FEE: u64 = 1000;
 pub(crate) const DEFAULT_MINIMUM_STANDARD_TRANSACTION_VERSION: u16 = TX_VERSION;
 pub(crate) const DEFAULT_MAXIMUM_STANDARD_TRANSACTION_VERSION: u16 = TX_VERSION;
 
+/// DEFAULT_MAXIMUM_STANDARD_TRANSACTION_MASS is the maximum mass allowed for transactions that
+/// are considered standard and will therefore be relayed and considered for mining.
+pub(crate) const DEFAULT_MAXIMUM_STANDARD_TRANSACTION_MASS: u64 = 100_000;
+
+/// DEFAULT_MAXIMUM_STANDARD_P2SH_SIG_OPS is the maximum number of signature operations
+/// that are considered standard in a pay-to-script-hash script.
+pub(crate) const DEFAULT_MAXIMUM_STANDARD_P2SH_SIG_OPS: u64 = 15;
+
+/// DEFAULT_STORAGE_MASS_FACTOR weights the storage mass of standard transactions as computed by consensus.
+pub(crate) const DEFAULT_STORAGE_MASS_FACTOR: f64 = 1.0;
+
+/// Relay policy of the node, ie. the limits beyond which transactions are considered non-standard and thus
+/// neither accepted to the mempool nor relayed. Unlike the consensus rules, the policy may differ between nodes.
+#[derive(Clone, Debug)]
+pub struct RelayPolicy {
+    /// Fee rate, in sompi per kg of mass, defining dust outputs: an output is dust if spending it costs more
+    /// than a third of its value at this rate
+    pub dust_relay_transaction_fee: u64,
+    /// Weight of the storage mass of transactions, a factor above 1 tightening the protection against
+    /// UTXO set bloat beyond what consensus enforces
+    pub storage_mass_factor: f64,
+    pub maximum_standard_transaction_mass: u64,
+    pub maximum_standard_p2sh_sig_ops: u64,
+}
+
+impl Default for RelayPolicy {
+    fn default() -> Self {
+        Self {
+            dust_relay_transaction_fee: DEFAULT_MINIMUM_RELAY_TRANSACTION_FEE,
+            storage_mass_factor: DEFAULT_STORAGE_MASS_FACTOR,
+            maximum_standard_transaction_mass: DEFAULT_MAXIMUM_STANDARD_TRANSACTION_MASS,
+            maximum_standard_p2sh_sig_ops: DEFAULT_MAXIMUM_STANDARD_P2SH_SIG_OPS,
+        }
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct Config {
     pub maximum_transaction_count: u64,
@@ -47,6 +83,10 @@ pub struct Config {
     pub minimum_relay_transaction_fee: u64,
     pub minimum_standard_transaction_version: u16,
     pub maximum_standard_transaction_version: u16,
+    pub dust_relay_transaction_fee: u64,
+    pub storage_mass_factor: f64,
+    pub maximum_standard_transaction_mass: u64,
+    pub maximum_standard_p2sh_sig_ops: u64,
 }
 
 impl Config {
@@ -70,6 +110,10 @@ impl Config {
         minimum_relay_transaction_fee: u64,
         minimum_standard_transaction_version: u16,
         maximum_standard_transaction_version: u16,
+        dust_relay_transaction_fee: u64,
+        storage_mass_factor: f64,
+        maximum_standard_transaction_mass: u64,
+        maximum_standard_p2sh_sig_ops: u64,
     ) -> Self {
         Self {
             maximum_transaction_count,
@@ -90,6 +134,10 @@ impl Config {
             minimum_relay_transaction_fee,
             minimum_standard_transaction_version,
             maximum_standard_transaction_version,
+            dust_relay_transaction_fee,
+            storage_mass_factor,
+            maximum_standard_transaction_mass,
+            maximum_standard_p2sh_sig_ops,
         }
     }
 
@@ -132,6 +180,10 @@ impl Config {
             minimum_relay_transaction_fee: DEFAULT_MINIMUM_RELAY_TRANSACTION_FEE,
             minimum_standard_transaction_version: DEFAULT_MINIMUM_STANDARD_TRANSACTION_VERSION,
             maximum_standard_transaction_version: DEFAULT_MAXIMUM_STANDARD_TRANSACTION_VERSION,
+            dust_relay_transaction_fee: DEFAULT_MINIMUM_RELAY_TRANSACTION_FEE,
+            storage_mass_factor: DEFAULT_STORAGE_MASS_FACTOR,
+            maximum_standard_transaction_mass: DEFAULT_MAXIMUM_STANDARD_TRANSACTION_MASS,
+            maximum_standard_p2sh_sig_ops: DEFAULT_MAXIMUM_STANDARD_P2SH_SIG_OPS,
         }
     }
 
@@ -140,4 +192,12 @@ impl Config {
             (self.maximum_transaction_count as f64 * ram_scale.min(1.0)) as u64; // Allow only scaling down
         self
     }
+
+    pub fn apply_relay_policy(mut self, relay_policy: &RelayPolicy) -> Self {
+        self.dust_relay_transaction_fee = relay_policy.dust_relay_transaction_fee;
+        self.storage_mass_factor = relay_policy.storage_mass_factor;
+        self.maximum_standard_transaction_mass = relay_policy.maximum_standard_transaction_mass;
+        self.maximum_standard_p2sh_sig_ops = relay_policy.maximum_standard_p2sh_sig_ops;
+        self
+    }
 }
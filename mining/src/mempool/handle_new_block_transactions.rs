@@ -44,6 +44,7 @@ impl Mempool {
                 .accepted_transactions
                 .add(transaction_id, block_daa_score)
             {
+                self.accepted_transactions.add_spends(transaction);
                 tx_accepted_counts += 1;
                 input_counts += transaction.inputs.len();
                 output_counts += transaction.outputs.len();
@@ -1,6 +1,7 @@
 use crate::{
     model::{
         candidate_tx::CandidateTransaction,
+        double_spend::DoubleSpend,
         owner_txs::{GroupedOwnerTransactions, ScriptPublicKeySet},
         tx_query::TransactionQuery,
     },
@@ -15,7 +16,7 @@ use self::{
     },
     tx::Priority,
 };
-use karlsen_consensus_core::tx::{MutableTransaction, TransactionId};
+use karlsen_consensus_core::tx::{MutableTransaction, Transaction, TransactionId};
 use karlsen_core::time::Stopwatch;
 use std::sync::Arc;
 
@@ -124,6 +125,27 @@ impl Mempool {
         (transactions, orphans)
     }
 
+    pub(crate) fn get_transaction_ids_after(
+        &self,
+        query: TransactionQuery,
+        after: Option<TransactionId>,
+        limit: usize,
+    ) -> Vec<TransactionId> {
+        let mut transaction_ids = Vec::new();
+        if query.include_transaction_pool() {
+            transaction_ids.extend(
+                self.transaction_pool
+                    .get_transaction_ids_after(after, limit),
+            );
+        }
+        if query.include_orphan_pool() {
+            transaction_ids.extend(self.orphan_pool.get_transaction_ids_after(after, limit));
+        }
+        transaction_ids.sort_unstable();
+        transaction_ids.truncate(limit);
+        transaction_ids
+    }
+
     pub(crate) fn get_transactions_by_addresses(
         &self,
         script_public_keys: &ScriptPublicKeySet,
@@ -199,6 +221,58 @@ impl Mempool {
         });
         self.accepted_transactions.unaccepted(&mut not_in_pools_txs)
     }
+
+    /// Returns the fee rate of a mempool transaction along with the percentage of the mempool
+    /// transactions paying a lower fee rate
+    pub(crate) fn fee_rate_percentile(&self, transaction_id: &TransactionId) -> Option<(f64, f64)> {
+        let fee_rate = self.transaction_pool.get(transaction_id)?.fee_rate();
+        let lower_count = self
+            .transaction_pool
+            .all()
+            .values()
+            .filter(|tx| tx.fee_rate() < fee_rate)
+            .count();
+        Some((
+            fee_rate,
+            100.0 * lower_count as f64 / self.transaction_pool.len() as f64,
+        ))
+    }
+
+    /// Returns the conflicts of `transaction` with the transactions of the mempool and the
+    /// recently accepted transactions spending some of its outpoints
+    pub(crate) fn find_double_spends(&self, transaction: &Transaction) -> Vec<DoubleSpend> {
+        let transaction_id = transaction.id();
+        let mut double_spends: Vec<DoubleSpend> = vec![];
+        for input in transaction.inputs.iter() {
+            let outpoint = input.previous_outpoint;
+            let conflict = match self.transaction_pool.get_outpoint_owner_id(&outpoint) {
+                Some(owner_id) => Some((*owner_id, false)),
+                None => self
+                    .accepted_transactions
+                    .get_spender(&outpoint)
+                    .map(|spender_id| (spender_id, true)),
+            };
+            let Some((conflicting_id, accepted)) = conflict else {
+                continue;
+            };
+            if conflicting_id == transaction_id {
+                continue;
+            }
+            match double_spends
+                .iter_mut()
+                .find(|x| x.conflicting_transaction_id == conflicting_id)
+            {
+                Some(double_spend) => double_spend.outpoints.push(outpoint),
+                None => double_spends.push(DoubleSpend::new(
+                    transaction_id,
+                    conflicting_id,
+                    vec![outpoint],
+                    accepted.then_some(conflicting_id),
+                )),
+            }
+        }
+        double_spends
+    }
 }
 
 pub mod tx {
@@ -1,5 +1,5 @@
 use crate::mempool::config::Config;
-use karlsen_consensus_core::tx::TransactionId;
+use karlsen_consensus_core::tx::{Transaction, TransactionId, TransactionOutpoint};
 use karlsen_core::{debug, time::unix_now};
 use std::{collections::HashMap, sync::Arc};
 
@@ -10,6 +10,9 @@ pub(crate) struct AcceptedTransactions {
     /// A map of Transaction IDs to DAA scores
     transactions: HashMap<TransactionId, u64>,
 
+    /// A map of the outpoints spent by the accepted transactions to their spender
+    spent_outpoints: HashMap<TransactionOutpoint, TransactionId>,
+
     /// Last expire scan DAA score
     last_expire_scan_daa_score: u64,
     /// last expire scan time in milliseconds
@@ -21,6 +24,7 @@ impl AcceptedTransactions {
         Self {
             config,
             transactions: Default::default(),
+            spent_outpoints: Default::default(),
             last_expire_scan_daa_score: 0,
             last_expire_scan_time: unix_now(),
         }
@@ -32,6 +36,20 @@ impl AcceptedTransactions {
             .is_none()
     }
 
+    /// Records the outpoints spent by an accepted transaction
+    pub(crate) fn add_spends(&mut self, transaction: &Transaction) {
+        let transaction_id = transaction.id();
+        for input in transaction.inputs.iter() {
+            self.spent_outpoints
+                .insert(input.previous_outpoint, transaction_id);
+        }
+    }
+
+    /// Returns the accepted transaction spending `outpoint`, if any
+    pub(crate) fn get_spender(&self, outpoint: &TransactionOutpoint) -> Option<TransactionId> {
+        self.spent_outpoints.get(outpoint).copied()
+    }
+
     pub(crate) fn remove(&mut self, transaction_id: &TransactionId) -> bool {
         self.transactions.remove(transaction_id).is_some()
     }
@@ -86,6 +104,10 @@ impl AcceptedTransactions {
         for transaction_id in expired_transactions.iter() {
             self.remove(transaction_id);
         }
+        if !expired_transactions.is_empty() {
+            self.spent_outpoints
+                .retain(|_, transaction_id| self.transactions.contains_key(transaction_id));
+        }
 
         debug!(
             "Removed {} accepted transactions from mempool cache. Currently containing {}",
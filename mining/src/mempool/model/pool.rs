@@ -10,7 +10,7 @@ use crate::{
     },
 };
 use karlsen_consensus_core::tx::{MutableTransaction, TransactionId};
-use std::collections::{hash_set::Iter, HashMap, HashSet, VecDeque};
+use std::collections::{hash_set::Iter, BinaryHeap, HashMap, HashSet, VecDeque};
 
 pub(crate) type TransactionsEdges = HashMap<TransactionId, TransactionIdSet>;
 
@@ -122,6 +122,29 @@ pub(crate) trait Pool {
         self.all().keys().cloned().collect()
     }
 
+    /// Returns the ids of up to `limit` transactions of the pool following `after` in id order, sorted.
+    fn get_transaction_ids_after(
+        &self,
+        after: Option<TransactionId>,
+        limit: usize,
+    ) -> Vec<TransactionId> {
+        // A max-heap keeping the lowest ids met so far
+        let mut lowest = BinaryHeap::with_capacity(limit.min(self.all().len()));
+        for id in self
+            .all()
+            .keys()
+            .filter(|id| after.map_or(true, |after| **id > after))
+        {
+            if lowest.len() < limit {
+                lowest.push(*id);
+            } else if lowest.peek().is_some_and(|highest| id < highest) {
+                lowest.pop();
+                lowest.push(*id);
+            }
+        }
+        lowest.into_sorted_vec()
+    }
+
     /// Fills owner transactions for a set of script public keys.
     fn fill_owner_set_transactions(
         &self,
@@ -17,6 +17,19 @@ use std::sync::Arc;
 
 impl Mempool {
     pub(crate) fn pre_validate_and_populate_transaction(
+        &self,
+        consensus: &dyn ConsensusApi,
+        transaction: MutableTransaction,
+    ) -> RuleResult<MutableTransaction> {
+        let transaction = self.pre_validate_and_populate_double_spend(consensus, transaction)?;
+        self.transaction_pool.check_double_spends(&transaction)?;
+        Ok(transaction)
+    }
+
+    /// Pre-validates and populates a transaction like [`Self::pre_validate_and_populate_transaction`]
+    /// but lets through a transaction double spending mempool transactions, so that it can be validated
+    /// before its conflicts get reported
+    pub(crate) fn pre_validate_and_populate_double_spend(
         &self,
         consensus: &dyn ConsensusApi,
         mut transaction: MutableTransaction,
@@ -26,7 +39,6 @@ impl Mempool {
         transaction.calculated_compute_mass =
             Some(consensus.calculate_transaction_compute_mass(&transaction.tx));
         self.validate_transaction_in_isolation(&transaction)?;
-        self.transaction_pool.check_double_spends(&transaction)?;
         self.populate_mempool_entries(&mut transaction);
         Ok(transaction)
     }
@@ -101,6 +113,24 @@ impl Mempool {
         Ok(Some(accepted_transaction))
     }
 
+    /// Runs the checks of `post_validate_and_insert_transaction` without altering the mempool.
+    ///
+    /// A transaction with missing outpoints is rejected since it would only qualify as an orphan.
+    pub(crate) fn post_validate_transaction(
+        &self,
+        validation_result: RuleResult<()>,
+        transaction: &MutableTransaction,
+    ) -> RuleResult<()> {
+        self.validate_transaction_unacceptance(transaction)?;
+        self.transaction_pool.check_double_spends(transaction)?;
+        validation_result?;
+        self.validate_transaction_in_context(transaction)?;
+        // Check there is room in the pool, possibly by evicting low priority transactions
+        self.transaction_pool
+            .limit_transaction_count(1, transaction)?;
+        Ok(())
+    }
+
     /// Validates that the transaction wasn't already accepted into the DAG
     fn validate_transaction_unacceptance(
         &self,
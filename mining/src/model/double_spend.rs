@@ -0,0 +1,112 @@
+use itertools::Itertools;
+use karlsen_consensus_core::tx::{TransactionId, TransactionOutpoint};
+use parking_lot::{Mutex, RwLock};
+use std::collections::{HashSet, VecDeque};
+use tokio::sync::mpsc::UnboundedSender;
+
+/// Count of recently reported conflicts kept for not reporting them twice
+const REPORTED_CAPACITY: usize = 10_000;
+
+/// A transaction observed spending outpoints already spent by another transaction
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DoubleSpend {
+    /// The transaction observed last
+    pub transaction_id: TransactionId,
+    /// The mempool or accepted transaction it conflicts with
+    pub conflicting_transaction_id: TransactionId,
+    /// The outpoints spent by both transactions
+    pub outpoints: Vec<TransactionOutpoint>,
+    /// The transaction of the two accepted by the DAG, if any
+    pub accepted_transaction_id: Option<TransactionId>,
+}
+
+impl DoubleSpend {
+    pub fn new(
+        transaction_id: TransactionId,
+        conflicting_transaction_id: TransactionId,
+        outpoints: Vec<TransactionOutpoint>,
+        accepted_transaction_id: Option<TransactionId>,
+    ) -> Self {
+        Self {
+            transaction_id,
+            conflicting_transaction_id,
+            outpoints,
+            accepted_transaction_id,
+        }
+    }
+}
+
+#[derive(Default)]
+struct Reported {
+    pairs: HashSet<(TransactionId, TransactionId)>,
+    order: VecDeque<(TransactionId, TransactionId)>,
+}
+
+/// Forwards the detected double spends to a subscriber, reporting every conflicting pair of
+/// transactions once even when the same transaction gets relayed by several peers
+#[derive(Default)]
+pub(crate) struct DoubleSpendReporter {
+    sender: RwLock<Option<UnboundedSender<DoubleSpend>>>,
+    reported: Mutex<Reported>,
+}
+
+impl DoubleSpendReporter {
+    pub(crate) fn set_sender(&self, sender: UnboundedSender<DoubleSpend>) {
+        *self.sender.write() = Some(sender);
+    }
+
+    /// Returns true if a subscriber is listening, so detecting double spends is worthwhile
+    pub(crate) fn is_enabled(&self) -> bool {
+        self.sender
+            .read()
+            .as_ref()
+            .is_some_and(|sender| !sender.is_closed())
+    }
+
+    /// Returns the transactions reported conflicting with `transaction_id`
+    pub(crate) fn conflicts_of(&self, transaction_id: &TransactionId) -> Vec<TransactionId> {
+        self.reported
+            .lock()
+            .order
+            .iter()
+            .filter_map(|(observed, conflicting)| {
+                if observed == transaction_id {
+                    Some(*conflicting)
+                } else if conflicting == transaction_id {
+                    Some(*observed)
+                } else {
+                    None
+                }
+            })
+            .unique()
+            .collect()
+    }
+
+    pub(crate) fn report(&self, double_spends: Vec<DoubleSpend>) {
+        if double_spends.is_empty() {
+            return;
+        }
+        let sender = self.sender.read();
+        let Some(sender) = sender.as_ref() else {
+            return;
+        };
+        let mut reported = self.reported.lock();
+        for double_spend in double_spends {
+            let pair = (
+                double_spend.transaction_id,
+                double_spend.conflicting_transaction_id,
+            );
+            if !reported.pairs.insert(pair) {
+                continue;
+            }
+            reported.order.push_back(pair);
+            if reported.order.len() > REPORTED_CAPACITY {
+                if let Some(evicted) = reported.order.pop_front() {
+                    reported.pairs.remove(&evicted);
+                }
+            }
+            // The subscriber may be gone due to a global shutdown, hence the error is ignored
+            let _ = sender.send(double_spend);
+        }
+    }
+}
@@ -1,7 +1,8 @@
 use karlsen_consensus_core::tx::TransactionId;
 use std::collections::HashSet;
 
-pub(crate) mod candidate_tx;
+pub mod candidate_tx;
+pub mod double_spend;
 pub mod owner_txs;
 pub mod topological_index;
 pub mod topological_sort;
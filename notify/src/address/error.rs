@@ -1,9 +1,13 @@
+use karlsen_addresses::Prefix;
 use thiserror::Error;
 
 #[derive(Clone, Debug, Error)]
 pub enum Error {
     #[error("the address store reached the maximum capacity")]
     MaxCapacityReached,
+
+    #[error("address {0} does not belong to the network of this node (expected prefix {1})")]
+    PrefixMismatch(String, Prefix),
 }
 
 pub type Result<T> = std::result::Result<T, Error>;
@@ -1,5 +1,6 @@
 use crate::error::Error;
 use crate::notification::Notification;
+use crate::queue::NotificationGap;
 use async_channel::Sender;
 use std::fmt::{Debug, Display};
 use std::hash::Hash;
@@ -13,6 +14,13 @@ pub trait Connection: Clone + Display + Debug + Send + Sync + 'static {
 
     fn encoding(&self) -> Self::Encoding;
     fn into_message(notification: &Self::Notification, encoding: &Self::Encoding) -> Self::Message;
+    /// Message reporting to the listener that some of its notifications were dropped, if the connection can carry one
+    fn into_gap_message(
+        _gap: &NotificationGap,
+        _encoding: &Self::Encoding,
+    ) -> Option<Self::Message> {
+        None
+    }
     async fn send(&self, message: Self::Message) -> Result<(), Self::Error>;
     fn close(&self) -> bool;
     fn is_closed(&self) -> bool;
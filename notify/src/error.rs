@@ -20,6 +20,9 @@ pub enum Error {
     #[error("connection closed")]
     ConnectionClosed,
 
+    #[error("notification queue overflow (capacity {0})")]
+    QueueOverflow(usize),
+
     #[error("event type disabled")]
     EventTypeDisabled,
 
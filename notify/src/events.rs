@@ -1,5 +1,6 @@
 use super::scope::Scope;
 use crate::error::Error;
+use borsh::{BorshDeserialize, BorshSerialize};
 use serde::{Deserialize, Serialize};
 use std::ops::{Index, IndexMut};
 use std::str::FromStr;
@@ -39,7 +40,7 @@ event_type_enum! {
     ///
     /// Note: This enum is central to the notification system. For supporting a new notification type, it is advised to
     /// start by adding a new variant here.
-    #[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+    #[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
     #[serde(rename_all = "kebab-case")]
     pub enum EventType {
         BlockAdded = 0,
@@ -51,10 +52,12 @@ event_type_enum! {
         VirtualDaaScoreChanged,
         PruningPointUtxoSetOverride,
         NewBlockTemplate,
+        TransactionStatusChanged,
+        DoubleSpendDetected,
     }
 }
 
-pub const EVENT_COUNT: usize = 9;
+pub const EVENT_COUNT: usize = 11;
 
 impl FromStr for EventType {
     type Err = Error;
@@ -70,6 +73,8 @@ impl FromStr for EventType {
             "virtual-daa-score-changed" => Ok(EventType::VirtualDaaScoreChanged),
             "pruning-point-utxo-set-override" => Ok(EventType::PruningPointUtxoSetOverride),
             "new-block-template" => Ok(EventType::NewBlockTemplate),
+            "transaction-status-changed" => Ok(EventType::TransactionStatusChanged),
+            "double-spend-detected" => Ok(EventType::DoubleSpendDetected),
             _ => Err(Error::InvalidEventType(s.to_string())),
         }
     }
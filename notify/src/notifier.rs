@@ -459,6 +459,12 @@ where
     ) -> Result<()> {
         let event = scope.event_type();
         if self.enabled_events[event] {
+            if let Scope::UtxosChanged(ref utxos_changed_scope) = scope {
+                if command == Command::Start {
+                    self.subscription_context
+                        .check_address_prefix(&utxos_changed_scope.addresses)?;
+                }
+            }
             let mut listeners = self.listeners.lock();
             if let Some(listener) = listeners.get_mut(&id) {
                 self.execute_subscribe_command_impl(id, listener, scope, command)?;
@@ -0,0 +1,570 @@
+//!
+//! Bounded, prioritized notification queues of the listeners.
+//!
+//! A [`QueuedConnection`] stands between the broadcasters of a notifier and the connection of a
+//! listener. The broadcasters enqueue the notifications without waiting on the connection, so a
+//! slow listener holds back neither the broadcasters nor the other listeners, and a task forwards
+//! the queued notifications to the connection in their order of arrival. Once the queue is more
+//! than half full, the notifications of the highest priority are forwarded first instead.
+//!
+//! A full queue applies the [`OverflowPolicy`] of its listener: it either drops the oldest
+//! notification of the lowest priority and reports the loss with a gap marker sent ahead of the
+//! remaining notifications, or it disconnects the listener.
+//!
+
+use crate::{
+    connection::Connection,
+    error::{Error, Result},
+    events::{EventArray, EventType, EVENT_TYPE_ARRAY},
+    notification::Notification,
+};
+use async_channel::{bounded, Receiver, Sender};
+use borsh::{BorshDeserialize, BorshSerialize};
+use karlsen_core::debug;
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::VecDeque,
+    fmt::{Debug, Display},
+    str::FromStr,
+    sync::{Arc, Weak},
+};
+
+/// Default count of notifications a listener queue holds
+pub const DEFAULT_QUEUE_CAPACITY: usize = 4096;
+
+const PRIORITY_COUNT: usize = 3;
+
+/// Delivery priority of a notification type
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum NotificationPriority {
+    Low = 0,
+    Normal,
+    High,
+}
+
+impl NotificationPriority {
+    /// Default priority of the notifications of `event_type`
+    pub fn of(event_type: EventType) -> Self {
+        match event_type {
+            // Notifications a listener cannot rebuild from the ones following them
+            EventType::UtxosChanged
+            | EventType::FinalityConflict
+            | EventType::FinalityConflictResolved
+            | EventType::PruningPointUtxoSetOverride
+            | EventType::DoubleSpendDetected => NotificationPriority::High,
+            EventType::BlockAdded
+            | EventType::VirtualChainChanged
+            | EventType::NewBlockTemplate
+            | EventType::TransactionStatusChanged => NotificationPriority::Normal,
+            // Notifications superseded by the next one of the same type
+            EventType::SinkBlueScoreChanged | EventType::VirtualDaaScoreChanged => {
+                NotificationPriority::Low
+            }
+        }
+    }
+}
+
+impl Display for NotificationPriority {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            NotificationPriority::Low => write!(f, "low"),
+            NotificationPriority::Normal => write!(f, "normal"),
+            NotificationPriority::High => write!(f, "high"),
+        }
+    }
+}
+
+impl FromStr for NotificationPriority {
+    type Err = Error;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "low" => Ok(NotificationPriority::Low),
+            "normal" => Ok(NotificationPriority::Normal),
+            "high" => Ok(NotificationPriority::High),
+            _ => Err(Error::General(format!(
+                "invalid notification priority `{s}`, expected one of: low, normal, high"
+            ))),
+        }
+    }
+}
+
+/// Behavior of a listener queue receiving a notification while full
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum OverflowPolicy {
+    /// Drop the oldest notification of the lowest priority and send a gap marker to the listener
+    #[default]
+    DropOldest,
+    /// Close the connection of the listener
+    Disconnect,
+}
+
+impl Display for OverflowPolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            OverflowPolicy::DropOldest => write!(f, "drop-oldest"),
+            OverflowPolicy::Disconnect => write!(f, "disconnect"),
+        }
+    }
+}
+
+impl FromStr for OverflowPolicy {
+    type Err = Error;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "drop-oldest" => Ok(OverflowPolicy::DropOldest),
+            "disconnect" => Ok(OverflowPolicy::Disconnect),
+            _ => Err(Error::General(format!(
+                "invalid overflow policy `{s}`, expected one of: drop-oldest, disconnect"
+            ))),
+        }
+    }
+}
+
+/// Priority of the notifications of an event type, parsed from `event-type=priority`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct EventPriority {
+    pub event_type: EventType,
+    pub priority: NotificationPriority,
+}
+
+impl FromStr for EventPriority {
+    type Err = Error;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        let (event_type, priority) = s.split_once('=').ok_or_else(|| {
+            Error::General(format!(
+                "invalid event priority `{s}`, expected event-type=priority"
+            ))
+        })?;
+        Ok(Self {
+            event_type: event_type.parse()?,
+            priority: priority.parse()?,
+        })
+    }
+}
+
+/// Settings of the notification queue of a listener
+#[derive(Clone, Debug)]
+pub struct QueueSettings {
+    pub capacity: usize,
+    pub overflow: OverflowPolicy,
+    pub priorities: EventArray<NotificationPriority>,
+}
+
+impl QueueSettings {
+    pub fn new(capacity: usize, overflow: OverflowPolicy) -> Self {
+        assert!(
+            capacity > 0,
+            "a notification queue requires a positive capacity"
+        );
+        Self {
+            capacity,
+            overflow,
+            priorities: EventArray::from_fn(|i| NotificationPriority::of(EVENT_TYPE_ARRAY[i])),
+        }
+    }
+
+    pub fn with_priority(mut self, event_type: EventType, priority: NotificationPriority) -> Self {
+        self.priorities[event_type] = priority;
+        self
+    }
+}
+
+impl Default for QueueSettings {
+    fn default() -> Self {
+        Self::new(DEFAULT_QUEUE_CAPACITY, OverflowPolicy::default())
+    }
+}
+
+/// Notifications dropped from a listener queue since the previous gap marker
+#[derive(
+    Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, BorshSerialize, BorshDeserialize,
+)]
+#[serde(rename_all = "camelCase")]
+pub struct NotificationGap {
+    /// Event types of the dropped notifications along with their counts
+    pub dropped: Vec<(EventType, u64)>,
+}
+
+impl NotificationGap {
+    /// Total count of dropped notifications
+    pub fn count(&self) -> u64 {
+        self.dropped.iter().map(|(_, count)| count).sum()
+    }
+}
+
+enum Dequeued<M> {
+    Gap(NotificationGap),
+    Message(M),
+}
+
+/// Bounded queue of messages, one FIFO per priority
+struct Queue<M> {
+    settings: QueueSettings,
+    /// Queued messages along with their arrival sequence number
+    levels: [VecDeque<(u64, EventType, M)>; PRIORITY_COUNT],
+    len: usize,
+    sequence: u64,
+    /// Count of notifications dropped since the previous gap marker, by event type
+    dropped: EventArray<u64>,
+    gap: bool,
+}
+
+impl<M> Queue<M> {
+    fn new(settings: QueueSettings) -> Self {
+        Self {
+            settings,
+            levels: Default::default(),
+            len: 0,
+            sequence: 0,
+            dropped: Default::default(),
+            gap: false,
+        }
+    }
+
+    /// Enqueues a message, failing if the queue is full and its policy is to disconnect
+    fn push(&mut self, event_type: EventType, message: M) -> Result<()> {
+        let priority = self.settings.priorities[event_type] as usize;
+        if self.len == self.settings.capacity {
+            if self.settings.overflow == OverflowPolicy::Disconnect {
+                return Err(Error::QueueOverflow(self.settings.capacity));
+            }
+            // Make room by dropping the oldest message of the lowest priority, unless
+            // the incoming one has a lower priority than all the queued messages
+            match self.levels[..=priority]
+                .iter_mut()
+                .find_map(|level| level.pop_front())
+            {
+                Some((_, dropped, _)) => {
+                    self.len -= 1;
+                    self.record_drop(dropped);
+                }
+                None => {
+                    self.record_drop(event_type);
+                    return Ok(());
+                }
+            }
+        }
+        self.levels[priority].push_back((self.sequence, event_type, message));
+        self.sequence += 1;
+        self.len += 1;
+        Ok(())
+    }
+
+    /// Whether the queue holds enough messages for the priorities to apply
+    fn is_under_pressure(&self) -> bool {
+        self.len * 2 > self.settings.capacity
+    }
+
+    /// Dequeues the pending gap marker, if any, or else the oldest message, of the highest
+    /// priority if the queue is under pressure
+    fn pop(&mut self) -> Option<Dequeued<M>> {
+        if self.gap {
+            self.gap = false;
+            let dropped = EVENT_TYPE_ARRAY
+                .iter()
+                .filter_map(|&event_type| {
+                    let count = std::mem::take(&mut self.dropped[event_type]);
+                    (count > 0).then_some((event_type, count))
+                })
+                .collect();
+            return Some(Dequeued::Gap(NotificationGap { dropped }));
+        }
+        let level = match self.is_under_pressure() {
+            true => self.levels.iter_mut().rev().find(|level| !level.is_empty()),
+            false => self
+                .levels
+                .iter_mut()
+                .filter(|level| !level.is_empty())
+                .min_by_key(|level| level.front().map(|(sequence, _, _)| *sequence)),
+        }?;
+        let (_, _, message) = level.pop_front()?;
+        self.len -= 1;
+        Some(Dequeued::Message(message))
+    }
+
+    fn record_drop(&mut self, event_type: EventType) {
+        self.dropped[event_type] += 1;
+        self.gap = true;
+    }
+}
+
+struct Inner<C>
+where
+    C: Connection,
+{
+    connection: C,
+    queue: Mutex<Queue<C::Message>>,
+    /// Wakes up the forwarding task, holding at most one pending signal
+    signal: Sender<()>,
+}
+
+/// A [`Connection`] forwarding the notifications through a bounded, prioritized queue
+#[derive(Clone)]
+pub struct QueuedConnection<C>
+where
+    C: Connection,
+{
+    inner: Arc<Inner<C>>,
+}
+
+impl<C> QueuedConnection<C>
+where
+    C: Connection,
+{
+    pub fn new(connection: C, settings: QueueSettings) -> Self {
+        let (signal, receiver) = bounded(1);
+        let inner = Arc::new(Inner {
+            connection,
+            queue: Mutex::new(Queue::new(settings)),
+            signal,
+        });
+        workflow_core::task::spawn(Self::forward(Arc::downgrade(&inner), receiver));
+        Self { inner }
+    }
+
+    pub fn connection(&self) -> &C {
+        &self.inner.connection
+    }
+
+    /// Forwards the queued messages to the connection until either gets closed
+    async fn forward(inner: Weak<Inner<C>>, signal: Receiver<()>) {
+        while signal.recv().await.is_ok() {
+            let Some(inner) = inner.upgrade() else {
+                break;
+            };
+            loop {
+                let dequeued = inner.queue.lock().pop();
+                let message = match dequeued {
+                    Some(Dequeued::Message(message)) => message,
+                    Some(Dequeued::Gap(gap)) => {
+                        debug!(
+                            "[{}] dropped {} notification(s) from a full queue",
+                            inner.connection,
+                            gap.count()
+                        );
+                        match C::into_gap_message(&gap, &inner.connection.encoding()) {
+                            Some(message) => message,
+                            None => continue,
+                        }
+                    }
+                    None => break,
+                };
+                if inner.connection.send(message).await.is_err() && inner.connection.is_closed() {
+                    return;
+                }
+            }
+        }
+    }
+}
+
+impl<C> Display for QueuedConnection<C>
+where
+    C: Connection,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.inner.connection)
+    }
+}
+
+impl<C> Debug for QueuedConnection<C>
+where
+    C: Connection,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("QueuedConnection")
+            .field("connection", &self.inner.connection)
+            .finish()
+    }
+}
+
+#[async_trait::async_trait]
+impl<C> Connection for QueuedConnection<C>
+where
+    C: Connection,
+    C::Notification: Notification,
+{
+    type Notification = C::Notification;
+    type Message = (EventType, C::Message);
+    type Encoding = C::Encoding;
+    type Error = Error;
+
+    fn encoding(&self) -> Self::Encoding {
+        self.inner.connection.encoding()
+    }
+
+    fn into_message(notification: &Self::Notification, encoding: &Self::Encoding) -> Self::Message {
+        (
+            notification.event_type(),
+            C::into_message(notification, encoding),
+        )
+    }
+
+    async fn send(&self, message: Self::Message) -> Result<()> {
+        if self.is_closed() {
+            return Err(Error::ConnectionClosed);
+        }
+        let (event_type, message) = message;
+        let result = self.inner.queue.lock().push(event_type, message);
+        match result {
+            Ok(()) => {
+                // A full signal channel already holds a wake-up for the forwarding task
+                let _ = self.inner.signal.try_send(());
+                Ok(())
+            }
+            Err(err) => {
+                debug!("[{}] {}, disconnecting", self, err);
+                self.inner.connection.close();
+                self.inner.signal.close();
+                Err(err)
+            }
+        }
+    }
+
+    fn close(&self) -> bool {
+        let closed = self.inner.connection.close();
+        self.inner.signal.close() || closed
+    }
+
+    fn is_closed(&self) -> bool {
+        self.inner.signal.is_closed() || self.inner.connection.is_closed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        connection::{ChannelConnection, ChannelType},
+        notification::test_helpers::*,
+    };
+    use async_channel::unbounded;
+
+    fn messages<M: Copy>(queue: &mut Queue<M>) -> Vec<std::result::Result<M, NotificationGap>> {
+        std::iter::from_fn(|| {
+            queue.pop().map(|x| match x {
+                Dequeued::Message(message) => Ok(message),
+                Dequeued::Gap(gap) => Err(gap),
+            })
+        })
+        .collect()
+    }
+
+    #[test]
+    fn test_queue_priorities() {
+        let push_all = |queue: &mut Queue<u32>| {
+            queue.push(EventType::VirtualDaaScoreChanged, 1).unwrap();
+            queue.push(EventType::BlockAdded, 2).unwrap();
+            queue.push(EventType::UtxosChanged, 3).unwrap();
+            queue.push(EventType::BlockAdded, 4).unwrap();
+            queue.push(EventType::VirtualDaaScoreChanged, 5).unwrap();
+        };
+
+        // Without pressure the messages keep their order of arrival
+        let mut queue = Queue::new(QueueSettings::new(16, OverflowPolicy::DropOldest));
+        push_all(&mut queue);
+        assert_eq!(
+            messages(&mut queue),
+            vec![Ok(1), Ok(2), Ok(3), Ok(4), Ok(5)]
+        );
+
+        // Under pressure, the highest priorities go first until the queue is half empty
+        let mut queue = Queue::new(QueueSettings::new(6, OverflowPolicy::DropOldest));
+        push_all(&mut queue);
+        assert_eq!(
+            messages(&mut queue),
+            vec![Ok(3), Ok(2), Ok(1), Ok(4), Ok(5)]
+        );
+
+        let settings = QueueSettings::new(6, OverflowPolicy::DropOldest).with_priority(
+            EventType::VirtualDaaScoreChanged,
+            NotificationPriority::High,
+        );
+        let mut queue = Queue::new(settings);
+        push_all(&mut queue);
+        assert_eq!(
+            messages(&mut queue),
+            vec![Ok(1), Ok(3), Ok(2), Ok(4), Ok(5)]
+        );
+    }
+
+    #[test]
+    fn test_event_priority() {
+        assert_eq!(
+            "utxos-changed=low".parse::<EventPriority>().unwrap(),
+            EventPriority {
+                event_type: EventType::UtxosChanged,
+                priority: NotificationPriority::Low
+            }
+        );
+        assert!("utxos-changed".parse::<EventPriority>().is_err());
+        assert!("utxos-changed=urgent".parse::<EventPriority>().is_err());
+        assert!("utxos=low".parse::<EventPriority>().is_err());
+    }
+
+    #[test]
+    fn test_queue_drop_oldest() {
+        let mut queue = Queue::new(QueueSettings::new(3, OverflowPolicy::DropOldest));
+        queue.push(EventType::UtxosChanged, 1).unwrap();
+        queue.push(EventType::BlockAdded, 2).unwrap();
+        queue.push(EventType::BlockAdded, 3).unwrap();
+        // The oldest message of the lowest priority makes room
+        queue.push(EventType::UtxosChanged, 4).unwrap();
+        // A message of a lower priority than all the queued ones is itself dropped
+        queue.push(EventType::VirtualDaaScoreChanged, 5).unwrap();
+        let gap = NotificationGap {
+            dropped: vec![
+                (EventType::BlockAdded, 1),
+                (EventType::VirtualDaaScoreChanged, 1),
+            ],
+        };
+        assert_eq!(gap.count(), 2);
+        assert_eq!(messages(&mut queue), vec![Err(gap), Ok(1), Ok(4), Ok(3)]);
+        // The gap marker is only reported once
+        queue.push(EventType::BlockAdded, 6).unwrap();
+        assert_eq!(messages(&mut queue), vec![Ok(6)]);
+    }
+
+    #[test]
+    fn test_queue_disconnect() {
+        let settings = QueueSettings::new(1, OverflowPolicy::Disconnect)
+            .with_priority(EventType::BlockAdded, NotificationPriority::Low);
+        let mut queue = Queue::new(settings);
+        queue.push(EventType::BlockAdded, 1).unwrap();
+        assert!(matches!(
+            queue.push(EventType::UtxosChanged, 2),
+            Err(Error::QueueOverflow(1))
+        ));
+        assert_eq!(messages(&mut queue), vec![Ok(1)]);
+    }
+
+    #[tokio::test]
+    async fn test_queued_connection() {
+        let (sender, receiver) = unbounded();
+        let connection = QueuedConnection::new(
+            ChannelConnection::new("test", sender, ChannelType::Closable),
+            QueueSettings::new(1, OverflowPolicy::Disconnect),
+        );
+        let notification = TestNotification::BlockAdded(BlockAddedNotification::default());
+        let message = (notification.event_type(), notification.clone());
+        connection.send(message.clone()).await.unwrap();
+        assert_eq!(receiver.recv().await.unwrap(), notification);
+
+        // Enqueuing never yields, so the forwarding task cannot drain the queue in between
+        connection.send(message.clone()).await.unwrap();
+        assert!(matches!(
+            connection.send(message.clone()).await,
+            Err(Error::QueueOverflow(1))
+        ));
+        assert!(connection.is_closed());
+        assert!(matches!(
+            connection.send(message).await,
+            Err(Error::ConnectionClosed)
+        ));
+    }
+}
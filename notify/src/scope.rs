@@ -44,6 +44,8 @@ pub enum Scope {
     VirtualDaaScoreChanged,
     PruningPointUtxoSetOverride,
     NewBlockTemplate,
+    TransactionStatusChanged,
+    DoubleSpendDetected,
 }
 }
 
@@ -210,3 +212,31 @@ pub struct PruningPointUtxoSetOverrideScope {}
     BorshDeserialize,
 )]
 pub struct NewBlockTemplateScope {}
+
+#[derive(
+    Clone,
+    Display,
+    Debug,
+    Default,
+    PartialEq,
+    Eq,
+    Serialize,
+    Deserialize,
+    BorshSerialize,
+    BorshDeserialize,
+)]
+pub struct TransactionStatusChangedScope {}
+
+#[derive(
+    Clone,
+    Display,
+    Debug,
+    Default,
+    PartialEq,
+    Eq,
+    Serialize,
+    Deserialize,
+    BorshSerialize,
+    BorshDeserialize,
+)]
+pub struct DoubleSpendDetectedScope {}
@@ -1,30 +1,33 @@
 use crate::{
-    address::tracker::Tracker,
+    address::{
+        error::{Error, Result},
+        tracker::Tracker,
+    },
     listener::ListenerId,
     subscription::{
         single::{UtxosChangedState, UtxosChangedSubscription},
         DynSubscription,
     },
 };
+use karlsen_addresses::{Address, Prefix};
 use std::{ops::Deref, sync::Arc};
 
-#[cfg(test)]
-use karlsen_addresses::Address;
-
 #[derive(Debug)]
 pub struct SubscriptionContextInner {
     pub address_tracker: Tracker,
     pub utxos_changed_subscription_to_all: DynSubscription,
+    /// Prefix every subscribed address must match, if any
+    pub address_prefix: Option<Prefix>,
 }
 
 impl SubscriptionContextInner {
     const CONTEXT_LISTENER_ID: ListenerId = ListenerId::MAX;
 
     pub fn new() -> Self {
-        Self::with_options(None)
+        Self::with_options(None, None)
     }
 
-    pub fn with_options(max_addresses: Option<usize>) -> Self {
+    pub fn with_options(max_addresses: Option<usize>, address_prefix: Option<Prefix>) -> Self {
         let address_tracker = Tracker::new(max_addresses);
         let utxos_changed_subscription_all = Arc::new(UtxosChangedSubscription::new(
             UtxosChangedState::All,
@@ -33,6 +36,7 @@ impl SubscriptionContextInner {
         Self {
             address_tracker,
             utxos_changed_subscription_to_all: utxos_changed_subscription_all,
+            address_prefix,
         }
     }
 
@@ -46,8 +50,22 @@ impl SubscriptionContextInner {
         Self {
             address_tracker,
             utxos_changed_subscription_to_all: utxos_changed_subscription_all,
+            address_prefix: None,
         }
     }
+
+    /// Checks that all `addresses` match the address prefix of the context, if any.
+    ///
+    /// Rejecting foreign addresses is essential since the tracker indexes addresses by
+    /// script public key, a representation which is blind to the network prefix.
+    pub fn check_address_prefix(&self, addresses: &[Address]) -> Result<()> {
+        if let Some(prefix) = self.address_prefix {
+            if let Some(address) = addresses.iter().find(|x| x.prefix != prefix) {
+                return Err(Error::PrefixMismatch(address.to_string(), prefix));
+            }
+        }
+        Ok(())
+    }
 }
 
 impl Default for SubscriptionContextInner {
@@ -67,7 +85,16 @@ impl SubscriptionContext {
     }
 
     pub fn with_options(max_addresses: Option<usize>) -> Self {
-        let inner = Arc::new(SubscriptionContextInner::with_options(max_addresses));
+        let inner = Arc::new(SubscriptionContextInner::with_options(max_addresses, None));
+        Self { inner }
+    }
+
+    /// Creates a context only accepting subscriptions to addresses matching `address_prefix`
+    pub fn with_address_prefix(max_addresses: Option<usize>, address_prefix: Prefix) -> Self {
+        let inner = Arc::new(SubscriptionContextInner::with_options(
+            max_addresses,
+            Some(address_prefix),
+        ));
         Self { inner }
     }
 
@@ -169,6 +196,24 @@ mod tests {
         measure_consumed_memory(item_len, num_items, ctor, length_and_capacity)
     }
 
+    #[test]
+    fn test_check_address_prefix() {
+        let addresses = create_addresses(2);
+        assert!(SubscriptionContext::new()
+            .check_address_prefix(&addresses)
+            .is_ok());
+        assert!(
+            SubscriptionContext::with_address_prefix(None, Prefix::Mainnet)
+                .check_address_prefix(&addresses)
+                .is_ok()
+        );
+        assert!(
+            SubscriptionContext::with_address_prefix(None, Prefix::Testnet)
+                .check_address_prefix(&addresses)
+                .is_err()
+        );
+    }
+
     #[test]
     #[ignore = "measuring consumed memory"]
     // ITEM = SubscriptionContext
@@ -1,14 +1,16 @@
 use crate::flowcontext::{
-    orphans::{OrphanBlocksPool, OrphanOutput},
+    clock::{ClockMonitor, ClockSkew},
+    orphans::{OrphanOutput, SharedOrphanBlocksPool},
+    pow_queue::PowVerificationQueue,
     process_queue::ProcessQueue,
-    transactions::TransactionsSpread,
+    transactions::{SharedTransactionAnnouncements, TransactionsSpread},
 };
 use crate::{v5, v6};
 use async_trait::async_trait;
 use futures::future::join_all;
 use karlsen_addressmanager::AddressManager;
 use karlsen_connectionmanager::ConnectionManager;
-use karlsen_consensus_core::api::{BlockValidationFuture, BlockValidationFutures};
+use karlsen_consensus_core::api::{BlockValidationFuture, BlockValidationFutures, PowPermit};
 use karlsen_consensus_core::block::Block;
 use karlsen_consensus_core::config::Config;
 use karlsen_consensus_core::errors::block::RuleError;
@@ -37,7 +39,9 @@ use karlsen_p2p_lib::{
     pb::{karlsend_message::Payload, InvRelayBlockMessage},
     ConnectionInitializer, Hub, KarlsendHandshake, PeerKey, PeerProperties, Router,
 };
+use karlsen_snapshot::SnapshotSource;
 use karlsen_utils::iter::IterExtensions;
+use karlsen_utils::mem_budget::memory_budget;
 use karlsen_utils::networking::PeerId;
 use parking_lot::{Mutex, RwLock};
 use std::collections::HashMap;
@@ -241,18 +245,24 @@ pub struct FlowContextInner {
     pub consensus_manager: Arc<ConsensusManager>,
     pub config: Arc<Config>,
     hub: Hub,
-    orphans_pool: AsyncRwLock<OrphanBlocksPool>,
+    orphans_pool: Arc<SharedOrphanBlocksPool>,
     shared_block_requests: Arc<Mutex<HashMap<Hash, RequestScopeMetadata>>>,
     transactions_spread: AsyncRwLock<TransactionsSpread>,
+    transaction_announcements: Arc<SharedTransactionAnnouncements>,
+    clock_monitor: Mutex<ClockMonitor>,
     shared_transaction_requests: Arc<Mutex<HashMap<TransactionId, RequestScopeMetadata>>>,
     is_ibd_running: Arc<AtomicBool>,
     ibd_metadata: Arc<RwLock<Option<IbdMetadata>>>,
     pub address_manager: Arc<Mutex<AddressManager>>,
     connection_manager: RwLock<Option<Arc<ConnectionManager>>>,
+    snapshot_source: RwLock<Option<Arc<SnapshotSource>>>,
     mining_manager: MiningManagerProxy,
     pub(crate) tick_service: Arc<TickService>,
     notification_root: Arc<ConsensusNotificationRoot>,
 
+    // Room left in the queue of headers awaiting proof of work verification
+    pow_verification_queue: PowVerificationQueue,
+
     // Special sampling logger used only for high-bps networks where logs must be throttled
     block_event_logger: Option<BlockEventLogger>,
 
@@ -343,22 +353,45 @@ impl FlowContext {
         // of how many orphans there can possibly be on average bounded by an upper bound.
         let max_orphans = (2u64.pow(orphan_resolution_range) as usize * config.ghostdag_k as usize)
             .min(MAX_ORPHANS_UPPER_BOUND);
+        let orphans_pool = Arc::new(SharedOrphanBlocksPool::new(max_orphans));
+        memory_budget().register("orphan blocks pool", None, orphans_pool.clone());
+        let transaction_announcements = Arc::new(SharedTransactionAnnouncements::default());
+        memory_budget().register(
+            "received transaction announcements",
+            None,
+            transaction_announcements.clone(),
+        );
+        let announced_transactions = Arc::new(SharedTransactionAnnouncements::default());
+        memory_budget().register(
+            "sent transaction announcements",
+            None,
+            announced_transactions.clone(),
+        );
         Self {
             inner: Arc::new(FlowContextInner {
                 node_id: Uuid::new_v4().into(),
                 consensus_manager,
-                orphans_pool: AsyncRwLock::new(OrphanBlocksPool::new(max_orphans)),
+                orphans_pool,
                 shared_block_requests: Arc::new(Mutex::new(HashMap::new())),
-                transactions_spread: AsyncRwLock::new(TransactionsSpread::new(hub.clone())),
+                transactions_spread: AsyncRwLock::new(TransactionsSpread::new(
+                    hub.clone(),
+                    announced_transactions,
+                )),
+                transaction_announcements,
+                clock_monitor: Default::default(),
                 shared_transaction_requests: Arc::new(Mutex::new(HashMap::new())),
                 is_ibd_running: Default::default(),
                 ibd_metadata: Default::default(),
                 hub,
                 address_manager,
                 connection_manager: Default::default(),
+                snapshot_source: Default::default(),
                 mining_manager,
                 tick_service,
                 notification_root,
+                pow_verification_queue: PowVerificationQueue::new(
+                    config.perf.pow_verification_queue_size,
+                ),
                 block_event_logger: if config.bps() > 1 {
                     Some(BlockEventLogger::new(config.bps() as usize))
                 } else {
@@ -383,6 +416,13 @@ impl FlowContext {
         self.max_orphans
     }
 
+    /// Waits for room for `count` headers in the queue of headers awaiting proof of work verification, so
+    /// a burst of headers from peers is held in their flows instead of piling up in consensus. Each header
+    /// is to be submitted along with one of the returned permits, see [`PowVerificationQueue::reserve`].
+    pub async fn reserve_pow_verifications(&self, count: usize) -> Vec<PowPermit> {
+        self.pow_verification_queue.reserve(count).await
+    }
+
     pub fn start_async_services(&self) {
         if let Some(logger) = self.block_event_logger.as_ref() {
             logger.start();
@@ -401,6 +441,19 @@ impl FlowContext {
         self.connection_manager.read().clone()
     }
 
+    /// Sets the snapshot server the IBDs with headers proof bootstrap from
+    pub fn set_snapshot_source(&self, snapshot_source: Arc<SnapshotSource>) {
+        self.snapshot_source.write().replace(snapshot_source);
+    }
+
+    /// Returns the snapshot source, unless none is set or it was disabled by a failure
+    pub fn snapshot_source(&self) -> Option<Arc<SnapshotSource>> {
+        self.snapshot_source
+            .read()
+            .clone()
+            .filter(|source| !source.is_disabled())
+    }
+
     pub fn consensus(&self) -> ConsensusInstance {
         self.consensus_manager.consensus()
     }
@@ -795,6 +848,78 @@ impl FlowContext {
         Ok(())
     }
 
+    /// Returns the count of distinct peers this node recently announced the transaction to
+    pub async fn transaction_announced_peer_count(&self, transaction_id: &TransactionId) -> usize {
+        self.transactions_spread
+            .read()
+            .await
+            .announced_peer_count(transaction_id)
+    }
+
+    /// Records the transactions announced by a peer
+    pub fn record_transaction_announcements(
+        &self,
+        peer: PeerId,
+        transaction_ids: &[TransactionId],
+    ) {
+        self.transaction_announcements
+            .lock()
+            .record(peer, transaction_ids);
+    }
+
+    /// Returns the count of distinct peers which recently announced the transaction
+    pub fn transaction_announcer_count(&self, transaction_id: &TransactionId) -> usize {
+        self.transaction_announcements
+            .lock()
+            .announcer_count(transaction_id)
+    }
+
+    /// Returns the measured skew of the local clock against the clocks of the network
+    pub fn clock_skew(&self) -> ClockSkew {
+        let peer_offsets = self
+            .hub
+            .active_peers()
+            .into_iter()
+            .map(|peer| peer.properties().time_offset);
+        self.clock_monitor.lock().skew(peer_offsets)
+    }
+
+    /// Sets the skew of the local clock against the network, in seconds, above which the node
+    /// warns (0 disables the warnings)
+    pub fn set_clock_skew_threshold(&self, threshold: u64) {
+        self.clock_monitor
+            .lock()
+            .set_threshold(threshold.saturating_mul(1000));
+    }
+
+    /// Returns true if the local clock is skewed by more than the configured threshold
+    pub fn is_clock_skewed(&self, skew: &ClockSkew) -> bool {
+        self.clock_monitor.lock().is_skewed(skew)
+    }
+
+    /// Samples the timestamp of a new relay block header and periodically checks the skew of
+    /// the local clock, warning if it exceeds the configured threshold
+    pub(crate) fn record_relay_header_timestamp(&self, timestamp: u64) {
+        if !self.clock_monitor.lock().record_header(timestamp) {
+            return;
+        }
+        let skew = self.clock_skew();
+        if !self.clock_monitor.lock().should_warn(&skew) {
+            return;
+        }
+        let offset = skew.max_offset().unwrap_or_default();
+        warn!(
+            "The local clock is {:.1} seconds {} the network (median offset of {} peers: {} ms, of {} recent blocks: {} ms), \
+            blocks mined or relayed by this node may be rejected. Please synchronize the system clock",
+            offset.unsigned_abs() as f64 / 1000.0,
+            if offset > 0 { "ahead of" } else { "behind" },
+            skew.peer_count,
+            skew.peer_offset.unwrap_or_default(),
+            skew.header_count,
+            skew.header_offset.unwrap_or_default(),
+        );
+    }
+
     /// Returns true if the time has come for running the task cleaning mempool transactions.
     async fn should_run_mempool_scanning_task(&self) -> bool {
         self.transactions_spread
@@ -0,0 +1,181 @@
+//! Monitoring of the local clock against the clock of the network.
+//!
+//! A node whose clock drifts mines block templates with timestamps the network rejects, and
+//! rejects or delays valid blocks itself. The skew of the local clock is estimated both from the
+//! timestamps peers send during the handshake and from the timestamps of the headers of recent
+//! relay blocks on their arrival, using the median of each so a few lying peers cannot move it.
+
+use karlsen_core::time::unix_now;
+use std::{
+    collections::VecDeque,
+    time::{Duration, Instant},
+};
+
+/// Count of recent relay block headers sampled
+const HEADER_SAMPLES: usize = 101;
+
+/// Minimum count of samples a measurement needs in order to raise a warning
+const MIN_WARNING_SAMPLES: usize = 3;
+
+/// Minimum interval between two checks of the skew triggered by relay blocks
+const CHECK_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Minimum interval between two warnings of a skewed clock
+const WARNING_INTERVAL: Duration = Duration::from_secs(10 * 60);
+
+/// Offsets of the local clock against the clock of the network, in milliseconds. A positive
+/// offset means the local clock is ahead.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ClockSkew {
+    /// Median offset against the clocks of the connected peers, as of their handshake
+    pub peer_offset: Option<i64>,
+    /// Count of peers the offset is measured on
+    pub peer_count: usize,
+    /// Median delay between the timestamps of recent relay block headers and their arrival,
+    /// which also includes the propagation time of the blocks
+    pub header_offset: Option<i64>,
+    /// Count of headers the offset is measured on
+    pub header_count: usize,
+}
+
+impl ClockSkew {
+    /// Returns the largest measured offset in absolute value, if measured on enough samples
+    pub fn max_offset(&self) -> Option<i64> {
+        let peer_offset = self
+            .peer_offset
+            .filter(|_| self.peer_count >= MIN_WARNING_SAMPLES);
+        let header_offset = self
+            .header_offset
+            .filter(|_| self.header_count >= MIN_WARNING_SAMPLES);
+        peer_offset
+            .into_iter()
+            .chain(header_offset)
+            .max_by_key(|offset| offset.unsigned_abs())
+    }
+
+    /// Returns true if the clock is skewed by more than `threshold` milliseconds
+    pub fn exceeds(&self, threshold: u64) -> bool {
+        self.max_offset()
+            .is_some_and(|offset| offset.unsigned_abs() > threshold)
+    }
+}
+
+#[derive(Default)]
+pub struct ClockMonitor {
+    /// Skew in milliseconds above which the clock is considered skewed, 0 disabling the warnings
+    threshold: u64,
+    header_offsets: VecDeque<i64>,
+    last_check: Option<Instant>,
+    last_warning: Option<Instant>,
+}
+
+impl ClockMonitor {
+    pub fn set_threshold(&mut self, threshold: u64) {
+        self.threshold = threshold;
+    }
+
+    /// Returns true if `skew` exceeds the threshold, which is never the case when disabled
+    pub fn is_skewed(&self, skew: &ClockSkew) -> bool {
+        self.threshold > 0 && skew.exceeds(self.threshold)
+    }
+
+    /// Records the arrival of a relay block header carrying `timestamp`, returning true if the
+    /// skew is due for a check
+    pub fn record_header(&mut self, timestamp: u64) -> bool {
+        self.record_header_offset(unix_now() as i64 - timestamp as i64);
+        if self
+            .last_check
+            .is_some_and(|last| last.elapsed() < CHECK_INTERVAL)
+        {
+            return false;
+        }
+        self.last_check = Some(Instant::now());
+        true
+    }
+
+    fn record_header_offset(&mut self, offset: i64) {
+        if self.header_offsets.len() == HEADER_SAMPLES {
+            self.header_offsets.pop_front();
+        }
+        self.header_offsets.push_back(offset);
+    }
+
+    /// Measures the skew of the local clock given the time offsets of the connected peers
+    pub fn skew(&self, peer_offsets: impl IntoIterator<Item = i64>) -> ClockSkew {
+        let peer_offsets: Vec<i64> = peer_offsets.into_iter().collect();
+        ClockSkew {
+            peer_offset: median(peer_offsets.clone()),
+            peer_count: peer_offsets.len(),
+            header_offset: median(self.header_offsets.iter().copied().collect()),
+            header_count: self.header_offsets.len(),
+        }
+    }
+
+    /// Returns true if a warning should be logged for `skew`, throttling the warnings of a
+    /// persistent skew
+    pub fn should_warn(&mut self, skew: &ClockSkew) -> bool {
+        if !self.is_skewed(skew) {
+            return false;
+        }
+        if self
+            .last_warning
+            .is_some_and(|last| last.elapsed() < WARNING_INTERVAL)
+        {
+            return false;
+        }
+        self.last_warning = Some(Instant::now());
+        true
+    }
+}
+
+fn median(mut values: Vec<i64>) -> Option<i64> {
+    if values.is_empty() {
+        return None;
+    }
+    let middle = values.len() / 2;
+    Some(*values.select_nth_unstable(middle).1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clock_skew() {
+        let mut monitor = ClockMonitor::default();
+
+        // Peers only: a lying minority does not move the median
+        let skew = monitor.skew([-100, 50, 30_000_000, 20, -40_000_000]);
+        assert_eq!(skew.peer_offset, Some(20));
+        assert_eq!(skew.header_offset, None);
+        assert!(!skew.exceeds(1_000));
+
+        // Fewer samples than required are not trusted
+        assert!(!monitor.skew([60_000, 60_000]).exceeds(1_000));
+        assert!(monitor.skew([60_000, 60_000, 60_000]).exceeds(1_000));
+
+        // The headers reveal a clock lagging behind while the peers are silent
+        for offset in [-45_000, -44_000, -46_000, 1_000_000] {
+            monitor.record_header_offset(offset);
+        }
+        let skew = monitor.skew([]);
+        assert_eq!(skew.header_offset, Some(-44_000));
+        assert_eq!(skew.max_offset(), Some(-44_000));
+        assert!(skew.exceeds(30_000));
+
+        // Warnings are disabled by a zero threshold, and throttled
+        assert!(!monitor.is_skewed(&skew));
+        assert!(!monitor.should_warn(&skew));
+        monitor.set_threshold(30_000);
+        assert!(monitor.is_skewed(&skew));
+        assert!(monitor.should_warn(&skew));
+        assert!(!monitor.should_warn(&skew));
+
+        // Old headers are evicted
+        for _ in 0..HEADER_SAMPLES {
+            monitor.record_header_offset(200);
+        }
+        assert_eq!(monitor.skew([]).header_offset, Some(200));
+        assert_eq!(monitor.skew([]).header_count, HEADER_SAMPLES);
+    }
+}
@@ -1,3 +1,5 @@
+pub mod clock;
 pub mod orphans;
+pub mod pow_queue;
 pub(crate) mod process_queue;
 pub mod transactions;
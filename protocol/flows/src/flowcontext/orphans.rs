@@ -2,16 +2,21 @@ use indexmap::{map::Entry::Occupied, IndexMap};
 use karlsen_consensus_core::{
     api::{BlockValidationFuture, BlockValidationFutures},
     block::Block,
+    header::Header,
+    tx::{Transaction, TransactionInput, TransactionOutput},
 };
 use karlsen_consensusmanager::{BlockProcessingBatch, ConsensusProxy};
 use karlsen_core::debug;
 use karlsen_hashes::Hash;
-use karlsen_utils::option::OptionExtensions;
+use karlsen_utils::{mem_budget::MemoryConsumer, option::OptionExtensions};
 use rand::Rng;
 use std::{
     collections::{HashMap, HashSet, VecDeque},
     iter::once,
+    mem::size_of,
+    ops::Deref,
 };
+use tokio::sync::RwLock as AsyncRwLock;
 
 use super::process_queue::ProcessQueue;
 
@@ -49,6 +54,31 @@ impl OrphanBlock {
     fn new(block: Block, children: HashSet<Hash>) -> Self {
         Self { block, children }
     }
+
+    /// Estimates the memory held by this orphan, signatures and scripts being counted at
+    /// their usual size
+    fn estimate_mem_bytes(&self) -> usize {
+        const NORMAL_SIG_SIZE: usize = 66;
+        let header = &self.block.header;
+        let parents = header
+            .parents_by_level
+            .iter()
+            .map(|x| x.len())
+            .sum::<usize>();
+        let (inputs, outputs) = self
+            .block
+            .transactions
+            .iter()
+            .fold((0, 0), |(ins, outs), tx| {
+                (ins + tx.inputs.len(), outs + tx.outputs.len())
+            });
+        size_of::<Header>()
+            + parents * size_of::<Hash>()
+            + self.block.transactions.len() * size_of::<Transaction>()
+            + inputs * (size_of::<TransactionInput>() + NORMAL_SIG_SIZE)
+            + outputs * size_of::<TransactionOutput>()
+            + self.children.len() * size_of::<Hash>()
+    }
 }
 
 pub struct OrphanBlocksPool {
@@ -154,6 +184,26 @@ impl OrphanBlocksPool {
         Some(OrphanOutput::Roots(roots))
     }
 
+    /// Returns the estimated memory held by the orphans, in bytes
+    pub fn estimate_mem_bytes(&self) -> usize {
+        self.orphans.values().map(|x| x.estimate_mem_bytes()).sum()
+    }
+
+    /// Evicts random orphans until the estimated memory they hold is no more than `target` bytes
+    pub fn evict_to(&mut self, target: usize) {
+        let mut usage = self.estimate_mem_bytes();
+        while usage > target && !self.orphans.is_empty() {
+            let rand_index = rand::thread_rng().gen_range(0..self.orphans.len());
+            if let Some((evicted, orphan)) = self.orphans.swap_remove_index(rand_index) {
+                debug!(
+                    "Evicted {} from the orphan blocks pool under memory pressure",
+                    evicted
+                );
+                usage -= orphan.estimate_mem_bytes();
+            }
+        }
+    }
+
     /// Returns whether this block is in the orphan pool.
     pub fn is_known_orphan(&self, hash: Hash) -> bool {
         self.orphans.contains_key(&hash)
@@ -358,6 +408,39 @@ impl OrphanBlocksPool {
     }
 }
 
+/// The orphan pool as shared with the process memory budget
+pub struct SharedOrphanBlocksPool(AsyncRwLock<OrphanBlocksPool>);
+
+impl SharedOrphanBlocksPool {
+    pub fn new(max_orphans: usize) -> Self {
+        Self(AsyncRwLock::new(OrphanBlocksPool::new(max_orphans)))
+    }
+}
+
+impl Deref for SharedOrphanBlocksPool {
+    type Target = AsyncRwLock<OrphanBlocksPool>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl MemoryConsumer for SharedOrphanBlocksPool {
+    fn memory_usage(&self) -> usize {
+        // The pool is skipped when busy, the budget being enforced periodically
+        self.0
+            .try_read()
+            .map(|pool| pool.estimate_mem_bytes())
+            .unwrap_or_default()
+    }
+
+    fn shrink_to(&self, target: usize) {
+        if let Ok(mut pool) = self.0.try_write() {
+            pool.evict_to(target);
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
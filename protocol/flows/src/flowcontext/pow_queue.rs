@@ -0,0 +1,95 @@
+use karlsen_consensus_core::api::PowPermit;
+use std::sync::Arc;
+use tokio::sync::Semaphore;
+
+/// Bounds the count of headers received from peers and awaiting proof of work verification.
+/// Flows reserve room before submitting headers and hand a permit to consensus along with each
+/// header, which drops it as soon as the proof of work of the header was checked.
+pub struct PowVerificationQueue {
+    room: Arc<Semaphore>,
+    size: usize,
+}
+
+impl PowVerificationQueue {
+    pub fn new(size: usize) -> Self {
+        let size = size.max(1);
+        Self {
+            room: Arc::new(Semaphore::new(size)),
+            size,
+        }
+    }
+
+    /// Waits for room for `count` headers and returns one permit per header.
+    ///
+    /// A reservation takes at most half of the queue, so a flow may hold two of them at once. The
+    /// permits exceeding that hold no room.
+    pub async fn reserve(&self, count: usize) -> Vec<PowPermit> {
+        let reserved = count.min((self.size / 2).max(1));
+        if reserved > 0 {
+            self.room
+                .acquire_many(reserved as u32)
+                .await
+                .expect("the queue is never closed")
+                .forget();
+        }
+        (0..count)
+            .map(|i| {
+                let room = (i < reserved).then(|| self.room.clone());
+                Box::new(HeaderRoom(room)) as PowPermit
+            })
+            .collect()
+    }
+
+    /// Returns the count of headers which can be reserved without waiting
+    pub fn available(&self) -> usize {
+        self.room.available_permits()
+    }
+}
+
+/// The room of a single header in the queue, given back when dropped
+struct HeaderRoom(Option<Arc<Semaphore>>);
+
+impl Drop for HeaderRoom {
+    fn drop(&mut self) {
+        if let Some(room) = self.0.take() {
+            room.add_permits(1);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+    use tokio::time::timeout;
+
+    #[tokio::test]
+    async fn test_pow_verification_queue() {
+        let queue = PowVerificationQueue::new(8);
+        let mut first = queue.reserve(4).await;
+        assert_eq!(first.len(), 4);
+        assert_eq!(queue.available(), 4);
+
+        // Reservations larger than half of the queue only hold half of it
+        let second = queue.reserve(6).await;
+        assert_eq!(second.len(), 6);
+        assert_eq!(queue.available(), 0);
+
+        // A full queue holds reservations back until the proof of work of some headers was checked
+        assert!(timeout(Duration::from_millis(50), queue.reserve(1))
+            .await
+            .is_err());
+        first.truncate(2);
+        assert_eq!(queue.available(), 2);
+        assert_eq!(queue.reserve(2).await.len(), 2);
+        assert_eq!(queue.available(), 2);
+
+        // Permits holding no room give none back
+        drop(second);
+        assert_eq!(queue.available(), 6);
+        drop(first);
+        assert_eq!(queue.available(), 8);
+        assert!(queue.reserve(0).await.is_empty());
+        assert_eq!(queue.available(), 8);
+    }
+}
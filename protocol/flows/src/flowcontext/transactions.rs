@@ -2,18 +2,30 @@ use super::process_queue::ProcessQueue;
 use itertools::Itertools;
 use karlsen_consensus_core::tx::TransactionId;
 use karlsen_core::debug;
+pub(crate) use karlsen_p2p_lib::convert::limits::MAX_INV_PER_TX_INV_MSG;
 use karlsen_p2p_lib::{
     make_message,
     pb::{karlsend_message::Payload, InvTransactionsMessage, KarlsendMessage},
     Hub,
 };
-use std::time::{Duration, Instant};
+use karlsen_utils::{mem_budget::MemoryConsumer, networking::PeerId};
+use parking_lot::Mutex;
+use std::{
+    collections::{HashMap, VecDeque},
+    mem::size_of,
+    ops::Deref,
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
 /// Interval between mempool scanning tasks (in seconds)
 const SCANNING_TASK_INTERVAL: u64 = 10;
 const REBROADCAST_FREQUENCY: u64 = 3;
 const BROADCAST_INTERVAL: Duration = Duration::from_millis(500);
-pub(crate) const MAX_INV_PER_TX_INV_MSG: usize = 131_072;
+/// Count of recently announced transactions whose announcing peers are remembered
+const MAX_ANNOUNCED_TRANSACTIONS: usize = 100_000;
+/// Count of distinct announcing peers remembered per transaction
+const MAX_ANNOUNCERS_PER_TRANSACTION: usize = 32;
 
 pub struct TransactionsSpread {
     hub: Hub,
@@ -22,10 +34,12 @@ pub struct TransactionsSpread {
     scanning_job_count: u64,
     transaction_ids: ProcessQueue<TransactionId>,
     last_broadcast_time: Instant,
+    /// The peers this node announced each recently broadcast transaction to
+    announced: Arc<SharedTransactionAnnouncements>,
 }
 
 impl TransactionsSpread {
-    pub fn new(hub: Hub) -> Self {
+    pub fn new(hub: Hub, announced: Arc<SharedTransactionAnnouncements>) -> Self {
         Self {
             hub,
             last_scanning_time: Instant::now(),
@@ -33,6 +47,7 @@ impl TransactionsSpread {
             scanning_job_count: 0,
             transaction_ids: ProcessQueue::new(),
             last_broadcast_time: Instant::now(),
+            announced,
         }
     }
 
@@ -104,20 +119,33 @@ impl TransactionsSpread {
             let ids = self
                 .transaction_ids
                 .dequeue_chunk(MAX_INV_PER_TX_INV_MSG)
-                .map(|x| x.into())
                 .collect_vec();
             debug!(
                 "Transaction propagation: broadcasting {} transactions",
                 ids.len()
             );
-            let msg = make_message!(Payload::InvTransactions, InvTransactionsMessage { ids });
-            self.broadcast(msg, should_throttle).await;
+            let msg = make_message!(
+                Payload::InvTransactions,
+                InvTransactionsMessage {
+                    ids: ids.iter().map(|x| x.into()).collect()
+                }
+            );
+            let peers = self.broadcast(msg, should_throttle).await;
+            let mut announced = self.announced.lock();
+            for peer in peers {
+                announced.record(peer, &ids);
+            }
         }
 
         self.last_broadcast_time = Instant::now();
     }
 
-    async fn broadcast(&self, msg: KarlsendMessage, should_throttle: bool) {
+    /// Returns the count of distinct peers this node announced the transaction to, up to a cap
+    pub fn announced_peer_count(&self, transaction_id: &TransactionId) -> usize {
+        self.announced.lock().announcer_count(transaction_id)
+    }
+
+    async fn broadcast(&self, msg: KarlsendMessage, should_throttle: bool) -> Vec<PeerId> {
         if should_throttle {
             // TODO: Figure out a better number
             self.hub.broadcast_to_some_peers(msg, 8).await
@@ -126,3 +154,119 @@ impl TransactionsSpread {
         }
     }
 }
+
+/// The peers associated with the announcements of recently relayed transactions, either the
+/// peers which announced them to this node, measuring how widely they propagated through the
+/// network, or the peers this node announced them to
+#[derive(Default)]
+pub struct TransactionAnnouncements {
+    announcers: HashMap<TransactionId, Vec<PeerId>>,
+    /// Announcement order, used for forgetting the oldest transactions
+    order: VecDeque<TransactionId>,
+    /// Count of recorded peers over all the transactions
+    peer_count: usize,
+}
+
+impl TransactionAnnouncements {
+    pub fn record(&mut self, peer: PeerId, transaction_ids: &[TransactionId]) {
+        for transaction_id in transaction_ids.iter().copied() {
+            let announcers = self.announcers.entry(transaction_id).or_insert_with(|| {
+                self.order.push_back(transaction_id);
+                Vec::new()
+            });
+            if announcers.len() < MAX_ANNOUNCERS_PER_TRANSACTION && !announcers.contains(&peer) {
+                announcers.push(peer);
+                self.peer_count += 1;
+            }
+        }
+        while self.order.len() > MAX_ANNOUNCED_TRANSACTIONS {
+            self.forget_oldest();
+        }
+    }
+
+    fn forget_oldest(&mut self) {
+        if let Some(forgotten) = self.order.pop_front() {
+            if let Some(announcers) = self.announcers.remove(&forgotten) {
+                self.peer_count -= announcers.len();
+            }
+        }
+    }
+
+    /// Returns the estimated memory held by the announcements, in bytes
+    pub fn estimate_mem_bytes(&self) -> usize {
+        // Each transaction id is held by both the map and the order queue
+        self.order.len() * (2 * size_of::<TransactionId>() + size_of::<Vec<PeerId>>())
+            + self.peer_count * size_of::<PeerId>()
+    }
+
+    /// Forgets the oldest transactions until the estimated memory held is no more than `target` bytes
+    pub fn forget_to(&mut self, target: usize) {
+        while self.estimate_mem_bytes() > target && !self.order.is_empty() {
+            self.forget_oldest();
+        }
+    }
+
+    /// Returns the count of distinct peers which announced the transaction, up to a cap
+    pub fn announcer_count(&self, transaction_id: &TransactionId) -> usize {
+        self.announcers
+            .get(transaction_id)
+            .map_or(0, |announcers| announcers.len())
+    }
+}
+
+/// Transaction announcements as shared with the process memory budget
+#[derive(Default)]
+pub struct SharedTransactionAnnouncements(Mutex<TransactionAnnouncements>);
+
+impl Deref for SharedTransactionAnnouncements {
+    type Target = Mutex<TransactionAnnouncements>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl MemoryConsumer for SharedTransactionAnnouncements {
+    fn memory_usage(&self) -> usize {
+        self.0.lock().estimate_mem_bytes()
+    }
+
+    fn shrink_to(&self, target: usize) {
+        self.0.lock().forget_to(target);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use karlsen_hashes::Hash;
+
+    #[test]
+    fn test_transaction_announcements() {
+        let peer = |byte: u8| PeerId::from_slice(&[byte; 16]).unwrap();
+        let mut announcements = TransactionAnnouncements::default();
+        let ids = [Hash::from_u64_word(1), Hash::from_u64_word(2)];
+        announcements.record(peer(1), &ids);
+        announcements.record(peer(2), &ids[..1]);
+        announcements.record(peer(1), &ids[..1]);
+        assert_eq!(announcements.announcer_count(&ids[0]), 2);
+        assert_eq!(announcements.announcer_count(&ids[1]), 1);
+        assert_eq!(announcements.announcer_count(&Hash::from_u64_word(3)), 0);
+
+        for byte in 3..=u8::MAX {
+            announcements.record(peer(byte), &ids[..1]);
+        }
+        assert_eq!(
+            announcements.announcer_count(&ids[0]),
+            MAX_ANNOUNCERS_PER_TRANSACTION
+        );
+
+        // Shrinking forgets the oldest transactions first
+        let usage = announcements.estimate_mem_bytes();
+        announcements.forget_to(usage - 1);
+        assert_eq!(announcements.announcer_count(&ids[0]), 0);
+        assert_eq!(announcements.announcer_count(&ids[1]), 1);
+        announcements.forget_to(0);
+        assert_eq!(announcements.estimate_mem_bytes(), 0);
+    }
+}
@@ -14,10 +14,6 @@ use std::sync::Arc;
 /// The maximum number of addresses that are sent in a single karlsen Addresses message.
 const MAX_ADDRESSES_SEND: usize = 1000;
 
-/// The maximum number of addresses that can be received in a single karlsen Addresses response.
-/// If a peer exceeds this value we consider it a protocol error.
-const MAX_ADDRESSES_RECEIVE: usize = 2500;
-
 pub struct ReceiveAddressesFlow {
     ctx: FlowContext,
     router: Arc<Router>,
@@ -56,14 +52,8 @@ impl ReceiveAddressesFlow {
             .await?;
 
         let msg = dequeue_with_timeout!(self.incoming_route, Payload::Addresses)?;
+        // Responses with more than `MAX_ADDRESSES_PER_MSG` addresses are rejected by the conversion
         let address_list: Vec<(IpAddress, u16)> = msg.try_into()?;
-        if address_list.len() > MAX_ADDRESSES_RECEIVE {
-            return Err(ProtocolError::OtherOwned(format!(
-                "address count {} exceeded {}",
-                address_list.len(),
-                MAX_ADDRESSES_RECEIVE
-            )));
-        }
         let mut amgr_lock = self.ctx.address_manager.lock();
         for (ip, port) in address_list {
             amgr_lock.add_address(NetAddress::new(ip, port))
@@ -193,10 +193,17 @@ impl HandleRelayInvsFlow {
                 continue;
             }
 
+            // Orphan roots are older blocks, so only direct relays tell the time of the network
+            if !inv.is_orphan_root {
+                self.ctx
+                    .record_relay_header_timestamp(block.header.timestamp);
+            }
+
+            let pow_permit = self.ctx.reserve_pow_verifications(1).await.remove(0);
             let BlockValidationFutures {
                 block_task,
                 mut virtual_state_task,
-            } = session.validate_and_insert_block(block.clone());
+            } = session.validate_and_insert_block_with_pow_permit(block.clone(), pow_permit);
 
             let ancestor_batch = match block_task.await {
                 Ok(_) => Default::default(),
@@ -246,6 +253,11 @@ impl HandleRelayInvsFlow {
                 Err(rule_error) => return Err(rule_error.into()),
             };
 
+            // The block was requested from this peer only, so it was the first to announce it
+            if !inv.is_orphan_root {
+                self.router.record_block_first_announced();
+            }
+
             // As a policy, we only relay blocks who stand a chance to enter past(virtual).
             // The only mining rule which permanently excludes a block is the merge depth bound
             // (as opposed to "max parents" and "mergeset size limit" rules)
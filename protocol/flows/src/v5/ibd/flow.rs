@@ -28,6 +28,7 @@ use karlsen_p2p_lib::{
     },
     IncomingRoute, Router,
 };
+use karlsen_snapshot::{SnapshotError, SnapshotManifest, SnapshotSource};
 use karlsen_utils::channel::JobReceiver;
 use std::{
     sync::Arc,
@@ -39,6 +40,9 @@ use super::{
     progress::ProgressReporter, HeadersChunk, PruningPointUtxosetChunkStream, IBD_BATCH_SIZE,
 };
 
+/// A snapshot server and the manifest of the snapshot the IBD bootstraps from
+type Snapshot = (Arc<SnapshotSource>, SnapshotManifest);
+
 /// Flow for managing IBD - Initial Block Download
 pub struct IbdFlow {
     pub(super) ctx: FlowContext,
@@ -265,8 +269,23 @@ impl IbdFlow {
 
         let staging_session = staging.session().await;
 
+        let mut snapshot = None;
+        if let Some(source) = self.ctx.snapshot_source() {
+            match source.fetch_manifest().await {
+                Ok(manifest) => {
+                    info!(
+                        "IBD: bootstrapping from the snapshot of {} served at {}",
+                        manifest.pruning_point,
+                        source.url()
+                    );
+                    snapshot = Some((source, manifest));
+                }
+                Err(err) => source.disable(&err),
+            }
+        }
+
         let pruning_point = self
-            .sync_and_validate_pruning_proof(&staging_session)
+            .sync_and_validate_pruning_proof(&staging_session, &mut snapshot)
             .await?;
         self.sync_headers(
             &staging_session,
@@ -278,15 +297,30 @@ impl IbdFlow {
         staging_session.async_validate_pruning_points().await?;
         self.validate_staging_timestamps(&self.ctx.consensus().session().await, &staging_session)
             .await?;
-        self.sync_pruning_point_utxoset(&staging_session, pruning_point)
+        self.sync_pruning_point_utxoset(&staging_session, pruning_point, snapshot)
             .await?;
         Ok(())
     }
 
-    async fn sync_and_validate_pruning_proof(
+    /// Fetches the pruning point proof from the snapshot if any, falling back to the peer if
+    /// the snapshot fails
+    async fn fetch_pruning_proof(
         &mut self,
-        staging: &ConsensusProxy,
-    ) -> Result<Hash, ProtocolError> {
+        snapshot: &mut Option<Snapshot>,
+    ) -> Result<PruningPointProof, ProtocolError> {
+        if let Some((source, manifest)) = snapshot.as_ref() {
+            match source.fetch_proof(manifest).await {
+                Ok(proof) => return Ok(proof),
+                Err(err) => {
+                    source.disable(&err);
+                    *snapshot = None;
+                }
+            }
+        }
+        self.request_pruning_proof().await
+    }
+
+    async fn request_pruning_proof(&mut self) -> Result<PruningPointProof, ProtocolError> {
         self.router
             .enqueue(make_message!(
                 Payload::RequestPruningPointProof,
@@ -300,7 +334,15 @@ impl IbdFlow {
             Payload::PruningPointProof,
             Duration::from_secs(600)
         )?;
-        let proof: PruningPointProof = msg.try_into()?;
+        Ok(msg.try_into()?)
+    }
+
+    /// Validates the proof in the context of the current consensus, returning it along with its
+    /// pruning point
+    async fn validate_pruning_proof(
+        &self,
+        proof: PruningPointProof,
+    ) -> Result<(PruningPointProof, Hash), ProtocolError> {
         debug!(
             "received proof with overall {} headers",
             proof.iter().map(|l| l.len()).sum::<usize>()
@@ -332,7 +374,32 @@ impl IbdFlow {
             ));
         }
 
-        drop(consensus);
+        Ok((proof, proof_pruning_point))
+    }
+
+    async fn sync_and_validate_pruning_proof(
+        &mut self,
+        staging: &ConsensusProxy,
+        snapshot: &mut Option<Snapshot>,
+    ) -> Result<Hash, ProtocolError> {
+        let proof = self.fetch_pruning_proof(snapshot).await?;
+        let (mut proof, mut proof_pruning_point) = match self.validate_pruning_proof(proof).await {
+            Ok(validated) => validated,
+            // A snapshot proof may be invalid or as old as the current pruning point, in which
+            // case the proof of the peer is used instead
+            Err(err) if snapshot.is_some() => {
+                let (source, _) = snapshot.take().unwrap();
+                source.disable(&SnapshotError::InvalidSnapshot(format!(
+                    "unusable pruning point proof: {err}"
+                )));
+                warn!(
+                    "IBD: the snapshot pruning point proof is unusable ({err}), falling back to the proof of the peer"
+                );
+                let proof = self.request_pruning_proof().await?;
+                self.validate_pruning_proof(proof).await?
+            }
+            Err(err) => return Err(err),
+        };
 
         self.router
             .enqueue(make_message!(
@@ -344,7 +411,35 @@ impl IbdFlow {
         let msg = dequeue_with_timeout!(self.incoming_route, Payload::PruningPoints)?;
         let pruning_points: PruningPointsList = msg.try_into()?;
 
-        if pruning_points.is_empty() || pruning_points.last().unwrap().hash != proof_pruning_point {
+        let Some(peer_pruning_point) = pruning_points.last().map(|header| header.hash) else {
+            return Err(ProtocolError::Other(
+                "the proof pruning point is not equal to the last pruning point in the list",
+            ));
+        };
+
+        // A snapshot taken before the latest pruning point of the peer is stale. The anticone
+        // and the headers the peer sends are those of its own pruning point, so the proof is
+        // requested from the peer once the trusted data below is received
+        let mut stale_snapshot = false;
+        if let Some((source, manifest)) = snapshot.as_ref() {
+            if manifest.pruning_point != peer_pruning_point
+                || proof_pruning_point != peer_pruning_point
+            {
+                source.disable(&SnapshotError::PruningPointMismatch(
+                    manifest.pruning_point,
+                    peer_pruning_point,
+                ));
+                warn!(
+                    "IBD: the snapshot pruning point {} is not the pruning point {} of the peer, falling back to the proof of the peer",
+                    manifest.pruning_point, peer_pruning_point
+                );
+                *snapshot = None;
+                stale_snapshot = true;
+                proof_pruning_point = peer_pruning_point;
+            }
+        }
+
+        if peer_pruning_point != proof_pruning_point {
             return Err(ProtocolError::Other(
                 "the proof pruning point is not equal to the last pruning point in the list",
             ));
@@ -397,6 +492,19 @@ impl IbdFlow {
             entries.push(entry);
         }
 
+        // The trusted data stream is complete, so the proof of the peer can now be requested
+        if stale_snapshot {
+            let peer_proof = self.request_pruning_proof().await?;
+            let (peer_proof, peer_proof_pruning_point) =
+                self.validate_pruning_proof(peer_proof).await?;
+            if peer_proof_pruning_point != peer_pruning_point {
+                return Err(ProtocolError::Other(
+                    "the proof pruning point is not equal to the last pruning point in the list",
+                ));
+            }
+            proof = peer_proof;
+        }
+
         let mut trusted_set = pkg.build_trusted_subdag(entries)?;
 
         if self.ctx.config.enable_sanity_checks {
@@ -509,11 +617,16 @@ impl IbdFlow {
                 let last_header = chunk.last().expect("chunk is never empty");
                 (last_header.daa_score, last_header.timestamp)
             };
+            let permits = self.ctx.reserve_pow_verifications(chunk.len()).await;
             let mut prev_jobs: Vec<BlockValidationFuture> = chunk
                 .into_iter()
-                .map(|h| {
+                .zip(permits)
+                .map(|(h, permit)| {
                     consensus
-                        .validate_and_insert_block(Block::from_header_arc(h))
+                        .validate_and_insert_block_with_pow_permit(
+                            Block::from_header_arc(h),
+                            permit,
+                        )
                         .virtual_state_task
                 })
                 .collect();
@@ -523,11 +636,17 @@ impl IbdFlow {
                     let last_header = chunk.last().expect("chunk is never empty");
                     (last_header.daa_score, last_header.timestamp)
                 };
+                // Stop reading from the peer while the verification queue is full
+                let permits = self.ctx.reserve_pow_verifications(chunk.len()).await;
                 let current_jobs = chunk
                     .into_iter()
-                    .map(|h| {
+                    .zip(permits)
+                    .map(|(h, permit)| {
                         consensus
-                            .validate_and_insert_block(Block::from_header_arc(h))
+                            .validate_and_insert_block_with_pow_permit(
+                                Block::from_header_arc(h),
+                                permit,
+                            )
                             .virtual_state_task
                     })
                     .collect();
@@ -587,11 +706,13 @@ impl IbdFlow {
 
         let msg = dequeue_with_timeout!(self.incoming_route, Payload::BlockHeaders)?;
         let chunk: HeadersChunk = msg.try_into()?;
+        let permits = self.ctx.reserve_pow_verifications(chunk.len()).await;
         let jobs: Vec<BlockValidationFuture> = chunk
             .into_iter()
-            .map(|h| {
+            .zip(permits)
+            .map(|(h, permit)| {
                 consensus
-                    .validate_and_insert_block(Block::from_header_arc(h))
+                    .validate_and_insert_block_with_pow_permit(Block::from_header_arc(h), permit)
                     .virtual_state_task
             })
             .collect();
@@ -644,7 +765,21 @@ staging selected tip ({}) is too small or negative. Aborting IBD...",
         &mut self,
         consensus: &ConsensusProxy,
         pruning_point: Hash,
+        snapshot: Option<Snapshot>,
     ) -> Result<(), ProtocolError> {
+        if let Some((source, manifest)) = snapshot {
+            if manifest.pruning_point == pruning_point {
+                return self
+                    .sync_pruning_point_utxoset_from_snapshot(
+                        consensus,
+                        pruning_point,
+                        &source,
+                        &manifest,
+                    )
+                    .await;
+            }
+        }
+
         self.router
             .enqueue(make_message!(
                 Payload::RequestPruningPointUtxoSet,
@@ -672,6 +807,55 @@ staging selected tip ({}) is too small or negative. Aborting IBD...",
         Ok(())
     }
 
+    /// Imports the UTXO set of the snapshot. If the import fails, the entries already appended
+    /// are discarded along with the staging consensus and the next IBD fetches the UTXO set from
+    /// a peer.
+    async fn sync_pruning_point_utxoset_from_snapshot(
+        &mut self,
+        consensus: &ConsensusProxy,
+        pruning_point: Hash,
+        source: &SnapshotSource,
+        manifest: &SnapshotManifest,
+    ) -> Result<(), ProtocolError> {
+        let mut multiset = MuHash::new();
+        let mut utxo_count = 0;
+        for index in 0..manifest.utxo_set.len() {
+            let chunk = match source.fetch_utxo_chunk(manifest, index).await {
+                Ok(chunk) => chunk,
+                Err(err) => {
+                    source.disable(&err);
+                    return Err(ProtocolError::OtherOwned(format!(
+                        "fetching the UTXO set of the snapshot failed: {err}"
+                    )));
+                }
+            };
+            utxo_count += chunk.len();
+            multiset = consensus
+                .clone()
+                .spawn_blocking(move |c| {
+                    c.append_imported_pruning_point_utxos(&chunk, &mut multiset);
+                    multiset
+                })
+                .await;
+            debug!(
+                "IBD: imported {} of the {} UTXO entries of the snapshot",
+                utxo_count, manifest.utxo_count
+            );
+        }
+        info!("Finished importing the UTXO set of the snapshot. Total UTXOs: {utxo_count}");
+        if let Err(err) = consensus
+            .clone()
+            .spawn_blocking(move |c| c.import_pruning_point_utxo_set(pruning_point, multiset))
+            .await
+        {
+            source.disable(&SnapshotError::InvalidSnapshot(format!(
+                "invalid UTXO set: {err}"
+            )));
+            return Err(err.into());
+        }
+        Ok(())
+    }
+
     async fn sync_missing_block_bodies(
         &mut self,
         consensus: &ConsensusProxy,
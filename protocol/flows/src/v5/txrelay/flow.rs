@@ -127,6 +127,9 @@ impl RelayTransactionsFlow {
                 ));
             }
 
+            self.ctx
+                .record_transaction_announcements(self.router.identity(), &inv);
+
             let session = self.ctx.consensus().unguarded_session();
 
             // Transaction relay is disabled if the node is out of sync and thus not mining
@@ -290,6 +293,9 @@ impl RelayTransactionsFlow {
                 Err(_) => {}
             }
         }
+        self.router.record_transactions_relayed(
+            insert_results.iter().filter(|res| res.is_ok()).count() as u64,
+        );
 
         self.ctx
             .broadcast_transactions(
@@ -0,0 +1,5 @@
+#!/bin/sh -ex
+rustc --version
+cargo install cargo-fuzz
+
+cargo fuzz run message --release -- -use_counters=1 -use_value_profile=1 -max_len=1048576 "$@" ../../../../rusty-karlsen-corpus/p2p/message/
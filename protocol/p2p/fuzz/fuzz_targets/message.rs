@@ -0,0 +1,117 @@
+#![no_main]
+//!
+//! Feeds arbitrary bytes to the p2p message decoding, then converts the decoded payload the way the flow
+//! handling it does. Malformed or oversized messages must be rejected with an error, never panic.
+//!
+
+use karlsen_consensus_core::{
+    block::Block,
+    header::Header,
+    pruning::{PruningPointProof, PruningPointsList},
+    tx::{Transaction, TransactionId, TransactionOutpoint, UtxoEntry},
+};
+use karlsen_hashes::Hash;
+use karlsen_p2p_lib::{
+    convert::{
+        error::ConversionError,
+        model::{
+            trusted::{TrustedDataEntry, TrustedDataPackage},
+            version::Version,
+        },
+    },
+    pb::{karlsend_message::Payload, KarlsendMessage},
+};
+use karlsen_utils::networking::IpAddress;
+use libfuzzer_sys::fuzz_target;
+use prost::Message;
+use std::sync::Arc;
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(message) = KarlsendMessage::decode(data) else {
+        return;
+    };
+    if let Some(payload) = message.payload {
+        let _ = convert(payload);
+    }
+});
+
+fn convert(payload: Payload) -> Result<(), ConversionError> {
+    match payload {
+        Payload::Addresses(msg) => {
+            let _: Vec<(IpAddress, u16)> = msg.try_into()?;
+        }
+        Payload::Block(msg) | Payload::IbdBlock(msg) => {
+            let block = Block::try_from(msg)?;
+            let _ = block.hash();
+        }
+        Payload::Transaction(msg) => {
+            let tx = Transaction::try_from(msg)?;
+            let _ = tx.id();
+        }
+        Payload::BlockLocator(msg) => {
+            let _: Vec<Hash> = msg.try_into()?;
+        }
+        Payload::RequestRelayBlocks(msg) => {
+            let _: Vec<Hash> = msg.try_into()?;
+        }
+        Payload::RequestTransactions(msg) => {
+            let _: Vec<TransactionId> = msg.try_into()?;
+        }
+        Payload::InvRelayBlock(msg) => {
+            let _: Hash = msg.try_into()?;
+        }
+        Payload::InvTransactions(msg) => {
+            let _: Vec<TransactionId> = msg.try_into()?;
+        }
+        Payload::Version(msg) => {
+            let _: Version = msg.try_into()?;
+        }
+        Payload::TransactionNotFound(msg) => {
+            let _: TransactionId = msg.try_into()?;
+        }
+        Payload::PruningPointUtxoSetChunk(msg) => {
+            let _: Vec<(TransactionOutpoint, UtxoEntry)> = msg.try_into()?;
+        }
+        Payload::RequestIbdBlocks(msg) => {
+            let _: Vec<Hash> = msg.try_into()?;
+        }
+        Payload::BlockWithTrustedDataV4(msg) => {
+            let _: TrustedDataEntry = msg.try_into()?;
+        }
+        Payload::BlockHeaders(msg) => {
+            let _: Vec<Arc<Header>> = msg.try_into()?;
+        }
+        Payload::RequestPruningPointUtxoSet(msg) => {
+            let _: Hash = msg.try_into()?;
+        }
+        Payload::RequestHeaders(msg) => {
+            let _: (Hash, Hash) = msg.try_into()?;
+        }
+        Payload::RequestBlockLocator(msg) => {
+            let _: (Hash, u32) = msg.try_into()?;
+        }
+        Payload::PruningPoints(msg) => {
+            let _: PruningPointsList = msg.try_into()?;
+        }
+        Payload::PruningPointProof(msg) => {
+            let _: PruningPointProof = msg.try_into()?;
+        }
+        Payload::TrustedData(msg) => {
+            // The IBD flow builds the trusted sub-DAG from the package and the entries following it
+            let package = TrustedDataPackage::try_from(msg)?;
+            let _ = package.build_trusted_subdag(vec![]);
+        }
+        Payload::RequestIbdChainBlockLocator(msg) => {
+            let _: (Option<Hash>, Option<Hash>) = msg.try_into()?;
+        }
+        Payload::IbdChainBlockLocator(msg) => {
+            let _: Vec<Hash> = msg.try_into()?;
+        }
+        Payload::RequestAntipast(msg) => {
+            let _: (Hash, Hash) = msg.try_into()?;
+        }
+        // The other messages carry no data converted by their flows
+        _ => {}
+    }
+    Ok(())
+}
@@ -12,6 +12,12 @@ pub enum ConversionError {
     #[error("IP has illegal length {0}")]
     IllegalIPLength(usize),
 
+    #[error("{0} has {1} items, exceeding the maximum of {2}")]
+    TooManyItems(&'static str, usize, usize),
+
+    #[error("{0} has length {1}, exceeding the maximum of {2}")]
+    FieldTooLong(&'static str, usize, usize),
+
     #[error("Bytes size mismatch error {0}")]
     ArrayBytesSizeError(#[from] std::array::TryFromSliceError),
 
@@ -1,4 +1,8 @@
-use super::{error::ConversionError, option::TryIntoOptionEx};
+use super::{
+    error::ConversionError,
+    limits::{check_count, MAX_MERGESET_SIZE},
+    option::TryIntoOptionEx,
+};
 use crate::pb as protowire;
 use karlsen_consensus_core::{
     trusted::{ExternalGhostdagData, TrustedGhostdagData, TrustedHeader},
@@ -48,6 +52,16 @@ impl TryFrom<protowire::BluesAnticoneSizes> for (Hash, KType) {
 impl TryFrom<protowire::GhostdagData> for ExternalGhostdagData {
     type Error = ConversionError;
     fn try_from(item: protowire::GhostdagData) -> Result<Self, Self::Error> {
+        check_count(
+            "mergeset",
+            item.merge_set_blues.len() + item.merge_set_reds.len(),
+            MAX_MERGESET_SIZE,
+        )?;
+        check_count(
+            "blues anticone sizes",
+            item.blues_anticone_sizes.len(),
+            MAX_MERGESET_SIZE,
+        )?;
         let mut blues_anticone_sizes =
             BlockHashMap::<KType>::with_capacity(item.blues_anticone_sizes.len());
         for res in item
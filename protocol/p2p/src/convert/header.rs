@@ -3,6 +3,7 @@ use karlsen_consensus_core::{header::Header, BlueWorkType};
 use karlsen_hashes::Hash;
 
 use super::error::ConversionError;
+use super::limits::{check_count, MAX_HEADER_PARENT_LEVELS, MAX_PARENTS_PER_LEVEL};
 use super::option::TryIntoOptionEx;
 
 // ----------------------------------------------------------------------------
@@ -51,6 +52,11 @@ impl From<&Vec<Hash>> for protowire::BlockLevelParents {
 impl TryFrom<protowire::BlockHeader> for Header {
     type Error = ConversionError;
     fn try_from(item: protowire::BlockHeader) -> Result<Self, Self::Error> {
+        check_count(
+            "header parent levels",
+            item.parents.len(),
+            MAX_HEADER_PARENT_LEVELS,
+        )?;
         Ok(Self::new_finalized(
             item.version.try_into()?,
             item.parents
@@ -75,6 +81,11 @@ impl TryFrom<protowire::BlockHeader> for Header {
 impl TryFrom<protowire::BlockLevelParents> for Vec<Hash> {
     type Error = ConversionError;
     fn try_from(item: protowire::BlockLevelParents) -> Result<Self, Self::Error> {
+        check_count(
+            "level parents",
+            item.parent_hashes.len(),
+            MAX_PARENTS_PER_LEVEL,
+        )?;
         item.parent_hashes
             .into_iter()
             .map(|x| x.try_into())
@@ -0,0 +1,137 @@
+//!
+//! Bounds on the lengths of the fields and lists of the p2p messages, checked before their content is
+//! converted to consensus types. A message breaking any of them cannot be valid, so it is rejected without
+//! allocating or hashing the items it carries.
+//!
+
+use super::error::ConversionError;
+
+/// Maximum length of the network name of a version message
+pub const MAX_NETWORK_NAME_LEN: usize = 64;
+
+/// Maximum count of parent levels of a header. Block levels are bytes, so no header has more.
+pub const MAX_HEADER_PARENT_LEVELS: usize = 256;
+
+/// Maximum count of parents of a header at a single level, well above the parents carried by valid headers
+pub const MAX_PARENTS_PER_LEVEL: usize = 1024;
+
+/// Maximum count of levels of a pruning point proof, one per block level
+pub const MAX_PRUNING_PROOF_LEVELS: usize = MAX_HEADER_PARENT_LEVELS;
+
+/// Maximum count of blocks in the mergeset of a ghostdag data entry, well above the mergeset size limit of
+/// every network
+pub const MAX_MERGESET_SIZE: usize = 4096;
+
+/// Maximum count of hashes of a block locator or of a block request
+pub const MAX_HASHES_PER_MSG: usize = 1 << 16;
+
+/// Maximum count of headers of a block headers message
+pub const MAX_HEADERS_PER_MSG: usize = 1 << 16;
+
+/// Maximum count of entries of a pruning point UTXO set chunk
+pub const MAX_UTXOS_PER_CHUNK: usize = 1 << 16;
+
+/// Maximum count of transaction ids of a transaction inventory or request
+pub const MAX_INV_PER_TX_INV_MSG: usize = 131_072;
+
+/// Maximum count of addresses of an addresses message
+pub const MAX_ADDRESSES_PER_MSG: usize = 2500;
+
+/// Fails if the list `field` has more than `max` items
+pub(crate) fn check_count(
+    field: &'static str,
+    count: usize,
+    max: usize,
+) -> Result<(), ConversionError> {
+    if count > max {
+        return Err(ConversionError::TooManyItems(field, count, max));
+    }
+    Ok(())
+}
+
+/// Fails if the field `field` is longer than `max` bytes
+pub(crate) fn check_length(
+    field: &'static str,
+    length: usize,
+    max: usize,
+) -> Result<(), ConversionError> {
+    if length > max {
+        return Err(ConversionError::FieldTooLong(field, length, max));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pb as protowire;
+    use karlsen_consensus_core::header::Header;
+    use karlsen_hashes::Hash;
+    use karlsen_utils::networking::IpAddress;
+
+    fn hashes(count: usize) -> Vec<protowire::Hash> {
+        (0..count as u64)
+            .map(|i| Hash::from_u64_word(i).into())
+            .collect()
+    }
+
+    #[test]
+    fn test_oversized_messages_are_rejected() {
+        let header: protowire::BlockHeader =
+            (&Header::from_precomputed_hash(Hash::from_u64_word(1), vec![])).into();
+
+        let mut too_many_levels = header.clone();
+        too_many_levels.parents = vec![
+            protowire::BlockLevelParents {
+                parent_hashes: hashes(1)
+            };
+            MAX_HEADER_PARENT_LEVELS + 1
+        ];
+        assert!(matches!(
+            Header::try_from(too_many_levels),
+            Err(ConversionError::TooManyItems("header parent levels", _, _))
+        ));
+
+        let mut too_many_parents = header.clone();
+        too_many_parents.parents = vec![protowire::BlockLevelParents {
+            parent_hashes: hashes(MAX_PARENTS_PER_LEVEL + 1),
+        }];
+        assert!(matches!(
+            Header::try_from(too_many_parents),
+            Err(ConversionError::TooManyItems("level parents", _, _))
+        ));
+
+        let mut long_blue_work = header.clone();
+        long_blue_work.blue_work = vec![1; 1024];
+        assert!(Header::try_from(long_blue_work).is_err());
+
+        let mut parents_within_bounds = header;
+        parents_within_bounds.parents = vec![protowire::BlockLevelParents {
+            parent_hashes: hashes(MAX_PARENTS_PER_LEVEL),
+        }];
+        assert!(Header::try_from(parents_within_bounds).is_ok());
+
+        let locator = protowire::BlockLocatorMessage {
+            hashes: hashes(MAX_HASHES_PER_MSG + 1),
+        };
+        assert!(matches!(
+            Vec::<Hash>::try_from(locator),
+            Err(ConversionError::TooManyItems(_, _, MAX_HASHES_PER_MSG))
+        ));
+
+        let addresses = protowire::AddressesMessage {
+            address_list: vec![
+                protowire::NetAddress {
+                    timestamp: 0,
+                    ip: vec![127, 0, 0, 1],
+                    port: 16111
+                };
+                MAX_ADDRESSES_PER_MSG + 1
+            ],
+        };
+        assert!(matches!(
+            Vec::<(IpAddress, u16)>::try_from(addresses),
+            Err(ConversionError::TooManyItems(_, _, MAX_ADDRESSES_PER_MSG))
+        ));
+    }
+}
@@ -1,8 +1,13 @@
 use super::{
     error::ConversionError,
+    limits::{
+        check_count, check_length, MAX_ADDRESSES_PER_MSG, MAX_HASHES_PER_MSG, MAX_HEADERS_PER_MSG,
+        MAX_INV_PER_TX_INV_MSG, MAX_NETWORK_NAME_LEN, MAX_PRUNING_PROOF_LEVELS,
+        MAX_UTXOS_PER_CHUNK,
+    },
     model::{
         trusted::{TrustedDataEntry, TrustedDataPackage},
-        version::Version,
+        version::{Version, MAX_USER_AGENT_LEN},
     },
     option::TryIntoOptionEx,
 };
@@ -44,6 +49,8 @@ impl From<Version> for protowire::VersionMessage {
 impl TryFrom<protowire::VersionMessage> for Version {
     type Error = ConversionError;
     fn try_from(msg: protowire::VersionMessage) -> Result<Self, Self::Error> {
+        check_length("user agent", msg.user_agent.len(), MAX_USER_AGENT_LEN)?;
+        check_length("network", msg.network.len(), MAX_NETWORK_NAME_LEN)?;
         Ok(Self {
             protocol_version: msg.protocol_version,
             services: msg.services,
@@ -93,6 +100,11 @@ impl TryFrom<protowire::RequestIbdChainBlockLocatorMessage> for (Option<Hash>, O
 impl TryFrom<protowire::PruningPointProofMessage> for PruningPointProof {
     type Error = ConversionError;
     fn try_from(msg: protowire::PruningPointProofMessage) -> Result<Self, Self::Error> {
+        check_count(
+            "pruning point proof levels",
+            msg.headers.len(),
+            MAX_PRUNING_PROOF_LEVELS,
+        )?;
         msg.headers.into_iter().map(|v| v.try_into()).collect()
     }
 }
@@ -100,6 +112,7 @@ impl TryFrom<protowire::PruningPointProofMessage> for PruningPointProof {
 impl TryFrom<protowire::PruningPointsMessage> for PruningPointsList {
     type Error = ConversionError;
     fn try_from(msg: protowire::PruningPointsMessage) -> Result<Self, Self::Error> {
+        check_count("pruning points", msg.headers.len(), MAX_HEADERS_PER_MSG)?;
         msg.headers
             .into_iter()
             .map(|x| x.try_into().map(Arc::new))
@@ -137,6 +150,11 @@ impl TryFrom<protowire::BlockWithTrustedDataV4Message> for TrustedDataEntry {
 impl TryFrom<protowire::IbdChainBlockLocatorMessage> for Vec<Hash> {
     type Error = ConversionError;
     fn try_from(msg: protowire::IbdChainBlockLocatorMessage) -> Result<Self, Self::Error> {
+        check_count(
+            "block locator",
+            msg.block_locator_hashes.len(),
+            MAX_HASHES_PER_MSG,
+        )?;
         msg.block_locator_hashes
             .into_iter()
             .map(|v| v.try_into())
@@ -147,6 +165,11 @@ impl TryFrom<protowire::IbdChainBlockLocatorMessage> for Vec<Hash> {
 impl TryFrom<protowire::BlockHeadersMessage> for Vec<Arc<Header>> {
     type Error = ConversionError;
     fn try_from(msg: protowire::BlockHeadersMessage) -> Result<Self, Self::Error> {
+        check_count(
+            "block headers",
+            msg.block_headers.len(),
+            MAX_HEADERS_PER_MSG,
+        )?;
         msg.block_headers
             .into_iter()
             .map(|v| v.try_into().map(Arc::new))
@@ -158,6 +181,11 @@ impl TryFrom<protowire::PruningPointUtxoSetChunkMessage> for Vec<(TransactionOut
     type Error = ConversionError;
 
     fn try_from(msg: protowire::PruningPointUtxoSetChunkMessage) -> Result<Self, Self::Error> {
+        check_count(
+            "UTXO set chunk",
+            msg.outpoint_and_utxo_entry_pairs.len(),
+            MAX_UTXOS_PER_CHUNK,
+        )?;
         msg.outpoint_and_utxo_entry_pairs
             .into_iter()
             .map(|p| p.try_into())
@@ -185,6 +213,11 @@ impl TryFrom<protowire::RequestRelayBlocksMessage> for Vec<Hash> {
     type Error = ConversionError;
 
     fn try_from(msg: protowire::RequestRelayBlocksMessage) -> Result<Self, Self::Error> {
+        check_count(
+            "requested relay blocks",
+            msg.hashes.len(),
+            MAX_HASHES_PER_MSG,
+        )?;
         msg.hashes.into_iter().map(|v| v.try_into()).collect()
     }
 }
@@ -193,6 +226,7 @@ impl TryFrom<protowire::RequestIbdBlocksMessage> for Vec<Hash> {
     type Error = ConversionError;
 
     fn try_from(msg: protowire::RequestIbdBlocksMessage) -> Result<Self, Self::Error> {
+        check_count("requested IBD blocks", msg.hashes.len(), MAX_HASHES_PER_MSG)?;
         msg.hashes.into_iter().map(|v| v.try_into()).collect()
     }
 }
@@ -201,6 +235,7 @@ impl TryFrom<protowire::BlockLocatorMessage> for Vec<Hash> {
     type Error = ConversionError;
 
     fn try_from(msg: protowire::BlockLocatorMessage) -> Result<Self, Self::Error> {
+        check_count("block locator", msg.hashes.len(), MAX_HASHES_PER_MSG)?;
         msg.hashes.into_iter().map(|v| v.try_into()).collect()
     }
 }
@@ -209,6 +244,7 @@ impl TryFrom<protowire::AddressesMessage> for Vec<(IpAddress, u16)> {
     type Error = ConversionError;
 
     fn try_from(msg: protowire::AddressesMessage) -> Result<Self, Self::Error> {
+        check_count("addresses", msg.address_list.len(), MAX_ADDRESSES_PER_MSG)?;
         msg.address_list
             .into_iter()
             .map(|addr| addr.try_into())
@@ -220,6 +256,11 @@ impl TryFrom<protowire::RequestTransactionsMessage> for Vec<TransactionId> {
     type Error = ConversionError;
 
     fn try_from(msg: protowire::RequestTransactionsMessage) -> Result<Self, Self::Error> {
+        check_count(
+            "requested transactions",
+            msg.ids.len(),
+            MAX_INV_PER_TX_INV_MSG,
+        )?;
         msg.ids.into_iter().map(|v| v.try_into()).collect()
     }
 }
@@ -228,6 +269,11 @@ impl TryFrom<protowire::InvTransactionsMessage> for Vec<TransactionId> {
     type Error = ConversionError;
 
     fn try_from(msg: protowire::InvTransactionsMessage) -> Result<Self, Self::Error> {
+        check_count(
+            "transaction inventory",
+            msg.ids.len(),
+            MAX_INV_PER_TX_INV_MSG,
+        )?;
         msg.ids.into_iter().map(|v| v.try_into()).collect()
     }
 }
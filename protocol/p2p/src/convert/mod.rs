@@ -3,6 +3,7 @@ pub mod error;
 pub mod ghostdag;
 pub mod hash;
 pub mod header;
+pub mod limits;
 pub mod messages;
 pub mod model;
 pub mod net_address;
@@ -1,5 +1,6 @@
 use crate::{common::ProtocolError, pb::KarlsendMessage, ConnectionInitializer, Peer, Router};
 use karlsen_core::{debug, info, warn};
+use karlsen_utils::networking::PeerId;
 use parking_lot::RwLock;
 use std::{
     collections::{hash_map::Entry::Occupied, HashMap},
@@ -155,23 +156,32 @@ impl Hub {
         }
     }
 
-    /// Broadcast a message to all peers
-    pub async fn broadcast(&self, msg: KarlsendMessage) {
+    /// Broadcast a message to all peers. Returns the peers the message was queued to
+    pub async fn broadcast(&self, msg: KarlsendMessage) -> Vec<PeerId> {
         let peers = self.peers.read().values().cloned().collect::<Vec<_>>();
-        for router in peers {
-            let _ = router.enqueue(msg.clone()).await;
-        }
+        Self::enqueue_to(peers, msg).await
     }
 
-    /// Broadcast a message to only some number of peers
-    pub async fn broadcast_to_some_peers(&self, msg: KarlsendMessage, num_peers: usize) {
+    /// Broadcast a message to only some number of peers. Returns the peers the message was queued to
+    pub async fn broadcast_to_some_peers(
+        &self,
+        msg: KarlsendMessage,
+        num_peers: usize,
+    ) -> Vec<PeerId> {
         assert!(num_peers > 0);
 
-        let peers = self.select_some_peers(num_peers);
+        let peers = self.select_some_peers(num_peers).collect::<Vec<_>>();
+        Self::enqueue_to(peers, msg).await
+    }
 
+    async fn enqueue_to(peers: Vec<Arc<Router>>, msg: KarlsendMessage) -> Vec<PeerId> {
+        let mut reached = Vec::with_capacity(peers.len());
         for router in peers {
-            let _ = router.enqueue(msg.clone()).await;
+            if router.enqueue(msg.clone()).await.is_ok() {
+                reached.push(router.identity());
+            }
         }
+        reached
     }
 
     /// Broadcast a vector of messages to all peers
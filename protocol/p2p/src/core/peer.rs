@@ -13,6 +13,15 @@ pub struct PeerProperties {
     pub time_offset: i64,
 }
 
+/// Useful work of a peer, recorded by the flows over the lifetime of its connection
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PeerQuality {
+    /// Count of new valid blocks first announced by the peer, ie. obtained from it
+    pub blocks_first_announced: u64,
+    /// Count of transactions relayed by the peer and accepted to the mempool
+    pub transactions_relayed: u64,
+}
+
 #[derive(Debug)]
 pub struct Peer {
     identity: PeerId,
@@ -21,6 +30,7 @@ pub struct Peer {
     connection_started: Instant,
     properties: Arc<PeerProperties>,
     last_ping_duration: u64,
+    quality: PeerQuality,
 }
 
 impl Peer {
@@ -31,6 +41,7 @@ impl Peer {
         connection_started: Instant,
         properties: Arc<PeerProperties>,
         last_ping_duration: u64,
+        quality: PeerQuality,
     ) -> Self {
         Self {
             identity,
@@ -39,6 +50,7 @@ impl Peer {
             connection_started,
             properties,
             last_ping_duration,
+            quality,
         }
     }
 
@@ -74,6 +86,10 @@ impl Peer {
     pub fn last_ping_duration(&self) -> u64 {
         self.last_ping_duration
     }
+
+    pub fn quality(&self) -> PeerQuality {
+        self.quality
+    }
 }
 
 #[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
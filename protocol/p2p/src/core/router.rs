@@ -19,7 +19,7 @@ use tokio::sync::mpsc::{channel as mpsc_channel, Receiver as MpscReceiver, Sende
 use tokio::sync::oneshot::{channel as oneshot_channel, Sender as OneshotSender};
 use tonic::Streaming;
 
-use super::peer::{PeerKey, PeerProperties};
+use super::peer::{PeerKey, PeerProperties, PeerQuality};
 
 pub struct IncomingRoute {
     rx: MpscReceiver<KarlsendMessage>,
@@ -103,6 +103,9 @@ struct RouterMutableState {
 
     /// Duration of the last ping to this peer
     last_ping_duration: u64,
+
+    /// Useful work of the peer
+    quality: PeerQuality,
 }
 
 impl RouterMutableState {
@@ -170,6 +173,7 @@ impl From<&Router> for Peer {
             router.connection_started,
             router.properties(),
             router.last_ping_duration(),
+            router.quality(),
         )
     }
 }
@@ -313,6 +317,22 @@ impl Router {
         self.mutable_state.lock().last_ping_duration
     }
 
+    /// Records a new valid block obtained from this peer following its announcement
+    pub fn record_block_first_announced(&self) {
+        self.mutable_state.lock().quality.blocks_first_announced += 1;
+    }
+
+    /// Records transactions relayed by this peer and accepted to the mempool
+    pub fn record_transactions_relayed(&self, count: u64) {
+        if count > 0 {
+            self.mutable_state.lock().quality.transactions_relayed += count;
+        }
+    }
+
+    pub fn quality(&self) -> PeerQuality {
+        self.mutable_state.lock().quality
+    }
+
     pub fn incoming_flow_baseline_channel_size() -> usize {
         256
     }
@@ -14,6 +14,6 @@ pub use crate::core::adaptor::{Adaptor, ConnectionInitializer};
 pub use crate::core::connection_handler::ConnectionError;
 pub use crate::core::hub::Hub;
 pub use crate::core::payload_type::KarlsendMessagePayloadType;
-pub use crate::core::peer::{Peer, PeerKey, PeerProperties};
+pub use crate::core::peer::{Peer, PeerKey, PeerProperties, PeerQuality};
 pub use crate::core::router::{IncomingRoute, Router, SharedIncomingRoute, BLANK_ROUTE_ID};
 pub use handshake::KarlsendHandshake;
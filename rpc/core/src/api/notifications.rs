@@ -44,6 +44,12 @@ pub enum Notification {
 
     #[display(fmt = "NewBlockTemplate notification")]
     NewBlockTemplate(NewBlockTemplateNotification),
+
+    #[display(fmt = "TransactionStatusChanged notification: transaction id {}", "_0.transaction_id")]
+    TransactionStatusChanged(TransactionStatusChangedNotification),
+
+    #[display(fmt = "DoubleSpendDetected notification: transaction id {} conflicting with {}", "_0.transaction_id", "_0.conflicting_transaction_id")]
+    DoubleSpendDetected(DoubleSpendDetectedNotification),
 }
 }
 
@@ -60,6 +66,8 @@ impl Notification {
             Notification::VirtualDaaScoreChanged(v) => to_value(&v),
             Notification::SinkBlueScoreChanged(v) => to_value(&v),
             Notification::VirtualChainChanged(v) => to_value(&v),
+            Notification::TransactionStatusChanged(v) => to_value(&v),
+            Notification::DoubleSpendDetected(v) => to_value(&v),
         }
     }
 }
@@ -13,7 +13,50 @@ use workflow_core::enums::Describe;
 /// or using Serde attributes. This applies only to RPC infrastructure that uses internal
 /// data structures and does not affect gRPC. gRPC should issue and handle its
 /// own versioning.
-pub const RPC_API_VERSION: [u16; 4] = [0, 1, 0, 0];
+///
+/// Changes since version 0.1.0, the ops being appended along with the version adding them:
+/// - 0.1.1 added `GetServerCapabilities`.
+/// - 0.1.2 added `GetNetworkInfo`.
+/// - 0.1.3 added the block added stream ops.
+/// - 0.2.0 added `verbosity` and `batch_size` to `GetBlocksRequest` and `next_low_hash` to
+///   `GetBlocksResponse`.
+/// - 0.2.1 added `TestMempoolAccept`.
+/// - 0.3.0 added `worker_id` to `GetBlockTemplateRequest` and `nonce_start` and
+///   `nonce_stride` to `GetBlockTemplateResponse`.
+/// - 0.3.1 added `GetDifficultyInfo`.
+/// - 0.3.2 added `DebugScript`.
+/// - 0.3.3 added `GetTransactionStatus` and the transaction status notifications.
+/// - 0.3.4 added the durable subscription ops.
+/// - 0.4.0 added the memory budget counters to `ProcessMetrics`.
+/// - 0.4.1 added `GetBlockFilterHeaders` and `GetBlockFilters`.
+/// - 0.4.2 added `GetBalanceByAddressesAt`.
+/// - 0.4.3 added the double spend notifications.
+/// - 0.4.4 added `GetUnconfirmedTxRisk`.
+/// - 0.5.0 added the clock offsets to `GetInfoResponse`.
+/// - 0.6.0 added the UTXO index consistency counters to `ConsensusMetrics`.
+/// - 0.6.1 added `GetDagSlice`.
+/// - 0.6.2 added `GetAcceptanceEventsSince`.
+/// - 0.6.3 added `GetTemplateDiff`.
+/// - 0.6.4 added `GetMetadata` and `UpdateMetadata`.
+/// - 0.7.0 added `GetMinerStats` and `reported_hashrate` to `GetBlockTemplateRequest`.
+/// - 0.7.1 added `GetAddressCluster`.
+/// - 0.8.0 added the pagination fields to `GetUtxosByAddressesRequest`,
+///   `GetUtxosByAddressesResponse`, `GetMempoolEntriesRequest` and `GetMempoolEntriesResponse`.
+/// - 0.9.0 added `bind_work` to `GetBlockTemplateRequest` and the work binding counters to
+///   `GetMinerStatsResponse`.
+/// - 0.9.1 added `NotificationGapNotification`.
+/// - 0.10.0 added `resolve_previous_outputs` to `GetBlockRequest`, `GetBlocksRequest`,
+///   `GetMempoolEntryRequest`, `GetMempoolEntriesRequest` and
+///   `GetMempoolEntriesByAddressesRequest`, and `previous_output` to
+///   `RpcTransactionInputVerboseData`.
+pub const RPC_API_VERSION: [u16; 4] = [0, 10, 0, 0];
+
+/// Protowire (gRPC) API version.
+/// This value is bumped whenever a breaking change is made to the protowire
+/// message definitions (i.e. a field is removed, renumbered or changes type).
+/// gRPC clients can read it through `GetServerCapabilities` and compare it
+/// with the version they were generated against before relying on the schema.
+pub const PROTOWIRE_API_VERSION: u32 = 1;
 
 #[derive(
     Describe,
@@ -126,6 +169,92 @@ pub enum RpcApiOps {
     VirtualDaaScoreChangedNotification,
     PruningPointUtxoSetOverrideNotification,
     NewBlockTemplateNotification,
+
+    // Ops appended after the original ones, so the values of the previous ops are kept
+
+    // 0.1.1
+    /// Get the API versions, methods and notifications supported by the node
+    GetServerCapabilities,
+
+    // 0.1.2
+    /// Get the network, address prefix and genesis hash of the node
+    GetNetworkInfo,
+
+    // 0.1.3
+    SubscribeBlockAdded,
+    UnsubscribeBlockAdded,
+    BlockAddedStreamNotification,
+
+    // 0.2.1
+    /// Runs the full mempool validation of a transaction without submitting it
+    TestMempoolAccept,
+
+    // 0.3.1
+    /// Get the difficulty adjustment state: DAA window summary, next expected target and recent chain blocks difficulty
+    GetDifficultyInfo,
+
+    // 0.3.2
+    /// Evaluates the scripts of a transaction input step by step
+    DebugScript,
+
+    // 0.3.3
+    /// Get the broadcast and acceptance status of a transaction submitted to this node
+    GetTransactionStatus,
+    NotifyTransactionStatusChanged,
+    TransactionStatusChangedNotification,
+
+    // 0.3.4
+    SubscribeDurable,
+    ResumeDurable,
+    UnsubscribeDurable,
+    DurableNotification,
+
+    // 0.4.1
+    /// Get the compact block filter headers of a range of chain blocks
+    GetBlockFilterHeaders,
+    /// Get the compact block filters of a range of chain blocks
+    GetBlockFilters,
+
+    // 0.4.2
+    /// Get the balances of multiple addresses as of a past DAA score
+    GetBalanceByAddressesAt,
+
+    // 0.4.3
+    NotifyDoubleSpendDetected,
+    DoubleSpendDetectedNotification,
+
+    // 0.4.4
+    /// Get the zero-confirmation risk heuristics of an unconfirmed transaction
+    GetUnconfirmedTxRisk,
+
+    // 0.6.1
+    /// Get a slice of the DAG with the relations and colors of its blocks
+    GetDagSlice,
+
+    // 0.6.2
+    /// Returns the chain acceptance events journaled after a cursor
+    GetAcceptanceEventsSince,
+
+    // 0.6.3
+    /// Returns a block template as a diff of its transactions relative to a previously issued template
+    GetTemplateDiff,
+
+    // 0.6.4
+    /// Returns entries of a namespace of the external indexer metadata store
+    GetMetadata,
+    /// Atomically updates entries of a namespace of the external indexer metadata store
+    UpdateMetadata,
+
+    // 0.7.0
+    /// Returns the mining profitability estimates of the miners connected to the node.
+    GetMinerStats,
+
+    // 0.7.1
+    /// Returns the common-input-ownership cluster of an address.
+    GetAddressCluster,
+
+    // 0.9.1
+    NotificationGapNotification,
 }
 
 impl RpcApiOps {
@@ -141,8 +270,35 @@ impl RpcApiOps {
                 | RpcApiOps::NotifyFinalityConflictResolved
                 | RpcApiOps::NotifySinkBlueScoreChanged
                 | RpcApiOps::NotifyVirtualDaaScoreChanged
+                | RpcApiOps::NotifyTransactionStatusChanged
+                | RpcApiOps::NotifyDoubleSpendDetected
                 | RpcApiOps::Subscribe
                 | RpcApiOps::Unsubscribe
+                | RpcApiOps::SubscribeDurable
+                | RpcApiOps::ResumeDurable
+                | RpcApiOps::UnsubscribeDurable
+                | RpcApiOps::SubscribeBlockAdded
+                | RpcApiOps::UnsubscribeBlockAdded
+        )
+    }
+
+    pub fn is_notification(&self) -> bool {
+        matches!(
+            self,
+            RpcApiOps::BlockAddedNotification
+                | RpcApiOps::VirtualChainChangedNotification
+                | RpcApiOps::FinalityConflictNotification
+                | RpcApiOps::FinalityConflictResolvedNotification
+                | RpcApiOps::UtxosChangedNotification
+                | RpcApiOps::SinkBlueScoreChangedNotification
+                | RpcApiOps::VirtualDaaScoreChangedNotification
+                | RpcApiOps::PruningPointUtxoSetOverrideNotification
+                | RpcApiOps::NewBlockTemplateNotification
+                | RpcApiOps::TransactionStatusChangedNotification
+                | RpcApiOps::DoubleSpendDetectedNotification
+                | RpcApiOps::DurableNotification
+                | RpcApiOps::NotificationGapNotification
+                | RpcApiOps::BlockAddedStreamNotification
         )
     }
 }
@@ -169,6 +325,25 @@ impl From<EventType> for RpcApiOps {
                 RpcApiOps::PruningPointUtxoSetOverrideNotification
             }
             EventType::NewBlockTemplate => RpcApiOps::NewBlockTemplateNotification,
+            EventType::TransactionStatusChanged => RpcApiOps::TransactionStatusChangedNotification,
+            EventType::DoubleSpendDetected => RpcApiOps::DoubleSpendDetectedNotification,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ops_values() {
+        // The values of the ops are part of the wRPC wire format, new ops must be appended
+        assert_eq!(u32::from(RpcApiOps::GetDaaScoreTimestampEstimate), 33);
+        assert_eq!(u32::from(RpcApiOps::NotifyBlockAdded), 34);
+        assert_eq!(u32::from(RpcApiOps::Unsubscribe), 44);
+        assert_eq!(u32::from(RpcApiOps::NewBlockTemplateNotification), 53);
+        assert_eq!(u32::from(RpcApiOps::GetServerCapabilities), 54);
+        assert_eq!(u32::from(RpcApiOps::BlockAddedStreamNotification), 58);
+        assert_eq!(u32::from(RpcApiOps::DurableNotification), 68);
+    }
+}
@@ -462,6 +462,269 @@ pub trait RpcApi: Sync + Send + AnySync {
         request: GetDaaScoreTimestampEstimateRequest,
     ) -> RpcResult<GetDaaScoreTimestampEstimateResponse>;
 
+    /// Requests the API versions as well as the methods and notifications supported by the node.
+    async fn get_server_capabilities(&self) -> RpcResult<GetServerCapabilitiesResponse> {
+        self.get_server_capabilities_call(GetServerCapabilitiesRequest {})
+            .await
+    }
+    async fn get_server_capabilities_call(
+        &self,
+        request: GetServerCapabilitiesRequest,
+    ) -> RpcResult<GetServerCapabilitiesResponse>;
+
+    /// Requests the network identifier, the address prefix and the genesis hash of the node.
+    async fn get_network_info(&self) -> RpcResult<GetNetworkInfoResponse> {
+        self.get_network_info_call(GetNetworkInfoRequest {}).await
+    }
+    async fn get_network_info_call(
+        &self,
+        request: GetNetworkInfoRequest,
+    ) -> RpcResult<GetNetworkInfoResponse>;
+
+    /// Runs the full mempool validation of a transaction without submitting it.
+    async fn test_mempool_accept(
+        &self,
+        transaction: RpcTransaction,
+    ) -> RpcResult<TestMempoolAcceptResponse> {
+        self.test_mempool_accept_call(TestMempoolAcceptRequest::new(transaction))
+            .await
+    }
+    async fn test_mempool_accept_call(
+        &self,
+        request: TestMempoolAcceptRequest,
+    ) -> RpcResult<TestMempoolAcceptResponse>;
+
+    /// Returns the difficulty adjustment state of the virtual block along with the difficulty
+    /// of the `chain_block_count` most recent selected chain blocks.
+    async fn get_difficulty_info(
+        &self,
+        chain_block_count: u32,
+    ) -> RpcResult<GetDifficultyInfoResponse> {
+        self.get_difficulty_info_call(GetDifficultyInfoRequest::new(chain_block_count))
+            .await
+    }
+    async fn get_difficulty_info_call(
+        &self,
+        request: GetDifficultyInfoRequest,
+    ) -> RpcResult<GetDifficultyInfoResponse>;
+
+    /// Evaluates the scripts of a transaction input step by step. Only available on devnets
+    /// or in unsafe RPC mode.
+    async fn debug_script(
+        &self,
+        transaction: RpcTransaction,
+        input_index: u32,
+        utxo_entries: Vec<RpcUtxoEntry>,
+    ) -> RpcResult<DebugScriptResponse> {
+        self.debug_script_call(DebugScriptRequest::new(
+            transaction,
+            input_index,
+            utxo_entries,
+        ))
+        .await
+    }
+    async fn debug_script_call(
+        &self,
+        request: DebugScriptRequest,
+    ) -> RpcResult<DebugScriptResponse>;
+
+    /// Returns the broadcast and acceptance status of a transaction. The acceptance is only
+    /// tracked for transactions submitted through the RPC of this node.
+    async fn get_transaction_status(
+        &self,
+        transaction_id: RpcTransactionId,
+    ) -> RpcResult<GetTransactionStatusResponse> {
+        self.get_transaction_status_call(GetTransactionStatusRequest::new(transaction_id))
+            .await
+    }
+    async fn get_transaction_status_call(
+        &self,
+        request: GetTransactionStatusRequest,
+    ) -> RpcResult<GetTransactionStatusResponse>;
+
+    /// Returns the compact filter headers of up to `limit` selected chain blocks, starting at
+    /// `start_hash`. Requires the node to run with a block filter index.
+    async fn get_block_filter_headers(
+        &self,
+        start_hash: RpcHash,
+        limit: u32,
+    ) -> RpcResult<GetBlockFilterHeadersResponse> {
+        self.get_block_filter_headers_call(GetBlockFilterHeadersRequest::new(start_hash, limit))
+            .await
+    }
+    async fn get_block_filter_headers_call(
+        &self,
+        request: GetBlockFilterHeadersRequest,
+    ) -> RpcResult<GetBlockFilterHeadersResponse>;
+
+    /// Returns the compact filters of up to `limit` selected chain blocks, starting at
+    /// `start_hash`. Requires the node to run with a block filter index.
+    async fn get_block_filters(
+        &self,
+        start_hash: RpcHash,
+        limit: u32,
+    ) -> RpcResult<GetBlockFiltersResponse> {
+        self.get_block_filters_call(GetBlockFiltersRequest::new(start_hash, limit))
+            .await
+    }
+    async fn get_block_filters_call(
+        &self,
+        request: GetBlockFiltersRequest,
+    ) -> RpcResult<GetBlockFiltersResponse>;
+
+    /// Returns the balances of `addresses` as of the highest selected chain block with a DAA
+    /// score not above `daa_score`. Requires the node to run with a UTXO index.
+    ///
+    /// On pruned nodes, only DAA scores above the pruning point can be queried. The node also
+    /// bounds the count of chain blocks it rolls the balances back along.
+    async fn get_balance_by_addresses_at(
+        &self,
+        addresses: Vec<RpcAddress>,
+        daa_score: u64,
+    ) -> RpcResult<GetBalanceByAddressesAtResponse> {
+        self.get_balance_by_addresses_at_call(GetBalanceByAddressesAtRequest::new(
+            addresses, daa_score,
+        ))
+        .await
+    }
+    async fn get_balance_by_addresses_at_call(
+        &self,
+        request: GetBalanceByAddressesAtRequest,
+    ) -> RpcResult<GetBalanceByAddressesAtResponse>;
+
+    /// Returns heuristics on the risk of accepting an unconfirmed transaction, based on its fee
+    /// rate, the conflicting spends seen, the age of its inputs and its propagation among peers.
+    async fn get_unconfirmed_tx_risk(
+        &self,
+        transaction_id: RpcTransactionId,
+    ) -> RpcResult<GetUnconfirmedTxRiskResponse> {
+        self.get_unconfirmed_tx_risk_call(GetUnconfirmedTxRiskRequest::new(transaction_id))
+            .await
+    }
+    async fn get_unconfirmed_tx_risk_call(
+        &self,
+        request: GetUnconfirmedTxRiskRequest,
+    ) -> RpcResult<GetUnconfirmedTxRiskResponse>;
+
+    /// Returns about `count` blocks of the DAG merged by the selected chain from DAA score
+    /// `from_daa_score` on, with their parents, colors and selected chain membership, for
+    /// drawing the DAG.
+    async fn get_dag_slice(
+        &self,
+        from_daa_score: u64,
+        count: u32,
+    ) -> RpcResult<GetDagSliceResponse> {
+        self.get_dag_slice_call(GetDagSliceRequest::new(from_daa_score, count))
+            .await
+    }
+    async fn get_dag_slice_call(
+        &self,
+        request: GetDagSliceRequest,
+    ) -> RpcResult<GetDagSliceResponse>;
+
+    /// Returns up to `max_count` chain acceptance events journaled after the event of sequence
+    /// `cursor`, from the oldest retained event if `cursor` is zero.
+    async fn get_acceptance_events_since(
+        &self,
+        cursor: u64,
+        max_count: u32,
+    ) -> RpcResult<GetAcceptanceEventsSinceResponse> {
+        self.get_acceptance_events_since_call(GetAcceptanceEventsSinceRequest::new(
+            cursor, max_count,
+        ))
+        .await
+    }
+    async fn get_acceptance_events_since_call(
+        &self,
+        request: GetAcceptanceEventsSinceRequest,
+    ) -> RpcResult<GetAcceptanceEventsSinceResponse>;
+
+    /// Requests a block template paying to `pay_address`, with its transactions expressed as a
+    /// diff relative to the template `since_template_id` previously issued by the node.
+    async fn get_template_diff(
+        &self,
+        pay_address: RpcAddress,
+        extra_data: RpcExtraData,
+        since_template_id: u64,
+    ) -> RpcResult<GetTemplateDiffResponse> {
+        self.get_template_diff_call(GetTemplateDiffRequest::new(
+            pay_address,
+            extra_data,
+            since_template_id,
+        ))
+        .await
+    }
+    async fn get_template_diff_call(
+        &self,
+        request: GetTemplateDiffRequest,
+    ) -> RpcResult<GetTemplateDiffResponse>;
+
+    /// Returns the entries of `keys` present in the `namespace` of the external indexer
+    /// metadata store.
+    async fn get_metadata(
+        &self,
+        namespace: String,
+        keys: Vec<String>,
+    ) -> RpcResult<Vec<RpcMetadataEntry>> {
+        Ok(self
+            .get_metadata_call(GetMetadataRequest::new(namespace, keys))
+            .await?
+            .entries)
+    }
+    async fn get_metadata_call(
+        &self,
+        request: GetMetadataRequest,
+    ) -> RpcResult<GetMetadataResponse>;
+
+    /// Atomically deletes `delete_keys` and sets `set_entries` in the `namespace` of the
+    /// external indexer metadata store, provided the namespace holds `expected_entries` and
+    /// none of `expected_absent_keys`.
+    async fn update_metadata(
+        &self,
+        namespace: String,
+        expected_entries: Vec<RpcMetadataEntry>,
+        expected_absent_keys: Vec<String>,
+        set_entries: Vec<RpcMetadataEntry>,
+        delete_keys: Vec<String>,
+    ) -> RpcResult<()> {
+        self.update_metadata_call(UpdateMetadataRequest::new(
+            namespace,
+            expected_entries,
+            expected_absent_keys,
+            set_entries,
+            delete_keys,
+        ))
+        .await?;
+        Ok(())
+    }
+    async fn update_metadata_call(
+        &self,
+        request: UpdateMetadataRequest,
+    ) -> RpcResult<UpdateMetadataResponse>;
+
+    /// Returns the mining profitability estimates of the miners connected to the node.
+    async fn get_miner_stats(&self) -> RpcResult<GetMinerStatsResponse> {
+        self.get_miner_stats_call(GetMinerStatsRequest {}).await
+    }
+    async fn get_miner_stats_call(
+        &self,
+        request: GetMinerStatsRequest,
+    ) -> RpcResult<GetMinerStatsResponse>;
+
+    /// Returns the addresses assumed to share the owner of `address`, up to `limit` of them.
+    async fn get_address_cluster(
+        &self,
+        address: RpcAddress,
+        limit: u32,
+    ) -> RpcResult<GetAddressClusterResponse> {
+        self.get_address_cluster_call(GetAddressClusterRequest::new(address, limit))
+            .await
+    }
+    async fn get_address_cluster_call(
+        &self,
+        request: GetAddressClusterRequest,
+    ) -> RpcResult<GetAddressClusterResponse>;
+
     // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
     // Notification API
 
@@ -1,6 +1,7 @@
 use crate::{
-    NotifyBlockAddedRequest, NotifyFinalityConflictRequest, NotifyNewBlockTemplateRequest,
-    NotifyPruningPointUtxoSetOverrideRequest, NotifySinkBlueScoreChangedRequest,
+    NotifyBlockAddedRequest, NotifyDoubleSpendDetectedRequest, NotifyFinalityConflictRequest,
+    NotifyNewBlockTemplateRequest, NotifyPruningPointUtxoSetOverrideRequest,
+    NotifySinkBlueScoreChangedRequest, NotifyTransactionStatusChangedRequest,
     NotifyUtxosChangedRequest, NotifyVirtualChainChangedRequest,
     NotifyVirtualDaaScoreChangedRequest,
 };
@@ -61,3 +62,5 @@ from!(SinkBlueScoreChanged);
 from!(VirtualDaaScoreChanged);
 from!(PruningPointUtxoSetOverride);
 from!(NewBlockTemplate);
+from!(TransactionStatusChanged);
+from!(DoubleSpendDetected);
@@ -1,10 +1,12 @@
-use karlsen_consensus_core::{subnets::SubnetworkConversionError, tx::TransactionId};
+use karlsen_consensus_core::{
+    network::NetworkId, subnets::SubnetworkConversionError, tx::TransactionId,
+};
 use karlsen_utils::networking::IpAddress;
 use std::{net::AddrParseError, num::TryFromIntError};
 use thiserror::Error;
 use workflow_core::channel::ChannelError;
 
-use crate::{api::ctl::RpcState, RpcHash, RpcTransactionId, SubmitBlockRejectReason};
+use crate::{api::ctl::RpcState, RpcErrorInfo, RpcHash, RpcTransactionId, SubmitBlockRejectReason};
 
 #[derive(Clone, Debug, Error)]
 pub enum RpcError {
@@ -44,13 +46,22 @@ pub enum RpcError {
     #[error("Coinbase payload is above max length ({0}). Try to shorten the extra data.")]
     CoinbasePayloadLengthAboveMax(usize),
 
+    #[error("Work binding unavailable. Run the node with the --work-binding-secret argument.")]
+    NoWorkBindingSecret,
+
+    #[error("The job carries no work binding")]
+    UnboundWork,
+
+    #[error("The job is not bound to the pay address and worker of the miner, it may have been redirected")]
+    WorkBindingMismatch,
+
     #[error("Rejected transaction {0}: {1}")]
     RejectedTransaction(RpcTransactionId, String),
 
     #[error("Block {0} is invalid. No verbose data can be built.")]
     InvalidBlock(RpcHash),
 
-    #[error("If includeTransactions is set, then includeBlockVerboseData must be set as well.")]
+    #[error("If includeTransactions or verbosity is set, then includeBlocks must be set as well.")]
     InvalidGetBlocksRequest,
 
     #[error("Transaction {0} not found")]
@@ -59,6 +70,41 @@ pub enum RpcError {
     #[error("Method unavailable. Run the node with the --utxoindex argument.")]
     NoUtxoIndex,
 
+    #[error("Method unavailable. Run the node with the --block-journal-size argument.")]
+    NoBlockAddedJournal,
+
+    #[error("Method unavailable. Run the node with the --blockfilterindex argument.")]
+    NoBlockFilterIndex,
+
+    #[error("Method unavailable. Run the node with the --acceptance-journal-size argument.")]
+    NoAcceptanceJournal,
+
+    #[error("Method unavailable. Run a node built with the analytics feature with the --addressclusterindex argument.")]
+    NoAddressClusterIndex,
+
+    #[error("Invalid metadata request: {0}")]
+    InvalidMetadataRequest(String),
+
+    #[error("Invalid cursor {0}")]
+    InvalidCursor(String),
+
+    #[error("The response exceeds the maximum size of {0} bytes, request it by pages")]
+    ResponseTooLarge(usize),
+
+    #[error(
+        "Resolving the previous outputs needs more than {0} consensus reads, request fewer blocks"
+    )]
+    PreviousOutputsReadLimit(usize),
+
+    #[error("Metadata update rejected: key {0} does not hold the expected value")]
+    MetadataConditionFailed(String),
+
+    #[error("Block {0} is not a chain block covered by the block filter index")]
+    BlockNotInFilterIndex(RpcHash),
+
+    #[error("The UTXO index is catching up with the virtual state, retry later.")]
+    UtxoIndexNotSynced,
+
     #[error("Method unavailable. No connection manager is currently available.")]
     NoConnectionManager,
 
@@ -71,6 +117,18 @@ pub enum RpcError {
     #[error("Method unavailable in safe mode. Run the node with --unsaferpc argument.")]
     UnavailableInSafeMode,
 
+    #[error("Method unavailable on {0} in safe mode. Run the node with --unsaferpc argument or use a devnet.")]
+    UnavailableOnNetwork(NetworkId),
+
+    #[error("Method {0} is not available on this RPC listener")]
+    MethodNotAllowed(String),
+
+    #[error("Request rate limit exceeded. Retry later.")]
+    RateLimited,
+
+    #[error("Expected one UTXO entry per transaction input ({0}), got {1}")]
+    UtxoEntriesCountMismatch(usize, usize),
+
     #[error("Cannot ban IP {0} because it has some permanent connection.")]
     IpHasPermanentConnection(IpAddress),
 
@@ -80,6 +138,9 @@ pub enum RpcError {
     #[error("Block was not submitted: {0}")]
     SubmitBlockError(SubmitBlockRejectReason),
 
+    #[error("Address {0} does not belong to the {1} network this node is running on")]
+    AddressNetworkMismatch(String, NetworkId),
+
     #[error(transparent)]
     AddressError(#[from] karlsen_addresses::AddressError),
 
@@ -107,6 +168,9 @@ pub enum RpcError {
     #[error("RPC Server (remote error) -> {0}")]
     RpcSubsystem(String),
 
+    #[error("{}", .0.message)]
+    Remote(RpcErrorInfo),
+
     #[error("{0}")]
     General(String),
 
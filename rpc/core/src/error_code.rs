@@ -0,0 +1,404 @@
+//!
+//! Numeric codes of the RPC errors, shared by all the RPC transports.
+//!
+//! Every [`RpcError`] maps to a stable numeric code whose thousands digit gives the
+//! [`RpcErrorCategory`]. The code, category, message and retryable flag of an error are
+//! carried over the wire as an [`RpcErrorInfo`] so clients can handle errors without
+//! parsing their messages.
+//!
+//! Servers predating the error codes only send a message, which clients decode as an
+//! [`RpcError::General`] error of code [`codes::UNKNOWN`].
+//!
+
+use crate::{RpcError, SubmitBlockRejectReason};
+use borsh::{BorshDeserialize, BorshSerialize};
+use karlsen_consensus_core::errors::consensus::ConsensusError;
+use serde::{Deserialize, Serialize};
+use std::{fmt::Display, str::FromStr};
+
+pub mod codes {
+    // Unclassified
+    pub const UNKNOWN: u32 = 0;
+
+    // Invalid request
+    pub const INT_CONVERSION: u32 = 1001;
+    pub const HEX_PARSING: u32 = 1002;
+    pub const BLUE_WORK_PARSING: u32 = 1003;
+    pub const INT_PARSING: u32 = 1004;
+    pub const IP_ADDRESS_PARSING: u32 = 1005;
+    pub const API_VERSION_FORMAT: u32 = 1006;
+    pub const INVALID_SCRIPT_CLASS: u32 = 1007;
+    pub const MISSING_FIELD: u32 = 1008;
+    pub const ENUM_CONVERSION: u32 = 1009;
+    pub const COINBASE_PAYLOAD_TOO_LONG: u32 = 1010;
+    pub const INVALID_GET_BLOCKS_REQUEST: u32 = 1011;
+    pub const WINDOW_SIZE_EXCEEDING_MAXIMUM: u32 = 1012;
+    pub const WINDOW_SIZE_EXCEEDING_PRUNING_DEPTH: u32 = 1013;
+    pub const ADDRESS_NETWORK_MISMATCH: u32 = 1014;
+    pub const INVALID_ADDRESS: u32 = 1015;
+    pub const INVALID_NETWORK: u32 = 1016;
+    pub const INCONSISTENT_MEMPOOL_QUERY: u32 = 1017;
+    pub const INVALID_SUBNETWORK: u32 = 1018;
+    pub const INVALID_NODE_ID: u32 = 1019;
+    pub const UTXO_ENTRIES_COUNT_MISMATCH: u32 = 1020;
+    pub const INVALID_ARGUMENT: u32 = 1021;
+    pub const INVALID_METADATA_REQUEST: u32 = 1022;
+    pub const INVALID_CURSOR: u32 = 1023;
+
+    // Not found
+    pub const TRANSACTION_NOT_FOUND: u32 = 2001;
+    pub const IP_NOT_BANNED: u32 = 2002;
+    pub const BLOCK_NOT_FOUND: u32 = 2003;
+    pub const BLOCK_NOT_IN_FILTER_INDEX: u32 = 2004;
+    pub const UTXO_HISTORY_PRUNED: u32 = 2005;
+
+    // Unavailable
+    pub const NOT_IMPLEMENTED: u32 = 3001;
+    pub const UNSUPPORTED_FEATURE: u32 = 3002;
+    pub const NO_UTXO_INDEX: u32 = 3003;
+    pub const NO_BLOCK_ADDED_JOURNAL: u32 = 3004;
+    pub const NO_CONNECTION_MANAGER: u32 = 3005;
+    pub const UNAVAILABLE_IN_SAFE_MODE: u32 = 3006;
+    pub const UNAVAILABLE_ON_NETWORK: u32 = 3007;
+    pub const NODE_NOT_SYNCED: u32 = 3008;
+    pub const ROUTE_IS_FULL: u32 = 3009;
+    pub const CONSENSUS_NOT_READY: u32 = 3010;
+    pub const METHOD_NOT_ALLOWED: u32 = 3011;
+    pub const RATE_LIMITED: u32 = 3012;
+    pub const NO_BLOCK_FILTER_INDEX: u32 = 3013;
+    pub const UTXO_INDEX_NOT_SYNCED: u32 = 3014;
+    pub const NO_ACCEPTANCE_JOURNAL: u32 = 3015;
+    pub const NO_ADDRESS_CLUSTER_INDEX: u32 = 3016;
+    pub const RESPONSE_TOO_LARGE: u32 = 3017;
+
+    // Rejected
+    pub const REJECTED_TRANSACTION: u32 = 4001;
+    pub const INVALID_BLOCK: u32 = 4002;
+    pub const REJECTED_BLOCK: u32 = 4003;
+    pub const IP_HAS_PERMANENT_CONNECTION: u32 = 4004;
+    pub const MINING_MANAGER_REJECTION: u32 = 4005;
+    pub const METADATA_CONDITION_FAILED: u32 = 4006;
+
+    // Internal
+    pub const NOTIFICATION_ERROR: u32 = 5001;
+    pub const CONTROL_DISPATCH_ERROR: u32 = 5002;
+    pub const CONSENSUS_ERROR: u32 = 5003;
+
+    // Transport
+    pub const TRANSPORT_ERROR: u32 = 6001;
+
+    /// Codes of the errors which may vanish by retrying the same request later
+    pub const RETRYABLE: [u32; 7] = [
+        NO_CONNECTION_MANAGER,
+        NODE_NOT_SYNCED,
+        UTXO_INDEX_NOT_SYNCED,
+        ROUTE_IS_FULL,
+        CONSENSUS_NOT_READY,
+        RATE_LIMITED,
+        TRANSPORT_ERROR,
+    ];
+}
+
+/// Broad class of an RPC error, letting clients pick a handling strategy without knowing every code
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    PartialEq,
+    Eq,
+    Hash,
+    Serialize,
+    Deserialize,
+    BorshSerialize,
+    BorshDeserialize,
+)]
+#[serde(rename_all = "camelCase")]
+pub enum RpcErrorCategory {
+    /// Unclassified errors, including all the errors of servers predating the error codes
+    Unknown,
+    /// The request is malformed or has invalid arguments
+    InvalidRequest,
+    /// The requested item does not exist
+    NotFound,
+    /// The method or the node is not able to serve the request in its current configuration or state
+    Unavailable,
+    /// The submitted item was rejected by the node
+    Rejected,
+    /// The node failed while processing the request
+    Internal,
+    /// The request or its response could not be exchanged with the node
+    Transport,
+}
+
+impl RpcErrorCategory {
+    pub fn from_code(code: u32) -> Self {
+        match code / 1000 {
+            1 => RpcErrorCategory::InvalidRequest,
+            2 => RpcErrorCategory::NotFound,
+            3 => RpcErrorCategory::Unavailable,
+            4 => RpcErrorCategory::Rejected,
+            5 => RpcErrorCategory::Internal,
+            6 => RpcErrorCategory::Transport,
+            _ => RpcErrorCategory::Unknown,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RpcErrorCategory::Unknown => "unknown",
+            RpcErrorCategory::InvalidRequest => "invalidRequest",
+            RpcErrorCategory::NotFound => "notFound",
+            RpcErrorCategory::Unavailable => "unavailable",
+            RpcErrorCategory::Rejected => "rejected",
+            RpcErrorCategory::Internal => "internal",
+            RpcErrorCategory::Transport => "transport",
+        }
+    }
+}
+
+impl Display for RpcErrorCategory {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for RpcErrorCategory {
+    type Err = RpcError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "unknown" => Ok(RpcErrorCategory::Unknown),
+            "invalidRequest" => Ok(RpcErrorCategory::InvalidRequest),
+            "notFound" => Ok(RpcErrorCategory::NotFound),
+            "unavailable" => Ok(RpcErrorCategory::Unavailable),
+            "rejected" => Ok(RpcErrorCategory::Rejected),
+            "internal" => Ok(RpcErrorCategory::Internal),
+            "transport" => Ok(RpcErrorCategory::Transport),
+            _ => Err(RpcError::PrimitiveToEnumConversionError),
+        }
+    }
+}
+
+/// Wire representation of an [`RpcError`]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcErrorInfo {
+    pub code: u32,
+    pub category: RpcErrorCategory,
+    pub message: String,
+    pub retryable: bool,
+}
+
+impl RpcErrorInfo {
+    /// Tag appended to the wRPC error messages, whose transport only carries text
+    const WRPC_TAG: &'static str = " (RPC error ";
+
+    pub fn new(code: u32, message: String) -> Self {
+        Self {
+            code,
+            category: RpcErrorCategory::from_code(code),
+            message,
+            retryable: codes::RETRYABLE.contains(&code),
+        }
+    }
+
+    /// Encodes the error as a message readable as is by the wRPC clients predating the error codes
+    pub fn to_wrpc_message(&self) -> String {
+        format!("{}{}{})", self.message, Self::WRPC_TAG, self.code)
+    }
+
+    /// Decodes a message built by [`RpcErrorInfo::to_wrpc_message`], possibly wrapped by the
+    /// transport error display, returning `None` if it holds no error code
+    pub fn from_wrpc_message(message: &str) -> Option<Self> {
+        let (head, tail) = message.rsplit_once(Self::WRPC_TAG)?;
+        let code = tail.strip_suffix(')')?.parse::<u32>().ok()?;
+        Some(Self::new(code, head.to_string()))
+    }
+}
+
+impl From<&RpcError> for RpcErrorInfo {
+    fn from(err: &RpcError) -> Self {
+        match err {
+            RpcError::Remote(info) => info.clone(),
+            err => Self::new(err.code(), err.to_string()),
+        }
+    }
+}
+
+impl From<RpcErrorInfo> for RpcError {
+    fn from(info: RpcErrorInfo) -> Self {
+        match info.code {
+            codes::UNKNOWN => RpcError::General(info.message),
+            _ => RpcError::Remote(info),
+        }
+    }
+}
+
+impl RpcError {
+    /// Returns the numeric code of the error, see [`codes`]
+    pub fn code(&self) -> u32 {
+        use codes::*;
+        match self {
+            RpcError::IntConversionError(_) => INT_CONVERSION,
+            RpcError::HexParsingError(_) => HEX_PARSING,
+            RpcError::RpcBlueWorkTypeParseError(_) => BLUE_WORK_PARSING,
+            RpcError::ParseIntError(_) => INT_PARSING,
+            RpcError::ParseIpAddressError(_) => IP_ADDRESS_PARSING,
+            RpcError::RpcApiVersionFormatError => API_VERSION_FORMAT,
+            RpcError::InvalidRpcScriptClass(_) | RpcError::ScriptClassError(_) => {
+                INVALID_SCRIPT_CLASS
+            }
+            RpcError::MissingRpcFieldError(_, _) => MISSING_FIELD,
+            RpcError::PrimitiveToEnumConversionError => ENUM_CONVERSION,
+            RpcError::CoinbasePayloadLengthAboveMax(_) => COINBASE_PAYLOAD_TOO_LONG,
+            RpcError::InvalidGetBlocksRequest => INVALID_GET_BLOCKS_REQUEST,
+            RpcError::WindowSizeExceedingMaximum(_, _) => WINDOW_SIZE_EXCEEDING_MAXIMUM,
+            RpcError::WindowSizeExceedingPruningDepth(_, _) => WINDOW_SIZE_EXCEEDING_PRUNING_DEPTH,
+            RpcError::AddressNetworkMismatch(_, _) => ADDRESS_NETWORK_MISMATCH,
+            RpcError::AddressError(_) => INVALID_ADDRESS,
+            RpcError::NetworkTypeError(_) | RpcError::NetworkIdError(_) => INVALID_NETWORK,
+            RpcError::InconsistentMempoolTxQuery => INCONSISTENT_MEMPOOL_QUERY,
+            RpcError::SubnetParsingError(_) => INVALID_SUBNETWORK,
+            RpcError::NodeIdError(_) => INVALID_NODE_ID,
+            RpcError::UtxoEntriesCountMismatch(_, _) => UTXO_ENTRIES_COUNT_MISMATCH,
+            RpcError::WasmError(_)
+            | RpcError::SerdeWasmBindgen(_)
+            | RpcError::ConsensusClient(_) => INVALID_ARGUMENT,
+            RpcError::InvalidMetadataRequest(_) => INVALID_METADATA_REQUEST,
+            RpcError::InvalidCursor(_) => INVALID_CURSOR,
+
+            RpcError::TransactionNotFound(_) => TRANSACTION_NOT_FOUND,
+            RpcError::IpIsNotBanned(_) => IP_NOT_BANNED,
+            RpcError::BlockNotInFilterIndex(_) => BLOCK_NOT_IN_FILTER_INDEX,
+
+            RpcError::NotImplemented => NOT_IMPLEMENTED,
+            RpcError::UnsupportedFeature => UNSUPPORTED_FEATURE,
+            RpcError::NoUtxoIndex => NO_UTXO_INDEX,
+            RpcError::NoBlockAddedJournal => NO_BLOCK_ADDED_JOURNAL,
+            RpcError::NoBlockFilterIndex => NO_BLOCK_FILTER_INDEX,
+            RpcError::NoAcceptanceJournal => NO_ACCEPTANCE_JOURNAL,
+            RpcError::NoAddressClusterIndex => NO_ADDRESS_CLUSTER_INDEX,
+            RpcError::UtxoIndexNotSynced => UTXO_INDEX_NOT_SYNCED,
+            RpcError::NoConnectionManager => NO_CONNECTION_MANAGER,
+            RpcError::UnavailableInSafeMode => UNAVAILABLE_IN_SAFE_MODE,
+            RpcError::UnavailableOnNetwork(_) => UNAVAILABLE_ON_NETWORK,
+            RpcError::MethodNotAllowed(_) => METHOD_NOT_ALLOWED,
+            RpcError::RateLimited => RATE_LIMITED,
+            RpcError::ResponseTooLarge(_) => RESPONSE_TOO_LARGE,
+
+            RpcError::RejectedTransaction(_, _) => REJECTED_TRANSACTION,
+            RpcError::InvalidBlock(_) => INVALID_BLOCK,
+            RpcError::SubmitBlockError(reason) => match reason {
+                SubmitBlockRejectReason::BlockInvalid => REJECTED_BLOCK,
+                SubmitBlockRejectReason::IsInIBD => NODE_NOT_SYNCED,
+                SubmitBlockRejectReason::RouteIsFull => ROUTE_IS_FULL,
+            },
+            RpcError::IpHasPermanentConnection(_) => IP_HAS_PERMANENT_CONNECTION,
+            RpcError::MiningManagerError(_) => MINING_MANAGER_REJECTION,
+            RpcError::MetadataConditionFailed(_) => METADATA_CONDITION_FAILED,
+
+            RpcError::ConsensusError(err) => match err {
+                ConsensusError::BlockNotFound(_)
+                | ConsensusError::HeaderNotFound(_)
+                | ConsensusError::MissingData(_) => BLOCK_NOT_FOUND,
+                ConsensusError::InvalidBlock(_) => INVALID_BLOCK,
+                ConsensusError::UtxoHistoryPruned(_) => UTXO_HISTORY_PRUNED,
+                ConsensusError::UtxoHistoryTooDeep(_, _) => INVALID_ARGUMENT,
+                ConsensusError::PruningPointInsufficientDepth
+                | ConsensusError::DifficultyError(_) => CONSENSUS_NOT_READY,
+                _ => CONSENSUS_ERROR,
+            },
+            RpcError::NotificationError(_) => NOTIFICATION_ERROR,
+            RpcError::RpcCtlDispatchError => CONTROL_DISPATCH_ERROR,
+
+            RpcError::RpcSubsystem(_) => TRANSPORT_ERROR,
+            RpcError::Remote(info) => info.code,
+            RpcError::General(_) => UNKNOWN,
+        }
+    }
+
+    pub fn category(&self) -> RpcErrorCategory {
+        match self {
+            RpcError::Remote(info) => info.category,
+            err => RpcErrorCategory::from_code(err.code()),
+        }
+    }
+
+    /// Returns true if retrying the same request later may succeed
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            RpcError::Remote(info) => info.retryable,
+            err => codes::RETRYABLE.contains(&err.code()),
+        }
+    }
+
+    /// Decodes an error received by a wRPC client, keeping errors with no code as transport errors
+    pub fn from_wrpc_message(message: String) -> Self {
+        match RpcErrorInfo::from_wrpc_message(&message) {
+            Some(info) => info.into(),
+            None => RpcError::RpcSubsystem(message),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_error_info_round_trip() {
+        let err = RpcError::NoUtxoIndex;
+        let info = RpcErrorInfo::from(&err);
+        assert_eq!(info.code, codes::NO_UTXO_INDEX);
+        assert_eq!(info.category, RpcErrorCategory::Unavailable);
+        assert!(!info.retryable);
+        assert_eq!(info.message, err.to_string());
+
+        // The remote error behaves like the original one
+        let remote = RpcError::from(info.clone());
+        assert_eq!(remote.code(), err.code());
+        assert_eq!(remote.category(), err.category());
+        assert_eq!(remote.to_string(), err.to_string());
+        assert_eq!(RpcErrorInfo::from(&remote), info);
+
+        let err = RpcError::SubmitBlockError(SubmitBlockRejectReason::IsInIBD);
+        assert!(err.is_retryable());
+        assert!(RpcError::from(RpcErrorInfo::from(&err)).is_retryable());
+
+        // Errors of servers predating the codes
+        let legacy = RpcError::from(RpcErrorInfo::new(codes::UNKNOWN, "legacy".to_string()));
+        assert!(matches!(legacy, RpcError::General(_)));
+        assert_eq!(legacy.category(), RpcErrorCategory::Unknown);
+    }
+
+    #[test]
+    fn test_wrpc_message() {
+        let info = RpcErrorInfo::from(&RpcError::TransactionNotFound(Default::default()));
+        let message = info.to_wrpc_message();
+        assert!(message.starts_with(&info.message));
+        assert_eq!(
+            RpcErrorInfo::from_wrpc_message(&message),
+            Some(info.clone())
+        );
+
+        // The transport may wrap the message
+        let err = RpcError::from_wrpc_message(format!("RPC error: {message}"));
+        assert_eq!(err.code(), codes::TRANSACTION_NOT_FOUND);
+        assert_eq!(err.category(), RpcErrorCategory::NotFound);
+
+        let err = RpcError::from_wrpc_message("connection closed".to_string());
+        assert!(matches!(err, RpcError::RpcSubsystem(_)));
+        assert!(err.is_retryable());
+
+        for category in [
+            RpcErrorCategory::Unknown,
+            RpcErrorCategory::Rejected,
+            RpcErrorCategory::Transport,
+        ] {
+            assert_eq!(
+                category.as_str().parse::<RpcErrorCategory>().unwrap(),
+                category
+            );
+        }
+    }
+}
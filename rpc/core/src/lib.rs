@@ -4,6 +4,7 @@
 pub mod api;
 pub mod convert;
 pub mod error;
+pub mod error_code;
 pub mod model;
 pub mod notify;
 pub mod wasm;
@@ -17,5 +18,6 @@ pub mod prelude {
 pub use api::notifications::*;
 pub use convert::utxo::*;
 pub use error::*;
+pub use error_code::{RpcErrorCategory, RpcErrorInfo};
 pub use model::script_class::*;
 pub use model::*;
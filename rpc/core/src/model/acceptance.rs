@@ -0,0 +1,22 @@
+use crate::{RpcHash, RpcTransactionId};
+use borsh::{BorshDeserialize, BorshSerialize};
+use serde::{Deserialize, Serialize};
+
+/// A chain block added to or removed from the selected chain, along with the transactions it
+/// accepted, as journaled by the acceptance journal
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcAcceptanceEvent {
+    /// Position of the event in the journal, to be passed as the cursor of the next call
+    pub sequence: u64,
+    pub chain_block_hash: RpcHash,
+    /// The block left the selected chain, so the transactions it accepted are no longer
+    /// accepted by it
+    pub removed: bool,
+    pub blue_score: u64,
+    /// Timestamp of the chain block
+    pub timestamp: u64,
+    /// Time the node recorded the event, in milliseconds since the unix epoch
+    pub recorded_time: u64,
+    pub accepted_transaction_ids: Vec<RpcTransactionId>,
+}
@@ -0,0 +1,21 @@
+use crate::RpcHash;
+use borsh::{BorshDeserialize, BorshSerialize};
+use serde::{Deserialize, Serialize};
+
+/// A block of a DAG slice along with its relations and colors, as needed to draw the DAG
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcDagBlock {
+    pub hash: RpcHash,
+    /// Direct parents of the block
+    pub parent_hashes: Vec<RpcHash>,
+    pub daa_score: u64,
+    pub blue_score: u64,
+    pub timestamp: u64,
+    /// The block is blue in the mergeset of the chain block merging it
+    pub is_blue: bool,
+    /// The block is on the selected chain of the virtual block
+    pub is_chain_block: bool,
+    /// The chain block merging the block, `None` if the block is only merged by the virtual block
+    pub merging_block_hash: Option<RpcHash>,
+}
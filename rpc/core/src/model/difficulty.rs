@@ -0,0 +1,43 @@
+use crate::RpcHash;
+use borsh::{BorshDeserialize, BorshSerialize};
+use serde::{Deserialize, Serialize};
+
+/// Difficulty of a selected chain block
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcBlockDifficulty {
+    pub hash: RpcHash,
+    pub daa_score: u64,
+    pub timestamp: u64,
+    pub bits: u32,
+    /// Difficulty as a multiple of the minimum difficulty
+    pub difficulty: f64,
+}
+
+impl RpcBlockDifficulty {
+    pub fn new(hash: RpcHash, daa_score: u64, timestamp: u64, bits: u32, difficulty: f64) -> Self {
+        Self {
+            hash,
+            daa_score,
+            timestamp,
+            bits,
+            difficulty,
+        }
+    }
+}
+
+/// Summary of the blocks composing the difficulty adjustment (DAA) window of the virtual block
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcDifficultyWindow {
+    /// Count of blocks in the window
+    pub size: u64,
+    /// The window holds one out of every `sample_rate` blocks
+    pub sample_rate: u64,
+    pub oldest_timestamp: u64,
+    pub newest_timestamp: u64,
+    pub min_difficulty: f64,
+    pub max_difficulty: f64,
+    /// Difficulty matching the average target of the window
+    pub average_difficulty: f64,
+}
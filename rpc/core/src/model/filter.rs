@@ -0,0 +1,39 @@
+use crate::RpcHash;
+use borsh::{BorshDeserialize, BorshSerialize};
+use serde::{Deserialize, Serialize};
+
+/// BIP158-style compact filter of a selected chain block, holding the script public keys of
+/// the outputs created and spent by the transactions the block accepted
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcBlockFilter {
+    pub block_hash: RpcHash,
+    #[serde(with = "hex::serde")]
+    pub filter: Vec<u8>,
+}
+
+impl RpcBlockFilter {
+    pub fn new(block_hash: RpcHash, filter: Vec<u8>) -> Self {
+        Self { block_hash, filter }
+    }
+}
+
+/// Filter header of a selected chain block, committing to its filter and to the filter header
+/// of its selected parent, or to its pruning point if the block moved it
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcBlockFilterHeader {
+    pub block_hash: RpcHash,
+    pub filter_hash: RpcHash,
+    pub header: RpcHash,
+}
+
+impl RpcBlockFilterHeader {
+    pub fn new(block_hash: RpcHash, filter_hash: RpcHash, header: RpcHash) -> Self {
+        Self {
+            block_hash,
+            filter_hash,
+            header,
+        }
+    }
+}
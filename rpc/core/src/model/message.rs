@@ -88,12 +88,65 @@ pub struct GetBlockTemplateRequest {
     pub pay_address: RpcAddress,
     // TODO: replace with hex serialization
     pub extra_data: RpcExtraData,
+    /// Identifier of the requesting worker, used to assign it a nonce range distinct
+    /// from the other workers mining the same template
+    #[serde(default)]
+    pub worker_id: Option<u16>,
+    /// Hashrate measured by the requesting miner, in hashes per second, aggregated by
+    /// `GetMinerStats` across the workers reporting it
+    #[serde(default)]
+    pub reported_hashrate: Option<f64>,
+    /// Whether to require the pay address and the worker id to be bound into the coinbase
+    /// payload, for the miner to check its jobs and for `SubmitBlock` to reject the block if it
+    /// pays elsewhere. A node configured with a work binding secret binds all its templates,
+    /// and fails the request otherwise.
+    #[serde(default)]
+    pub bind_work: bool,
 }
 impl GetBlockTemplateRequest {
+    /// Count of distinct nonce ranges handed out to the workers
+    pub const NONCE_PARTITION_COUNT: u64 = u16::MAX as u64 + 1;
+
     pub fn new(pay_address: RpcAddress, extra_data: RpcExtraData) -> Self {
         Self {
             pay_address,
             extra_data,
+            worker_id: None,
+            reported_hashrate: None,
+            bind_work: false,
+        }
+    }
+
+    pub fn with_worker_id(self, worker_id: u16) -> Self {
+        Self {
+            worker_id: Some(worker_id),
+            ..self
+        }
+    }
+
+    pub fn with_reported_hashrate(self, hashrate: f64) -> Self {
+        Self {
+            reported_hashrate: Some(hashrate),
+            ..self
+        }
+    }
+
+    pub fn with_work_binding(self) -> Self {
+        Self {
+            bind_work: true,
+            ..self
+        }
+    }
+
+    /// Returns the recommended nonce start and stride of the requesting worker.
+    ///
+    /// Workers with distinct ids try disjoint nonce sets `start + i * stride`, so miners
+    /// running several rigs on the same pay address do not duplicate work. A request
+    /// without worker id gets the whole nonce space.
+    pub fn nonce_partition(&self) -> (u64, u64) {
+        match self.worker_id {
+            Some(worker_id) => (worker_id as u64, Self::NONCE_PARTITION_COUNT),
+            None => (0, 1),
         }
     }
 }
@@ -108,6 +161,26 @@ pub struct GetBlockTemplateResponse {
     /// That is because when karlsend isn't in sync with the rest of the network there's a high
     /// chance the block will never be accepted, thus the solving effort would have been wasted.
     pub is_synced: bool,
+
+    /// First nonce recommended to the requesting worker
+    #[serde(default)]
+    pub nonce_start: u64,
+
+    /// Increment between two nonces tried by the requesting worker
+    #[serde(default = "default_nonce_stride")]
+    pub nonce_stride: u64,
+}
+
+fn default_nonce_stride() -> u64 {
+    1
+}
+
+impl GetBlockTemplateResponse {
+    /// Returns the `index`-th nonce of the range recommended to the requesting worker
+    pub fn nonce_at(&self, index: u64) -> u64 {
+        self.nonce_start
+            .wrapping_add(index.wrapping_mul(self.nonce_stride))
+    }
 }
 
 /// GetBlockRequest requests information about a specific block
@@ -119,12 +192,25 @@ pub struct GetBlockRequest {
 
     /// Whether to include transaction data in the response
     pub include_transactions: bool,
+
+    /// Whether to set the output spent by every transaction input, for computing the fees
+    /// and the senders of the transactions without looking up each previous transaction
+    #[serde(default)]
+    pub resolve_previous_outputs: bool,
 }
 impl GetBlockRequest {
     pub fn new(hash: RpcHash, include_transactions: bool) -> Self {
         Self {
             hash,
             include_transactions,
+            resolve_previous_outputs: false,
+        }
+    }
+
+    pub fn with_previous_outputs(self) -> Self {
+        Self {
+            resolve_previous_outputs: true,
+            ..self
         }
     }
 }
@@ -150,6 +236,14 @@ pub struct GetInfoResponse {
     pub is_synced: bool,
     pub has_notify_command: bool,
     pub has_message_id: bool,
+    /// Median offset of the local clock against the clocks of the connected peers, in
+    /// milliseconds (positive if the local clock is ahead)
+    pub peer_clock_offset: i64,
+    /// Median delay between the timestamps of recent relay block headers and their arrival,
+    /// in milliseconds
+    pub header_clock_offset: i64,
+    /// Whether the local clock is skewed beyond the warning threshold of the node
+    pub is_clock_skewed: bool,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
@@ -213,6 +307,9 @@ pub struct GetMempoolEntryRequest {
     pub include_orphan_pool: bool,
     // TODO: replace with `include_transaction_pool`
     pub filter_transaction_pool: bool,
+    /// Whether to set the output spent by every transaction input
+    #[serde(default)]
+    pub resolve_previous_outputs: bool,
 }
 
 impl GetMempoolEntryRequest {
@@ -225,6 +322,14 @@ impl GetMempoolEntryRequest {
             transaction_id,
             include_orphan_pool,
             filter_transaction_pool,
+            resolve_previous_outputs: false,
+        }
+    }
+
+    pub fn with_previous_outputs(self) -> Self {
+        Self {
+            resolve_previous_outputs: true,
+            ..self
         }
     }
 }
@@ -241,12 +346,27 @@ impl GetMempoolEntryResponse {
     }
 }
 
+/// GetMempoolEntriesRequest requests the transactions of the mempool.
+///
+/// A response exceeding the maximum response size of the node is an error unless the request
+/// is `paginate`d, in which case the entries come by pages ordered by transaction id. Pages are
+/// iterated by sending the request returned by [`GetMempoolEntriesRequest::next_page`] until
+/// it returns none.
 #[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct GetMempoolEntriesRequest {
     pub include_orphan_pool: bool,
     // TODO: replace with `include_transaction_pool`
     pub filter_transaction_pool: bool,
+    /// Splits an oversized response in pages instead of failing
+    #[serde(default)]
+    pub paginate: bool,
+    /// Cursor of the requested page, `None` for the first one
+    #[serde(default)]
+    pub cursor: Option<String>,
+    /// Whether to set the output spent by every transaction input
+    #[serde(default)]
+    pub resolve_previous_outputs: bool,
 }
 
 impl GetMempoolEntriesRequest {
@@ -254,19 +374,58 @@ impl GetMempoolEntriesRequest {
         Self {
             include_orphan_pool,
             filter_transaction_pool,
+            paginate: false,
+            cursor: None,
+            resolve_previous_outputs: false,
+        }
+    }
+
+    pub fn with_previous_outputs(self) -> Self {
+        Self {
+            resolve_previous_outputs: true,
+            ..self
+        }
+    }
+
+    pub fn with_pagination(self) -> Self {
+        Self {
+            paginate: true,
+            ..self
         }
     }
+
+    /// Returns the request of the page following `response` or `None` if it is the last page
+    pub fn next_page(&self, response: &GetMempoolEntriesResponse) -> Option<Self> {
+        response.next_cursor.clone().map(|cursor| Self {
+            paginate: true,
+            cursor: Some(cursor),
+            ..self.clone()
+        })
+    }
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct GetMempoolEntriesResponse {
     pub mempool_entries: Vec<RpcMempoolEntry>,
+    /// Cursor of the next page, `None` if the response is complete
+    #[serde(default)]
+    pub next_cursor: Option<String>,
 }
 
 impl GetMempoolEntriesResponse {
     pub fn new(mempool_entries: Vec<RpcMempoolEntry>) -> Self {
-        Self { mempool_entries }
+        Self {
+            mempool_entries,
+            next_cursor: None,
+        }
+    }
+
+    pub fn with_next_cursor(self, next_cursor: Option<String>) -> Self {
+        Self {
+            next_cursor,
+            ..self
+        }
     }
 }
 
@@ -396,12 +555,56 @@ impl GetVirtualChainFromBlockResponse {
     }
 }
 
+/// Level of detail of the blocks returned by [`GetBlocksRequest`]
+#[derive(
+    Clone, Copy, Eq, PartialEq, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize,
+)]
+#[serde(rename_all = "camelCase")]
+pub enum RpcBlockVerbosity {
+    /// Block header and verbose data, without the transaction ids
+    Low = 1,
+    /// Block header and verbose data, including the transaction ids
+    Medium = 2,
+    /// Block header, verbose data and full transactions with their verbose data
+    High = 3,
+}
+
+impl RpcBlockVerbosity {
+    pub fn includes_transaction_ids(&self) -> bool {
+        *self != RpcBlockVerbosity::Low
+    }
+
+    pub fn includes_transactions(&self) -> bool {
+        *self == RpcBlockVerbosity::High
+    }
+}
+
+/// GetBlocksRequest requests the blocks between a certain block `low_hash` up to this node's
+/// current virtual, in ascending order.
+///
+/// A single call returns a bounded batch. If the response has a `next_low_hash`, the range
+/// continues and can be iterated by sending the request returned by [`GetBlocksRequest::next_batch`]
+/// until the response has none.
 #[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct GetBlocksRequest {
     pub low_hash: Option<RpcHash>,
     pub include_blocks: bool,
     pub include_transactions: bool,
+    /// If set, overrides `include_transactions` for selecting the level of detail of the blocks.
+    /// Requires `include_blocks`.
+    #[serde(default)]
+    pub verbosity: Option<RpcBlockVerbosity>,
+    /// Requested maximum count of blocks in a batch, 0 meaning the node default.
+    ///
+    /// The node bounds this value: it is raised to the mergeset size limit and may be lowered,
+    /// notably when full transactions are requested. A batch may also exceed it by the sink
+    /// anticone when the range reaches the sink.
+    #[serde(default)]
+    pub batch_size: u32,
+    /// Whether to set the output spent by every transaction input
+    #[serde(default)]
+    pub resolve_previous_outputs: bool,
 }
 
 impl GetBlocksRequest {
@@ -414,8 +617,52 @@ impl GetBlocksRequest {
             low_hash,
             include_blocks,
             include_transactions,
+            verbosity: None,
+            batch_size: 0,
+            resolve_previous_outputs: false,
+        }
+    }
+
+    pub fn with_verbosity(self, verbosity: RpcBlockVerbosity) -> Self {
+        Self {
+            include_blocks: true,
+            verbosity: Some(verbosity),
+            ..self
+        }
+    }
+
+    pub fn with_batch_size(self, batch_size: u32) -> Self {
+        Self { batch_size, ..self }
+    }
+
+    pub fn with_previous_outputs(self) -> Self {
+        Self {
+            resolve_previous_outputs: true,
+            ..self
+        }
+    }
+
+    /// Returns the level of detail of the requested blocks or `None` if only hashes are requested
+    pub fn block_verbosity(&self) -> Option<RpcBlockVerbosity> {
+        match (
+            self.include_blocks,
+            self.verbosity,
+            self.include_transactions,
+        ) {
+            (false, _, _) => None,
+            (true, Some(verbosity), _) => Some(verbosity),
+            (true, None, true) => Some(RpcBlockVerbosity::High),
+            (true, None, false) => Some(RpcBlockVerbosity::Medium),
         }
     }
+
+    /// Returns the request of the batch following `response` or `None` if the range is complete
+    pub fn next_batch(&self, response: &GetBlocksResponse) -> Option<Self> {
+        response.next_low_hash.map(|low_hash| Self {
+            low_hash: Some(low_hash),
+            ..self.clone()
+        })
+    }
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
@@ -423,13 +670,24 @@ impl GetBlocksRequest {
 pub struct GetBlocksResponse {
     pub block_hashes: Vec<RpcHash>,
     pub blocks: Vec<RpcBlock>,
+    /// Low hash of the next batch, `None` if this batch reaches the sink.
+    ///
+    /// Since the low hash of a request is always returned first, the next batch starts with
+    /// the last chain block of this one.
+    #[serde(default)]
+    pub next_low_hash: Option<RpcHash>,
 }
 
 impl GetBlocksResponse {
-    pub fn new(block_hashes: Vec<RpcHash>, blocks: Vec<RpcBlock>) -> Self {
+    pub fn new(
+        block_hashes: Vec<RpcHash>,
+        blocks: Vec<RpcBlock>,
+        next_low_hash: Option<RpcHash>,
+    ) -> Self {
         Self {
             block_hashes,
             blocks,
+            next_low_hash,
         }
     }
 }
@@ -607,15 +865,47 @@ impl GetSinkBlueScoreResponse {
     }
 }
 
+/// GetUtxosByAddressesRequest requests the UTXOs of some addresses.
+///
+/// A response exceeding the maximum response size of the node is an error unless the request
+/// is `paginate`d, in which case the entries come by pages listing the addresses one after the
+/// other. Pages are iterated by sending the request returned by
+/// [`GetUtxosByAddressesRequest::next_page`] until it returns none.
 #[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct GetUtxosByAddressesRequest {
     pub addresses: Vec<RpcAddress>,
+    /// Splits an oversized response in pages instead of failing
+    #[serde(default)]
+    pub paginate: bool,
+    /// Cursor of the requested page, `None` for the first one
+    #[serde(default)]
+    pub cursor: Option<String>,
 }
 
 impl GetUtxosByAddressesRequest {
     pub fn new(addresses: Vec<RpcAddress>) -> Self {
-        Self { addresses }
+        Self {
+            addresses,
+            paginate: false,
+            cursor: None,
+        }
+    }
+
+    pub fn with_pagination(self) -> Self {
+        Self {
+            paginate: true,
+            ..self
+        }
+    }
+
+    /// Returns the request of the page following `response` or `None` if it is the last page
+    pub fn next_page(&self, response: &GetUtxosByAddressesResponse) -> Option<Self> {
+        response.next_cursor.clone().map(|cursor| Self {
+            paginate: true,
+            cursor: Some(cursor),
+            ..self.clone()
+        })
     }
 }
 
@@ -623,11 +913,24 @@ impl GetUtxosByAddressesRequest {
 #[serde(rename_all = "camelCase")]
 pub struct GetUtxosByAddressesResponse {
     pub entries: Vec<RpcUtxosByAddressesEntry>,
+    /// Cursor of the next page, `None` if the response is complete
+    #[serde(default)]
+    pub next_cursor: Option<String>,
 }
 
 impl GetUtxosByAddressesResponse {
     pub fn new(entries: Vec<RpcUtxosByAddressesEntry>) -> Self {
-        Self { entries }
+        Self {
+            entries,
+            next_cursor: None,
+        }
+    }
+
+    pub fn with_next_cursor(self, next_cursor: Option<String>) -> Self {
+        Self {
+            next_cursor,
+            ..self
+        }
     }
 }
 
@@ -700,6 +1003,9 @@ pub struct GetMempoolEntriesByAddressesRequest {
     pub include_orphan_pool: bool,
     // TODO: replace with `include_transaction_pool`
     pub filter_transaction_pool: bool,
+    /// Whether to set the output spent by every transaction input
+    #[serde(default)]
+    pub resolve_previous_outputs: bool,
 }
 
 impl GetMempoolEntriesByAddressesRequest {
@@ -712,6 +1018,14 @@ impl GetMempoolEntriesByAddressesRequest {
             addresses,
             include_orphan_pool,
             filter_transaction_pool,
+            resolve_previous_outputs: false,
+        }
+    }
+
+    pub fn with_previous_outputs(self) -> Self {
+        Self {
+            resolve_previous_outputs: true,
+            ..self
         }
     }
 }
@@ -779,6 +1093,14 @@ pub struct ProcessMetrics {
     pub disk_io_write_bytes: u64,
     pub disk_io_read_per_sec: f32,
     pub disk_io_write_per_sec: f32,
+    /// Memory limit of the process in bytes, 0 if the memory budget is disabled
+    pub memory_limit: u64,
+    /// Estimated memory held by the caches and pools under the memory budget, in bytes
+    pub memory_budget_usage: u64,
+    /// Count of times the process was found above the memory limit
+    pub memory_pressure_events: u64,
+    /// Estimated memory released by the caches and pools under pressure, in bytes
+    pub memory_released_bytes: u64,
 }
 
 #[derive(Default, Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
@@ -827,6 +1149,10 @@ pub struct ConsensusMetrics {
     pub network_past_median_time: u64,
     pub network_virtual_parent_hashes_count: u32,
     pub network_virtual_daa_score: u64,
+
+    pub node_utxo_index_checked_entries_count: u64,
+    pub node_utxo_index_divergent_entries_count: u64,
+    pub node_utxo_index_repaired_entries_count: u64,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
@@ -906,162 +1232,842 @@ impl GetDaaScoreTimestampEstimateResponse {
     }
 }
 
-// ----------------------------------------------------------------------------
-// Subscriptions & notifications
-// ----------------------------------------------------------------------------
-
-// ~~~~~~~~~~~~~~~~~~~~~~
-// BlockAddedNotification
+/// GetServerCapabilitiesRequest requests the versions and the feature set of the node RPC server.
+/// Clients are expected to issue it right after connecting and to check the reported versions
+/// before relying on any method or notification.
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetServerCapabilitiesRequest {}
 
-/// NotifyBlockAddedRequest registers this connection for blockAdded notifications.
-///
-/// See: BlockAddedNotification
 #[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
 #[serde(rename_all = "camelCase")]
-pub struct NotifyBlockAddedRequest {
-    pub command: Command,
+pub struct GetServerCapabilitiesResponse {
+    pub rpc_api_version: [u16; 4],
+    pub protowire_api_version: u32,
+    pub server_version: String,
+    /// Names of the RPC methods implemented by the node and allowed by the listener
+    pub methods: Vec<String>,
+    /// Names of the notification event types the node is able to emit through the listener
+    pub notifications: Vec<String>,
 }
-impl NotifyBlockAddedRequest {
-    pub fn new(command: Command) -> Self {
-        Self { command }
+
+impl GetServerCapabilitiesResponse {
+    pub fn new(
+        rpc_api_version: [u16; 4],
+        protowire_api_version: u32,
+        server_version: String,
+        methods: Vec<String>,
+        notifications: Vec<String>,
+    ) -> Self {
+        Self {
+            rpc_api_version,
+            protowire_api_version,
+            server_version,
+            methods,
+            notifications,
+        }
+    }
+
+    pub fn supports_method(&self, method: &str) -> bool {
+        self.methods.iter().any(|m| m == method)
+    }
+
+    pub fn supports_notification(&self, notification: &str) -> bool {
+        self.notifications.iter().any(|n| n == notification)
     }
 }
 
+/// GetNetworkInfoRequest requests the identity of the network the node is running on.
+/// Clients building transactions should compare it with the network they target
+/// before submitting anything.
 #[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
 #[serde(rename_all = "camelCase")]
-pub struct NotifyBlockAddedResponse {}
+pub struct GetNetworkInfoRequest {}
 
-/// BlockAddedNotification is sent whenever a blocks has been added (NOT accepted)
-/// into the DAG.
-///
-/// See: NotifyBlockAddedRequest
 #[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
 #[serde(rename_all = "camelCase")]
-pub struct BlockAddedNotification {
-    pub block: Arc<RpcBlock>,
+pub struct GetNetworkInfoResponse {
+    pub network_id: RpcNetworkId,
+    /// Prefix of the addresses accepted by the node (ie. `karlsen`, `karlsentest`)
+    pub address_prefix: String,
+    pub genesis_hash: RpcHash,
 }
 
-// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
-// VirtualChainChangedNotification
+impl GetNetworkInfoResponse {
+    pub fn new(network_id: RpcNetworkId, address_prefix: String, genesis_hash: RpcHash) -> Self {
+        Self {
+            network_id,
+            address_prefix,
+            genesis_hash,
+        }
+    }
+}
 
-// NotifyVirtualChainChangedRequest registers this connection for
-// virtualDaaScoreChanged notifications.
-//
-// See: VirtualChainChangedNotification
+/// TestMempoolAcceptRequest runs the full mempool validation of a transaction (mass, fees,
+/// scripts, maturity, conflicts) without adding it to the mempool nor relaying it.
 #[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
 #[serde(rename_all = "camelCase")]
-pub struct NotifyVirtualChainChangedRequest {
-    pub include_accepted_transaction_ids: bool,
-    pub command: Command,
+pub struct TestMempoolAcceptRequest {
+    pub transaction: RpcTransaction,
 }
 
-impl NotifyVirtualChainChangedRequest {
-    pub fn new(include_accepted_transaction_ids: bool, command: Command) -> Self {
-        Self {
-            include_accepted_transaction_ids,
-            command,
-        }
+impl TestMempoolAcceptRequest {
+    pub fn new(transaction: RpcTransaction) -> Self {
+        Self { transaction }
     }
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
 #[serde(rename_all = "camelCase")]
-pub struct NotifyVirtualChainChangedResponse {}
+pub struct TestMempoolAcceptResponse {
+    pub transaction_id: RpcTransactionId,
+    pub accepted: bool,
+    /// Reason of the rejection, `None` if the transaction would be accepted
+    pub reject_reason: Option<String>,
+    /// Fee paid by the transaction, 0 if its inputs could not be resolved
+    pub fee: u64,
+    /// Compute mass of the transaction, 0 if not reached by the validation
+    pub compute_mass: u64,
+    /// Contextual mass of the transaction, including the storage mass, 0 if not reached by the validation
+    pub mass: u64,
+    /// Fee per gram of contextual mass, as used for the transaction selection, 0 if not available
+    pub feerate: f64,
+}
+
+impl TestMempoolAcceptResponse {
+    pub fn new(
+        transaction_id: RpcTransactionId,
+        reject_reason: Option<String>,
+        fee: u64,
+        compute_mass: u64,
+        mass: u64,
+        feerate: f64,
+    ) -> Self {
+        Self {
+            transaction_id,
+            accepted: reject_reason.is_none(),
+            reject_reason,
+            fee,
+            compute_mass,
+            mass,
+            feerate,
+        }
+    }
+}
 
-// VirtualChainChangedNotification is sent whenever the DAG's selected parent
-// chain had changed.
-//
-// See: NotifyVirtualChainChangedRequest
+/// GetDifficultyInfoRequest requests the difficulty adjustment state of the virtual block
+/// along with the difficulty of the `chain_block_count` most recent selected chain blocks.
 #[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
 #[serde(rename_all = "camelCase")]
-pub struct VirtualChainChangedNotification {
-    pub removed_chain_block_hashes: Arc<Vec<RpcHash>>,
-    pub added_chain_block_hashes: Arc<Vec<RpcHash>>,
-    pub accepted_transaction_ids: Arc<Vec<RpcAcceptedTransactionIds>>,
+pub struct GetDifficultyInfoRequest {
+    pub chain_block_count: u32,
 }
 
-// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~
-// FinalityConflictNotification
+impl GetDifficultyInfoRequest {
+    pub fn new(chain_block_count: u32) -> Self {
+        Self { chain_block_count }
+    }
+}
 
 #[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
 #[serde(rename_all = "camelCase")]
-pub struct NotifyFinalityConflictRequest {
-    pub command: Command,
+pub struct GetDifficultyInfoResponse {
+    /// DAA score of the virtual block
+    pub daa_score: u64,
+    /// Difficulty bits expected for the next block
+    pub next_bits: u32,
+    pub next_difficulty: f64,
+    /// Target time per block in milliseconds
+    pub target_time_per_block: u64,
+    pub window: RpcDifficultyWindow,
+    /// Most recent selected chain blocks, starting from the sink
+    pub chain_blocks: Vec<RpcBlockDifficulty>,
 }
 
-impl NotifyFinalityConflictRequest {
-    pub fn new(command: Command) -> Self {
-        Self { command }
+impl GetDifficultyInfoResponse {
+    pub fn new(
+        daa_score: u64,
+        next_bits: u32,
+        next_difficulty: f64,
+        target_time_per_block: u64,
+        window: RpcDifficultyWindow,
+        chain_blocks: Vec<RpcBlockDifficulty>,
+    ) -> Self {
+        Self {
+            daa_score,
+            next_bits,
+            next_difficulty,
+            target_time_per_block,
+            window,
+            chain_blocks,
+        }
     }
 }
 
+/// DebugScriptRequest evaluates the signature script of the input `input_index` of `transaction`
+/// against the script public key of its UTXO entry, recording the engine state after every opcode.
+///
+/// The transaction is not validated, so both scripts can be freely edited while debugging.
+/// `utxo_entries` holds the entries spent by every input of the transaction since they are all
+/// committed to by the signature hashes.
 #[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
 #[serde(rename_all = "camelCase")]
-pub struct NotifyFinalityConflictResponse {}
+pub struct DebugScriptRequest {
+    pub transaction: RpcTransaction,
+    pub input_index: u32,
+    pub utxo_entries: Vec<RpcUtxoEntry>,
+}
+
+impl DebugScriptRequest {
+    pub fn new(
+        transaction: RpcTransaction,
+        input_index: u32,
+        utxo_entries: Vec<RpcUtxoEntry>,
+    ) -> Self {
+        Self {
+            transaction,
+            input_index,
+            utxo_entries,
+        }
+    }
+}
 
 #[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
 #[serde(rename_all = "camelCase")]
-pub struct FinalityConflictNotification {
-    pub violating_block_hash: RpcHash,
+pub struct DebugScriptResponse {
+    pub success: bool,
+    /// Reason of the script failure, `None` on success
+    pub error: Option<String>,
+    pub steps: Vec<RpcScriptStep>,
 }
 
-// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
-// FinalityConflictResolvedNotification
+impl DebugScriptResponse {
+    pub fn new(error: Option<String>, steps: Vec<RpcScriptStep>) -> Self {
+        Self {
+            success: error.is_none(),
+            error,
+            steps,
+        }
+    }
+}
 
+/// GetTransactionStatusRequest reports the broadcast and acceptance status of a transaction.
+///
+/// The node has no transaction index, so the accepting block and the confirmations are only
+/// reported for transactions submitted through the RPC of this node since it started (see
+/// `GetTransactionStatusResponse::is_tracked`).
 #[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
 #[serde(rename_all = "camelCase")]
-pub struct NotifyFinalityConflictResolvedRequest {
-    pub command: Command,
+pub struct GetTransactionStatusRequest {
+    pub transaction_id: RpcTransactionId,
 }
 
-impl NotifyFinalityConflictResolvedRequest {
-    pub fn new(command: Command) -> Self {
-        Self { command }
+impl GetTransactionStatusRequest {
+    pub fn new(transaction_id: RpcTransactionId) -> Self {
+        Self { transaction_id }
     }
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
 #[serde(rename_all = "camelCase")]
-pub struct NotifyFinalityConflictResolvedResponse {}
+pub struct GetTransactionStatusResponse {
+    pub transaction_id: RpcTransactionId,
+    pub in_mempool: bool,
+    pub is_orphan: bool,
+    /// The transaction was submitted through the RPC of this node and its acceptance is tracked
+    pub is_tracked: bool,
+    /// Count of distinct peers this node sent an announcement of the transaction to, whether
+    /// submitted locally or relayed, capped at 32. It stays 0 until the next broadcast round
+    /// and for orphans, and is forgotten after 100,000 newer announced transactions
+    pub announced_peers: u32,
+    /// Selected chain block accepting the transaction, if any
+    pub accepting_block_hash: Option<RpcHash>,
+    /// Blue score difference between the sink and the accepting block, 0 if not accepted
+    pub confirmations: u64,
+}
+
+impl GetTransactionStatusResponse {
+    pub fn new(
+        transaction_id: RpcTransactionId,
+        in_mempool: bool,
+        is_orphan: bool,
+        is_tracked: bool,
+        announced_peers: u32,
+        accepting_block_hash: Option<RpcHash>,
+        confirmations: u64,
+    ) -> Self {
+        Self {
+            transaction_id,
+            in_mempool,
+            is_orphan,
+            is_tracked,
+            announced_peers,
+            accepting_block_hash,
+            confirmations,
+        }
+    }
+}
 
+/// GetBlockFilterHeadersRequest requests the compact filter headers of up to `limit` selected
+/// chain blocks, starting at `start_hash` and ascending the chain.
+///
+/// Filter headers let a light client check the filters served by a node against the ones
+/// served by others. Requires the node to run with a block filter index.
 #[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
 #[serde(rename_all = "camelCase")]
-pub struct FinalityConflictResolvedNotification {
-    pub finality_block_hash: RpcHash,
+pub struct GetBlockFilterHeadersRequest {
+    pub start_hash: RpcHash,
+    pub limit: u32,
 }
 
-// ~~~~~~~~~~~~~~~~~~~~~~~~
-// UtxosChangedNotification
+impl GetBlockFilterHeadersRequest {
+    pub fn new(start_hash: RpcHash, limit: u32) -> Self {
+        Self { start_hash, limit }
+    }
+}
 
-// NotifyUtxosChangedRequestMessage registers this connection for utxoChanged notifications
-// for the given addresses. Depending on the provided `command`, notifications will
-// start or stop for the provided `addresses`.
-//
-// If `addresses` is empty, the notifications will start or stop for all addresses.
-//
-// This call is only available when this karlsend was started with `--utxoindex`
-//
-// See: UtxosChangedNotification
 #[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
 #[serde(rename_all = "camelCase")]
-pub struct NotifyUtxosChangedRequest {
-    pub addresses: Vec<RpcAddress>,
-    pub command: Command,
+pub struct GetBlockFilterHeadersResponse {
+    pub headers: Vec<RpcBlockFilterHeader>,
+    /// Highest chain block covered by the index
+    pub tip_hash: RpcHash,
 }
 
-impl NotifyUtxosChangedRequest {
-    pub fn new(addresses: Vec<RpcAddress>, command: Command) -> Self {
-        Self { addresses, command }
+impl GetBlockFilterHeadersResponse {
+    pub fn new(headers: Vec<RpcBlockFilterHeader>, tip_hash: RpcHash) -> Self {
+        Self { headers, tip_hash }
     }
 }
 
+/// GetBlockFiltersRequest requests the compact filters of up to `limit` selected chain blocks,
+/// starting at `start_hash` and ascending the chain.
+///
+/// A wallet matches its script public keys against the filters locally and only fetches the
+/// blocks whose filter matches, never disclosing its addresses to the node. Requires the node
+/// to run with a block filter index.
 #[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
 #[serde(rename_all = "camelCase")]
-pub struct NotifyUtxosChangedResponse {}
+pub struct GetBlockFiltersRequest {
+    pub start_hash: RpcHash,
+    pub limit: u32,
+}
 
-// UtxosChangedNotificationMessage is sent whenever the UTXO index had been updated.
-//
-// See: NotifyUtxosChangedRequest
+impl GetBlockFiltersRequest {
+    pub fn new(start_hash: RpcHash, limit: u32) -> Self {
+        Self { start_hash, limit }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetBlockFiltersResponse {
+    pub filters: Vec<RpcBlockFilter>,
+}
+
+impl GetBlockFiltersResponse {
+    pub fn new(filters: Vec<RpcBlockFilter>) -> Self {
+        Self { filters }
+    }
+}
+
+/// Requests the balances of `addresses` as of the highest selected chain block with a DAA
+/// score not above `daa_score`
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetBalanceByAddressesAtRequest {
+    pub addresses: Vec<RpcAddress>,
+    pub daa_score: u64,
+}
+
+impl GetBalanceByAddressesAtRequest {
+    pub fn new(addresses: Vec<RpcAddress>, daa_score: u64) -> Self {
+        Self {
+            addresses,
+            daa_score,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetBalanceByAddressesAtResponse {
+    pub entries: Vec<RpcBalancesByAddressesEntry>,
+    /// Chain block the balances are taken at
+    pub chain_block_hash: RpcHash,
+    /// DAA score of the chain block
+    pub daa_score: u64,
+}
+
+impl GetBalanceByAddressesAtResponse {
+    pub fn new(
+        entries: Vec<RpcBalancesByAddressesEntry>,
+        chain_block_hash: RpcHash,
+        daa_score: u64,
+    ) -> Self {
+        Self {
+            entries,
+            chain_block_hash,
+            daa_score,
+        }
+    }
+}
+
+/// GetUnconfirmedTxRiskRequest reports heuristics helping to decide whether an unconfirmed
+/// transaction can be accepted as a payment before getting confirmed.
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetUnconfirmedTxRiskRequest {
+    pub transaction_id: RpcTransactionId,
+}
+
+impl GetUnconfirmedTxRiskRequest {
+    pub fn new(transaction_id: RpcTransactionId) -> Self {
+        Self { transaction_id }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetUnconfirmedTxRiskResponse {
+    pub transaction_id: RpcTransactionId,
+    pub in_mempool: bool,
+    pub is_orphan: bool,
+    /// The transaction was recently accepted by the DAG
+    pub is_accepted: bool,
+    /// Fee per gram of mass, 0 if the transaction is not in the mempool
+    pub fee_rate: f64,
+    /// Percentage of the mempool transactions paying a lower fee rate
+    pub fee_rate_percentile: f64,
+    /// Transactions recently observed spending some of the outpoints of the transaction
+    pub conflicting_transaction_ids: Vec<RpcTransactionId>,
+    /// Age in DAA score of the UTXO spent by each input, 0 for unconfirmed UTXOs
+    pub input_ages: Vec<u64>,
+    /// Count of inputs spending outputs of unconfirmed or unknown transactions
+    pub unconfirmed_input_count: u32,
+    /// Count of distinct peers which announced the transaction to this node
+    pub announcing_peer_count: u32,
+}
+
+/// GetDagSliceRequest requests the blocks of the DAG merged by the selected chain blocks from the
+/// first one reaching a DAA score on, along with their parents, their colors and their membership
+/// in the selected chain, for drawing the DAG.
+///
+/// The chain is walked forward until `count` blocks are collected. The mergeset of a chain block
+/// is never split between two slices, so a slice may hold a few more blocks than `count`. A slice
+/// reaching the sink ends with the blocks merged by the virtual block only, which the following
+/// slices return again once a chain block merges them.
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetDagSliceRequest {
+    pub from_daa_score: u64,
+    pub count: u32,
+}
+
+impl GetDagSliceRequest {
+    pub fn new(from_daa_score: u64, count: u32) -> Self {
+        Self {
+            from_daa_score,
+            count,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetDagSliceResponse {
+    /// Blocks ordered by DAA score
+    pub blocks: Vec<RpcDagBlock>,
+    /// DAA score to request the following slice from
+    pub next_daa_score: u64,
+}
+
+/// GetAcceptanceEventsSinceRequest replays the chain acceptance events journaled after
+/// `cursor`, letting a client which was offline catch up with the transactions accepted and
+/// unaccepted by the selected chain meanwhile.
+///
+/// The client pages through this call from the sequence of the last event it processed until
+/// `has_more` is false. A `gap` means the node no longer retains the events following the
+/// cursor, so the client must fall back to a full rescan.
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetAcceptanceEventsSinceRequest {
+    /// Sequence of the last event processed by the client. If zero, the replay starts at the
+    /// oldest event retained by the node.
+    pub cursor: u64,
+    pub max_count: u32,
+}
+
+impl GetAcceptanceEventsSinceRequest {
+    pub fn new(cursor: u64, max_count: u32) -> Self {
+        Self { cursor, max_count }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetAcceptanceEventsSinceResponse {
+    pub events: Vec<RpcAcceptanceEvent>,
+    /// The cursor is no longer retained by the node: some events were missed before the
+    /// first returned one
+    pub gap: bool,
+    /// More events are available after the last returned one
+    pub has_more: bool,
+}
+
+impl GetAcceptanceEventsSinceResponse {
+    pub fn new(events: Vec<RpcAcceptanceEvent>, gap: bool, has_more: bool) -> Self {
+        Self {
+            events,
+            gap,
+            has_more,
+        }
+    }
+}
+
+/// GetTemplateDiffRequest requests a current block template, sending only the transactions
+/// added to and removed from the template `since_template_id` previously issued to the caller.
+///
+/// Meant for pool bridges refreshing their jobs several times per second: the transaction set of
+/// successive templates mostly overlaps, so resending it in full is wasted serialization.
+/// The coinbase is bound to the pay address like the one of `GetBlockTemplate`, without worker id.
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetTemplateDiffRequest {
+    /// Which karlsen address should the coinbase block reward transaction pay into
+    pub pay_address: RpcAddress,
+    pub extra_data: RpcExtraData,
+    /// Id of the template the caller holds, zero for none
+    pub since_template_id: u64,
+}
+
+impl GetTemplateDiffRequest {
+    pub fn new(pay_address: RpcAddress, extra_data: RpcExtraData, since_template_id: u64) -> Self {
+        Self {
+            pay_address,
+            extra_data,
+            since_template_id,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetTemplateDiffResponse {
+    /// Id of the issued template, to be passed as `since_template_id` of the next call
+    pub template_id: u64,
+    /// Id of the template the diff applies to. Zero if the requested template is no longer
+    /// retained by the node, in which case all the transactions are reported as added.
+    pub base_template_id: u64,
+    pub header: RpcHeader,
+    pub coinbase_transaction: RpcTransaction,
+    /// Ids of the non-coinbase transactions of the template, in block order
+    pub transaction_ids: Vec<RpcTransactionId>,
+    /// Transactions of the template missing from the base template, in block order
+    pub added_transactions: Vec<RpcTransaction>,
+    /// Transactions of the base template missing from the template
+    pub removed_transaction_ids: Vec<RpcTransactionId>,
+    /// Whether karlsend thinks that it's synced, see `GetBlockTemplateResponse`
+    pub is_synced: bool,
+}
+
+/// GetMetadataRequest reads entries of a namespace of the metadata store kept by the node on
+/// behalf of the external indexers running alongside it.
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetMetadataRequest {
+    pub namespace: String,
+    pub keys: Vec<String>,
+}
+
+impl GetMetadataRequest {
+    pub fn new(namespace: String, keys: Vec<String>) -> Self {
+        Self { namespace, keys }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetMetadataResponse {
+    /// Entries of the requested keys present in the namespace, in request order
+    pub entries: Vec<RpcMetadataEntry>,
+}
+
+impl GetMetadataResponse {
+    pub fn new(entries: Vec<RpcMetadataEntry>) -> Self {
+        Self { entries }
+    }
+}
+
+/// UpdateMetadataRequest atomically changes entries of a namespace of the metadata store.
+///
+/// The update is applied only if all the expected entries hold their values and all the
+/// expected absent keys are missing, otherwise nothing is changed and the call fails. Deletions
+/// are applied before the entries are set.
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateMetadataRequest {
+    pub namespace: String,
+    pub expected_entries: Vec<RpcMetadataEntry>,
+    pub expected_absent_keys: Vec<String>,
+    pub set_entries: Vec<RpcMetadataEntry>,
+    pub delete_keys: Vec<String>,
+}
+
+impl UpdateMetadataRequest {
+    pub fn new(
+        namespace: String,
+        expected_entries: Vec<RpcMetadataEntry>,
+        expected_absent_keys: Vec<String>,
+        set_entries: Vec<RpcMetadataEntry>,
+        delete_keys: Vec<String>,
+    ) -> Self {
+        Self {
+            namespace,
+            expected_entries,
+            expected_absent_keys,
+            set_entries,
+            delete_keys,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateMetadataResponse {}
+
+/// GetMinerStatsRequest estimates the profitability of the miners connected to the node, from
+/// the blocks they submitted and the difficulty of the latest block template.
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetMinerStatsRequest {}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetMinerStatsResponse {
+    /// Blocks submitted by the miners since the node started, accepted and rejected
+    pub accepted_blocks: u64,
+    pub rejected_blocks: u64,
+    /// Hashrate reported by the miners along with their template requests, or estimated from
+    /// the blocks they found over the last day when none reports it, in hashes per second
+    pub hashrate: f64,
+    /// Network hashrate implied by the difficulty of the latest template, in hashes per second
+    pub network_hashrate: f64,
+    /// Share of the network hashrate held by the miners, in [0, 1]
+    pub network_share: f64,
+    /// Expected time between two blocks found by the miners, unknown while their hashrate is
+    pub expected_seconds_to_block: Option<f64>,
+    /// Coinbase value of the latest template, in sompi
+    pub block_reward: u64,
+    /// Expected coinbase value earned per day, in sompi
+    pub expected_daily_reward: u64,
+    /// Daily cost of the power drawn by the miners, if configured on the node
+    pub daily_power_cost: Option<f64>,
+    /// Power cost of a mined KLS, if configured on the node and the hashrate is known
+    pub cost_per_kls: Option<f64>,
+    /// Templates issued with their work bound to the pay address and worker
+    pub bound_templates: u64,
+    /// Accepted blocks carrying a valid work binding
+    pub bound_blocks: u64,
+    /// Blocks rejected for carrying no valid binding of the address they pay
+    pub binding_mismatches: u64,
+}
+
+/// GetAddressClusterRequest returns the addresses assumed to share the owner of `address`,
+/// having been spent along with it by a transaction (the common-input-ownership heuristic).
+/// Only the transactions accepted since the pruning point the index was built from are
+/// clustered, so links made by older transactions are missing.
+///
+/// Requires the node to be built with the analytics feature and to run with an address cluster
+/// index.
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetAddressClusterRequest {
+    pub address: RpcAddress,
+    /// Maximum count of addresses returned, capped at 1000 by the node
+    pub limit: u32,
+}
+
+impl GetAddressClusterRequest {
+    pub fn new(address: RpcAddress, limit: u32) -> Self {
+        Self { address, limit }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetAddressClusterResponse {
+    /// Count of script public keys in the cluster, 1 if the address was never spent along with others
+    pub cluster_size: u64,
+    /// Addresses of the cluster, up to the requested limit. Non-standard scripts are left out.
+    pub addresses: Vec<RpcAddress>,
+}
+
+impl GetAddressClusterResponse {
+    pub fn new(cluster_size: u64, addresses: Vec<RpcAddress>) -> Self {
+        Self {
+            cluster_size,
+            addresses,
+        }
+    }
+}
+
+// ----------------------------------------------------------------------------
+// Subscriptions & notifications
+// ----------------------------------------------------------------------------
+
+// ~~~~~~~~~~~~~~~~~~~~~~
+// BlockAddedNotification
+
+/// NotifyBlockAddedRequest registers this connection for blockAdded notifications.
+///
+/// See: BlockAddedNotification
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NotifyBlockAddedRequest {
+    pub command: Command,
+}
+impl NotifyBlockAddedRequest {
+    pub fn new(command: Command) -> Self {
+        Self { command }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NotifyBlockAddedResponse {}
+
+/// BlockAddedNotification is sent whenever a blocks has been added (NOT accepted)
+/// into the DAG.
+///
+/// See: NotifyBlockAddedRequest
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockAddedNotification {
+    pub block: Arc<RpcBlock>,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// VirtualChainChangedNotification
+
+// NotifyVirtualChainChangedRequest registers this connection for
+// virtualDaaScoreChanged notifications.
+//
+// See: VirtualChainChangedNotification
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NotifyVirtualChainChangedRequest {
+    pub include_accepted_transaction_ids: bool,
+    pub command: Command,
+}
+
+impl NotifyVirtualChainChangedRequest {
+    pub fn new(include_accepted_transaction_ids: bool, command: Command) -> Self {
+        Self {
+            include_accepted_transaction_ids,
+            command,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NotifyVirtualChainChangedResponse {}
+
+// VirtualChainChangedNotification is sent whenever the DAG's selected parent
+// chain had changed.
+//
+// See: NotifyVirtualChainChangedRequest
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VirtualChainChangedNotification {
+    pub removed_chain_block_hashes: Arc<Vec<RpcHash>>,
+    pub added_chain_block_hashes: Arc<Vec<RpcHash>>,
+    pub accepted_transaction_ids: Arc<Vec<RpcAcceptedTransactionIds>>,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// FinalityConflictNotification
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NotifyFinalityConflictRequest {
+    pub command: Command,
+}
+
+impl NotifyFinalityConflictRequest {
+    pub fn new(command: Command) -> Self {
+        Self { command }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NotifyFinalityConflictResponse {}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FinalityConflictNotification {
+    pub violating_block_hash: RpcHash,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// FinalityConflictResolvedNotification
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NotifyFinalityConflictResolvedRequest {
+    pub command: Command,
+}
+
+impl NotifyFinalityConflictResolvedRequest {
+    pub fn new(command: Command) -> Self {
+        Self { command }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NotifyFinalityConflictResolvedResponse {}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FinalityConflictResolvedNotification {
+    pub finality_block_hash: RpcHash,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~
+// UtxosChangedNotification
+
+// NotifyUtxosChangedRequestMessage registers this connection for utxoChanged notifications
+// for the given addresses. Depending on the provided `command`, notifications will
+// start or stop for the provided `addresses`.
+//
+// If `addresses` is empty, the notifications will start or stop for all addresses.
+//
+// This call is only available when this karlsend was started with `--utxoindex`
+//
+// See: UtxosChangedNotification
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NotifyUtxosChangedRequest {
+    pub addresses: Vec<RpcAddress>,
+    pub command: Command,
+}
+
+impl NotifyUtxosChangedRequest {
+    pub fn new(addresses: Vec<RpcAddress>, command: Command) -> Self {
+        Self { addresses, command }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NotifyUtxosChangedResponse {}
+
+// UtxosChangedNotificationMessage is sent whenever the UTXO index had been updated.
+//
+// See: NotifyUtxosChangedRequest
 #[derive(Clone, Debug, Default, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct UtxosChangedNotification {
@@ -1228,6 +2234,80 @@ pub struct NotifyNewBlockTemplateResponse {}
 #[serde(rename_all = "camelCase")]
 pub struct NewBlockTemplateNotification {}
 
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// TransactionStatusChangedNotification
+
+/// NotifyTransactionStatusChangedRequest registers this connection for transactionStatusChanged
+/// notifications.
+///
+/// See: TransactionStatusChangedNotification
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NotifyTransactionStatusChangedRequest {
+    pub command: Command,
+}
+impl NotifyTransactionStatusChangedRequest {
+    pub fn new(command: Command) -> Self {
+        Self { command }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NotifyTransactionStatusChangedResponse {}
+
+/// TransactionStatusChangedNotification is sent whenever a transaction submitted through
+/// the RPC of this node gets accepted by a selected chain block or unaccepted by a reorg.
+///
+/// See: NotifyTransactionStatusChangedRequest
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionStatusChangedNotification {
+    pub transaction_id: RpcTransactionId,
+    /// Selected chain block now accepting the transaction, `None` if the transaction got unaccepted
+    pub accepting_block_hash: Option<RpcHash>,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// DoubleSpendDetectedNotification
+
+/// NotifyDoubleSpendDetectedRequest registers this connection for doubleSpendDetected
+/// notifications.
+///
+/// See: DoubleSpendDetectedNotification
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NotifyDoubleSpendDetectedRequest {
+    pub command: Command,
+}
+impl NotifyDoubleSpendDetectedRequest {
+    pub fn new(command: Command) -> Self {
+        Self { command }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NotifyDoubleSpendDetectedResponse {}
+
+/// DoubleSpendDetectedNotification is sent whenever a transaction spending outpoints already
+/// spent by a mempool or a recently accepted transaction is observed, be it submitted through
+/// the RPC, relayed by a peer or accepted by a new block.
+///
+/// See: NotifyDoubleSpendDetectedRequest
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DoubleSpendDetectedNotification {
+    /// The transaction observed last
+    pub transaction_id: RpcTransactionId,
+    /// The mempool or accepted transaction it conflicts with
+    pub conflicting_transaction_id: RpcTransactionId,
+    /// The outpoints spent by both transactions
+    pub outpoints: Vec<RpcTransactionOutpoint>,
+    /// The transaction of the two accepted by the DAG, if any
+    pub accepted_transaction_id: Option<RpcTransactionId>,
+}
+
 ///
 ///  wRPC response for RpcApiOps::Subscribe request
 ///
@@ -1249,3 +2329,226 @@ impl SubscribeResponse {
 #[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct UnsubscribeResponse {}
+
+///
+///  wRPC request for RpcApiOps::SubscribeBlockAdded
+///
+/// Starts a block added stream on the calling connection. The blocks retained by the node
+/// after `cursor` are replayed first, then the blocks added to the DAG are pushed as they
+/// get notified, all in notification order and each delivered as a
+/// [`BlockAddedStreamNotification`]. Subscribing again replaces the stream of the connection.
+///
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SubscribeBlockAddedRequest {
+    /// Hash of the last block processed by the client. If `None`, the stream starts at the
+    /// oldest block retained by the node.
+    pub cursor: Option<RpcHash>,
+}
+
+impl SubscribeBlockAddedRequest {
+    pub fn new(cursor: Option<RpcHash>) -> Self {
+        Self { cursor }
+    }
+}
+
+///
+///  wRPC response for RpcApiOps::SubscribeBlockAdded
+///
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SubscribeBlockAddedResponse {
+    /// Count of replayed blocks
+    pub replayed: u64,
+    /// True if the cursor is no longer retained by the node, in which case the blocks
+    /// missed before the first streamed one must be fetched by other means
+    pub gap: bool,
+}
+
+impl SubscribeBlockAddedResponse {
+    pub fn new(replayed: u64, gap: bool) -> Self {
+        Self { replayed, gap }
+    }
+}
+
+///
+///  wRPC request for RpcApiOps::UnsubscribeBlockAdded
+///
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UnsubscribeBlockAddedRequest {}
+
+///
+///  wRPC response for RpcApiOps::UnsubscribeBlockAdded
+///
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UnsubscribeBlockAddedResponse {}
+
+///
+///  wRPC notification RpcApiOps::BlockAddedStreamNotification
+///
+/// A block of the block added stream of the connection
+///
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockAddedStreamNotification {
+    pub block: Arc<RpcBlock>,
+    /// Set on the first block following a cursor no longer retained by the node
+    pub gap: bool,
+}
+
+///
+///  wRPC request for RpcApiOps::SubscribeDurable
+///
+/// Registers a subscription journaling its notifications under a client chosen token,
+/// so they can be replayed with [`ResumeDurableRequest`] after a reconnection or a
+/// node restart. Subscribing again with the same token adds a scope to the subscription.
+///
+/// The subscription is bound to the secret given when registering it: every later
+/// request on the token has to present the same secret.
+///
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SubscribeDurableRequest {
+    pub token: String,
+    /// Client secret of at least 16 characters, only known to the server by its hash
+    pub secret: String,
+    pub scope: karlsen_notify::scope::Scope,
+}
+
+impl SubscribeDurableRequest {
+    pub fn new(token: String, secret: String, scope: karlsen_notify::scope::Scope) -> Self {
+        Self {
+            token,
+            secret,
+            scope,
+        }
+    }
+}
+
+///
+///  wRPC response for RpcApiOps::SubscribeDurable
+///
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SubscribeDurableResponse {
+    /// Sequence number of the next notification journaled by the subscription
+    pub next_sequence: u64,
+}
+
+impl SubscribeDurableResponse {
+    pub fn new(next_sequence: u64) -> Self {
+        Self { next_sequence }
+    }
+}
+
+///
+///  wRPC request for RpcApiOps::ResumeDurable
+///
+/// Replays the journaled notifications starting at `next_sequence` and then attaches the
+/// subscription to the calling connection, each notification being delivered as a
+/// [`DurableNotification`].
+///
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResumeDurableRequest {
+    pub token: String,
+    /// Secret the subscription was registered with
+    pub secret: String,
+    /// Sequence number following the last notification processed by the client
+    pub next_sequence: u64,
+}
+
+impl ResumeDurableRequest {
+    pub fn new(token: String, secret: String, next_sequence: u64) -> Self {
+        Self {
+            token,
+            secret,
+            next_sequence,
+        }
+    }
+}
+
+///
+///  wRPC response for RpcApiOps::ResumeDurable
+///
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResumeDurableResponse {
+    /// Count of replayed notifications
+    pub replayed: u64,
+    /// Sequence number of the next notification journaled by the subscription
+    pub next_sequence: u64,
+    /// True if some requested notifications were already dropped from the journal,
+    /// in which case the client has to reconcile its state by other means
+    pub gap: bool,
+}
+
+impl ResumeDurableResponse {
+    pub fn new(replayed: u64, next_sequence: u64, gap: bool) -> Self {
+        Self {
+            replayed,
+            next_sequence,
+            gap,
+        }
+    }
+}
+
+///
+///  wRPC request for RpcApiOps::UnsubscribeDurable
+///
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UnsubscribeDurableRequest {
+    pub token: String,
+    /// Secret the subscription was registered with
+    pub secret: String,
+}
+
+impl UnsubscribeDurableRequest {
+    pub fn new(token: String, secret: String) -> Self {
+        Self { token, secret }
+    }
+}
+
+///
+///  wRPC response for RpcApiOps::UnsubscribeDurable
+///
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UnsubscribeDurableResponse {}
+
+///
+///  wRPC notification RpcApiOps::DurableNotification
+///
+/// A notification of a durable subscription along with its sequence number
+///
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DurableNotification {
+    pub token: String,
+    pub sequence: u64,
+    pub notification: crate::Notification,
+}
+
+///
+///  wRPC notification RpcApiOps::NotificationGapNotification
+///
+/// Sent to a connection whose notification queue overflowed, ahead of the notifications
+/// queued after the dropped ones
+///
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NotificationGapNotification {
+    /// Event types of the dropped notifications along with their counts
+    pub dropped: Vec<(karlsen_notify::events::EventType, u64)>,
+}
+
+impl From<&karlsen_notify::queue::NotificationGap> for NotificationGapNotification {
+    fn from(gap: &karlsen_notify::queue::NotificationGap) -> Self {
+        Self {
+            dropped: gap.dropped.clone(),
+        }
+    }
+}
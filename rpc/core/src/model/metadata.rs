@@ -0,0 +1,17 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use serde::{Deserialize, Serialize};
+
+/// An entry of the metadata store of the external indexers
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcMetadataEntry {
+    pub key: String,
+    #[serde(with = "hex::serde")]
+    pub value: Vec<u8>,
+}
+
+impl RpcMetadataEntry {
+    pub fn new(key: String, value: Vec<u8>) -> Self {
+        Self { key, value }
+    }
+}
@@ -0,0 +1,240 @@
+//! Binding of the work of a block template to the miner owning it.
+//!
+//! A node configured with a work binding secret appends to the coinbase extra data of its
+//! templates a binding made of the id of the requesting worker, a random template id and a MAC
+//! keyed with the secret over these and the script the coinbase pays. The secret is shared with
+//! the miners only: a proxy standing between a miner and the node can neither bind its own script
+//! nor strip the binding, since `SubmitBlock` rejects unbound blocks as well as mismatching ones,
+//! and the miner checks that its jobs carry a valid binding of its own script and worker.
+
+use crate::{RpcError, RpcResult};
+use borsh::{BorshDeserialize, BorshSerialize};
+use karlsen_consensus_core::{coinbase::MinerData, tx::ScriptPublicKey};
+use karlsen_hashes::{Hasher, HasherBase, WorkBindingHash};
+use serde::{Deserialize, Serialize};
+
+/// Declared owner of the work of a block template
+#[derive(
+    Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, BorshSerialize, BorshDeserialize,
+)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcWorkBinding {
+    pub worker_id: Option<u16>,
+    /// Random id of the template, so bindings of the same script and worker differ
+    pub template_id: u64,
+}
+
+/// Binding status of the coinbase of a block
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RpcWorkBindingStatus {
+    /// The coinbase extra data does not end with a binding
+    Unbound,
+    /// The coinbase pays the script authenticated by its binding
+    Bound(RpcWorkBinding),
+    /// The binding does not authenticate the script the coinbase pays
+    Mismatch,
+}
+
+impl RpcWorkBinding {
+    const MAGIC: [u8; 4] = *b"kwb2";
+    const WORKER_LEN: usize = 3;
+    const TEMPLATE_ID_LEN: usize = 8;
+    const MAC_LEN: usize = 16;
+    /// Length of an encoded binding: the magic, the worker id, the template id and the MAC
+    pub const ENCODED_LEN: usize =
+        Self::MAGIC.len() + Self::WORKER_LEN + Self::TEMPLATE_ID_LEN + Self::MAC_LEN;
+    /// Minimum length of the secret keying the MAC
+    pub const MIN_SECRET_LEN: usize = 16;
+
+    pub fn new(worker_id: Option<u16>, template_id: u64) -> Self {
+        Self {
+            worker_id,
+            template_id,
+        }
+    }
+
+    /// Encodes the binding of work paying `script_public_key`, to be appended to the coinbase
+    /// extra data
+    pub fn encode(&self, script_public_key: &ScriptPublicKey, secret: &[u8]) -> Vec<u8> {
+        let worker = self.encode_worker();
+        let template_id = self.template_id.to_le_bytes();
+        let mut data = Vec::with_capacity(Self::ENCODED_LEN);
+        data.extend_from_slice(&Self::MAGIC);
+        data.extend_from_slice(&worker);
+        data.extend_from_slice(&template_id);
+        data.extend_from_slice(&Self::mac(secret, script_public_key, &worker, &template_id));
+        data
+    }
+
+    /// Returns the binding status of the block whose coinbase has the given payload
+    pub fn status(coinbase_payload: &[u8], secret: &[u8]) -> RpcWorkBindingStatus {
+        let Some(miner_data) = MinerData::from_coinbase_payload(coinbase_payload) else {
+            return RpcWorkBindingStatus::Unbound;
+        };
+        let extra_data = miner_data.extra_data;
+        let Some(encoded) = extra_data
+            .len()
+            .checked_sub(Self::ENCODED_LEN)
+            .map(|start| &extra_data[start..])
+        else {
+            return RpcWorkBindingStatus::Unbound;
+        };
+        let (magic, encoded) = encoded.split_at(Self::MAGIC.len());
+        if magic != Self::MAGIC {
+            return RpcWorkBindingStatus::Unbound;
+        }
+        let (worker, encoded) = encoded.split_at(Self::WORKER_LEN);
+        let (template_id, mac) = encoded.split_at(Self::TEMPLATE_ID_LEN);
+        if mac != Self::mac(secret, &miner_data.script_public_key, worker, template_id) {
+            return RpcWorkBindingStatus::Mismatch;
+        }
+        let worker_id = (worker[0] != 0).then(|| u16::from_le_bytes([worker[1], worker[2]]));
+        RpcWorkBindingStatus::Bound(Self::new(
+            worker_id,
+            u64::from_le_bytes(template_id.try_into().unwrap()),
+        ))
+    }
+
+    /// Checks, on the miner side, that a job paying `script_public_key` was bound by the node
+    /// to the work of `worker_id`, returning its binding
+    pub fn verify_job(
+        coinbase_payload: &[u8],
+        script_public_key: &ScriptPublicKey,
+        worker_id: Option<u16>,
+        secret: &[u8],
+    ) -> RpcResult<Self> {
+        let pays_miner = MinerData::from_coinbase_payload(coinbase_payload)
+            .is_some_and(|miner_data| miner_data.script_public_key == *script_public_key);
+        match Self::status(coinbase_payload, secret) {
+            RpcWorkBindingStatus::Bound(binding)
+                if pays_miner && binding.worker_id == worker_id =>
+            {
+                Ok(binding)
+            }
+            RpcWorkBindingStatus::Unbound => Err(RpcError::UnboundWork),
+            _ => Err(RpcError::WorkBindingMismatch),
+        }
+    }
+
+    fn encode_worker(&self) -> [u8; Self::WORKER_LEN] {
+        match self.worker_id {
+            Some(worker_id) => {
+                let [low, high] = worker_id.to_le_bytes();
+                [1, low, high]
+            }
+            None => [0; Self::WORKER_LEN],
+        }
+    }
+
+    fn mac(
+        secret: &[u8],
+        script_public_key: &ScriptPublicKey,
+        worker: &[u8],
+        template_id: &[u8],
+    ) -> [u8; Self::MAC_LEN] {
+        // The secret is length prefixed, so no other secret and message yield the same input
+        let mut hasher = WorkBindingHash::new();
+        hasher
+            .update((secret.len() as u64).to_le_bytes())
+            .update(secret)
+            .update(script_public_key.version().to_le_bytes())
+            .update([script_public_key.script().len() as u8])
+            .update(script_public_key.script())
+            .update(worker)
+            .update(template_id);
+        hasher.finalize().as_bytes()[..Self::MAC_LEN]
+            .try_into()
+            .unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use karlsen_consensus_core::{coinbase::COINBASE_PAYLOAD_HEADER_LEN, tx::ScriptVec};
+
+    const SECRET: &[u8] = b"0123456789abcdef";
+
+    fn coinbase_payload(script_public_key: &ScriptPublicKey, extra_data: &[u8]) -> Vec<u8> {
+        let mut payload = vec![0u8; COINBASE_PAYLOAD_HEADER_LEN - 3];
+        payload.extend_from_slice(&script_public_key.version().to_le_bytes());
+        payload.push(script_public_key.script().len() as u8);
+        payload.extend_from_slice(script_public_key.script());
+        payload.extend_from_slice(extra_data);
+        payload
+    }
+
+    #[test]
+    fn test_work_binding() {
+        let owner = ScriptPublicKey::new(0, ScriptVec::from_slice(&[1u8; 34]));
+        let proxy = ScriptPublicKey::new(0, ScriptVec::from_slice(&[2u8; 34]));
+
+        for worker_id in [None, Some(0), Some(u16::MAX)] {
+            let binding = RpcWorkBinding::new(worker_id, 42);
+            let mut extra_data = b"0.1.0/miner".to_vec();
+            extra_data.extend(binding.encode(&owner, SECRET));
+            let payload = coinbase_payload(&owner, &extra_data);
+            assert_eq!(
+                RpcWorkBinding::status(&payload, SECRET),
+                RpcWorkBindingStatus::Bound(binding)
+            );
+            assert_eq!(
+                RpcWorkBinding::verify_job(&payload, &owner, worker_id, SECRET).unwrap(),
+                binding
+            );
+
+            // Paying another script breaks the binding
+            let redirected = coinbase_payload(&proxy, &extra_data);
+            assert_eq!(
+                RpcWorkBinding::status(&redirected, SECRET),
+                RpcWorkBindingStatus::Mismatch
+            );
+            assert!(matches!(
+                RpcWorkBinding::verify_job(&redirected, &owner, worker_id, SECRET),
+                Err(RpcError::WorkBindingMismatch)
+            ));
+
+            // A proxy ignoring the secret cannot bind its own script
+            let mut extra_data = b"0.1.0/miner".to_vec();
+            extra_data.extend(binding.encode(&proxy, b"fedcba9876543210"));
+            assert_eq!(
+                RpcWorkBinding::status(&coinbase_payload(&proxy, &extra_data), SECRET),
+                RpcWorkBindingStatus::Mismatch
+            );
+        }
+
+        // A job bound to the script of someone else, or to another worker, is not the work of
+        // the miner
+        let mut extra_data = RpcWorkBinding::new(Some(1), 42).encode(&proxy, SECRET);
+        let payload = coinbase_payload(&proxy, &extra_data);
+        assert!(matches!(
+            RpcWorkBinding::verify_job(&payload, &owner, Some(1), SECRET),
+            Err(RpcError::WorkBindingMismatch)
+        ));
+        assert!(matches!(
+            RpcWorkBinding::verify_job(&payload, &proxy, Some(2), SECRET),
+            Err(RpcError::WorkBindingMismatch)
+        ));
+
+        // Tampering with the template id breaks the binding
+        extra_data[RpcWorkBinding::MAGIC.len() + RpcWorkBinding::WORKER_LEN] ^= 1;
+        assert_eq!(
+            RpcWorkBinding::status(&coinbase_payload(&proxy, &extra_data), SECRET),
+            RpcWorkBindingStatus::Mismatch
+        );
+
+        let stripped = coinbase_payload(&owner, b"0.1.0/miner");
+        assert_eq!(
+            RpcWorkBinding::status(&stripped, SECRET),
+            RpcWorkBindingStatus::Unbound
+        );
+        assert!(matches!(
+            RpcWorkBinding::verify_job(&stripped, &owner, None, SECRET),
+            Err(RpcError::UnboundWork)
+        ));
+        assert_eq!(
+            RpcWorkBinding::status(&[0u8; 4], SECRET),
+            RpcWorkBindingStatus::Unbound
+        );
+    }
+}
@@ -1,26 +1,40 @@
+pub mod acceptance;
 pub mod address;
 pub mod block;
 pub mod blue_work;
+pub mod dag;
+pub mod difficulty;
+pub mod filter;
 pub mod hash;
 pub mod header;
 pub mod hex_cnv;
 pub mod mempool;
 pub mod message;
+pub mod metadata;
+pub mod mining;
 pub mod network;
 pub mod peer;
+pub mod script;
 pub mod script_class;
 pub mod subnets;
 pub mod tx;
 
+pub use acceptance::*;
 pub use address::*;
 pub use block::*;
 pub use blue_work::*;
+pub use dag::*;
+pub use difficulty::*;
+pub use filter::*;
 pub use hash::*;
 pub use header::*;
 pub use hex_cnv::*;
 pub use mempool::*;
 pub use message::*;
+pub use metadata::*;
+pub use mining::*;
 pub use network::*;
 pub use peer::*;
+pub use script::*;
 pub use subnets::*;
 pub use tx::*;
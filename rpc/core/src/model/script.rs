@@ -0,0 +1,39 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use karlsen_txscript::debugger::ScriptStep;
+use serde::{Deserialize, Serialize};
+
+/// Engine state after the evaluation of a single opcode, see [`karlsen_txscript::debugger`]
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcScriptStep {
+    /// 0 for the signature script, 1 for the script public key and 2 for a P2SH redeem script
+    pub script_index: u32,
+    pub opcode_index: u32,
+    pub opcode: u8,
+    pub opcode_name: String,
+    /// Hex encoded data pushed by the opcode
+    pub data: String,
+    /// False if the opcode was skipped by a conditional branch
+    pub executed: bool,
+    /// Hex encoded items of the data stack, from bottom to top
+    pub stack: Vec<String>,
+    /// Hex encoded items of the alt stack, from bottom to top
+    pub alt_stack: Vec<String>,
+    pub error: Option<String>,
+}
+
+impl From<&ScriptStep> for RpcScriptStep {
+    fn from(item: &ScriptStep) -> Self {
+        Self {
+            script_index: item.script_index as u32,
+            opcode_index: item.opcode_index as u32,
+            opcode: item.opcode,
+            opcode_name: item.opcode_name().to_string(),
+            data: hex::encode(&item.data),
+            executed: item.executed,
+            stack: item.stack.iter().map(hex::encode).collect(),
+            alt_stack: item.alt_stack.iter().map(hex::encode).collect(),
+            error: item.error.clone(),
+        }
+    }
+}
@@ -51,7 +51,12 @@ impl RpcTransactionInput {
 /// Represent Karlsen transaction input verbose data
 #[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
 #[serde(rename_all = "camelCase")]
-pub struct RpcTransactionInputVerboseData {}
+pub struct RpcTransactionInputVerboseData {
+    /// Output spent by the input, set when the request resolves the previous outputs
+    /// and the node could find it
+    #[serde(default)]
+    pub previous_output: Option<RpcTransactionOutput>,
+}
 
 /// Represents a Karlsend transaction output
 #[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
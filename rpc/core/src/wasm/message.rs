@@ -243,6 +243,9 @@ declare! {
         hasNotifyCommand : boolean;
         /** GRPC ONLY */
         hasMessageId : boolean;
+        peerClockOffset : bigint;
+        headerClockOffset : bigint;
+        isClockSkewed : boolean;
     }
     "#,
 }
@@ -448,6 +451,356 @@ try_from! ( args: GetServerInfoResponse, IGetServerInfoResponse, {
 
 // ---
 
+declare! {
+    IGetServerCapabilitiesRequest,
+    r#"
+    /**
+     * @category Node RPC
+     */
+    export interface IGetServerCapabilitiesRequest { }
+    "#,
+}
+
+try_from! ( args: IGetServerCapabilitiesRequest, GetServerCapabilitiesRequest, {
+    Ok(from_value(args.into())?)
+});
+
+declare! {
+    IGetServerCapabilitiesResponse,
+    r#"
+    /**
+     * @category Node RPC
+     */
+    export interface IGetServerCapabilitiesResponse {
+        rpcApiVersion : number[];
+        protowireApiVersion : number;
+        serverVersion : string;
+        methods : string[];
+        notifications : string[];
+    }
+    "#,
+}
+
+try_from! ( args: GetServerCapabilitiesResponse, IGetServerCapabilitiesResponse, {
+    Ok(to_value(&args)?.into())
+});
+
+// ---
+
+declare! {
+    IGetNetworkInfoRequest,
+    r#"
+    /**
+     * @category Node RPC
+     */
+    export interface IGetNetworkInfoRequest { }
+    "#,
+}
+
+try_from! ( args: IGetNetworkInfoRequest, GetNetworkInfoRequest, {
+    Ok(from_value(args.into())?)
+});
+
+declare! {
+    IGetNetworkInfoResponse,
+    r#"
+    /**
+     * @category Node RPC
+     */
+    export interface IGetNetworkInfoResponse {
+        networkId : string;
+        addressPrefix : string;
+        genesisHash : HexString;
+    }
+    "#,
+}
+
+try_from! ( args: GetNetworkInfoResponse, IGetNetworkInfoResponse, {
+    Ok(to_value(&args)?.into())
+});
+
+// ---
+
+declare! {
+    IGetDifficultyInfoRequest,
+    r#"
+    /**
+     * Request the difficulty adjustment state of the virtual block along with
+     * the difficulty of the `chainBlockCount` most recent selected chain blocks.
+     * 
+     * @category Node RPC
+     */
+    export interface IGetDifficultyInfoRequest {
+        chainBlockCount : number;
+    }
+    "#,
+}
+
+try_from! ( args: IGetDifficultyInfoRequest, GetDifficultyInfoRequest, {
+    Ok(from_value(args.into())?)
+});
+
+declare! {
+    IGetDifficultyInfoResponse,
+    r#"
+    /**
+     * 
+     * 
+     * @category Node RPC
+     */
+    export interface IGetDifficultyInfoResponse {
+        daaScore : bigint;
+        nextBits : number;
+        nextDifficulty : number;
+        /**
+         * Target time per block in milliseconds.
+         */
+        targetTimePerBlock : bigint;
+        window : {
+            size : bigint;
+            sampleRate : bigint;
+            oldestTimestamp : bigint;
+            newestTimestamp : bigint;
+            minDifficulty : number;
+            maxDifficulty : number;
+            averageDifficulty : number;
+        };
+        /**
+         * Most recent selected chain blocks, starting from the sink.
+         */
+        chainBlocks : {
+            hash : HexString;
+            daaScore : bigint;
+            timestamp : bigint;
+            bits : number;
+            difficulty : number;
+        }[];
+    }
+    "#,
+}
+
+try_from! ( args: GetDifficultyInfoResponse, IGetDifficultyInfoResponse, {
+    Ok(to_value(&args)?.into())
+});
+
+// ---
+
+declare! {
+    IDebugScriptRequest,
+    r#"
+    /**
+     * Evaluate the signature script of the input `inputIndex` of `transaction` against
+     * the script public key of its UTXO entry, recording the engine state after every opcode.
+     * `utxoEntries` holds the entries spent by every input, in input order.
+     * Only available on devnets or in unsafe RPC mode.
+     * 
+     * @category Node RPC
+     */
+    export interface IDebugScriptRequest {
+        transaction : ITransaction;
+        inputIndex : number;
+        utxoEntries : {
+            amount : bigint;
+            scriptPublicKey : IScriptPublicKey;
+            blockDaaScore : bigint;
+            isCoinbase : boolean;
+        }[];
+    }
+    "#,
+}
+
+try_from! ( args: IDebugScriptRequest, DebugScriptRequest, {
+    Ok(from_value(args.into())?)
+});
+
+declare! {
+    IDebugScriptResponse,
+    r#"
+    /**
+     * 
+     * 
+     * @category Node RPC
+     */
+    export interface IDebugScriptResponse {
+        success : boolean;
+        /**
+         * Reason of the script failure, missing on success.
+         */
+        error? : string;
+        steps : {
+            /**
+             * 0 for the signature script, 1 for the script public key and 2 for a P2SH redeem script.
+             */
+            scriptIndex : number;
+            opcodeIndex : number;
+            opcode : number;
+            opcodeName : string;
+            data : HexString;
+            executed : boolean;
+            stack : HexString[];
+            altStack : HexString[];
+            error? : string;
+        }[];
+    }
+    "#,
+}
+
+try_from! ( args: DebugScriptResponse, IDebugScriptResponse, {
+    Ok(to_value(&args)?.into())
+});
+
+// ---
+
+declare! {
+    IGetTransactionStatusRequest,
+    r#"
+    /**
+     * Get the broadcast and acceptance status of a transaction. The accepting
+     * block and the confirmations are only reported for transactions submitted
+     * through the RPC of the node since it started.
+     * 
+     * @category Node RPC
+     */
+    export interface IGetTransactionStatusRequest {
+        transactionId : HexString;
+    }
+    "#,
+}
+
+try_from! ( args: IGetTransactionStatusRequest, GetTransactionStatusRequest, {
+    Ok(from_value(args.into())?)
+});
+
+declare! {
+    IGetTransactionStatusResponse,
+    r#"
+    /**
+     * 
+     * 
+     * @category Node RPC
+     */
+    export interface IGetTransactionStatusResponse {
+        transactionId : HexString;
+        inMempool : boolean;
+        isOrphan : boolean;
+        /**
+         * The transaction was submitted through the RPC of the node and its acceptance is tracked.
+         */
+        isTracked : boolean;
+        /**
+         * Count of distinct peers the node sent an announcement of the transaction to,
+         * whether submitted locally or relayed, capped at 32.
+         */
+        announcedPeers : number;
+        acceptingBlockHash? : HexString;
+        confirmations : bigint;
+    }
+    "#,
+}
+
+try_from! ( args: GetTransactionStatusResponse, IGetTransactionStatusResponse, {
+    Ok(to_value(&args)?.into())
+});
+
+// ---
+
+declare! {
+    IGetBlockFilterHeadersRequest,
+    r#"
+    /**
+     * Get the compact filter headers of up to `limit` selected chain blocks, starting
+     * at `startHash` and ascending the chain. Requires the node to run with `--blockfilterindex`.
+     * 
+     * @category Node RPC
+     */
+    export interface IGetBlockFilterHeadersRequest {
+        startHash : HexString;
+        limit : number;
+    }
+    "#,
+}
+
+try_from! ( args: IGetBlockFilterHeadersRequest, GetBlockFilterHeadersRequest, {
+    Ok(from_value(args.into())?)
+});
+
+declare! {
+    IGetBlockFilterHeadersResponse,
+    r#"
+    /**
+     * 
+     * 
+     * @category Node RPC
+     */
+    export interface IGetBlockFilterHeadersResponse {
+        headers : {
+            blockHash : HexString;
+            filterHash : HexString;
+            /**
+             * Commitment to the filter and to the filter header of the selected parent.
+             */
+            header : HexString;
+        }[];
+        /**
+         * Highest chain block covered by the index.
+         */
+        tipHash : HexString;
+    }
+    "#,
+}
+
+try_from! ( args: GetBlockFilterHeadersResponse, IGetBlockFilterHeadersResponse, {
+    Ok(to_value(&args)?.into())
+});
+
+// ---
+
+declare! {
+    IGetBlockFiltersRequest,
+    r#"
+    /**
+     * Get the BIP158-style compact filters of up to `limit` selected chain blocks, starting
+     * at `startHash` and ascending the chain. Requires the node to run with `--blockfilterindex`.
+     * 
+     * @category Node RPC
+     */
+    export interface IGetBlockFiltersRequest {
+        startHash : HexString;
+        limit : number;
+    }
+    "#,
+}
+
+try_from! ( args: IGetBlockFiltersRequest, GetBlockFiltersRequest, {
+    Ok(from_value(args.into())?)
+});
+
+declare! {
+    IGetBlockFiltersResponse,
+    r#"
+    /**
+     * 
+     * 
+     * @category Node RPC
+     */
+    export interface IGetBlockFiltersResponse {
+        filters : {
+            blockHash : HexString;
+            /**
+             * Golomb-coded set of the script public keys created and spent by the block.
+             */
+            filter : HexString;
+        }[];
+    }
+    "#,
+}
+
+try_from! ( args: GetBlockFiltersResponse, IGetBlockFiltersResponse, {
+    Ok(to_value(&args)?.into())
+});
+
+// ---
+
 declare! {
     IGetSyncStatusRequest,
     r#"
@@ -458,232 +811,717 @@ declare! {
     "#,
 }
 
-try_from! ( args: IGetSyncStatusRequest, GetSyncStatusRequest, {
+try_from! ( args: IGetSyncStatusRequest, GetSyncStatusRequest, {
+    Ok(from_value(args.into())?)
+});
+
+declare! {
+    IGetSyncStatusResponse,
+    r#"
+    /**
+     * @category Node RPC
+     */
+    export interface IGetSyncStatusResponse {
+        isSynced : boolean;
+    }
+    "#,
+}
+
+try_from! ( args: GetSyncStatusResponse, IGetSyncStatusResponse, {
+    Ok(to_value(&args)?.into())
+});
+
+/*
+    Interfaces for methods with arguments
+*/
+
+declare! {
+    IAddPeerRequest,
+    r#"
+    /**
+     * 
+     * 
+     * @category Node RPC
+     */
+    export interface IAddPeerRequest {
+        peerAddress : INetworkAddress;
+        isPermanent : boolean;
+    }
+    "#,
+}
+
+try_from! ( args: IAddPeerRequest, AddPeerRequest, {
+    Ok(from_value(args.into())?)
+});
+
+declare! {
+    IAddPeerResponse,
+    r#"
+    /**
+     * 
+     * 
+     * @category Node RPC
+     */
+    export interface IAddPeerResponse { }
+    "#,
+}
+
+try_from! ( args: AddPeerResponse, IAddPeerResponse, {
+    Ok(to_value(&args)?.into())
+});
+
+// ---
+declare! {
+    IBanRequest,
+    r#"
+    /**
+     * 
+     * 
+     * @category Node RPC
+     */
+    export interface IBanRequest {
+        /**
+         * IPv4 or IPv6 address to ban.
+         */
+        ip : string;
+    }
+    "#,
+}
+
+try_from! ( args: IBanRequest, BanRequest, {
+    Ok(from_value(args.into())?)
+});
+
+declare! {
+    IBanResponse,
+    r#"
+    /**
+     * 
+     * 
+     * @category Node RPC
+     */
+    export interface IBanResponse { }
+    "#,
+}
+
+try_from! ( args: BanResponse, IBanResponse, {
+    Ok(to_value(&args)?.into())
+});
+
+// ---
+
+declare! {
+    IEstimateNetworkHashesPerSecondRequest,
+    r#"
+    /**
+     * @category Node RPC
+     */
+    export interface IEstimateNetworkHashesPerSecondRequest {
+        windowSize : number;
+        startHash? : HexString;
+    }
+    "#,
+}
+
+try_from! ( args: IEstimateNetworkHashesPerSecondRequest, EstimateNetworkHashesPerSecondRequest, {
+    Ok(from_value(args.into())?)
+});
+
+declare! {
+    IEstimateNetworkHashesPerSecondResponse,
+    r#"
+    /**
+     * @category Node RPC
+     */
+    export interface IEstimateNetworkHashesPerSecondResponse {
+        networkHashesPerSecond : bigint;
+    }
+    "#,
+}
+
+try_from! ( args: EstimateNetworkHashesPerSecondResponse, IEstimateNetworkHashesPerSecondResponse, {
+    Ok(to_value(&args)?.into())
+});
+
+// ---
+
+declare! {
+    IGetBalanceByAddressRequest,
+    r#"
+    /**
+     * @category Node RPC
+     */
+    export interface IGetBalanceByAddressRequest {
+        address : Address | string;
+    }
+    "#,
+}
+
+try_from! ( args: IGetBalanceByAddressRequest, GetBalanceByAddressRequest, {
+    let js_value = JsValue::from(args);
+    let request = if let Ok(address) = Address::try_owned_from(js_value.clone()) {
+        GetBalanceByAddressRequest { address }
+    } else {
+        // TODO - evaluate Object property
+        from_value::<GetBalanceByAddressRequest>(js_value)?
+    };
+    Ok(request)
+});
+
+declare! {
+    IGetBalanceByAddressResponse,
+    r#"
+    /**
+     * 
+     * 
+     * @category Node RPC
+     */
+    export interface IGetBalanceByAddressResponse {
+        balance : bigint;
+    }
+    "#,
+}
+
+try_from! ( args: GetBalanceByAddressResponse, IGetBalanceByAddressResponse, {
+    Ok(to_value(&args)?.into())
+});
+
+// ---
+
+declare! {
+    IGetBalancesByAddressesRequest,
+    "IGetBalancesByAddressesRequest | Address[] | string[]",
+    r#"
+    /**
+     * 
+     * 
+     * @category Node RPC
+     */
+    export interface IGetBalancesByAddressesRequest {
+        addresses : Address[] | string[];
+    }
+    "#,
+}
+
+try_from! ( args: IGetBalancesByAddressesRequest, GetBalancesByAddressesRequest, {
+    let js_value = JsValue::from(args);
+    let request = if let Ok(addresses) = Vec::<Address>::try_from(AddressOrStringArrayT::from(js_value.clone())) {
+        GetBalancesByAddressesRequest { addresses }
+    } else {
+        from_value::<GetBalancesByAddressesRequest>(js_value)?
+    };
+    Ok(request)
+});
+
+declare! {
+    IGetBalancesByAddressesResponse,
+    r#"
+    /**
+     * 
+     * 
+     * @category Node RPC
+     */
+    export interface IBalancesByAddressesEntry {
+        address : Address;
+        balance : bigint;
+    }
+    /**
+     * 
+     * 
+     * @category Node RPC
+     */
+    export interface IGetBalancesByAddressesResponse {
+        entries : IBalancesByAddressesEntry[];
+    }
+    "#,
+}
+
+try_from! ( args: GetBalancesByAddressesResponse, IGetBalancesByAddressesResponse, {
+    Ok(to_value(&args)?.into())
+});
+
+// ---
+
+declare! {
+    IGetBalanceByAddressesAtRequest,
+    r#"
+    /**
+     * Get the balances of `addresses` as of the highest selected chain block with
+     * a DAA score not above `daaScore`. Pruned nodes only serve DAA scores above
+     * the pruning point.
+     * 
+     * @category Node RPC
+     */
+    export interface IGetBalanceByAddressesAtRequest {
+        addresses : Address[] | string[];
+        daaScore : bigint;
+    }
+    "#,
+}
+
+try_from! ( args: IGetBalanceByAddressesAtRequest, GetBalanceByAddressesAtRequest, {
+    Ok(from_value(args.into())?)
+});
+
+declare! {
+    IGetBalanceByAddressesAtResponse,
+    r#"
+    /**
+     * 
+     * 
+     * @category Node RPC
+     */
+    export interface IGetBalanceByAddressesAtResponse {
+        entries : IBalancesByAddressesEntry[];
+        /**
+         * Chain block the balances are taken at.
+         */
+        chainBlockHash : HexString;
+        daaScore : bigint;
+    }
+    "#,
+}
+
+try_from! ( args: GetBalanceByAddressesAtResponse, IGetBalanceByAddressesAtResponse, {
+    Ok(to_value(&args)?.into())
+});
+
+// ---
+
+declare! {
+    IGetUnconfirmedTxRiskRequest,
+    r#"
+    /**
+     * Get heuristics on the risk of accepting an unconfirmed transaction as a
+     * payment: fee rate percentile, conflicting spends seen, input ages and
+     * propagation among peers.
+     * 
+     * @category Node RPC
+     */
+    export interface IGetUnconfirmedTxRiskRequest {
+        transactionId : HexString;
+    }
+    "#,
+}
+
+try_from! ( args: IGetUnconfirmedTxRiskRequest, GetUnconfirmedTxRiskRequest, {
     Ok(from_value(args.into())?)
 });
 
 declare! {
-    IGetSyncStatusResponse,
+    IGetUnconfirmedTxRiskResponse,
     r#"
     /**
+     * 
+     * 
      * @category Node RPC
      */
-    export interface IGetSyncStatusResponse {
-        isSynced : boolean;
+    export interface IGetUnconfirmedTxRiskResponse {
+        transactionId : HexString;
+        inMempool : boolean;
+        isOrphan : boolean;
+        isAccepted : boolean;
+        feeRate : number;
+        /**
+         * Percentage of the mempool transactions paying a lower fee rate.
+         */
+        feeRatePercentile : number;
+        conflictingTransactionIds : HexString[];
+        /**
+         * Age in DAA score of the UTXO spent by each input, 0 for unconfirmed UTXOs.
+         */
+        inputAges : bigint[];
+        unconfirmedInputCount : number;
+        /**
+         * Count of distinct peers which announced the transaction to the node.
+         */
+        announcingPeerCount : number;
     }
     "#,
 }
 
-try_from! ( args: GetSyncStatusResponse, IGetSyncStatusResponse, {
+try_from! ( args: GetUnconfirmedTxRiskResponse, IGetUnconfirmedTxRiskResponse, {
     Ok(to_value(&args)?.into())
 });
 
-/*
-    Interfaces for methods with arguments
-*/
+// ---
 
 declare! {
-    IAddPeerRequest,
+    IGetDagSliceRequest,
     r#"
     /**
-     * 
+     * Get the blocks of the DAG starting at a DAA score, with their parents,
+     * colors and selected chain membership, for drawing the DAG. Blocks sharing
+     * a DAA score are never split between two slices.
      * 
      * @category Node RPC
      */
-    export interface IAddPeerRequest {
-        peerAddress : INetworkAddress;
-        isPermanent : boolean;
+    export interface IGetDagSliceRequest {
+        fromDaaScore : bigint;
+        count : number;
     }
     "#,
 }
 
-try_from! ( args: IAddPeerRequest, AddPeerRequest, {
+try_from! ( args: IGetDagSliceRequest, GetDagSliceRequest, {
     Ok(from_value(args.into())?)
 });
 
 declare! {
-    IAddPeerResponse,
+    IGetDagSliceResponse,
     r#"
     /**
      * 
      * 
      * @category Node RPC
      */
-    export interface IAddPeerResponse { }
+    export interface IGetDagSliceResponse {
+        /**
+         * Blocks ordered by DAA score.
+         */
+        blocks : {
+            hash : HexString;
+            parentHashes : HexString[];
+            daaScore : bigint;
+            blueScore : bigint;
+            timestamp : bigint;
+            isBlue : boolean;
+            isChainBlock : boolean;
+            /**
+             * Undefined if the block is only merged by the virtual block.
+             */
+            mergingBlockHash? : HexString;
+        }[];
+        /**
+         * DAA score to request the following slice from.
+         */
+        nextDaaScore : bigint;
+    }
     "#,
 }
 
-try_from! ( args: AddPeerResponse, IAddPeerResponse, {
+try_from! ( args: GetDagSliceResponse, IGetDagSliceResponse, {
     Ok(to_value(&args)?.into())
 });
 
 // ---
+
 declare! {
-    IBanRequest,
+    IGetAcceptanceEventsSinceRequest,
     r#"
     /**
-     * 
+     * Replay the chain acceptance events journaled after a cursor.
+     * Requires the node to run with `--acceptance-journal-size`.
      * 
      * @category Node RPC
      */
-    export interface IBanRequest {
+    export interface IGetAcceptanceEventsSinceRequest {
         /**
-         * IPv4 or IPv6 address to ban.
+         * Sequence of the last event processed by the client.
+         * If zero, the replay starts at the oldest event retained by the node.
          */
-        ip : string;
+        cursor : bigint;
+        maxCount : number;
     }
     "#,
 }
 
-try_from! ( args: IBanRequest, BanRequest, {
+try_from! ( args: IGetAcceptanceEventsSinceRequest, GetAcceptanceEventsSinceRequest, {
     Ok(from_value(args.into())?)
 });
 
 declare! {
-    IBanResponse,
+    IGetAcceptanceEventsSinceResponse,
     r#"
     /**
      * 
      * 
      * @category Node RPC
      */
-    export interface IBanResponse { }
+    export interface IGetAcceptanceEventsSinceResponse {
+        events : {
+            sequence : bigint;
+            chainBlockHash : HexString;
+            /**
+             * The block left the selected chain, so the transactions it accepted are no longer accepted.
+             */
+            removed : boolean;
+            blueScore : bigint;
+            timestamp : bigint;
+            recordedTime : bigint;
+            acceptedTransactionIds : HexString[];
+        }[];
+        /**
+         * Some events were missed before the first returned one.
+         */
+        gap : boolean;
+        hasMore : boolean;
+    }
     "#,
 }
 
-try_from! ( args: BanResponse, IBanResponse, {
+try_from! ( args: GetAcceptanceEventsSinceResponse, IGetAcceptanceEventsSinceResponse, {
     Ok(to_value(&args)?.into())
 });
 
 // ---
 
 declare! {
-    IEstimateNetworkHashesPerSecondRequest,
+    IGetTemplateDiffRequest,
     r#"
     /**
+     * Get a block template with its transactions expressed as a diff relative
+     * to a template previously issued by the node.
+     * 
      * @category Node RPC
      */
-    export interface IEstimateNetworkHashesPerSecondRequest {
-        windowSize : number;
-        startHash? : HexString;
+    export interface IGetTemplateDiffRequest {
+        payAddress : Address | string;
+        /**
+         * `extraData` can contain a user-supplied plain text or a byte array represented by `Uint8array`.
+         */
+        extraData? : string | Uint8Array;
+        /**
+         * Id of the template held by the caller, 0 or omitted for none.
+         */
+        sinceTemplateId? : bigint;
     }
     "#,
 }
 
-try_from! ( args: IEstimateNetworkHashesPerSecondRequest, EstimateNetworkHashesPerSecondRequest, {
-    Ok(from_value(args.into())?)
+try_from! ( args: IGetTemplateDiffRequest, GetTemplateDiffRequest, {
+    let pay_address = args.get_cast::<Address>("payAddress")?.into_owned();
+    let extra_data = if let Some(extra_data) = args.try_get_value("extraData")? {
+        if let Some(text) = extra_data.as_string() {
+            text.into_bytes()
+        } else {
+            extra_data.try_as_vec_u8()?
+        }
+    } else {
+        Default::default()
+    };
+    let since_template_id = args.try_get_value("sinceTemplateId")?.map(|_| args.get_u64("sinceTemplateId")).transpose()?.unwrap_or_default();
+    Ok(GetTemplateDiffRequest {
+        pay_address,
+        extra_data,
+        since_template_id,
+    })
 });
 
 declare! {
-    IEstimateNetworkHashesPerSecondResponse,
+    IGetTemplateDiffResponse,
     r#"
     /**
+     * 
+     * 
      * @category Node RPC
      */
-    export interface IEstimateNetworkHashesPerSecondResponse {
-        networkHashesPerSecond : bigint;
+    export interface IGetTemplateDiffResponse {
+        /**
+         * Id of the issued template, to pass as `sinceTemplateId` of the next call.
+         */
+        templateId : bigint;
+        /**
+         * Id of the template the diff applies to, 0 if all the transactions are reported as added.
+         */
+        baseTemplateId : bigint;
+        header : IHeader;
+        coinbaseTransaction : ITransaction;
+        /**
+         * Ids of the non-coinbase transactions of the template, in block order.
+         */
+        transactionIds : HexString[];
+        addedTransactions : ITransaction[];
+        removedTransactionIds : HexString[];
+        isSynced : boolean;
     }
     "#,
 }
 
-try_from! ( args: EstimateNetworkHashesPerSecondResponse, IEstimateNetworkHashesPerSecondResponse, {
+try_from! ( args: GetTemplateDiffResponse, IGetTemplateDiffResponse, {
     Ok(to_value(&args)?.into())
 });
 
-// ---
+declare! {
+    IMetadataEntry,
+    r#"
+    /**
+     * Entry of the external indexer metadata store.
+     * 
+     * @category Node RPC
+     */
+    export interface IMetadataEntry {
+        key : string;
+        value : HexString;
+    }
+    "#,
+}
 
 declare! {
-    IGetBalanceByAddressRequest,
+    IGetMetadataRequest,
     r#"
     /**
+     * 
+     * 
      * @category Node RPC
      */
-    export interface IGetBalanceByAddressRequest {
-        address : Address | string;
+    export interface IGetMetadataRequest {
+        namespace : string;
+        keys : string[];
     }
     "#,
 }
 
-try_from! ( args: IGetBalanceByAddressRequest, GetBalanceByAddressRequest, {
-    let js_value = JsValue::from(args);
-    let request = if let Ok(address) = Address::try_owned_from(js_value.clone()) {
-        GetBalanceByAddressRequest { address }
-    } else {
-        // TODO - evaluate Object property
-        from_value::<GetBalanceByAddressRequest>(js_value)?
-    };
-    Ok(request)
+try_from! ( args: IGetMetadataRequest, GetMetadataRequest, {
+    Ok(from_value(args.into())?)
 });
 
 declare! {
-    IGetBalanceByAddressResponse,
+    IGetMetadataResponse,
     r#"
     /**
      * 
      * 
      * @category Node RPC
      */
-    export interface IGetBalanceByAddressResponse {
-        balance : bigint;
+    export interface IGetMetadataResponse {
+        /**
+         * Entries of the requested keys present in the namespace, in request order.
+         */
+        entries : IMetadataEntry[];
     }
     "#,
 }
 
-try_from! ( args: GetBalanceByAddressResponse, IGetBalanceByAddressResponse, {
+try_from! ( args: GetMetadataResponse, IGetMetadataResponse, {
     Ok(to_value(&args)?.into())
 });
 
-// ---
-
 declare! {
-    IGetBalancesByAddressesRequest,
-    "IGetBalancesByAddressesRequest | Address[] | string[]",
+    IUpdateMetadataRequest,
     r#"
     /**
-     * 
+     * The update is applied only if the namespace holds all the `expectedEntries`
+     * and none of the `expectedAbsentKeys`. Deletions are applied before the
+     * entries are set.
      * 
      * @category Node RPC
      */
-    export interface IGetBalancesByAddressesRequest {
-        addresses : Address[] | string[];
+    export interface IUpdateMetadataRequest {
+        namespace : string;
+        expectedEntries : IMetadataEntry[];
+        expectedAbsentKeys : string[];
+        setEntries : IMetadataEntry[];
+        deleteKeys : string[];
     }
     "#,
 }
 
-try_from! ( args: IGetBalancesByAddressesRequest, GetBalancesByAddressesRequest, {
-    let js_value = JsValue::from(args);
-    let request = if let Ok(addresses) = Vec::<Address>::try_from(AddressOrStringArrayT::from(js_value.clone())) {
-        GetBalancesByAddressesRequest { addresses }
-    } else {
-        from_value::<GetBalancesByAddressesRequest>(js_value)?
-    };
-    Ok(request)
+try_from! ( args: IUpdateMetadataRequest, UpdateMetadataRequest, {
+    Ok(from_value(args.into())?)
 });
 
 declare! {
-    IGetBalancesByAddressesResponse,
+    IUpdateMetadataResponse,
     r#"
     /**
      * 
      * 
      * @category Node RPC
      */
-    export interface IBalancesByAddressesEntry {
-        address : Address;
-        balance : bigint;
+    export interface IUpdateMetadataResponse { }
+    "#,
+}
+
+try_from! ( args: UpdateMetadataResponse, IUpdateMetadataResponse, {
+    Ok(to_value(&args)?.into())
+});
+
+// ---
+
+declare! {
+    IGetMinerStatsRequest,
+    r#"
+    /**
+     * @category Node RPC
+     */
+    export interface IGetMinerStatsRequest { }
+    "#,
+}
+
+try_from! ( args: IGetMinerStatsRequest, GetMinerStatsRequest, {
+    Ok(from_value(args.into())?)
+});
+
+declare! {
+    IGetMinerStatsResponse,
+    r#"
+    /**
+     * Mining profitability estimates of the miners connected to the node.
+     *
+     * @category Node RPC
+     */
+    export interface IGetMinerStatsResponse {
+        acceptedBlocks : bigint;
+        rejectedBlocks : bigint;
+        hashrate : number;
+        networkHashrate : number;
+        networkShare : number;
+        expectedSecondsToBlock? : number;
+        blockReward : bigint;
+        expectedDailyReward : bigint;
+        dailyPowerCost? : number;
+        costPerKls? : number;
+        boundTemplates : bigint;
+        boundBlocks : bigint;
+        bindingMismatches : bigint;
     }
+    "#,
+}
+
+try_from! ( args: GetMinerStatsResponse, IGetMinerStatsResponse, {
+    Ok(to_value(&args)?.into())
+});
+
+// ---
+
+declare! {
+    IGetAddressClusterRequest,
+    r#"
     /**
-     * 
-     * 
+     * Get the addresses assumed to share the owner of `address`, having been spent along
+     * with it by a transaction. Only the transactions accepted since the pruning point the
+     * index was built from are clustered. Requires the node to run with `--addressclusterindex`.
+     *
      * @category Node RPC
      */
-    export interface IGetBalancesByAddressesResponse {
-        entries : IBalancesByAddressesEntry[];
+    export interface IGetAddressClusterRequest {
+        address : Address | string;
+        limit : number;
     }
     "#,
 }
 
-try_from! ( args: GetBalancesByAddressesResponse, IGetBalancesByAddressesResponse, {
+try_from! ( args: IGetAddressClusterRequest, GetAddressClusterRequest, {
+    Ok(from_value(args.into())?)
+});
+
+declare! {
+    IGetAddressClusterResponse,
+    r#"
+    /**
+     *
+     *
+     * @category Node RPC
+     */
+    export interface IGetAddressClusterResponse {
+        clusterSize : bigint;
+        addresses : Address[];
+    }
+    "#,
+}
+
+try_from! ( args: GetAddressClusterResponse, IGetAddressClusterResponse, {
     Ok(to_value(&args)?.into())
 });
 
@@ -700,6 +1538,10 @@ declare! {
     export interface IGetBlockRequest {
         hash : HexString;
         includeTransactions : boolean;
+        /**
+         * Sets the output spent by every transaction input when found by the node.
+         */
+        resolvePreviousOutputs? : boolean;
     }
     "#,
 }
@@ -740,6 +1582,18 @@ declare! {
         lowHash? : HexString;
         includeBlocks : boolean;
         includeTransactions : boolean;
+        /**
+         * Overrides `includeTransactions` if set. Requires `includeBlocks`.
+         */
+        verbosity? : "low" | "medium" | "high";
+        /**
+         * Requested maximum count of blocks in a batch, bounded by the node.
+         */
+        batchSize? : number;
+        /**
+         * Sets the output spent by every transaction input when found by the node.
+         */
+        resolvePreviousOutputs? : boolean;
     }
     "#,
 }
@@ -759,6 +1613,10 @@ declare! {
     export interface IGetBlocksResponse {
         blockHashes : HexString[];
         blocks : IBlock[];
+        /**
+         * Low hash of the next batch, absent if this batch reaches the sink.
+         */
+        nextLowHash? : HexString;
     }
     "#,
 }
@@ -783,6 +1641,21 @@ declare! {
          * `extraData` can contain a user-supplied plain text or a byte array represented by `Uint8array`.
          */
         extraData? : string | Uint8Array;
+        /**
+         * Identifier of the requesting worker (0 to 65535), used to assign it a nonce range
+         * distinct from the other workers mining the same template.
+         */
+        workerId? : number;
+        /**
+         * Hashrate measured by the requesting miner, in hashes per second, aggregated by
+         * `getMinerStats` across the workers reporting it.
+         */
+        reportedHashrate? : number;
+        /**
+         * Whether to commit the pay address and the worker id into the coinbase payload,
+         * for the miner to check its jobs and for `submitBlock` to reject the block if it pays elsewhere.
+         */
+        bindWork? : boolean;
     }
     "#,
 }
@@ -798,9 +1671,15 @@ try_from! ( args: IGetBlockTemplateRequest, GetBlockTemplateRequest, {
     } else {
         Default::default()
     };
+    let worker_id = args.try_get_value("workerId")?.map(|_| args.get_u16("workerId")).transpose()?;
+    let reported_hashrate = args.try_get_value("reportedHashrate")?.and_then(|value| value.as_f64());
+    let bind_work = args.try_get_bool("bindWork")?.unwrap_or(false);
     Ok(GetBlockTemplateRequest {
         pay_address,
         extra_data,
+        worker_id,
+        reported_hashrate,
+        bind_work,
     })
 });
 
@@ -814,6 +1693,12 @@ declare! {
      */
     export interface IGetBlockTemplateResponse {
         block : IBlock;
+        isSynced : boolean;
+        /**
+         * Recommended nonces of the requesting worker are `nonceStart + i * nonceStride`.
+         */
+        nonceStart : bigint;
+        nonceStride : bigint;
     }
     "#,
 }
@@ -949,6 +1834,12 @@ declare! {
     export interface IGetMempoolEntriesRequest {
         includeOrphanPool? : boolean;
         filterTransactionPool? : boolean;
+        paginate? : boolean;
+        cursor? : string;
+        /**
+         * Sets the output spent by every transaction input when found by the node.
+         */
+        resolvePreviousOutputs? : boolean;
     }
     "#,
 }
@@ -967,6 +1858,7 @@ declare! {
      */
     export interface IGetMempoolEntriesResponse {
         mempoolEntries : IMempoolEntry[];
+        nextCursor? : string;
     }
     "#,
 }
@@ -989,6 +1881,10 @@ declare! {
         addresses : Address[] | string[];
         includeOrphanPool? : boolean;
         filterTransactionPool? : boolean;
+        /**
+         * Sets the output spent by every transaction input when found by the node.
+         */
+        resolvePreviousOutputs? : boolean;
     }
     "#,
 }
@@ -1029,6 +1925,10 @@ declare! {
         transactionId : HexString;
         includeOrphanPool? : boolean;
         filterTransactionPool? : boolean;
+        /**
+         * Sets the output spent by every transaction input when found by the node.
+         */
+        resolvePreviousOutputs? : boolean;
     }
     "#,
 }
@@ -1105,7 +2005,9 @@ declare! {
      * @category Node RPC
      */
     export interface IGetUtxosByAddressesRequest { 
-        addresses : Address[] | string[]
+        addresses : Address[] | string[];
+        paginate? : boolean;
+        cursor? : string;
     }
     "#,
 }
@@ -1113,7 +2015,7 @@ declare! {
 try_from! ( args: IGetUtxosByAddressesRequest, GetUtxosByAddressesRequest, {
     let js_value = JsValue::from(args);
     let request = if let Ok(addresses) = Vec::<Address>::try_from(AddressOrStringArrayT::from(js_value.clone())) {
-        GetUtxosByAddressesRequest { addresses }
+        GetUtxosByAddressesRequest::new(addresses)
     } else {
         from_value::<GetUtxosByAddressesRequest>(js_value)?
     };
@@ -1130,16 +2032,20 @@ declare! {
      */
     export interface IGetUtxosByAddressesResponse {
         entries : IUtxoEntry[];
+        nextCursor? : string;
     }
     "#,
 }
 
 try_from! ( args: GetUtxosByAddressesResponse, IGetUtxosByAddressesResponse, {
-    let GetUtxosByAddressesResponse { entries } = args;
+    let GetUtxosByAddressesResponse { entries, next_cursor } = args;
     let entries = entries.into_iter().map(UtxoEntryReference::from).collect::<Vec<UtxoEntryReference>>();
     let entries = js_sys::Array::from_iter(entries.into_iter().map(JsValue::from));
     let response = IGetUtxosByAddressesResponse::default();
     response.set("entries", entries.as_ref())?;
+    if let Some(next_cursor) = next_cursor {
+        response.set("nextCursor", &JsValue::from(next_cursor))?;
+    }
     Ok(response)
 });
 
@@ -1347,6 +2253,63 @@ try_from! ( args: SubmitTransactionResponse, ISubmitTransactionResponse, {
 
 // ---
 
+declare! {
+    ITestMempoolAcceptRequest,
+    r#"
+    /**
+     * Run the full mempool validation of a transaction without submitting it.
+     * 
+     * @category Node RPC
+     */
+    export interface ITestMempoolAcceptRequest {
+        transaction : Transaction,
+    }
+    "#,
+}
+
+try_from! ( args: ITestMempoolAcceptRequest, TestMempoolAcceptRequest, {
+    let transaction = if let Some(transaction) = args.try_get_value("transaction")? {
+        transaction
+    } else {
+        args.into()
+    };
+
+    let request = if let Ok(transaction) = Transaction::try_owned_from(&transaction) {
+        TestMempoolAcceptRequest {
+            transaction : transaction.into(),
+        }
+    } else {
+        from_value(transaction)?
+    };
+    Ok(request)
+});
+
+declare! {
+    ITestMempoolAcceptResponse,
+    r#"
+    /**
+     * 
+     * 
+     * @category Node RPC
+     */
+    export interface ITestMempoolAcceptResponse {
+        transactionId : HexString;
+        accepted : boolean;
+        rejectReason? : string;
+        fee : bigint;
+        computeMass : bigint;
+        mass : bigint;
+        feerate : number;
+    }
+    "#,
+}
+
+try_from! ( args: TestMempoolAcceptResponse, ITestMempoolAcceptResponse, {
+    Ok(to_value(&args)?.into())
+});
+
+// ---
+
 declare! {
     IUnbanRequest,
     r#"
@@ -324,6 +324,23 @@ impl RpcApi for GrpcClient {
         get_daa_score_timestamp_estimate_call,
         GetDaaScoreTimestampEstimate
     );
+    route!(get_server_capabilities_call, GetServerCapabilities);
+    route!(get_network_info_call, GetNetworkInfo);
+    route!(test_mempool_accept_call, TestMempoolAccept);
+    route!(get_difficulty_info_call, GetDifficultyInfo);
+    route!(debug_script_call, DebugScript);
+    route!(get_transaction_status_call, GetTransactionStatus);
+    route!(get_block_filter_headers_call, GetBlockFilterHeaders);
+    route!(get_block_filters_call, GetBlockFilters);
+    route!(get_balance_by_addresses_at_call, GetBalanceByAddressesAt);
+    route!(get_unconfirmed_tx_risk_call, GetUnconfirmedTxRisk);
+    route!(get_dag_slice_call, GetDagSlice);
+    route!(get_acceptance_events_since_call, GetAcceptanceEventsSince);
+    route!(get_template_diff_call, GetTemplateDiff);
+    route!(get_metadata_call, GetMetadata);
+    route!(update_metadata_call, UpdateMetadata);
+    route!(get_miner_stats_call, GetMinerStats);
+    route!(get_address_cluster_call, GetAddressCluster);
 
     // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
     // Notification API
@@ -759,6 +776,10 @@ impl Inner {
             let id = u64::from_le_bytes(rand::random::<[u8; 8]>());
             let mut request: KarlsendRequest = request.into();
             request.id = id;
+            // Only the responses matched by id can be streamed by pages
+            if self.server_features.handle_message_id {
+                request.enable_page_streaming();
+            }
 
             trace!("GRPC client: resolver call: {:?}", request);
             if request.payload.is_some() {
@@ -16,15 +16,21 @@ use tokio::sync::oneshot;
 
 #[derive(Debug)]
 struct Pending {
+    /// Time of the request or of its last received page
     timestamp: Instant,
     sender: KarlsendResponseSender,
+    /// Merged pages of a response streamed by pages
+    pages: Option<KarlsendResponse>,
+    streaming: bool,
 }
 
 impl Pending {
-    fn new(sender: KarlsendResponseSender) -> Self {
+    fn new(sender: KarlsendResponseSender, streaming: bool) -> Self {
         Self {
             timestamp: Instant::now(),
             sender,
+            pages: None,
+            streaming,
         }
     }
 }
@@ -51,19 +57,41 @@ impl Resolver for IdResolver {
         let (sender, receiver) = oneshot::channel::<Result<KarlsendResponse>>();
         {
             let mut pending_calls = self.pending_calls.lock().unwrap();
-            pending_calls.insert(request.id, Pending::new(sender));
+            pending_calls.insert(
+                request.id,
+                Pending::new(sender, request.is_page_streaming()),
+            );
             drop(pending_calls);
         }
         receiver
     }
 
     fn handle_response(&self, response: KarlsendResponse) {
-        match self.pending_calls.lock().unwrap().remove(&response.id) {
+        let mut pending_calls = self.pending_calls.lock().unwrap();
+        if let Some(pending) = pending_calls.get_mut(&response.id) {
+            if pending.streaming && response.next_page_cursor().is_some() {
+                // More pages follow, the request is resolved by the last one
+                pending.timestamp = Instant::now();
+                match pending.pages.as_mut() {
+                    Some(pages) => pages.merge_page(response),
+                    None => pending.pages = Some(response),
+                }
+                return;
+            }
+        }
+        match pending_calls.remove(&response.id) {
             Some(pending) => {
                 trace!(
                     "[Resolver] handle_response has matching request with id {}",
                     response.id
                 );
+                let response = match pending.pages {
+                    Some(mut pages) => {
+                        pages.merge_page(response);
+                        pages
+                    }
+                    None => response,
+                };
                 match pending.sender.send(Ok(response)) {
                     Ok(_) => {}
                     Err(err) => {
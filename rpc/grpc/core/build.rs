@@ -1,10 +1,15 @@
+use std::{env, path::PathBuf};
+
 fn main() {
     let protowire_files = &["./proto/messages.proto", "./proto/rpc.proto"];
     let dirs = &["./proto"];
+    let out_dir = PathBuf::from(env::var("OUT_DIR").expect("OUT_DIR is set by cargo"));
 
     tonic_build::configure()
         .build_server(true)
         .build_client(true)
+        // The encoded file descriptor set is served by the gRPC reflection service
+        .file_descriptor_set_path(out_dir.join("protowire_descriptor.bin"))
         // In case we want protowire.rs to be explicitly integrated in the crate code,
         // uncomment this line and reflect the change in src/lib.rs
         //.out_dir("./src")
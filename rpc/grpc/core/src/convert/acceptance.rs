@@ -0,0 +1,40 @@
+use crate::protowire;
+use crate::{from, try_from};
+use karlsen_rpc_core::{RpcError, RpcHash, RpcTransactionId};
+use std::str::FromStr;
+
+// ----------------------------------------------------------------------------
+// rpc_core to protowire
+// ----------------------------------------------------------------------------
+
+from!(item: &karlsen_rpc_core::RpcAcceptanceEvent, protowire::RpcAcceptanceEvent, {
+    Self {
+        sequence: item.sequence,
+        chain_block_hash: item.chain_block_hash.to_string(),
+        removed: item.removed,
+        blue_score: item.blue_score,
+        timestamp: item.timestamp,
+        recorded_time: item.recorded_time,
+        accepted_transaction_ids: item.accepted_transaction_ids.iter().map(|x| x.to_string()).collect(),
+    }
+});
+
+// ----------------------------------------------------------------------------
+// protowire to rpc_core
+// ----------------------------------------------------------------------------
+
+try_from!(item: &protowire::RpcAcceptanceEvent, karlsen_rpc_core::RpcAcceptanceEvent, {
+    Self {
+        sequence: item.sequence,
+        chain_block_hash: RpcHash::from_str(&item.chain_block_hash)?,
+        removed: item.removed,
+        blue_score: item.blue_score,
+        timestamp: item.timestamp,
+        recorded_time: item.recorded_time,
+        accepted_transaction_ids: item
+            .accepted_transaction_ids
+            .iter()
+            .map(|x| RpcTransactionId::from_str(x))
+            .collect::<Result<Vec<_>, _>>()?,
+    }
+});
@@ -0,0 +1,42 @@
+use crate::protowire;
+use crate::{from, try_from};
+use karlsen_rpc_core::{RpcError, RpcHash};
+use std::str::FromStr;
+
+// ----------------------------------------------------------------------------
+// rpc_core to protowire
+// ----------------------------------------------------------------------------
+
+from!(item: &karlsen_rpc_core::RpcDagBlock, protowire::RpcDagBlock, {
+    Self {
+        hash: item.hash.to_string(),
+        parent_hashes: item.parent_hashes.iter().map(|x| x.to_string()).collect(),
+        daa_score: item.daa_score,
+        blue_score: item.blue_score,
+        timestamp: item.timestamp,
+        is_blue: item.is_blue,
+        is_chain_block: item.is_chain_block,
+        merging_block_hash: item.merging_block_hash.map(|x| x.to_string()).unwrap_or_default(),
+    }
+});
+
+// ----------------------------------------------------------------------------
+// protowire to rpc_core
+// ----------------------------------------------------------------------------
+
+try_from!(item: &protowire::RpcDagBlock, karlsen_rpc_core::RpcDagBlock, {
+    Self {
+        hash: RpcHash::from_str(&item.hash)?,
+        parent_hashes: item.parent_hashes.iter().map(|x| RpcHash::from_str(x)).collect::<Result<Vec<_>, _>>()?,
+        daa_score: item.daa_score,
+        blue_score: item.blue_score,
+        timestamp: item.timestamp,
+        is_blue: item.is_blue,
+        is_chain_block: item.is_chain_block,
+        merging_block_hash: if item.merging_block_hash.is_empty() {
+            None
+        } else {
+            Some(RpcHash::from_str(&item.merging_block_hash)?)
+        },
+    }
+});
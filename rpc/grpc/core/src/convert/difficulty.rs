@@ -0,0 +1,50 @@
+use crate::protowire;
+use crate::{from, try_from};
+use karlsen_rpc_core::{RpcError, RpcHash};
+use std::str::FromStr;
+
+// ----------------------------------------------------------------------------
+// rpc_core to protowire
+// ----------------------------------------------------------------------------
+
+from!(item: &karlsen_rpc_core::RpcBlockDifficulty, protowire::RpcBlockDifficulty, {
+    Self {
+        hash: item.hash.to_string(),
+        daa_score: item.daa_score,
+        timestamp: item.timestamp,
+        bits: item.bits,
+        difficulty: item.difficulty,
+    }
+});
+
+from!(item: &karlsen_rpc_core::RpcDifficultyWindow, protowire::RpcDifficultyWindow, {
+    Self {
+        size: item.size,
+        sample_rate: item.sample_rate,
+        oldest_timestamp: item.oldest_timestamp,
+        newest_timestamp: item.newest_timestamp,
+        min_difficulty: item.min_difficulty,
+        max_difficulty: item.max_difficulty,
+        average_difficulty: item.average_difficulty,
+    }
+});
+
+// ----------------------------------------------------------------------------
+// protowire to rpc_core
+// ----------------------------------------------------------------------------
+
+try_from!(item: &protowire::RpcBlockDifficulty, karlsen_rpc_core::RpcBlockDifficulty, {
+    Self::new(RpcHash::from_str(&item.hash)?, item.daa_score, item.timestamp, item.bits, item.difficulty)
+});
+
+try_from!(item: &protowire::RpcDifficultyWindow, karlsen_rpc_core::RpcDifficultyWindow, {
+    Self {
+        size: item.size,
+        sample_rate: item.sample_rate,
+        oldest_timestamp: item.oldest_timestamp,
+        newest_timestamp: item.newest_timestamp,
+        min_difficulty: item.min_difficulty,
+        max_difficulty: item.max_difficulty,
+        average_difficulty: item.average_difficulty,
+    }
+});
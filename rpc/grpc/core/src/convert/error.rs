@@ -1,15 +1,28 @@
 use crate::from;
 use crate::protowire;
+use karlsen_rpc_core::{RpcErrorCategory, RpcErrorInfo};
 
 // ----------------------------------------------------------------------------
 // rpc_core to protowire
 // ----------------------------------------------------------------------------
 
-from!(item: karlsen_rpc_core::RpcError, protowire::RpcError, { Self { message: item.to_string() } });
-from!(item: &karlsen_rpc_core::RpcError, protowire::RpcError, { Self { message: item.to_string() } });
+from!(item: karlsen_rpc_core::RpcError, protowire::RpcError, { (&item).into() });
+from!(item: &karlsen_rpc_core::RpcError, protowire::RpcError, {
+    let info = RpcErrorInfo::from(item);
+    Self { message: info.message, code: info.code, category: info.category.to_string(), retryable: info.retryable }
+});
 
 // ----------------------------------------------------------------------------
 // protowire to rpc_core
 // ----------------------------------------------------------------------------
 
-from!(item: &protowire::RpcError, karlsen_rpc_core::RpcError, { karlsen_rpc_core::RpcError::from(item.message.to_string()) });
+from!(item: &protowire::RpcError, karlsen_rpc_core::RpcError, {
+    RpcErrorInfo {
+        code: item.code,
+        // The category is derived from the code when missing or unknown to this client
+        category: item.category.parse().unwrap_or_else(|_| RpcErrorCategory::from_code(item.code)),
+        message: item.message.clone(),
+        retryable: item.retryable,
+    }
+    .into()
+});
@@ -0,0 +1,28 @@
+use crate::protowire;
+use crate::{from, try_from};
+use karlsen_rpc_core::{FromRpcHex, RpcError, RpcHash, ToRpcHex};
+use std::str::FromStr;
+
+// ----------------------------------------------------------------------------
+// rpc_core to protowire
+// ----------------------------------------------------------------------------
+
+from!(item: &karlsen_rpc_core::RpcBlockFilter, protowire::RpcBlockFilter, {
+    Self { block_hash: item.block_hash.to_string(), filter: item.filter.to_rpc_hex() }
+});
+
+from!(item: &karlsen_rpc_core::RpcBlockFilterHeader, protowire::RpcBlockFilterHeader, {
+    Self { block_hash: item.block_hash.to_string(), filter_hash: item.filter_hash.to_string(), header: item.header.to_string() }
+});
+
+// ----------------------------------------------------------------------------
+// protowire to rpc_core
+// ----------------------------------------------------------------------------
+
+try_from!(item: &protowire::RpcBlockFilter, karlsen_rpc_core::RpcBlockFilter, {
+    Self::new(RpcHash::from_str(&item.block_hash)?, Vec::from_rpc_hex(&item.filter)?)
+});
+
+try_from!(item: &protowire::RpcBlockFilterHeader, karlsen_rpc_core::RpcBlockFilterHeader, {
+    Self::new(RpcHash::from_str(&item.block_hash)?, RpcHash::from_str(&item.filter_hash)?, RpcHash::from_str(&item.header)?)
+});
@@ -60,6 +60,23 @@ pub mod karlsend_request_convert {
     impl_into_karlsend_request!(GetServerInfo);
     impl_into_karlsend_request!(GetSyncStatus);
     impl_into_karlsend_request!(GetDaaScoreTimestampEstimate);
+    impl_into_karlsend_request!(GetServerCapabilities);
+    impl_into_karlsend_request!(GetNetworkInfo);
+    impl_into_karlsend_request!(TestMempoolAccept);
+    impl_into_karlsend_request!(GetDifficultyInfo);
+    impl_into_karlsend_request!(DebugScript);
+    impl_into_karlsend_request!(GetTransactionStatus);
+    impl_into_karlsend_request!(GetBlockFilterHeaders);
+    impl_into_karlsend_request!(GetBlockFilters);
+    impl_into_karlsend_request!(GetBalanceByAddressesAt);
+    impl_into_karlsend_request!(GetUnconfirmedTxRisk);
+    impl_into_karlsend_request!(GetDagSlice);
+    impl_into_karlsend_request!(GetAcceptanceEventsSince);
+    impl_into_karlsend_request!(GetTemplateDiff);
+    impl_into_karlsend_request!(GetMetadata);
+    impl_into_karlsend_request!(UpdateMetadata);
+    impl_into_karlsend_request!(GetMinerStats);
+    impl_into_karlsend_request!(GetAddressCluster);
 
     impl_into_karlsend_request!(NotifyBlockAdded);
     impl_into_karlsend_request!(NotifyNewBlockTemplate);
@@ -69,6 +86,8 @@ pub mod karlsend_request_convert {
     impl_into_karlsend_request!(NotifyVirtualDaaScoreChanged);
     impl_into_karlsend_request!(NotifyVirtualChainChanged);
     impl_into_karlsend_request!(NotifySinkBlueScoreChanged);
+    impl_into_karlsend_request!(NotifyTransactionStatusChanged);
+    impl_into_karlsend_request!(NotifyDoubleSpendDetected);
 
     macro_rules! impl_into_karlsend_request {
         ($name:tt) => {
@@ -206,6 +225,23 @@ pub mod karlsend_response_convert {
     impl_into_karlsend_response!(GetServerInfo);
     impl_into_karlsend_response!(GetSyncStatus);
     impl_into_karlsend_response!(GetDaaScoreTimestampEstimate);
+    impl_into_karlsend_response!(GetServerCapabilities);
+    impl_into_karlsend_response!(GetNetworkInfo);
+    impl_into_karlsend_response!(TestMempoolAccept);
+    impl_into_karlsend_response!(GetDifficultyInfo);
+    impl_into_karlsend_response!(DebugScript);
+    impl_into_karlsend_response!(GetTransactionStatus);
+    impl_into_karlsend_response!(GetBlockFilterHeaders);
+    impl_into_karlsend_response!(GetBlockFilters);
+    impl_into_karlsend_response!(GetBalanceByAddressesAt);
+    impl_into_karlsend_response!(GetUnconfirmedTxRisk);
+    impl_into_karlsend_response!(GetDagSlice);
+    impl_into_karlsend_response!(GetAcceptanceEventsSince);
+    impl_into_karlsend_response!(GetTemplateDiff);
+    impl_into_karlsend_response!(GetMetadata);
+    impl_into_karlsend_response!(UpdateMetadata);
+    impl_into_karlsend_response!(GetMinerStats);
+    impl_into_karlsend_response!(GetAddressCluster);
 
     impl_into_karlsend_notify_response!(NotifyBlockAdded);
     impl_into_karlsend_notify_response!(NotifyNewBlockTemplate);
@@ -215,6 +251,8 @@ pub mod karlsend_response_convert {
     impl_into_karlsend_notify_response!(NotifyVirtualDaaScoreChanged);
     impl_into_karlsend_notify_response!(NotifyVirtualChainChanged);
     impl_into_karlsend_notify_response!(NotifySinkBlueScoreChanged);
+    impl_into_karlsend_notify_response!(NotifyTransactionStatusChanged);
+    impl_into_karlsend_notify_response!(NotifyDoubleSpendDetected);
 
     impl_into_karlsend_notify_response!(NotifyUtxosChanged, StopNotifyingUtxosChanged);
     impl_into_karlsend_notify_response!(
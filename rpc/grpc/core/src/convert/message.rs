@@ -18,7 +18,9 @@
 //!
 //! The SubmitBlockResponse is a notable exception to this general rule.
 
-use crate::protowire::{self, submit_block_response_message::RejectReason};
+use crate::protowire::{
+    self, get_blocks_request_message::Verbosity, submit_block_response_message::RejectReason,
+};
 use karlsen_consensus_core::network::NetworkId;
 use karlsen_core::debug;
 use karlsen_notify::subscription::Command;
@@ -159,14 +161,27 @@ from!(item: &karlsen_rpc_core::GetBlockTemplateRequest, protowire::GetBlockTempl
     Self {
         pay_address: (&item.pay_address).into(),
         extra_data: String::from_utf8(item.extra_data.clone()).expect("extra data has to be valid UTF-8"),
+        worker_id: item.worker_id.map(|x| x as u32),
+        reported_hashrate: item.reported_hashrate,
+        bind_work: item.bind_work,
     }
 });
 from!(item: RpcResult<&karlsen_rpc_core::GetBlockTemplateResponse>, protowire::GetBlockTemplateResponseMessage, {
-    Self { block: Some((&item.block).into()), is_synced: item.is_synced, error: None }
+    Self {
+        block: Some((&item.block).into()),
+        is_synced: item.is_synced,
+        nonce_start: item.nonce_start,
+        nonce_stride: item.nonce_stride,
+        error: None,
+    }
 });
 
 from!(item: &karlsen_rpc_core::GetBlockRequest, protowire::GetBlockRequestMessage, {
-    Self { hash: item.hash.to_string(), include_transactions: item.include_transactions }
+    Self {
+        hash: item.hash.to_string(),
+        include_transactions: item.include_transactions,
+        resolve_previous_outputs: item.resolve_previous_outputs,
+    }
 });
 from!(item: RpcResult<&karlsen_rpc_core::GetBlockResponse>, protowire::GetBlockResponseMessage, {
     Self { block: Some((&item.block).into()), error: None }
@@ -193,6 +208,9 @@ from!(item: RpcResult<&karlsen_rpc_core::GetInfoResponse>, protowire::GetInfoRes
         is_synced: item.is_synced,
         has_notify_command: item.has_notify_command,
         has_message_id: item.has_message_id,
+        peer_clock_offset: item.peer_clock_offset,
+        header_clock_offset: item.header_clock_offset,
+        is_clock_skewed: item.is_clock_skewed,
         error: None,
     }
 });
@@ -240,6 +258,7 @@ from!(item: &karlsen_rpc_core::GetMempoolEntryRequest, protowire::GetMempoolEntr
         tx_id: item.transaction_id.to_string(),
         include_orphan_pool: item.include_orphan_pool,
         filter_transaction_pool: item.filter_transaction_pool,
+        resolve_previous_outputs: item.resolve_previous_outputs,
     }
 });
 from!(item: RpcResult<&karlsen_rpc_core::GetMempoolEntryResponse>, protowire::GetMempoolEntryResponseMessage, {
@@ -247,10 +266,21 @@ from!(item: RpcResult<&karlsen_rpc_core::GetMempoolEntryResponse>, protowire::Ge
 });
 
 from!(item: &karlsen_rpc_core::GetMempoolEntriesRequest, protowire::GetMempoolEntriesRequestMessage, {
-    Self { include_orphan_pool: item.include_orphan_pool, filter_transaction_pool: item.filter_transaction_pool }
+    Self {
+        include_orphan_pool: item.include_orphan_pool,
+        filter_transaction_pool: item.filter_transaction_pool,
+        paginate: item.paginate,
+        cursor: item.cursor.clone().unwrap_or_default(),
+        stream_pages: false,
+        resolve_previous_outputs: item.resolve_previous_outputs,
+    }
 });
 from!(item: RpcResult<&karlsen_rpc_core::GetMempoolEntriesResponse>, protowire::GetMempoolEntriesResponseMessage, {
-    Self { entries: item.mempool_entries.iter().map(|x| x.into()).collect(), error: None }
+    Self {
+        entries: item.mempool_entries.iter().map(|x| x.into()).collect(),
+        next_cursor: item.next_cursor.clone().unwrap_or_default(),
+        error: None,
+    }
 });
 
 from!(
@@ -297,17 +327,30 @@ from!(item: RpcResult<&karlsen_rpc_core::GetVirtualChainFromBlockResponse>, prot
     }
 });
 
+from!(item: Option<karlsen_rpc_core::RpcBlockVerbosity>, Verbosity, {
+    match item {
+        None => Verbosity::Unspecified,
+        Some(karlsen_rpc_core::RpcBlockVerbosity::Low) => Verbosity::Low,
+        Some(karlsen_rpc_core::RpcBlockVerbosity::Medium) => Verbosity::Medium,
+        Some(karlsen_rpc_core::RpcBlockVerbosity::High) => Verbosity::High,
+    }
+});
+
 from!(item: &karlsen_rpc_core::GetBlocksRequest, protowire::GetBlocksRequestMessage, {
     Self {
         low_hash: item.low_hash.map_or(Default::default(), |x| x.to_string()),
         include_blocks: item.include_blocks,
         include_transactions: item.include_transactions,
+        verbosity: Verbosity::from(item.verbosity) as i32,
+        batch_size: item.batch_size,
+        resolve_previous_outputs: item.resolve_previous_outputs,
     }
 });
 from!(item: RpcResult<&karlsen_rpc_core::GetBlocksResponse>, protowire::GetBlocksResponseMessage, {
     Self {
         block_hashes: item.block_hashes.iter().map(|x| x.to_string()).collect::<Vec<_>>(),
         blocks: item.blocks.iter().map(|x| x.into()).collect::<Vec<_>>(),
+        next_low_hash: item.next_low_hash.map_or(Default::default(), |x| x.to_string()),
         error: None,
     }
 });
@@ -364,11 +407,20 @@ from!(item: RpcResult<&karlsen_rpc_core::GetHeadersResponse>, protowire::GetHead
 });
 
 from!(item: &karlsen_rpc_core::GetUtxosByAddressesRequest, protowire::GetUtxosByAddressesRequestMessage, {
-    Self { addresses: item.addresses.iter().map(|x| x.into()).collect() }
+    Self {
+        addresses: item.addresses.iter().map(|x| x.into()).collect(),
+        paginate: item.paginate,
+        cursor: item.cursor.clone().unwrap_or_default(),
+        stream_pages: false,
+    }
 });
 from!(item: RpcResult<&karlsen_rpc_core::GetUtxosByAddressesResponse>, protowire::GetUtxosByAddressesResponseMessage, {
     debug!("GRPC, Creating GetUtxosByAddresses message with {} entries", item.entries.len());
-    Self { entries: item.entries.iter().map(|x| x.into()).collect(), error: None }
+    Self {
+        entries: item.entries.iter().map(|x| x.into()).collect(),
+        next_cursor: item.next_cursor.clone().unwrap_or_default(),
+        error: None,
+    }
 });
 
 from!(item: &karlsen_rpc_core::GetBalanceByAddressRequest, protowire::GetBalanceByAddressRequestMessage, {
@@ -415,6 +467,7 @@ from!(item: &karlsen_rpc_core::GetMempoolEntriesByAddressesRequest, protowire::G
         addresses: item.addresses.iter().map(|x| x.into()).collect(),
         include_orphan_pool: item.include_orphan_pool,
         filter_transaction_pool: item.filter_transaction_pool,
+        resolve_previous_outputs: item.resolve_previous_outputs,
     }
 });
 from!(
@@ -494,6 +547,232 @@ from!(item: RpcResult<&karlsen_rpc_core::GetSyncStatusResponse>, protowire::GetS
     }
 });
 
+from!(
+    &karlsen_rpc_core::GetServerCapabilitiesRequest,
+    protowire::GetServerCapabilitiesRequestMessage
+);
+from!(item: RpcResult<&karlsen_rpc_core::GetServerCapabilitiesResponse>, protowire::GetServerCapabilitiesResponseMessage, {
+    Self {
+        rpc_api_version: item.rpc_api_version.iter().map(|x| *x as u32).collect(),
+        protowire_api_version: item.protowire_api_version,
+        server_version: item.server_version.clone(),
+        methods: item.methods.clone(),
+        notifications: item.notifications.clone(),
+        error: None,
+    }
+});
+
+from!(
+    &karlsen_rpc_core::GetNetworkInfoRequest,
+    protowire::GetNetworkInfoRequestMessage
+);
+from!(item: RpcResult<&karlsen_rpc_core::GetNetworkInfoResponse>, protowire::GetNetworkInfoResponseMessage, {
+    Self {
+        network_id: item.network_id.to_string(),
+        address_prefix: item.address_prefix.clone(),
+        genesis_hash: item.genesis_hash.to_string(),
+        error: None,
+    }
+});
+
+from!(item: &karlsen_rpc_core::TestMempoolAcceptRequest, protowire::TestMempoolAcceptRequestMessage, {
+    Self { transaction: Some((&item.transaction).into()) }
+});
+from!(item: RpcResult<&karlsen_rpc_core::TestMempoolAcceptResponse>, protowire::TestMempoolAcceptResponseMessage, {
+    Self {
+        transaction_id: item.transaction_id.to_string(),
+        accepted: item.accepted,
+        reject_reason: item.reject_reason.clone().unwrap_or_default(),
+        fee: item.fee,
+        compute_mass: item.compute_mass,
+        mass: item.mass,
+        feerate: item.feerate,
+        error: None,
+    }
+});
+
+from!(item: &karlsen_rpc_core::GetDifficultyInfoRequest, protowire::GetDifficultyInfoRequestMessage, {
+    Self { chain_block_count: item.chain_block_count }
+});
+from!(item: RpcResult<&karlsen_rpc_core::GetDifficultyInfoResponse>, protowire::GetDifficultyInfoResponseMessage, {
+    Self {
+        daa_score: item.daa_score,
+        next_bits: item.next_bits,
+        next_difficulty: item.next_difficulty,
+        target_time_per_block: item.target_time_per_block,
+        window: Some((&item.window).into()),
+        chain_blocks: item.chain_blocks.iter().map(|x| x.into()).collect(),
+        error: None,
+    }
+});
+
+from!(item: &karlsen_rpc_core::DebugScriptRequest, protowire::DebugScriptRequestMessage, {
+    Self {
+        transaction: Some((&item.transaction).into()),
+        input_index: item.input_index,
+        utxo_entries: item.utxo_entries.iter().map(|x| x.into()).collect(),
+    }
+});
+from!(item: RpcResult<&karlsen_rpc_core::DebugScriptResponse>, protowire::DebugScriptResponseMessage, {
+    Self {
+        success: item.success,
+        script_error: item.error.clone().unwrap_or_default(),
+        steps: item.steps.iter().map(|x| x.into()).collect(),
+        error: None,
+    }
+});
+
+from!(item: &karlsen_rpc_core::GetTransactionStatusRequest, protowire::GetTransactionStatusRequestMessage, {
+    Self { transaction_id: item.transaction_id.to_string() }
+});
+from!(item: RpcResult<&karlsen_rpc_core::GetTransactionStatusResponse>, protowire::GetTransactionStatusResponseMessage, {
+    Self {
+        transaction_id: item.transaction_id.to_string(),
+        in_mempool: item.in_mempool,
+        is_orphan: item.is_orphan,
+        is_tracked: item.is_tracked,
+        announced_peers: item.announced_peers,
+        accepting_block_hash: item.accepting_block_hash.map(|x| x.to_string()).unwrap_or_default(),
+        confirmations: item.confirmations,
+        error: None,
+    }
+});
+
+from!(item: &karlsen_rpc_core::GetBlockFilterHeadersRequest, protowire::GetBlockFilterHeadersRequestMessage, {
+    Self { start_hash: item.start_hash.to_string(), limit: item.limit }
+});
+from!(item: RpcResult<&karlsen_rpc_core::GetBlockFilterHeadersResponse>, protowire::GetBlockFilterHeadersResponseMessage, {
+    Self { headers: item.headers.iter().map(|x| x.into()).collect(), tip_hash: item.tip_hash.to_string(), error: None }
+});
+
+from!(item: &karlsen_rpc_core::GetBlockFiltersRequest, protowire::GetBlockFiltersRequestMessage, {
+    Self { start_hash: item.start_hash.to_string(), limit: item.limit }
+});
+from!(item: RpcResult<&karlsen_rpc_core::GetBlockFiltersResponse>, protowire::GetBlockFiltersResponseMessage, {
+    Self { filters: item.filters.iter().map(|x| x.into()).collect(), error: None }
+});
+
+from!(item: &karlsen_rpc_core::GetBalanceByAddressesAtRequest, protowire::GetBalanceByAddressesAtRequestMessage, {
+    Self { addresses: item.addresses.iter().map(|x| x.into()).collect(), daa_score: item.daa_score }
+});
+from!(item: RpcResult<&karlsen_rpc_core::GetBalanceByAddressesAtResponse>, protowire::GetBalanceByAddressesAtResponseMessage, {
+    Self {
+        entries: item.entries.iter().map(|x| x.into()).collect(),
+        chain_block_hash: item.chain_block_hash.to_string(),
+        daa_score: item.daa_score,
+        error: None,
+    }
+});
+
+from!(item: &karlsen_rpc_core::GetUnconfirmedTxRiskRequest, protowire::GetUnconfirmedTxRiskRequestMessage, {
+    Self { transaction_id: item.transaction_id.to_string() }
+});
+from!(item: RpcResult<&karlsen_rpc_core::GetUnconfirmedTxRiskResponse>, protowire::GetUnconfirmedTxRiskResponseMessage, {
+    Self {
+        transaction_id: item.transaction_id.to_string(),
+        in_mempool: item.in_mempool,
+        is_orphan: item.is_orphan,
+        is_accepted: item.is_accepted,
+        fee_rate: item.fee_rate,
+        fee_rate_percentile: item.fee_rate_percentile,
+        conflicting_transaction_ids: item.conflicting_transaction_ids.iter().map(|x| x.to_string()).collect(),
+        input_ages: item.input_ages.clone(),
+        unconfirmed_input_count: item.unconfirmed_input_count,
+        announcing_peer_count: item.announcing_peer_count,
+        error: None,
+    }
+});
+
+from!(item: &karlsen_rpc_core::GetDagSliceRequest, protowire::GetDagSliceRequestMessage, {
+    Self { from_daa_score: item.from_daa_score, count: item.count }
+});
+from!(item: RpcResult<&karlsen_rpc_core::GetDagSliceResponse>, protowire::GetDagSliceResponseMessage, {
+    Self { blocks: item.blocks.iter().map(|x| x.into()).collect(), next_daa_score: item.next_daa_score, error: None }
+});
+
+from!(item: &karlsen_rpc_core::GetAcceptanceEventsSinceRequest, protowire::GetAcceptanceEventsSinceRequestMessage, {
+    Self { cursor: item.cursor, max_count: item.max_count }
+});
+from!(item: RpcResult<&karlsen_rpc_core::GetAcceptanceEventsSinceResponse>, protowire::GetAcceptanceEventsSinceResponseMessage, {
+    Self { events: item.events.iter().map(|x| x.into()).collect(), gap: item.gap, has_more: item.has_more, error: None }
+});
+
+from!(item: &karlsen_rpc_core::GetTemplateDiffRequest, protowire::GetTemplateDiffRequestMessage, {
+    Self {
+        pay_address: (&item.pay_address).into(),
+        extra_data: String::from_utf8(item.extra_data.clone()).expect("extra data has to be valid UTF-8"),
+        since_template_id: item.since_template_id,
+    }
+});
+from!(item: RpcResult<&karlsen_rpc_core::GetTemplateDiffResponse>, protowire::GetTemplateDiffResponseMessage, {
+    Self {
+        template_id: item.template_id,
+        base_template_id: item.base_template_id,
+        header: Some((&item.header).into()),
+        coinbase_transaction: Some((&item.coinbase_transaction).into()),
+        transaction_ids: item.transaction_ids.iter().map(|x| x.to_string()).collect(),
+        added_transactions: item.added_transactions.iter().map(|x| x.into()).collect(),
+        removed_transaction_ids: item.removed_transaction_ids.iter().map(|x| x.to_string()).collect(),
+        is_synced: item.is_synced,
+        error: None,
+    }
+});
+
+from!(item: &karlsen_rpc_core::GetMetadataRequest, protowire::GetMetadataRequestMessage, {
+    Self { namespace: item.namespace.clone(), keys: item.keys.clone() }
+});
+from!(item: RpcResult<&karlsen_rpc_core::GetMetadataResponse>, protowire::GetMetadataResponseMessage, {
+    Self { entries: item.entries.iter().map(|x| x.into()).collect(), error: None }
+});
+
+from!(item: &karlsen_rpc_core::UpdateMetadataRequest, protowire::UpdateMetadataRequestMessage, {
+    Self {
+        namespace: item.namespace.clone(),
+        expected_entries: item.expected_entries.iter().map(|x| x.into()).collect(),
+        expected_absent_keys: item.expected_absent_keys.clone(),
+        set_entries: item.set_entries.iter().map(|x| x.into()).collect(),
+        delete_keys: item.delete_keys.clone(),
+    }
+});
+from!(
+    RpcResult<&karlsen_rpc_core::UpdateMetadataResponse>,
+    protowire::UpdateMetadataResponseMessage
+);
+
+from!(
+    &karlsen_rpc_core::GetMinerStatsRequest,
+    protowire::GetMinerStatsRequestMessage
+);
+from!(item: RpcResult<&karlsen_rpc_core::GetMinerStatsResponse>, protowire::GetMinerStatsResponseMessage, {
+    Self {
+        accepted_blocks: item.accepted_blocks,
+        rejected_blocks: item.rejected_blocks,
+        hashrate: item.hashrate,
+        network_hashrate: item.network_hashrate,
+        network_share: item.network_share,
+        expected_seconds_to_block: item.expected_seconds_to_block,
+        block_reward: item.block_reward,
+        expected_daily_reward: item.expected_daily_reward,
+        daily_power_cost: item.daily_power_cost,
+        cost_per_kls: item.cost_per_kls,
+        bound_templates: item.bound_templates,
+        bound_blocks: item.bound_blocks,
+        binding_mismatches: item.binding_mismatches,
+        error: None,
+    }
+});
+
+from!(item: &karlsen_rpc_core::GetAddressClusterRequest, protowire::GetAddressClusterRequestMessage, {
+    Self { address: (&item.address).into(), limit: item.limit }
+});
+from!(item: RpcResult<&karlsen_rpc_core::GetAddressClusterResponse>, protowire::GetAddressClusterResponseMessage, {
+    Self {
+        cluster_size: item.cluster_size,
+        addresses: item.addresses.iter().map(|x| x.into()).collect(),
+        error: None,
+    }
+});
+
 from!(item: &karlsen_rpc_core::NotifyUtxosChangedRequest, protowire::NotifyUtxosChangedRequestMessage, {
     Self { addresses: item.addresses.iter().map(|x| x.into()).collect(), command: item.command.into() }
 });
@@ -557,6 +836,22 @@ from!(
     protowire::NotifySinkBlueScoreChangedResponseMessage
 );
 
+from!(item: &karlsen_rpc_core::NotifyTransactionStatusChangedRequest, protowire::NotifyTransactionStatusChangedRequestMessage, {
+    Self { command: item.command.into() }
+});
+from!(
+    RpcResult<&karlsen_rpc_core::NotifyTransactionStatusChangedResponse>,
+    protowire::NotifyTransactionStatusChangedResponseMessage
+);
+
+from!(item: &karlsen_rpc_core::NotifyDoubleSpendDetectedRequest, protowire::NotifyDoubleSpendDetectedRequestMessage, {
+    Self { command: item.command.into() }
+});
+from!(
+    RpcResult<&karlsen_rpc_core::NotifyDoubleSpendDetectedResponse>,
+    protowire::NotifyDoubleSpendDetectedResponseMessage
+);
+
 // ----------------------------------------------------------------------------
 // protowire to rpc_core
 // ----------------------------------------------------------------------------
@@ -612,7 +907,16 @@ impl TryFrom<&protowire::SubmitBlockResponseMessage> for karlsen_rpc_core::Submi
 }
 
 try_from!(item: &protowire::GetBlockTemplateRequestMessage, karlsen_rpc_core::GetBlockTemplateRequest, {
-    Self { pay_address: item.pay_address.clone().try_into()?, extra_data: RpcExtraData::from_iter(item.extra_data.bytes()) }
+    Self {
+        pay_address: item.pay_address.clone().try_into()?,
+        extra_data: RpcExtraData::from_iter(item.extra_data.bytes()),
+        worker_id: item
+            .worker_id
+            .map(|x| u16::try_from(x).map_err(|_| RpcError::General(format!("worker id {x} is above {}", u16::MAX))))
+            .transpose()?,
+        reported_hashrate: item.reported_hashrate,
+        bind_work: item.bind_work,
+    }
 });
 try_from!(item: &protowire::GetBlockTemplateResponseMessage, RpcResult<karlsen_rpc_core::GetBlockTemplateResponse>, {
     Self {
@@ -622,11 +926,17 @@ try_from!(item: &protowire::GetBlockTemplateResponseMessage, RpcResult<karlsen_r
             .ok_or_else(|| RpcError::MissingRpcFieldError("GetBlockTemplateResponseMessage".to_string(), "block".to_string()))?
             .try_into()?,
         is_synced: item.is_synced,
+        nonce_start: item.nonce_start,
+        nonce_stride: item.nonce_stride,
     }
 });
 
 try_from!(item: &protowire::GetBlockRequestMessage, karlsen_rpc_core::GetBlockRequest, {
-    Self { hash: RpcHash::from_str(&item.hash)?, include_transactions: item.include_transactions }
+    Self {
+        hash: RpcHash::from_str(&item.hash)?,
+        include_transactions: item.include_transactions,
+        resolve_previous_outputs: item.resolve_previous_outputs,
+    }
 });
 try_from!(item: &protowire::GetBlockResponseMessage, RpcResult<karlsen_rpc_core::GetBlockResponse>, {
     Self {
@@ -659,6 +969,9 @@ try_from!(item: &protowire::GetInfoResponseMessage, RpcResult<karlsen_rpc_core::
         is_synced: item.is_synced,
         has_notify_command: item.has_notify_command,
         has_message_id: item.has_message_id,
+        peer_clock_offset: item.peer_clock_offset,
+        header_clock_offset: item.header_clock_offset,
+        is_clock_skewed: item.is_clock_skewed,
     }
 });
 
@@ -707,6 +1020,7 @@ try_from!(item: &protowire::GetMempoolEntryRequestMessage, karlsen_rpc_core::Get
         transaction_id: karlsen_rpc_core::RpcTransactionId::from_str(&item.tx_id)?,
         include_orphan_pool: item.include_orphan_pool,
         filter_transaction_pool: item.filter_transaction_pool,
+        resolve_previous_outputs: item.resolve_previous_outputs,
     }
 });
 try_from!(item: &protowire::GetMempoolEntryResponseMessage, RpcResult<karlsen_rpc_core::GetMempoolEntryResponse>, {
@@ -720,10 +1034,20 @@ try_from!(item: &protowire::GetMempoolEntryResponseMessage, RpcResult<karlsen_rp
 });
 
 try_from!(item: &protowire::GetMempoolEntriesRequestMessage, karlsen_rpc_core::GetMempoolEntriesRequest, {
-    Self { include_orphan_pool: item.include_orphan_pool, filter_transaction_pool: item.filter_transaction_pool }
+    Self {
+        include_orphan_pool: item.include_orphan_pool,
+        filter_transaction_pool: item.filter_transaction_pool,
+        // Streamed pages are paginated by the node
+        paginate: item.paginate || item.stream_pages,
+        cursor: (!item.cursor.is_empty()).then(|| item.cursor.clone()),
+        resolve_previous_outputs: item.resolve_previous_outputs,
+    }
 });
 try_from!(item: &protowire::GetMempoolEntriesResponseMessage, RpcResult<karlsen_rpc_core::GetMempoolEntriesResponse>, {
-    Self { mempool_entries: item.entries.iter().map(karlsen_rpc_core::RpcMempoolEntry::try_from).collect::<Result<Vec<_>, _>>()? }
+    Self {
+        mempool_entries: item.entries.iter().map(karlsen_rpc_core::RpcMempoolEntry::try_from).collect::<Result<Vec<_>, _>>()?,
+        next_cursor: (!item.next_cursor.is_empty()).then(|| item.next_cursor.clone()),
+    }
 });
 
 try_from!(
@@ -778,17 +1102,30 @@ try_from!(item: &protowire::GetVirtualChainFromBlockResponseMessage, RpcResult<k
     }
 });
 
+from!(item: Verbosity, Option<karlsen_rpc_core::RpcBlockVerbosity>, {
+    match item {
+        Verbosity::Unspecified => None,
+        Verbosity::Low => Some(karlsen_rpc_core::RpcBlockVerbosity::Low),
+        Verbosity::Medium => Some(karlsen_rpc_core::RpcBlockVerbosity::Medium),
+        Verbosity::High => Some(karlsen_rpc_core::RpcBlockVerbosity::High),
+    }
+});
+
 try_from!(item: &protowire::GetBlocksRequestMessage, karlsen_rpc_core::GetBlocksRequest, {
     Self {
         low_hash: if item.low_hash.is_empty() { None } else { Some(RpcHash::from_str(&item.low_hash)?) },
         include_blocks: item.include_blocks,
         include_transactions: item.include_transactions,
+        verbosity: Verbosity::try_from(item.verbosity).map_err(|_| RpcError::PrimitiveToEnumConversionError)?.into(),
+        batch_size: item.batch_size,
+        resolve_previous_outputs: item.resolve_previous_outputs,
     }
 });
 try_from!(item: &protowire::GetBlocksResponseMessage, RpcResult<karlsen_rpc_core::GetBlocksResponse>, {
     Self {
         block_hashes: item.block_hashes.iter().map(|x| RpcHash::from_str(x)).collect::<Result<Vec<_>, _>>()?,
         blocks: item.blocks.iter().map(|x| x.try_into()).collect::<Result<Vec<_>, _>>()?,
+        next_low_hash: if item.next_low_hash.is_empty() { None } else { Some(RpcHash::from_str(&item.next_low_hash)?) },
     }
 });
 
@@ -845,10 +1182,18 @@ try_from!(item: &protowire::GetHeadersResponseMessage, RpcResult<karlsen_rpc_cor
 });
 
 try_from!(item: &protowire::GetUtxosByAddressesRequestMessage, karlsen_rpc_core::GetUtxosByAddressesRequest, {
-    Self { addresses: item.addresses.iter().map(|x| x.as_str().try_into()).collect::<Result<Vec<_>, _>>()? }
+    Self {
+        addresses: item.addresses.iter().map(|x| x.as_str().try_into()).collect::<Result<Vec<_>, _>>()?,
+        // Streamed pages are paginated by the node
+        paginate: item.paginate || item.stream_pages,
+        cursor: (!item.cursor.is_empty()).then(|| item.cursor.clone()),
+    }
 });
 try_from!(item: &protowire::GetUtxosByAddressesResponseMessage, RpcResult<karlsen_rpc_core::GetUtxosByAddressesResponse>, {
-    Self { entries: item.entries.iter().map(|x| x.try_into()).collect::<Result<Vec<_>, _>>()? }
+    Self {
+        entries: item.entries.iter().map(|x| x.try_into()).collect::<Result<Vec<_>, _>>()?,
+        next_cursor: (!item.next_cursor.is_empty()).then(|| item.next_cursor.clone()),
+    }
 });
 
 try_from!(item: &protowire::GetBalanceByAddressRequestMessage, karlsen_rpc_core::GetBalanceByAddressRequest, {
@@ -902,6 +1247,7 @@ try_from!(item: &protowire::GetMempoolEntriesByAddressesRequestMessage, karlsen_
         addresses: item.addresses.iter().map(|x| x.as_str().try_into()).collect::<Result<Vec<_>, _>>()?,
         include_orphan_pool: item.include_orphan_pool,
         filter_transaction_pool: item.filter_transaction_pool,
+        resolve_previous_outputs: item.resolve_previous_outputs,
     }
 });
 try_from!(
@@ -974,6 +1320,266 @@ try_from!(item: &protowire::GetSyncStatusResponseMessage, RpcResult<karlsen_rpc_
     }
 });
 
+try_from!(
+    &protowire::GetServerCapabilitiesRequestMessage,
+    karlsen_rpc_core::GetServerCapabilitiesRequest
+);
+try_from!(item: &protowire::GetServerCapabilitiesResponseMessage, RpcResult<karlsen_rpc_core::GetServerCapabilitiesResponse>, {
+    Self {
+        rpc_api_version: item.rpc_api_version.iter().map(|x| *x as u16).collect::<Vec<_>>().as_slice().try_into().map_err(|_| RpcError::RpcApiVersionFormatError)?,
+        protowire_api_version: item.protowire_api_version,
+        server_version: item.server_version.clone(),
+        methods: item.methods.clone(),
+        notifications: item.notifications.clone(),
+    }
+});
+
+try_from!(
+    &protowire::GetNetworkInfoRequestMessage,
+    karlsen_rpc_core::GetNetworkInfoRequest
+);
+try_from!(item: &protowire::GetNetworkInfoResponseMessage, RpcResult<karlsen_rpc_core::GetNetworkInfoResponse>, {
+    Self {
+        network_id: NetworkId::from_str(&item.network_id)?,
+        address_prefix: item.address_prefix.clone(),
+        genesis_hash: RpcHash::from_str(&item.genesis_hash)?,
+    }
+});
+
+try_from!(item: &protowire::TestMempoolAcceptRequestMessage, karlsen_rpc_core::TestMempoolAcceptRequest, {
+    Self {
+        transaction: item
+            .transaction
+            .as_ref()
+            .ok_or_else(|| RpcError::MissingRpcFieldError("TestMempoolAcceptRequestMessage".to_string(), "transaction".to_string()))?
+            .try_into()?,
+    }
+});
+try_from!(item: &protowire::TestMempoolAcceptResponseMessage, RpcResult<karlsen_rpc_core::TestMempoolAcceptResponse>, {
+    Self {
+        transaction_id: RpcHash::from_str(&item.transaction_id)?,
+        accepted: item.accepted,
+        reject_reason: if item.reject_reason.is_empty() { None } else { Some(item.reject_reason.clone()) },
+        fee: item.fee,
+        compute_mass: item.compute_mass,
+        mass: item.mass,
+        feerate: item.feerate,
+    }
+});
+
+try_from!(item: &protowire::GetDifficultyInfoRequestMessage, karlsen_rpc_core::GetDifficultyInfoRequest, {
+    Self { chain_block_count: item.chain_block_count }
+});
+try_from!(item: &protowire::GetDifficultyInfoResponseMessage, RpcResult<karlsen_rpc_core::GetDifficultyInfoResponse>, {
+    Self {
+        daa_score: item.daa_score,
+        next_bits: item.next_bits,
+        next_difficulty: item.next_difficulty,
+        target_time_per_block: item.target_time_per_block,
+        window: item
+            .window
+            .as_ref()
+            .ok_or_else(|| RpcError::MissingRpcFieldError("GetDifficultyInfoResponseMessage".to_string(), "window".to_string()))?
+            .try_into()?,
+        chain_blocks: item.chain_blocks.iter().map(|x| x.try_into()).collect::<Result<Vec<_>, _>>()?,
+    }
+});
+
+try_from!(item: &protowire::DebugScriptRequestMessage, karlsen_rpc_core::DebugScriptRequest, {
+    Self {
+        transaction: item
+            .transaction
+            .as_ref()
+            .ok_or_else(|| RpcError::MissingRpcFieldError("DebugScriptRequestMessage".to_string(), "transaction".to_string()))?
+            .try_into()?,
+        input_index: item.input_index,
+        utxo_entries: item.utxo_entries.iter().map(|x| x.try_into()).collect::<Result<Vec<_>, _>>()?,
+    }
+});
+try_from!(item: &protowire::DebugScriptResponseMessage, RpcResult<karlsen_rpc_core::DebugScriptResponse>, {
+    Self {
+        success: item.success,
+        error: if item.script_error.is_empty() { None } else { Some(item.script_error.clone()) },
+        steps: item.steps.iter().map(|x| x.try_into()).collect::<Result<Vec<_>, _>>()?,
+    }
+});
+
+try_from!(item: &protowire::GetTransactionStatusRequestMessage, karlsen_rpc_core::GetTransactionStatusRequest, {
+    Self { transaction_id: RpcHash::from_str(&item.transaction_id)? }
+});
+try_from!(item: &protowire::GetTransactionStatusResponseMessage, RpcResult<karlsen_rpc_core::GetTransactionStatusResponse>, {
+    Self {
+        transaction_id: RpcHash::from_str(&item.transaction_id)?,
+        in_mempool: item.in_mempool,
+        is_orphan: item.is_orphan,
+        is_tracked: item.is_tracked,
+        announced_peers: item.announced_peers,
+        accepting_block_hash: if item.accepting_block_hash.is_empty() {
+            None
+        } else {
+            Some(RpcHash::from_str(&item.accepting_block_hash)?)
+        },
+        confirmations: item.confirmations,
+    }
+});
+
+try_from!(item: &protowire::GetBlockFilterHeadersRequestMessage, karlsen_rpc_core::GetBlockFilterHeadersRequest, {
+    Self { start_hash: RpcHash::from_str(&item.start_hash)?, limit: item.limit }
+});
+try_from!(item: &protowire::GetBlockFilterHeadersResponseMessage, RpcResult<karlsen_rpc_core::GetBlockFilterHeadersResponse>, {
+    Self {
+        headers: item.headers.iter().map(|x| x.try_into()).collect::<Result<Vec<_>, _>>()?,
+        tip_hash: RpcHash::from_str(&item.tip_hash)?,
+    }
+});
+
+try_from!(item: &protowire::GetBlockFiltersRequestMessage, karlsen_rpc_core::GetBlockFiltersRequest, {
+    Self { start_hash: RpcHash::from_str(&item.start_hash)?, limit: item.limit }
+});
+try_from!(item: &protowire::GetBlockFiltersResponseMessage, RpcResult<karlsen_rpc_core::GetBlockFiltersResponse>, {
+    Self { filters: item.filters.iter().map(|x| x.try_into()).collect::<Result<Vec<_>, _>>()? }
+});
+
+try_from!(item: &protowire::GetBalanceByAddressesAtRequestMessage, karlsen_rpc_core::GetBalanceByAddressesAtRequest, {
+    Self {
+        addresses: item.addresses.iter().map(|x| x.as_str().try_into()).collect::<Result<Vec<_>, _>>()?,
+        daa_score: item.daa_score,
+    }
+});
+try_from!(item: &protowire::GetBalanceByAddressesAtResponseMessage, RpcResult<karlsen_rpc_core::GetBalanceByAddressesAtResponse>, {
+    Self {
+        entries: item.entries.iter().map(|x| x.try_into()).collect::<Result<Vec<_>, _>>()?,
+        chain_block_hash: RpcHash::from_str(&item.chain_block_hash)?,
+        daa_score: item.daa_score,
+    }
+});
+
+try_from!(item: &protowire::GetUnconfirmedTxRiskRequestMessage, karlsen_rpc_core::GetUnconfirmedTxRiskRequest, {
+    Self { transaction_id: RpcHash::from_str(&item.transaction_id)? }
+});
+try_from!(item: &protowire::GetUnconfirmedTxRiskResponseMessage, RpcResult<karlsen_rpc_core::GetUnconfirmedTxRiskResponse>, {
+    Self {
+        transaction_id: RpcHash::from_str(&item.transaction_id)?,
+        in_mempool: item.in_mempool,
+        is_orphan: item.is_orphan,
+        is_accepted: item.is_accepted,
+        fee_rate: item.fee_rate,
+        fee_rate_percentile: item.fee_rate_percentile,
+        conflicting_transaction_ids: item
+            .conflicting_transaction_ids
+            .iter()
+            .map(|x| RpcHash::from_str(x))
+            .collect::<Result<Vec<_>, _>>()?,
+        input_ages: item.input_ages.clone(),
+        unconfirmed_input_count: item.unconfirmed_input_count,
+        announcing_peer_count: item.announcing_peer_count,
+    }
+});
+
+try_from!(item: &protowire::GetDagSliceRequestMessage, karlsen_rpc_core::GetDagSliceRequest, {
+    Self { from_daa_score: item.from_daa_score, count: item.count }
+});
+try_from!(item: &protowire::GetDagSliceResponseMessage, RpcResult<karlsen_rpc_core::GetDagSliceResponse>, {
+    Self {
+        blocks: item.blocks.iter().map(|x| x.try_into()).collect::<Result<Vec<_>, _>>()?,
+        next_daa_score: item.next_daa_score,
+    }
+});
+
+try_from!(item: &protowire::GetAcceptanceEventsSinceRequestMessage, karlsen_rpc_core::GetAcceptanceEventsSinceRequest, {
+    Self { cursor: item.cursor, max_count: item.max_count }
+});
+try_from!(item: &protowire::GetAcceptanceEventsSinceResponseMessage, RpcResult<karlsen_rpc_core::GetAcceptanceEventsSinceResponse>, {
+    Self {
+        events: item.events.iter().map(|x| x.try_into()).collect::<Result<Vec<_>, _>>()?,
+        gap: item.gap,
+        has_more: item.has_more,
+    }
+});
+
+try_from!(item: &protowire::GetTemplateDiffRequestMessage, karlsen_rpc_core::GetTemplateDiffRequest, {
+    Self {
+        pay_address: item.pay_address.clone().try_into()?,
+        extra_data: RpcExtraData::from_iter(item.extra_data.bytes()),
+        since_template_id: item.since_template_id,
+    }
+});
+try_from!(item: &protowire::GetTemplateDiffResponseMessage, RpcResult<karlsen_rpc_core::GetTemplateDiffResponse>, {
+    Self {
+        template_id: item.template_id,
+        base_template_id: item.base_template_id,
+        header: item
+            .header
+            .as_ref()
+            .ok_or_else(|| RpcError::MissingRpcFieldError("GetTemplateDiffResponseMessage".to_string(), "header".to_string()))?
+            .try_into()?,
+        coinbase_transaction: item
+            .coinbase_transaction
+            .as_ref()
+            .ok_or_else(|| {
+                RpcError::MissingRpcFieldError("GetTemplateDiffResponseMessage".to_string(), "coinbase_transaction".to_string())
+            })?
+            .try_into()?,
+        transaction_ids: item.transaction_ids.iter().map(|x| RpcHash::from_str(x)).collect::<Result<Vec<_>, _>>()?,
+        added_transactions: item.added_transactions.iter().map(|x| x.try_into()).collect::<Result<Vec<_>, _>>()?,
+        removed_transaction_ids: item.removed_transaction_ids.iter().map(|x| RpcHash::from_str(x)).collect::<Result<Vec<_>, _>>()?,
+        is_synced: item.is_synced,
+    }
+});
+
+try_from!(item: &protowire::GetMetadataRequestMessage, karlsen_rpc_core::GetMetadataRequest, {
+    Self { namespace: item.namespace.clone(), keys: item.keys.clone() }
+});
+try_from!(item: &protowire::GetMetadataResponseMessage, RpcResult<karlsen_rpc_core::GetMetadataResponse>, {
+    Self { entries: item.entries.iter().map(|x| x.try_into()).collect::<Result<Vec<_>, _>>()? }
+});
+
+try_from!(item: &protowire::UpdateMetadataRequestMessage, karlsen_rpc_core::UpdateMetadataRequest, {
+    Self {
+        namespace: item.namespace.clone(),
+        expected_entries: item.expected_entries.iter().map(|x| x.try_into()).collect::<Result<Vec<_>, _>>()?,
+        expected_absent_keys: item.expected_absent_keys.clone(),
+        set_entries: item.set_entries.iter().map(|x| x.try_into()).collect::<Result<Vec<_>, _>>()?,
+        delete_keys: item.delete_keys.clone(),
+    }
+});
+try_from!(
+    &protowire::UpdateMetadataResponseMessage,
+    RpcResult<karlsen_rpc_core::UpdateMetadataResponse>
+);
+
+try_from!(
+    &protowire::GetMinerStatsRequestMessage,
+    karlsen_rpc_core::GetMinerStatsRequest
+);
+try_from!(item: &protowire::GetMinerStatsResponseMessage, RpcResult<karlsen_rpc_core::GetMinerStatsResponse>, {
+    Self {
+        accepted_blocks: item.accepted_blocks,
+        rejected_blocks: item.rejected_blocks,
+        hashrate: item.hashrate,
+        network_hashrate: item.network_hashrate,
+        network_share: item.network_share,
+        expected_seconds_to_block: item.expected_seconds_to_block,
+        block_reward: item.block_reward,
+        expected_daily_reward: item.expected_daily_reward,
+        daily_power_cost: item.daily_power_cost,
+        cost_per_kls: item.cost_per_kls,
+        bound_templates: item.bound_templates,
+        bound_blocks: item.bound_blocks,
+        binding_mismatches: item.binding_mismatches,
+    }
+});
+
+try_from!(item: &protowire::GetAddressClusterRequestMessage, karlsen_rpc_core::GetAddressClusterRequest, {
+    Self { address: item.address.as_str().try_into()?, limit: item.limit }
+});
+try_from!(item: &protowire::GetAddressClusterResponseMessage, RpcResult<karlsen_rpc_core::GetAddressClusterResponse>, {
+    Self {
+        cluster_size: item.cluster_size,
+        addresses: item.addresses.iter().map(|x| x.as_str().try_into()).collect::<Result<Vec<_>, _>>()?,
+    }
+});
+
 try_from!(item: &protowire::NotifyUtxosChangedRequestMessage, karlsen_rpc_core::NotifyUtxosChangedRequest, {
     Self {
         addresses: item.addresses.iter().map(|x| x.as_str().try_into()).collect::<Result<Vec<_>, _>>()?,
@@ -1046,6 +1652,22 @@ try_from!(
     RpcResult<karlsen_rpc_core::NotifySinkBlueScoreChangedResponse>
 );
 
+try_from!(item: &protowire::NotifyTransactionStatusChangedRequestMessage, karlsen_rpc_core::NotifyTransactionStatusChangedRequest, {
+    Self { command: item.command.into() }
+});
+try_from!(
+    &protowire::NotifyTransactionStatusChangedResponseMessage,
+    RpcResult<karlsen_rpc_core::NotifyTransactionStatusChangedResponse>
+);
+
+try_from!(item: &protowire::NotifyDoubleSpendDetectedRequestMessage, karlsen_rpc_core::NotifyDoubleSpendDetectedRequest, {
+    Self { command: item.command.into() }
+});
+try_from!(
+    &protowire::NotifyDoubleSpendDetectedResponseMessage,
+    RpcResult<karlsen_rpc_core::NotifyDoubleSpendDetectedResponse>
+);
+
 // ----------------------------------------------------------------------------
 // Unit tests
 // ----------------------------------------------------------------------------
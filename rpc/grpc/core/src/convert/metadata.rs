@@ -0,0 +1,19 @@
+use crate::protowire;
+use crate::{from, try_from};
+use karlsen_rpc_core::{FromRpcHex, RpcError, ToRpcHex};
+
+// ----------------------------------------------------------------------------
+// rpc_core to protowire
+// ----------------------------------------------------------------------------
+
+from!(item: &karlsen_rpc_core::RpcMetadataEntry, protowire::RpcMetadataEntry, {
+    Self { key: item.key.clone(), value: item.value.to_rpc_hex() }
+});
+
+// ----------------------------------------------------------------------------
+// protowire to rpc_core
+// ----------------------------------------------------------------------------
+
+try_from!(item: &protowire::RpcMetadataEntry, karlsen_rpc_core::RpcMetadataEntry, {
+    Self::new(item.key.clone(), Vec::from_rpc_hex(&item.value)?)
+});
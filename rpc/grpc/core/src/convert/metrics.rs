@@ -17,6 +17,10 @@ from!(item: &karlsen_rpc_core::ProcessMetrics, protowire::ProcessMetrics, {
         disk_io_write_bytes: item.disk_io_write_bytes,
         disk_io_read_per_sec: item.disk_io_read_per_sec,
         disk_io_write_per_sec: item.disk_io_write_per_sec,
+        memory_limit: item.memory_limit,
+        memory_budget_usage: item.memory_budget_usage,
+        memory_pressure_events: item.memory_pressure_events,
+        memory_released_bytes: item.memory_released_bytes,
     }
 });
 
@@ -63,6 +67,10 @@ from!(item: &karlsen_rpc_core::ConsensusMetrics, protowire::ConsensusMetrics, {
         past_median_time: item.network_past_median_time,
         virtual_parent_hashes_count: item.network_virtual_parent_hashes_count,
         virtual_daa_score: item.network_virtual_daa_score,
+
+        utxo_index_checked_counts: item.node_utxo_index_checked_entries_count,
+        utxo_index_divergent_counts: item.node_utxo_index_divergent_entries_count,
+        utxo_index_repaired_counts: item.node_utxo_index_repaired_entries_count,
     }
 });
 
@@ -81,6 +89,10 @@ try_from!(item: &protowire::ProcessMetrics, karlsen_rpc_core::ProcessMetrics, {
         disk_io_write_bytes: item.disk_io_write_bytes,
         disk_io_read_per_sec: item.disk_io_read_per_sec,
         disk_io_write_per_sec: item.disk_io_write_per_sec,
+        memory_limit: item.memory_limit,
+        memory_budget_usage: item.memory_budget_usage,
+        memory_pressure_events: item.memory_pressure_events,
+        memory_released_bytes: item.memory_released_bytes,
     }
 });
 
@@ -127,5 +139,9 @@ try_from!(item: &protowire::ConsensusMetrics, karlsen_rpc_core::ConsensusMetrics
         network_past_median_time: item.past_median_time,
         network_virtual_parent_hashes_count: item.virtual_parent_hashes_count,
         network_virtual_daa_score: item.virtual_daa_score,
+
+        node_utxo_index_checked_entries_count: item.utxo_index_checked_counts,
+        node_utxo_index_divergent_entries_count: item.utxo_index_divergent_counts,
+        node_utxo_index_repaired_entries_count: item.utxo_index_repaired_counts,
     }
 });
@@ -1,11 +1,17 @@
+pub mod acceptance;
 pub mod address;
 pub mod block;
+pub mod dag;
+pub mod difficulty;
 pub mod error;
+pub mod filter;
 pub mod header;
 pub mod karlsend;
 pub mod mempool;
 pub mod message;
+pub mod metadata;
 pub mod metrics;
 pub mod notification;
 pub mod peer;
+pub mod script;
 pub mod tx;
@@ -3,16 +3,16 @@ use crate::protowire::{
     NewBlockTemplateNotificationMessage, RpcNotifyCommand,
 };
 use crate::protowire::{
-    FinalityConflictNotificationMessage, FinalityConflictResolvedNotificationMessage,
-    NotifyPruningPointUtxoSetOverrideRequestMessage,
+    DoubleSpendDetectedNotificationMessage, FinalityConflictNotificationMessage,
+    FinalityConflictResolvedNotificationMessage, NotifyPruningPointUtxoSetOverrideRequestMessage,
     NotifyPruningPointUtxoSetOverrideResponseMessage, NotifyUtxosChangedRequestMessage,
     NotifyUtxosChangedResponseMessage, PruningPointUtxoSetOverrideNotificationMessage,
     SinkBlueScoreChangedNotificationMessage,
     StopNotifyingPruningPointUtxoSetOverrideRequestMessage,
     StopNotifyingPruningPointUtxoSetOverrideResponseMessage,
     StopNotifyingUtxosChangedRequestMessage, StopNotifyingUtxosChangedResponseMessage,
-    UtxosChangedNotificationMessage, VirtualChainChangedNotificationMessage,
-    VirtualDaaScoreChangedNotificationMessage,
+    TransactionStatusChangedNotificationMessage, UtxosChangedNotificationMessage,
+    VirtualChainChangedNotificationMessage, VirtualDaaScoreChangedNotificationMessage,
 };
 use crate::{from, try_from};
 use karlsen_notify::subscription::Command;
@@ -39,6 +39,8 @@ from!(item: &karlsen_rpc_core::Notification, Payload, {
         Notification::PruningPointUtxoSetOverride(ref notification) => {
             Payload::PruningPointUtxoSetOverrideNotification(notification.into())
         }
+        Notification::TransactionStatusChanged(ref notification) => Payload::TransactionStatusChangedNotification(notification.into()),
+        Notification::DoubleSpendDetected(ref notification) => Payload::DoubleSpendDetectedNotification(notification.into()),
     }
 });
 
@@ -85,6 +87,22 @@ from!(
     PruningPointUtxoSetOverrideNotificationMessage
 );
 
+from!(item: &karlsen_rpc_core::TransactionStatusChangedNotification, TransactionStatusChangedNotificationMessage, {
+    Self {
+        transaction_id: item.transaction_id.to_string(),
+        accepting_block_hash: item.accepting_block_hash.map(|x| x.to_string()).unwrap_or_default(),
+    }
+});
+
+from!(item: &karlsen_rpc_core::DoubleSpendDetectedNotification, DoubleSpendDetectedNotificationMessage, {
+    Self {
+        transaction_id: item.transaction_id.to_string(),
+        conflicting_transaction_id: item.conflicting_transaction_id.to_string(),
+        outpoints: item.outpoints.iter().map(|x| x.into()).collect(),
+        accepted_transaction_id: item.accepted_transaction_id.map(|x| x.to_string()).unwrap_or_default(),
+    }
+});
+
 from!(item: Command, RpcNotifyCommand, {
     match item {
         Command::Start => RpcNotifyCommand::NotifyStart,
@@ -128,6 +146,10 @@ try_from!(item: &Payload, karlsen_rpc_core::Notification, {
         Payload::PruningPointUtxoSetOverrideNotification(ref notification) => {
             Notification::PruningPointUtxoSetOverride(notification.try_into()?)
         }
+        Payload::TransactionStatusChangedNotification(ref notification) => {
+            Notification::TransactionStatusChanged(notification.try_into()?)
+        }
+        Payload::DoubleSpendDetectedNotification(ref notification) => Notification::DoubleSpendDetected(notification.try_into()?),
         _ => Err(RpcError::UnsupportedFeature)?,
     }
 });
@@ -188,6 +210,30 @@ try_from!(
     karlsen_rpc_core::PruningPointUtxoSetOverrideNotification
 );
 
+try_from!(item: &TransactionStatusChangedNotificationMessage, karlsen_rpc_core::TransactionStatusChangedNotification, {
+    Self {
+        transaction_id: RpcHash::from_str(&item.transaction_id)?,
+        accepting_block_hash: if item.accepting_block_hash.is_empty() {
+            None
+        } else {
+            Some(RpcHash::from_str(&item.accepting_block_hash)?)
+        },
+    }
+});
+
+try_from!(item: &DoubleSpendDetectedNotificationMessage, karlsen_rpc_core::DoubleSpendDetectedNotification, {
+    Self {
+        transaction_id: RpcHash::from_str(&item.transaction_id)?,
+        conflicting_transaction_id: RpcHash::from_str(&item.conflicting_transaction_id)?,
+        outpoints: item.outpoints.iter().map(|x| x.try_into()).collect::<Result<Vec<_>, _>>()?,
+        accepted_transaction_id: if item.accepted_transaction_id.is_empty() {
+            None
+        } else {
+            Some(RpcHash::from_str(&item.accepted_transaction_id)?)
+        },
+    }
+});
+
 from!(item: RpcNotifyCommand, Command, {
     match item {
         RpcNotifyCommand::NotifyStart => Command::Start,
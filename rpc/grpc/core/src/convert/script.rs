@@ -0,0 +1,39 @@
+use crate::protowire;
+use crate::{from, try_from};
+use karlsen_rpc_core::RpcError;
+
+// ----------------------------------------------------------------------------
+// rpc_core to protowire
+// ----------------------------------------------------------------------------
+
+from!(item: &karlsen_rpc_core::RpcScriptStep, protowire::RpcScriptStep, {
+    Self {
+        script_index: item.script_index,
+        opcode_index: item.opcode_index,
+        opcode: item.opcode as u32,
+        opcode_name: item.opcode_name.clone(),
+        data: item.data.clone(),
+        executed: item.executed,
+        stack: item.stack.clone(),
+        alt_stack: item.alt_stack.clone(),
+        error: item.error.clone().unwrap_or_default(),
+    }
+});
+
+// ----------------------------------------------------------------------------
+// protowire to rpc_core
+// ----------------------------------------------------------------------------
+
+try_from!(item: &protowire::RpcScriptStep, karlsen_rpc_core::RpcScriptStep, {
+    Self {
+        script_index: item.script_index,
+        opcode_index: item.opcode_index,
+        opcode: u8::try_from(item.opcode)?,
+        opcode_name: item.opcode_name.clone(),
+        data: item.data.clone(),
+        executed: item.executed,
+        stack: item.stack.clone(),
+        alt_stack: item.alt_stack.clone(),
+        error: if item.error.is_empty() { None } else { Some(item.error.clone()) },
+    }
+});
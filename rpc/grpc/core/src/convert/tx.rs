@@ -66,10 +66,9 @@ from!(item: &karlsen_rpc_core::RpcTransactionVerboseData, protowire::RpcTransact
     }
 });
 
-from!(
-    &karlsen_rpc_core::RpcTransactionInputVerboseData,
-    protowire::RpcTransactionInputVerboseData
-);
+from!(item: &karlsen_rpc_core::RpcTransactionInputVerboseData, protowire::RpcTransactionInputVerboseData, {
+    Self { previous_output: item.previous_output.as_ref().map(|x| x.into()) }
+});
 
 from!(item: &karlsen_rpc_core::RpcTransactionOutputVerboseData, protowire::RpcTransactionOutputVerboseData, {
     Self {
@@ -176,10 +175,11 @@ try_from!(item: &protowire::RpcTransactionVerboseData, karlsen_rpc_core::RpcTran
     }
 });
 
-try_from!(
-    &protowire::RpcTransactionInputVerboseData,
-    karlsen_rpc_core::RpcTransactionInputVerboseData
-);
+try_from!(item: &protowire::RpcTransactionInputVerboseData, karlsen_rpc_core::RpcTransactionInputVerboseData, {
+    Self {
+        previous_output: item.previous_output.as_ref().map(karlsen_rpc_core::RpcTransactionOutput::try_from).transpose()?,
+    }
+});
 
 try_from!(item: &protowire::RpcTransactionOutputVerboseData, karlsen_rpc_core::RpcTransactionOutputVerboseData, {
     Self {
@@ -2,9 +2,10 @@ use karlsen_notify::{scope::Scope, subscription::Command};
 
 use crate::protowire::{
     karlsend_request, karlsend_response, KarlsendRequest, KarlsendResponse,
-    NotifyBlockAddedRequestMessage, NotifyFinalityConflictRequestMessage,
-    NotifyNewBlockTemplateRequestMessage, NotifyPruningPointUtxoSetOverrideRequestMessage,
-    NotifySinkBlueScoreChangedRequestMessage, NotifyUtxosChangedRequestMessage,
+    NotifyBlockAddedRequestMessage, NotifyDoubleSpendDetectedRequestMessage,
+    NotifyFinalityConflictRequestMessage, NotifyNewBlockTemplateRequestMessage,
+    NotifyPruningPointUtxoSetOverrideRequestMessage, NotifySinkBlueScoreChangedRequestMessage,
+    NotifyTransactionStatusChangedRequestMessage, NotifyUtxosChangedRequestMessage,
     NotifyVirtualChainChangedRequestMessage, NotifyVirtualDaaScoreChangedRequestMessage,
 };
 
@@ -88,6 +89,20 @@ impl karlsend_request::Payload {
                     },
                 )
             }
+            Scope::TransactionStatusChanged(_) => {
+                karlsend_request::Payload::NotifyTransactionStatusChangedRequest(
+                    NotifyTransactionStatusChangedRequestMessage {
+                        command: command.into(),
+                    },
+                )
+            }
+            Scope::DoubleSpendDetected(_) => {
+                karlsend_request::Payload::NotifyDoubleSpendDetectedRequest(
+                    NotifyDoubleSpendDetectedRequestMessage {
+                        command: command.into(),
+                    },
+                )
+            }
         }
     }
 
@@ -103,6 +118,8 @@ impl karlsend_request::Payload {
                 | Payload::NotifyVirtualDaaScoreChangedRequest(_)
                 | Payload::NotifyPruningPointUtxoSetOverrideRequest(_)
                 | Payload::NotifyNewBlockTemplateRequest(_)
+                | Payload::NotifyTransactionStatusChangedRequest(_)
+                | Payload::NotifyDoubleSpendDetectedRequest(_)
                 | Payload::StopNotifyingUtxosChangedRequest(_)
                 | Payload::StopNotifyingPruningPointUtxoSetOverrideRequest(_)
         )
@@ -132,6 +149,8 @@ impl karlsend_response::Payload {
             Payload::VirtualDaaScoreChangedNotification(_) => true,
             Payload::PruningPointUtxoSetOverrideNotification(_) => true,
             Payload::NewBlockTemplateNotification(_) => true,
+            Payload::TransactionStatusChangedNotification(_) => true,
+            Payload::DoubleSpendDetectedNotification(_) => true,
             _ => false,
         }
     }
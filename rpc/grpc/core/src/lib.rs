@@ -3,10 +3,15 @@ pub mod convert;
 pub mod ext;
 pub mod macros;
 pub mod ops;
+pub mod paging;
 
 /// Maximum decoded gRPC message size to send and receive
 pub const RPC_MAX_MESSAGE_SIZE: usize = 1024 * 1024 * 1024; // 1GB
 
 pub mod protowire {
     tonic::include_proto!("protowire");
+
+    /// Encoded file descriptor set of the protowire package, as served by the gRPC reflection service
+    pub const FILE_DESCRIPTOR_SET: &[u8] =
+        tonic::include_file_descriptor_set!("protowire_descriptor");
 }
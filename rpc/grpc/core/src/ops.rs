@@ -83,6 +83,23 @@ pub enum KarlsendPayloadOps {
     GetServerInfo,
     GetSyncStatus,
     GetDaaScoreTimestampEstimate,
+    GetServerCapabilities,
+    GetNetworkInfo,
+    TestMempoolAccept,
+    GetDifficultyInfo,
+    DebugScript,
+    GetTransactionStatus,
+    GetBlockFilterHeaders,
+    GetBlockFilters,
+    GetBalanceByAddressesAt,
+    GetUnconfirmedTxRisk,
+    GetDagSlice,
+    GetAcceptanceEventsSince,
+    GetTemplateDiff,
+    GetMetadata,
+    UpdateMetadata,
+    GetMinerStats,
+    GetAddressCluster,
 
     // Subscription commands for starting/stopping notifications
     NotifyBlockAdded,
@@ -93,6 +110,8 @@ pub enum KarlsendPayloadOps {
     NotifyPruningPointUtxoSetOverride,
     NotifyVirtualDaaScoreChanged,
     NotifyVirtualChainChanged,
+    NotifyTransactionStatusChanged,
+    NotifyDoubleSpendDetected,
 
     // Legacy stop subscription commands
     StopNotifyingUtxosChanged,
@@ -0,0 +1,167 @@
+//!
+//! Streaming of paginated responses over the gRPC message stream.
+//!
+//! The node splits a response exceeding its maximum response size in pages, each of them
+//! carrying the cursor of the next one. A request setting `streamPages` gets all the pages in
+//! a row as responses sharing the request id, so a client can merge them into a single
+//! response without requesting every page by itself.
+//!
+
+use crate::protowire::{karlsend_request, karlsend_response, KarlsendRequest, KarlsendResponse};
+
+impl KarlsendRequest {
+    /// Requests the pages of the response to be streamed, if the method supports pagination and
+    /// the request does not paginate by itself. Returns true if the pages are streamed.
+    pub fn enable_page_streaming(&mut self) -> bool {
+        match self.payload.as_mut() {
+            Some(karlsend_request::Payload::GetMempoolEntriesRequest(request))
+                if !request.paginate =>
+            {
+                request.stream_pages = true;
+                true
+            }
+            Some(karlsend_request::Payload::GetUtxosByAddressesRequest(request))
+                if !request.paginate =>
+            {
+                request.stream_pages = true;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    pub fn is_page_streaming(&self) -> bool {
+        match self.payload.as_ref() {
+            Some(karlsend_request::Payload::GetMempoolEntriesRequest(request)) => {
+                request.stream_pages
+            }
+            Some(karlsend_request::Payload::GetUtxosByAddressesRequest(request)) => {
+                request.stream_pages
+            }
+            _ => false,
+        }
+    }
+
+    /// Returns the request of the page following `cursor`
+    pub fn with_page_cursor(&self, cursor: &str) -> Self {
+        let mut request = self.clone();
+        match request.payload.as_mut() {
+            Some(karlsend_request::Payload::GetMempoolEntriesRequest(request)) => {
+                request.cursor = cursor.to_string()
+            }
+            Some(karlsend_request::Payload::GetUtxosByAddressesRequest(request)) => {
+                request.cursor = cursor.to_string()
+            }
+            _ => {}
+        }
+        request
+    }
+}
+
+impl KarlsendResponse {
+    /// Returns the cursor of the page following this response, `None` if the response is complete
+    pub fn next_page_cursor(&self) -> Option<&str> {
+        let cursor = match self.payload.as_ref() {
+            Some(karlsend_response::Payload::GetMempoolEntriesResponse(response)) => {
+                &response.next_cursor
+            }
+            Some(karlsend_response::Payload::GetUtxosByAddressesResponse(response)) => {
+                &response.next_cursor
+            }
+            _ => return None,
+        };
+        (!cursor.is_empty()).then_some(cursor.as_str())
+    }
+
+    /// Appends the entries of `page`, the page following this response, taking its next cursor.
+    /// A page carrying an error replaces the response.
+    pub fn merge_page(&mut self, page: KarlsendResponse) {
+        match (self.payload.as_mut(), page.payload) {
+            (
+                Some(karlsend_response::Payload::GetMempoolEntriesResponse(response)),
+                Some(karlsend_response::Payload::GetMempoolEntriesResponse(page)),
+            ) if page.error.is_none() => {
+                response.entries.extend(page.entries);
+                response.next_cursor = page.next_cursor;
+            }
+            (
+                Some(karlsend_response::Payload::GetUtxosByAddressesResponse(response)),
+                Some(karlsend_response::Payload::GetUtxosByAddressesResponse(page)),
+            ) if page.error.is_none() => {
+                response.entries.extend(page.entries);
+                response.next_cursor = page.next_cursor;
+            }
+            (_, payload) => self.payload = payload,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::protowire::{
+        karlsend_request::Payload, GetUtxosByAddressesRequestMessage,
+        GetUtxosByAddressesResponseMessage, KarlsendRequest, KarlsendResponse, PingRequestMessage,
+        RpcError, RpcUtxosByAddressesEntry,
+    };
+
+    fn request(payload: Payload) -> KarlsendRequest {
+        KarlsendRequest {
+            id: 0,
+            payload: Some(payload),
+        }
+    }
+
+    fn page(addresses: &[&str], next_cursor: &str) -> KarlsendResponse {
+        let entries = addresses
+            .iter()
+            .map(|address| RpcUtxosByAddressesEntry {
+                address: address.to_string(),
+                ..Default::default()
+            })
+            .collect();
+        GetUtxosByAddressesResponseMessage {
+            entries,
+            next_cursor: next_cursor.to_string(),
+            error: None,
+        }
+        .into()
+    }
+
+    #[test]
+    fn test_page_streaming() {
+        let mut streamed = request(Payload::GetUtxosByAddressesRequest(Default::default()));
+        assert!(!streamed.is_page_streaming());
+        assert!(streamed.enable_page_streaming());
+        assert!(streamed.is_page_streaming());
+        let next = streamed.with_page_cursor("a");
+        assert!(next.is_page_streaming());
+        assert_ne!(next, streamed);
+
+        // Requests paginated by the client and methods without pages are left as is
+        let mut paginated = request(Payload::GetUtxosByAddressesRequest(
+            GetUtxosByAddressesRequestMessage {
+                paginate: true,
+                ..Default::default()
+            },
+        ));
+        assert!(!paginated.enable_page_streaming());
+        let mut ping = request(Payload::PingRequest(PingRequestMessage {}));
+        assert!(!ping.enable_page_streaming());
+        assert!(!ping.is_page_streaming());
+
+        let mut response = page(&["a", "b"], "b");
+        assert_eq!(response.next_page_cursor(), Some("b"));
+        response.merge_page(page(&["c"], ""));
+        assert_eq!(response, page(&["a", "b", "c"], ""));
+        assert_eq!(response.next_page_cursor(), None);
+
+        let failed: KarlsendResponse = GetUtxosByAddressesResponseMessage {
+            error: Some(RpcError::default()),
+            ..Default::default()
+        }
+        .into();
+        let mut response = page(&["a"], "a");
+        response.merge_page(failed.clone());
+        assert_eq!(response, failed);
+    }
+}
@@ -4,6 +4,7 @@ use karlsen_notify::{notifier::Notifier, subscription::context::SubscriptionCont
 use karlsen_rpc_core::{
     api::rpc::DynRpcService, notify::connection::ChannelConnection, Notification, RpcResult,
 };
+use karlsen_rpc_service::policy::RpcPolicy;
 use karlsen_utils::networking::NetAddress;
 use karlsen_utils_tower::counters::TowerConnectionCounters;
 use std::{ops::Deref, sync::Arc};
@@ -47,6 +48,7 @@ impl Adaptor {
         subscription_context: SubscriptionContext,
         broadcasters: usize,
         counters: Arc<TowerConnectionCounters>,
+        policy: Arc<RpcPolicy>,
     ) -> Arc<Self> {
         let (manager_sender, manager_receiver) = mpsc_channel(Self::manager_channel_size());
         let connection_handler = ConnectionHandler::new(
@@ -57,6 +59,7 @@ impl Adaptor {
             subscription_context,
             broadcasters,
             counters,
+            policy,
         );
         let server_termination = connection_handler.serve(serve_address);
         let adaptor = Arc::new(Adaptor::new(
@@ -9,7 +9,7 @@ use karlsen_core::{debug, info, warn};
 use karlsen_grpc_core::{
     protowire::{
         rpc_server::{Rpc, RpcServer},
-        KarlsendRequest, KarlsendResponse,
+        KarlsendRequest, KarlsendResponse, FILE_DESCRIPTOR_SET,
     },
     RPC_MAX_MESSAGE_SIZE,
 };
@@ -26,6 +26,7 @@ use karlsen_rpc_core::{
     notify::{channel::NotificationChannel, connection::ChannelConnection},
     Notification, RpcResult,
 };
+use karlsen_rpc_service::policy::RpcPolicy;
 use karlsen_utils::networking::NetAddress;
 use karlsen_utils_tower::{
     counters::TowerConnectionCounters,
@@ -54,16 +55,20 @@ pub struct ServerContext {
     pub core_service: DynRpcService,
     /// The notifier relaying RPC core notifications to connections
     pub notifier: Arc<Notifier<Notification, Connection>>,
+    /// The access policy of the listener
+    pub policy: Arc<RpcPolicy>,
 }
 
 impl ServerContext {
     pub fn new(
         core_service: DynRpcService,
         notifier: Arc<Notifier<Notification, Connection>>,
+        policy: Arc<RpcPolicy>,
     ) -> Self {
         Self {
             core_service,
             notifier,
+            policy,
         }
     }
 }
@@ -95,6 +100,7 @@ impl ConnectionHandler {
         subscription_context: SubscriptionContext,
         broadcasters: usize,
         counters: Arc<TowerConnectionCounters>,
+        policy: Arc<RpcPolicy>,
     ) -> Self {
         // This notifier UTXOs subscription granularity to rpc-core notifier
         let policies = MutationPolicies::new(UtxosChangedMutationPolicy::AddressSet);
@@ -129,7 +135,7 @@ impl ConnectionHandler {
             broadcasters,
             policies,
         ));
-        let server_context = ServerContext::new(core_service, notifier);
+        let server_context = ServerContext::new(core_service, notifier, policy);
         let interface = Arc::new(Factory::new_interface(server_context.clone(), network_bps));
         let running = Default::default();
 
@@ -159,6 +165,12 @@ impl ConnectionHandler {
                 .send_compressed(CompressionEncoding::Gzip)
                 .max_decoding_message_size(RPC_MAX_MESSAGE_SIZE);
 
+            // Server reflection lets generic tools (grpcurl, Postman, ...) discover the protowire schema
+            let reflection_server = tonic_reflection::server::Builder::configure()
+                .register_encoded_file_descriptor_set(FILE_DESCRIPTOR_SET)
+                .build()
+                .expect("protowire file descriptor set is valid");
+
             // TODO: check whether we should set tcp_keepalive
             // const GRPC_KEEP_ALIVE_PING_INTERVAL: Duration = Duration::from_secs(5);
             // const GRPC_KEEP_ALIVE_PING_TIMEOUT: Duration = Duration::from_secs(120);
@@ -170,6 +182,7 @@ impl ConnectionHandler {
                     CountBytesBody::new(body, bytes_tx.clone())
                 }))
                 .add_service(protowire_server)
+                .add_service(reflection_server)
                 .serve_with_shutdown(
                     serve_address.into(),
                     signal_receiver.map(|_| {
@@ -83,6 +83,23 @@ impl Factory {
                 GetServerInfo,
                 GetSyncStatus,
                 GetDaaScoreTimestampEstimate,
+                GetServerCapabilities,
+                GetNetworkInfo,
+                TestMempoolAccept,
+                GetDifficultyInfo,
+                DebugScript,
+                GetTransactionStatus,
+                GetBlockFilterHeaders,
+                GetBlockFilters,
+                GetBalanceByAddressesAt,
+                GetUnconfirmedTxRisk,
+                GetDagSlice,
+                GetAcceptanceEventsSince,
+                GetTemplateDiff,
+                GetMetadata,
+                UpdateMetadata,
+                GetMinerStats,
+                GetAddressCluster,
                 NotifyBlockAdded,
                 NotifyNewBlockTemplate,
                 NotifyFinalityConflict,
@@ -91,6 +108,8 @@ impl Factory {
                 NotifyPruningPointUtxoSetOverride,
                 NotifyVirtualDaaScoreChanged,
                 NotifyVirtualChainChanged,
+                NotifyTransactionStatusChanged,
+                NotifyDoubleSpendDetected,
                 StopNotifyingUtxosChanged,
                 StopNotifyingPruningPointUtxoSetOverride,
             ]
@@ -10,11 +10,15 @@ use crate::{
 use karlsen_core::debug;
 use karlsen_grpc_core::{
     ops::KarlsendPayloadOps,
-    protowire::{KarlsendRequest, KarlsendResponse},
+    protowire::{karlsend_response::Payload, KarlsendRequest, KarlsendResponse},
 };
+use karlsen_rpc_core::{api::ops::RpcApiOps, RpcError};
+use karlsen_rpc_service::policy::parse_method;
 
 pub struct RequestHandler {
     rpc_op: KarlsendPayloadOps,
+    /// The RPC API method matching `rpc_op`, checked against the listener policy
+    api_op: Option<RpcApiOps>,
     incoming_route: IncomingRoute,
     server_ctx: ServerContext,
     method: DynKarlsendMethod,
@@ -30,8 +34,10 @@ impl RequestHandler {
         connection: Connection,
     ) -> Self {
         let method = interface.get_method(&rpc_op);
+        let api_op = rpc_op_to_api_op(rpc_op);
         Self {
             rpc_op,
+            api_op,
             incoming_route,
             server_ctx: server_context,
             method,
@@ -44,28 +50,48 @@ impl RequestHandler {
         request: KarlsendRequest,
     ) -> GrpcServerResult<KarlsendResponse> {
         let id = request.id;
+        let client = self.connection.net_address().ip();
+        let allowed = match self.api_op {
+            Some(op) => self.server_ctx.policy.check(op, client),
+            None if self.server_ctx.policy.is_restricted() => {
+                Err(RpcError::MethodNotAllowed(self.rpc_op.as_str().to_string()))
+            }
+            None => Ok(()),
+        };
+        if let Err(err) = allowed {
+            return Ok(KarlsendResponse {
+                id,
+                payload: Some(self.rpc_op.to_error_response(err)),
+            });
+        }
         let mut response = self
             .method
             .call(self.server_ctx.clone(), self.connection.clone(), request)
             .await?;
         response.id = id;
+        if let Some(Payload::GetServerCapabilitiesResponse(ref mut capabilities)) = response.payload
+        {
+            self.server_ctx
+                .policy
+                .filter_capabilities(&mut capabilities.methods, &mut capabilities.notifications);
+        }
         Ok(response)
     }
-}
 
-#[async_trait::async_trait]
-impl Handler for RequestHandler {
-    async fn start(&mut self) {
-        debug!(
-            "GRPC, Starting request handler {:?} for client {}",
-            self.rpc_op, self.connection
-        );
-        while let Ok(request) = self.incoming_route.recv().await {
-            let response = self.handle_request(request).await;
-            match response {
+    /// Handles a request and enqueues its response, page by page if the request streams pages.
+    /// Returns false if the connection is closed.
+    async fn handle_and_enqueue(&self, request: KarlsendRequest) -> bool {
+        let mut request = Some(request);
+        while let Some(current) = request.take() {
+            // Pages are requested one by one so every page is bounded by the response size limit
+            let streaming = current.is_page_streaming().then(|| current.clone());
+            match self.handle_request(current).await {
                 Ok(response) => {
+                    request = streaming
+                        .zip(response.next_page_cursor())
+                        .map(|(streaming, cursor)| streaming.with_page_cursor(cursor));
                     if self.connection.enqueue(response).await.is_err() {
-                        break;
+                        return false;
                     }
                 }
                 Err(e) => {
@@ -76,6 +102,32 @@ impl Handler for RequestHandler {
                 }
             }
         }
+        true
+    }
+}
+
+/// Maps a protowire operation to its RPC API method. Stopping a notification is the same
+/// method as starting it, both being subscription commands.
+fn rpc_op_to_api_op(rpc_op: KarlsendPayloadOps) -> Option<RpcApiOps> {
+    let name = rpc_op.as_str();
+    match name.strip_prefix("StopNotifying") {
+        Some(event) => parse_method(&format!("Notify{}", event)),
+        None => parse_method(name),
+    }
+}
+
+#[async_trait::async_trait]
+impl Handler for RequestHandler {
+    async fn start(&mut self) {
+        debug!(
+            "GRPC, Starting request handler {:?} for client {}",
+            self.rpc_op, self.connection
+        );
+        while let Ok(request) = self.incoming_route.recv().await {
+            if !self.handle_and_enqueue(request).await {
+                break;
+            }
+        }
         debug!(
             "GRPC, Exiting request handler {:?} for client {}",
             self.rpc_op, self.connection
@@ -5,7 +5,7 @@ use karlsen_core::{
     task::service::{AsyncService, AsyncServiceFuture},
     trace, warn,
 };
-use karlsen_rpc_service::service::RpcCoreService;
+use karlsen_rpc_service::{policy::RpcPolicy, service::RpcCoreService};
 use karlsen_utils::{networking::NetAddress, triggers::SingleTrigger};
 use karlsen_utils_tower::counters::TowerConnectionCounters;
 use std::sync::Arc;
@@ -20,6 +20,7 @@ pub struct GrpcService {
     started: SingleTrigger,
     shutdown: SingleTrigger,
     counters: Arc<TowerConnectionCounters>,
+    policy: Arc<RpcPolicy>,
 }
 
 impl GrpcService {
@@ -32,6 +33,7 @@ impl GrpcService {
         rpc_max_clients: usize,
         broadcasters: usize,
         counters: Arc<TowerConnectionCounters>,
+        policy: Arc<RpcPolicy>,
     ) -> Self {
         Self {
             net_address: address,
@@ -42,6 +44,7 @@ impl GrpcService {
             started: Default::default(),
             shutdown: Default::default(),
             counters,
+            policy,
         }
     }
 
@@ -71,6 +74,7 @@ impl AsyncService for GrpcService {
             self.core_service.subscription_context(),
             self.broadcasters,
             self.counters.clone(),
+            self.policy.clone(),
         );
 
         // Signal the server was started
@@ -84,6 +84,9 @@ impl RpcApi for RpcCoreMock {
             is_synced: false,
             has_notify_command: true,
             has_message_id: true,
+            peer_clock_offset: 0,
+            header_clock_offset: 0,
+            is_clock_skewed: false,
         })
     }
 
@@ -288,6 +291,125 @@ impl RpcApi for RpcCoreMock {
         Err(RpcError::NotImplemented)
     }
 
+    async fn get_server_capabilities_call(
+        &self,
+        _request: GetServerCapabilitiesRequest,
+    ) -> RpcResult<GetServerCapabilitiesResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn get_network_info_call(
+        &self,
+        _request: GetNetworkInfoRequest,
+    ) -> RpcResult<GetNetworkInfoResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn test_mempool_accept_call(
+        &self,
+        _request: TestMempoolAcceptRequest,
+    ) -> RpcResult<TestMempoolAcceptResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn get_difficulty_info_call(
+        &self,
+        _request: GetDifficultyInfoRequest,
+    ) -> RpcResult<GetDifficultyInfoResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn debug_script_call(
+        &self,
+        _request: DebugScriptRequest,
+    ) -> RpcResult<DebugScriptResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn get_transaction_status_call(
+        &self,
+        _request: GetTransactionStatusRequest,
+    ) -> RpcResult<GetTransactionStatusResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn get_block_filter_headers_call(
+        &self,
+        _request: GetBlockFilterHeadersRequest,
+    ) -> RpcResult<GetBlockFilterHeadersResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn get_block_filters_call(
+        &self,
+        _request: GetBlockFiltersRequest,
+    ) -> RpcResult<GetBlockFiltersResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn get_balance_by_addresses_at_call(
+        &self,
+        _request: GetBalanceByAddressesAtRequest,
+    ) -> RpcResult<GetBalanceByAddressesAtResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn get_unconfirmed_tx_risk_call(
+        &self,
+        _request: GetUnconfirmedTxRiskRequest,
+    ) -> RpcResult<GetUnconfirmedTxRiskResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn get_dag_slice_call(
+        &self,
+        _request: GetDagSliceRequest,
+    ) -> RpcResult<GetDagSliceResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn get_acceptance_events_since_call(
+        &self,
+        _request: GetAcceptanceEventsSinceRequest,
+    ) -> RpcResult<GetAcceptanceEventsSinceResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn get_template_diff_call(
+        &self,
+        _request: GetTemplateDiffRequest,
+    ) -> RpcResult<GetTemplateDiffResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn get_metadata_call(
+        &self,
+        _request: GetMetadataRequest,
+    ) -> RpcResult<GetMetadataResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn update_metadata_call(
+        &self,
+        _request: UpdateMetadataRequest,
+    ) -> RpcResult<UpdateMetadataResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn get_miner_stats_call(
+        &self,
+        _request: GetMinerStatsRequest,
+    ) -> RpcResult<GetMinerStatsResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn get_address_cluster_call(
+        &self,
+        _request: GetAddressClusterRequest,
+    ) -> RpcResult<GetAddressClusterResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
     // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
     // Notification API
 
@@ -77,7 +77,7 @@ impl ToTokens for RpcTable {
                         //let request = request;
                         let __ret: RpcResult<#response_type> = {
                             let resp: ClientResult<#response_type> = __self.inner.rpc_client.call(#rpc_api_ops::#handler, request).await;
-                            Ok(resp.map_err(|e| karlsen_rpc_core::error::RpcError::RpcSubsystem(e.to_string()))?)
+                            Ok(resp.map_err(|e| karlsen_rpc_core::error::RpcError::from_wrpc_message(e.to_string()))?)
                         };
                         #[allow(unreachable_code)]
                         __ret
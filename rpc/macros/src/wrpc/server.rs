@@ -62,10 +62,12 @@ impl ToTokens for RpcTable {
             targets.push(quote! {
                 #rpc_api_ops::#handler => {
                     interface.method(#rpc_api_ops::#handler, method!(|server_ctx: #server_ctx_type, connection_ctx: #connection_ctx_type, request: #request_type| async move {
+                        server_ctx.check_policy(&connection_ctx, #rpc_api_ops::#handler)
+                            .map_err(|e|ServerError::Text(karlsen_rpc_core::RpcErrorInfo::from(&e).to_wrpc_message()))?;
                         let verbose = server_ctx.verbose();
                         if verbose { workflow_log::log_info!("request: {:?}",request); }
                         let response: #response_type = server_ctx.rpc_service(&connection_ctx).#fn_call(request).await
-                            .map_err(|e|ServerError::Text(e.to_string()))?;
+                            .map_err(|e|ServerError::Text(karlsen_rpc_core::RpcErrorInfo::from(&e).to_wrpc_message()))?;
                         if verbose { workflow_log::log_info!("response: {:?}",response); }
                         Ok(response)
                     }));
@@ -0,0 +1,31 @@
+use crate::miner_stats::PowerCost;
+
+/// Settings of the RPC core service
+#[derive(Debug, Clone)]
+pub struct RpcCoreConfig {
+    /// Threads of the pool running the heavy queries, 0 to run them on the shared runtime
+    pub workers: usize,
+    /// Power drawn by the miners of the node and its price, reported along with the mining stats
+    pub power_cost: Option<PowerCost>,
+    /// Maximum estimated size in bytes of the responses listing unbounded collections, above
+    /// which they are either split in pages or rejected (0 means unlimited)
+    pub max_response_size: usize,
+    /// Maximum count of consensus reads a request may spend resolving the previous outputs of
+    /// the blocks it returns
+    pub max_previous_outputs_reads: usize,
+    /// Secret shared with the miners keying the binding of the block templates to their work,
+    /// all the templates being bound when set
+    pub work_binding_secret: Option<Vec<u8>>,
+}
+
+impl Default for RpcCoreConfig {
+    fn default() -> Self {
+        Self {
+            workers: 1,
+            power_cost: None,
+            max_response_size: 0,
+            max_previous_outputs_reads: 100_000,
+            work_binding_secret: None,
+        }
+    }
+}
@@ -1,6 +1,8 @@
+use crate::converter::previous_outputs::PreviousOutputsResolver;
 use async_trait::async_trait;
 use karlsen_addresses::Address;
 use karlsen_consensus_core::{
+    acceptance_data::AcceptanceData,
     block::Block,
     config::Config,
     hashing::tx::hash,
@@ -18,8 +20,8 @@ use karlsen_notify::converter::Converter;
 use karlsen_rpc_core::{
     BlockAddedNotification, Notification, RpcAcceptedTransactionIds, RpcBlock, RpcBlockVerboseData,
     RpcHash, RpcMempoolEntry, RpcMempoolEntryByAddress, RpcResult, RpcTransaction,
-    RpcTransactionInput, RpcTransactionOutput, RpcTransactionOutputVerboseData,
-    RpcTransactionVerboseData,
+    RpcTransactionInput, RpcTransactionInputVerboseData, RpcTransactionOutput,
+    RpcTransactionOutputVerboseData, RpcTransactionVerboseData,
 };
 use karlsen_txscript::{extract_script_pub_key_address, script_class::ScriptClass};
 use std::{collections::HashMap, fmt::Debug, sync::Arc};
@@ -46,10 +48,16 @@ impl ConsensusConverter {
         // work limit directly because the block difficulty is encoded in a block
         // with the compact form which loses precision.
         let target = Uint256::from_compact_target_bits(bits);
+        self.get_target_difficulty_ratio(&target)
+    }
+
+    /// Returns the proof-of-work difficulty matching `target` as a multiple of the minimum difficulty
+    pub fn get_target_difficulty_ratio(&self, target: &Uint256) -> f64 {
         self.config.max_difficulty_target_f64 / target.as_f64()
     }
 
-    /// Converts a consensus [`Block`] into an [`RpcBlock`], optionally including transaction verbose data.
+    /// Converts a consensus [`Block`] into an [`RpcBlock`], optionally including transaction verbose data
+    /// and the outputs spent by the transaction inputs.
     ///
     /// _GO-KARLSEND: PopulateBlockWithVerboseData_
     pub async fn get_block(
@@ -58,6 +66,7 @@ impl ConsensusConverter {
         block: &Block,
         include_transactions: bool,
         include_transaction_verbose_data: bool,
+        previous_outputs_resolver: Option<&mut PreviousOutputsResolver>,
     ) -> RpcResult<RpcBlock> {
         let hash = block.hash();
         let ghostdag_data = consensus.async_get_ghostdag_data(hash).await?;
@@ -66,7 +75,10 @@ impl ConsensusConverter {
             .async_get_block_children(hash)
             .await
             .unwrap_or_default();
-        let is_chain_block = consensus.async_is_chain_block(hash).await?;
+        // Checked against the committed virtual state, matching the acceptance data served for it
+        let is_chain_block = consensus
+            .async_with_read_view(move |view| view.is_chain_block(hash))
+            .await?;
         let verbose_data = Some(RpcBlockVerboseData {
             hash,
             difficulty: self.get_difficulty_ratio(block.header.bits),
@@ -80,7 +92,7 @@ impl ConsensusConverter {
             is_chain_block,
         });
 
-        let transactions = if include_transactions {
+        let mut transactions = if include_transactions {
             block
                 .transactions
                 .iter()
@@ -96,6 +108,21 @@ impl ConsensusConverter {
         } else {
             vec![]
         };
+        if let Some(resolver) = previous_outputs_resolver.filter(|_| include_transactions) {
+            let previous_outputs = resolver.resolve(consensus, block).await?;
+            transactions
+                .iter_mut()
+                .zip(block.transactions.iter())
+                .for_each(|(rpc_transaction, transaction)| {
+                    self.set_previous_outputs(
+                        rpc_transaction,
+                        transaction
+                            .inputs
+                            .iter()
+                            .map(|input| previous_outputs.get(&input.previous_outpoint)),
+                    )
+                });
+        }
 
         Ok(RpcBlock {
             header: (*block.header).clone(),
@@ -104,13 +131,47 @@ impl ConsensusConverter {
         })
     }
 
+    /// Sets the outputs spent by the inputs of `transaction`, as returned by `previous_outputs`
+    fn set_previous_outputs<'a>(
+        &self,
+        transaction: &mut RpcTransaction,
+        previous_outputs: impl Iterator<Item = Option<&'a TransactionOutput>>,
+    ) {
+        transaction
+            .inputs
+            .iter_mut()
+            .zip(previous_outputs)
+            .for_each(|(input, previous_output)| {
+                input.verbose_data = Some(RpcTransactionInputVerboseData {
+                    previous_output: previous_output.map(|x| self.get_transaction_output(x)),
+                });
+            });
+    }
+
     pub fn get_mempool_entry(
         &self,
         consensus: &ConsensusProxy,
         transaction: &MutableTransaction,
+        resolve_previous_outputs: bool,
     ) -> RpcMempoolEntry {
         let is_orphan = !transaction.is_fully_populated();
-        let rpc_transaction = self.get_transaction(consensus, &transaction.tx, None, true);
+        let mut rpc_transaction = self.get_transaction(consensus, &transaction.tx, None, true);
+        if resolve_previous_outputs {
+            // The entries of the mempool transactions are populated from the virtual UTXO set and the mempool
+            let previous_outputs = transaction
+                .entries
+                .iter()
+                .map(|entry| {
+                    entry.as_ref().map(|entry| {
+                        TransactionOutput::new(entry.amount, entry.script_public_key.clone())
+                    })
+                })
+                .collect::<Vec<_>>();
+            self.set_previous_outputs(
+                &mut rpc_transaction,
+                previous_outputs.iter().map(Option::as_ref),
+            );
+        }
         RpcMempoolEntry::new(
             transaction.calculated_fee.unwrap_or_default(),
             rpc_transaction,
@@ -124,11 +185,20 @@ impl ConsensusConverter {
         address: Address,
         owner_transactions: &OwnerTransactions,
         transactions: &HashMap<TransactionId, MutableTransaction>,
+        resolve_previous_outputs: bool,
     ) -> RpcMempoolEntryByAddress {
-        let sending =
-            self.get_owner_entries(consensus, &owner_transactions.sending_txs, transactions);
-        let receiving =
-            self.get_owner_entries(consensus, &owner_transactions.receiving_txs, transactions);
+        let sending = self.get_owner_entries(
+            consensus,
+            &owner_transactions.sending_txs,
+            transactions,
+            resolve_previous_outputs,
+        );
+        let receiving = self.get_owner_entries(
+            consensus,
+            &owner_transactions.receiving_txs,
+            transactions,
+            resolve_previous_outputs,
+        );
         RpcMempoolEntryByAddress::new(address, sending, receiving)
     }
 
@@ -137,11 +207,16 @@ impl ConsensusConverter {
         consensus: &ConsensusProxy,
         transaction_ids: &TransactionIdSet,
         transactions: &HashMap<TransactionId, MutableTransaction>,
+        resolve_previous_outputs: bool,
     ) -> Vec<RpcMempoolEntry> {
         transaction_ids
             .iter()
             .map(|x| {
-                self.get_mempool_entry(consensus, transactions.get(x).expect("transaction exists"))
+                self.get_mempool_entry(
+                    consensus,
+                    transactions.get(x).expect("transaction exists"),
+                    resolve_previous_outputs,
+                )
             })
             .collect()
     }
@@ -208,16 +283,14 @@ impl ConsensusConverter {
         }
     }
 
-    pub async fn get_virtual_chain_accepted_transaction_ids(
+    /// Returns the transactions accepted by the blocks added by `chain_path`, along with their
+    /// `acceptance_data`
+    pub fn get_virtual_chain_accepted_transaction_ids(
         &self,
-        consensus: &ConsensusProxy,
         chain_path: &ChainPath,
-    ) -> RpcResult<Vec<RpcAcceptedTransactionIds>> {
-        let acceptance_data = consensus
-            .async_get_blocks_acceptance_data(chain_path.added.clone())
-            .await
-            .unwrap();
-        Ok(chain_path
+        acceptance_data: &[Arc<AcceptanceData>],
+    ) -> Vec<RpcAcceptedTransactionIds> {
+        chain_path
             .added
             .iter()
             .zip(acceptance_data.iter())
@@ -228,7 +301,7 @@ impl ConsensusConverter {
                     .flat_map(|x| x.accepted_transactions.iter().map(|tx| tx.transaction_id))
                     .collect(),
             })
-            .collect())
+            .collect()
     }
 }
 
@@ -243,7 +316,7 @@ impl Converter for ConsensusConverter {
                 let session = self.consensus_manager.consensus().unguarded_session();
                 // If get_block fails, rely on the infallible From implementation which will lack verbose data
                 let block = Arc::new(
-                    self.get_block(&session, &msg.block, true, true)
+                    self.get_block(&session, &msg.block, true, true, None)
                         .await
                         .unwrap_or_else(|_| (&msg.block).into()),
                 );
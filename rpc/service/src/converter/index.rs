@@ -1,10 +1,13 @@
 use async_trait::async_trait;
-use karlsen_consensus_core::config::Config;
-use karlsen_index_core::indexed_utxos::UtxoSetByScriptPublicKey;
+use karlsen_consensus_core::{
+    config::Config,
+    tx::{ScriptPublicKey, TransactionOutpoint, UtxoEntry},
+};
+use karlsen_index_core::indexed_utxos::{CompactUtxoEntry, UtxoSetByScriptPublicKey};
 use karlsen_index_core::notification::{self as index_notify, Notification as IndexNotification};
 use karlsen_notify::converter::Converter;
 use karlsen_rpc_core::{
-    utxo_set_into_rpc, Notification, RpcUtxosByAddressesEntry, UtxosChangedNotification,
+    utxo_set_into_rpc, Notification, RpcAddress, RpcUtxosByAddressesEntry, UtxosChangedNotification,
 };
 use std::sync::Arc;
 
@@ -29,6 +32,25 @@ impl IndexConverter {
         }
     }
 
+    pub fn get_utxos_by_addresses_entry(
+        &self,
+        address: &RpcAddress,
+        script_public_key: &ScriptPublicKey,
+        outpoint: TransactionOutpoint,
+        entry: &CompactUtxoEntry,
+    ) -> RpcUtxosByAddressesEntry {
+        RpcUtxosByAddressesEntry {
+            address: Some(address.clone()),
+            outpoint,
+            utxo_entry: UtxoEntry::new(
+                entry.amount,
+                script_public_key.clone(),
+                entry.block_daa_score,
+                entry.is_coinbase,
+            ),
+        }
+    }
+
     pub fn get_utxos_by_addresses_entries(
         &self,
         item: &UtxoSetByScriptPublicKey,
@@ -1,3 +1,4 @@
 pub mod consensus;
 pub mod index;
+pub mod previous_outputs;
 pub mod protocol;
@@ -0,0 +1,484 @@
+//! Resolution of the outputs spent by the transaction inputs of the blocks served by the RPC.
+//!
+//! The outputs spent by a block are found in the block itself, else in the UTXO diff and the
+//! mergeset blocks of the chain block accepting it, or in the virtual UTXO set while the block is
+//! not merged yet. The accepting block is found by walking the selected parents of the block down
+//! to the selected chain, then the selected chain up to the block merging it.
+//!
+//! Every consensus read counts against the budget of the request, which fails once it is spent.
+//! The reads are cached along the request, since the blocks requested together mostly share their
+//! accepting blocks and mergesets.
+
+use karlsen_consensus_core::{
+    block::Block,
+    tx::{TransactionOutpoint, TransactionOutput},
+    utxo::utxo_diff::UtxoDiff,
+};
+use karlsen_consensusmanager::ConsensusProxy;
+use karlsen_hashes::Hash;
+use karlsen_rpc_core::{RpcError, RpcResult};
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+};
+
+/// Previous outputs of the inputs of a block, by outpoint
+pub type PreviousOutputs = HashMap<TransactionOutpoint, TransactionOutput>;
+
+/// Chain block accepting a block, along with its mergeset
+struct Acceptance {
+    hash: Hash,
+    mergeset: Arc<Vec<Hash>>,
+}
+
+/// Resolves the previous outputs of the blocks of a single request
+pub struct PreviousOutputsResolver {
+    /// Maximum count of consensus reads of the request
+    limit: usize,
+    reads: usize,
+    /// Whether each block read is a chain block
+    chain_blocks: HashMap<Hash, bool>,
+    /// Selected parent of each block read
+    selected_parents: HashMap<Hash, Hash>,
+    /// Mergeset of each chain block read
+    mergesets: HashMap<Hash, Arc<Vec<Hash>>>,
+    /// Next chain block of each chain block, `None` for the sink
+    chain_children: HashMap<Hash, Option<Hash>>,
+    /// UTXO diff of each accepting block, `None` if its data is pruned
+    utxo_diffs: HashMap<Hash, Option<Arc<UtxoDiff>>>,
+    /// Mergeset blocks read, `None` if their body is missing
+    blocks: HashMap<Hash, Option<Block>>,
+}
+
+impl PreviousOutputsResolver {
+    pub fn new(limit: usize) -> Self {
+        Self {
+            limit,
+            reads: 0,
+            chain_blocks: HashMap::new(),
+            selected_parents: HashMap::new(),
+            mergesets: HashMap::new(),
+            chain_children: HashMap::new(),
+            utxo_diffs: HashMap::new(),
+            blocks: HashMap::new(),
+        }
+    }
+
+    /// Returns the outputs spent by the inputs of the transactions of `block`, omitting the ones
+    /// whose data is pruned
+    pub async fn resolve(
+        &mut self,
+        consensus: &ConsensusProxy,
+        block: &Block,
+    ) -> RpcResult<PreviousOutputs> {
+        let mut unresolved = block
+            .transactions
+            .iter()
+            .flat_map(|transaction| {
+                transaction
+                    .inputs
+                    .iter()
+                    .map(|input| input.previous_outpoint)
+            })
+            .collect::<HashSet<_>>();
+        let mut previous_outputs = HashMap::new();
+        resolve_created_outputs(block, &mut unresolved, &mut previous_outputs);
+        if unresolved.is_empty() {
+            return Ok(previous_outputs);
+        }
+
+        match self.find_accepting_block(consensus, block.hash()).await? {
+            Some(acceptance) => {
+                // The entries spent by the accepted transactions are removed by the diff of the
+                // accepting block
+                if let Some(utxo_diff) = self.utxo_diff(consensus, acceptance.hash).await? {
+                    unresolved.retain(|outpoint| match utxo_diff.remove.get(outpoint) {
+                        Some(entry) => {
+                            previous_outputs.insert(
+                                *outpoint,
+                                TransactionOutput::new(
+                                    entry.amount,
+                                    entry.script_public_key.clone(),
+                                ),
+                            );
+                            false
+                        }
+                        None => true,
+                    });
+                }
+                // Outputs created and spent within the same mergeset never reach the diff
+                for hash in acceptance.mergeset.iter().copied() {
+                    if unresolved.is_empty() {
+                        break;
+                    }
+                    if hash == block.hash() {
+                        continue;
+                    }
+                    if let Some(merged_block) = self.block(consensus, hash).await? {
+                        resolve_created_outputs(
+                            &merged_block,
+                            &mut unresolved,
+                            &mut previous_outputs,
+                        );
+                    }
+                }
+            }
+            None => {
+                self.read()?;
+                let outpoints = unresolved.into_iter().collect::<Vec<_>>();
+                let entries = consensus
+                    .async_get_virtual_utxo_entries(outpoints.clone())
+                    .await;
+                outpoints
+                    .into_iter()
+                    .zip(entries)
+                    .filter_map(|(outpoint, entry)| entry.map(|entry| (outpoint, entry)))
+                    .for_each(|(outpoint, entry)| {
+                        previous_outputs.insert(
+                            outpoint,
+                            TransactionOutput::new(entry.amount, entry.script_public_key),
+                        );
+                    });
+            }
+        }
+        Ok(previous_outputs)
+    }
+
+    /// Returns the chain block merging the block `hash`, or `None` if the block is not merged by
+    /// the selected chain yet
+    async fn find_accepting_block(
+        &mut self,
+        consensus: &ConsensusProxy,
+        hash: Hash,
+    ) -> RpcResult<Option<Acceptance>> {
+        // The closest chain block in the past of the block, or the block itself
+        let mut current = hash;
+        while !self.is_chain_block(consensus, current).await? {
+            current = self.selected_parent(consensus, current).await?;
+        }
+
+        // The accepting block lies on the chain above it
+        while let Some(child) = self.chain_child(consensus, current).await? {
+            let mergeset = self.mergeset(consensus, child).await?;
+            if mergeset.contains(&hash) {
+                return Ok(Some(Acceptance {
+                    hash: child,
+                    mergeset,
+                }));
+            }
+            current = child;
+        }
+        Ok(None)
+    }
+
+    async fn is_chain_block(&mut self, consensus: &ConsensusProxy, hash: Hash) -> RpcResult<bool> {
+        if let Some(is_chain_block) = self.chain_blocks.get(&hash) {
+            return Ok(*is_chain_block);
+        }
+        self.read()?;
+        let is_chain_block = consensus.async_is_chain_block(hash).await?;
+        self.chain_blocks.insert(hash, is_chain_block);
+        Ok(is_chain_block)
+    }
+
+    async fn selected_parent(&mut self, consensus: &ConsensusProxy, hash: Hash) -> RpcResult<Hash> {
+        if let Some(selected_parent) = self.selected_parents.get(&hash) {
+            return Ok(*selected_parent);
+        }
+        self.read()?;
+        let selected_parent = consensus
+            .async_get_ghostdag_data(hash)
+            .await?
+            .selected_parent;
+        self.selected_parents.insert(hash, selected_parent);
+        Ok(selected_parent)
+    }
+
+    async fn mergeset(
+        &mut self,
+        consensus: &ConsensusProxy,
+        hash: Hash,
+    ) -> RpcResult<Arc<Vec<Hash>>> {
+        if let Some(mergeset) = self.mergesets.get(&hash) {
+            return Ok(mergeset.clone());
+        }
+        self.read()?;
+        let ghostdag_data = consensus.async_get_ghostdag_data(hash).await?;
+        let mergeset = Arc::new(
+            ghostdag_data
+                .mergeset_blues
+                .into_iter()
+                .chain(ghostdag_data.mergeset_reds)
+                .collect::<Vec<_>>(),
+        );
+        self.mergesets.insert(hash, mergeset.clone());
+        Ok(mergeset)
+    }
+
+    async fn chain_child(
+        &mut self,
+        consensus: &ConsensusProxy,
+        hash: Hash,
+    ) -> RpcResult<Option<Hash>> {
+        if let Some(chain_child) = self.chain_children.get(&hash) {
+            return Ok(*chain_child);
+        }
+        self.read()?;
+        let mut chain_child = None;
+        for child in consensus
+            .async_get_block_children(hash)
+            .await
+            .unwrap_or_default()
+        {
+            if self.is_chain_block(consensus, child).await? {
+                chain_child = Some(child);
+                break;
+            }
+        }
+        self.chain_children.insert(hash, chain_child);
+        Ok(chain_child)
+    }
+
+    async fn utxo_diff(
+        &mut self,
+        consensus: &ConsensusProxy,
+        hash: Hash,
+    ) -> RpcResult<Option<Arc<UtxoDiff>>> {
+        if let Some(utxo_diff) = self.utxo_diffs.get(&hash) {
+            return Ok(utxo_diff.clone());
+        }
+        self.read()?;
+        let utxo_diff = consensus.async_get_block_utxo_diff(hash).await.ok();
+        self.utxo_diffs.insert(hash, utxo_diff.clone());
+        Ok(utxo_diff)
+    }
+
+    async fn block(&mut self, consensus: &ConsensusProxy, hash: Hash) -> RpcResult<Option<Block>> {
+        if let Some(block) = self.blocks.get(&hash) {
+            return Ok(block.clone());
+        }
+        self.read()?;
+        let block = consensus.async_get_block(hash).await.ok();
+        self.blocks.insert(hash, block.clone());
+        Ok(block)
+    }
+
+    /// Counts a consensus read against the budget of the request
+    fn read(&mut self) -> RpcResult<()> {
+        if self.reads == self.limit {
+            return Err(RpcError::PreviousOutputsReadLimit(self.limit));
+        }
+        self.reads += 1;
+        Ok(())
+    }
+}
+
+/// Moves the unresolved outpoints created by the transactions of `block` into `previous_outputs`
+fn resolve_created_outputs(
+    block: &Block,
+    unresolved: &mut HashSet<TransactionOutpoint>,
+    previous_outputs: &mut PreviousOutputs,
+) {
+    for transaction in block.transactions.iter() {
+        for (index, output) in transaction.outputs.iter().enumerate() {
+            let outpoint = TransactionOutpoint::new(transaction.id(), index as u32);
+            if unresolved.remove(&outpoint) {
+                previous_outputs.insert(outpoint, output.clone());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use karlsen_consensus_core::{
+        api::ConsensusApi,
+        errors::consensus::{ConsensusError, ConsensusResult},
+        header::Header,
+        subnets::SUBNETWORK_ID_NATIVE,
+        trusted::ExternalGhostdagData,
+        tx::{ScriptPublicKey, Transaction, TransactionInput, UtxoEntry},
+    };
+    use karlsen_consensusmanager::{ConsensusInstance, SessionLock};
+
+    /// A DAG whose selected chain is `G <- A <- C`, where `C` also merges `B`, a child of `G`,
+    /// and `D` is a tip child of `A` not merged yet
+    #[derive(Default)]
+    struct MockConsensus {
+        blocks: HashMap<Hash, Block>,
+        ghostdag_data: HashMap<Hash, ExternalGhostdagData>,
+        children: HashMap<Hash, Vec<Hash>>,
+        chain: HashSet<Hash>,
+        utxo_diffs: HashMap<Hash, Arc<UtxoDiff>>,
+        virtual_utxos: HashMap<TransactionOutpoint, UtxoEntry>,
+    }
+
+    impl MockConsensus {
+        fn add_block(
+            &mut self,
+            hash: Hash,
+            parents: Vec<Hash>,
+            mergeset: Vec<Hash>,
+            transactions: Vec<Transaction>,
+        ) {
+            let selected_parent = parents.first().copied().unwrap_or_default();
+            for parent in parents.iter() {
+                self.children.entry(*parent).or_default().push(hash);
+            }
+            self.blocks.insert(
+                hash,
+                Block::new(Header::from_precomputed_hash(hash, parents), transactions),
+            );
+            self.ghostdag_data.insert(
+                hash,
+                ExternalGhostdagData {
+                    blue_score: 0,
+                    blue_work: Default::default(),
+                    selected_parent,
+                    mergeset_blues: mergeset,
+                    mergeset_reds: vec![],
+                    blues_anticone_sizes: Default::default(),
+                },
+            );
+        }
+    }
+
+    impl ConsensusApi for MockConsensus {
+        fn is_chain_block(&self, hash: Hash) -> ConsensusResult<bool> {
+            Ok(self.chain.contains(&hash))
+        }
+
+        fn get_ghostdag_data(&self, hash: Hash) -> ConsensusResult<ExternalGhostdagData> {
+            self.ghostdag_data
+                .get(&hash)
+                .cloned()
+                .ok_or(ConsensusError::MissingData(hash))
+        }
+
+        fn get_block_children(&self, hash: Hash) -> Option<Vec<Hash>> {
+            self.children.get(&hash).cloned()
+        }
+
+        fn get_block(&self, hash: Hash) -> ConsensusResult<Block> {
+            self.blocks
+                .get(&hash)
+                .cloned()
+                .ok_or(ConsensusError::BlockNotFound(hash))
+        }
+
+        fn get_block_utxo_diff(&self, hash: Hash) -> ConsensusResult<Arc<UtxoDiff>> {
+            self.utxo_diffs
+                .get(&hash)
+                .cloned()
+                .ok_or(ConsensusError::MissingData(hash))
+        }
+
+        fn get_virtual_utxo_entries(
+            &self,
+            outpoints: &[TransactionOutpoint],
+        ) -> Vec<Option<UtxoEntry>> {
+            outpoints
+                .iter()
+                .map(|outpoint| self.virtual_utxos.get(outpoint).cloned())
+                .collect()
+        }
+    }
+
+    fn transaction(previous_outpoints: &[TransactionOutpoint], value: u64) -> Transaction {
+        Transaction::new(
+            0,
+            previous_outpoints
+                .iter()
+                .map(|outpoint| TransactionInput::new(*outpoint, vec![], 0, 0))
+                .collect(),
+            vec![TransactionOutput::new(value, ScriptPublicKey::default())],
+            0,
+            SUBNETWORK_ID_NATIVE,
+            0,
+            vec![],
+        )
+    }
+
+    fn output(transaction: &Transaction) -> TransactionOutpoint {
+        TransactionOutpoint::new(transaction.id(), 0)
+    }
+
+    fn entry(amount: u64) -> UtxoEntry {
+        UtxoEntry::new(amount, ScriptPublicKey::default(), 0, false)
+    }
+
+    #[tokio::test]
+    async fn test_resolve_previous_outputs() {
+        let [g, a, b, c, d] = [1u64, 2, 3, 4, 5].map(Hash::from_u64_word);
+        // Spent by B and found in the diff of C
+        let old = TransactionOutpoint::new(Hash::from_u64_word(100), 0);
+        // Created by A and spent by B, both merged by C, so missing from its diff
+        let a_tx = transaction(&[], 20);
+        let b_tx = transaction(&[old, output(&a_tx)], 30);
+        // Created and spent within C
+        let c_tx = transaction(&[], 40);
+        let c_spend = transaction(&[output(&c_tx)], 41);
+        // Spent by D, not merged yet, and found in the virtual UTXO set
+        let unspent = TransactionOutpoint::new(Hash::from_u64_word(101), 0);
+        let d_tx = transaction(&[unspent], 50);
+        // Spent by D and found nowhere
+        let missing = TransactionOutpoint::new(Hash::from_u64_word(102), 0);
+        let d_spend = transaction(&[missing], 51);
+
+        let mut mock = MockConsensus::default();
+        mock.add_block(g, vec![], vec![], vec![]);
+        mock.add_block(a, vec![g], vec![g], vec![a_tx]);
+        mock.add_block(b, vec![g], vec![g], vec![b_tx.clone()]);
+        mock.add_block(c, vec![a, b], vec![a, b], vec![c_tx, c_spend]);
+        mock.add_block(d, vec![a], vec![a], vec![d_tx, d_spend]);
+        mock.chain.extend([g, a, c]);
+        mock.utxo_diffs.insert(
+            c,
+            Arc::new(UtxoDiff {
+                add: Default::default(),
+                remove: [(old, entry(10))].into_iter().collect(),
+            }),
+        );
+        mock.virtual_utxos.insert(unspent, entry(11));
+        let consensus = ConsensusInstance::new(SessionLock::new(), Arc::new(mock))
+            .session()
+            .await;
+        let values = |previous_outputs: PreviousOutputs| {
+            let mut values = previous_outputs
+                .values()
+                .map(|output| output.value)
+                .collect::<Vec<_>>();
+            values.sort();
+            values
+        };
+
+        let mut resolver = PreviousOutputsResolver::new(100);
+        let block = consensus.async_get_block(b).await.unwrap();
+        assert_eq!(
+            values(resolver.resolve(&consensus, &block).await.unwrap()),
+            vec![10, 20]
+        );
+        let block = consensus.async_get_block(c).await.unwrap();
+        assert_eq!(
+            values(resolver.resolve(&consensus, &block).await.unwrap()),
+            vec![40]
+        );
+        let block = consensus.async_get_block(d).await.unwrap();
+        assert_eq!(
+            values(resolver.resolve(&consensus, &block).await.unwrap()),
+            vec![11]
+        );
+
+        // The reads are cached along the request, which fails once its budget is spent
+        let block = consensus.async_get_block(b).await.unwrap();
+        let reads = resolver.reads;
+        resolver.resolve(&consensus, &block).await.unwrap();
+        assert_eq!(resolver.reads, reads);
+        let mut resolver = PreviousOutputsResolver::new(3);
+        assert!(matches!(
+            resolver.resolve(&consensus, &block).await,
+            Err(RpcError::PreviousOutputsReadLimit(3))
+        ));
+    }
+}
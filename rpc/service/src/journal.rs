@@ -0,0 +1,234 @@
+//! A short retained journal of the blocks added to the DAG.
+//!
+//! The journal records the `BlockAdded` notifications emitted by the RPC core in
+//! their emission order and pushes them to block added streams. A client opening a
+//! stream gives a cursor (the hash of the last block it processed) so it resumes
+//! after a disconnection where it stopped, instead of falling back to range queries.
+
+use karlsen_rpc_core::{RpcBlock, RpcHash};
+use parking_lot::Mutex;
+use std::{collections::VecDeque, sync::Arc};
+
+/// Receiver of the blocks of a stream
+pub trait BlockAddedSink: Send + Sync {
+    /// Delivers a block of the stream, `gap` reporting that blocks may have been missed
+    /// before this one. Returns false if the sink can no longer receive blocks.
+    fn deliver(&self, block: &Arc<RpcBlock>, gap: bool) -> bool;
+}
+
+/// Outcome of opening a stream
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BlockAddedStream {
+    /// Identifier of the stream in the journal
+    pub id: u64,
+    /// Count of retained blocks replayed to the sink
+    pub replayed: usize,
+    /// The cursor is not retained by the journal so some blocks may have been missed
+    /// before the first streamed one
+    pub gap: bool,
+}
+
+struct Stream {
+    id: u64,
+    sink: Arc<dyn BlockAddedSink>,
+    /// A gap is still to be reported with the next delivered block
+    pending_gap: bool,
+}
+
+#[derive(Default)]
+struct Inner {
+    entries: VecDeque<Arc<RpcBlock>>,
+    streams: Vec<Stream>,
+    next_stream_id: u64,
+}
+
+pub struct BlockAddedJournal {
+    capacity: usize,
+    inner: Mutex<Inner>,
+}
+
+impl BlockAddedJournal {
+    pub fn new(capacity: usize) -> Self {
+        assert!(
+            capacity > 0,
+            "the block added journal capacity must be positive"
+        );
+        Self {
+            capacity,
+            inner: Mutex::new(Inner {
+                entries: VecDeque::with_capacity(capacity),
+                ..Default::default()
+            }),
+        }
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Appends a block, evicting the oldest one if the journal is full, and pushes it to
+    /// the open streams. Streams whose sink is gone get closed.
+    pub fn push(&self, block: Arc<RpcBlock>) {
+        let mut inner = self.inner.lock();
+        if inner.entries.len() == self.capacity {
+            inner.entries.pop_front();
+        }
+        inner.entries.push_back(block.clone());
+        inner.streams.retain_mut(|stream| {
+            let delivered = stream.sink.deliver(&block, stream.pending_gap);
+            stream.pending_gap = false;
+            delivered
+        });
+    }
+
+    /// Opens a stream replaying to `sink` the blocks following the block identified by
+    /// `cursor` and then pushing it the blocks appended to the journal.
+    ///
+    /// If `cursor` is `None`, the replay starts at the oldest retained block. If `cursor` is
+    /// not found, the replay also starts at the oldest retained block and a gap is signaled.
+    /// The replay and the registration of the stream happen under the journal lock, so no
+    /// block is missed nor duplicated in between.
+    pub fn subscribe(
+        &self,
+        cursor: Option<RpcHash>,
+        sink: Arc<dyn BlockAddedSink>,
+    ) -> BlockAddedStream {
+        let mut inner = self.inner.lock();
+        let (start, gap) = match cursor {
+            None => (0, false),
+            Some(hash) => match inner.entries.iter().rposition(|x| x.header.hash == hash) {
+                Some(position) => (position + 1, false),
+                None => (0, true),
+            },
+        };
+        let mut pending_gap = gap;
+        let mut replayed = 0;
+        for block in inner.entries.iter().skip(start) {
+            if !sink.deliver(block, pending_gap) {
+                break;
+            }
+            pending_gap = false;
+            replayed += 1;
+        }
+        let id = inner.next_stream_id;
+        inner.next_stream_id += 1;
+        inner.streams.push(Stream {
+            id,
+            sink,
+            pending_gap,
+        });
+        BlockAddedStream { id, replayed, gap }
+    }
+
+    /// Closes a stream, returning false if it was already closed
+    pub fn unsubscribe(&self, id: u64) -> bool {
+        let mut inner = self.inner.lock();
+        let len = inner.streams.len();
+        inner.streams.retain(|stream| stream.id != id);
+        inner.streams.len() < len
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use karlsen_consensus_core::header::Header;
+
+    fn block(i: u64) -> Arc<RpcBlock> {
+        let mut header = Header::from_precomputed_hash(RpcHash::from_u64_word(i), vec![]);
+        header.blue_score = i;
+        Arc::new(RpcBlock {
+            header,
+            transactions: vec![],
+            verbose_data: None,
+        })
+    }
+
+    /// Records the delivered blocks as (blue score, gap) pairs, accepting up to `limit` blocks
+    struct TestSink {
+        limit: usize,
+        delivered: Mutex<Vec<(u64, bool)>>,
+    }
+
+    impl TestSink {
+        fn new(limit: usize) -> Arc<Self> {
+            Arc::new(Self {
+                limit,
+                delivered: Default::default(),
+            })
+        }
+
+        fn take(&self) -> Vec<(u64, bool)> {
+            std::mem::take(&mut *self.delivered.lock())
+        }
+    }
+
+    impl BlockAddedSink for TestSink {
+        fn deliver(&self, block: &Arc<RpcBlock>, gap: bool) -> bool {
+            let mut delivered = self.delivered.lock();
+            if delivered.len() == self.limit {
+                return false;
+            }
+            delivered.push((block.header.blue_score, gap));
+            true
+        }
+    }
+
+    #[test]
+    fn test_block_added_journal() {
+        let journal = BlockAddedJournal::new(4);
+        (1..=6).for_each(|i| journal.push(block(i)));
+
+        // Blocks 1 and 2 were evicted
+        let sink = TestSink::new(usize::MAX);
+        let stream = journal.subscribe(None, sink.clone());
+        assert_eq!((stream.replayed, stream.gap), (4, false));
+        assert_eq!(
+            sink.take(),
+            vec![(3, false), (4, false), (5, false), (6, false)]
+        );
+
+        // The replay is followed by the pushed blocks, in order
+        let resumed = TestSink::new(usize::MAX);
+        let stream = journal.subscribe(Some(RpcHash::from_u64_word(4)), resumed.clone());
+        assert_eq!((stream.replayed, stream.gap), (2, false));
+        journal.push(block(7));
+        assert_eq!(resumed.take(), vec![(5, false), (6, false), (7, false)]);
+        assert_eq!(sink.take(), vec![(7, false)]);
+
+        // A closed stream gets no more blocks
+        assert!(journal.unsubscribe(stream.id));
+        assert!(!journal.unsubscribe(stream.id));
+        journal.push(block(8));
+        assert!(resumed.take().is_empty());
+        assert_eq!(sink.take(), vec![(8, false)]);
+    }
+
+    #[test]
+    fn test_block_added_journal_gaps() {
+        let journal = BlockAddedJournal::new(2);
+        (1..=4).for_each(|i| journal.push(block(i)));
+
+        // An evicted cursor signals a gap on the first replayed block
+        let sink = TestSink::new(usize::MAX);
+        let stream = journal.subscribe(Some(RpcHash::from_u64_word(1)), sink.clone());
+        assert_eq!((stream.replayed, stream.gap), (2, true));
+        assert_eq!(sink.take(), vec![(3, true), (4, false)]);
+
+        // With nothing to replay, the gap is reported on the first pushed block
+        let journal = BlockAddedJournal::new(2);
+        let sink = TestSink::new(usize::MAX);
+        let stream = journal.subscribe(Some(RpcHash::from_u64_word(1)), sink.clone());
+        assert_eq!((stream.replayed, stream.gap), (0, true));
+        journal.push(block(2));
+        journal.push(block(3));
+        assert_eq!(sink.take(), vec![(2, true), (3, false)]);
+
+        // A sink failing a delivery gets its stream closed
+        let sink = TestSink::new(1);
+        let stream = journal.subscribe(None, sink.clone());
+        assert_eq!(stream.replayed, 1);
+        journal.push(block(4));
+        assert!(!journal.unsubscribe(stream.id));
+    }
+}
@@ -1,3 +1,12 @@
 pub mod collector;
+pub mod config;
 pub mod converter;
+pub mod journal;
+pub mod metadata;
+pub mod miner_stats;
+pub mod paging;
+pub mod policy;
 pub mod service;
+pub mod template_history;
+pub mod tx_status;
+pub mod workers;
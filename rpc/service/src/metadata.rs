@@ -0,0 +1,283 @@
+//! Key-value store of the metadata of the external indexers running alongside the node.
+//!
+//! Every client owns a namespace in which it keeps its cursors and checkpoints. The entries
+//! live in the meta database, next to the node data, so they survive consensus resets and are
+//! part of any backup of the data directory. Updates are applied atomically and may be made
+//! conditional on the current values of some keys, letting a client advance its cursors only if
+//! they still hold the values it last read.
+
+use karlsen_database::{
+    prelude::{BatchDbWriter, CachePolicy, CachedDbAccess, StoreResultExtensions, DB},
+    registry::DatabaseStorePrefixes,
+};
+use karlsen_rpc_core::{RpcError, RpcMetadataEntry, RpcResult};
+use parking_lot::Mutex;
+use rocksdb::WriteBatch;
+use std::{fmt::Display, sync::Arc};
+
+/// Max length of a namespace, in bytes
+pub const MAX_NAMESPACE_LENGTH: usize = 64;
+/// Max length of a key, in bytes
+pub const MAX_KEY_LENGTH: usize = 256;
+/// Max length of a value, in bytes
+pub const MAX_VALUE_LENGTH: usize = 64 * 1024;
+/// Max count of keys read, checked or written by a single call
+pub const MAX_KEYS_PER_CALL: usize = 1024;
+
+/// Namespace length followed by the namespace and the key, so the keys of distinct namespaces
+/// never collide
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+struct MetadataKey(Vec<u8>);
+
+impl MetadataKey {
+    fn new(namespace: &str, key: &str) -> Self {
+        let mut bytes = Vec::with_capacity(1 + namespace.len() + key.len());
+        bytes.push(namespace.len() as u8);
+        bytes.extend_from_slice(namespace.as_bytes());
+        bytes.extend_from_slice(key.as_bytes());
+        Self(bytes)
+    }
+}
+
+impl AsRef<[u8]> for MetadataKey {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl Display for MetadataKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", String::from_utf8_lossy(&self.0[1..]))
+    }
+}
+
+/// A conditional batch of changes to the entries of a namespace
+#[derive(Debug, Default)]
+pub struct MetadataUpdate {
+    /// Entries which must hold these values for the update to be applied
+    pub expected_entries: Vec<RpcMetadataEntry>,
+    /// Keys which must be absent for the update to be applied
+    pub expected_absent_keys: Vec<String>,
+    pub set_entries: Vec<RpcMetadataEntry>,
+    pub delete_keys: Vec<String>,
+}
+
+pub struct MetadataStore {
+    db: Arc<DB>,
+    access: CachedDbAccess<MetadataKey, Vec<u8>>,
+    /// Serializes the updates so their conditions are checked against the values they replace
+    write_lock: Mutex<()>,
+}
+
+impl MetadataStore {
+    pub fn new(db: Arc<DB>) -> Self {
+        Self {
+            db: db.clone(),
+            access: CachedDbAccess::new(
+                db,
+                CachePolicy::Empty,
+                DatabaseStorePrefixes::ExternalMetadata.into(),
+            ),
+            write_lock: Mutex::new(()),
+        }
+    }
+
+    /// Returns the entries of `keys` found in `namespace`, in the order of `keys`
+    pub fn get(&self, namespace: &str, keys: &[String]) -> RpcResult<Vec<RpcMetadataEntry>> {
+        check_namespace(namespace)?;
+        check_key_count(keys.len())?;
+        keys.iter().try_for_each(|key| check_key(key))?;
+        let mut entries = Vec::with_capacity(keys.len());
+        for key in keys {
+            if let Some(value) = self.read(namespace, key)? {
+                entries.push(RpcMetadataEntry::new(key.clone(), value));
+            }
+        }
+        Ok(entries)
+    }
+
+    /// Applies all the changes of `update` to `namespace` in a single write if all its
+    /// conditions hold, and none of them otherwise
+    pub fn update(&self, namespace: &str, update: MetadataUpdate) -> RpcResult<()> {
+        check_namespace(namespace)?;
+        check_key_count(
+            update.expected_entries.len()
+                + update.expected_absent_keys.len()
+                + update.set_entries.len()
+                + update.delete_keys.len(),
+        )?;
+        update
+            .expected_entries
+            .iter()
+            .chain(update.set_entries.iter())
+            .try_for_each(check_entry)?;
+        update
+            .expected_absent_keys
+            .iter()
+            .chain(update.delete_keys.iter())
+            .try_for_each(|key| check_key(key))?;
+
+        let _guard = self.write_lock.lock();
+        for entry in update.expected_entries.iter() {
+            if self.read(namespace, &entry.key)?.as_ref() != Some(&entry.value) {
+                return Err(RpcError::MetadataConditionFailed(entry.key.clone()));
+            }
+        }
+        for key in update.expected_absent_keys.iter() {
+            if self.read(namespace, key)?.is_some() {
+                return Err(RpcError::MetadataConditionFailed(key.clone()));
+            }
+        }
+
+        let mut batch = WriteBatch::default();
+        for key in update.delete_keys {
+            self.access
+                .delete(
+                    BatchDbWriter::new(&mut batch),
+                    MetadataKey::new(namespace, &key),
+                )
+                .map_err(|err| RpcError::General(err.to_string()))?;
+        }
+        for entry in update.set_entries {
+            self.access
+                .write(
+                    BatchDbWriter::new(&mut batch),
+                    MetadataKey::new(namespace, &entry.key),
+                    entry.value,
+                )
+                .map_err(|err| RpcError::General(err.to_string()))?;
+        }
+        self.db
+            .write(batch)
+            .map_err(|err| RpcError::General(err.to_string()))
+    }
+
+    fn read(&self, namespace: &str, key: &str) -> RpcResult<Option<Vec<u8>>> {
+        self.access
+            .read(MetadataKey::new(namespace, key))
+            .optional()
+            .map_err(|err| RpcError::General(err.to_string()))
+    }
+}
+
+fn check_namespace(namespace: &str) -> RpcResult<()> {
+    if namespace.is_empty()
+        || namespace.len() > MAX_NAMESPACE_LENGTH
+        || !namespace
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+    {
+        return Err(RpcError::InvalidMetadataRequest(format!(
+            "the namespace must have 1 to {MAX_NAMESPACE_LENGTH} ASCII alphanumeric, '-', '_' or '.' characters"
+        )));
+    }
+    Ok(())
+}
+
+fn check_key_count(count: usize) -> RpcResult<()> {
+    if count > MAX_KEYS_PER_CALL {
+        return Err(RpcError::InvalidMetadataRequest(format!(
+            "{count} keys exceed the maximum of {MAX_KEYS_PER_CALL} keys per call"
+        )));
+    }
+    Ok(())
+}
+
+fn check_key(key: &str) -> RpcResult<()> {
+    if key.is_empty() || key.len() > MAX_KEY_LENGTH {
+        return Err(RpcError::InvalidMetadataRequest(format!(
+            "the keys must have 1 to {MAX_KEY_LENGTH} bytes"
+        )));
+    }
+    Ok(())
+}
+
+fn check_entry(entry: &RpcMetadataEntry) -> RpcResult<()> {
+    check_key(&entry.key)?;
+    if entry.value.len() > MAX_VALUE_LENGTH {
+        return Err(RpcError::InvalidMetadataRequest(format!(
+            "the value of key {} exceeds the maximum of {MAX_VALUE_LENGTH} bytes",
+            entry.key
+        )));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use karlsen_database::{create_temp_db, prelude::ConnBuilder};
+
+    fn entry(key: &str, value: &[u8]) -> RpcMetadataEntry {
+        RpcMetadataEntry::new(key.to_string(), value.to_vec())
+    }
+
+    #[test]
+    fn test_metadata_store() {
+        let (_lifetime, db) = create_temp_db!(ConnBuilder::default().with_files_limit(10));
+        let store = MetadataStore::new(db);
+        let keys = vec!["cursor".to_string(), "checkpoint".to_string()];
+
+        store
+            .update(
+                "indexer-a",
+                MetadataUpdate {
+                    expected_absent_keys: vec!["cursor".to_string()],
+                    set_entries: vec![entry("cursor", &[1]), entry("checkpoint", &[2])],
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+        let entries = store.get("indexer-a", &keys).unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].value, vec![1]);
+        assert_eq!(entries[1].value, vec![2]);
+
+        // Namespaces are isolated from each other
+        assert!(store.get("indexer-b", &keys).unwrap().is_empty());
+
+        // A failed condition leaves all the entries untouched
+        let err = store
+            .update(
+                "indexer-a",
+                MetadataUpdate {
+                    expected_entries: vec![entry("cursor", &[0])],
+                    set_entries: vec![entry("cursor", &[3])],
+                    delete_keys: vec!["checkpoint".to_string()],
+                    ..Default::default()
+                },
+            )
+            .unwrap_err();
+        assert!(matches!(err, RpcError::MetadataConditionFailed(key) if key == "cursor"));
+        assert_eq!(store.get("indexer-a", &keys).unwrap().len(), 2);
+
+        store
+            .update(
+                "indexer-a",
+                MetadataUpdate {
+                    expected_entries: vec![entry("cursor", &[1])],
+                    set_entries: vec![entry("cursor", &[3])],
+                    delete_keys: vec!["checkpoint".to_string()],
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+        let entries = store.get("indexer-a", &keys).unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].key, "cursor");
+        assert_eq!(entries[0].value, vec![3]);
+
+        // Invalid namespaces and oversized values are rejected
+        assert!(store.get("", &keys).is_err());
+        assert!(store.get("indexer/a", &keys).is_err());
+        assert!(store
+            .update(
+                "indexer-a",
+                MetadataUpdate {
+                    set_entries: vec![entry("cursor", &vec![0; MAX_VALUE_LENGTH + 1])],
+                    ..Default::default()
+                },
+            )
+            .is_err());
+    }
+}
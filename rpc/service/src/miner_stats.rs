@@ -0,0 +1,419 @@
+//! Mining profitability estimates of the miners connected to the node.
+//!
+//! Miners may report the hashrate they measure along with their template requests, the latest
+//! report of each worker counting for a few minutes. Without any report, the node only sees the
+//! blocks its miners submit, so their hashrate is estimated from the expected work of these
+//! blocks over a sliding window. The network hashrate is derived from the difficulty of the
+//! latest template and the target block rate.
+
+use karlsen_consensus_core::constants::SOMPI_PER_KARLSEN;
+use karlsen_math::Uint256;
+use parking_lot::Mutex;
+use std::{
+    collections::{HashMap, VecDeque},
+    fmt::{self, Display, Formatter},
+    time::{Duration, Instant},
+};
+
+/// Period over which the hashrate of the miners is estimated
+pub const HASHRATE_WINDOW: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Period after which the hashrate reported by a worker no longer counts
+pub const HASHRATE_REPORT_TIMEOUT: Duration = Duration::from_secs(5 * 60);
+
+/// Maximum count of workers whose reported hashrate is tracked
+const MAX_REPORTING_WORKERS: usize = 4096;
+
+const SECONDS_PER_DAY: f64 = 24.0 * 60.0 * 60.0;
+
+/// Power drawn by the mining rig and the price paid for it
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PowerCost {
+    pub watts: f64,
+    /// Price of a kWh, in the currency the profitability is reported in
+    pub price_per_kwh: f64,
+}
+
+impl PowerCost {
+    pub fn daily_cost(&self) -> f64 {
+        self.watts * 24.0 / 1000.0 * self.price_per_kwh
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct MinerStatsSnapshot {
+    pub accepted_blocks: u64,
+    pub rejected_blocks: u64,
+    /// Templates issued with their work bound to the pay address and worker
+    pub bound_templates: u64,
+    /// Accepted blocks whose coinbase carries a work binding
+    pub bound_blocks: u64,
+    /// Blocks rejected for carrying no valid binding of the address they pay
+    pub binding_mismatches: u64,
+    /// Hashrate reported by the miners, or estimated from their blocks, in hashes per second
+    pub hashrate: f64,
+    /// Count of workers currently reporting their hashrate
+    pub reporting_workers: usize,
+    /// Estimated hashrate of the network, in hashes per second
+    pub network_hashrate: f64,
+    /// Share of the network hashrate held by the miners, in [0, 1]
+    pub network_share: f64,
+    /// Expected time between two blocks found by the miners, `None` while the hashrate is unknown
+    pub expected_time_to_block: Option<Duration>,
+    /// Coinbase value of the latest template, in sompi
+    pub block_reward: u64,
+    /// Expected coinbase value earned per day, in sompi
+    pub expected_daily_reward: u64,
+    /// Daily cost of the power drawn by the miners, if the power cost is configured
+    pub daily_power_cost: Option<f64>,
+    /// Power cost of a mined KLS, if the power cost is configured and the hashrate is known
+    pub cost_per_kls: Option<f64>,
+}
+
+impl Display for MinerStatsSnapshot {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} blocks found ({} rejected), hashrate {} ({:.6}% of {})",
+            self.accepted_blocks,
+            self.rejected_blocks,
+            format_hashrate(self.hashrate),
+            self.network_share * 100.0,
+            format_hashrate(self.network_hashrate)
+        )?;
+        if self.reporting_workers > 0 {
+            write!(f, " reported by {} workers", self.reporting_workers)?;
+        }
+        if let Some(time) = self.expected_time_to_block {
+            write!(
+                f,
+                ", a block expected every {:.1}h, {:.2} KLS/day",
+                time.as_secs_f64() / 3600.0,
+                self.expected_daily_reward as f64 / SOMPI_PER_KARLSEN as f64
+            )?;
+        }
+        if let Some(cost) = self.daily_power_cost {
+            write!(f, ", power cost {cost:.2}/day")?;
+        }
+        if let Some(cost) = self.cost_per_kls {
+            write!(f, " ({cost:.6}/KLS)")?;
+        }
+        if self.bound_templates > 0 || self.binding_mismatches > 0 {
+            write!(
+                f,
+                ", {} bound blocks out of {} bound templates ({} redirected)",
+                self.bound_blocks, self.bound_templates, self.binding_mismatches
+            )?;
+        }
+        Ok(())
+    }
+}
+
+pub struct MinerStats {
+    target_time_per_block: Duration,
+    power_cost: Option<PowerCost>,
+    started: Instant,
+    inner: Mutex<MinerStatsInner>,
+}
+
+#[derive(Default)]
+struct MinerStatsInner {
+    /// Submission times and expected work of the accepted blocks within the window
+    blocks: VecDeque<(Instant, f64)>,
+    /// Latest hashrate reported by each worker, keyed by pay address and worker id
+    reports: HashMap<(String, Option<u16>), (Instant, f64)>,
+    accepted_blocks: u64,
+    rejected_blocks: u64,
+    bound_templates: u64,
+    bound_blocks: u64,
+    binding_mismatches: u64,
+    /// Difficulty bits and coinbase value of the latest template
+    latest_template: Option<(u32, u64)>,
+}
+
+impl MinerStats {
+    /// Creates the stats of a network producing a block every `target_time_per_block`
+    /// milliseconds
+    pub fn new(target_time_per_block: u64, power_cost: Option<PowerCost>) -> Self {
+        Self {
+            target_time_per_block: Duration::from_millis(target_time_per_block),
+            power_cost,
+            started: Instant::now(),
+            inner: Default::default(),
+        }
+    }
+
+    pub fn record_template(&self, bits: u32, block_reward: u64, bound: bool) {
+        let mut inner = self.inner.lock();
+        inner.latest_template = Some((bits, block_reward));
+        if bound {
+            inner.bound_templates += 1;
+        }
+    }
+
+    /// Records the hashrate measured by a worker, replacing its previous report
+    pub fn record_reported_hashrate(
+        &self,
+        pay_address: String,
+        worker_id: Option<u16>,
+        hashrate: f64,
+    ) {
+        self.record_reported_hashrate_at(Instant::now(), pay_address, worker_id, hashrate)
+    }
+
+    fn record_reported_hashrate_at(
+        &self,
+        now: Instant,
+        pay_address: String,
+        worker_id: Option<u16>,
+        hashrate: f64,
+    ) {
+        if !hashrate.is_finite() || hashrate < 0.0 {
+            return;
+        }
+        let mut inner = self.inner.lock();
+        let key = (pay_address, worker_id);
+        if inner.reports.len() >= MAX_REPORTING_WORKERS && !inner.reports.contains_key(&key) {
+            inner.expire_reports(now);
+            if inner.reports.len() >= MAX_REPORTING_WORKERS {
+                return;
+            }
+        }
+        inner.reports.insert(key, (now, hashrate));
+    }
+
+    pub fn record_submitted_block(&self, bits: u32, accepted: bool, bound: bool) {
+        self.record_submitted_block_at(Instant::now(), bits, accepted, bound)
+    }
+
+    /// Records a block rejected for carrying no valid binding of the address it pays
+    pub fn record_binding_mismatch(&self) {
+        let mut inner = self.inner.lock();
+        inner.rejected_blocks += 1;
+        inner.binding_mismatches += 1;
+    }
+
+    fn record_submitted_block_at(&self, now: Instant, bits: u32, accepted: bool, bound: bool) {
+        let mut inner = self.inner.lock();
+        if !accepted {
+            inner.rejected_blocks += 1;
+            return;
+        }
+        inner.accepted_blocks += 1;
+        if bound {
+            inner.bound_blocks += 1;
+        }
+        inner.blocks.push_back((now, expected_work(bits)));
+    }
+
+    pub fn snapshot(&self) -> MinerStatsSnapshot {
+        self.snapshot_at(Instant::now())
+    }
+
+    fn snapshot_at(&self, now: Instant) -> MinerStatsSnapshot {
+        let mut inner = self.inner.lock();
+        while inner
+            .blocks
+            .front()
+            .is_some_and(|(time, _)| now.saturating_duration_since(*time) > HASHRATE_WINDOW)
+        {
+            inner.blocks.pop_front();
+        }
+
+        inner.expire_reports(now);
+
+        let reporting_workers = inner.reports.len();
+        let hashrate = if reporting_workers > 0 {
+            inner
+                .reports
+                .values()
+                .map(|(_, hashrate)| hashrate)
+                .sum::<f64>()
+        } else {
+            let elapsed = now
+                .saturating_duration_since(self.started)
+                .min(HASHRATE_WINDOW)
+                .as_secs_f64();
+            match elapsed > 0.0 {
+                true => inner.blocks.iter().map(|(_, work)| work).sum::<f64>() / elapsed,
+                false => 0.0,
+            }
+        };
+        let (network_work, block_reward) = inner
+            .latest_template
+            .map(|(bits, reward)| (expected_work(bits), reward))
+            .unwrap_or_default();
+        let network_hashrate = network_work / self.target_time_per_block.as_secs_f64();
+        let network_share = match network_hashrate > 0.0 {
+            true => (hashrate / network_hashrate).min(1.0),
+            false => 0.0,
+        };
+        let expected_time_to_block = (hashrate > 0.0 && network_work > 0.0)
+            .then(|| Duration::from_secs_f64(network_work / hashrate));
+        let expected_daily_reward = match expected_time_to_block {
+            Some(time) => (SECONDS_PER_DAY / time.as_secs_f64() * block_reward as f64) as u64,
+            None => 0,
+        };
+        let daily_power_cost = self.power_cost.map(|power_cost| power_cost.daily_cost());
+        let cost_per_kls = daily_power_cost
+            .filter(|_| expected_daily_reward > 0)
+            .map(|cost| cost / (expected_daily_reward as f64 / SOMPI_PER_KARLSEN as f64));
+
+        MinerStatsSnapshot {
+            accepted_blocks: inner.accepted_blocks,
+            rejected_blocks: inner.rejected_blocks,
+            bound_templates: inner.bound_templates,
+            bound_blocks: inner.bound_blocks,
+            binding_mismatches: inner.binding_mismatches,
+            hashrate,
+            reporting_workers,
+            network_hashrate,
+            network_share,
+            expected_time_to_block,
+            block_reward,
+            expected_daily_reward,
+            daily_power_cost,
+            cost_per_kls,
+        }
+    }
+}
+
+impl MinerStatsInner {
+    fn expire_reports(&mut self, now: Instant) {
+        self.reports
+            .retain(|_, (time, _)| now.saturating_duration_since(*time) <= HASHRATE_REPORT_TIMEOUT);
+    }
+}
+
+/// Expected count of hashes needed to find a block of difficulty `bits`
+fn expected_work(bits: u32) -> f64 {
+    let target = Uint256::from_compact_target_bits(bits);
+    2f64.powi(256) / (target.as_f64() + 1.0)
+}
+
+fn format_hashrate(hashrate: f64) -> String {
+    const UNITS: [&str; 7] = ["H/s", "KH/s", "MH/s", "GH/s", "TH/s", "PH/s", "EH/s"];
+    let mut value = hashrate;
+    let mut unit = 0;
+    while value >= 1000.0 && unit < UNITS.len() - 1 {
+        value /= 1000.0;
+        unit += 1;
+    }
+    format!("{value:.2} {}", UNITS[unit])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_miner_stats() {
+        // A target of 2^224 - 1, so 2^32 hashes are expected per block
+        let bits = 0x1d00ffff;
+        let work = expected_work(bits);
+        assert!((work / 2f64.powi(32) - 1.0).abs() < 1e-4);
+
+        let stats = MinerStats::new(
+            1000,
+            Some(PowerCost {
+                watts: 500.0,
+                price_per_kwh: 0.2,
+            }),
+        );
+        let snapshot = stats.snapshot_at(stats.started);
+        assert_eq!(snapshot.hashrate, 0.0);
+        assert_eq!(snapshot.expected_time_to_block, None);
+        assert_eq!(snapshot.cost_per_kls, None);
+
+        stats.record_template(bits, 100 * SOMPI_PER_KARLSEN, true);
+        let start = stats.started;
+        // Four blocks found in the first 100 seconds, the first one with its work bound
+        for i in 1..=4 {
+            stats.record_submitted_block_at(
+                start + Duration::from_secs(25 * i),
+                bits,
+                true,
+                i == 1,
+            );
+        }
+        stats.record_submitted_block_at(start + Duration::from_secs(100), bits, false, false);
+        stats.record_binding_mismatch();
+
+        let snapshot = stats.snapshot_at(start + Duration::from_secs(100));
+        assert_eq!(snapshot.accepted_blocks, 4);
+        assert_eq!(snapshot.rejected_blocks, 2);
+        assert_eq!(snapshot.bound_templates, 1);
+        assert_eq!(snapshot.bound_blocks, 1);
+        assert_eq!(snapshot.binding_mismatches, 1);
+        assert!((snapshot.hashrate - work / 25.0).abs() < 1.0);
+        assert!((snapshot.network_hashrate - work).abs() < 1.0);
+        assert!((snapshot.network_share - 0.04).abs() < 1e-9);
+        assert_eq!(
+            snapshot
+                .expected_time_to_block
+                .unwrap()
+                .as_secs_f64()
+                .round(),
+            25.0
+        );
+        // 3456 blocks a day
+        assert!(
+            snapshot
+                .expected_daily_reward
+                .abs_diff(345_600 * SOMPI_PER_KARLSEN)
+                < SOMPI_PER_KARLSEN
+        );
+        assert!((snapshot.daily_power_cost.unwrap() - 2.4).abs() < 1e-9);
+        assert!((snapshot.cost_per_kls.unwrap() - 2.4 / 345_600.0).abs() < 1e-9);
+
+        assert_eq!(format_hashrate(snapshot.network_hashrate), "4.29 GH/s");
+
+        // The blocks leave the window after a day
+        let snapshot = stats.snapshot_at(start + HASHRATE_WINDOW + Duration::from_secs(101));
+        assert_eq!(snapshot.hashrate, 0.0);
+        assert_eq!(snapshot.accepted_blocks, 4);
+    }
+
+    #[test]
+    fn test_reported_hashrate() {
+        let bits = 0x1d00ffff;
+        let work = expected_work(bits);
+        let stats = MinerStats::new(1000, None);
+        let start = stats.started;
+        stats.record_template(bits, 100 * SOMPI_PER_KARLSEN, false);
+
+        // The reports count before any block is found, the latest one of each worker replacing
+        // the previous one
+        stats.record_reported_hashrate_at(start, "a".to_string(), Some(1), work / 100.0);
+        stats.record_reported_hashrate_at(start, "a".to_string(), Some(2), work / 100.0);
+        stats.record_reported_hashrate_at(
+            start + Duration::from_secs(60),
+            "a".to_string(),
+            Some(1),
+            work / 50.0,
+        );
+        stats.record_reported_hashrate_at(start, "b".to_string(), None, f64::NAN);
+
+        let snapshot = stats.snapshot_at(start + Duration::from_secs(60));
+        assert_eq!(snapshot.accepted_blocks, 0);
+        assert_eq!(snapshot.reporting_workers, 2);
+        assert!((snapshot.hashrate - work * 0.03).abs() < 1.0);
+        assert!((snapshot.network_share - 0.03).abs() < 1e-9);
+        assert_eq!(
+            snapshot
+                .expected_time_to_block
+                .unwrap()
+                .as_secs_f64()
+                .round(),
+            33.0
+        );
+
+        // The reports expire, falling back to the blocks found, none here
+        let snapshot = stats.snapshot_at(
+            start + Duration::from_secs(60) + HASHRATE_REPORT_TIMEOUT + Duration::from_secs(1),
+        );
+        assert_eq!(snapshot.reporting_workers, 0);
+        assert_eq!(snapshot.hashrate, 0.0);
+        assert_eq!(snapshot.expected_time_to_block, None);
+    }
+}
@@ -0,0 +1,284 @@
+//!
+//! Size limit of the RPC responses listing unbounded collections.
+//!
+//! Such responses are assembled entry by entry in a stable order, summing the estimated
+//! serialized sizes of the entries. Reaching the limit fails a request which does not paginate
+//! before the node does the remaining work, while a paginated request gets the assembled
+//! entries along with a cursor resuming the listing after the last one.
+//!
+//! Pages are read from successive states of the node, so an entry may appear in two pages or
+//! in none if it was added or removed in between.
+//!
+
+use borsh::BorshSerialize;
+use karlsen_consensus_core::tx::{TransactionId, TransactionOutpoint};
+use karlsen_rpc_core::{RpcAddress, RpcError, RpcResult};
+use std::{io, str::FromStr};
+
+/// Key ordering the entries of a paginated response, encoded as the cursor of the next page
+pub trait CursorKey: Sized {
+    fn to_cursor(&self) -> String;
+    fn from_cursor(cursor: &str) -> Option<Self>;
+
+    /// Parses the cursor of a request, `None` meaning the first page
+    fn parse_cursor(cursor: Option<&str>) -> RpcResult<Option<Self>> {
+        cursor
+            .map(|cursor| {
+                Self::from_cursor(cursor).ok_or_else(|| RpcError::InvalidCursor(cursor.to_string()))
+            })
+            .transpose()
+    }
+}
+
+impl CursorKey for TransactionId {
+    fn to_cursor(&self) -> String {
+        self.to_string()
+    }
+
+    fn from_cursor(cursor: &str) -> Option<Self> {
+        TransactionId::from_str(cursor).ok()
+    }
+}
+
+impl CursorKey for TransactionOutpoint {
+    fn to_cursor(&self) -> String {
+        format!("{}:{}", self.transaction_id, self.index)
+    }
+
+    fn from_cursor(cursor: &str) -> Option<Self> {
+        let (transaction_id, index) = cursor.split_once(':')?;
+        Some(TransactionOutpoint::new(
+            TransactionId::from_str(transaction_id).ok()?,
+            index.parse().ok()?,
+        ))
+    }
+}
+
+/// Position of an entry of the UTXOs of some addresses, which are listed address by address
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AddressOutpoint {
+    pub address: RpcAddress,
+    pub outpoint: TransactionOutpoint,
+}
+
+impl CursorKey for AddressOutpoint {
+    fn to_cursor(&self) -> String {
+        format!("{}/{}", self.address, self.outpoint.to_cursor())
+    }
+
+    fn from_cursor(cursor: &str) -> Option<Self> {
+        let (address, outpoint) = cursor.split_once('/')?;
+        Some(AddressOutpoint {
+            address: RpcAddress::try_from(address).ok()?,
+            outpoint: TransactionOutpoint::from_cursor(outpoint)?,
+        })
+    }
+}
+
+/// The entries of a response page along with the cursor of the next page, if any
+pub struct Page<T> {
+    pub entries: Vec<T>,
+    pub next_cursor: Option<String>,
+}
+
+/// Maximum estimated size of a response, the binary encoding of its entries being the estimate
+#[derive(Clone, Copy, Debug)]
+pub struct ResponseSizeLimit {
+    /// Maximum size in bytes, 0 meaning no limit
+    max_size: usize,
+}
+
+impl ResponseSizeLimit {
+    pub fn new(max_size: usize) -> Self {
+        Self { max_size }
+    }
+
+    /// Starts a page of entries to be pushed in ascending key order
+    pub fn page<K: CursorKey, T: BorshSerialize>(&self, paginate: bool) -> PageBuilder<K, T> {
+        PageBuilder {
+            max_size: self.max_size,
+            paginate,
+            size: 0,
+            entries: Vec::new(),
+            last_key: None,
+            full: false,
+        }
+    }
+
+    /// Collects the entries of `entries`, given in ascending key order, up to the size limit.
+    ///
+    /// Entries are converted lazily by the iterator, so the ones beyond the limit are never
+    /// built.
+    pub fn collect<K: CursorKey, T: BorshSerialize>(
+        &self,
+        entries: impl Iterator<Item = (K, T)>,
+        paginate: bool,
+    ) -> RpcResult<Page<T>> {
+        let mut page = self.page(paginate);
+        for (key, entry) in entries {
+            if !page.push(key, entry)? {
+                break;
+            }
+        }
+        Ok(page.build())
+    }
+}
+
+/// A response page assembled entry by entry, so the caller reads its source only up to the
+/// size limit. A page holds at least one entry so every page makes progress.
+pub struct PageBuilder<K, T> {
+    max_size: usize,
+    paginate: bool,
+    size: usize,
+    entries: Vec<T>,
+    last_key: Option<K>,
+    /// Whether an entry was left for the next page
+    full: bool,
+}
+
+impl<K: CursorKey, T: BorshSerialize> PageBuilder<K, T> {
+    /// Adds `entry` to the page, returning false if the page is full, in which case the entry
+    /// is left for the next page and no more entries must be pushed. Fails if the page is full
+    /// while the request does not paginate.
+    pub fn push(&mut self, key: K, entry: T) -> RpcResult<bool> {
+        if self.max_size > 0 {
+            self.size += serialized_size(&entry);
+            if self.size > self.max_size && !self.entries.is_empty() {
+                if !self.paginate {
+                    return Err(RpcError::ResponseTooLarge(self.max_size));
+                }
+                self.full = true;
+                return Ok(false);
+            }
+        }
+        self.entries.push(entry);
+        self.last_key = Some(key);
+        Ok(true)
+    }
+
+    /// Returns the page, with the cursor of the next one if an entry was left out
+    pub fn build(self) -> Page<T> {
+        Page {
+            next_cursor: self
+                .last_key
+                .filter(|_| self.full)
+                .as_ref()
+                .map(K::to_cursor),
+            entries: self.entries,
+        }
+    }
+}
+
+/// Returns the length of the binary encoding of `item` without allocating it
+fn serialized_size(item: &impl BorshSerialize) -> usize {
+    struct Counter(usize);
+
+    impl io::Write for Counter {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0 += buf.len();
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    let mut counter = Counter(0);
+    item.serialize(&mut counter)
+        .expect("writing to a counter never fails");
+    counter.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use karlsen_addresses::{Prefix, Version};
+
+    fn entries(keys: &[u64]) -> impl Iterator<Item = (TransactionId, [u8; 10])> + '_ {
+        keys.iter()
+            .map(|&key| (TransactionId::from_u64_word(key), [0; 10]))
+    }
+
+    #[test]
+    fn test_response_size_limit() {
+        // Unlimited
+        let page = ResponseSizeLimit::new(0)
+            .collect(entries(&[1, 2, 3]), false)
+            .unwrap();
+        assert_eq!(page.entries.len(), 3);
+        assert!(page.next_cursor.is_none());
+
+        let limit = ResponseSizeLimit::new(25);
+        assert!(matches!(
+            limit.collect(entries(&[1, 2, 3]), false),
+            Err(RpcError::ResponseTooLarge(25))
+        ));
+        let page = limit.collect(entries(&[1, 2, 3]), true).unwrap();
+        assert_eq!(page.entries.len(), 2);
+        let cursor = page.next_cursor.unwrap();
+        assert_eq!(
+            TransactionId::parse_cursor(Some(&cursor)).unwrap(),
+            Some(TransactionId::from_u64_word(2))
+        );
+
+        // An entry larger than the limit still makes a page
+        let page = ResponseSizeLimit::new(5)
+            .collect(entries(&[1, 2]), true)
+            .unwrap();
+        assert_eq!(page.entries.len(), 1);
+        assert!(page.next_cursor.is_some());
+        let page = ResponseSizeLimit::new(5)
+            .collect(entries(&[1]), false)
+            .unwrap();
+        assert_eq!(page.entries.len(), 1);
+        assert!(page.next_cursor.is_none());
+
+        // A builder tells its source when to stop reading
+        let mut page = ResponseSizeLimit::new(25).page(true);
+        assert!(page
+            .push(TransactionId::from_u64_word(1), [0u8; 10])
+            .unwrap());
+        assert!(page
+            .push(TransactionId::from_u64_word(2), [0u8; 10])
+            .unwrap());
+        assert!(!page
+            .push(TransactionId::from_u64_word(3), [0u8; 10])
+            .unwrap());
+        let page = page.build();
+        assert_eq!(page.entries.len(), 2);
+        assert_eq!(
+            page.next_cursor,
+            Some(TransactionId::from_u64_word(2).to_cursor())
+        );
+    }
+
+    #[test]
+    fn test_cursor_keys() {
+        let outpoint = TransactionOutpoint::new(TransactionId::from_u64_word(7), 3);
+        assert_eq!(
+            TransactionOutpoint::parse_cursor(Some(&outpoint.to_cursor())).unwrap(),
+            Some(outpoint)
+        );
+        assert_eq!(TransactionOutpoint::parse_cursor(None).unwrap(), None);
+
+        let address_outpoint = AddressOutpoint {
+            address: RpcAddress::new(Prefix::Mainnet, Version::PubKey, &[7; 32]),
+            outpoint,
+        };
+        assert_eq!(
+            AddressOutpoint::parse_cursor(Some(&address_outpoint.to_cursor())).unwrap(),
+            Some(address_outpoint)
+        );
+        assert!(matches!(
+            AddressOutpoint::parse_cursor(Some(&outpoint.to_cursor())),
+            Err(RpcError::InvalidCursor(_))
+        ));
+        for cursor in ["", "7:3", &TransactionId::from_u64_word(7).to_string()] {
+            assert!(matches!(
+                TransactionOutpoint::parse_cursor(Some(cursor)),
+                Err(RpcError::InvalidCursor(_))
+            ));
+        }
+    }
+}
@@ -0,0 +1,258 @@
+//! Access policies of the RPC listeners.
+//!
+//! Every gRPC and wRPC listener of the node enforces a policy, so a single node can serve
+//! a localhost listener with the full API next to a public listener restricted to the
+//! read-only methods and rate limited per client IP.
+
+use karlsen_rpc_core::{api::ops::RpcApiOps, RpcError, RpcResult};
+use parking_lot::Mutex;
+use std::{
+    collections::{HashMap, HashSet},
+    net::IpAddr,
+    time::Instant,
+};
+
+/// Methods which neither alter the state of the node nor disclose its peers
+pub const READ_ONLY_METHODS: [RpcApiOps; 48] = [
+    RpcApiOps::Ping,
+    RpcApiOps::GetServerInfo,
+    RpcApiOps::GetSyncStatus,
+    RpcApiOps::GetCurrentNetwork,
+    RpcApiOps::GetSink,
+    RpcApiOps::GetMempoolEntry,
+    RpcApiOps::GetMempoolEntries,
+    RpcApiOps::GetBlock,
+    RpcApiOps::GetVirtualChainFromBlock,
+    RpcApiOps::GetBlocks,
+    RpcApiOps::GetBlockCount,
+    RpcApiOps::GetBlockDagInfo,
+    RpcApiOps::GetUtxosByAddresses,
+    RpcApiOps::GetBalanceByAddress,
+    RpcApiOps::GetBalancesByAddresses,
+    RpcApiOps::GetBalanceByAddressesAt,
+    RpcApiOps::GetSinkBlueScore,
+    RpcApiOps::GetInfo,
+    RpcApiOps::EstimateNetworkHashesPerSecond,
+    RpcApiOps::GetMempoolEntriesByAddresses,
+    RpcApiOps::GetCoinSupply,
+    RpcApiOps::GetDaaScoreTimestampEstimate,
+    RpcApiOps::GetServerCapabilities,
+    RpcApiOps::GetNetworkInfo,
+    RpcApiOps::TestMempoolAccept,
+    RpcApiOps::GetDifficultyInfo,
+    RpcApiOps::GetDagSlice,
+    RpcApiOps::GetAcceptanceEventsSince,
+    RpcApiOps::DebugScript,
+    RpcApiOps::GetTransactionStatus,
+    RpcApiOps::GetUnconfirmedTxRisk,
+    RpcApiOps::GetBlockFilterHeaders,
+    RpcApiOps::GetBlockFilters,
+    RpcApiOps::GetMinerStats,
+    RpcApiOps::GetAddressCluster,
+    RpcApiOps::NotifyBlockAdded,
+    RpcApiOps::NotifyFinalityConflict,
+    RpcApiOps::NotifyFinalityConflictResolved,
+    RpcApiOps::NotifyUtxosChanged,
+    RpcApiOps::NotifySinkBlueScoreChanged,
+    RpcApiOps::NotifyVirtualDaaScoreChanged,
+    RpcApiOps::NotifyVirtualChainChanged,
+    RpcApiOps::NotifyTransactionStatusChanged,
+    RpcApiOps::NotifyDoubleSpendDetected,
+    RpcApiOps::Subscribe,
+    RpcApiOps::Unsubscribe,
+    RpcApiOps::SubscribeBlockAdded,
+    RpcApiOps::UnsubscribeBlockAdded,
+];
+
+/// Count of client buckets above which the idle ones get dropped
+const MAX_IDLE_BUCKETS: usize = 4096;
+
+struct TokenBucket {
+    tokens: f64,
+    updated: Instant,
+}
+
+/// Rate limiter granting each client IP up to `rate` requests per second, with bursts of
+/// up to `rate` requests
+struct RateLimiter {
+    rate: f64,
+    buckets: Mutex<HashMap<IpAddr, TokenBucket>>,
+}
+
+impl RateLimiter {
+    fn new(requests_per_second: u32) -> Self {
+        Self {
+            rate: requests_per_second as f64,
+            buckets: Default::default(),
+        }
+    }
+
+    fn try_acquire(&self, client: IpAddr, now: Instant) -> bool {
+        let mut buckets = self.buckets.lock();
+        if buckets.len() >= MAX_IDLE_BUCKETS && !buckets.contains_key(&client) {
+            // Buckets which refilled completely carry no state worth keeping
+            let rate = self.rate;
+            buckets.retain(|_, bucket| {
+                bucket.tokens + now.duration_since(bucket.updated).as_secs_f64() * rate < rate
+            });
+        }
+        let bucket = buckets.entry(client).or_insert(TokenBucket {
+            tokens: self.rate,
+            updated: now,
+        });
+        let elapsed = now.duration_since(bucket.updated).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * self.rate).min(self.rate);
+        bucket.updated = now;
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            true
+        } else {
+            false
+        }
+    }
+}
+
+/// Access policy of an RPC listener
+#[derive(Default)]
+pub struct RpcPolicy {
+    /// Methods callable through the listener, all of them if `None`
+    allowed_methods: Option<HashSet<RpcApiOps>>,
+    rate_limiter: Option<RateLimiter>,
+}
+
+impl RpcPolicy {
+    /// Policy granting access to the full API, with no rate limit
+    pub fn unrestricted() -> Self {
+        Self::default()
+    }
+
+    /// Policy of a public listener: the read-only methods along with `extra_methods`, rate
+    /// limited to `requests_per_second` per client IP (0 meaning no limit)
+    pub fn public(requests_per_second: u32, extra_methods: &[RpcApiOps]) -> Self {
+        Self::unrestricted()
+            .with_allowed_methods(READ_ONLY_METHODS.iter().chain(extra_methods).copied())
+            .with_rate_limit(requests_per_second)
+    }
+
+    pub fn with_allowed_methods(mut self, methods: impl IntoIterator<Item = RpcApiOps>) -> Self {
+        self.allowed_methods = Some(methods.into_iter().collect());
+        self
+    }
+
+    /// Limits the requests of every client IP to `requests_per_second`, 0 removing any limit
+    pub fn with_rate_limit(mut self, requests_per_second: u32) -> Self {
+        self.rate_limiter =
+            (requests_per_second > 0).then(|| RateLimiter::new(requests_per_second));
+        self
+    }
+
+    pub fn is_restricted(&self) -> bool {
+        self.allowed_methods.is_some()
+    }
+
+    pub fn is_allowed(&self, op: RpcApiOps) -> bool {
+        self.allowed_methods
+            .as_ref()
+            .map_or(true, |methods| methods.contains(&op))
+    }
+
+    /// Drops the methods and notifications the policy denies from the lists advertised
+    /// by `GetServerCapabilities`
+    pub fn filter_capabilities(&self, methods: &mut Vec<String>, notifications: &mut Vec<String>) {
+        if !self.is_restricted() {
+            return;
+        }
+        methods.retain(|method| parse_method(method).is_some_and(|op| self.is_allowed(op)));
+        notifications.retain(|event| self.is_allowed(notification_method(event)));
+    }
+
+    /// Checks a request of `client` to the method `op` against the policy
+    pub fn check(&self, op: RpcApiOps, client: IpAddr) -> RpcResult<()> {
+        if !self.is_allowed(op) {
+            return Err(RpcError::MethodNotAllowed(op.as_str().to_string()));
+        }
+        match self.rate_limiter.as_ref() {
+            Some(rate_limiter) if !rate_limiter.try_acquire(client, Instant::now()) => {
+                Err(RpcError::RateLimited)
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Parses a method name as written in the RPC API, ignoring the case (ie. `getInfo` or `GetInfo`)
+pub fn parse_method(name: &str) -> Option<RpcApiOps> {
+    RpcApiOps::list()
+        .iter()
+        .find(|op| op.as_str().eq_ignore_ascii_case(name))
+        .copied()
+}
+
+/// Returns the method a subscription to the notifications of `event` is checked as, ie. the
+/// matching `Notify*` method
+pub fn notification_method(event: &str) -> RpcApiOps {
+    parse_method(&format!("Notify{}", event)).unwrap_or(RpcApiOps::Subscribe)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{net::Ipv4Addr, time::Duration};
+
+    #[test]
+    fn test_rpc_policy() {
+        let client = IpAddr::V4(Ipv4Addr::LOCALHOST);
+
+        let policy = RpcPolicy::unrestricted();
+        assert!(!policy.is_restricted());
+        assert!(policy.check(RpcApiOps::Shutdown, client).is_ok());
+
+        let policy = RpcPolicy::public(0, &[RpcApiOps::SubmitTransaction]);
+        assert!(policy.is_restricted());
+        assert!(policy.check(RpcApiOps::GetBlockDagInfo, client).is_ok());
+        assert!(policy.check(RpcApiOps::SubmitTransaction, client).is_ok());
+        assert!(matches!(
+            policy.check(RpcApiOps::Shutdown, client),
+            Err(RpcError::MethodNotAllowed(method)) if method == "Shutdown"
+        ));
+        assert!(policy.check(RpcApiOps::GetPeerAddresses, client).is_err());
+        assert!(policy.check(RpcApiOps::SubscribeDurable, client).is_err());
+
+        assert_eq!(
+            parse_method("submitTransaction"),
+            Some(RpcApiOps::SubmitTransaction)
+        );
+        assert_eq!(parse_method("GetInfo"), Some(RpcApiOps::GetInfo));
+        assert_eq!(parse_method("getNothing"), None);
+
+        let mut methods = vec!["GetInfo".to_string(), "Shutdown".to_string()];
+        let mut notifications = vec![
+            "BlockAdded".to_string(),
+            "PruningPointUtxoSetOverride".to_string(),
+        ];
+        policy.filter_capabilities(&mut methods, &mut notifications);
+        assert_eq!(methods, vec!["GetInfo".to_string()]);
+        assert_eq!(notifications, vec!["BlockAdded".to_string()]);
+    }
+
+    #[test]
+    fn test_rate_limiter() {
+        let limiter = RateLimiter::new(2);
+        let a = IpAddr::V4(Ipv4Addr::new(1, 1, 1, 1));
+        let b = IpAddr::V4(Ipv4Addr::new(2, 2, 2, 2));
+        let now = Instant::now();
+
+        // Burst of `rate` requests, then the bucket is empty
+        assert!(limiter.try_acquire(a, now));
+        assert!(limiter.try_acquire(a, now));
+        assert!(!limiter.try_acquire(a, now));
+
+        // Clients are limited independently
+        assert!(limiter.try_acquire(b, now));
+
+        // Half a second refills one token
+        let later = now + Duration::from_millis(500);
+        assert!(limiter.try_acquire(a, later));
+        assert!(!limiter.try_acquire(a, later));
+    }
+}
@@ -1,20 +1,36 @@
 //! Core server implementation for ClientAPI
 
 use super::collector::{CollectorFromConsensus, CollectorFromIndex};
+use crate::config::RpcCoreConfig;
 use crate::converter::{
-    consensus::ConsensusConverter, index::IndexConverter, protocol::ProtocolConverter,
+    consensus::ConsensusConverter, index::IndexConverter,
+    previous_outputs::PreviousOutputsResolver, protocol::ProtocolConverter,
 };
+use crate::journal::BlockAddedJournal;
+use crate::metadata::{MetadataStore, MetadataUpdate};
+use crate::miner_stats::MinerStats;
+use crate::paging::{AddressOutpoint, CursorKey, ResponseSizeLimit};
 use crate::service::NetworkType::{Mainnet, Testnet};
+use crate::template_history::TemplateHistory;
+use crate::tx_status::TransactionStatusTracker;
+use crate::workers::RpcWorkerPool;
 use async_trait::async_trait;
+use karlsen_acceptancejournal::AcceptanceJournal;
+#[cfg(feature = "analytics")]
+use karlsen_clusterindex::AddressClusterIndex;
 use karlsen_consensus_core::api::counters::ProcessingCounters;
 use karlsen_consensus_core::errors::block::RuleError;
 use karlsen_consensus_core::{
-    block::Block,
-    coinbase::MinerData,
+    block::{Block, BlockTemplate},
+    coinbase::{MinerData, COINBASE_PAYLOAD_HEADER_LEN},
     config::Config,
-    constants::MAX_SOMPI,
+    constants::{MAX_SOMPI, UNACCEPTED_DAA_SCORE},
+    dag_slice::DagSlice,
     network::NetworkType,
-    tx::{Transaction, COINBASE_TRANSACTION_INDEX},
+    tx::{
+        PopulatedTransaction, ScriptPublicKeys, Transaction, TransactionId,
+        COINBASE_TRANSACTION_INDEX,
+    },
 };
 use karlsen_consensus_notify::{
     notifier::ConsensusNotifier,
@@ -24,17 +40,18 @@ use karlsen_consensusmanager::ConsensusManager;
 use karlsen_core::time::unix_now;
 use karlsen_core::{
     core::Core,
-    debug,
+    debug, info,
     karlsend_env::version,
     signals::Shutdown,
     task::service::{AsyncService, AsyncServiceError, AsyncServiceFuture},
     task::tick::TickService,
     trace, warn,
 };
+use karlsen_filterindex::{errors::BlockFilterIndexError, BlockFilterIndex};
 use karlsen_index_core::indexed_utxos::BalanceByScriptPublicKey;
 use karlsen_index_core::{
-    connection::IndexChannelConnection, indexed_utxos::UtxoSetByScriptPublicKey,
-    notification::Notification as IndexNotification, notifier::IndexNotifier,
+    connection::IndexChannelConnection, notification::Notification as IndexNotification,
+    notifier::IndexNotifier,
 };
 use karlsen_mining::model::tx_query::TransactionQuery;
 use karlsen_mining::{manager::MiningManagerProxy, mempool::tx::Orphan};
@@ -46,8 +63,8 @@ use karlsen_notify::{
     connection::ChannelType,
     events::{EventSwitches, EventType, EVENT_TYPE_ARRAY},
     listener::ListenerId,
-    notifier::Notifier,
-    scope::Scope,
+    notifier::{Notifier, Notify},
+    scope::{BlockAddedScope, Scope, VirtualChainChangedScope},
     subscriber::{Subscriber, SubscriptionManager},
 };
 use karlsen_p2p_flows::flow_context::FlowContext;
@@ -55,24 +72,28 @@ use karlsen_p2p_lib::common::ProtocolError;
 use karlsen_perf_monitor::{counters::CountersSnapshot, Monitor as PerfMonitor};
 use karlsen_rpc_core::{
     api::{
-        ops::RPC_API_VERSION,
+        ops::{RpcApiOps, PROTOWIRE_API_VERSION, RPC_API_VERSION},
         rpc::{RpcApi, MAX_SAFE_WINDOW_SIZE},
     },
     model::*,
-    notify::connection::ChannelConnection,
+    notify::{channel::NotificationChannel, connection::ChannelConnection},
     Notification, RpcError, RpcResult,
 };
-use karlsen_txscript::{extract_script_pub_key_address, pay_to_address_script};
-use karlsen_utils::{channel::Channel, triggers::SingleTrigger};
+use karlsen_txscript::{
+    debugger::debug_transaction_input, extract_script_pub_key_address, pay_to_address_script,
+};
+use karlsen_utils::{channel::Channel, mem_budget::memory_budget, triggers::SingleTrigger};
 use karlsen_utils_tower::counters::TowerConnectionCounters;
 use karlsen_utxoindex::api::UtxoIndexProxy;
 use std::{
     collections::HashMap,
+    future::Future,
     iter::once,
-    sync::{atomic::Ordering, Arc},
+    sync::{atomic::Ordering, Arc, Weak},
+    time::Duration,
     vec,
 };
-use tokio::join;
+use tokio::{join, sync::mpsc::unbounded_channel};
 use workflow_rpc::server::WebSocketCounters as WrpcServerCounters;
 
 /// A service implementing the Rpc API at karlsen_rpc_core level.
@@ -93,11 +114,16 @@ use workflow_rpc::server::WebSocketCounters as WrpcServerCounters;
 /// by adding respectively to the registered service a Collector and a
 /// Subscriber.
 pub struct RpcCoreService {
+    this: Weak<Self>,
     consensus_manager: Arc<ConsensusManager>,
     notifier: Arc<Notifier<Notification, ChannelConnection>>,
     mining_manager: MiningManagerProxy,
     flow_context: Arc<FlowContext>,
     utxoindex: Option<UtxoIndexProxy>,
+    blockfilterindex: Option<Arc<BlockFilterIndex>>,
+    acceptance_journal: Option<Arc<AcceptanceJournal>>,
+    address_cluster_index: Option<Arc<AddressClusterIndex>>,
+    metadata_store: Arc<MetadataStore>,
     config: Arc<Config>,
     consensus_converter: Arc<ConsensusConverter>,
     index_converter: Arc<IndexConverter>,
@@ -111,9 +137,68 @@ pub struct RpcCoreService {
     perf_monitor: Arc<PerfMonitor<Arc<TickService>>>,
     p2p_tower_counters: Arc<TowerConnectionCounters>,
     grpc_tower_counters: Arc<TowerConnectionCounters>,
+    block_added_journal: Option<Arc<BlockAddedJournal>>,
+    template_history: TemplateHistory,
+    transaction_status: Arc<TransactionStatusTracker>,
+    miner_stats: MinerStats,
+    /// Pool running the heavy queries, `None` if they run on the shared runtime
+    workers: Option<RpcWorkerPool>,
+    response_size_limit: ResponseSizeLimit,
+    /// Secret keying the binding of the templates to the work of the miners, if configured
+    work_binding_secret: Option<Vec<u8>>,
+    max_previous_outputs_reads: usize,
 }
 
 const RPC_CORE: &str = "rpc-core";
+const RPC_CORE_BLOCK_JOURNAL: &str = "rpc-core-block-journal";
+const RPC_CORE_TX_STATUS: &str = "rpc-core-tx-status";
+
+/// Maximum count of RPC-submitted transactions whose status is tracked
+const MAX_TRACKED_TRANSACTIONS: usize = 10_000;
+
+/// Upper bound of the GetBlocks batch size when no full transactions are requested
+const MAX_GET_BLOCKS_BATCH_SIZE: usize = 4_000;
+
+/// Maximum count of chain blocks returned by a single GetDifficultyInfo call
+const MAX_DIFFICULTY_INFO_CHAIN_BLOCKS: usize = 1_000;
+
+/// Maximum count of chain blocks a GetBalanceByAddressesAt call rolls the balances back along
+const MAX_BALANCE_ROLLBACK_CHAIN_BLOCKS: usize = 20_000;
+
+/// Attempts at matching a balance rollback with the state of the UTXO index
+const BALANCE_SNAPSHOT_ATTEMPTS: usize = 10;
+
+/// Delay between the attempts at matching a balance rollback with the state of the UTXO index
+const BALANCE_SNAPSHOT_RETRY_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Maximum count of filter headers returned by a single GetBlockFilterHeaders call
+const MAX_BLOCK_FILTER_HEADERS: usize = 2_000;
+
+/// Maximum count of filters returned by a single GetBlockFilters call
+const MAX_BLOCK_FILTERS: usize = 1_000;
+
+/// Maximum count of addresses returned by a single GetAddressCluster call, bounding the members
+/// read from the index while its store is locked
+#[cfg(feature = "analytics")]
+const MAX_ADDRESS_CLUSTER_MEMBERS: usize = 1_000;
+
+/// Stands in for the address cluster index when the analytics feature is disabled, so the
+/// index can never be provided and `GetAddressCluster` reports it as unavailable
+#[cfg(not(feature = "analytics"))]
+pub enum AddressClusterIndex {}
+
+/// Maximum count of blocks requested by a single GetDagSlice call
+const MAX_DAG_SLICE_BLOCKS: usize = 10_000;
+
+/// Maximum count of events returned by a single GetAcceptanceEventsSince call
+const MAX_ACCEPTANCE_EVENTS: usize = 1_000;
+
+/// Count of entries read at once while assembling a page of UTXOs or mempool entries, the reads
+/// stopping as soon as the page is full
+const PAGE_READ_CHUNK_SIZE: usize = 1_000;
+
+/// Count of issued templates retained as bases of the GetTemplateDiff calls
+const TEMPLATE_HISTORY_SIZE: usize = 256;
 
 impl RpcCoreService {
     pub const IDENT: &'static str = "rpc-core-service";
@@ -127,7 +212,12 @@ impl RpcCoreService {
         flow_context: Arc<FlowContext>,
         subscription_context: SubscriptionContext,
         utxoindex: Option<UtxoIndexProxy>,
+        blockfilterindex: Option<Arc<BlockFilterIndex>>,
+        acceptance_journal: Option<Arc<AcceptanceJournal>>,
+        address_cluster_index: Option<Arc<AddressClusterIndex>>,
+        metadata_store: Arc<MetadataStore>,
         config: Arc<Config>,
+        rpc_config: RpcCoreConfig,
         core: Arc<Core>,
         processing_counters: Arc<ProcessingCounters>,
         wrpc_borsh_counters: Arc<WrpcServerCounters>,
@@ -135,7 +225,7 @@ impl RpcCoreService {
         perf_monitor: Arc<PerfMonitor<Arc<TickService>>>,
         p2p_tower_counters: Arc<TowerConnectionCounters>,
         grpc_tower_counters: Arc<TowerConnectionCounters>,
-    ) -> Self {
+    ) -> Arc<Self> {
         // This notifier UTXOs subscription granularity to index-processor or consensus notifier
         let policies = match index_notifier {
             Some(_) => MutationPolicies::new(UtxosChangedMutationPolicy::AddressSet),
@@ -157,6 +247,8 @@ impl RpcCoreService {
         let mut consensus_events: EventSwitches = EVENT_TYPE_ARRAY[..].into();
         consensus_events[EventType::UtxosChanged] = false;
         consensus_events[EventType::PruningPointUtxoSetOverride] = index_notifier.is_none();
+        consensus_events[EventType::TransactionStatusChanged] = false;
+        consensus_events[EventType::DoubleSpendDetected] = false;
         let consensus_converter = Arc::new(ConsensusConverter::new(
             consensus_manager.clone(),
             config.clone(),
@@ -211,6 +303,21 @@ impl RpcCoreService {
             subscribers.push(index_subscriber);
         }
 
+        // Block added journal
+        let block_added_journal = match config.block_added_journal_size {
+            0 => None,
+            size => Some(Arc::new(BlockAddedJournal::new(size))),
+        };
+
+        // Heavy queries get their own threads so they do not compete with the P2P flows and
+        // the consensus thread-pools beyond the configured count
+        let workers = match rpc_config.workers {
+            0 => None,
+            workers => Some(RpcWorkerPool::new(workers)),
+        };
+
+        let miner_stats = MinerStats::new(config.target_time_per_block, rpc_config.power_cost);
+
         // Protocol converter
         let protocol_converter = Arc::new(ProtocolConverter::new(flow_context.clone()));
 
@@ -225,12 +332,19 @@ impl RpcCoreService {
             policies,
         ));
 
-        Self {
+        let response_size_limit = ResponseSizeLimit::new(rpc_config.max_response_size);
+
+        Arc::new_cyclic(|this| Self {
+            this: this.clone(),
             consensus_manager,
             notifier,
             mining_manager,
             flow_context,
             utxoindex,
+            blockfilterindex,
+            acceptance_journal,
+            address_cluster_index,
+            metadata_store,
             config,
             consensus_converter,
             index_converter,
@@ -244,11 +358,172 @@ impl RpcCoreService {
             perf_monitor,
             p2p_tower_counters,
             grpc_tower_counters,
-        }
+            block_added_journal,
+            template_history: TemplateHistory::new(TEMPLATE_HISTORY_SIZE),
+            transaction_status: Arc::new(TransactionStatusTracker::new(MAX_TRACKED_TRANSACTIONS)),
+            miner_stats,
+            workers,
+            response_size_limit,
+            work_binding_secret: rpc_config.work_binding_secret,
+            max_previous_outputs_reads: rpc_config.max_previous_outputs_reads,
+        })
     }
 
     pub fn start_impl(&self) {
         self.notifier().start();
+        if let Some(journal) = self.block_added_journal.clone() {
+            self.start_block_added_journal(journal);
+        }
+        self.start_transaction_status_tracking();
+        self.start_double_spend_alerts();
+    }
+
+    /// Registers an internal listener feeding the block added journal
+    fn start_block_added_journal(&self, journal: Arc<BlockAddedJournal>) {
+        let channel = NotificationChannel::default();
+        let listener_id = self.notifier.register_new_listener(
+            ChannelConnection::new(
+                RPC_CORE_BLOCK_JOURNAL,
+                channel.sender(),
+                ChannelType::Closable,
+            ),
+            ListenerLifespan::Dynamic,
+        );
+        if let Err(err) = self
+            .notifier
+            .try_start_notify(listener_id, Scope::BlockAdded(BlockAddedScope {}))
+        {
+            warn!(
+                "{} could not subscribe to block added notifications: {}",
+                Self::IDENT,
+                err
+            );
+            return;
+        }
+        let receiver = channel.receiver();
+        tokio::spawn(async move {
+            // The channel gets closed when the notifier stops
+            while let Ok(notification) = receiver.recv().await {
+                if let Notification::BlockAdded(notification) = notification {
+                    journal.push(notification.block);
+                }
+            }
+            trace!("{} block added journal feed exited", Self::IDENT);
+        });
+    }
+
+    /// Registers an internal listener following the acceptance of the tracked transactions
+    /// and notifying their status changes
+    fn start_transaction_status_tracking(&self) {
+        let channel = NotificationChannel::default();
+        let listener_id = self.notifier.register_new_listener(
+            ChannelConnection::new(RPC_CORE_TX_STATUS, channel.sender(), ChannelType::Closable),
+            ListenerLifespan::Dynamic,
+        );
+        if let Err(err) = self.notifier.try_start_notify(
+            listener_id,
+            Scope::VirtualChainChanged(VirtualChainChangedScope::new(true)),
+        ) {
+            warn!(
+                "{} could not subscribe to virtual chain changed notifications: {}",
+                Self::IDENT,
+                err
+            );
+            return;
+        }
+        let receiver = channel.receiver();
+        let notifier = self.notifier.clone();
+        let tracker = self.transaction_status.clone();
+        tokio::spawn(async move {
+            // The channel gets closed when the notifier stops
+            while let Ok(notification) = receiver.recv().await {
+                let Notification::VirtualChainChanged(notification) = notification else {
+                    continue;
+                };
+                if tracker.is_empty() {
+                    continue;
+                }
+                let changes = tracker.apply_chain_changed(
+                    &notification.removed_chain_block_hashes,
+                    &notification.accepted_transaction_ids,
+                );
+                for (transaction_id, accepting_block_hash) in changes {
+                    // The notifier may be closing due to a global shutdown, hence the error is ignored
+                    let _ = notifier.notify(Notification::TransactionStatusChanged(
+                        TransactionStatusChangedNotification {
+                            transaction_id,
+                            accepting_block_hash,
+                        },
+                    ));
+                }
+            }
+            trace!("{} transaction status feed exited", Self::IDENT);
+        });
+    }
+
+    /// Subscribes to the double spends detected by the mining manager and notifies them
+    fn start_double_spend_alerts(&self) {
+        let (sender, mut receiver) = unbounded_channel();
+        self.mining_manager.set_double_spend_sender(sender);
+        let notifier = self.notifier.clone();
+        tokio::spawn(async move {
+            while let Some(double_spend) = receiver.recv().await {
+                let notification = DoubleSpendDetectedNotification {
+                    transaction_id: double_spend.transaction_id,
+                    conflicting_transaction_id: double_spend.conflicting_transaction_id,
+                    outpoints: double_spend.outpoints,
+                    accepted_transaction_id: double_spend.accepted_transaction_id,
+                };
+                // Stop once the notifier is closed, which disables the detection of double spends
+                if notifier
+                    .notify(Notification::DoubleSpendDetected(notification))
+                    .is_err()
+                {
+                    break;
+                }
+            }
+            trace!("{} double spend feed exited", Self::IDENT);
+        });
+    }
+
+    /// Runs a query scanning indexes or DAG ranges on the worker pool
+    async fn run_heavy<T, F, Fut>(&self, query: F) -> RpcResult<T>
+    where
+        F: FnOnce(Arc<Self>) -> Fut,
+        Fut: Future<Output = RpcResult<T>> + Send + 'static,
+        T: Send + 'static,
+    {
+        let this = self
+            .this
+            .upgrade()
+            .ok_or_else(|| RpcError::General("the RPC core service is shut down".to_owned()))?;
+        match self.workers.as_ref() {
+            Some(workers) => workers.run(query(this)).await,
+            None => query(this).await,
+        }
+    }
+
+    /// Returns the block of a `GetBlock` request
+    async fn get_block_response(&self, request: GetBlockRequest) -> RpcResult<GetBlockResponse> {
+        let session = self.consensus_manager.consensus().session().await;
+        let block = session
+            .async_get_block_even_if_header_only(request.hash)
+            .await?;
+        let mut resolver = request
+            .resolve_previous_outputs
+            .then(|| PreviousOutputsResolver::new(self.max_previous_outputs_reads));
+        Ok(GetBlockResponse {
+            block: self
+                .consensus_converter
+                .get_block(
+                    &session,
+                    &block,
+                    request.include_transactions,
+                    request.include_transactions,
+                    resolver.as_mut(),
+                )
+                .await?,
+        })
     }
 
     pub async fn join(&self) -> RpcResult<()> {
@@ -271,16 +546,11 @@ impl RpcCoreService {
         self.core_shutdown_request.listener.clone()
     }
 
-    async fn get_utxo_set_by_script_public_key<'a>(
-        &self,
-        addresses: impl Iterator<Item = &'a RpcAddress>,
-    ) -> UtxoSetByScriptPublicKey {
-        self.utxoindex
+    /// Journal feeding the block added streams, if enabled
+    pub fn block_added_journal(&self) -> RpcResult<Arc<BlockAddedJournal>> {
+        self.block_added_journal
             .clone()
-            .unwrap()
-            .get_utxos_by_script_public_keys(addresses.map(pay_to_address_script).collect())
-            .await
-            .unwrap_or_default()
+            .ok_or(RpcError::NoBlockAddedJournal)
     }
 
     async fn get_balance_by_script_public_key<'a>(
@@ -295,12 +565,98 @@ impl RpcCoreService {
             .unwrap_or_default()
     }
 
+    /// Builds a block template paying to `pay_address`, along with whether the node thinks it
+    /// is synced. The template is bound to the work of `worker_id` if a work binding secret is
+    /// configured, which `bind_work` requires.
+    async fn build_block_template(
+        &self,
+        pay_address: &RpcAddress,
+        extra_data: &RpcExtraData,
+        worker_id: Option<u16>,
+        bind_work: bool,
+    ) -> RpcResult<(BlockTemplate, bool)> {
+        if *self.config.net == NetworkType::Mainnet && !self.config.enable_mainnet_mining {
+            return Err(RpcError::General(
+                "Mining on mainnet is not supported for initial Rust versions".to_owned(),
+            ));
+        }
+        let work_binding = match self.work_binding_secret.as_deref() {
+            Some(secret) => Some((RpcWorkBinding::new(worker_id, rand::random()), secret)),
+            None if bind_work => return Err(RpcError::NoWorkBindingSecret),
+            None => None,
+        };
+
+        // Make sure the pay address prefix matches the config network type
+        self.check_addresses_network(once(pay_address))?;
+
+        // Build block template
+        let script_public_key = karlsen_txscript::pay_to_address_script(pay_address);
+        let mut extra_data = coinbase_extra_data(self.config.miner_tag.as_deref(), extra_data);
+        if let Some((work_binding, secret)) = work_binding {
+            extra_data.extend(work_binding.encode(&script_public_key, secret));
+        }
+
+        // Check the coinbase payload length before building, so a template is never built with
+        // a coinbase the consensus rejects
+        if COINBASE_PAYLOAD_HEADER_LEN + script_public_key.script().len() + extra_data.len()
+            > self.config.max_coinbase_payload_len
+        {
+            return Err(RpcError::CoinbasePayloadLengthAboveMax(
+                self.config.max_coinbase_payload_len,
+            ));
+        }
+        let miner_data: MinerData = MinerData::new(script_public_key, extra_data);
+        let session = self.consensus_manager.consensus().unguarded_session();
+        let block_template = self
+            .mining_manager
+            .clone()
+            .get_block_template(&session, miner_data)
+            .await?;
+
+        self.miner_stats.record_template(
+            block_template.block.header.bits,
+            block_template.block.transactions[COINBASE_TRANSACTION_INDEX]
+                .outputs
+                .iter()
+                .map(|output| output.value)
+                .sum(),
+            work_binding.is_some(),
+        );
+
+        let is_nearly_synced = self.config.is_nearly_synced(
+            block_template.selected_parent_timestamp,
+            block_template.selected_parent_daa_score,
+        );
+        Ok((
+            block_template,
+            self.has_sufficient_peer_connectivity() && is_nearly_synced,
+        ))
+    }
+
     fn has_sufficient_peer_connectivity(&self) -> bool {
         // Other network types can be used in an isolated environment without peers
         !matches!(self.flow_context.config.net.network_type, Mainnet | Testnet)
             || self.flow_context.hub().has_peers()
     }
 
+    /// Rejects any address not belonging to the network of this node.
+    ///
+    /// Script public keys carry no network information so a foreign address would
+    /// otherwise silently resolve to the same UTXOs, balances or outputs as its local twin.
+    fn check_addresses_network<'a>(
+        &self,
+        addresses: impl IntoIterator<Item = &'a RpcAddress>,
+    ) -> RpcResult<()> {
+        let prefix = self.config.prefix();
+        match addresses.into_iter().find(|x| x.prefix != prefix) {
+            Some(address) => Err(RpcError::AddressNetworkMismatch(
+                address.to_string(),
+                self.config.net,
+            )),
+            None => Ok(()),
+        }
+    }
+
     fn extract_tx_query(
         &self,
         filter_transaction_pool: bool,
@@ -349,6 +705,7 @@ impl RpcApi for RpcCoreService {
         }
         let block = try_block?;
         let hash = block.hash();
+        let bits = block.header.bits;
 
         if !request.allow_non_daa_blocks {
             let virtual_daa_score = session.get_virtual_daa_score();
@@ -367,15 +724,47 @@ impl RpcApi for RpcCoreService {
             }
         }
 
+        // With work binding, every template the node issued is bound to the script its coinbase
+        // pays, so a block without a valid binding of its script was redirected on its way from
+        // the miner, or its binding stripped
+        let work_binding = match (
+            self.work_binding_secret.as_deref(),
+            block.transactions.get(COINBASE_TRANSACTION_INDEX),
+        ) {
+            (Some(secret), Some(coinbase)) => RpcWorkBinding::status(&coinbase.payload, secret),
+            (Some(_), None) => RpcWorkBindingStatus::Mismatch,
+            (None, _) => RpcWorkBindingStatus::Unbound,
+        };
+        if self.work_binding_secret.is_some()
+            && !matches!(work_binding, RpcWorkBindingStatus::Bound(_))
+        {
+            warn!(
+                "The RPC submitted block {} carries no valid binding of the address it pays, rejecting it",
+                hash
+            );
+            self.miner_stats.record_binding_mismatch();
+            return Ok(SubmitBlockResponse {
+                report: SubmitBlockReport::Reject(SubmitBlockRejectReason::BlockInvalid),
+            });
+        }
+
         trace!("incoming SubmitBlockRequest for block {}", hash);
-        match self
+        let result = self
             .flow_context
             .submit_rpc_block(&session, block.clone())
-            .await
-        {
-            Ok(_) => Ok(SubmitBlockResponse {
-                report: SubmitBlockReport::Success,
-            }),
+            .await;
+        self.miner_stats.record_submitted_block(
+            bits,
+            result.is_ok(),
+            matches!(work_binding, RpcWorkBindingStatus::Bound(_)),
+        );
+        match result {
+            Ok(_) => {
+                info!("Miner stats: {}", self.miner_stats.snapshot());
+                Ok(SubmitBlockResponse {
+                    report: SubmitBlockReport::Success,
+                })
+            }
             Err(ProtocolError::RuleError(RuleError::BadMerkleRoot(h1, h2))) => {
                 warn!(
                     "The RPC submitted block triggered a {} error: {}. 
@@ -407,141 +796,220 @@ NOTE: This error usually indicates an RPC conversion error between the node and
         request: GetBlockTemplateRequest,
     ) -> RpcResult<GetBlockTemplateResponse> {
         trace!("incoming GetBlockTemplate request");
-
-        if *self.config.net == NetworkType::Mainnet && !self.config.enable_mainnet_mining {
-            return Err(RpcError::General(
-                "Mining on mainnet is not supported for initial Rust versions".to_owned(),
-            ));
-        }
-
-        // Make sure the pay address prefix matches the config network type
-        if request.pay_address.prefix != self.config.prefix() {
-            return Err(karlsen_addresses::AddressError::InvalidPrefix(
-                request.pay_address.prefix.to_string(),
-            ))?;
-        }
-
-        // Build block template
-        let script_public_key = karlsen_txscript::pay_to_address_script(&request.pay_address);
-        let extra_data = version()
-            .as_bytes()
-            .iter()
-            .chain(once(&(b'/')))
-            .chain(&request.extra_data)
-            .cloned()
-            .collect::<Vec<_>>();
-        let miner_data: MinerData = MinerData::new(script_public_key, extra_data);
-        let session = self.consensus_manager.consensus().unguarded_session();
-        let block_template = self
-            .mining_manager
-            .clone()
-            .get_block_template(&session, miner_data)
+        let (block_template, is_synced) = self
+            .build_block_template(
+                &request.pay_address,
+                &request.extra_data,
+                request.worker_id,
+                request.bind_work,
+            )
             .await?;
-
-        // Check coinbase tx payload length
-        if block_template.block.transactions[COINBASE_TRANSACTION_INDEX]
-            .payload
-            .len()
-            > self.config.max_coinbase_payload_len
-        {
-            return Err(RpcError::CoinbasePayloadLengthAboveMax(
-                self.config.max_coinbase_payload_len,
-            ));
+        if let Some(hashrate) = request.reported_hashrate {
+            self.miner_stats.record_reported_hashrate(
+                request.pay_address.to_string(),
+                request.worker_id,
+                hashrate,
+            );
         }
-
-        let is_nearly_synced = self.config.is_nearly_synced(
-            block_template.selected_parent_timestamp,
-            block_template.selected_parent_daa_score,
-        );
+        let (nonce_start, nonce_stride) = request.nonce_partition();
         Ok(GetBlockTemplateResponse {
             block: (&block_template.block).into(),
-            is_synced: self.has_sufficient_peer_connectivity() && is_nearly_synced,
+            is_synced,
+            nonce_start,
+            nonce_stride,
         })
     }
 
-    async fn get_block_call(&self, request: GetBlockRequest) -> RpcResult<GetBlockResponse> {
-        // TODO: test
-        let session = self.consensus_manager.consensus().session().await;
-        let block = session
-            .async_get_block_even_if_header_only(request.hash)
+    async fn get_template_diff_call(
+        &self,
+        request: GetTemplateDiffRequest,
+    ) -> RpcResult<GetTemplateDiffResponse> {
+        trace!("incoming GetTemplateDiff request");
+        let (block_template, is_synced) = self
+            .build_block_template(&request.pay_address, &request.extra_data, None, false)
             .await?;
-        Ok(GetBlockResponse {
-            block: self
-                .consensus_converter
-                .get_block(
-                    &session,
-                    &block,
-                    request.include_transactions,
-                    request.include_transactions,
-                )
-                .await?,
+        let block = block_template.block;
+        let transactions = &block.transactions[COINBASE_TRANSACTION_INDEX + 1..];
+        let transaction_ids: Vec<RpcTransactionId> =
+            transactions.iter().map(|tx| tx.id()).collect();
+        let (template_id, diff) = self
+            .template_history
+            .issue(transaction_ids.clone(), request.since_template_id);
+        Ok(GetTemplateDiffResponse {
+            template_id,
+            base_template_id: diff.base_template_id,
+            header: block.header.clone(),
+            coinbase_transaction: (&block.transactions[COINBASE_TRANSACTION_INDEX]).into(),
+            transaction_ids,
+            added_transactions: diff
+                .added
+                .into_iter()
+                .map(|i| (&transactions[i]).into())
+                .collect(),
+            removed_transaction_ids: diff.removed,
+            is_synced,
         })
     }
 
-    async fn get_blocks_call(&self, request: GetBlocksRequest) -> RpcResult<GetBlocksResponse> {
-        // Validate that user didn't set include_transactions without setting include_blocks
-        if !request.include_blocks && request.include_transactions {
-            return Err(RpcError::InvalidGetBlocksRequest);
+    async fn get_metadata_call(
+        &self,
+        request: GetMetadataRequest,
+    ) -> RpcResult<GetMetadataResponse> {
+        if !self.config.unsafe_rpc {
+            warn!("GetMetadata RPC command called while node in safe RPC mode -- ignoring.");
+            return Err(RpcError::UnavailableInSafeMode);
         }
+        let metadata_store = self.metadata_store.clone();
+        let entries = tokio::task::spawn_blocking(move || {
+            metadata_store.get(&request.namespace, &request.keys)
+        })
+        .await
+        .map_err(|err| RpcError::General(err.to_string()))??;
+        Ok(GetMetadataResponse::new(entries))
+    }
 
-        let session = self.consensus_manager.consensus().session().await;
-
-        // If low_hash is empty - use genesis instead.
-        let low_hash = match request.low_hash {
-            Some(low_hash) => {
-                // Make sure low_hash points to an existing and valid block
-                session.async_get_ghostdag_data(low_hash).await?;
-                low_hash
-            }
-            None => self.config.genesis.hash,
-        };
-
-        // Get hashes between low_hash and sink
-        let sink_hash = session.async_get_sink().await;
+    async fn update_metadata_call(
+        &self,
+        request: UpdateMetadataRequest,
+    ) -> RpcResult<UpdateMetadataResponse> {
+        if !self.config.unsafe_rpc {
+            warn!("UpdateMetadata RPC command called while node in safe RPC mode -- ignoring.");
+            return Err(RpcError::UnavailableInSafeMode);
+        }
+        let metadata_store = self.metadata_store.clone();
+        tokio::task::spawn_blocking(move || {
+            metadata_store.update(
+                &request.namespace,
+                MetadataUpdate {
+                    expected_entries: request.expected_entries,
+                    expected_absent_keys: request.expected_absent_keys,
+                    set_entries: request.set_entries,
+                    delete_keys: request.delete_keys,
+                },
+            )
+        })
+        .await
+        .map_err(|err| RpcError::General(err.to_string()))??;
+        Ok(UpdateMetadataResponse {})
+    }
 
-        // We use +1 because low_hash is also returned
-        // max_blocks MUST be >= mergeset_size_limit + 1
-        let max_blocks = self.config.mergeset_size_limit as usize + 1;
-        let (block_hashes, high_hash) = session
-            .async_get_hashes_between(low_hash, sink_hash, max_blocks)
-            .await?;
+    async fn get_miner_stats_call(
+        &self,
+        _request: GetMinerStatsRequest,
+    ) -> RpcResult<GetMinerStatsResponse> {
+        let stats = self.miner_stats.snapshot();
+        Ok(GetMinerStatsResponse {
+            accepted_blocks: stats.accepted_blocks,
+            rejected_blocks: stats.rejected_blocks,
+            hashrate: stats.hashrate,
+            network_hashrate: stats.network_hashrate,
+            network_share: stats.network_share,
+            expected_seconds_to_block: stats.expected_time_to_block.map(|time| time.as_secs_f64()),
+            block_reward: stats.block_reward,
+            expected_daily_reward: stats.expected_daily_reward,
+            daily_power_cost: stats.daily_power_cost,
+            cost_per_kls: stats.cost_per_kls,
+            bound_templates: stats.bound_templates,
+            bound_blocks: stats.bound_blocks,
+            binding_mismatches: stats.binding_mismatches,
+        })
+    }
 
-        // If the high hash is equal to sink it means get_hashes_between didn't skip any hashes, and
-        // there's space to add the sink anticone, otherwise we cannot add the anticone because
-        // there's no guarantee that all of the anticone root ancestors will be present.
-        let sink_anticone = if high_hash == sink_hash {
-            session.async_get_anticone(sink_hash).await?
+    async fn get_block_call(&self, request: GetBlockRequest) -> RpcResult<GetBlockResponse> {
+        // TODO: test
+        // Resolving the previous outputs may read many blocks of the DAG
+        if request.include_transactions && request.resolve_previous_outputs {
+            self.run_heavy(move |this| async move { this.get_block_response(request).await })
+                .await
         } else {
-            vec![]
-        };
-        // Prepend low hash to make it inclusive and append the sink anticone
-        let block_hashes = once(low_hash)
-            .chain(block_hashes)
-            .chain(sink_anticone)
-            .collect::<Vec<_>>();
-        let blocks = if request.include_blocks {
-            let mut blocks = Vec::with_capacity(block_hashes.len());
-            for hash in block_hashes.iter().copied() {
-                let block = session.async_get_block_even_if_header_only(hash).await?;
-                let rpc_block = self
-                    .consensus_converter
-                    .get_block(
-                        &session,
-                        &block,
-                        request.include_transactions,
-                        request.include_transactions,
-                    )
-                    .await?;
-                blocks.push(rpc_block)
+            self.get_block_response(request).await
+        }
+    }
+
+    async fn get_blocks_call(&self, request: GetBlocksRequest) -> RpcResult<GetBlocksResponse> {
+        self.run_heavy(move |this| async move {
+            // Validate that user didn't set include_transactions or verbosity without setting include_blocks
+            if !request.include_blocks
+                && (request.include_transactions || request.verbosity.is_some())
+            {
+                return Err(RpcError::InvalidGetBlocksRequest);
             }
-            blocks
-        } else {
-            Vec::new()
-        };
-        Ok(GetBlocksResponse {
-            block_hashes,
-            blocks,
+            let verbosity = request.block_verbosity();
+
+            let session = this.consensus_manager.consensus().session().await;
+
+            // If low_hash is empty - use genesis instead.
+            let low_hash = match request.low_hash {
+                Some(low_hash) => {
+                    // Make sure low_hash points to an existing and valid block
+                    session.async_get_ghostdag_data(low_hash).await?;
+                    low_hash
+                }
+                None => this.config.genesis.hash,
+            };
+
+            // Get hashes between low_hash and sink
+            let sink_hash = session.async_get_sink().await;
+
+            // We use +1 because low_hash is also returned
+            // max_blocks MUST be >= mergeset_size_limit + 1
+            let min_batch_size = this.config.mergeset_size_limit as usize + 1;
+            // Full transactions are kept to the minimal batch size to bound the response size
+            let max_batch_size = match verbosity {
+                Some(RpcBlockVerbosity::High) => min_batch_size,
+                _ => MAX_GET_BLOCKS_BATCH_SIZE.max(min_batch_size),
+            };
+            let max_blocks = (request.batch_size as usize).clamp(min_batch_size, max_batch_size);
+            let (block_hashes, high_hash) = session
+                .async_get_hashes_between(low_hash, sink_hash, max_blocks)
+                .await?;
+
+            // If the high hash is equal to sink it means get_hashes_between didn't skip any hashes, and
+            // there's space to add the sink anticone, otherwise we cannot add the anticone because
+            // there's no guarantee that all of the anticone root ancestors will be present.
+            let (sink_anticone, next_low_hash) = if high_hash == sink_hash {
+                (session.async_get_anticone(sink_hash).await?, None)
+            } else {
+                (vec![], Some(high_hash))
+            };
+            // Prepend low hash to make it inclusive and append the sink anticone
+            let block_hashes = once(low_hash)
+                .chain(block_hashes)
+                .chain(sink_anticone)
+                .collect::<Vec<_>>();
+            let blocks = if let Some(verbosity) = verbosity {
+                let include_transactions = verbosity.includes_transactions();
+                // A single budget bounds the reads resolving the previous outputs of the whole range
+                let mut resolver = request
+                    .resolve_previous_outputs
+                    .then(|| PreviousOutputsResolver::new(this.max_previous_outputs_reads));
+                let mut blocks = Vec::with_capacity(block_hashes.len());
+                for hash in block_hashes.iter().copied() {
+                    let block = session.async_get_block_even_if_header_only(hash).await?;
+                    let mut rpc_block = this
+                        .consensus_converter
+                        .get_block(
+                            &session,
+                            &block,
+                            include_transactions,
+                            include_transactions,
+                            resolver.as_mut(),
+                        )
+                        .await?;
+                    if !verbosity.includes_transaction_ids() {
+                        if let Some(verbose_data) = rpc_block.verbose_data.as_mut() {
+                            verbose_data.transaction_ids.clear();
+                        }
+                    }
+                    blocks.push(rpc_block)
+                }
+                blocks
+            } else {
+                Vec::new()
+            };
+            Ok(GetBlocksResponse::new(block_hashes, blocks, next_low_hash))
         })
+        .await
     }
 
     async fn get_info_call(&self, _request: GetInfoRequest) -> RpcResult<GetInfoResponse> {
@@ -551,6 +1019,7 @@ NOTE: This error usually indicates an RPC conversion error between the node and
             .unguarded_session()
             .async_is_nearly_synced()
             .await;
+        let clock_skew = self.flow_context.clock_skew();
         Ok(GetInfoResponse {
             p2p_id: self.flow_context.node_id.to_string(),
             mempool_size: self
@@ -561,6 +1030,9 @@ NOTE: This error usually indicates an RPC conversion error between the node and
             is_synced: self.has_sufficient_peer_connectivity() && is_nearly_synced,
             has_notify_command: true,
             has_message_id: true,
+            peer_clock_offset: clock_skew.peer_offset.unwrap_or_default(),
+            header_clock_offset: clock_skew.header_offset.unwrap_or_default(),
+            is_clock_skewed: self.flow_context.is_clock_skewed(&clock_skew),
         })
     }
 
@@ -580,8 +1052,11 @@ NOTE: This error usually indicates an RPC conversion error between the node and
         };
         let session = self.consensus_manager.consensus().unguarded_session();
         Ok(GetMempoolEntryResponse::new(
-            self.consensus_converter
-                .get_mempool_entry(&session, &transaction),
+            self.consensus_converter.get_mempool_entry(
+                &session,
+                &transaction,
+                request.resolve_previous_outputs,
+            ),
         ))
     }
 
@@ -591,56 +1066,74 @@ NOTE: This error usually indicates an RPC conversion error between the node and
     ) -> RpcResult<GetMempoolEntriesResponse> {
         let query =
             self.extract_tx_query(request.filter_transaction_pool, request.include_orphan_pool)?;
+        let mut after = TransactionId::parse_cursor(request.cursor.as_deref())?;
         let session = self.consensus_manager.consensus().unguarded_session();
-        let (transactions, orphans) = self
-            .mining_manager
-            .clone()
-            .get_all_transactions(query)
-            .await;
-        let mempool_entries = transactions
-            .iter()
-            .chain(orphans.iter())
-            .map(|transaction| {
-                self.consensus_converter
-                    .get_mempool_entry(&session, transaction)
-            })
-            .collect();
-        Ok(GetMempoolEntriesResponse::new(mempool_entries))
+        // The mempool is read by chunks following the cursor, and entries are only converted
+        // up to the size limit
+        let mut page = self.response_size_limit.page(request.paginate);
+        'chunks: loop {
+            let transactions = self
+                .mining_manager
+                .clone()
+                .get_transactions_after(query, after, PAGE_READ_CHUNK_SIZE)
+                .await;
+            for transaction in transactions.iter() {
+                let entry = self.consensus_converter.get_mempool_entry(
+                    &session,
+                    transaction,
+                    request.resolve_previous_outputs,
+                );
+                if !page.push(transaction.id(), entry)? {
+                    break 'chunks;
+                }
+            }
+            if transactions.len() < PAGE_READ_CHUNK_SIZE {
+                break;
+            }
+            after = transactions.last().map(|transaction| transaction.id());
+        }
+        let page = page.build();
+        Ok(GetMempoolEntriesResponse::new(page.entries).with_next_cursor(page.next_cursor))
     }
 
     async fn get_mempool_entries_by_addresses_call(
         &self,
         request: GetMempoolEntriesByAddressesRequest,
     ) -> RpcResult<GetMempoolEntriesByAddressesResponse> {
-        let query =
-            self.extract_tx_query(request.filter_transaction_pool, request.include_orphan_pool)?;
-        let session = self.consensus_manager.consensus().unguarded_session();
-        let script_public_keys = request
-            .addresses
-            .iter()
-            .map(pay_to_address_script)
-            .collect();
-        let grouped_txs = self
-            .mining_manager
-            .clone()
-            .get_transactions_by_addresses(script_public_keys, query)
-            .await;
-        let mempool_entries = grouped_txs
-            .owners
-            .iter()
-            .map(|(script_public_key, owner_transactions)| {
-                let address =
-                    extract_script_pub_key_address(script_public_key, self.config.prefix())
-                        .expect("script public key is convertible into an address");
-                self.consensus_converter.get_mempool_entries_by_address(
-                    &session,
-                    address,
-                    owner_transactions,
-                    &grouped_txs.transactions,
-                )
-            })
-            .collect();
-        Ok(GetMempoolEntriesByAddressesResponse::new(mempool_entries))
+        self.run_heavy(move |this| async move {
+            this.check_addresses_network(request.addresses.iter())?;
+            let query = this
+                .extract_tx_query(request.filter_transaction_pool, request.include_orphan_pool)?;
+            let session = this.consensus_manager.consensus().unguarded_session();
+            let script_public_keys = request
+                .addresses
+                .iter()
+                .map(pay_to_address_script)
+                .collect();
+            let grouped_txs = this
+                .mining_manager
+                .clone()
+                .get_transactions_by_addresses(script_public_keys, query)
+                .await;
+            let mempool_entries = grouped_txs
+                .owners
+                .iter()
+                .map(|(script_public_key, owner_transactions)| {
+                    let address =
+                        extract_script_pub_key_address(script_public_key, this.config.prefix())
+                            .expect("script public key is convertible into an address");
+                    this.consensus_converter.get_mempool_entries_by_address(
+                        &session,
+                        address,
+                        owner_transactions,
+                        &grouped_txs.transactions,
+                        request.resolve_previous_outputs,
+                    )
+                })
+                .collect();
+            Ok(GetMempoolEntriesByAddressesResponse::new(mempool_entries))
+        })
+        .await
     }
 
     async fn submit_transaction_call(
@@ -667,9 +1160,391 @@ NOTE: This error usually indicates an RPC conversion error between the node and
                 debug!("{err}");
                 err
             })?;
+        self.transaction_status.track(transaction_id);
         Ok(SubmitTransactionResponse::new(transaction_id))
     }
 
+    async fn test_mempool_accept_call(
+        &self,
+        request: TestMempoolAcceptRequest,
+    ) -> RpcResult<TestMempoolAcceptResponse> {
+        let transaction: Transaction = (&request.transaction).try_into()?;
+        let transaction_id = transaction.id();
+        let session = self.consensus_manager.consensus().unguarded_session();
+        let (transaction, result) = self
+            .mining_manager
+            .clone()
+            .test_mempool_accept(&session, transaction)
+            .await;
+        let fee = transaction.calculated_fee.unwrap_or_default();
+        let mass = transaction.tx.mass();
+        let feerate = match mass {
+            0 => 0.0,
+            mass => fee as f64 / mass as f64,
+        };
+        Ok(TestMempoolAcceptResponse::new(
+            transaction_id,
+            result.err().map(|err| err.to_string()),
+            fee,
+            transaction.calculated_compute_mass.unwrap_or_default(),
+            mass,
+            feerate,
+        ))
+    }
+
+    async fn get_difficulty_info_call(
+        &self,
+        request: GetDifficultyInfoRequest,
+    ) -> RpcResult<GetDifficultyInfoResponse> {
+        self.run_heavy(move |this| async move {
+            let chain_block_count =
+                (request.chain_block_count as usize).min(MAX_DIFFICULTY_INFO_CHAIN_BLOCKS);
+            let session = this.consensus_manager.consensus().session().await;
+            let info = session.async_get_difficulty_info(chain_block_count).await?;
+            let converter = &this.consensus_converter;
+            let window = RpcDifficultyWindow {
+                size: info.window.size,
+                sample_rate: info.window.sample_rate,
+                oldest_timestamp: info.window.oldest_timestamp,
+                newest_timestamp: info.window.newest_timestamp,
+                min_difficulty: converter.get_difficulty_ratio(info.window.easiest_bits),
+                max_difficulty: converter.get_difficulty_ratio(info.window.hardest_bits),
+                average_difficulty: converter
+                    .get_target_difficulty_ratio(&info.window.average_target),
+            };
+            let chain_blocks = info
+                .chain_blocks
+                .into_iter()
+                .map(|x| {
+                    RpcBlockDifficulty::new(
+                        x.hash,
+                        x.daa_score,
+                        x.timestamp,
+                        x.bits,
+                        converter.get_difficulty_ratio(x.bits),
+                    )
+                })
+                .collect();
+            Ok(GetDifficultyInfoResponse::new(
+                info.daa_score,
+                info.next_bits,
+                converter.get_difficulty_ratio(info.next_bits),
+                this.config.target_time_per_block,
+                window,
+                chain_blocks,
+            ))
+        })
+        .await
+    }
+
+    async fn get_dag_slice_call(
+        &self,
+        request: GetDagSliceRequest,
+    ) -> RpcResult<GetDagSliceResponse> {
+        self.run_heavy(move |this| async move {
+            let count = (request.count as usize).min(MAX_DAG_SLICE_BLOCKS);
+            let session = this.consensus_manager.consensus().session().await;
+            let DagSlice {
+                blocks,
+                next_daa_score,
+            } = session
+                .async_get_dag_slice(request.from_daa_score, count)
+                .await?;
+            let blocks = blocks
+                .into_iter()
+                .map(|block| RpcDagBlock {
+                    hash: block.hash,
+                    parent_hashes: block.parents,
+                    daa_score: block.daa_score,
+                    blue_score: block.blue_score,
+                    timestamp: block.timestamp,
+                    is_blue: block.is_blue,
+                    is_chain_block: block.is_chain_block,
+                    merging_block_hash: block.merging_block,
+                })
+                .collect();
+            Ok(GetDagSliceResponse {
+                blocks,
+                next_daa_score,
+            })
+        })
+        .await
+    }
+
+    async fn get_acceptance_events_since_call(
+        &self,
+        request: GetAcceptanceEventsSinceRequest,
+    ) -> RpcResult<GetAcceptanceEventsSinceResponse> {
+        let journal = self
+            .acceptance_journal
+            .clone()
+            .ok_or(RpcError::NoAcceptanceJournal)?;
+        self.run_heavy(move |_| async move {
+            let max_count = (request.max_count as usize).min(MAX_ACCEPTANCE_EVENTS);
+            let read = tokio::task::spawn_blocking(move || journal.read(request.cursor, max_count))
+                .await
+                .map_err(|err| RpcError::General(err.to_string()))?
+                .map_err(|err| RpcError::General(err.to_string()))?;
+            let events = read
+                .events
+                .into_iter()
+                .map(|event| RpcAcceptanceEvent {
+                    sequence: event.sequence,
+                    chain_block_hash: event.chain_block_hash,
+                    removed: event.removed,
+                    blue_score: event.blue_score,
+                    timestamp: event.timestamp,
+                    recorded_time: event.recorded_time,
+                    accepted_transaction_ids: event.accepted_transaction_ids,
+                })
+                .collect();
+            Ok(GetAcceptanceEventsSinceResponse::new(
+                events,
+                read.gap,
+                read.has_more,
+            ))
+        })
+        .await
+    }
+
+    async fn get_block_filter_headers_call(
+        &self,
+        request: GetBlockFilterHeadersRequest,
+    ) -> RpcResult<GetBlockFilterHeadersResponse> {
+        let index = self
+            .blockfilterindex
+            .clone()
+            .ok_or(RpcError::NoBlockFilterIndex)?;
+        self.run_heavy(move |_| async move {
+            let limit = (request.limit as usize).min(MAX_BLOCK_FILTER_HEADERS);
+            let (entries, tip) = tokio::task::spawn_blocking(move || {
+                Ok::<_, BlockFilterIndexError>((
+                    index.get_filters(request.start_hash, limit)?,
+                    index.get_tip()?,
+                ))
+            })
+            .await
+            .map_err(|err| RpcError::General(err.to_string()))?
+            .map_err(block_filter_index_error)?;
+            let headers = entries
+                .into_iter()
+                .map(|(hash, entry)| {
+                    RpcBlockFilterHeader::new(hash, entry.filter.hash(), entry.header)
+                })
+                .collect();
+            let tip_hash = tip.map(|x| x.hash).unwrap_or_default();
+            Ok(GetBlockFilterHeadersResponse::new(headers, tip_hash))
+        })
+        .await
+    }
+
+    async fn get_block_filters_call(
+        &self,
+        request: GetBlockFiltersRequest,
+    ) -> RpcResult<GetBlockFiltersResponse> {
+        let index = self
+            .blockfilterindex
+            .clone()
+            .ok_or(RpcError::NoBlockFilterIndex)?;
+        self.run_heavy(move |_| async move {
+            let limit = (request.limit as usize).min(MAX_BLOCK_FILTERS);
+            let entries =
+                tokio::task::spawn_blocking(move || index.get_filters(request.start_hash, limit))
+                    .await
+                    .map_err(|err| RpcError::General(err.to_string()))?
+                    .map_err(block_filter_index_error)?;
+            let filters = entries
+                .into_iter()
+                .map(|(hash, entry)| RpcBlockFilter::new(hash, entry.filter.as_bytes().to_vec()))
+                .collect();
+            Ok(GetBlockFiltersResponse::new(filters))
+        })
+        .await
+    }
+
+    async fn get_address_cluster_call(
+        &self,
+        request: GetAddressClusterRequest,
+    ) -> RpcResult<GetAddressClusterResponse> {
+        let index = self
+            .address_cluster_index
+            .clone()
+            .ok_or(RpcError::NoAddressClusterIndex)?;
+        #[cfg(not(feature = "analytics"))]
+        {
+            let _ = request;
+            match *index {}
+        }
+        #[cfg(feature = "analytics")]
+        {
+            self.check_addresses_network(once(&request.address))?;
+            self.run_heavy(move |this| async move {
+                let script_public_key = pay_to_address_script(&request.address);
+                let limit = (request.limit as usize).min(MAX_ADDRESS_CLUSTER_MEMBERS);
+                let cluster = tokio::task::spawn_blocking(move || {
+                    index.get_cluster(&script_public_key, limit)
+                })
+                .await
+                .map_err(|err| RpcError::General(err.to_string()))?
+                .map_err(|err| RpcError::General(err.to_string()))?;
+                let prefix = this.config.prefix();
+                let addresses = cluster
+                    .members
+                    .iter()
+                    .filter_map(|script_public_key| {
+                        extract_script_pub_key_address(script_public_key, prefix).ok()
+                    })
+                    .collect();
+                Ok(GetAddressClusterResponse::new(cluster.size, addresses))
+            })
+            .await
+        }
+    }
+
+    async fn debug_script_call(
+        &self,
+        request: DebugScriptRequest,
+    ) -> RpcResult<DebugScriptResponse> {
+        if !self.config.unsafe_rpc && matches!(self.config.net.network_type, Mainnet | Testnet) {
+            warn!(
+                "DebugScript RPC command called on {} while node in safe RPC mode -- ignoring.",
+                self.config.net
+            );
+            return Err(RpcError::UnavailableOnNetwork(self.config.net));
+        }
+        let transaction: Transaction = (&request.transaction).try_into()?;
+        if request.utxo_entries.len() != transaction.inputs.len() {
+            return Err(RpcError::UtxoEntriesCountMismatch(
+                transaction.inputs.len(),
+                request.utxo_entries.len(),
+            ));
+        }
+        let populated = PopulatedTransaction::new(&transaction, request.utxo_entries);
+        let trace = debug_transaction_input(&populated, request.input_index as usize);
+        Ok(DebugScriptResponse::new(
+            trace.result.err().map(|err| err.to_string()),
+            trace.steps.iter().map(RpcScriptStep::from).collect(),
+        ))
+    }
+
+    async fn get_transaction_status_call(
+        &self,
+        request: GetTransactionStatusRequest,
+    ) -> RpcResult<GetTransactionStatusResponse> {
+        let transaction_id = request.transaction_id;
+        let in_mempool = self
+            .mining_manager
+            .clone()
+            .has_transaction(transaction_id, TransactionQuery::TransactionsOnly)
+            .await;
+        let is_orphan = self
+            .mining_manager
+            .clone()
+            .has_transaction(transaction_id, TransactionQuery::OrphansOnly)
+            .await;
+        let tracked = self.transaction_status.get(&transaction_id);
+        let accepting_block_hash = tracked.and_then(|x| x.accepting_block_hash);
+        let confirmations = match accepting_block_hash {
+            Some(accepting_block_hash) => {
+                let session = self.consensus_manager.consensus().session().await;
+                let accepting_blue_score = session
+                    .async_get_header(accepting_block_hash)
+                    .await?
+                    .blue_score;
+                // The sink of the committed virtual state rather than of the last known good one
+                let sink = session.async_with_read_view(|view| view.get_sink()).await;
+                let sink_blue_score = session.async_get_ghostdag_data(sink).await?.blue_score;
+                sink_blue_score.saturating_sub(accepting_blue_score)
+            }
+            None => 0,
+        };
+        Ok(GetTransactionStatusResponse::new(
+            transaction_id,
+            in_mempool,
+            is_orphan,
+            tracked.is_some(),
+            self.flow_context
+                .transaction_announced_peer_count(&transaction_id)
+                .await as u32,
+            accepting_block_hash,
+            confirmations,
+        ))
+    }
+
+    async fn get_unconfirmed_tx_risk_call(
+        &self,
+        request: GetUnconfirmedTxRiskRequest,
+    ) -> RpcResult<GetUnconfirmedTxRiskResponse> {
+        let transaction_id = request.transaction_id;
+        let transaction = self
+            .mining_manager
+            .clone()
+            .get_transaction(transaction_id, TransactionQuery::All)
+            .await;
+        let is_orphan = self
+            .mining_manager
+            .clone()
+            .has_transaction(transaction_id, TransactionQuery::OrphansOnly)
+            .await;
+        let is_accepted = self
+            .mining_manager
+            .clone()
+            .has_accepted_transaction(transaction_id)
+            .await;
+        let (fee_rate, fee_rate_percentile) = self
+            .mining_manager
+            .clone()
+            .get_fee_rate_percentile(transaction_id)
+            .await
+            .unwrap_or_default();
+        let conflicting_transaction_ids = self
+            .mining_manager
+            .clone()
+            .get_conflicting_transactions(transaction_id)
+            .await;
+
+        // Outputs of mempool transactions carry an unaccepted DAA score and the outputs of
+        // missing transactions have no entry, both count as unconfirmed
+        let virtual_daa_score = self
+            .consensus_manager
+            .consensus()
+            .unguarded_session()
+            .get_virtual_daa_score();
+        let input_ages = transaction.as_ref().map_or(vec![], |transaction| {
+            transaction
+                .entries
+                .iter()
+                .map(|entry| match entry {
+                    Some(entry) if entry.block_daa_score != UNACCEPTED_DAA_SCORE => {
+                        Some(virtual_daa_score.saturating_sub(entry.block_daa_score))
+                    }
+                    _ => None,
+                })
+                .collect::<Vec<_>>()
+        });
+        let unconfirmed_input_count = input_ages.iter().filter(|age| age.is_none()).count() as u32;
+        let input_ages = input_ages
+            .into_iter()
+            .map(Option::unwrap_or_default)
+            .collect();
+
+        Ok(GetUnconfirmedTxRiskResponse {
+            transaction_id,
+            in_mempool: transaction.is_some() && !is_orphan,
+            is_orphan,
+            is_accepted,
+            fee_rate,
+            fee_rate_percentile,
+            conflicting_transaction_ids,
+            input_ages,
+            unconfirmed_input_count,
+            announcing_peer_count: self
+                .flow_context
+                .transaction_announcer_count(&transaction_id)
+                as u32,
+        })
+    }
+
     async fn get_current_network_call(
         &self,
         _: GetCurrentNetworkRequest,
@@ -711,22 +1586,30 @@ NOTE: This error usually indicates an RPC conversion error between the node and
         &self,
         request: GetVirtualChainFromBlockRequest,
     ) -> RpcResult<GetVirtualChainFromBlockResponse> {
-        let session = self.consensus_manager.consensus().session().await;
-        let virtual_chain = session
-            .async_get_virtual_chain_from_block(request.start_hash)
-            .await?;
-        let accepted_transaction_ids = if request.include_accepted_transaction_ids {
-            self.consensus_converter
-                .get_virtual_chain_accepted_transaction_ids(&session, &virtual_chain)
-                .await?
-        } else {
-            vec![]
-        };
-        Ok(GetVirtualChainFromBlockResponse::new(
-            virtual_chain.removed,
-            virtual_chain.added,
-            accepted_transaction_ids,
-        ))
+        self.run_heavy(move |this| async move {
+            let session = this.consensus_manager.consensus().session().await;
+            // The chain and the acceptance data of its added blocks are read from a single view
+            let include_accepted_transaction_ids = request.include_accepted_transaction_ids;
+            let (virtual_chain, acceptance_data) = session
+                .async_with_read_view(move |view| {
+                    let virtual_chain = view.get_virtual_chain_from_block(request.start_hash)?;
+                    let acceptance_data = match include_accepted_transaction_ids {
+                        true => view.get_blocks_acceptance_data(&virtual_chain.added)?,
+                        false => vec![],
+                    };
+                    Ok::<_, RpcError>((virtual_chain, acceptance_data))
+                })
+                .await?;
+            let accepted_transaction_ids = this
+                .consensus_converter
+                .get_virtual_chain_accepted_transaction_ids(&virtual_chain, &acceptance_data);
+            Ok(GetVirtualChainFromBlockResponse::new(
+                virtual_chain.removed,
+                virtual_chain.added,
+                accepted_transaction_ids,
+            ))
+        })
+        .await
     }
 
     async fn get_block_count_call(
@@ -745,146 +1628,289 @@ NOTE: This error usually indicates an RPC conversion error between the node and
         &self,
         request: GetUtxosByAddressesRequest,
     ) -> RpcResult<GetUtxosByAddressesResponse> {
-        if !self.config.utxoindex {
-            return Err(RpcError::NoUtxoIndex);
-        }
-        // TODO: discuss if the entry order is part of the method requirements
-        //       (the current impl does not retain an entry order matching the request addresses order)
-        let entry_map = self
-            .get_utxo_set_by_script_public_key(request.addresses.iter())
-            .await;
-        Ok(GetUtxosByAddressesResponse::new(
-            self.index_converter
-                .get_utxos_by_addresses_entries(&entry_map),
-        ))
+        self.run_heavy(move |this| async move {
+            if !this.config.utxoindex {
+                return Err(RpcError::NoUtxoIndex);
+            }
+            this.check_addresses_network(request.addresses.iter())?;
+            // Entries are listed address by address in a stable order, so the index is read
+            // from the cursor by chunks until the page is full
+            let mut addresses = request.addresses;
+            addresses.sort_unstable_by_key(|address| address.to_string());
+            addresses.dedup();
+            let mut from = match AddressOutpoint::parse_cursor(request.cursor.as_deref())? {
+                Some(cursor) => {
+                    let position = addresses
+                        .iter()
+                        .position(|address| *address == cursor.address)
+                        .ok_or_else(|| RpcError::InvalidCursor(cursor.to_cursor()))?;
+                    Some((position, cursor.outpoint))
+                }
+                None => None,
+            };
+            let script_public_keys = Arc::new(
+                addresses
+                    .iter()
+                    .map(pay_to_address_script)
+                    .collect::<Vec<_>>(),
+            );
+            let mut page = this.response_size_limit.page(request.paginate);
+            'chunks: loop {
+                let entries = this
+                    .utxoindex
+                    .clone()
+                    .unwrap()
+                    .get_utxos_by_script_public_keys_from(
+                        script_public_keys.clone(),
+                        from,
+                        PAGE_READ_CHUNK_SIZE,
+                    )
+                    .await
+                    .map_err(|err| RpcError::General(err.to_string()))?;
+                for &(position, outpoint, ref entry) in entries.iter() {
+                    let key = AddressOutpoint {
+                        address: addresses[position].clone(),
+                        outpoint,
+                    };
+                    let entry = this.index_converter.get_utxos_by_addresses_entry(
+                        &addresses[position],
+                        &script_public_keys[position],
+                        outpoint,
+                        entry,
+                    );
+                    if !page.push(key, entry)? {
+                        break 'chunks;
+                    }
+                }
+                if entries.len() < PAGE_READ_CHUNK_SIZE {
+                    break;
+                }
+                from = entries
+                    .last()
+                    .map(|&(position, outpoint, _)| (position, outpoint));
+            }
+            let page = page.build();
+            Ok(GetUtxosByAddressesResponse::new(page.entries).with_next_cursor(page.next_cursor))
+        })
+        .await
     }
 
     async fn get_balance_by_address_call(
         &self,
         request: GetBalanceByAddressRequest,
     ) -> RpcResult<GetBalanceByAddressResponse> {
-        if !self.config.utxoindex {
-            return Err(RpcError::NoUtxoIndex);
-        }
-        let entry_map = self
-            .get_balance_by_script_public_key(once(&request.address))
-            .await;
-        let balance = entry_map.values().sum();
-        Ok(GetBalanceByAddressResponse::new(balance))
+        self.run_heavy(move |this| async move {
+            if !this.config.utxoindex {
+                return Err(RpcError::NoUtxoIndex);
+            }
+            this.check_addresses_network(once(&request.address))?;
+            let entry_map = this
+                .get_balance_by_script_public_key(once(&request.address))
+                .await;
+            let balance = entry_map.values().sum();
+            Ok(GetBalanceByAddressResponse::new(balance))
+        })
+        .await
     }
 
     async fn get_balances_by_addresses_call(
         &self,
         request: GetBalancesByAddressesRequest,
     ) -> RpcResult<GetBalancesByAddressesResponse> {
-        if !self.config.utxoindex {
-            return Err(RpcError::NoUtxoIndex);
-        }
-        let entry_map = self
-            .get_balance_by_script_public_key(request.addresses.iter())
-            .await;
-        let entries = request
-            .addresses
-            .iter()
-            .map(|address| {
-                let script_public_key = pay_to_address_script(address);
-                let balance = entry_map.get(&script_public_key).copied();
-                RpcBalancesByAddressesEntry {
-                    address: address.to_owned(),
-                    balance,
+        self.run_heavy(move |this| async move {
+            if !this.config.utxoindex {
+                return Err(RpcError::NoUtxoIndex);
+            }
+            this.check_addresses_network(request.addresses.iter())?;
+            let entry_map = this
+                .get_balance_by_script_public_key(request.addresses.iter())
+                .await;
+            let entries = request
+                .addresses
+                .iter()
+                .map(|address| {
+                    let script_public_key = pay_to_address_script(address);
+                    let balance = entry_map.get(&script_public_key).copied();
+                    RpcBalancesByAddressesEntry {
+                        address: address.to_owned(),
+                        balance,
+                    }
+                })
+                .collect();
+            Ok(GetBalancesByAddressesResponse::new(entries))
+        })
+        .await
+    }
+
+    async fn get_balance_by_addresses_at_call(
+        &self,
+        request: GetBalanceByAddressesAtRequest,
+    ) -> RpcResult<GetBalanceByAddressesAtResponse> {
+        self.run_heavy(move |this| async move {
+            if !this.config.utxoindex {
+                return Err(RpcError::NoUtxoIndex);
+            }
+            this.check_addresses_network(request.addresses.iter())?;
+            let script_public_keys: ScriptPublicKeys = request
+                .addresses
+                .iter()
+                .map(pay_to_address_script)
+                .collect();
+            let session = this.consensus_manager.consensus().session().await;
+            let mut rollback = session
+                .async_get_balance_rollback(
+                    request.daa_score,
+                    script_public_keys.clone(),
+                    MAX_BALANCE_ROLLBACK_CHAIN_BLOCKS,
+                )
+                .await?;
+            // The UTXO index trails the virtual state by the notifications in flight, so the
+            // rollback only applies to its balances once both share the same virtual parents.
+            // The rollback is kept while the index catches up with it, and only walked again
+            // once the index reached a later virtual state, which the rollback then misses.
+            for _ in 0..BALANCE_SNAPSHOT_ATTEMPTS {
+                let (balances, tips) = this
+                    .utxoindex
+                    .clone()
+                    .unwrap()
+                    .get_balance_by_script_public_keys_with_tips(script_public_keys.clone())
+                    .await
+                    .map_err(|err| RpcError::General(err.to_string()))?;
+                if *tips != rollback.virtual_parents {
+                    if *tips == session.get_virtual_parents() {
+                        rollback = session
+                            .async_get_balance_rollback(
+                                request.daa_score,
+                                script_public_keys.clone(),
+                                MAX_BALANCE_ROLLBACK_CHAIN_BLOCKS,
+                            )
+                            .await?;
+                    } else {
+                        tokio::time::sleep(BALANCE_SNAPSHOT_RETRY_INTERVAL).await;
+                    }
+                    continue;
                 }
-            })
-            .collect();
-        Ok(GetBalancesByAddressesResponse::new(entries))
+                let entries = request
+                    .addresses
+                    .iter()
+                    .map(|address| {
+                        let script_public_key = pay_to_address_script(address);
+                        let current = balances
+                            .get(&script_public_key)
+                            .copied()
+                            .unwrap_or_default();
+                        RpcBalancesByAddressesEntry {
+                            address: address.to_owned(),
+                            balance: Some(rollback.rollback(&script_public_key, current)),
+                        }
+                    })
+                    .collect();
+                return Ok(GetBalanceByAddressesAtResponse::new(
+                    entries,
+                    rollback.chain_block,
+                    rollback.daa_score,
+                ));
+            }
+            Err(RpcError::UtxoIndexNotSynced)
+        })
+        .await
     }
 
     async fn get_coin_supply_call(
         &self,
         _: GetCoinSupplyRequest,
     ) -> RpcResult<GetCoinSupplyResponse> {
-        if !self.config.utxoindex {
-            return Err(RpcError::NoUtxoIndex);
-        }
-        let circulating_sompi = self
-            .utxoindex
-            .clone()
-            .unwrap()
-            .get_circulating_supply()
-            .await
-            .map_err(|e| RpcError::General(e.to_string()))?;
-        Ok(GetCoinSupplyResponse::new(MAX_SOMPI, circulating_sompi))
+        self.run_heavy(move |this| async move {
+            if !this.config.utxoindex {
+                return Err(RpcError::NoUtxoIndex);
+            }
+            let circulating_sompi = this
+                .utxoindex
+                .clone()
+                .unwrap()
+                .get_circulating_supply()
+                .await
+                .map_err(|e| RpcError::General(e.to_string()))?;
+            Ok(GetCoinSupplyResponse::new(MAX_SOMPI, circulating_sompi))
+        })
+        .await
     }
 
     async fn get_daa_score_timestamp_estimate_call(
         &self,
         request: GetDaaScoreTimestampEstimateRequest,
     ) -> RpcResult<GetDaaScoreTimestampEstimateResponse> {
-        let session = self.consensus_manager.consensus().session().await;
-        // TODO: cache samples based on sufficient recency of the data and append sink data
-        let mut headers = session.async_get_chain_block_samples().await;
-        let mut requested_daa_scores = request.daa_scores.clone();
-        let mut daa_score_timestamp_map = HashMap::<u64, u64>::new();
-
-        headers.reverse();
-        requested_daa_scores.sort_by(|a, b| b.cmp(a));
-
-        let mut header_idx = 0;
-        let mut req_idx = 0;
-
-        // Loop runs at O(n + m) where n = # pp headers, m = # requested daa_scores
-        // Loop will always end because in the worst case the last header with daa_score = 0 (the genesis)
-        // will cause every remaining requested daa_score to be "found in range"
-        //
-        // TODO: optimize using binary search over the samples to obtain O(m log n) complexity (which is an improvement assuming m << n)
-        while header_idx < headers.len() && req_idx < request.daa_scores.len() {
-            let header = headers.get(header_idx).unwrap();
-            let curr_daa_score = requested_daa_scores[req_idx];
-
-            // Found daa_score in range
-            if header.daa_score <= curr_daa_score {
-                // For daa_score later than the last header, we estimate in milliseconds based on the difference
-                let time_adjustment = if header_idx == 0 {
-                    // estimate milliseconds = (daa_score * target_time_per_block)
-                    (curr_daa_score - header.daa_score)
-                        .checked_mul(self.config.target_time_per_block)
-                        .unwrap_or(u64::MAX)
-                } else {
-                    // "next" header is the one that we processed last iteration
-                    let next_header = &headers[header_idx - 1];
-                    // Unlike DAA scores which are monotonic (over the selected chain), timestamps are not strictly monotonic, so we avoid assuming so
-                    let time_between_headers = next_header
+        self.run_heavy(move |this| async move {
+            let session = this.consensus_manager.consensus().session().await;
+            // TODO: cache samples based on sufficient recency of the data and append sink data
+            let mut headers = session.async_get_chain_block_samples().await;
+            let mut requested_daa_scores = request.daa_scores.clone();
+            let mut daa_score_timestamp_map = HashMap::<u64, u64>::new();
+
+            headers.reverse();
+            requested_daa_scores.sort_by(|a, b| b.cmp(a));
+
+            let mut header_idx = 0;
+            let mut req_idx = 0;
+
+            // Loop runs at O(n + m) where n = # pp headers, m = # requested daa_scores
+            // Loop will always end because in the worst case the last header with daa_score = 0 (the genesis)
+            // will cause every remaining requested daa_score to be "found in range"
+            //
+            // TODO: optimize using binary search over the samples to obtain O(m log n) complexity (which is an improvement assuming m << n)
+            while header_idx < headers.len() && req_idx < request.daa_scores.len() {
+                let header = headers.get(header_idx).unwrap();
+                let curr_daa_score = requested_daa_scores[req_idx];
+
+                // Found daa_score in range
+                if header.daa_score <= curr_daa_score {
+                    // For daa_score later than the last header, we estimate in milliseconds based on the difference
+                    let time_adjustment = if header_idx == 0 {
+                        // estimate milliseconds = (daa_score * target_time_per_block)
+                        (curr_daa_score - header.daa_score)
+                            .checked_mul(this.config.target_time_per_block)
+                            .unwrap_or(u64::MAX)
+                    } else {
+                        // "next" header is the one that we processed last iteration
+                        let next_header = &headers[header_idx - 1];
+                        // Unlike DAA scores which are monotonic (over the selected chain), timestamps are not strictly monotonic, so we avoid assuming so
+                        let time_between_headers = next_header
+                            .timestamp
+                            .checked_sub(header.timestamp)
+                            .unwrap_or_default();
+                        let score_between_query_and_header =
+                            (curr_daa_score - header.daa_score) as f64;
+                        let score_between_headers =
+                            (next_header.daa_score - header.daa_score) as f64;
+                        // Interpolate the timestamp delta using the estimated fraction based on DAA scores
+                        ((time_between_headers as f64)
+                            * (score_between_query_and_header / score_between_headers))
+                            as u64
+                    };
+
+                    let daa_score_timestamp = header
                         .timestamp
-                        .checked_sub(header.timestamp)
-                        .unwrap_or_default();
-                    let score_between_query_and_header = (curr_daa_score - header.daa_score) as f64;
-                    let score_between_headers = (next_header.daa_score - header.daa_score) as f64;
-                    // Interpolate the timestamp delta using the estimated fraction based on DAA scores
-                    ((time_between_headers as f64)
-                        * (score_between_query_and_header / score_between_headers))
-                        as u64
-                };
+                        .checked_add(time_adjustment)
+                        .unwrap_or(u64::MAX);
+                    daa_score_timestamp_map.insert(curr_daa_score, daa_score_timestamp);
 
-                let daa_score_timestamp = header
-                    .timestamp
-                    .checked_add(time_adjustment)
-                    .unwrap_or(u64::MAX);
-                daa_score_timestamp_map.insert(curr_daa_score, daa_score_timestamp);
-
-                // Process the next daa score that's <= than current one (at earlier idx)
-                req_idx += 1;
-            } else {
-                header_idx += 1;
+                    // Process the next daa score that's <= than current one (at earlier idx)
+                    req_idx += 1;
+                } else {
+                    header_idx += 1;
+                }
             }
-        }
 
-        // Note: it is safe to assume all entries exist in the map since the first sampled header is expected to have daa_score=0
-        let timestamps = request
-            .daa_scores
-            .iter()
-            .map(|curr_daa_score| daa_score_timestamp_map[curr_daa_score])
-            .collect();
+            // Note: it is safe to assume all entries exist in the map since the first sampled header is expected to have daa_score=0
+            let timestamps = request
+                .daa_scores
+                .iter()
+                .map(|curr_daa_score| daa_score_timestamp_map[curr_daa_score])
+                .collect();
 
-        Ok(GetDaaScoreTimestampEstimateResponse::new(timestamps))
+            Ok(GetDaaScoreTimestampEstimateResponse::new(timestamps))
+        })
+        .await
     }
 
     async fn ping_call(&self, _: PingRequest) -> RpcResult<PingResponse> {
@@ -928,38 +1954,44 @@ NOTE: This error usually indicates an RPC conversion error between the node and
         &self,
         request: EstimateNetworkHashesPerSecondRequest,
     ) -> RpcResult<EstimateNetworkHashesPerSecondResponse> {
-        if !self.config.unsafe_rpc && request.window_size > MAX_SAFE_WINDOW_SIZE {
-            return Err(RpcError::WindowSizeExceedingMaximum(
-                request.window_size,
-                MAX_SAFE_WINDOW_SIZE,
-            ));
-        }
-        if request.window_size as u64 > self.config.pruning_depth {
-            return Err(RpcError::WindowSizeExceedingPruningDepth(
-                request.window_size,
-                self.config.pruning_depth,
-            ));
-        }
+        self.run_heavy(move |this| async move {
+            if !this.config.unsafe_rpc && request.window_size > MAX_SAFE_WINDOW_SIZE {
+                return Err(RpcError::WindowSizeExceedingMaximum(
+                    request.window_size,
+                    MAX_SAFE_WINDOW_SIZE,
+                ));
+            }
+            if request.window_size as u64 > this.config.pruning_depth {
+                return Err(RpcError::WindowSizeExceedingPruningDepth(
+                    request.window_size,
+                    this.config.pruning_depth,
+                ));
+            }
 
-        // In the previous golang implementation the convention for virtual was the following const.
-        // In the current implementation, consensus behaves the same when it gets a None instead.
-        const LEGACY_VIRTUAL: karlsen_hashes::Hash =
-            karlsen_hashes::Hash::from_bytes([0xff; karlsen_hashes::HASH_SIZE]);
-        let mut start_hash = request.start_hash;
-        if let Some(start) = start_hash {
-            if start == LEGACY_VIRTUAL {
-                start_hash = None;
+            // In the previous golang implementation the convention for virtual was the following const.
+            // In the current implementation, consensus behaves the same when it gets a None instead.
+            const LEGACY_VIRTUAL: karlsen_hashes::Hash =
+                karlsen_hashes::Hash::from_bytes([0xff; karlsen_hashes::HASH_SIZE]);
+            let mut start_hash = request.start_hash;
+            if let Some(start) = start_hash {
+                if start == LEGACY_VIRTUAL {
+                    start_hash = None;
+                }
             }
-        }
 
-        Ok(EstimateNetworkHashesPerSecondResponse::new(
-            self.consensus_manager
-                .consensus()
-                .session()
-                .await
-                .async_estimate_network_hashes_per_second(start_hash, request.window_size as usize)
-                .await?,
-        ))
+            Ok(EstimateNetworkHashesPerSecondResponse::new(
+                this.consensus_manager
+                    .consensus()
+                    .session()
+                    .await
+                    .async_estimate_network_hashes_per_second(
+                        start_hash,
+                        request.window_size as usize,
+                    )
+                    .await?,
+            ))
+        })
+        .await
     }
 
     async fn add_peer_call(&self, request: AddPeerRequest) -> RpcResult<AddPeerResponse> {
@@ -1045,7 +2077,7 @@ NOTE: This error usually indicates an RPC conversion error between the node and
         // giving time for the response to be sent to the caller.
         let core = self.core.clone();
         tokio::spawn(async move {
-            tokio::time::sleep(std::time::Duration::from_secs(1)).await;
+            tokio::time::sleep(Duration::from_secs(1)).await;
             core.shutdown();
         });
 
@@ -1076,16 +2108,23 @@ NOTE: This error usually indicates an RPC conversion error between the node and
             disk_io_write_per_sec,
         } = self.perf_monitor.snapshot();
 
-        let process_metrics = req.process_metrics.then_some(ProcessMetrics {
-            resident_set_size,
-            virtual_memory_size,
-            core_num: core_num as u32,
-            cpu_usage: cpu_usage as f32,
-            fd_num: fd_num as u32,
-            disk_io_read_bytes,
-            disk_io_write_bytes,
-            disk_io_read_per_sec: disk_io_read_per_sec as f32,
-            disk_io_write_per_sec: disk_io_write_per_sec as f32,
+        let process_metrics = req.process_metrics.then(|| {
+            let memory_budget = memory_budget().stats();
+            ProcessMetrics {
+                resident_set_size,
+                virtual_memory_size,
+                core_num: core_num as u32,
+                cpu_usage: cpu_usage as f32,
+                fd_num: fd_num as u32,
+                disk_io_read_bytes,
+                disk_io_write_bytes,
+                disk_io_read_per_sec: disk_io_read_per_sec as f32,
+                disk_io_write_per_sec: disk_io_write_per_sec as f32,
+                memory_limit: memory_budget.limit as u64,
+                memory_budget_usage: memory_budget.usage as u64,
+                memory_pressure_events: memory_budget.pressure_events,
+                memory_released_bytes: memory_budget.released_bytes,
+            }
         });
 
         let connection_metrics = req.connection_metrics.then_some(ConnectionMetrics {
@@ -1136,6 +2175,11 @@ NOTE: This error usually indicates an RPC conversion error between the node and
                 .async_get_stats()
                 .await;
             let processing_counters = self.processing_counters.snapshot();
+            let utxoindex_consistency_counters = self
+                .utxoindex
+                .as_ref()
+                .map(|utxoindex| utxoindex.consistency_counters().snapshot())
+                .unwrap_or_default();
 
             Some(ConsensusMetrics {
                 node_blocks_submitted_count: processing_counters.blocks_submitted,
@@ -1159,6 +2203,13 @@ NOTE: This error usually indicates an RPC conversion error between the node and
                 network_past_median_time: consensus_stats.virtual_stats.past_median_time,
                 network_virtual_parent_hashes_count: consensus_stats.virtual_stats.num_parents,
                 network_virtual_daa_score: consensus_stats.virtual_stats.daa_score,
+                // ---
+                node_utxo_index_checked_entries_count: utxoindex_consistency_counters
+                    .checked_counts,
+                node_utxo_index_divergent_entries_count: utxoindex_consistency_counters
+                    .divergent_counts,
+                node_utxo_index_repaired_entries_count: utxoindex_consistency_counters
+                    .repaired_counts,
             })
         } else {
             None
@@ -1196,6 +2247,57 @@ NOTE: This error usually indicates an RPC conversion error between the node and
         })
     }
 
+    async fn get_server_capabilities_call(
+        &self,
+        _request: GetServerCapabilitiesRequest,
+    ) -> RpcResult<GetServerCapabilitiesResponse> {
+        // Methods answering with RpcError::NotImplemented are not advertised
+        const UNIMPLEMENTED_METHODS: [RpcApiOps; 3] = [
+            RpcApiOps::GetSubnetwork,
+            RpcApiOps::GetHeaders,
+            RpcApiOps::ResolveFinalityConflict,
+        ];
+        // Neither are the methods of the features the node is built without
+        let disabled_methods: &[RpcApiOps] = if cfg!(feature = "analytics") {
+            &[]
+        } else {
+            &[RpcApiOps::GetAddressCluster]
+        };
+        let methods = RpcApiOps::list()
+            .iter()
+            .filter(|op| {
+                !op.is_subscription()
+                    && !op.is_notification()
+                    && !UNIMPLEMENTED_METHODS.contains(*op)
+                    && !disabled_methods.contains(*op)
+            })
+            .map(|op| op.as_str().to_string())
+            .collect();
+        let notifications = EVENT_TYPE_ARRAY
+            .iter()
+            .map(|event| event.to_string())
+            .collect();
+
+        Ok(GetServerCapabilitiesResponse::new(
+            RPC_API_VERSION,
+            PROTOWIRE_API_VERSION,
+            version().to_string(),
+            methods,
+            notifications,
+        ))
+    }
+
+    async fn get_network_info_call(
+        &self,
+        _request: GetNetworkInfoRequest,
+    ) -> RpcResult<GetNetworkInfoResponse> {
+        Ok(GetNetworkInfoResponse::new(
+            self.config.net,
+            self.config.prefix().to_string(),
+            self.config.genesis.hash,
+        ))
+    }
+
     async fn get_sync_status_call(
         &self,
         _request: GetSyncStatusRequest,
@@ -1225,6 +2327,9 @@ NOTE: This error usually indicates an RPC conversion error between the node and
 
     /// Start sending notifications of some type to a listener.
     async fn start_notify(&self, id: ListenerId, scope: Scope) -> RpcResult<()> {
+        if let Scope::UtxosChanged(ref utxos_changed_scope) = scope {
+            self.check_addresses_network(utxos_changed_scope.addresses.iter())?;
+        }
         match scope {
             Scope::UtxosChanged(ref utxos_changed_scope)
                 if !self.config.unsafe_rpc && utxos_changed_scope.addresses.is_empty() =>
@@ -1252,6 +2357,26 @@ NOTE: This error usually indicates an RPC conversion error between the node and
     }
 }
 
+fn block_filter_index_error(err: BlockFilterIndexError) -> RpcError {
+    match err {
+        BlockFilterIndexError::BlockNotIndexed(hash) => RpcError::BlockNotInFilterIndex(hash),
+        err => RpcError::General(err.to_string()),
+    }
+}
+
+/// Extra data of the coinbase payload: the node version, the miner tag of the node if any, then
+/// the extra data of the request, separated by slashes
+fn coinbase_extra_data(miner_tag: Option<&str>, extra_data: &[u8]) -> Vec<u8> {
+    let mut data = version().as_bytes().to_vec();
+    data.push(b'/');
+    if let Some(miner_tag) = miner_tag {
+        data.extend_from_slice(miner_tag.as_bytes());
+        data.push(b'/');
+    }
+    data.extend_from_slice(extra_data);
+    data
+}
+
 // It might be necessary to opt this out in the context of wasm32
 
 impl AsyncService for RpcCoreService {
@@ -1292,3 +2417,25 @@ impl AsyncService for RpcCoreService {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_coinbase_extra_data() {
+        let prefix = format!("{}/", version());
+        assert_eq!(
+            coinbase_extra_data(None, b"pool"),
+            format!("{prefix}pool").into_bytes()
+        );
+        assert_eq!(
+            coinbase_extra_data(Some("my-rig"), b"pool"),
+            format!("{prefix}my-rig/pool").into_bytes()
+        );
+        assert_eq!(
+            coinbase_extra_data(Some("my-rig"), b""),
+            format!("{prefix}my-rig/").into_bytes()
+        );
+    }
+}
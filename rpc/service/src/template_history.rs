@@ -0,0 +1,162 @@
+//! A short retained history of the transaction sets of the block templates issued by
+//! GetTemplateDiff.
+//!
+//! Every issued template gets an id, which the clients pass back on their next call so only
+//! the transactions added to and removed from that template are sent to them. Ids carry a
+//! random epoch in their high bits, so an id issued before a restart of the node is never
+//! mistaken for a template of the current process.
+
+use karlsen_rpc_core::RpcTransactionId;
+use parking_lot::Mutex;
+use std::{
+    collections::{HashSet, VecDeque},
+    sync::Arc,
+};
+
+/// Changes of the transaction set of a template relative to a previously issued one
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct TemplateDiff {
+    /// Id of the template the diff applies to, zero if the base template is unknown and all
+    /// the transactions are reported as added
+    pub base_template_id: u64,
+    /// Positions, in the new template, of the transactions missing from the base template
+    pub added: Vec<usize>,
+    pub removed: Vec<RpcTransactionId>,
+}
+
+pub struct TemplateHistory {
+    capacity: usize,
+    inner: Mutex<TemplateHistoryInner>,
+}
+
+struct TemplateHistoryInner {
+    /// Id of the next issued template, the epoch in the 32 high bits followed by a sequence
+    /// starting at 1
+    next_id: u64,
+    entries: VecDeque<(u64, Arc<Vec<RpcTransactionId>>)>,
+}
+
+impl TemplateHistory {
+    pub fn new(capacity: usize) -> Self {
+        Self::with_epoch(capacity, rand::random::<u32>().max(1))
+    }
+
+    fn with_epoch(capacity: usize, epoch: u32) -> Self {
+        assert!(
+            capacity > 0,
+            "the template history capacity must be positive"
+        );
+        Self {
+            capacity,
+            inner: Mutex::new(TemplateHistoryInner {
+                next_id: ((epoch as u64) << 32) | 1,
+                entries: VecDeque::with_capacity(capacity),
+            }),
+        }
+    }
+
+    /// Records the transaction ids of a new template, evicting the oldest template if the
+    /// history is full, and diffs them against the template `since_template_id`.
+    ///
+    /// Returns the id of the new template along with the diff.
+    pub fn issue(
+        &self,
+        transaction_ids: Vec<RpcTransactionId>,
+        since_template_id: u64,
+    ) -> (u64, TemplateDiff) {
+        let mut inner = self.inner.lock();
+        let base = inner
+            .entries
+            .iter()
+            .find(|(id, _)| *id == since_template_id)
+            .map(|(_, ids)| ids.clone());
+        let diff = match base {
+            Some(base) => {
+                let base_set: HashSet<_> = base.iter().collect();
+                let new_set: HashSet<_> = transaction_ids.iter().collect();
+                TemplateDiff {
+                    base_template_id: since_template_id,
+                    added: transaction_ids
+                        .iter()
+                        .enumerate()
+                        .filter(|(_, id)| !base_set.contains(id))
+                        .map(|(i, _)| i)
+                        .collect(),
+                    removed: base
+                        .iter()
+                        .filter(|id| !new_set.contains(id))
+                        .copied()
+                        .collect(),
+                }
+            }
+            None => TemplateDiff {
+                base_template_id: 0,
+                added: (0..transaction_ids.len()).collect(),
+                removed: vec![],
+            },
+        };
+
+        let id = inner.next_id;
+        inner.next_id += 1;
+        if inner.entries.len() == self.capacity {
+            inner.entries.pop_front();
+        }
+        inner.entries.push_back((id, Arc::new(transaction_ids)));
+        (id, diff)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ids(words: &[u64]) -> Vec<RpcTransactionId> {
+        words
+            .iter()
+            .map(|&word| RpcTransactionId::from_u64_word(word))
+            .collect()
+    }
+
+    #[test]
+    fn test_template_history() {
+        let history = TemplateHistory::with_epoch(2, 7);
+
+        // Without a base template, all the transactions are added
+        let (first, diff) = history.issue(ids(&[1, 2, 3]), 0);
+        assert_eq!(first, (7 << 32) | 1);
+        assert_eq!(diff.base_template_id, 0);
+        assert_eq!(diff.added, vec![0, 1, 2]);
+        assert!(diff.removed.is_empty());
+
+        let (second, diff) = history.issue(ids(&[2, 4, 3, 5]), first);
+        assert_eq!(second, first + 1);
+        assert_eq!(
+            diff,
+            TemplateDiff {
+                base_template_id: first,
+                added: vec![1, 3],
+                removed: ids(&[1]),
+            }
+        );
+
+        // The first template gets evicted by the third one
+        let (third, _) = history.issue(ids(&[2]), second);
+        let (_, diff) = history.issue(ids(&[2]), first);
+        assert_eq!(diff.base_template_id, 0);
+        assert_eq!(diff.added, vec![0]);
+        let (_, diff) = history.issue(ids(&[2]), third);
+        assert_eq!(diff.base_template_id, third);
+        assert!(diff.added.is_empty() && diff.removed.is_empty());
+
+        // The ids of the templates issued before a restart are unknown to the new process
+        let restarted = TemplateHistory::with_epoch(2, 8);
+        let (id, diff) = restarted.issue(ids(&[2]), 0);
+        assert_ne!(id, first);
+        let (_, diff_since_stale) = restarted.issue(ids(&[2]), first);
+        assert_eq!(diff_since_stale.base_template_id, 0);
+        assert_eq!(diff_since_stale.added, diff.added);
+
+        // Random epochs are never zero, so the ids are never mistaken for the unknown base
+        assert!(TemplateHistory::new(1).issue(vec![], 0).0 > u32::MAX as u64);
+    }
+}
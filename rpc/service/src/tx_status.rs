@@ -0,0 +1,169 @@
+//! Tracking of the transactions submitted through the RPC of this node.
+//!
+//! The node has no transaction index, so the block accepting a transaction is only known
+//! for the transactions submitted locally. Their acceptance is followed through the
+//! `VirtualChainChanged` notifications, including the reorgs unaccepting them. The peers a
+//! transaction was announced to are recorded by the p2p layer for every relayed transaction.
+
+use karlsen_rpc_core::{RpcAcceptedTransactionIds, RpcHash, RpcTransactionId};
+use parking_lot::RwLock;
+use std::collections::{HashMap, HashSet, VecDeque};
+
+/// Status of a tracked transaction
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct TrackedTransaction {
+    /// Selected chain block currently accepting the transaction
+    pub accepting_block_hash: Option<RpcHash>,
+}
+
+#[derive(Default)]
+struct Inner {
+    transactions: HashMap<RpcTransactionId, TrackedTransaction>,
+    /// Tracking order, used for evicting the oldest transactions
+    order: VecDeque<RpcTransactionId>,
+}
+
+pub struct TransactionStatusTracker {
+    capacity: usize,
+    inner: RwLock<Inner>,
+}
+
+impl TransactionStatusTracker {
+    pub fn new(capacity: usize) -> Self {
+        assert!(
+            capacity > 0,
+            "the transaction status tracker capacity must be positive"
+        );
+        Self {
+            capacity,
+            inner: RwLock::new(Inner::default()),
+        }
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Starts tracking a transaction, evicting the oldest one if the tracker is full.
+    ///
+    /// Tracking an already tracked transaction (ie. a resubmission) keeps its status.
+    pub fn track(&self, transaction_id: RpcTransactionId) {
+        let mut inner = self.inner.write();
+        if inner.transactions.contains_key(&transaction_id) {
+            return;
+        }
+        if inner.order.len() == self.capacity {
+            if let Some(evicted) = inner.order.pop_front() {
+                inner.transactions.remove(&evicted);
+            }
+        }
+        inner.order.push_back(transaction_id);
+        inner.transactions.insert(
+            transaction_id,
+            TrackedTransaction {
+                accepting_block_hash: None,
+            },
+        );
+    }
+
+    pub fn get(&self, transaction_id: &RpcTransactionId) -> Option<TrackedTransaction> {
+        self.inner.read().transactions.get(transaction_id).copied()
+    }
+
+    pub fn len(&self) -> usize {
+        self.inner.read().order.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Applies a selected chain change and returns the tracked transactions whose accepting
+    /// block changed, along with their new accepting block (`None` if they got unaccepted)
+    pub fn apply_chain_changed(
+        &self,
+        removed_chain_block_hashes: &[RpcHash],
+        accepted_transaction_ids: &[RpcAcceptedTransactionIds],
+    ) -> Vec<(RpcTransactionId, Option<RpcHash>)> {
+        let mut inner = self.inner.write();
+        let mut previous = HashMap::new();
+
+        if !removed_chain_block_hashes.is_empty() {
+            let removed: HashSet<_> = removed_chain_block_hashes.iter().copied().collect();
+            for (id, tracked) in inner.transactions.iter_mut() {
+                if let Some(hash) = tracked.accepting_block_hash {
+                    if removed.contains(&hash) {
+                        previous.insert(*id, tracked.accepting_block_hash.take());
+                    }
+                }
+            }
+        }
+
+        for accepted in accepted_transaction_ids.iter() {
+            for id in accepted.accepted_transaction_ids.iter() {
+                if let Some(tracked) = inner.transactions.get_mut(id) {
+                    previous.entry(*id).or_insert(tracked.accepting_block_hash);
+                    tracked.accepting_block_hash = Some(accepted.accepting_block_hash);
+                }
+            }
+        }
+
+        previous
+            .into_iter()
+            .filter_map(|(id, previous)| {
+                let current = inner.transactions[&id].accepting_block_hash;
+                (current != previous).then_some((id, current))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hash(value: u64) -> RpcHash {
+        RpcHash::from_u64_word(value)
+    }
+
+    fn accepted(block: u64, ids: &[u64]) -> RpcAcceptedTransactionIds {
+        RpcAcceptedTransactionIds {
+            accepting_block_hash: hash(block),
+            accepted_transaction_ids: ids.iter().copied().map(hash).collect(),
+        }
+    }
+
+    #[test]
+    fn test_transaction_status_tracker() {
+        let tracker = TransactionStatusTracker::new(2);
+        tracker.track(hash(1));
+        tracker.track(hash(2));
+        tracker.track(hash(2));
+        assert_eq!(tracker.len(), 2);
+
+        // Untracked transactions are ignored
+        let changes = tracker.apply_chain_changed(&[], &[accepted(100, &[1, 3])]);
+        assert_eq!(changes, vec![(hash(1), Some(hash(100)))]);
+        assert_eq!(
+            tracker.get(&hash(1)).unwrap().accepting_block_hash,
+            Some(hash(100))
+        );
+
+        // A reorg unaccepts the transaction
+        let changes = tracker.apply_chain_changed(&[hash(100)], &[]);
+        assert_eq!(changes, vec![(hash(1), None)]);
+
+        // Acceptance by a block of the new chain
+        let changes = tracker.apply_chain_changed(&[], &[accepted(101, &[1])]);
+        assert_eq!(changes, vec![(hash(1), Some(hash(101)))]);
+
+        // A reorg re-accepting the transaction in the same block reports no change
+        let changes = tracker.apply_chain_changed(&[hash(101)], &[accepted(101, &[1])]);
+        assert!(changes.is_empty());
+
+        // Tracking a third transaction evicts the oldest
+        tracker.track(hash(3));
+        assert!(tracker.get(&hash(1)).is_none());
+        assert_eq!(tracker.len(), 2);
+    }
+}
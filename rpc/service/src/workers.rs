@@ -0,0 +1,139 @@
+//! Worker pool of the heavy RPC queries.
+//!
+//! Queries scanning indexes or DAG ranges run on a dedicated runtime instead of the one
+//! shared with the P2P flows. The blocking consensus and index reads they spawn then land
+//! on the blocking threads of this runtime, which are capped to the pool size, so a burst
+//! of such queries cannot take more cores than configured away from block processing.
+
+use karlsen_rpc_core::{RpcError, RpcResult};
+use std::future::Future;
+use tokio::runtime::{Builder, Runtime};
+
+const RPC_WORKER_THREAD_NAME: &str = "rpc-worker";
+
+pub struct RpcWorkerPool {
+    runtime: Option<Runtime>,
+}
+
+impl RpcWorkerPool {
+    /// Builds a pool running the queries on `workers` async threads, their blocking
+    /// sections on at most `workers` blocking threads
+    pub fn new(workers: usize) -> Self {
+        assert!(
+            workers > 0,
+            "the RPC worker pool must have at least one worker"
+        );
+        let runtime = Builder::new_multi_thread()
+            .worker_threads(workers)
+            .max_blocking_threads(workers)
+            .thread_name(RPC_WORKER_THREAD_NAME)
+            .enable_all()
+            .build()
+            .expect("the RPC worker runtime is built");
+        Self {
+            runtime: Some(runtime),
+        }
+    }
+
+    /// Runs `query` on the pool and waits for its result
+    pub async fn run<T, F>(&self, query: F) -> RpcResult<T>
+    where
+        F: Future<Output = RpcResult<T>> + Send + 'static,
+        T: Send + 'static,
+    {
+        let runtime = self
+            .runtime
+            .as_ref()
+            .expect("the runtime is only taken on drop");
+        runtime
+            .spawn(query)
+            .await
+            .map_err(|err| RpcError::General(format!("heavy query aborted: {err}")))?
+    }
+}
+
+impl Drop for RpcWorkerPool {
+    fn drop(&mut self) {
+        // The service may be dropped from within an async context where a runtime
+        // cannot block on its shutdown
+        if let Some(runtime) = self.runtime.take() {
+            runtime.shutdown_background();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc,
+        },
+        thread,
+        time::Duration,
+    };
+
+    #[tokio::test]
+    async fn test_rpc_worker_pool() {
+        let pool = RpcWorkerPool::new(2);
+
+        // Queries and their blocking sections run on the threads of the pool
+        let names = pool
+            .run(async {
+                let worker = thread::current().name().map(str::to_owned);
+                let blocking =
+                    tokio::task::spawn_blocking(|| thread::current().name().map(str::to_owned))
+                        .await
+                        .unwrap();
+                Ok((worker, blocking))
+            })
+            .await
+            .unwrap();
+        assert_eq!(
+            names,
+            (
+                Some(RPC_WORKER_THREAD_NAME.to_owned()),
+                Some(RPC_WORKER_THREAD_NAME.to_owned())
+            )
+        );
+
+        // Blocking sections never exceed the pool size
+        let pool = Arc::new(pool);
+        let running = Arc::new(AtomicUsize::new(0));
+        let peak = Arc::new(AtomicUsize::new(0));
+        let queries = (0..8)
+            .map(|_| {
+                let (pool, running, peak) = (pool.clone(), running.clone(), peak.clone());
+                tokio::spawn(async move {
+                    pool.run(async move {
+                        tokio::task::spawn_blocking(move || {
+                            let current = running.fetch_add(1, Ordering::SeqCst) + 1;
+                            peak.fetch_max(current, Ordering::SeqCst);
+                            thread::sleep(Duration::from_millis(20));
+                            running.fetch_sub(1, Ordering::SeqCst);
+                        })
+                        .await
+                        .unwrap();
+                        Ok(())
+                    })
+                    .await
+                })
+            })
+            .collect::<Vec<_>>();
+        for query in queries {
+            query.await.unwrap().unwrap();
+        }
+        assert!(peak.load(Ordering::SeqCst) <= 2);
+
+        // Errors of the queries are returned as is
+        let err = pool
+            .run(async { Err::<(), _>(RpcError::General("failed".to_owned())) })
+            .await
+            .unwrap_err();
+        assert!(matches!(err, RpcError::General(msg) if msg == "failed"));
+
+        // Dropping the pool from an async context does not panic
+        drop(pool);
+    }
+}
@@ -0,0 +1,237 @@
+use crate::error::{WebhookError, WebhookResult};
+use karlsen_addresses::{Address, Prefix};
+use karlsen_notify::scope::{BlockAddedScope, Scope, VirtualChainChangedScope};
+use serde::{Deserialize, Serialize};
+use std::{fs, path::Path, time::Duration};
+
+/// Kinds of node events a webhook endpoint can subscribe to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum WebhookEventKind {
+    /// A block was added to the DAG
+    BlockAdded,
+    /// A transaction paying to or spending from a watched address was accepted by the virtual chain
+    TransactionAccepted,
+    /// The virtual selected chain was reorganized by at least `reorg-depth` blocks
+    DeepReorg,
+}
+
+impl std::fmt::Display for WebhookEventKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            WebhookEventKind::BlockAdded => "block-added",
+            WebhookEventKind::TransactionAccepted => "transaction-accepted",
+            WebhookEventKind::DeepReorg => "deep-reorg",
+        };
+        f.write_str(s)
+    }
+}
+
+/// A webhook receiver
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+pub struct EndpointConfig {
+    /// URL the payloads are POSTed to
+    pub url: String,
+    /// Optional shared secret used to sign the payloads (HMAC-SHA256)
+    #[serde(default)]
+    pub secret: Option<String>,
+    /// Events this endpoint is notified of
+    pub events: Vec<WebhookEventKind>,
+    /// Addresses watched for the `transaction-accepted` event
+    #[serde(default)]
+    pub addresses: Vec<Address>,
+}
+
+impl EndpointConfig {
+    pub fn subscribes_to(&self, event: WebhookEventKind) -> bool {
+        self.events.contains(&event)
+    }
+}
+
+/// Webhooks configuration, loaded from the TOML file provided with `--webhooks-config`
+///
+/// Example:
+/// ```toml
+/// reorg-depth = 10
+/// max-retries = 5
+///
+/// [[endpoint]]
+/// url = "https://example.com/karlsen/events"
+/// secret = "a shared secret"
+/// events = ["block-added", "deep-reorg"]
+///
+/// [[endpoint]]
+/// url = "https://example.com/karlsen/payments"
+/// events = ["transaction-accepted"]
+/// addresses = ["karlsen:qz..."]
+/// ```
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, rename_all = "kebab-case", deny_unknown_fields)]
+pub struct WebhookConfig {
+    /// Minimal count of removed chain blocks for a virtual chain change to be reported as a deep reorg
+    pub reorg_depth: usize,
+    /// Count of delivery retries after a first failed attempt
+    pub max_retries: u32,
+    /// Delay before the first retry, doubled at every further attempt
+    pub initial_backoff_ms: u64,
+    /// Upper bound of the delay between two attempts
+    pub max_backoff_ms: u64,
+    /// Timeout of a single HTTP request
+    pub request_timeout_ms: u64,
+    /// Count of payloads queued per endpoint before new ones get dropped
+    pub queue_capacity: usize,
+    #[serde(rename = "endpoint")]
+    pub endpoints: Vec<EndpointConfig>,
+}
+
+impl Default for WebhookConfig {
+    fn default() -> Self {
+        Self {
+            reorg_depth: 10,
+            max_retries: 5,
+            initial_backoff_ms: 500,
+            max_backoff_ms: 60_000,
+            request_timeout_ms: 10_000,
+            queue_capacity: 1_000,
+            endpoints: vec![],
+        }
+    }
+}
+
+impl WebhookConfig {
+    /// Loads the configuration from a TOML file
+    pub fn load<P: AsRef<Path>>(path: P) -> WebhookResult<Self> {
+        let path = path.as_ref();
+        let content = fs::read_to_string(path)
+            .map_err(|err| WebhookError::ConfigFile(path.display().to_string(), err))?;
+        Self::parse(&content)
+    }
+
+    pub fn parse(content: &str) -> WebhookResult<Self> {
+        Ok(toml::from_str(content)?)
+    }
+
+    /// Checks the configuration is consistent with the node settings
+    pub fn validate(&self, prefix: Prefix) -> WebhookResult<()> {
+        if self.endpoints.is_empty() {
+            return Err(WebhookError::NoEndpoint);
+        }
+        if self.reorg_depth == 0 {
+            return Err(WebhookError::InvalidReorgDepth);
+        }
+        for endpoint in self.endpoints.iter() {
+            if !endpoint.url.starts_with("http://") && !endpoint.url.starts_with("https://") {
+                return Err(WebhookError::InvalidUrl(endpoint.url.clone()));
+            }
+            if endpoint.events.is_empty() {
+                return Err(WebhookError::NoEvent(endpoint.url.clone()));
+            }
+            if endpoint.subscribes_to(WebhookEventKind::TransactionAccepted)
+                && endpoint.addresses.is_empty()
+            {
+                return Err(WebhookError::NoWatchedAddress(endpoint.url.clone()));
+            }
+            if let Some(address) = endpoint.addresses.iter().find(|x| x.prefix != prefix) {
+                return Err(WebhookError::AddressNetworkMismatch(
+                    endpoint.url.clone(),
+                    address.to_string(),
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns true if at least one endpoint subscribes to `event`
+    pub fn has_event(&self, event: WebhookEventKind) -> bool {
+        self.endpoints.iter().any(|x| x.subscribes_to(event))
+    }
+
+    /// Returns the notification scopes the dispatcher must subscribe to in order to
+    /// serve all the configured endpoints
+    pub fn scopes(&self) -> Vec<Scope> {
+        let mut scopes = vec![];
+        if self.has_event(WebhookEventKind::BlockAdded) {
+            scopes.push(Scope::BlockAdded(BlockAddedScope {}));
+        }
+        // The accepted transactions are read from the acceptance data of the added chain blocks
+        if self.has_event(WebhookEventKind::DeepReorg)
+            || self.has_event(WebhookEventKind::TransactionAccepted)
+        {
+            scopes.push(Scope::VirtualChainChanged(VirtualChainChangedScope::new(
+                false,
+            )));
+        }
+        scopes
+    }
+
+    /// Delay to wait before retry number `attempt` (starting at 1)
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        let factor = 1u64
+            .checked_shl(attempt.saturating_sub(1))
+            .unwrap_or(u64::MAX);
+        Duration::from_millis(
+            self.initial_backoff_ms
+                .saturating_mul(factor)
+                .min(self.max_backoff_ms),
+        )
+    }
+
+    pub fn request_timeout(&self) -> Duration {
+        Duration::from_millis(self.request_timeout_ms)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONFIG: &str = r#"
+        reorg-depth = 5
+
+        [[endpoint]]
+        url = "https://example.com/blocks"
+        secret = "secret"
+        events = ["block-added", "deep-reorg"]
+
+        [[endpoint]]
+        url = "http://127.0.0.1:8080/payments"
+        events = ["transaction-accepted"]
+        addresses = ["karlsen:qpauqsvk7yf9unexwmxsnmg547mhyga37csh0kj53q6xxgl24ydxjsgzthw5j"]
+    "#;
+
+    #[test]
+    fn test_parse_and_validate() {
+        let config = WebhookConfig::parse(CONFIG).unwrap();
+        assert_eq!(config.reorg_depth, 5);
+        assert_eq!(config.max_retries, WebhookConfig::default().max_retries);
+        assert_eq!(config.endpoints.len(), 2);
+        assert_eq!(config.endpoints[0].secret.as_deref(), Some("secret"));
+        assert!(config.endpoints[1].subscribes_to(WebhookEventKind::TransactionAccepted));
+        assert_eq!(config.scopes().len(), 2);
+
+        assert!(config.validate(Prefix::Mainnet).is_ok());
+        assert!(matches!(
+            config.validate(Prefix::Testnet),
+            Err(WebhookError::AddressNetworkMismatch(_, _))
+        ));
+        assert!(matches!(
+            WebhookConfig::parse("").unwrap().validate(Prefix::Mainnet),
+            Err(WebhookError::NoEndpoint)
+        ));
+    }
+
+    #[test]
+    fn test_backoff() {
+        let config = WebhookConfig {
+            initial_backoff_ms: 100,
+            max_backoff_ms: 1_000,
+            ..Default::default()
+        };
+        assert_eq!(config.backoff(1), Duration::from_millis(100));
+        assert_eq!(config.backoff(2), Duration::from_millis(200));
+        assert_eq!(config.backoff(4), Duration::from_millis(800));
+        assert_eq!(config.backoff(5), Duration::from_millis(1_000));
+        assert_eq!(config.backoff(100), Duration::from_millis(1_000));
+    }
+}
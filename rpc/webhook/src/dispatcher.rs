@@ -0,0 +1,179 @@
+use crate::{
+    config::{EndpointConfig, WebhookConfig, WebhookEventKind},
+    error::{WebhookError, WebhookResult},
+};
+use async_channel::{bounded, Receiver, Sender, TrySendError};
+use hmac::{Hmac, Mac};
+use karlsen_core::{karlsend_env::version, trace, warn};
+use reqwest::{header::CONTENT_TYPE, Client};
+use sha2::Sha256;
+use std::sync::Arc;
+use tokio::task::JoinHandle;
+
+/// Header carrying the HMAC-SHA256 signature of the body, formatted as `sha256=<hex>`
+pub const SIGNATURE_HEADER: &str = "X-Karlsen-Signature";
+/// Header carrying the event kind of the payload
+pub const EVENT_HEADER: &str = "X-Karlsen-Event";
+/// Header carrying the sequence number of the event, identical across retries
+pub const DELIVERY_HEADER: &str = "X-Karlsen-Delivery";
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Signs `body` with `secret`, returning the value of the [`SIGNATURE_HEADER`] header
+pub fn sign(secret: &[u8], body: &[u8]) -> String {
+    let mut mac = HmacSha256::new_from_slice(secret).expect("HMAC accepts keys of any size");
+    mac.update(body);
+    format!("sha256={}", hex::encode(mac.finalize().into_bytes()))
+}
+
+/// A serialized payload waiting to be delivered to an endpoint
+#[derive(Debug, Clone)]
+pub struct Delivery {
+    pub id: u64,
+    pub event: WebhookEventKind,
+    pub body: Arc<Vec<u8>>,
+}
+
+/// Delivers payloads to the configured endpoints.
+///
+/// Every endpoint is served by its own task and queue so a slow or unreachable
+/// receiver neither delays the others nor the node notification pipeline.
+/// Payloads of a given endpoint are delivered in order.
+pub struct Dispatcher {
+    senders: Vec<Sender<Delivery>>,
+    workers: Vec<JoinHandle<()>>,
+}
+
+impl Dispatcher {
+    pub fn new(config: Arc<WebhookConfig>) -> WebhookResult<Self> {
+        let client = Client::builder()
+            .timeout(config.request_timeout())
+            .user_agent(format!("karlsend/{}", version()))
+            .build()?;
+        let (senders, workers) = (0..config.endpoints.len())
+            .map(|index| {
+                let (sender, receiver) = bounded(config.queue_capacity.max(1));
+                let worker = tokio::spawn(Self::run_worker(
+                    client.clone(),
+                    config.clone(),
+                    index,
+                    receiver,
+                ));
+                (sender, worker)
+            })
+            .unzip();
+        Ok(Self { senders, workers })
+    }
+
+    /// Queues `delivery` for the endpoint at `index` in the config, dropping it if the queue is full
+    pub fn dispatch(&self, index: usize, delivery: Delivery) {
+        match self.senders[index].try_send(delivery) {
+            Ok(_) => {}
+            Err(TrySendError::Full(delivery)) => {
+                warn!(
+                    "Webhook endpoint #{} queue is full, dropping {} event {}",
+                    index, delivery.event, delivery.id
+                );
+            }
+            Err(TrySendError::Closed(_)) => {}
+        }
+    }
+
+    /// Stops all endpoint tasks, discarding the payloads not delivered yet
+    pub fn close(&self) {
+        self.senders.iter().for_each(|x| {
+            x.close();
+        });
+        self.workers.iter().for_each(|x| x.abort());
+    }
+
+    async fn run_worker(
+        client: Client,
+        config: Arc<WebhookConfig>,
+        index: usize,
+        receiver: Receiver<Delivery>,
+    ) {
+        let endpoint = &config.endpoints[index];
+        while let Ok(delivery) = receiver.recv().await {
+            let mut attempt = 0;
+            loop {
+                match Self::deliver(&client, endpoint, &delivery).await {
+                    Ok(_) => {
+                        trace!(
+                            "Webhook {} event {} delivered to {}",
+                            delivery.event,
+                            delivery.id,
+                            endpoint.url
+                        );
+                        break;
+                    }
+                    Err(err) if attempt < config.max_retries => {
+                        attempt += 1;
+                        let delay = config.backoff(attempt);
+                        trace!(
+                            "Webhook {} event {} delivery to {} failed ({}), retrying in {:?}",
+                            delivery.event,
+                            delivery.id,
+                            endpoint.url,
+                            err,
+                            delay
+                        );
+                        tokio::time::sleep(delay).await;
+                    }
+                    Err(err) => {
+                        warn!(
+                            "Webhook {} event {} could not be delivered to {} after {} attempts: {}",
+                            delivery.event,
+                            delivery.id,
+                            endpoint.url,
+                            attempt + 1,
+                            err
+                        );
+                        break;
+                    }
+                }
+            }
+        }
+    }
+
+    async fn deliver(
+        client: &Client,
+        endpoint: &EndpointConfig,
+        delivery: &Delivery,
+    ) -> WebhookResult<()> {
+        let mut request = client
+            .post(&endpoint.url)
+            .header(CONTENT_TYPE, "application/json")
+            .header(EVENT_HEADER, delivery.event.to_string())
+            .header(DELIVERY_HEADER, delivery.id.to_string());
+        if let Some(secret) = endpoint.secret.as_ref() {
+            request = request.header(SIGNATURE_HEADER, sign(secret.as_bytes(), &delivery.body));
+        }
+        let response = request.body(delivery.body.as_ref().clone()).send().await?;
+        match response.status().is_success() {
+            true => Ok(()),
+            false => Err(WebhookError::Status(response.status().as_u16())),
+        }
+    }
+}
+
+impl Drop for Dispatcher {
+    fn drop(&mut self) {
+        self.close();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sign() {
+        // Test vector from RFC 4231, test case 2
+        let signature = sign(b"Jefe", b"what do ya want for nothing?");
+        assert_eq!(
+            signature,
+            "sha256=5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+    }
+}
@@ -0,0 +1,42 @@
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum WebhookError {
+    #[error("failed reading webhooks config file {0}: {1}")]
+    ConfigFile(String, std::io::Error),
+
+    #[error("failed parsing webhooks config file: {0}")]
+    ConfigParse(#[from] toml::de::Error),
+
+    #[error("webhooks config has no endpoint")]
+    NoEndpoint,
+
+    #[error("webhook endpoint {0} subscribes to no event")]
+    NoEvent(String),
+
+    #[error("webhook endpoint {0} has an invalid url")]
+    InvalidUrl(String),
+
+    #[error("webhook endpoint {0} subscribes to transaction-accepted but watches no address")]
+    NoWatchedAddress(String),
+
+    #[error("webhook endpoint {0} watches address {1} which does not belong to the node network")]
+    AddressNetworkMismatch(String, String),
+
+    #[error("webhooks config reorg-depth must be greater than 0")]
+    InvalidReorgDepth,
+
+    #[error("HTTP client error: {0}")]
+    Http(#[from] reqwest::Error),
+
+    #[error("endpoint responded with HTTP status {0}")]
+    Status(u16),
+
+    #[error("JSON serialization error: {0}")]
+    Json(#[from] serde_json::Error),
+
+    #[error("notification subsystem error: {0}")]
+    Notification(#[from] karlsen_notify::error::Error),
+}
+
+pub type WebhookResult<T> = std::result::Result<T, WebhookError>;
@@ -0,0 +1,15 @@
+//! Webhook dispatcher
+//!
+//! An optional node subsystem POSTing JSON payloads to operator-provided URLs
+//! whenever one of the configured events occurs (block added, transaction accepted
+//! involving a watched address, deep reorg).
+//!
+//! Deliveries are retried with an exponential backoff and, when a secret is
+//! configured for an endpoint, signed with HMAC-SHA256 so the receiver can
+//! authenticate their origin.
+
+pub mod config;
+pub mod dispatcher;
+pub mod error;
+pub mod payload;
+pub mod service;
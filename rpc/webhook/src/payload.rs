@@ -0,0 +1,93 @@
+use crate::config::WebhookEventKind;
+use karlsen_rpc_core::{
+    RpcAddress, RpcHash, RpcTransactionId, RpcTransactionOutpoint, RpcUtxoEntry,
+};
+use serde::Serialize;
+
+/// JSON body POSTed to the webhook endpoints
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WebhookPayload {
+    /// Sequence number of the event, unique for the lifetime of the node process
+    pub id: u64,
+    pub event: WebhookEventKind,
+    /// Network the node is running on (ie. `mainnet`, `testnet-11`)
+    pub network: String,
+    /// Unix time in milliseconds when the event was dispatched
+    pub timestamp: u64,
+    pub data: WebhookEventData,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(untagged)]
+pub enum WebhookEventData {
+    BlockAdded(BlockAddedData),
+    TransactionAccepted(TransactionAcceptedData),
+    DeepReorg(DeepReorgData),
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockAddedData {
+    pub hash: RpcHash,
+    pub timestamp: u64,
+    pub daa_score: u64,
+    pub blue_score: u64,
+    pub transaction_count: usize,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionAcceptedData {
+    pub transaction_id: RpcTransactionId,
+    /// Chain block whose mergeset accepted the transaction
+    pub accepting_block_hash: RpcHash,
+    /// Outputs paying to a watched address
+    pub received: Vec<UtxoChange>,
+    /// Outputs of a watched address spent by an accepted transaction
+    pub spent: Vec<UtxoChange>,
+}
+
+impl TransactionAcceptedData {
+    pub fn is_empty(&self) -> bool {
+        self.received.is_empty() && self.spent.is_empty()
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UtxoChange {
+    pub address: RpcAddress,
+    /// Outpoint of the output: the id of the transaction that created it and its index in the
+    /// transaction outputs. For a spent output, this is the outpoint spent by the accepted
+    /// transaction, not the accepted transaction itself.
+    pub outpoint: RpcTransactionOutpoint,
+    pub amount: u64,
+    pub block_daa_score: u64,
+    pub is_coinbase: bool,
+}
+
+impl UtxoChange {
+    pub fn new(
+        address: RpcAddress,
+        outpoint: RpcTransactionOutpoint,
+        entry: &RpcUtxoEntry,
+    ) -> Self {
+        Self {
+            address,
+            outpoint,
+            amount: entry.amount,
+            block_daa_score: entry.block_daa_score,
+            is_coinbase: entry.is_coinbase,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeepReorgData {
+    /// Count of chain blocks removed from the virtual selected chain
+    pub depth: usize,
+    pub removed_chain_block_hashes: Vec<RpcHash>,
+    pub added_chain_block_hashes: Vec<RpcHash>,
+}
@@ -0,0 +1,601 @@
+use crate::{
+    config::{WebhookConfig, WebhookEventKind},
+    dispatcher::{Delivery, Dispatcher},
+    error::WebhookResult,
+    payload::{
+        BlockAddedData, DeepReorgData, TransactionAcceptedData, UtxoChange, WebhookEventData,
+        WebhookPayload,
+    },
+};
+use futures::{select_biased, FutureExt};
+use karlsen_addresses::Address;
+use karlsen_consensus_core::{
+    errors::consensus::ConsensusResult,
+    tx::{ScriptPublicKey, Transaction, TransactionOutpoint, UtxoEntry},
+    utxo::utxo_collection::UtxoCollection,
+};
+use karlsen_consensusmanager::{ConsensusManager, ConsensusProxy};
+use karlsen_core::{
+    debug, info,
+    task::service::{AsyncService, AsyncServiceError, AsyncServiceFuture},
+    time::unix_now,
+    trace, warn,
+};
+use karlsen_notify::{connection::ChannelType, listener::ListenerLifespan};
+use karlsen_rpc_core::{
+    notify::{channel::NotificationChannel, connection::ChannelConnection},
+    BlockAddedNotification, Notification, RpcHash, VirtualChainChangedNotification,
+};
+use karlsen_rpc_service::service::RpcCoreService;
+use karlsen_txscript::standard::pay_to_address_script;
+use karlsen_utils::triggers::SingleTrigger;
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+};
+
+/// A transaction accepted by a chain block, along with the entries of the outputs it spends
+struct AcceptedTransaction {
+    accepting_block_hash: RpcHash,
+    accepting_daa_score: u64,
+    transaction: Transaction,
+    /// Entry spent by every input of the transaction, `None` if it could not be resolved
+    spent_entries: Vec<Option<UtxoEntry>>,
+}
+
+/// Turns the RPC core notifications into the deliveries of the endpoints subscribing to them
+struct WebhookEvents {
+    config: Arc<WebhookConfig>,
+    network: String,
+    /// Scripts of the watched addresses of every endpoint, indexed like `config.endpoints`
+    watched_scripts: Vec<HashMap<ScriptPublicKey, Address>>,
+    sequence: AtomicU64,
+}
+
+impl WebhookEvents {
+    fn new(config: Arc<WebhookConfig>, network: String) -> Self {
+        let watched_scripts = config
+            .endpoints
+            .iter()
+            .map(|x| {
+                x.addresses
+                    .iter()
+                    .map(|address| (pay_to_address_script(address), address.clone()))
+                    .collect()
+            })
+            .collect();
+        Self {
+            config,
+            network,
+            watched_scripts,
+            sequence: AtomicU64::new(0),
+        }
+    }
+
+    /// Returns the deliveries of `notification`, along with the index of their endpoint in the config
+    fn deliveries(&self, notification: Notification) -> Vec<(usize, Delivery)> {
+        match notification {
+            Notification::BlockAdded(notification) => self.block_added(notification),
+            Notification::VirtualChainChanged(notification) => {
+                self.virtual_chain_changed(notification)
+            }
+            _ => vec![],
+        }
+    }
+
+    fn block_added(&self, notification: BlockAddedNotification) -> Vec<(usize, Delivery)> {
+        let header = &notification.block.header;
+        let data = WebhookEventData::BlockAdded(BlockAddedData {
+            hash: header.hash,
+            timestamp: header.timestamp,
+            daa_score: header.daa_score,
+            blue_score: header.blue_score,
+            transaction_count: notification.block.transactions.len(),
+        });
+        self.broadcast(WebhookEventKind::BlockAdded, data)
+    }
+
+    fn virtual_chain_changed(
+        &self,
+        notification: VirtualChainChangedNotification,
+    ) -> Vec<(usize, Delivery)> {
+        let depth = notification.removed_chain_block_hashes.len();
+        if depth < self.config.reorg_depth {
+            return vec![];
+        }
+        info!("Virtual chain reorg of depth {} detected", depth);
+        let data = WebhookEventData::DeepReorg(DeepReorgData {
+            depth,
+            removed_chain_block_hashes: notification.removed_chain_block_hashes.as_ref().clone(),
+            added_chain_block_hashes: notification.added_chain_block_hashes.as_ref().clone(),
+        });
+        self.broadcast(WebhookEventKind::DeepReorg, data)
+    }
+
+    /// Returns one delivery per accepted transaction involving the watched addresses of an endpoint
+    fn transactions_accepted(
+        &self,
+        transactions: &[AcceptedTransaction],
+    ) -> Vec<(usize, Delivery)> {
+        let mut deliveries = vec![];
+        for accepted in transactions {
+            let transaction_id = accepted.transaction.id();
+            let is_coinbase = accepted.transaction.is_coinbase();
+            for (index, endpoint) in self.config.endpoints.iter().enumerate() {
+                if !endpoint.subscribes_to(WebhookEventKind::TransactionAccepted) {
+                    continue;
+                }
+                let watched = &self.watched_scripts[index];
+                let received = accepted
+                    .transaction
+                    .outputs
+                    .iter()
+                    .enumerate()
+                    .filter_map(|(i, output)| {
+                        watched.get(&output.script_public_key).map(|address| {
+                            let entry = UtxoEntry::new(
+                                output.value,
+                                output.script_public_key.clone(),
+                                accepted.accepting_daa_score,
+                                is_coinbase,
+                            );
+                            let outpoint = TransactionOutpoint::new(transaction_id, i as u32);
+                            UtxoChange::new(address.clone(), outpoint, &entry)
+                        })
+                    })
+                    .collect();
+                let spent = accepted
+                    .transaction
+                    .inputs
+                    .iter()
+                    .zip(accepted.spent_entries.iter())
+                    .filter_map(|(input, entry)| {
+                        let entry = entry.as_ref()?;
+                        watched.get(&entry.script_public_key).map(|address| {
+                            UtxoChange::new(address.clone(), input.previous_outpoint, entry)
+                        })
+                    })
+                    .collect();
+                let data = TransactionAcceptedData {
+                    transaction_id,
+                    accepting_block_hash: accepted.accepting_block_hash,
+                    received,
+                    spent,
+                };
+                if data.is_empty() {
+                    continue;
+                }
+                let event = WebhookEventKind::TransactionAccepted;
+                if let Some(delivery) =
+                    self.delivery(event, WebhookEventData::TransactionAccepted(data))
+                {
+                    deliveries.push((index, delivery));
+                }
+            }
+        }
+        deliveries
+    }
+
+    /// Returns the deliveries of an event to all the endpoints subscribing to it
+    fn broadcast(&self, event: WebhookEventKind, data: WebhookEventData) -> Vec<(usize, Delivery)> {
+        let Some(delivery) = self.delivery(event, data) else {
+            return vec![];
+        };
+        self.config
+            .endpoints
+            .iter()
+            .enumerate()
+            .filter(|(_, endpoint)| endpoint.subscribes_to(event))
+            .map(|(index, _)| (index, delivery.clone()))
+            .collect()
+    }
+
+    fn delivery(&self, event: WebhookEventKind, data: WebhookEventData) -> Option<Delivery> {
+        let id = self.sequence.fetch_add(1, Ordering::Relaxed);
+        let payload = WebhookPayload {
+            id,
+            event,
+            network: self.network.clone(),
+            timestamp: unix_now(),
+            data,
+        };
+        match serde_json::to_vec(&payload) {
+            Ok(body) => Some(Delivery {
+                id,
+                event,
+                body: Arc::new(body),
+            }),
+            Err(err) => {
+                warn!(
+                    "Webhook {} event {} serialization failed: {}",
+                    event, id, err
+                );
+                None
+            }
+        }
+    }
+}
+
+/// Listens to the RPC core notifications and turns the ones matching the
+/// configured events into webhook deliveries.
+pub struct WebhookService {
+    config: Arc<WebhookConfig>,
+    core_service: Arc<RpcCoreService>,
+    consensus_manager: Arc<ConsensusManager>,
+    events: WebhookEvents,
+    shutdown: SingleTrigger,
+}
+
+impl WebhookService {
+    pub const IDENT: &'static str = "webhook-service";
+
+    pub fn new(
+        config: WebhookConfig,
+        core_service: Arc<RpcCoreService>,
+        consensus_manager: Arc<ConsensusManager>,
+        network: String,
+    ) -> Self {
+        let config = Arc::new(config);
+        Self {
+            events: WebhookEvents::new(config.clone(), network),
+            config,
+            core_service,
+            consensus_manager,
+            shutdown: Default::default(),
+        }
+    }
+
+    /// Returns the deliveries of `notification`, reading the transactions accepted by the
+    /// added chain blocks when some endpoint subscribes to them
+    async fn deliveries(&self, notification: Notification) -> Vec<(usize, Delivery)> {
+        let mut deliveries = self.events.deliveries(notification.clone());
+        if let Notification::VirtualChainChanged(notification) = notification {
+            if self.config.has_event(WebhookEventKind::TransactionAccepted) {
+                let transactions = self.accepted_transactions(&notification).await;
+                deliveries.extend(self.events.transactions_accepted(&transactions));
+            }
+        }
+        deliveries
+    }
+
+    async fn accepted_transactions(
+        &self,
+        notification: &VirtualChainChangedNotification,
+    ) -> Vec<AcceptedTransaction> {
+        let session = self.consensus_manager.consensus().session().await;
+        let mut transactions = vec![];
+        for hash in notification.added_chain_block_hashes.iter().copied() {
+            match Self::chain_block_transactions(&session, hash).await {
+                Ok(accepted) => transactions.extend(accepted),
+                Err(err) => warn!(
+                    "Webhook could not read the transactions accepted by chain block {}: {}",
+                    hash, err
+                ),
+            }
+        }
+        transactions
+    }
+
+    /// Reads the transactions accepted by the mergeset of chain block `hash`, in acceptance order
+    async fn chain_block_transactions(
+        session: &ConsensusProxy,
+        hash: RpcHash,
+    ) -> ConsensusResult<Vec<AcceptedTransaction>> {
+        let daa_score = session.async_get_header(hash).await?.daa_score;
+        let acceptance_data = session.async_get_block_acceptance_data(hash).await?;
+        let utxo_diff = session.async_get_block_utxo_diff(hash).await?;
+        let mut transactions = vec![];
+        for mergeset_block in acceptance_data.iter() {
+            let block = session.async_get_block(mergeset_block.block_hash).await?;
+            transactions.extend(mergeset_block.accepted_transactions.iter().filter_map(|x| {
+                block
+                    .transactions
+                    .get(x.index_within_block as usize)
+                    .cloned()
+            }));
+        }
+
+        // The UTXO diff of the chain block nets out the outputs created and spent within its
+        // mergeset, so those are resolved from the accepted transactions themselves
+        let created: UtxoCollection = transactions
+            .iter()
+            .flat_map(|tx| {
+                let (id, is_coinbase) = (tx.id(), tx.is_coinbase());
+                tx.outputs.iter().enumerate().map(move |(i, output)| {
+                    let entry = UtxoEntry::new(
+                        output.value,
+                        output.script_public_key.clone(),
+                        daa_score,
+                        is_coinbase,
+                    );
+                    (TransactionOutpoint::new(id, i as u32), entry)
+                })
+            })
+            .collect();
+        Ok(transactions
+            .into_iter()
+            .map(|transaction| {
+                let spent_entries = transaction
+                    .inputs
+                    .iter()
+                    .map(|input| {
+                        utxo_diff
+                            .remove
+                            .get(&input.previous_outpoint)
+                            .or_else(|| created.get(&input.previous_outpoint))
+                            .cloned()
+                    })
+                    .collect();
+                AcceptedTransaction {
+                    accepting_block_hash: hash,
+                    accepting_daa_score: daa_score,
+                    transaction,
+                    spent_entries,
+                }
+            })
+            .collect())
+    }
+
+    async fn run(self: Arc<Self>) -> WebhookResult<()> {
+        let shutdown_signal = self.shutdown.listener.clone();
+        let notifier = self.core_service.notifier();
+        let channel = NotificationChannel::default();
+        let listener_id = notifier.register_new_listener(
+            ChannelConnection::new(Self::IDENT, channel.sender(), ChannelType::Closable),
+            ListenerLifespan::Dynamic,
+        );
+        for scope in self.config.scopes() {
+            notifier.try_start_notify(listener_id, scope)?;
+        }
+
+        let dispatcher = Dispatcher::new(self.config.clone())?;
+        info!(
+            "Webhook dispatcher started with {} endpoint(s)",
+            self.config.endpoints.len()
+        );
+
+        let receiver = channel.receiver();
+        loop {
+            select_biased! {
+                _ = shutdown_signal.clone().fuse() => break,
+                notification = receiver.recv().fuse() => match notification {
+                    Ok(notification) => self
+                        .deliveries(notification)
+                        .await
+                        .into_iter()
+                        .for_each(|(index, delivery)| dispatcher.dispatch(index, delivery)),
+                    Err(_) => {
+                        debug!("{} notification channel was closed", Self::IDENT);
+                        break;
+                    }
+                },
+            }
+        }
+
+        if let Err(err) = notifier.unregister_listener(listener_id) {
+            trace!(
+                "{} error while unregistering its listener: {}",
+                Self::IDENT,
+                err
+            );
+        }
+        dispatcher.close();
+        Ok(())
+    }
+}
+
+impl AsyncService for WebhookService {
+    fn ident(self: Arc<Self>) -> &'static str {
+        Self::IDENT
+    }
+
+    fn start(self: Arc<Self>) -> AsyncServiceFuture {
+        trace!("{} starting", Self::IDENT);
+        Box::pin(async move {
+            self.run()
+                .await
+                .map_err(|err| AsyncServiceError::Service(err.to_string()))
+        })
+    }
+
+    fn signal_exit(self: Arc<Self>) {
+        trace!("sending an exit signal to {}", Self::IDENT);
+        self.shutdown.trigger.trigger();
+    }
+
+    fn stop(self: Arc<Self>) -> AsyncServiceFuture {
+        Box::pin(async move {
+            trace!("{} stopped", Self::IDENT);
+            Ok(())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::EndpointConfig;
+    use karlsen_addresses::{Prefix, Version};
+    use karlsen_consensus_core::{
+        subnets::SUBNETWORK_ID_NATIVE,
+        tx::{TransactionInput, TransactionOutput},
+    };
+    use serde_json::Value;
+
+    fn address(byte: u8) -> Address {
+        Address::new(Prefix::Mainnet, Version::PubKey, &[byte; 32])
+    }
+
+    fn transaction(inputs: Vec<TransactionOutpoint>, outputs: Vec<(Address, u64)>) -> Transaction {
+        Transaction::new(
+            0,
+            inputs
+                .into_iter()
+                .map(|outpoint| TransactionInput::new(outpoint, vec![], 0, 1))
+                .collect(),
+            outputs
+                .into_iter()
+                .map(|(address, value)| {
+                    TransactionOutput::new(value, pay_to_address_script(&address))
+                })
+                .collect(),
+            0,
+            SUBNETWORK_ID_NATIVE,
+            0,
+            vec![],
+        )
+    }
+
+    fn events() -> WebhookEvents {
+        let endpoint = |events: Vec<WebhookEventKind>, addresses: Vec<Address>| EndpointConfig {
+            url: "https://example.com".to_string(),
+            secret: None,
+            events,
+            addresses,
+        };
+        let config = WebhookConfig {
+            reorg_depth: 2,
+            endpoints: vec![
+                endpoint(vec![WebhookEventKind::DeepReorg], vec![]),
+                endpoint(
+                    vec![WebhookEventKind::TransactionAccepted],
+                    vec![address(1)],
+                ),
+                endpoint(
+                    vec![
+                        WebhookEventKind::TransactionAccepted,
+                        WebhookEventKind::DeepReorg,
+                    ],
+                    vec![address(2)],
+                ),
+            ],
+            ..Default::default()
+        };
+        WebhookEvents::new(Arc::new(config), "mainnet".to_string())
+    }
+
+    fn body(delivery: &Delivery) -> Value {
+        serde_json::from_slice(&delivery.body).unwrap()
+    }
+
+    #[test]
+    fn test_transaction_accepted_deliveries() {
+        let events = events();
+        let block_hash = RpcHash::from_u64_word(5);
+        let previous_outpoint = TransactionOutpoint::new(RpcHash::from_u64_word(20), 3);
+        let previous_entry = UtxoEntry::new(100, pay_to_address_script(&address(2)), 7, false);
+
+        // The first transaction spends an output of address 2 and pays to addresses 1 and 3,
+        // the second one spends the output of address 1 in the same mergeset and pays to address 2
+        let funding = transaction(
+            vec![previous_outpoint],
+            vec![(address(1), 60), (address(3), 40)],
+        );
+        let spending = transaction(
+            vec![TransactionOutpoint::new(funding.id(), 0)],
+            vec![(address(2), 60)],
+        );
+        let accepted = |transaction: &Transaction, spent_entries: Vec<Option<UtxoEntry>>| {
+            AcceptedTransaction {
+                accepting_block_hash: block_hash,
+                accepting_daa_score: 9,
+                transaction: transaction.clone(),
+                spent_entries,
+            }
+        };
+        let transactions = vec![
+            accepted(&funding, vec![Some(previous_entry)]),
+            accepted(
+                &spending,
+                vec![Some(UtxoEntry::new(
+                    60,
+                    pay_to_address_script(&address(1)),
+                    9,
+                    false,
+                ))],
+            ),
+        ];
+        let deliveries = events.transactions_accepted(&transactions);
+
+        // Every endpoint only gets the transactions involving its own watched addresses
+        assert_eq!(
+            deliveries
+                .iter()
+                .map(|(index, _)| *index)
+                .collect::<Vec<_>>(),
+            vec![1, 2, 1, 2]
+        );
+        let received = body(&deliveries[0].1);
+        assert_eq!(received["event"], "transaction-accepted");
+        assert_eq!(received["network"], "mainnet");
+        assert_eq!(received["data"]["transactionId"], funding.id().to_string());
+        assert_eq!(
+            received["data"]["acceptingBlockHash"],
+            block_hash.to_string()
+        );
+        assert_eq!(received["data"]["received"].as_array().unwrap().len(), 1);
+        assert!(received["data"]["spent"].as_array().unwrap().is_empty());
+        assert_eq!(
+            received["data"]["received"][0]["address"],
+            address(1).to_string()
+        );
+        assert_eq!(received["data"]["received"][0]["blockDaaScore"], 9);
+
+        // A spent entry carries the outpoint it spends
+        let spent = body(&deliveries[1].1);
+        let outpoint = &spent["data"]["spent"][0]["outpoint"];
+        assert_eq!(
+            outpoint["transactionId"],
+            RpcHash::from_u64_word(20).to_string()
+        );
+        assert_eq!(outpoint["index"], 3);
+        assert_eq!(spent["data"]["spent"][0]["amount"], 100);
+
+        // An output created and spent within the same mergeset is reported on both sides
+        let spending_body = body(&deliveries[2].1);
+        assert_eq!(
+            spending_body["data"]["transactionId"],
+            spending.id().to_string()
+        );
+        assert_eq!(
+            spending_body["data"]["spent"][0]["outpoint"]["transactionId"],
+            funding.id().to_string()
+        );
+        assert_eq!(body(&deliveries[3].1)["data"]["received"][0]["amount"], 60);
+
+        // Sequence numbers are unique
+        assert_ne!(deliveries[0].1.id, deliveries[1].1.id);
+    }
+
+    #[test]
+    fn test_deep_reorg_deliveries() {
+        let events = events();
+        let notification = |depth: u64| {
+            Notification::VirtualChainChanged(VirtualChainChangedNotification {
+                removed_chain_block_hashes: Arc::new(
+                    (0..depth).map(RpcHash::from_u64_word).collect(),
+                ),
+                added_chain_block_hashes: Arc::new(vec![RpcHash::from_u64_word(100)]),
+                accepted_transaction_ids: Arc::new(vec![]),
+            })
+        };
+        assert!(events.deliveries(notification(1)).is_empty());
+
+        let deliveries = events.deliveries(notification(2));
+        assert_eq!(
+            deliveries
+                .iter()
+                .map(|(index, _)| *index)
+                .collect::<Vec<_>>(),
+            vec![0, 2]
+        );
+        // A broadcast event is serialized once for all its endpoints
+        assert!(Arc::ptr_eq(&deliveries[0].1.body, &deliveries[1].1.body));
+        let reorg = body(&deliveries[0].1);
+        assert_eq!(reorg["event"], "deep-reorg");
+        assert_eq!(reorg["data"]["depth"], 2);
+    }
+}
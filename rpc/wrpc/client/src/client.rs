@@ -26,6 +26,9 @@ struct Inner {
     rpc_client: Arc<RpcClient<RpcApiOps>>,
     notification_relay_channel: Channel<Notification>,
     notification_intake_channel: Mutex<Channel<Notification>>,
+    block_added_stream_channel: Channel<BlockAddedStreamNotification>,
+    durable_notification_channel: Channel<DurableNotification>,
+    notification_gap_channel: Channel<NotificationGapNotification>,
     notifier: Arc<Mutex<Option<RpcClientNotifier>>>,
     encoding: Encoding,
     wrpc_ctl_multiplexer: Multiplexer<WrpcCtl>,
@@ -57,6 +60,9 @@ impl Inner {
 
         let notification_relay_channel = Channel::unbounded();
         let notification_intake_channel = Mutex::new(Channel::unbounded());
+        let block_added_stream_channel = Channel::unbounded();
+        let durable_notification_channel = Channel::unbounded();
+        let notification_gap_channel = Channel::unbounded();
 
         // The `Interface` struct can be used to register for server-side
         // notifications. All notification methods have to be created at
@@ -73,6 +79,8 @@ impl Inner {
             RpcApiOps::VirtualDaaScoreChangedNotification,
             RpcApiOps::PruningPointUtxoSetOverrideNotification,
             RpcApiOps::NewBlockTemplateNotification,
+            RpcApiOps::TransactionStatusChangedNotification,
+            RpcApiOps::DoubleSpendDetectedNotification,
         ]
         .into_iter()
         .for_each(|notification_op| {
@@ -96,6 +104,58 @@ impl Inner {
             );
         });
 
+        // Blocks of the block added stream are relayed as is to a dedicated channel
+        let block_added_stream_sender = block_added_stream_channel.sender.clone();
+        interface.notification(
+            RpcApiOps::BlockAddedStreamNotification,
+            workflow_rpc::client::Notification::new(
+                move |notification: BlockAddedStreamNotification| {
+                    let block_added_stream_sender = block_added_stream_sender.clone();
+                    Box::pin(async move {
+                        block_added_stream_sender.send(notification).await?;
+                        Ok(())
+                    })
+                },
+            ),
+        );
+
+        // Notifications of durable subscriptions carry their sequence number and are
+        // relayed as is to a dedicated channel
+        let durable_notification_sender = durable_notification_channel.sender.clone();
+        interface.notification(
+            RpcApiOps::DurableNotification,
+            workflow_rpc::client::Notification::new(move |notification: DurableNotification| {
+                let durable_notification_sender = durable_notification_sender.clone();
+                Box::pin(async move {
+                    durable_notification_sender.send(notification).await?;
+                    Ok(())
+                })
+            }),
+        );
+
+        // Gap markers report notifications dropped by the server from the overflowing
+        // queue of the connection
+        let notification_gap_sender = notification_gap_channel.sender.clone();
+        interface.notification(
+            RpcApiOps::NotificationGapNotification,
+            workflow_rpc::client::Notification::new(
+                move |notification: NotificationGapNotification| {
+                    let notification_gap_sender = notification_gap_sender.clone();
+                    Box::pin(async move {
+                        if notification_gap_sender.receiver_count() > 1 {
+                            notification_gap_sender.send(notification).await?;
+                        } else {
+                            log_warn!(
+                                "WARNING: Karlsen RPC server dropped notifications: {:?}",
+                                notification
+                            );
+                        }
+                        Ok(())
+                    })
+                },
+            ),
+        );
+
         let rpc = Arc::new(RpcClient::new_with_encoding(
             encoding,
             interface.into(),
@@ -106,6 +166,9 @@ impl Inner {
             rpc_client: rpc,
             notification_relay_channel,
             notification_intake_channel,
+            block_added_stream_channel,
+            durable_notification_channel,
+            notification_gap_channel,
             notifier: Default::default(),
             encoding,
             wrpc_ctl_multiplexer,
@@ -534,6 +597,117 @@ impl KarlsenRpcClient {
             .clone()
     }
 
+    /// Receiver of the blocks of the block added stream opened by this client
+    pub fn block_added_stream_channel_receiver(&self) -> Receiver<BlockAddedStreamNotification> {
+        self.inner.block_added_stream_channel.receiver.clone()
+    }
+
+    /// Opens a block added stream replaying the blocks retained by the node after `cursor`,
+    /// then pushing the blocks added to the DAG, to the block added stream channel.
+    ///
+    /// Clients track the hash of the last block they processed and open the stream again
+    /// with it after every reconnection.
+    pub async fn subscribe_block_added(
+        &self,
+        cursor: Option<RpcHash>,
+    ) -> RpcResult<SubscribeBlockAddedResponse> {
+        let response: SubscribeBlockAddedResponse = self
+            .inner
+            .rpc_client
+            .call(
+                RpcApiOps::SubscribeBlockAdded,
+                SubscribeBlockAddedRequest::new(cursor),
+            )
+            .await
+            .map_err(|err| err.to_string())?;
+        Ok(response)
+    }
+
+    /// Closes the block added stream opened by this client
+    pub async fn unsubscribe_block_added(&self) -> RpcResult<()> {
+        let _: UnsubscribeBlockAddedResponse = self
+            .inner
+            .rpc_client
+            .call(
+                RpcApiOps::UnsubscribeBlockAdded,
+                UnsubscribeBlockAddedRequest {},
+            )
+            .await
+            .map_err(|err| err.to_string())?;
+        Ok(())
+    }
+
+    /// Receiver of the notifications of the durable subscriptions resumed by this client
+    pub fn durable_notification_channel_receiver(&self) -> Receiver<DurableNotification> {
+        self.inner.durable_notification_channel.receiver.clone()
+    }
+
+    /// Receiver of the gap markers sent by the server when it drops notifications
+    /// of this client from the overflowing queue of the connection
+    pub fn notification_gap_channel_receiver(&self) -> Receiver<NotificationGapNotification> {
+        self.inner.notification_gap_channel.receiver.clone()
+    }
+
+    /// Registers `scope` in the durable subscription identified by `token`, binding a new
+    /// subscription to `secret`.
+    ///
+    /// The notifications are journaled by the server until the subscription gets resumed
+    /// by [`KarlsenRpcClient::resume_durable`].
+    pub async fn subscribe_durable(
+        &self,
+        token: &str,
+        secret: &str,
+        scope: Scope,
+    ) -> RpcResult<SubscribeDurableResponse> {
+        let response: SubscribeDurableResponse = self
+            .inner
+            .rpc_client
+            .call(
+                RpcApiOps::SubscribeDurable,
+                SubscribeDurableRequest::new(token.to_string(), secret.to_string(), scope),
+            )
+            .await
+            .map_err(|err| err.to_string())?;
+        Ok(response)
+    }
+
+    /// Replays the notifications journaled since `next_sequence` to the durable notification
+    /// channel and keeps feeding it with the following ones.
+    ///
+    /// Clients track the sequence following the last notification they processed and
+    /// resume with it after every reconnection.
+    pub async fn resume_durable(
+        &self,
+        token: &str,
+        secret: &str,
+        next_sequence: u64,
+    ) -> RpcResult<ResumeDurableResponse> {
+        let response: ResumeDurableResponse = self
+            .inner
+            .rpc_client
+            .call(
+                RpcApiOps::ResumeDurable,
+                ResumeDurableRequest::new(token.to_string(), secret.to_string(), next_sequence),
+            )
+            .await
+            .map_err(|err| err.to_string())?;
+        Ok(response)
+    }
+
+    /// Drops the durable subscription identified by `token` along with its journal
+    pub async fn unsubscribe_durable(&self, token: &str, secret: &str) -> RpcResult<()> {
+        let _: UnsubscribeDurableResponse = self
+            .inner
+            .rpc_client
+            .call(
+                RpcApiOps::UnsubscribeDurable,
+                UnsubscribeDurableRequest::new(token.to_string(), secret.to_string()),
+            )
+            .await
+            .map_err(|err| err.to_string())?;
+        Ok(())
+    }
+
     pub fn ctl(&self) -> &RpcCtl {
         &self.inner.rpc_ctl
     }
@@ -664,17 +838,29 @@ impl RpcApi for KarlsenRpcClient {
         [
             AddPeer,
             Ban,
+            DebugScript,
             EstimateNetworkHashesPerSecond,
+            GetAcceptanceEventsSince,
+            GetAddressCluster,
             GetBalanceByAddress,
+            GetBalanceByAddressesAt,
             GetBalancesByAddresses,
             GetBlock,
             GetBlockCount,
             GetBlockDagInfo,
+            GetBlockFilterHeaders,
+            GetBlockFilters,
             GetBlocks,
             GetBlockTemplate,
             GetCoinSupply,
             GetConnectedPeerInfo,
             GetDaaScoreTimestampEstimate,
+            GetDagSlice,
+            GetDifficultyInfo,
+            GetMetadata,
+            GetMinerStats,
+            GetNetworkInfo,
+            GetServerCapabilities,
             GetServerInfo,
             GetCurrentNetwork,
             GetHeaders,
@@ -687,6 +873,9 @@ impl RpcApi for KarlsenRpcClient {
             GetSink,
             GetSyncStatus,
             GetSubnetwork,
+            GetTemplateDiff,
+            GetTransactionStatus,
+            GetUnconfirmedTxRisk,
             GetUtxosByAddresses,
             GetSinkBlueScore,
             GetVirtualChainFromBlock,
@@ -695,7 +884,9 @@ impl RpcApi for KarlsenRpcClient {
             Shutdown,
             SubmitBlock,
             SubmitTransaction,
+            TestMempoolAccept,
             Unban,
+            UpdateMetadata,
         ]
     );
 
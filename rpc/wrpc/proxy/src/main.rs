@@ -86,6 +86,9 @@ async fn main() -> Result<()> {
             grpc_proxy_address.unwrap_or_else(|| format!("grpc://127.0.0.1:{karlsend_port}")),
         ),
         verbose,
+        durable_subscriptions: None,
+        policy: Default::default(),
+        notification_queue: Default::default(),
         // ..Options::default()
     });
     log_info!("");
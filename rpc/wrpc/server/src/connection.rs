@@ -5,11 +5,16 @@ use karlsen_notify::{
     listener::ListenerId,
     notification::Notification as NotificationT,
     notifier::Notify,
+    queue::NotificationGap,
 };
-use karlsen_rpc_core::{api::ops::RpcApiOps, notify::mode::NotificationMode, Notification};
+use karlsen_rpc_core::{
+    api::ops::RpcApiOps, notify::mode::NotificationMode, BlockAddedStreamNotification,
+    Notification, NotificationGapNotification, RpcBlock,
+};
+use karlsen_rpc_service::journal::BlockAddedSink;
 use std::{
     fmt::{Debug, Display},
-    sync::{Arc, Mutex},
+    sync::{Arc, Mutex, MutexGuard},
 };
 use workflow_log::log_trace;
 use workflow_rpc::{
@@ -50,6 +55,8 @@ struct ConnectionInner {
     pub grpc_client: Option<Arc<GrpcClient>>,
     // not using an atomic in case an Id will change type in the future...
     pub listener_id: Mutex<Option<ListenerId>>,
+    /// Identifier of the block added stream of the connection in the block added journal
+    pub block_added_stream: Mutex<Option<u64>>,
 }
 
 impl ConnectionInner {
@@ -105,6 +112,7 @@ impl Connection {
                 messenger,
                 grpc_client,
                 listener_id,
+                block_added_stream: Mutex::new(None),
             }),
         }
     }
@@ -141,6 +149,11 @@ impl Connection {
         &self.inner.peer
     }
 
+    /// Identifier of the block added stream opened by the connection, if any
+    pub fn block_added_stream(&self) -> MutexGuard<'_, Option<u64>> {
+        self.inner.block_added_stream.lock().unwrap()
+    }
+
     /// Creates a WebSocket [`Message`] that can be posted to the connection ([`Messenger`]) sink
     /// directly.
     pub fn create_serialized_notification_message<Ops, Msg>(
@@ -194,6 +207,15 @@ impl ConnectionT for Connection {
         .unwrap()
     }
 
+    fn into_gap_message(gap: &NotificationGap, encoding: &Self::Encoding) -> Option<Self::Message> {
+        Self::create_serialized_notification_message(
+            encoding.clone().into(),
+            RpcApiOps::NotificationGapNotification,
+            NotificationGapNotification::from(gap),
+        )
+        .ok()
+    }
+
     async fn send(&self, message: Self::Message) -> core::result::Result<(), Self::Error> {
         self.inner
             .send(message)
@@ -216,4 +238,20 @@ impl ConnectionT for Connection {
     }
 }
 
+impl BlockAddedSink for Connection {
+    fn deliver(&self, block: &Arc<RpcBlock>, gap: bool) -> bool {
+        Self::create_serialized_notification_message(
+            self.messenger().encoding(),
+            RpcApiOps::BlockAddedStreamNotification,
+            BlockAddedStreamNotification {
+                block: block.clone(),
+                gap,
+            },
+        )
+        .map_err(crate::error::Error::from)
+        .and_then(|message| self.inner.send(message))
+        .is_ok()
+    }
+}
+
 pub type ConnectionReference = Arc<Connection>;
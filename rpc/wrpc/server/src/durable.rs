@@ -0,0 +1,772 @@
+//!
+//! Durable notification subscriptions.
+//!
+//! A durable subscription is identified by a token chosen by the client and outlives the
+//! wRPC connection that registered it. It is bound to a client secret, so only the client
+//! knowing the secret can resume or drop it. Its notifications are journaled under
+//! increasing sequence numbers, up to the configured retention, so a client reconnecting
+//! after a disconnection or a node restart can resume the feed where it stopped instead
+//! of reconciling its whole state.
+//!
+//! Every change of the subscriptions and every journaled notification is appended to a
+//! log file as it happens, so the journals survive a crash of the node. The log gets
+//! compacted into the retained state on startup, on shutdown and when it grows beyond
+//! the retention of the subscriptions.
+//!
+
+use crate::{connection::Connection, error::Error, result::Result};
+use borsh::{BorshDeserialize, BorshSerialize};
+use karlsen_core::{
+    info,
+    task::service::{AsyncService, AsyncServiceError, AsyncServiceFuture},
+    trace, warn,
+};
+use karlsen_notify::{
+    connection::ChannelType,
+    listener::{ListenerId, ListenerLifespan},
+    scope::Scope,
+};
+use karlsen_rpc_core::{
+    api::ops::RpcApiOps,
+    notify::{channel::NotificationChannel, connection::ChannelConnection},
+    DurableNotification, Notification, ResumeDurableResponse,
+};
+use karlsen_rpc_service::service::RpcCoreService;
+use karlsen_utils::triggers::SingleTrigger;
+use std::{
+    collections::{HashMap, VecDeque},
+    fs::{File, OpenOptions},
+    io::{BufWriter, Write},
+    path::PathBuf,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+/// Name of the file the durable subscriptions are logged to
+pub const DURABLE_SUBSCRIPTIONS_FILE: &str = "wrpc-durable-subscriptions.log";
+
+const MAX_DURABLE_SUBSCRIPTIONS: usize = 256;
+const MAX_TOKEN_LENGTH: usize = 128;
+const MIN_SECRET_LENGTH: usize = 16;
+
+/// Interval between checks of the log size
+const LOG_COMPACTION_INTERVAL: Duration = Duration::from_secs(60);
+
+type SecretHash = [u8; 32];
+
+/// Hashes a client secret, salted by its token so equal secrets of distinct tokens differ
+fn hash_secret(token: &str, secret: &str) -> SecretHash {
+    let hash = blake2b_simd::Params::new()
+        .hash_length(32)
+        .to_state()
+        .update(token.as_bytes())
+        .update(&[0])
+        .update(secret.as_bytes())
+        .finalize();
+    hash.as_bytes().try_into().expect("the hash length is 32")
+}
+
+/// Notifications of a subscription, bounded by a retention count
+struct Journal<T> {
+    retention: usize,
+    entries: VecDeque<T>,
+    next_sequence: u64,
+}
+
+impl<T> Journal<T> {
+    fn new(retention: usize, next_sequence: u64, entries: Vec<T>) -> Self {
+        let mut journal = Self {
+            retention,
+            entries: VecDeque::with_capacity(retention),
+            next_sequence: next_sequence.saturating_sub(entries.len() as u64),
+        };
+        entries.into_iter().for_each(|x| {
+            journal.push(x);
+        });
+        journal
+    }
+
+    fn first_sequence(&self) -> u64 {
+        self.next_sequence - self.entries.len() as u64
+    }
+
+    /// Journals an entry, dropping the oldest one if the retention is reached, and returns its sequence number
+    fn push(&mut self, entry: T) -> u64 {
+        if self.entries.len() == self.retention {
+            self.entries.pop_front();
+        }
+        self.entries.push_back(entry);
+        self.next_sequence += 1;
+        self.next_sequence - 1
+    }
+
+    /// Returns the entries starting at `sequence`, along with a flag reporting that some
+    /// of the requested entries are unknown to the journal
+    fn since(&self, sequence: u64) -> (impl Iterator<Item = (u64, &T)>, bool) {
+        let first = self.first_sequence();
+        let gap = sequence < first || sequence > self.next_sequence;
+        let skip = sequence.saturating_sub(first) as usize;
+        let entries = self
+            .entries
+            .iter()
+            .enumerate()
+            .skip(skip)
+            .map(move |(i, x)| (first + i as u64, x));
+        (entries, gap)
+    }
+}
+
+/// Entry of the subscriptions log
+#[derive(Clone, Debug, PartialEq, BorshSerialize, BorshDeserialize)]
+enum Record<T> {
+    /// A subscription whose next journaled notification gets `next_sequence`
+    Subscription {
+        token: String,
+        secret_hash: SecretHash,
+        next_sequence: u64,
+    },
+    Scope {
+        token: String,
+        scope: Scope,
+    },
+    Notification {
+        token: String,
+        notification: T,
+    },
+    Unsubscription {
+        token: String,
+    },
+}
+
+/// Decodes the records of a log, ignoring a last record truncated by a crash
+fn decode_records<T: BorshDeserialize>(mut bytes: &[u8]) -> Vec<Record<T>> {
+    let mut records = Vec::new();
+    while bytes.len() >= 4 {
+        let len = u32::from_le_bytes(bytes[..4].try_into().unwrap()) as usize;
+        let Some(Ok(record)) = bytes.get(4..4 + len).map(Record::try_from_slice) else {
+            break;
+        };
+        records.push(record);
+        bytes = &bytes[4 + len..];
+    }
+    records
+}
+
+fn encode_record<T: BorshSerialize>(record: &Record<T>, writer: &mut impl Write) -> Result<()> {
+    let data = record.try_to_vec()?;
+    writer.write_all(&(data.len() as u32).to_le_bytes())?;
+    writer.write_all(&data)?;
+    Ok(())
+}
+
+/// A subscription restored from the log
+struct Restored<T> {
+    token: String,
+    secret_hash: SecretHash,
+    scopes: Vec<Scope>,
+    journal: Journal<T>,
+}
+
+/// Replays the records of a log, returning the live subscriptions in registration order
+fn replay<T>(records: Vec<Record<T>>, retention: usize) -> Vec<Restored<T>> {
+    let mut restored: Vec<Restored<T>> = Vec::new();
+    for record in records {
+        match record {
+            Record::Subscription {
+                token,
+                secret_hash,
+                next_sequence,
+            } => {
+                restored.retain(|x| x.token != token);
+                restored.push(Restored {
+                    token,
+                    secret_hash,
+                    scopes: vec![],
+                    journal: Journal::new(retention, next_sequence, vec![]),
+                });
+            }
+            Record::Scope { token, scope } => {
+                if let Some(x) = restored.iter_mut().find(|x| x.token == token) {
+                    if !x.scopes.contains(&scope) {
+                        x.scopes.push(scope);
+                    }
+                }
+            }
+            Record::Notification {
+                token,
+                notification,
+            } => {
+                if let Some(x) = restored.iter_mut().find(|x| x.token == token) {
+                    x.journal.push(notification);
+                }
+            }
+            Record::Unsubscription { token } => restored.retain(|x| x.token != token),
+        }
+    }
+    restored
+}
+
+/// Append-only log of the subscriptions
+struct Log {
+    path: PathBuf,
+    writer: BufWriter<File>,
+    /// Count of records appended since the last compaction
+    appended: usize,
+}
+
+impl Log {
+    fn open(path: PathBuf) -> Result<Self> {
+        let file = OpenOptions::new().create(true).append(true).open(&path)?;
+        Ok(Self {
+            path,
+            writer: BufWriter::new(file),
+            appended: 0,
+        })
+    }
+
+    /// Appends a record and hands it to the OS, so it survives a crash of the node
+    fn append(&mut self, record: &Record<Notification>) -> Result<()> {
+        encode_record(record, &mut self.writer)?;
+        self.writer.flush()?;
+        self.appended += 1;
+        Ok(())
+    }
+
+    /// Replaces the log with `records`, writing them to a temporary file first so a crash
+    /// during the compaction leaves the previous log intact
+    fn compact(&mut self, records: impl Iterator<Item = Record<Notification>>) -> Result<()> {
+        let temp_path = self.path.with_extension("tmp");
+        let mut writer = BufWriter::new(File::create(&temp_path)?);
+        for record in records {
+            encode_record(&record, &mut writer)?;
+        }
+        writer
+            .into_inner()
+            .map_err(|err| err.into_error())?
+            .sync_all()?;
+        std::fs::rename(&temp_path, &self.path)?;
+        *self = Self::open(self.path.clone())?;
+        Ok(())
+    }
+}
+
+struct State {
+    scopes: Vec<Scope>,
+    journal: Journal<Notification>,
+    /// Connection the notifications are forwarded to as they get journaled
+    connection: Option<Connection>,
+}
+
+struct DurableSubscription {
+    token: String,
+    secret_hash: SecretHash,
+    listener_id: ListenerId,
+    log: Option<Arc<Mutex<Log>>>,
+    state: Mutex<State>,
+}
+
+impl DurableSubscription {
+    fn authorize(&self, secret: &str) -> Result<()> {
+        if hash_secret(&self.token, secret) != self.secret_hash {
+            return Err(Error::DurableSecretMismatch(self.token.clone()));
+        }
+        Ok(())
+    }
+
+    fn journal(&self, notification: Notification) {
+        // The log is locked first, as by the compaction, so the notification is either
+        // appended to the log or part of the compacted state
+        let mut log = self.log.as_ref().map(|x| x.lock().unwrap());
+        let mut state = self.state.lock().unwrap();
+        let sequence = state.journal.push(notification.clone());
+        if let Some(log) = log.as_mut() {
+            let record = Record::Notification {
+                token: self.token.clone(),
+                notification: notification.clone(),
+            };
+            if let Err(err) = log.append(&record) {
+                warn!(
+                    "{} could not log a notification of {}: {}",
+                    DurableSubscriptions::IDENT,
+                    self.token,
+                    err
+                );
+            }
+        }
+        drop(log);
+        if let Some(connection) = state.connection.as_ref() {
+            if let Err(err) = send(connection, &self.token, sequence, notification) {
+                trace!(
+                    "durable subscription {} detached from connection {}: {}",
+                    self.token,
+                    connection,
+                    err
+                );
+                state.connection = None;
+            }
+        }
+    }
+
+    /// Returns the records restoring the subscription with its retained journal
+    fn records(&self) -> Vec<Record<Notification>> {
+        let state = self.state.lock().unwrap();
+        let mut records = vec![Record::Subscription {
+            token: self.token.clone(),
+            secret_hash: self.secret_hash,
+            next_sequence: state.journal.first_sequence(),
+        }];
+        records.extend(state.scopes.iter().map(|scope| Record::Scope {
+            token: self.token.clone(),
+            scope: scope.clone(),
+        }));
+        records.extend(
+            state
+                .journal
+                .entries
+                .iter()
+                .map(|notification| Record::Notification {
+                    token: self.token.clone(),
+                    notification: notification.clone(),
+                }),
+        );
+        records
+    }
+}
+
+fn send(
+    connection: &Connection,
+    token: &str,
+    sequence: u64,
+    notification: Notification,
+) -> Result<()> {
+    let message = Connection::create_serialized_notification_message(
+        connection.messenger().encoding(),
+        RpcApiOps::DurableNotification,
+        DurableNotification {
+            token: token.to_string(),
+            sequence,
+            notification,
+        },
+    )?;
+    Ok(connection.messenger().send_raw_message(message)?)
+}
+
+/// Durable subscriptions shared by the wRPC servers of a node
+pub struct DurableSubscriptions {
+    core_service: Arc<RpcCoreService>,
+    retention: usize,
+    /// File the subscriptions are restored from and logged to, if any
+    path: Option<PathBuf>,
+    log: Mutex<Option<Arc<Mutex<Log>>>>,
+    subscriptions: Mutex<HashMap<String, Arc<DurableSubscription>>>,
+    shutdown: SingleTrigger,
+}
+
+impl DurableSubscriptions {
+    pub const IDENT: &'static str = "wrpc-durable-subscriptions";
+
+    pub fn new(core_service: Arc<RpcCoreService>, retention: usize, path: Option<PathBuf>) -> Self {
+        assert!(
+            retention > 0,
+            "the durable subscriptions retention must be positive"
+        );
+        Self {
+            core_service,
+            retention,
+            path,
+            log: Default::default(),
+            subscriptions: Default::default(),
+            shutdown: Default::default(),
+        }
+    }
+
+    pub fn retention(&self) -> usize {
+        self.retention
+    }
+
+    /// Adds `scope` to the subscription identified by `token`, registering the subscription
+    /// bound to `secret` if needed. Returns the sequence number of the next journaled notification.
+    pub fn subscribe(&self, token: String, secret: &str, scope: Scope) -> Result<u64> {
+        if token.is_empty() || token.len() > MAX_TOKEN_LENGTH {
+            return Err(Error::InvalidDurableToken(MAX_TOKEN_LENGTH));
+        }
+        if secret.len() < MIN_SECRET_LENGTH {
+            return Err(Error::InvalidDurableSecret(MIN_SECRET_LENGTH));
+        }
+        let log = self.log.lock()?.clone();
+        let mut log = log.as_ref().map(|x| x.lock()).transpose()?;
+        let subscription = {
+            let mut subscriptions = self.subscriptions.lock()?;
+            match subscriptions.get(&token) {
+                Some(subscription) => {
+                    subscription.authorize(secret)?;
+                    subscription.clone()
+                }
+                None => {
+                    if subscriptions.len() >= MAX_DURABLE_SUBSCRIPTIONS {
+                        return Err(Error::DurableSubscriptionsLimit(MAX_DURABLE_SUBSCRIPTIONS));
+                    }
+                    let secret_hash = hash_secret(&token, secret);
+                    if let Some(log) = log.as_mut() {
+                        log.append(&Record::Subscription {
+                            token: token.clone(),
+                            secret_hash,
+                            next_sequence: 0,
+                        })?;
+                    }
+                    let subscription = self.register(
+                        token.clone(),
+                        secret_hash,
+                        Journal::new(self.retention, 0, vec![]),
+                    )?;
+                    subscriptions.insert(token, subscription.clone());
+                    subscription
+                }
+            }
+        };
+        self.core_service
+            .notifier()
+            .try_start_notify(subscription.listener_id, scope.clone())?;
+        let mut state = subscription.state.lock()?;
+        if !state.scopes.contains(&scope) {
+            if let Some(log) = log.as_mut() {
+                log.append(&Record::Scope {
+                    token: subscription.token.clone(),
+                    scope: scope.clone(),
+                })?;
+            }
+            state.scopes.push(scope);
+        }
+        Ok(state.journal.next_sequence)
+    }
+
+    /// Replays to `connection` the notifications journaled since `next_sequence` and attaches
+    /// the subscription to it, replacing any previously attached connection
+    pub fn resume(
+        &self,
+        token: &str,
+        secret: &str,
+        next_sequence: u64,
+        connection: &Connection,
+    ) -> Result<ResumeDurableResponse> {
+        let subscription = self.get(token)?;
+        subscription.authorize(secret)?;
+        let mut state = subscription.state.lock()?;
+        let (entries, gap) = state.journal.since(next_sequence);
+        let mut replayed = 0;
+        for (sequence, notification) in entries {
+            send(connection, token, sequence, notification.clone())?;
+            replayed += 1;
+        }
+        let next_sequence = state.journal.next_sequence;
+        state.connection = Some(connection.clone());
+        Ok(ResumeDurableResponse::new(replayed, next_sequence, gap))
+    }
+
+    /// Drops the subscription identified by `token` along with its journal
+    pub fn unsubscribe(&self, token: &str, secret: &str) -> Result<()> {
+        let log = self.log.lock()?.clone();
+        let mut log = log.as_ref().map(|x| x.lock()).transpose()?;
+        let subscription = {
+            let mut subscriptions = self.subscriptions.lock()?;
+            let subscription = subscriptions
+                .get(token)
+                .ok_or_else(|| Error::UnknownDurableToken(token.to_string()))?;
+            subscription.authorize(secret)?;
+            subscriptions.remove(token).expect("checked above")
+        };
+        if let Some(log) = log.as_mut() {
+            log.append(&Record::Unsubscription {
+                token: token.to_string(),
+            })?;
+        }
+        self.core_service
+            .notifier()
+            .unregister_listener(subscription.listener_id)?;
+        Ok(())
+    }
+
+    /// Detaches the subscriptions attached to a closing connection
+    pub fn detach(&self, connection_id: u64) {
+        self.subscriptions
+            .lock()
+            .unwrap()
+            .values()
+            .for_each(|subscription| {
+                let mut state = subscription.state.lock().unwrap();
+                if state.connection.as_ref().map(|x| x.id()) == Some(connection_id) {
+                    state.connection = None;
+                }
+            });
+    }
+
+    fn get(&self, token: &str) -> Result<Arc<DurableSubscription>> {
+        self.subscriptions
+            .lock()?
+            .get(token)
+            .cloned()
+            .ok_or_else(|| Error::UnknownDurableToken(token.to_string()))
+    }
+
+    /// Registers a notification listener feeding the journal of a new subscription
+    fn register(
+        &self,
+        token: String,
+        secret_hash: SecretHash,
+        journal: Journal<Notification>,
+    ) -> Result<Arc<DurableSubscription>> {
+        let channel = NotificationChannel::default();
+        let listener_id = self.core_service.notifier().register_new_listener(
+            ChannelConnection::new(Self::IDENT, channel.sender(), ChannelType::Closable),
+            ListenerLifespan::Dynamic,
+        );
+        let subscription = Arc::new(DurableSubscription {
+            token,
+            secret_hash,
+            listener_id,
+            log: self.log.lock()?.clone(),
+            state: Mutex::new(State {
+                scopes: vec![],
+                journal,
+                connection: None,
+            }),
+        });
+
+        let receiver = channel.receiver();
+        let feed = subscription.clone();
+        tokio::spawn(async move {
+            // The channel gets closed when the listener is unregistered or when the notifier stops
+            while let Ok(notification) = receiver.recv().await {
+                feed.journal(notification);
+            }
+            trace!("{} feed of {} exited", Self::IDENT, feed.token);
+        });
+        Ok(subscription)
+    }
+
+    /// Restores the subscriptions logged by a previous run of the node and opens the log
+    fn load(&self) -> Result<()> {
+        let Some(path) = self.path.as_ref() else {
+            return Ok(());
+        };
+        let records = match path.exists() {
+            true => decode_records::<Notification>(&std::fs::read(path)?),
+            false => vec![],
+        };
+        *self.log.lock()? = Some(Arc::new(Mutex::new(Log::open(path.clone())?)));
+
+        let notifier = self.core_service.notifier();
+        let mut subscriptions = self.subscriptions.lock()?;
+        for restored in replay(records, self.retention) {
+            let subscription = self.register(
+                restored.token.clone(),
+                restored.secret_hash,
+                restored.journal,
+            )?;
+            for scope in restored.scopes {
+                match notifier.try_start_notify(subscription.listener_id, scope.clone()) {
+                    Ok(()) => subscription.state.lock()?.scopes.push(scope),
+                    Err(err) => warn!(
+                        "{} could not restore scope {} of {}: {}",
+                        Self::IDENT,
+                        scope,
+                        restored.token,
+                        err
+                    ),
+                }
+            }
+            subscriptions.insert(restored.token, subscription);
+        }
+        info!(
+            "Restored {} durable wRPC subscription(s)",
+            subscriptions.len()
+        );
+        drop(subscriptions);
+        self.compact()
+    }
+
+    /// Rewrites the log with the retained state of the subscriptions
+    fn compact(&self) -> Result<()> {
+        let Some(log) = self.log.lock()?.clone() else {
+            return Ok(());
+        };
+        let mut log = log.lock()?;
+        let records = self
+            .subscriptions
+            .lock()?
+            .values()
+            .flat_map(|x| x.records())
+            .collect::<Vec<_>>();
+        log.compact(records.into_iter())
+    }
+
+    /// Compacts the log once it holds more records than the retained state can
+    fn compact_if_needed(&self) -> Result<()> {
+        let Some(log) = self.log.lock()?.clone() else {
+            return Ok(());
+        };
+        let threshold = self.subscriptions.lock()?.len().max(1) * (self.retention + 2);
+        if log.lock()?.appended > threshold {
+            self.compact()?;
+        }
+        Ok(())
+    }
+
+    async fn run(self: Arc<Self>) -> Result<()> {
+        self.load()?;
+        loop {
+            tokio::select! {
+                _ = self.shutdown.listener.clone() => break,
+                _ = tokio::time::sleep(LOG_COMPACTION_INTERVAL) => {
+                    if let Err(err) = self.compact_if_needed() {
+                        warn!("{} could not compact its log: {}", Self::IDENT, err);
+                    }
+                }
+            }
+        }
+
+        // Compact before unregistering the listeners so the journals are complete
+        self.compact()?;
+        let notifier = self.core_service.notifier();
+        for subscription in self.subscriptions.lock()?.values() {
+            if let Err(err) = notifier.unregister_listener(subscription.listener_id) {
+                trace!(
+                    "{} error while unregistering a listener: {}",
+                    Self::IDENT,
+                    err
+                );
+            }
+        }
+        Ok(())
+    }
+}
+
+impl AsyncService for DurableSubscriptions {
+    fn ident(self: Arc<Self>) -> &'static str {
+        Self::IDENT
+    }
+
+    fn start(self: Arc<Self>) -> AsyncServiceFuture {
+        trace!("{} starting", Self::IDENT);
+        Box::pin(async move {
+            self.run()
+                .await
+                .map_err(|err| AsyncServiceError::Service(err.to_string()))
+        })
+    }
+
+    fn signal_exit(self: Arc<Self>) {
+        trace!("sending an exit signal to {}", Self::IDENT);
+        self.shutdown.trigger.trigger();
+    }
+
+    fn stop(self: Arc<Self>) -> AsyncServiceFuture {
+        Box::pin(async move {
+            trace!("{} stopped", Self::IDENT);
+            Ok(())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn collect(journal: &Journal<u64>, sequence: u64) -> (Vec<(u64, u64)>, bool) {
+        let (entries, gap) = journal.since(sequence);
+        (entries.map(|(s, x)| (s, *x)).collect(), gap)
+    }
+
+    #[test]
+    fn test_journal() {
+        let mut journal = Journal::new(3, 0, vec![]);
+        assert_eq!(collect(&journal, 0), (vec![], false));
+        for x in 10..15 {
+            journal.push(x);
+        }
+        assert_eq!(journal.next_sequence, 5);
+        assert_eq!(journal.first_sequence(), 2);
+
+        assert_eq!(collect(&journal, 3), (vec![(3, 13), (4, 14)], false));
+        assert_eq!(collect(&journal, 5), (vec![], false));
+
+        // Entries dropped by the retention
+        assert_eq!(
+            collect(&journal, 1),
+            (vec![(2, 12), (3, 13), (4, 14)], true)
+        );
+
+        // A sequence ahead of the journal, ie. a journal lost by the node
+        assert_eq!(collect(&journal, 8), (vec![], true));
+
+        // A restored journal keeps its sequence numbers, even with a lower retention
+        let journal = Journal::new(2, 5, vec![12, 13, 14]);
+        assert_eq!(journal.first_sequence(), 3);
+        assert_eq!(collect(&journal, 3), (vec![(3, 13), (4, 14)], false));
+    }
+
+    #[test]
+    fn test_log_replay() {
+        let subscription = |token: &str, next_sequence| Record::Subscription {
+            token: token.to_string(),
+            secret_hash: hash_secret(token, "0123456789abcdef"),
+            next_sequence,
+        };
+        let notification = |token: &str, notification| Record::Notification {
+            token: token.to_string(),
+            notification,
+        };
+        let records: Vec<Record<u64>> = vec![
+            subscription("a", 0),
+            subscription("b", 7),
+            Record::Scope {
+                token: "a".to_string(),
+                scope: Scope::BlockAdded(Default::default()),
+            },
+            notification("a", 10),
+            notification("b", 20),
+            notification("a", 11),
+            notification("a", 12),
+            Record::Unsubscription {
+                token: "b".to_string(),
+            },
+            notification("c", 30),
+        ];
+        let mut bytes = vec![];
+        records
+            .iter()
+            .for_each(|x| encode_record(x, &mut bytes).unwrap());
+
+        // A record truncated by a crash is ignored along with the following bytes
+        let mut truncated = bytes.clone();
+        encode_record(&notification("a", 13), &mut truncated).unwrap();
+        truncated.pop();
+        assert_eq!(decode_records::<u64>(&truncated), records);
+
+        // Notifications are journaled with their sequence numbers, within the retention
+        let restored = replay(decode_records::<u64>(&bytes), 2);
+        assert_eq!(restored.len(), 1);
+        assert_eq!(restored[0].token, "a");
+        assert_eq!(restored[0].scopes.len(), 1);
+        assert_eq!(
+            collect(&restored[0].journal, 0),
+            (vec![(1, 11), (2, 12)], true)
+        );
+
+        // A compacted subscription keeps its sequence numbers
+        let compacted = vec![subscription("a", 1), notification("a", 11)];
+        let restored = replay(compacted, 2);
+        assert_eq!(collect(&restored[0].journal, 1), (vec![(1, 11)], false));
+    }
+
+    #[test]
+    fn test_secret_hash() {
+        let secret = "0123456789abcdef";
+        assert_eq!(hash_secret("a", secret), hash_secret("a", secret));
+        assert_ne!(hash_secret("a", secret), hash_secret("b", secret));
+        assert_ne!(
+            hash_secret("a", secret),
+            hash_secret("a", "fedcba9876543210")
+        );
+    }
+}
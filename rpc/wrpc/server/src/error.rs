@@ -20,6 +20,30 @@ pub enum Error {
 
     #[error("Notify error: {0}")]
     NotifyError(#[from] NotifyError),
+
+    #[error("block added streams are not available through a gRPC proxy")]
+    ProxiedBlockAddedStream,
+
+    #[error("IO error: {0}")]
+    IoError(#[from] std::io::Error),
+
+    #[error("durable subscriptions are not enabled on this server")]
+    DurableSubscriptionsDisabled,
+
+    #[error("durable subscription tokens must be non-empty and at most {0} characters long")]
+    InvalidDurableToken(usize),
+
+    #[error("the server reached its limit of {0} durable subscriptions")]
+    DurableSubscriptionsLimit(usize),
+
+    #[error("unknown durable subscription token {0}")]
+    UnknownDurableToken(String),
+
+    #[error("durable subscription secrets must be at least {0} characters long")]
+    InvalidDurableSecret(usize),
+
+    #[error("wrong secret for durable subscription token {0}")]
+    DurableSecretMismatch(String),
 }
 
 impl<T> From<PoisonError<T>> for Error {
@@ -2,6 +2,7 @@
 pub mod address;
 pub mod collector;
 pub mod connection;
+pub mod durable;
 pub mod error;
 pub mod result;
 pub mod router;
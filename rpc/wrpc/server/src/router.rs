@@ -1,6 +1,6 @@
 use crate::{connection::*, server::*};
 use karlsen_notify::scope::Scope;
-use karlsen_rpc_core::{api::ops::RpcApiOps, prelude::*};
+use karlsen_rpc_core::{api::ops::RpcApiOps, prelude::*, RpcErrorInfo};
 use karlsen_rpc_macros::build_wrpc_server_interface;
 use std::sync::Arc;
 use workflow_rpc::server::prelude::*;
@@ -34,17 +34,28 @@ impl Router {
             [
                 AddPeer,
                 Ban,
+                DebugScript,
                 EstimateNetworkHashesPerSecond,
+                GetAcceptanceEventsSince,
+                GetAddressCluster,
                 GetBalanceByAddress,
+                GetBalanceByAddressesAt,
                 GetBalancesByAddresses,
                 GetBlock,
                 GetBlockCount,
                 GetBlockDagInfo,
+                GetBlockFilterHeaders,
+                GetBlockFilters,
                 GetBlocks,
                 GetBlockTemplate,
                 GetCoinSupply,
                 GetConnectedPeerInfo,
                 GetDaaScoreTimestampEstimate,
+                GetDagSlice,
+                GetDifficultyInfo,
+                GetMetadata,
+                GetMinerStats,
+                GetNetworkInfo,
                 GetServerInfo,
                 GetCurrentNetwork,
                 GetHeaders,
@@ -58,6 +69,9 @@ impl Router {
                 GetSink,
                 GetSubnetwork,
                 GetSyncStatus,
+                GetTemplateDiff,
+                GetTransactionStatus,
+                GetUnconfirmedTxRisk,
                 GetUtxosByAddresses,
                 GetSinkBlueScore,
                 GetVirtualChainFromBlock,
@@ -66,15 +80,43 @@ impl Router {
                 Shutdown,
                 SubmitBlock,
                 SubmitTransaction,
+                TestMempoolAccept,
                 Unban,
+                UpdateMetadata,
             ]
         );
 
+        // The capabilities are filtered by the listener policy
+        interface.method(
+            RpcApiOps::GetServerCapabilities,
+            workflow_rpc::server::Method::new(
+                move |manager: Server,
+                      connection: Connection,
+                      request: GetServerCapabilitiesRequest| {
+                    Box::pin(async move {
+                        manager
+                            .check_policy(&connection, RpcApiOps::GetServerCapabilities)
+                            .map_err(|err| RpcErrorInfo::from(&err).to_wrpc_message())?;
+                        let mut response = manager
+                            .rpc_service(&connection)
+                            .get_server_capabilities_call(request)
+                            .await
+                            .map_err(|err| RpcErrorInfo::from(&err).to_wrpc_message())?;
+                        manager.filter_capabilities(&mut response);
+                        Ok(response)
+                    })
+                },
+            ),
+        );
+
         interface.method(
             RpcApiOps::Subscribe,
             workflow_rpc::server::Method::new(
                 move |manager: Server, connection: Connection, scope: Scope| {
                     Box::pin(async move {
+                        manager
+                            .check_subscription_policy(&connection, &scope)
+                            .map_err(|err| RpcErrorInfo::from(&err).to_wrpc_message())?;
                         manager
                             .start_notify(&connection, scope)
                             .await
@@ -104,6 +146,99 @@ impl Router {
             ),
         );
 
+        interface.method(
+            RpcApiOps::SubscribeBlockAdded,
+            workflow_rpc::server::Method::new(
+                move |manager: Server,
+                      connection: Connection,
+                      request: SubscribeBlockAddedRequest| {
+                    Box::pin(async move {
+                        manager
+                            .check_policy(&connection, RpcApiOps::SubscribeBlockAdded)
+                            .map_err(|err| RpcErrorInfo::from(&err).to_wrpc_message())?;
+                        let response = manager
+                            .subscribe_block_added(&connection, request.cursor)
+                            .map_err(|err| err.to_string())?;
+                        Ok(response)
+                    })
+                },
+            ),
+        );
+
+        interface.method(
+            RpcApiOps::UnsubscribeBlockAdded,
+            workflow_rpc::server::Method::new(
+                move |manager: Server,
+                      connection: Connection,
+                      _request: UnsubscribeBlockAddedRequest| {
+                    Box::pin(async move {
+                        manager
+                            .unsubscribe_block_added(&connection)
+                            .map_err(|err| err.to_string())?;
+                        Ok(UnsubscribeBlockAddedResponse {})
+                    })
+                },
+            ),
+        );
+
+        interface.method(
+            RpcApiOps::SubscribeDurable,
+            workflow_rpc::server::Method::new(
+                move |manager: Server, connection: Connection, request: SubscribeDurableRequest| {
+                    Box::pin(async move {
+                        manager
+                            .check_policy(&connection, RpcApiOps::SubscribeDurable)
+                            .map_err(|err| RpcErrorInfo::from(&err).to_wrpc_message())?;
+                        let next_sequence = manager
+                            .subscribe_durable(request.token, &request.secret, request.scope)
+                            .map_err(|err| err.to_string())?;
+                        Ok(SubscribeDurableResponse::new(next_sequence))
+                    })
+                },
+            ),
+        );
+
+        interface.method(
+            RpcApiOps::ResumeDurable,
+            workflow_rpc::server::Method::new(
+                move |manager: Server, connection: Connection, request: ResumeDurableRequest| {
+                    Box::pin(async move {
+                        manager
+                            .check_policy(&connection, RpcApiOps::ResumeDurable)
+                            .map_err(|err| RpcErrorInfo::from(&err).to_wrpc_message())?;
+                        let response = manager
+                            .resume_durable(
+                                &connection,
+                                &request.token,
+                                &request.secret,
+                                request.next_sequence,
+                            )
+                            .map_err(|err| err.to_string())?;
+                        Ok(response)
+                    })
+                },
+            ),
+        );
+
+        interface.method(
+            RpcApiOps::UnsubscribeDurable,
+            workflow_rpc::server::Method::new(
+                move |manager: Server,
+                      connection: Connection,
+                      request: UnsubscribeDurableRequest| {
+                    Box::pin(async move {
+                        manager
+                            .check_policy(&connection, RpcApiOps::UnsubscribeDurable)
+                            .map_err(|err| RpcErrorInfo::from(&err).to_wrpc_message())?;
+                        manager
+                            .unsubscribe_durable(&request.token, &request.secret)
+                            .map_err(|err| err.to_string())?;
+                        Ok(UnsubscribeDurableResponse {})
+                    })
+                },
+            ),
+        );
+
         Router {
             interface: Arc::new(interface),
             server_context,
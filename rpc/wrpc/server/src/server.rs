@@ -1,6 +1,8 @@
 use crate::{
     collector::{WrpcServiceCollector, WrpcServiceConverter},
     connection::Connection,
+    durable::DurableSubscriptions,
+    error::Error,
     result::Result,
     service::Options,
 };
@@ -10,16 +12,23 @@ use karlsen_notify::{
     events::EVENT_TYPE_ARRAY,
     listener::ListenerLifespan,
     notifier::Notifier,
+    queue::QueuedConnection,
     scope::Scope,
     subscriber::Subscriber,
     subscription::{MutationPolicies, UtxosChangedMutationPolicy},
 };
 use karlsen_rpc_core::{
-    api::rpc::{DynRpcService, RpcApi},
+    api::{
+        ops::RpcApiOps,
+        rpc::{DynRpcService, RpcApi},
+    },
     notify::{channel::NotificationChannel, connection::ChannelConnection, mode::NotificationMode},
-    Notification, RpcResult,
+    GetServerCapabilitiesResponse, Notification, ResumeDurableResponse, RpcHash, RpcResult,
+    SubscribeBlockAddedResponse,
+};
+use karlsen_rpc_service::{
+    journal::BlockAddedJournal, policy::notification_method, service::RpcCoreService,
 };
-use karlsen_rpc_service::service::RpcCoreService;
 use std::{
     collections::HashMap,
     sync::{
@@ -30,7 +39,7 @@ use std::{
 use workflow_log::*;
 use workflow_rpc::server::prelude::*;
 
-pub type WrpcNotifier = Notifier<Notification, Connection>;
+pub type WrpcNotifier = Notifier<Notification, QueuedConnection<Connection>>;
 
 struct RpcCore {
     pub service: Arc<RpcCoreService>,
@@ -185,6 +194,16 @@ impl Server {
             let _ = connection.grpc_client().join().await;
         }
 
+        if let Some(id) = connection.block_added_stream().take() {
+            if let Ok(journal) = self.block_added_journal() {
+                journal.unsubscribe(id);
+            }
+        }
+
+        if let Some(durable_subscriptions) = &self.inner.options.durable_subscriptions {
+            durable_subscriptions.detach(connection.id());
+        }
+
         self.inner.sockets.lock().unwrap().remove(&connection.id());
 
         // FIXME: determine if messenger should be closed explicitly
@@ -217,8 +236,13 @@ impl Server {
             let notifier = self.notifier().unwrap_or_else(|| {
                 panic!("Incorrect use: `server::Server` does not carry an internal notifier")
             });
-            let listener_id =
-                notifier.register_new_listener(connection.clone(), ListenerLifespan::Dynamic);
+            let listener_id = notifier.register_new_listener(
+                QueuedConnection::new(
+                    connection.clone(),
+                    self.inner.options.notification_queue.clone(),
+                ),
+                ListenerLifespan::Dynamic,
+            );
             connection.register_notification_listener(listener_id);
             listener_id
         };
@@ -257,10 +281,106 @@ impl Server {
         Ok(())
     }
 
+    fn block_added_journal(&self) -> Result<Arc<BlockAddedJournal>> {
+        let rpc_core = self
+            .inner
+            .rpc_core
+            .as_ref()
+            .ok_or(Error::ProxiedBlockAddedStream)?;
+        Ok(rpc_core.service.block_added_journal()?)
+    }
+
+    /// Opens the block added stream of the connection from `cursor`, closing the stream
+    /// previously opened by the connection if any
+    pub fn subscribe_block_added(
+        &self,
+        connection: &Connection,
+        cursor: Option<RpcHash>,
+    ) -> Result<SubscribeBlockAddedResponse> {
+        workflow_log::log_trace!(
+            "block added stream subscribe[{}] {cursor:?}",
+            connection.id()
+        );
+        let journal = self.block_added_journal()?;
+        let mut current = connection.block_added_stream();
+        if let Some(id) = current.take() {
+            journal.unsubscribe(id);
+        }
+        let stream = journal.subscribe(cursor, Arc::new(connection.clone()));
+        current.replace(stream.id);
+        Ok(SubscribeBlockAddedResponse::new(
+            stream.replayed as u64,
+            stream.gap,
+        ))
+    }
+
+    pub fn unsubscribe_block_added(&self, connection: &Connection) -> Result<()> {
+        workflow_log::log_trace!("block added stream unsubscribe[{}]", connection.id());
+        if let Some(id) = connection.block_added_stream().take() {
+            self.block_added_journal()?.unsubscribe(id);
+        }
+        Ok(())
+    }
+
+    fn durable_subscriptions(&self) -> Result<&Arc<DurableSubscriptions>> {
+        self.inner
+            .options
+            .durable_subscriptions
+            .as_ref()
+            .ok_or(Error::DurableSubscriptionsDisabled)
+    }
+
+    pub fn subscribe_durable(&self, token: String, secret: &str, scope: Scope) -> Result<u64> {
+        workflow_log::log_trace!("durable notification subscribe[{token}] {scope:?}");
+        self.durable_subscriptions()?
+            .subscribe(token, secret, scope)
+    }
+
+    pub fn resume_durable(
+        &self,
+        connection: &Connection,
+        token: &str,
+        secret: &str,
+        next_sequence: u64,
+    ) -> Result<ResumeDurableResponse> {
+        self.durable_subscriptions()?
+            .resume(token, secret, next_sequence, connection)
+    }
+
+    pub fn unsubscribe_durable(&self, token: &str, secret: &str) -> Result<()> {
+        workflow_log::log_trace!("durable notification unsubscribe[{token}]");
+        self.durable_subscriptions()?.unsubscribe(token, secret)
+    }
+
     pub fn verbose(&self) -> bool {
         self.inner.options.verbose
     }
 
+    /// Checks a request of the connection to the method `op` against the listener policy
+    pub fn check_policy(&self, connection: &Connection, op: RpcApiOps) -> RpcResult<()> {
+        self.inner.options.policy.check(op, connection.peer().ip())
+    }
+
+    /// Checks a subscription of the connection against the listener policy, as a call to the
+    /// matching `Notify*` method
+    pub fn check_subscription_policy(
+        &self,
+        connection: &Connection,
+        scope: &Scope,
+    ) -> RpcResult<()> {
+        let op = notification_method(&scope.event_type().to_string());
+        self.check_policy(connection, op)
+    }
+
+    /// Drops the methods and notifications the listener policy denies from the capabilities
+    /// reported to the connection
+    pub fn filter_capabilities(&self, capabilities: &mut GetServerCapabilitiesResponse) {
+        self.inner
+            .options
+            .policy
+            .filter_capabilities(&mut capabilities.methods, &mut capabilities.notifications);
+    }
+
     pub async fn join(&self) -> Result<()> {
         if let Some(rpc_core) = &self.inner.rpc_core {
             // Wait for the internal notifier to stop
@@ -1,12 +1,13 @@
-use crate::{connection::*, router::*, server::*};
+use crate::{connection::*, durable::DurableSubscriptions, router::*, server::*};
 use async_trait::async_trait;
 use karlsen_core::{
     info,
     task::service::{AsyncService, AsyncServiceError, AsyncServiceFuture},
     trace, warn,
 };
+use karlsen_notify::queue::QueueSettings;
 use karlsen_rpc_core::api::ops::RpcApiOps;
-use karlsen_rpc_service::service::RpcCoreService;
+use karlsen_rpc_service::{policy::RpcPolicy, service::RpcCoreService};
 use karlsen_utils::triggers::SingleTrigger;
 use std::sync::Arc;
 use tokio::sync::oneshot::{channel as oneshot_channel, Sender as OneshotSender};
@@ -20,6 +21,12 @@ pub struct Options {
     pub listen_address: String,
     pub grpc_proxy_address: Option<String>,
     pub verbose: bool,
+    /// Durable subscriptions, shared by all the wRPC servers of the node, if enabled
+    pub durable_subscriptions: Option<Arc<DurableSubscriptions>>,
+    /// Access policy of the listener
+    pub policy: Arc<RpcPolicy>,
+    /// Notification queue of every connection of the listener
+    pub notification_queue: QueueSettings,
 }
 
 impl Default for Options {
@@ -28,6 +35,9 @@ impl Default for Options {
             listen_address: "127.0.0.1:43110".to_owned(),
             verbose: false,
             grpc_proxy_address: None,
+            durable_subscriptions: None,
+            policy: Default::default(),
+            notification_queue: Default::default(),
         }
     }
 }
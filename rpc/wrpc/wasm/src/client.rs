@@ -1017,6 +1017,14 @@ build_wrpc_wasm_bindgen_subscriptions!([
     /// New block template notification event is produced when a new block
     /// template is generated for mining in the Karlsen BlockDAG.
     NewBlockTemplate,
+    /// Manage subscription for a transaction status changed notification event.
+    /// Transaction status changed notification event is produced when a transaction
+    /// submitted through the RPC of the node gets accepted or unaccepted.
+    TransactionStatusChanged,
+    /// Manage subscription for a double spend detected notification event.
+    /// Double spend detected notification event is produced when a transaction
+    /// conflicting with a mempool or recently accepted transaction is observed.
+    DoubleSpendDetected,
 ]);
 
 // Build RPC method invocation functions. This macro
@@ -1081,9 +1089,19 @@ build_wrpc_wasm_bindgen_interface!(
         /// Returned information: Version of the Karlsen server, protocol
         /// version, network identifier.
         GetServerInfo,
+        /// Retrieves the API versions and the feature set of the Karlsen server.
+        /// Returned information: RPC and protowire API versions, supported
+        /// methods and notifications.
+        GetServerCapabilities,
+        /// Retrieves the identity of the network the Karlsen node is running on.
+        /// Returned information: Network id, address prefix, genesis hash.
+        GetNetworkInfo,
         /// Obtains basic information about the synchronization status of the Karlsen node.
         /// Returned information: Syncing status.
         GetSyncStatus,
+        /// Estimates the profitability of the miners connected to the node.
+        /// Returned information: Hashrate, network share, expected time to block and power cost.
+        GetMinerStats,
     ],
     [
         // functions with `request` argument
@@ -1093,6 +1111,9 @@ build_wrpc_wasm_bindgen_interface!(
         /// Bans a peer from connecting to the Karlsen node for a specified duration.
         /// Returned information: None.
         Ban,
+        /// Evaluates the scripts of a transaction input step by step (devnets or unsafe RPC mode only).
+        /// Returned information: Engine stacks after every opcode and the script failure reason, if any.
+        DebugScript,
         /// Estimates the network's current hash rate in hashes per second.
         /// Returned information: Estimated network hashes per second.
         EstimateNetworkHashesPerSecond,
@@ -1102,12 +1123,21 @@ build_wrpc_wasm_bindgen_interface!(
         /// Retrieves balances for multiple addresses in the Karlsen BlockDAG.
         /// Returned information: Balances of the addresses.
         GetBalancesByAddresses,
+        /// Retrieves balances for multiple addresses as of a past DAA score.
+        /// Returned information: Balances of the addresses and the chain block they are taken at.
+        GetBalanceByAddressesAt,
         /// Retrieves a specific block from the Karlsen BlockDAG.
         /// Returned information: Block information.
         GetBlock,
         /// Retrieves multiple blocks from the Karlsen BlockDAG.
         /// Returned information: List of block information.
         GetBlocks,
+        /// Retrieves the compact filter headers of a range of selected chain blocks.
+        /// Returned information: List of filter headers and the tip of the filter index.
+        GetBlockFilterHeaders,
+        /// Retrieves the compact filters of a range of selected chain blocks.
+        /// Returned information: List of block filters.
+        GetBlockFilters,
         /// Generates a new block template for mining.
         /// Returned information: Block template information.
         GetBlockTemplate,
@@ -1118,6 +1148,27 @@ build_wrpc_wasm_bindgen_interface!(
         /// Retrieves the current network configuration.
         /// Returned information: Current network configuration.
         GetCurrentNetwork,
+        /// Retrieves the difficulty adjustment state of the virtual block.
+        /// Returned information: DAA window summary, next target and recent chain blocks difficulty.
+        GetDifficultyInfo,
+        /// Retrieves the blocks of the DAG starting at a DAA score.
+        /// Returned information: List of blocks with their parents, colors and chain membership.
+        GetDagSlice,
+        /// Replays the chain acceptance events journaled after a given cursor.
+        /// Returned information: List of chain blocks with their accepted transactions, gap and pagination flags.
+        GetAcceptanceEventsSince,
+        /// Retrieves a block template as a diff relative to a previously issued template.
+        /// Returned information: Header, coinbase and the added and removed transactions.
+        GetTemplateDiff,
+        /// Reads entries of a namespace of the external indexer metadata store (unsafe RPC mode only).
+        /// Returned information: List of the entries found.
+        GetMetadata,
+        /// Atomically updates entries of a namespace of the external indexer metadata store (unsafe RPC mode only).
+        /// Returned information: None.
+        UpdateMetadata,
+        /// Retrieves the addresses assumed to share the owner of an address (analytics nodes only).
+        /// Returned information: Cluster size and addresses.
+        GetAddressCluster,
         /// Retrieves block headers from the Karlsen BlockDAG.
         /// Returned information: List of block headers.
         GetHeaders,
@@ -1133,6 +1184,12 @@ build_wrpc_wasm_bindgen_interface!(
         /// Retrieves information about a subnetwork in the Karlsen BlockDAG.
         /// Returned information: Subnetwork information.
         GetSubnetwork,
+        /// Retrieves the broadcast and acceptance status of a transaction submitted to the node.
+        /// Returned information: Mempool presence, announced peers, accepting block and confirmations.
+        GetTransactionStatus,
+        /// Retrieves heuristics on the risk of accepting an unconfirmed transaction.
+        /// Returned information: Fee rate percentile, conflicting spends, input ages and announcing peers.
+        GetUnconfirmedTxRisk,
         /// Retrieves unspent transaction outputs (UTXOs) associated with
         /// specific addresses.
         /// Returned information: List of UTXOs.
@@ -1149,6 +1206,9 @@ build_wrpc_wasm_bindgen_interface!(
         /// Submits a transaction to the Karlsen network.
         /// Returned information: None.
         SubmitTransaction,
+        /// Runs the full mempool validation of a transaction without submitting it.
+        /// Returned information: Acceptance, rejection reason, fee, masses and feerate.
+        TestMempoolAccept,
         /// Unbans a previously banned peer, allowing it to connect
         /// to the Karlsen node again.
         /// Returned information: None.
@@ -21,6 +21,8 @@ export enum RpcEventType {
     VirtualDaaScoreChanged = "virtual-daa-score-changed",
     PruningPointUtxoSetOverride = "pruning-point-utxo-set-override",
     NewBlockTemplate = "new-block-template",
+    TransactionStatusChanged = "transaction-status-changed",
+    DoubleSpendDetected = "double-spend-detected",
 }
 
 /**
@@ -36,7 +38,9 @@ export type RpcEventData = IBlockAdded
     | ISinkBlueScoreChanged 
     | IVirtualDaaScoreChanged 
     | IPruningPointUtxoSetOverride 
-    | INewBlockTemplate;
+    | INewBlockTemplate 
+    | ITransactionStatusChanged 
+    | IDoubleSpendDetected;
 
 /**
  * RPC notification event data map.
@@ -55,6 +59,8 @@ export type RpcEventMap = {
     "virtual-daa-score-changed" : IVirtualDaaScoreChanged,
     "pruning-point-utxo-set-override" : IPruningPointUtxoSetOverride,
     "new-block-template" : INewBlockTemplate,
+    "transaction-status-changed" : ITransactionStatusChanged,
+    "double-spend-detected" : IDoubleSpendDetected,
 }
 
 /**
@@ -80,6 +86,8 @@ export type RpcEvent = {
  * {@link RpcClient.subscribeSinkBlueScoreChanged},
  * {@link RpcClient.subscribePruningPointUtxoSetOverride},
  * {@link RpcClient.subscribeNewBlockTemplate},
+ * {@link RpcClient.subscribeTransactionStatusChanged},
+ * {@link RpcClient.subscribeDoubleSpendDetected},
  * 
  * @category Node RPC
  */
@@ -237,3 +245,39 @@ declare! {
     }
     "#,
 }
+
+declare! {
+    ITransactionStatusChanged,
+    r#"
+    /**
+     * Transaction status changed notification event is produced when a
+     * transaction submitted through the RPC of the node gets accepted by
+     * a selected chain block or unaccepted by a reorg.
+     * 
+     * @category Node RPC
+     */
+    export interface ITransactionStatusChanged {
+        transactionId : HexString;
+        acceptingBlockHash? : HexString;
+    }
+    "#,
+}
+
+declare! {
+    IDoubleSpendDetected,
+    r#"
+    /**
+     * Double spend detected notification event is produced when a transaction
+     * spending outpoints already spent by a mempool or a recently accepted
+     * transaction is observed.
+     * 
+     * @category Node RPC
+     */
+    export interface IDoubleSpendDetected {
+        transactionId : HexString;
+        conflictingTransactionId : HexString;
+        outpoints : ITransactionOutpoint[];
+        acceptedTransactionId? : HexString;
+    }
+    "#,
+}
@@ -28,7 +28,7 @@ use karlsen_database::{create_temp_db, load_existing_db};
 use karlsen_hashes::Hash;
 use karlsen_perf_monitor::{builder::Builder, counters::CountersSnapshot};
 use karlsen_utils::fd_budget;
-use simulator::network::KarlsenNetworkSimulator;
+use simulator::{fishhash::FishHashModel, network::KarlsenNetworkSimulator};
 use std::{collections::VecDeque, sync::Arc, time::Duration};
 
 pub mod simulator;
@@ -57,6 +57,19 @@ struct Args {
     #[arg(short, long, default_value_t = 600)]
     sim_time: u64,
 
+    /// Time miners spend building the FishHash dataset before they start hashing (seconds)
+    #[arg(long, default_value_t = 0.0)]
+    dataset_warmup: f64,
+
+    /// Fluctuation of the hashrate of the miners, as the standard deviation of its log, sampled
+    /// for every block
+    #[arg(long, default_value_t = 0.0)]
+    hashrate_deviation: f64,
+
+    /// Delay between a miner finding a block and broadcasting it (seconds)
+    #[arg(long, default_value_t = 0.0)]
+    submit_latency: f64,
+
     /// Target number of blocks the simulation should produce (overrides --sim-time if specified)
     #[arg(short = 'n', long)]
     target_blocks: Option<u64>,
@@ -262,6 +275,11 @@ fn main_impl(mut args: Args) {
             args.target_blocks,
             config.clone(),
             args.output_dir,
+            FishHashModel::new(
+                (args.dataset_warmup * 1000.0) as u64,
+                args.hashrate_deviation,
+                (args.submit_latency * 1000.0) as u64,
+            ),
         );
         let (consensus, handles, lifetime) = sim
             .init(
@@ -0,0 +1,134 @@
+//! Parameterized model of the miner-side timing of FishHash mining.
+//!
+//! Block discovery remains a Poisson process, no hashing actually happens. Around it, a miner
+//! first builds the FishHash dataset before it can hash at all, then hashes at a rate which
+//! fluctuates with its memory-bound workload and submits the blocks it finds with a latency.
+//! With the default parameters mining stays instantaneous.
+
+use karlsen_core::info;
+use rand::Rng;
+use rand_distr::{Distribution, LogNormal};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Relative spread of the dataset warm-up time among miners
+const WARMUP_SPREAD: f64 = 0.1;
+
+#[derive(Clone)]
+pub struct FishHashModel {
+    /// Mean time for building the dataset before hashing starts, in milliseconds
+    dataset_warmup: u64,
+    /// Hashrate of a miner relative to its nominal hashrate, sampled for every block
+    speed: Option<LogNormal<f64>>,
+    /// Delay between finding a block and broadcasting it, in milliseconds
+    submit_latency: u64,
+}
+
+impl FishHashModel {
+    /// Builds a model where the log of the relative hashrate of a miner has a standard deviation
+    /// of `hashrate_deviation`, keeping the mean hashrate at its nominal value
+    pub fn new(dataset_warmup: u64, hashrate_deviation: f64, submit_latency: u64) -> Self {
+        assert!(
+            hashrate_deviation >= 0.0,
+            "the hashrate deviation cannot be negative"
+        );
+        let speed = (hashrate_deviation > 0.0).then(|| {
+            LogNormal::new(
+                -hashrate_deviation * hashrate_deviation / 2.0,
+                hashrate_deviation,
+            )
+            .unwrap()
+        });
+        Self {
+            dataset_warmup,
+            speed,
+            submit_latency,
+        }
+    }
+
+    pub fn submit_latency(&self) -> u64 {
+        self.submit_latency
+    }
+
+    /// Samples the time a miner spends building the dataset
+    pub fn sample_warmup(&self, rng: &mut impl Rng) -> u64 {
+        if self.dataset_warmup == 0 {
+            return 0;
+        }
+        (self.dataset_warmup as f64 * rng.gen_range(1.0 - WARMUP_SPREAD..=1.0 + WARMUP_SPREAD))
+            as u64
+    }
+
+    /// Samples the hashrate of a miner relative to its nominal hashrate
+    pub fn sample_speed(&self, rng: &mut impl Rng) -> f64 {
+        self.speed.as_ref().map_or(1.0, |speed| speed.sample(rng))
+    }
+}
+
+impl Default for FishHashModel {
+    fn default() -> Self {
+        Self::new(0, 0.0, 0)
+    }
+}
+
+/// Mining counters of a simulated miner, reported once the simulation ends
+#[derive(Default)]
+pub struct MinerStats {
+    /// Time the miner spent building the dataset, in milliseconds
+    pub warmup: AtomicU64,
+    /// Simulation time of the last event of the miner, in milliseconds
+    pub last_event_time: AtomicU64,
+    pub mined_blocks: AtomicU64,
+}
+
+impl MinerStats {
+    /// Logs the effective hashrate of the miner, expressed as its share of the network hashrate
+    /// needed to mine its blocks at `bps` during the time it was hashing
+    pub fn report(&self, id: u64, nominal_share: f64, bps: f64, start_time: u64) {
+        let warmup = self.warmup.load(Ordering::Relaxed);
+        let mined_blocks = self.mined_blocks.load(Ordering::Relaxed);
+        let hashing_time = self
+            .last_event_time
+            .load(Ordering::Relaxed)
+            .saturating_sub(start_time + warmup);
+        let effective_share = if hashing_time > 0 {
+            mined_blocks as f64 / (bps * hashing_time as f64 / 1000.0)
+        } else {
+            0.0
+        };
+        info!(
+            "[Miner {}] warm-up: {:.1}s, hashing: {:.1}s, mined blocks: {}, hashrate share: nominal {:.2}%, effective {:.2}%",
+            id,
+            warmup as f64 / 1000.0,
+            hashing_time as f64 / 1000.0,
+            mined_blocks,
+            nominal_share * 100.0,
+            effective_share * 100.0
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fishhash_model() {
+        let mut rng = rand::thread_rng();
+
+        let instantaneous = FishHashModel::default();
+        assert_eq!(instantaneous.sample_warmup(&mut rng), 0);
+        assert_eq!(instantaneous.sample_speed(&mut rng), 1.0);
+
+        let model = FishHashModel::new(60_000, 0.5, 200);
+        for _ in 0..100 {
+            assert!((54_000..=66_000).contains(&model.sample_warmup(&mut rng)));
+        }
+        // The sampled hashrate fluctuates around the nominal hashrate
+        let samples = 100_000;
+        let mean = (0..samples)
+            .map(|_| model.sample_speed(&mut rng))
+            .sum::<f64>()
+            / samples as f64;
+        assert!((mean - 1.0).abs() < 0.02, "mean speed {}", mean);
+    }
+}
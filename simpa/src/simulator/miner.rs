@@ -22,8 +22,11 @@ use rand_distr::{Distribution, Exp};
 use rayon::prelude::{IntoParallelIterator, ParallelIterator};
 use std::cmp::max;
 use std::iter::once;
+use std::sync::atomic::Ordering;
 use std::sync::Arc;
 
+use super::fishhash::{FishHashModel, MinerStats};
+
 struct OnetimeTxSelector {
     txs: Option<Vec<Transaction>>,
 }
@@ -67,6 +70,10 @@ pub struct Miner {
     dist: Exp<f64>, // The time interval between Poisson(lambda) events distributes ~Exp(lambda)
     rng: ThreadRng,
 
+    // Mining timing
+    fishhash: FishHashModel,
+    stats: Arc<MinerStats>,
+
     // Counters
     num_blocks: u64,
     sim_time: u64,
@@ -91,6 +98,8 @@ impl Miner {
         params: &Params,
         target_txs_per_block: u64,
         target_blocks: Option<u64>,
+        fishhash: FishHashModel,
+        stats: Arc<MinerStats>,
     ) -> Self {
         let (schnorr_public_key, _) = pk.x_only_public_key();
         let script_pub_key_script = once(0x20)
@@ -110,6 +119,8 @@ impl Miner {
             possible_unspent_outpoints: IndexSet::new(),
             dist: Exp::new(bps * hashrate).unwrap(),
             rng: rand::thread_rng(),
+            fishhash,
+            stats,
             num_blocks: 0,
             sim_time: 0,
             target_txs_per_block,
@@ -239,15 +250,36 @@ impl Miner {
 
     pub fn mine(&mut self, env: &mut Environment<Block>) -> Suspension {
         let block = self.build_new_block(env.now());
-        env.broadcast(self.id, block);
+        env.broadcast_after(self.fishhash.submit_latency(), self.id, block);
+        self.stats.mined_blocks.fetch_add(1, Ordering::Relaxed);
+        self.stats
+            .last_event_time
+            .store(env.now(), Ordering::Relaxed);
         self.sample_mining_interval()
     }
 
+    /// Waits for the dataset to be built before hashing for the first block
+    fn warm_up(&mut self) -> Suspension {
+        let warmup = self.fishhash.sample_warmup(&mut self.rng);
+        self.stats.warmup.store(warmup, Ordering::Relaxed);
+        match self.sample_mining_interval() {
+            Suspension::Timeout(interval) => Suspension::Timeout(warmup + interval),
+            suspension => suspension,
+        }
+    }
+
     fn sample_mining_interval(&mut self) -> Suspension {
-        Suspension::Timeout(max((self.dist.sample(&mut self.rng) * 1000.0) as u64, 1))
+        let speed = self.fishhash.sample_speed(&mut self.rng);
+        Suspension::Timeout(max(
+            (self.dist.sample(&mut self.rng) / speed * 1000.0) as u64,
+            1,
+        ))
     }
 
     fn process_block(&mut self, block: Block, env: &mut Environment<Block>) -> Suspension {
+        self.stats
+            .last_event_time
+            .store(env.now(), Ordering::Relaxed);
         for tx in block.transactions.iter() {
             for (i, output) in tx.outputs.iter().enumerate() {
                 if output
@@ -308,7 +340,7 @@ impl Process<Block> for Miner {
         env: &mut Environment<Block>,
     ) -> Suspension {
         match resumption {
-            Resumption::Initial => self.sample_mining_interval(),
+            Resumption::Initial => self.warm_up(),
             Resumption::Scheduled => self.mine(env),
             Resumption::Message(block) => self.process_block(block, env),
         }
@@ -1,2 +1,3 @@
+pub mod fishhash;
 pub mod miner;
 pub mod network;
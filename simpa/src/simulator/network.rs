@@ -4,6 +4,7 @@ use karlsen_core::time::unix_now;
 use std::sync::Arc;
 use std::thread::JoinHandle;
 
+use super::fishhash::{FishHashModel, MinerStats};
 use super::miner::Miner;
 
 use karlsen_consensus::config::Config;
@@ -28,6 +29,10 @@ pub struct KarlsenNetworkSimulator {
     bps: f64,                   // Blocks per second
     target_blocks: Option<u64>, // Target simulation blocks
     output_dir: Option<String>, // Possible permanent output directory
+    fishhash: FishHashModel,    // Miner-side timing of mining
+
+    // Mining counters of the miners
+    miner_stats: Vec<Arc<MinerStats>>,
 }
 
 impl KarlsenNetworkSimulator {
@@ -37,6 +42,7 @@ impl KarlsenNetworkSimulator {
         target_blocks: Option<u64>,
         config: Arc<Config>,
         output_dir: Option<String>,
+        fishhash: FishHashModel,
     ) -> Self {
         Self {
             simulation: Simulation::with_start_time(
@@ -48,6 +54,8 @@ impl KarlsenNetworkSimulator {
             config,
             target_blocks,
             output_dir,
+            fishhash,
+            miner_stats: Vec::new(),
         }
     }
 
@@ -111,6 +119,7 @@ impl KarlsenNetworkSimulator {
             ));
             let handles = consensus.run_processors();
             let (sk, pk) = secp.generate_keypair(&mut rng);
+            let stats = Arc::new(MinerStats::default());
             let miner_process = Box::new(Miner::new(
                 i,
                 self.bps,
@@ -121,8 +130,11 @@ impl KarlsenNetworkSimulator {
                 &self.config,
                 target_txs_per_block,
                 self.target_blocks,
+                self.fishhash.clone(),
+                stats.clone(),
             ));
             self.simulation.register(i, miner_process);
+            self.miner_stats.push(stats);
             self.consensuses.push((consensus, handles, lifetime));
         }
         self
@@ -130,6 +142,15 @@ impl KarlsenNetworkSimulator {
 
     pub fn run(&mut self, until: u64) -> ConsensusWrapper {
         self.simulation.run(until);
+        let nominal_share = 1f64 / self.miner_stats.len() as f64;
+        for (id, stats) in self.miner_stats.iter().enumerate() {
+            stats.report(
+                id as u64,
+                nominal_share,
+                self.bps,
+                self.config.genesis.timestamp,
+            );
+        }
         for (consensus, handles, _) in self.consensuses.drain(1..) {
             consensus.shutdown(handles);
         }
@@ -0,0 +1,244 @@
+//!
+//! The state of each node observed through RPC after every step, and the comparison of these states.
+//!
+
+use karlsen_addresses::Address;
+use karlsen_consensus_core::tx::{TransactionId, TransactionOutpoint, UtxoEntry};
+use karlsen_hashes::Hash;
+use karlsen_rpc_core::{api::rpc::RpcApi, RpcResult};
+use std::fmt::Debug;
+
+/// The virtual state of a node, along with the acceptance data of the chain blocks added by the step
+#[derive(Clone, Debug, PartialEq)]
+pub struct NodeState {
+    pub sink: Hash,
+    pub sink_blue_score: u64,
+    pub tips: Vec<Hash>,
+    pub virtual_parents: Vec<Hash>,
+    pub virtual_daa_score: u64,
+    pub past_median_time: u64,
+    pub difficulty: f64,
+    pub pruning_point: Hash,
+    /// Removed chain blocks since the sink of the previous step
+    pub removed_chain_blocks: Vec<Hash>,
+    /// Added chain blocks since the sink of the previous step, with the ids of the transactions they accepted
+    pub accepted_transactions: Vec<(Hash, Vec<TransactionId>)>,
+    /// UTXO set of the generator address
+    pub utxos: Vec<(TransactionOutpoint, UtxoEntry)>,
+}
+
+impl NodeState {
+    /// Reads the state of the node behind `client`, the chain changes being read from `chain_start`. Lists
+    /// are sorted, the order of the RPC responses being an implementation detail.
+    pub async fn fetch(
+        client: &impl RpcApi,
+        chain_start: Hash,
+        address: &Address,
+    ) -> RpcResult<Self> {
+        let dag_info = client.get_block_dag_info().await?;
+        let sink_blue_score = client.get_sink_blue_score().await?;
+        let chain = client
+            .get_virtual_chain_from_block(chain_start, true)
+            .await?;
+        let mut utxos = client
+            .get_utxos_by_addresses(vec![address.clone()])
+            .await?
+            .into_iter()
+            .map(|entry| (entry.outpoint, entry.utxo_entry))
+            .collect::<Vec<_>>();
+        utxos.sort_by_key(|(outpoint, _)| (outpoint.transaction_id, outpoint.index));
+        Ok(Self {
+            sink: dag_info.sink,
+            sink_blue_score,
+            tips: sorted(dag_info.tip_hashes),
+            virtual_parents: sorted(dag_info.virtual_parent_hashes),
+            virtual_daa_score: dag_info.virtual_daa_score,
+            past_median_time: dag_info.past_median_time,
+            difficulty: dag_info.difficulty,
+            pruning_point: dag_info.pruning_point_hash,
+            removed_chain_blocks: chain.removed_chain_block_hashes,
+            accepted_transactions: chain
+                .accepted_transaction_ids
+                .into_iter()
+                .map(|accepted| {
+                    (
+                        accepted.accepting_block_hash,
+                        sorted(accepted.accepted_transaction_ids),
+                    )
+                })
+                .collect(),
+            utxos,
+        })
+    }
+
+    /// Returns a description of every field differing between the local and the reference states
+    pub fn diff(&self, reference: &NodeState) -> Vec<String> {
+        let mut diffs = Vec::new();
+        compare("sink", &self.sink, &reference.sink, &mut diffs);
+        compare(
+            "sink blue score",
+            &self.sink_blue_score,
+            &reference.sink_blue_score,
+            &mut diffs,
+        );
+        compare("tips", &self.tips, &reference.tips, &mut diffs);
+        compare(
+            "virtual parents",
+            &self.virtual_parents,
+            &reference.virtual_parents,
+            &mut diffs,
+        );
+        compare(
+            "virtual DAA score",
+            &self.virtual_daa_score,
+            &reference.virtual_daa_score,
+            &mut diffs,
+        );
+        compare(
+            "past median time",
+            &self.past_median_time,
+            &reference.past_median_time,
+            &mut diffs,
+        );
+        // The difficulty ratio is a float computed by both nodes
+        if (self.difficulty - reference.difficulty).abs() > self.difficulty.abs() * 1e-9 {
+            compare(
+                "difficulty",
+                &self.difficulty,
+                &reference.difficulty,
+                &mut diffs,
+            );
+        }
+        compare(
+            "pruning point",
+            &self.pruning_point,
+            &reference.pruning_point,
+            &mut diffs,
+        );
+        compare(
+            "removed chain blocks",
+            &self.removed_chain_blocks,
+            &reference.removed_chain_blocks,
+            &mut diffs,
+        );
+        compare(
+            "accepted transactions",
+            &self.accepted_transactions,
+            &reference.accepted_transactions,
+            &mut diffs,
+        );
+        compare("UTXO set", &self.utxos, &reference.utxos, &mut diffs);
+        diffs
+    }
+}
+
+/// The GHOSTDAG view of a node on a block
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BlockState {
+    pub is_chain_block: bool,
+    pub blue_score: u64,
+    pub selected_parent: Hash,
+    pub mergeset_blues: Vec<Hash>,
+    pub mergeset_reds: Vec<Hash>,
+}
+
+impl BlockState {
+    /// Reads the view of the node behind `client` on the block `hash`, `None` if the node has no such block
+    pub async fn fetch(client: &impl RpcApi, hash: Hash) -> RpcResult<Option<Self>> {
+        let Ok(block) = client.get_block(hash, false).await else {
+            return Ok(None);
+        };
+        let verbose_data = block
+            .verbose_data
+            .unwrap_or_else(|| panic!("the block {hash} has no verbose data"));
+        Ok(Some(Self {
+            is_chain_block: verbose_data.is_chain_block,
+            blue_score: verbose_data.blue_score,
+            selected_parent: verbose_data.selected_parent_hash,
+            mergeset_blues: sorted(verbose_data.merge_set_blues_hashes),
+            mergeset_reds: sorted(verbose_data.merge_set_reds_hashes),
+        }))
+    }
+}
+
+/// The decision of a node on a submitted block or transaction
+#[derive(Clone, Debug)]
+pub struct Decision {
+    pub accepted: bool,
+    /// The rejection reason reported by the node, which is implementation specific
+    pub reason: String,
+}
+
+impl Decision {
+    pub fn new<T, E: Debug>(result: &Result<T, E>) -> Self {
+        match result {
+            Ok(_) => Self::accepted(),
+            Err(err) => Self::rejected(format!("{err:?}")),
+        }
+    }
+
+    pub fn accepted() -> Self {
+        Self {
+            accepted: true,
+            reason: String::new(),
+        }
+    }
+
+    pub fn rejected(reason: impl Into<String>) -> Self {
+        Self {
+            accepted: false,
+            reason: reason.into(),
+        }
+    }
+}
+
+fn compare<T: PartialEq + Debug>(field: &str, local: &T, reference: &T, diffs: &mut Vec<String>) {
+    if local != reference {
+        diffs.push(format!(
+            "{field}: {local:?} locally, {reference:?} on the reference node"
+        ));
+    }
+}
+
+fn sorted<T: Ord>(mut items: Vec<T>) -> Vec<T> {
+    items.sort();
+    items
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn state() -> NodeState {
+        NodeState {
+            sink: Hash::from_u64_word(1),
+            sink_blue_score: 10,
+            tips: vec![Hash::from_u64_word(1), Hash::from_u64_word(2)],
+            virtual_parents: vec![Hash::from_u64_word(1)],
+            virtual_daa_score: 12,
+            past_median_time: 1000,
+            difficulty: 1.5,
+            pruning_point: Hash::from_u64_word(0),
+            removed_chain_blocks: vec![],
+            accepted_transactions: vec![(Hash::from_u64_word(1), vec![Hash::from_u64_word(3)])],
+            utxos: vec![],
+        }
+    }
+
+    #[test]
+    fn test_state_diff() {
+        let local = state();
+        assert!(local.diff(&state()).is_empty());
+
+        let mut reference = state();
+        reference.difficulty += 1e-12;
+        assert!(local.diff(&reference).is_empty());
+
+        reference.tips.pop();
+        reference.accepted_transactions[0].1.clear();
+        let diffs = local.diff(&reference);
+        assert_eq!(diffs.len(), 2);
+        assert!(diffs[0].starts_with("tips"));
+        assert!(diffs[1].starts_with("accepted transactions"));
+    }
+}
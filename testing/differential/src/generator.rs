@@ -0,0 +1,432 @@
+//!
+//! Seeded generation of the blocks and transactions submitted to both nodes. Most of them are valid, the others
+//! break a single rule so that the rejection decisions of both nodes are compared as well.
+//!
+
+use itertools::Itertools;
+use karlsen_addresses::{Address, Prefix, Version};
+use karlsen_consensus_core::{
+    block::{Block, MutableBlock},
+    constants::TX_VERSION,
+    merkle::calc_hash_merkle_root_with_options,
+    sign::sign,
+    subnets::SUBNETWORK_ID_NATIVE,
+    tx::{
+        MutableTransaction, Transaction, TransactionInput, TransactionOutpoint, TransactionOutput,
+        UtxoEntry,
+    },
+};
+use karlsen_hashes::Hash;
+use karlsen_txscript::pay_to_address_script;
+use rand::{rngs::StdRng, seq::SliceRandom, Rng, SeedableRng};
+use secp256k1::Keypair;
+use std::fmt::{self, Display, Formatter};
+
+/// Fee paid by every generated transaction, well above the minimum relay fee of both nodes
+pub const TRANSACTION_FEE: u64 = 100_000;
+
+/// Maximum advance of the seeded clock between two steps, in milliseconds. Steps last longer than that, so the
+/// clock stays behind the wall clock of the nodes.
+const MAX_CLOCK_ADVANCE: u64 = 500;
+
+/// Timestamp of the year 3000, beyond the allowed deviation whatever the seeded clock
+const FAR_FUTURE_TIMESTAMP: u64 = 32_503_680_000_000;
+
+/// A change applied to a block template before it is submitted
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BlockMutation {
+    None,
+    /// The timestamp is set to the year 3000, beyond the allowed deviation
+    FutureTimestamp,
+    /// The timestamp is set to zero, below the past median time
+    ZeroTimestamp,
+    WrongBits,
+    WrongVersion,
+    WrongMerkleRoot,
+    /// The coinbase transaction is removed, along with all the others
+    NoTransactions,
+    /// The last transaction is included twice, the merkle root being recomputed
+    DuplicateTransaction,
+}
+
+impl BlockMutation {
+    const INVALID: [BlockMutation; 7] = [
+        BlockMutation::FutureTimestamp,
+        BlockMutation::ZeroTimestamp,
+        BlockMutation::WrongBits,
+        BlockMutation::WrongVersion,
+        BlockMutation::WrongMerkleRoot,
+        BlockMutation::NoTransactions,
+        BlockMutation::DuplicateTransaction,
+    ];
+
+    /// Returns the mutated block. The merkle root of `block` tells whether transaction hashes include the mass.
+    pub fn apply(self, block: &Block, rng: &mut StdRng) -> Block {
+        let include_mass_field =
+            calc_hash_merkle_root_with_options(block.transactions.iter(), true)
+                == block.header.hash_merkle_root;
+        let mut mutable =
+            MutableBlock::new((*block.header).clone(), block.transactions.as_ref().clone());
+        let header = &mut mutable.header;
+        match self {
+            BlockMutation::None => {}
+            BlockMutation::FutureTimestamp => header.timestamp = FAR_FUTURE_TIMESTAMP,
+            BlockMutation::ZeroTimestamp => header.timestamp = 0,
+            BlockMutation::WrongBits => header.bits ^= 1,
+            BlockMutation::WrongVersion => header.version = header.version.wrapping_add(1),
+            BlockMutation::WrongMerkleRoot => header.hash_merkle_root = Hash::from_bytes(rng.gen()),
+            BlockMutation::NoTransactions => {
+                mutable.transactions.clear();
+                mutable.header.hash_merkle_root = calc_hash_merkle_root_with_options(
+                    mutable.transactions.iter(),
+                    include_mass_field,
+                );
+            }
+            BlockMutation::DuplicateTransaction => {
+                let last = mutable.transactions.last().cloned();
+                mutable.transactions.extend(last);
+                mutable.header.hash_merkle_root = calc_hash_merkle_root_with_options(
+                    mutable.transactions.iter(),
+                    include_mass_field,
+                );
+            }
+        }
+        mutable.header.finalize();
+        mutable.to_immutable()
+    }
+}
+
+/// Returns `template` with `timestamp` in place of the wall-clock time set by the node, and its transactions
+/// sorted by id behind the coinbase, the selection order of the node not being seeded. Generated transactions
+/// never spend each other, so any order is valid.
+pub fn seeded_block(template: &Block, timestamp: u64) -> Block {
+    let include_mass_field = calc_hash_merkle_root_with_options(template.transactions.iter(), true)
+        == template.header.hash_merkle_root;
+    let mut mutable = MutableBlock::new(
+        (*template.header).clone(),
+        template.transactions.as_ref().clone(),
+    );
+    if let Some((_, transactions)) = mutable.transactions.split_first_mut() {
+        transactions.sort_by_key(|transaction| transaction.id());
+    }
+    mutable.header.hash_merkle_root =
+        calc_hash_merkle_root_with_options(mutable.transactions.iter(), include_mass_field);
+    mutable.header.timestamp = timestamp;
+    mutable.header.finalize();
+    mutable.to_immutable()
+}
+
+impl Display for BlockMutation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            BlockMutation::None => "none",
+            BlockMutation::FutureTimestamp => "future timestamp",
+            BlockMutation::ZeroTimestamp => "zero timestamp",
+            BlockMutation::WrongBits => "wrong bits",
+            BlockMutation::WrongVersion => "wrong version",
+            BlockMutation::WrongMerkleRoot => "wrong merkle root",
+            BlockMutation::NoTransactions => "no transactions",
+            BlockMutation::DuplicateTransaction => "duplicate transaction",
+        };
+        f.write_str(name)
+    }
+}
+
+/// A change applied to a generated transaction before it is submitted
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TransactionMutation {
+    None,
+    /// The outputs spend more than the inputs
+    Overspend,
+    /// A byte of the first signature is flipped
+    WrongSignature,
+    /// The transaction spends the same outpoint twice
+    DuplicateInput,
+}
+
+impl TransactionMutation {
+    const INVALID: [TransactionMutation; 3] = [
+        TransactionMutation::Overspend,
+        TransactionMutation::WrongSignature,
+        TransactionMutation::DuplicateInput,
+    ];
+}
+
+impl Display for TransactionMutation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            TransactionMutation::None => "none",
+            TransactionMutation::Overspend => "overspend",
+            TransactionMutation::WrongSignature => "wrong signature",
+            TransactionMutation::DuplicateInput => "duplicate input",
+        };
+        f.write_str(name)
+    }
+}
+
+/// Draws the blocks and transactions of a run from a seed. Block timestamps come from a seeded clock rather than
+/// from the templates, so two runs with the same seed from the same starting state submit the same blocks.
+pub struct Generator {
+    rng: StdRng,
+    keypair: Keypair,
+    address: Address,
+    /// Probability of a generated block or transaction to be invalid
+    invalid_rate: f64,
+    /// Probability of a step to submit sibling blocks along with its block
+    sibling_rate: f64,
+    /// Current time of the seeded clock, in milliseconds
+    clock: u64,
+}
+
+impl Generator {
+    /// Creates a generator whose clock starts at `start_time`, the timestamp of the starting sink
+    pub fn new(
+        seed: u64,
+        prefix: Prefix,
+        invalid_rate: f64,
+        sibling_rate: f64,
+        start_time: u64,
+    ) -> Self {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let keypair = loop {
+            if let Ok(keypair) =
+                Keypair::from_seckey_slice(secp256k1::SECP256K1, &rng.gen::<[u8; 32]>())
+            {
+                break keypair;
+            }
+        };
+        let address = Address::new(
+            prefix,
+            Version::PubKey,
+            &keypair.x_only_public_key().0.serialize(),
+        );
+        Self {
+            rng,
+            keypair,
+            address,
+            invalid_rate,
+            sibling_rate,
+            clock: start_time,
+        }
+    }
+
+    /// The address receiving the coinbase rewards and the outputs of the generated transactions
+    pub fn address(&self) -> &Address {
+        &self.address
+    }
+
+    pub fn rng(&mut self) -> &mut StdRng {
+        &mut self.rng
+    }
+
+    /// Advances the seeded clock, returning the timestamp of the block of the next step
+    pub fn next_timestamp(&mut self) -> u64 {
+        self.clock += self.rng.gen_range(1..=MAX_CLOCK_ADVANCE);
+        self.clock
+    }
+
+    /// Returns the number of sibling blocks built on the parents of the block of the next step. The next template
+    /// merges them, so the merging rules are compared as well.
+    pub fn next_sibling_count(&mut self) -> usize {
+        match self.rng.gen_bool(self.sibling_rate) {
+            true => self.rng.gen_range(1..=2),
+            false => 0,
+        }
+    }
+
+    /// Returns the coinbase extra data of a sibling block, making its coinbase differ from the other blocks of the step
+    pub fn next_extra_data(&mut self) -> Vec<u8> {
+        self.rng.gen::<[u8; 8]>().to_vec()
+    }
+
+    pub fn next_block_mutation(&mut self) -> BlockMutation {
+        match self.rng.gen_bool(self.invalid_rate) {
+            true => *BlockMutation::INVALID.choose(&mut self.rng).unwrap(),
+            false => BlockMutation::None,
+        }
+    }
+
+    /// Spends up to two of `utxos` to the generator address, removing the spent ones
+    pub fn next_transaction(
+        &mut self,
+        utxos: &mut Vec<(TransactionOutpoint, UtxoEntry)>,
+    ) -> Option<(Transaction, TransactionMutation)> {
+        if utxos.is_empty() {
+            return None;
+        }
+        let mutation = match self.rng.gen_bool(self.invalid_rate) {
+            true => *TransactionMutation::INVALID.choose(&mut self.rng).unwrap(),
+            false => TransactionMutation::None,
+        };
+        let input_count = self.rng.gen_range(1..=utxos.len().min(2));
+        let mut spent = (0..input_count)
+            .map(|_| utxos.swap_remove(self.rng.gen_range(0..utxos.len())))
+            .collect_vec();
+        if mutation == TransactionMutation::DuplicateInput {
+            spent.push(spent[0].clone());
+        }
+
+        let total_in = spent.iter().map(|(_, entry)| entry.amount).sum::<u64>();
+        let mut total_out = total_in.saturating_sub(TRANSACTION_FEE);
+        if mutation == TransactionMutation::Overspend {
+            total_out = total_in + 1;
+        }
+        let output_count = self.rng.gen_range(1..=2u64);
+        let script_public_key = pay_to_address_script(&self.address);
+        let outputs = (0..output_count)
+            .map(|i| TransactionOutput {
+                // The first output takes the remainder
+                value: total_out / output_count + if i == 0 { total_out % output_count } else { 0 },
+                script_public_key: script_public_key.clone(),
+            })
+            .collect_vec();
+        let inputs = spent
+            .iter()
+            .map(|(outpoint, _)| TransactionInput {
+                previous_outpoint: *outpoint,
+                signature_script: vec![],
+                sequence: 0,
+                sig_op_count: 1,
+            })
+            .collect_vec();
+        let unsigned = Transaction::new(
+            TX_VERSION,
+            inputs,
+            outputs,
+            0,
+            SUBNETWORK_ID_NATIVE,
+            0,
+            vec![],
+        );
+        let entries = spent.into_iter().map(|(_, entry)| entry).collect_vec();
+        let mut transaction = sign(
+            MutableTransaction::with_entries(unsigned, entries),
+            self.keypair,
+        )
+        .tx;
+        if mutation == TransactionMutation::WrongSignature {
+            // The signature follows the push opcode
+            transaction.inputs[0].signature_script[1] ^= 1;
+            transaction.finalize();
+        }
+        Some((transaction, mutation))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use karlsen_consensus_core::{
+        header::Header, subnets::SUBNETWORK_ID_COINBASE, tx::ScriptPublicKey,
+    };
+
+    fn coinbase_block() -> Block {
+        let coinbase = Transaction::new(
+            0,
+            vec![],
+            vec![TransactionOutput {
+                value: 50,
+                script_public_key: ScriptPublicKey::from_vec(0, vec![1; 34]),
+            }],
+            0,
+            SUBNETWORK_ID_COINBASE,
+            0,
+            vec![],
+        );
+        let mut header = Header::from_precomputed_hash(Hash::from_u64_word(1), vec![]);
+        header.hash_merkle_root =
+            calc_hash_merkle_root_with_options([&coinbase].into_iter(), false);
+        header.finalize();
+        Block::new(header, vec![coinbase])
+    }
+
+    #[test]
+    fn test_generation_is_seeded() {
+        let draw = |seed| {
+            let mut generator = Generator::new(seed, Prefix::Simnet, 0.5, 0.5, 1_000);
+            let steps = (0..32)
+                .map(|_| {
+                    (
+                        generator.next_timestamp(),
+                        generator.next_sibling_count(),
+                        generator.next_block_mutation(),
+                    )
+                })
+                .collect_vec();
+            (generator.address().clone(), steps)
+        };
+        assert_eq!(draw(7), draw(7));
+        assert_ne!(draw(7), draw(8));
+    }
+
+    #[test]
+    fn test_seeded_blocks() {
+        let mut generator = Generator::new(3, Prefix::Simnet, 0.0, 0.0, 1_000);
+        let timestamps = (0..32).map(|_| generator.next_timestamp()).collect_vec();
+        assert!(timestamps[0] > 1_000);
+        assert!(timestamps.windows(2).all(|pair| pair[0] < pair[1]));
+
+        let block = coinbase_block();
+        let seeded = seeded_block(&block, timestamps[0]);
+        assert_eq!(seeded.header.timestamp, timestamps[0]);
+        assert_ne!(seeded.hash(), block.hash());
+        assert_eq!(seeded.hash(), seeded_block(&block, timestamps[0]).hash());
+    }
+
+    #[test]
+    fn test_block_mutations() {
+        let block = coinbase_block();
+        let mut rng = StdRng::seed_from_u64(0);
+        assert_eq!(
+            BlockMutation::None.apply(&block, &mut rng).hash(),
+            block.hash()
+        );
+        for mutation in BlockMutation::INVALID {
+            let mutated = mutation.apply(&block, &mut rng);
+            assert_ne!(mutated.hash(), block.hash(), "{mutation}");
+        }
+
+        // Transaction mutations keep the merkle root consistent
+        let duplicated = BlockMutation::DuplicateTransaction.apply(&block, &mut rng);
+        assert_eq!(duplicated.transactions.len(), 2);
+        assert_eq!(
+            calc_hash_merkle_root_with_options(duplicated.transactions.iter(), false),
+            duplicated.header.hash_merkle_root
+        );
+    }
+
+    #[test]
+    fn test_transaction_generation() {
+        let mut generator = Generator::new(1, Prefix::Simnet, 0.0, 0.0, 0);
+        let entry = UtxoEntry::new(
+            1_000_000_000,
+            pay_to_address_script(generator.address()),
+            0,
+            true,
+        );
+        let mut utxos = (0..3u32)
+            .map(|i| {
+                (
+                    TransactionOutpoint::new(Hash::from_u64_word(i as u64 + 1), i),
+                    entry.clone(),
+                )
+            })
+            .collect_vec();
+        let (transaction, mutation) = generator.next_transaction(&mut utxos).unwrap();
+        assert_eq!(mutation, TransactionMutation::None);
+        assert_eq!(utxos.len() + transaction.inputs.len(), 3);
+        let total_out = transaction
+            .outputs
+            .iter()
+            .map(|output| output.value)
+            .sum::<u64>();
+        assert_eq!(
+            total_out + TRANSACTION_FEE,
+            entry.amount * transaction.inputs.len() as u64
+        );
+        assert!(transaction
+            .inputs
+            .iter()
+            .all(|input| !input.signature_script.is_empty()));
+    }
+}
@@ -0,0 +1,411 @@
+//!
+//! Differential consensus testing against a reference node, typically the golang node.
+//!
+//! Both nodes must run the same network from the same genesis, with the UTXO index enabled and without peers, so
+//! each of them only learns of the blocks submitted by the harness. Simnet is expected, its proof of work being
+//! skipped. Every step submits a few seeded transactions and a seeded block built from the template of the local
+//! node to both nodes, then compares their decisions and their resulting states. Some steps also submit sibling
+//! blocks sharing the parents of the block, which the next step merges. Block timestamps come from a clock seeded
+//! from the starting sink rather than from the wall clock. A share of the blocks and transactions break a single
+//! rule, so rejections are compared as well. A divergence is reported along with the seed and the step, and running
+//! the same seed against nodes reset to the same starting state replays it.
+//!
+
+mod diff;
+mod generator;
+
+use clap::{Arg, ArgAction, Command};
+use diff::{BlockState, Decision, NodeState};
+use generator::{seeded_block, BlockMutation, Generator};
+use karlsen_addresses::Prefix;
+use karlsen_consensus_core::{
+    block::Block,
+    config::params::Params,
+    tx::{TransactionOutpoint, UtxoEntry},
+};
+use karlsen_core::{info, karlsend_env::version, time::unix_now, warn};
+use karlsen_grpc_client::GrpcClient;
+use karlsen_hashes::Hash;
+use karlsen_notify::subscription::context::SubscriptionContext;
+use karlsen_rpc_core::{api::rpc::RpcApi, notify::mode::NotificationMode, RpcBlock, RpcResult};
+use std::{collections::HashSet, process::ExitCode, time::Duration};
+
+/// Interval between the polls of a node waiting for its virtual state to settle
+const SETTLE_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Maximum time given to a node for processing a submitted block
+const SETTLE_TIMEOUT: Duration = Duration::from_secs(10);
+
+pub struct Args {
+    pub local: String,
+    pub reference: String,
+    pub seed: u64,
+    pub steps: u64,
+    pub invalid_rate: f64,
+    pub sibling_rate: f64,
+    pub transactions_per_step: usize,
+    pub keep_going: bool,
+}
+
+impl Args {
+    fn parse() -> Self {
+        let m = cli().get_matches();
+        Args {
+            local: m.get_one::<String>("local").cloned().unwrap(),
+            reference: m.get_one::<String>("reference").cloned().unwrap(),
+            seed: m.get_one::<u64>("seed").cloned().unwrap_or_else(unix_now),
+            steps: m.get_one::<u64>("steps").cloned().unwrap(),
+            invalid_rate: m.get_one::<f64>("invalid-rate").cloned().unwrap(),
+            sibling_rate: m.get_one::<f64>("sibling-rate").cloned().unwrap(),
+            transactions_per_step: m.get_one::<usize>("transactions").cloned().unwrap(),
+            keep_going: m.get_flag("keep-going"),
+        }
+    }
+}
+
+pub fn cli() -> Command {
+    Command::new("karlsen-testing-differential")
+        .about(format!("{} v{}", env!("CARGO_PKG_DESCRIPTION"), version()))
+        .version(env!("CARGO_PKG_VERSION"))
+        .arg(
+            Arg::new("local")
+                .long("local")
+                .value_name("address")
+                .default_value("localhost:42310")
+                .help("gRPC server of the tested node"),
+        )
+        .arg(
+            Arg::new("reference")
+                .long("reference")
+                .value_name("address")
+                .required(true)
+                .help("gRPC server of the reference node, typically the golang node"),
+        )
+        .arg(
+            Arg::new("seed")
+                .long("seed")
+                .value_parser(clap::value_parser!(u64))
+                .help("Seed of the generated blocks and transactions (default: the current time)"),
+        )
+        .arg(
+            Arg::new("steps")
+                .long("steps")
+                .default_value("1000")
+                .value_parser(clap::value_parser!(u64))
+                .help("Number of steps, each submitting a block and its siblings"),
+        )
+        .arg(
+            Arg::new("invalid-rate")
+                .long("invalid-rate")
+                .default_value("0.2")
+                .value_parser(clap::value_parser!(f64))
+                .help("Share of the blocks and transactions breaking a rule"),
+        )
+        .arg(
+            Arg::new("sibling-rate")
+                .long("sibling-rate")
+                .default_value("0.2")
+                .value_parser(clap::value_parser!(f64))
+                .help("Share of the steps submitting sibling blocks, merged by the next step"),
+        )
+        .arg(
+            Arg::new("transactions")
+                .long("transactions")
+                .default_value("4")
+                .value_parser(clap::value_parser!(usize))
+                .help("Maximum number of transactions submitted before every block"),
+        )
+        .arg(
+            Arg::new("keep-going")
+                .long("keep-going")
+                .action(ArgAction::SetTrue)
+                .help("Keep running after a divergence"),
+        )
+}
+
+async fn connect(address: &str) -> GrpcClient {
+    GrpcClient::connect_with_args(
+        NotificationMode::Direct,
+        format!("grpc://{}", address),
+        Some(SubscriptionContext::new()),
+        true,
+        None,
+        false,
+        Some(500_000),
+        Default::default(),
+    )
+    .await
+    .unwrap_or_else(|err| panic!("cannot connect to {address}: {err}"))
+}
+
+struct Harness {
+    local: GrpcClient,
+    reference: GrpcClient,
+    generator: Generator,
+    coinbase_maturity: u64,
+    transactions_per_step: usize,
+    /// The common sink of the previous step, from which the chain changes of the next step are read
+    chain_start: Hash,
+    /// Outpoints spent by the transactions of the local mempool
+    spent_in_mempool: HashSet<TransactionOutpoint>,
+}
+
+impl Harness {
+    /// Runs a step, returning the divergences between the nodes
+    async fn step(&mut self) -> RpcResult<Vec<String>> {
+        let mut divergences = Vec::new();
+
+        let mut utxos = self.spendable_utxos().await?;
+        for _ in 0..self.transactions_per_step {
+            let Some((transaction, mutation)) = self.generator.next_transaction(&mut utxos) else {
+                break;
+            };
+            let local = Decision::new(
+                &self
+                    .local
+                    .submit_transaction((&transaction).into(), false)
+                    .await,
+            );
+            let reference = Decision::new(
+                &self
+                    .reference
+                    .submit_transaction((&transaction).into(), false)
+                    .await,
+            );
+            if local.accepted {
+                self.spent_in_mempool.extend(
+                    transaction
+                        .inputs
+                        .iter()
+                        .map(|input| input.previous_outpoint),
+                );
+            }
+            if local.accepted != reference.accepted {
+                divergences.push(format!(
+                    "transaction {} ({mutation} mutation): {} locally, {} on the reference node",
+                    transaction.id(),
+                    describe(&local),
+                    describe(&reference)
+                ));
+            }
+        }
+
+        // Siblings are built from templates requested before any submission, so they share the parents of the block
+        let timestamp = self.generator.next_timestamp();
+        let template = self.template(vec![], timestamp).await?;
+        let mut siblings = Vec::new();
+        for i in 1..=self.generator.next_sibling_count() as u64 {
+            let extra_data = self.generator.next_extra_data();
+            siblings.push(self.template(extra_data, timestamp + i).await?);
+        }
+        let mutation = self.generator.next_block_mutation();
+        let block = mutation.apply(&template, self.generator.rng());
+        let blocks = std::iter::once((block, mutation))
+            .chain(
+                siblings
+                    .into_iter()
+                    .map(|sibling| (sibling, BlockMutation::None)),
+            )
+            .collect::<Vec<_>>();
+        for (block, mutation) in blocks.iter() {
+            let hash = block.hash();
+            let local = submit_block(&self.local, block).await;
+            let reference = submit_block(&self.reference, block).await;
+            if local.accepted != reference.accepted {
+                divergences.push(format!(
+                    "block {hash} ({mutation} mutation): {} locally, {} on the reference node",
+                    describe(&local),
+                    describe(&reference)
+                ));
+            }
+            if local.accepted {
+                // The templates included the whole mempool
+                self.spent_in_mempool.clear();
+            }
+        }
+
+        settle(&self.local).await?;
+        settle(&self.reference).await?;
+        for (block, _) in blocks.iter() {
+            let hash = block.hash();
+            let local_block = BlockState::fetch(&self.local, hash).await?;
+            let reference_block = BlockState::fetch(&self.reference, hash).await?;
+            if local_block != reference_block {
+                divergences.push(format!(
+                    "block {hash}: {local_block:?} locally, {reference_block:?} on the reference node"
+                ));
+            }
+        }
+        let address = self.generator.address().clone();
+        let local_state = NodeState::fetch(&self.local, self.chain_start, &address).await?;
+        let reference_state = NodeState::fetch(&self.reference, self.chain_start, &address).await?;
+        divergences.extend(local_state.diff(&reference_state));
+        if local_state.sink == reference_state.sink {
+            self.chain_start = local_state.sink;
+        }
+        Ok(divergences)
+    }
+
+    /// Returns a block template of the local node paying to the generator address, made reproducible with the seeded `timestamp`
+    async fn template(&self, extra_data: Vec<u8>, timestamp: u64) -> RpcResult<Block> {
+        let template = self
+            .local
+            .get_block_template(self.generator.address().clone(), extra_data)
+            .await?
+            .block;
+        Ok(seeded_block(&Block::try_from(&template)?, timestamp))
+    }
+
+    /// Returns the mature UTXOs of the generator address not spent by the local mempool, as seen by the local node
+    async fn spendable_utxos(&self) -> RpcResult<Vec<(TransactionOutpoint, UtxoEntry)>> {
+        let virtual_daa_score = self.local.get_block_dag_info().await?.virtual_daa_score;
+        let mut utxos = self
+            .local
+            .get_utxos_by_addresses(vec![self.generator.address().clone()])
+            .await?
+            .into_iter()
+            .filter(|entry| {
+                !entry.utxo_entry.is_coinbase
+                    || entry.utxo_entry.block_daa_score + self.coinbase_maturity
+                        <= virtual_daa_score
+            })
+            .filter(|entry| !self.spent_in_mempool.contains(&entry.outpoint))
+            .map(|entry| (entry.outpoint, entry.utxo_entry))
+            .collect::<Vec<_>>();
+        // The UTXO index returns the entries in no particular order, while generation must be reproducible
+        utxos.sort_by_key(|(outpoint, _)| (outpoint.transaction_id, outpoint.index));
+        Ok(utxos)
+    }
+}
+
+async fn submit_block(client: &GrpcClient, block: &Block) -> Decision {
+    match client.submit_block(RpcBlock::from(block), false).await {
+        Ok(response) if response.report.is_success() => Decision::accepted(),
+        Ok(response) => Decision::rejected(format!("{:?}", response.report)),
+        Err(err) => Decision::rejected(err.to_string()),
+    }
+}
+
+/// Waits for the virtual state of the node to stop changing, the submitted block having been fully processed
+async fn settle(client: &GrpcClient) -> RpcResult<()> {
+    let started = std::time::Instant::now();
+    let mut previous = None;
+    loop {
+        let dag_info = client.get_block_dag_info().await?;
+        let current = Some((
+            dag_info.sink,
+            dag_info.virtual_daa_score,
+            dag_info.tip_hashes,
+        ));
+        if current == previous || started.elapsed() > SETTLE_TIMEOUT {
+            return Ok(());
+        }
+        previous = current;
+        tokio::time::sleep(SETTLE_INTERVAL).await;
+    }
+}
+
+fn describe(decision: &Decision) -> String {
+    match decision.accepted {
+        true => "accepted".to_string(),
+        false => format!("rejected ({})", decision.reason),
+    }
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    karlsen_core::log::init_logger(None, "INFO");
+    let args = Args::parse();
+
+    let local = connect(&args.local).await;
+    let reference = connect(&args.reference).await;
+    let local_info = local.get_block_dag_info().await.unwrap();
+    let reference_info = reference.get_block_dag_info().await.unwrap();
+    if local_info.network != reference_info.network {
+        warn!(
+            "The local node runs {} while the reference node runs {}",
+            local_info.network, reference_info.network
+        );
+        return ExitCode::FAILURE;
+    }
+    let params = Params::from(local_info.network);
+    // The seeded clock starts from the common starting sink, so that a replay submits the same timestamps
+    let start_time = match local.get_block(local_info.sink, false).await {
+        Ok(sink) => sink.header.timestamp,
+        Err(err) => {
+            warn!("Cannot read the sink of the local node: {err}");
+            return ExitCode::FAILURE;
+        }
+    };
+    let mut harness = Harness {
+        local,
+        reference,
+        generator: Generator::new(
+            args.seed,
+            Prefix::from(local_info.network),
+            args.invalid_rate,
+            args.sibling_rate,
+            start_time,
+        ),
+        coinbase_maturity: params.coinbase_maturity,
+        transactions_per_step: args.transactions_per_step,
+        chain_start: local_info.sink,
+        spent_in_mempool: HashSet::new(),
+    };
+    info!(
+        "Running {} steps on {} with seed {}, paying to {}",
+        args.steps,
+        local_info.network,
+        args.seed,
+        harness.generator.address()
+    );
+
+    // Both nodes must start from the same state
+    let address = harness.generator.address().clone();
+    let local_state = NodeState::fetch(&harness.local, local_info.sink, &address)
+        .await
+        .unwrap();
+    let reference_state = NodeState::fetch(&harness.reference, local_info.sink, &address).await;
+    match reference_state {
+        Ok(reference_state) if local_state.diff(&reference_state).is_empty() => {}
+        _ => {
+            warn!("The nodes do not start from the same state, both must be reset to the genesis");
+            return ExitCode::FAILURE;
+        }
+    }
+
+    let mut diverging_steps = 0;
+    for step in 1..=args.steps {
+        let divergences = match harness.step().await {
+            Ok(divergences) => divergences,
+            Err(err) => {
+                warn!("Step {step} failed: {err}");
+                return ExitCode::FAILURE;
+            }
+        };
+        if divergences.is_empty() {
+            if step % 100 == 0 {
+                info!("{step} steps without divergence");
+            }
+            continue;
+        }
+        diverging_steps += 1;
+        warn!("Divergence at step {step} of seed {}:", args.seed);
+        for divergence in divergences {
+            warn!("    {divergence}");
+        }
+        if !args.keep_going {
+            return ExitCode::FAILURE;
+        }
+    }
+    match diverging_steps {
+        0 => {
+            info!("Completed {} steps without divergence", args.steps);
+            ExitCode::SUCCESS
+        }
+        n => {
+            warn!("{n} of {} steps diverged", args.steps);
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -61,6 +61,7 @@ use karlsen_index_processor::service::IndexService;
 use karlsen_math::Uint256;
 use karlsen_muhash::MuHash;
 use karlsen_notify::subscription::context::SubscriptionContext;
+use karlsen_spv::{HeaderSync, SpvError};
 use karlsen_txscript::caches::TxScriptCacheCounters;
 use karlsen_utxoindex::api::{UtxoIndexApi, UtxoIndexProxy};
 use karlsen_utxoindex::UtxoIndex;
@@ -1361,6 +1362,9 @@ async fn json_test(file_path: &str, concurrency: bool) {
     // Assert that the indexed selected chain store matches the virtual chain obtained
     // through the reachability iterator
     assert_selected_chain_store_matches_virtual_chain(&tc);
+    if !proof_exists && tc.pruning_point() != config.genesis.hash {
+        assert_spv_and_consensus_proof_validations_agree(&tc, &config);
+    }
     let virtual_utxos: HashSet<TransactionOutpoint> = HashSet::from_iter(
         tc.get_virtual_utxos(None, usize::MAX, false)
             .into_iter()
@@ -1372,6 +1376,33 @@ async fn json_test(file_path: &str, concurrency: bool) {
     assert!(utxoindex_utxos.is_subset(&virtual_utxos));
 }
 
+/// Validates the pruning point proof built by `tc` with both a fresh consensus and the header-only
+/// (SPV) validation, and checks that both reject the same tampered proofs with the same errors
+fn assert_spv_and_consensus_proof_validations_agree(tc: &TestConsensus, config: &Config) {
+    let proof = (*tc.get_pruning_point_proof()).clone();
+    let fresh = TestConsensus::new(config);
+    fresh.validate_pruning_proof(&proof).unwrap();
+    let sync = HeaderSync::new(config.params.clone());
+    assert_eq!(
+        sync.apply_pruning_point_proof(&proof).unwrap(),
+        tc.pruning_point()
+    );
+
+    let mut missing_level = proof.clone();
+    missing_level.pop();
+    let mut duplicate_header = proof.clone();
+    duplicate_header[0].push(proof[0].last().unwrap().clone());
+    for tampered in [missing_level, duplicate_header] {
+        let consensus_err = fresh.validate_pruning_proof(&tampered).unwrap_err();
+        match HeaderSync::new(config.params.clone()).apply_pruning_point_proof(&tampered) {
+            Err(SpvError::Proof(spv_err)) => {
+                assert_eq!(spv_err.to_string(), consensus_err.to_string())
+            }
+            res => panic!("Unexpected result: {res:?}"),
+        }
+    }
+}
+
 fn submit_header_chunk(
     tc: &TestConsensus,
     external_block_store: &DbBlockTransactionsStore,
@@ -2250,6 +2281,7 @@ async fn staging_consensus_test() {
         meta_db,
         &config,
         consensus_db_dir,
+        None,
         4,
         notification_root,
         counters,
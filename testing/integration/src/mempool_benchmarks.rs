@@ -20,7 +20,7 @@ use karlsen_notify::{
     listener::ListenerId,
     scope::{NewBlockTemplateScope, Scope},
 };
-use karlsen_rpc_core::{api::rpc::RpcApi, Notification, RpcError};
+use karlsen_rpc_core::{api::rpc::RpcApi, error_code::codes, Notification};
 use karlsen_txscript::pay_to_address_script;
 use karlsen_utils::fd_budget;
 use karlsend_lib::args::Args;
@@ -173,7 +173,11 @@ async fn bench_bbt_latency() {
     let submit_tx_pool_tasks = submit_tx_pool.start(|c, (i, tx)| async move {
         match c.submit_transaction(tx.as_ref().into(), false).await {
             Ok(_) => {}
-            Err(RpcError::General(msg)) if msg.contains("orphan") => {
+            Err(err)
+                if err.code() == codes::REJECTED_TRANSACTION
+                    && err.to_string().contains("orphan") =>
+            {
+                let msg = err.to_string();
                 karlsen_core::warn!("\n\n\n{msg}\n\n");
                 karlsen_core::warn!("Submitted {} transactions, exiting tx submit loop", i);
                 return true;
@@ -4,23 +4,39 @@ use crate::common::{client_notify::ChannelNotify, daemon::Daemon};
 use futures_util::future::try_join_all;
 use karlsen_addresses::{Address, Prefix, Version};
 use karlsen_consensus::params::SIMNET_GENESIS;
-use karlsen_consensus_core::{constants::MAX_SOMPI, subnets::SubnetworkId, tx::Transaction};
+use karlsen_consensus_core::{
+    constants::MAX_SOMPI,
+    subnets::SubnetworkId,
+    tx::{ScriptPublicKey, Transaction, TransactionInput, TransactionOutpoint, UtxoEntry},
+};
 use karlsen_core::info;
 use karlsen_grpc_core::ops::KarlsendPayloadOps;
 use karlsen_hashes::Hash;
 use karlsen_notify::{
     connection::{ChannelConnection, ChannelType},
     scope::{
-        BlockAddedScope, FinalityConflictScope, NewBlockTemplateScope,
-        PruningPointUtxoSetOverrideScope, Scope, SinkBlueScoreChangedScope, UtxosChangedScope,
-        VirtualChainChangedScope, VirtualDaaScoreChangedScope,
+        BlockAddedScope, DoubleSpendDetectedScope, FinalityConflictScope, NewBlockTemplateScope,
+        PruningPointUtxoSetOverrideScope, Scope, SinkBlueScoreChangedScope,
+        TransactionStatusChangedScope, UtxosChangedScope, VirtualChainChangedScope,
+        VirtualDaaScoreChangedScope,
+    },
+};
+use karlsen_rpc_core::{
+    api::{
+        ops::{PROTOWIRE_API_VERSION, RPC_API_VERSION},
+        rpc::RpcApi,
     },
+    error_code::codes,
+    model::*,
+    Notification, RpcErrorCategory,
 };
-use karlsen_rpc_core::{api::rpc::RpcApi, model::*, Notification};
+use karlsen_txscript::{opcodes::codes::OpTrue, pay_to_address_script};
 use karlsen_utils::{fd_budget, networking::ContextualNetAddress};
 use karlsend_lib::args::Args;
 use tokio::task::JoinHandle;
 
+const WORK_BINDING_SECRET: &str = "rpc-tests-work-binding";
+
 #[macro_export]
 macro_rules! tst {
     ($op:ident, $test_body:block) => {
@@ -50,7 +66,10 @@ async fn sanity_test() {
         enable_unsynced_mining: true,
         block_template_cache_lifetime: Some(0),
         utxoindex: true,
+        blockfilterindex: true,
         unsafe_rpc: true,
+        acceptance_journal_size: 100,
+        work_binding_secret: Some(WORK_BINDING_SECRET.to_string()),
         ..Default::default()
     };
 
@@ -112,14 +131,43 @@ async fn sanity_test() {
                     assert!(response.removed_chain_block_hashes.is_empty());
 
                     // Get a block template
-                    let GetBlockTemplateResponse { block, is_synced } = rpc_client
+                    let pay_address = Address::new(Prefix::Simnet, Version::PubKey, &[0u8; 32]);
+                    let GetBlockTemplateResponse {
+                        block,
+                        is_synced,
+                        nonce_start,
+                        nonce_stride,
+                    } = rpc_client
                         .get_block_template_call(GetBlockTemplateRequest {
-                            pay_address: Address::new(Prefix::Simnet, Version::PubKey, &[0u8; 32]),
+                            pay_address: pay_address.clone(),
                             extra_data: Vec::new(),
+                            worker_id: Some(3),
+                            reported_hashrate: Some(1_000_000.0),
+                            bind_work: true,
                         })
                         .await
                         .unwrap();
                     assert!(!is_synced);
+                    assert_eq!(nonce_start, 3);
+                    assert_eq!(nonce_stride, GetBlockTemplateRequest::NONCE_PARTITION_COUNT);
+                    let binding = RpcWorkBinding::verify_job(
+                        &block.transactions[0].payload,
+                        &pay_to_address_script(&pay_address),
+                        Some(3),
+                        WORK_BINDING_SECRET.as_bytes(),
+                    )
+                    .unwrap();
+                    assert_eq!(binding.worker_id, Some(3));
+
+                    // The block is rejected once its binding is stripped
+                    let mut stripped = block.clone();
+                    let payload = &mut stripped.transactions[0].payload;
+                    payload.truncate(payload.len() - RpcWorkBinding::ENCODED_LEN);
+                    let response = rpc_client.submit_block(stripped, false).await.unwrap();
+                    assert_eq!(
+                        response.report,
+                        SubmitBlockReport::Reject(SubmitBlockRejectReason::BlockInvalid)
+                    );
 
                     // Submit the template (no mining, in simnet PoW is skipped)
                     let response = rpc_client.submit_block(block.clone(), false).await.unwrap();
@@ -198,6 +246,7 @@ async fn sanity_test() {
                         .get_block_call(GetBlockRequest {
                             hash: 0.into(),
                             include_transactions: false,
+                            resolve_previous_outputs: false,
                         })
                         .await;
                     assert!(result.is_err());
@@ -206,10 +255,20 @@ async fn sanity_test() {
                         .get_block_call(GetBlockRequest {
                             hash: SIMNET_GENESIS.hash,
                             include_transactions: false,
+                            resolve_previous_outputs: false,
                         })
                         .await
                         .unwrap();
                     assert_eq!(response.block.header.hash, SIMNET_GENESIS.hash);
+
+                    let response = rpc_client
+                        .get_block_call(
+                            GetBlockRequest::new(SIMNET_GENESIS.hash, true).with_previous_outputs(),
+                        )
+                        .await
+                        .unwrap();
+                    // The coinbase transaction spends no output
+                    assert!(response.block.transactions[0].inputs.is_empty());
                 })
             }
 
@@ -221,12 +280,42 @@ async fn sanity_test() {
                             include_blocks: true,
                             include_transactions: false,
                             low_hash: None,
+                            verbosity: None,
+                            batch_size: 0,
+                            resolve_previous_outputs: false,
                         })
                         .await
                         .unwrap();
                     assert_eq!(response.blocks.len(), 1, "genesis block should be returned");
                     assert_eq!(response.blocks[0].header.hash, SIMNET_GENESIS.hash);
                     assert_eq!(response.block_hashes[0], SIMNET_GENESIS.hash);
+                    assert!(response.next_low_hash.is_none());
+
+                    // Header-only verbosity drops the transaction ids
+                    let response = rpc_client
+                        .get_blocks_call(
+                            GetBlocksRequest::new(None, false, false)
+                                .with_verbosity(RpcBlockVerbosity::Low)
+                                .with_batch_size(10),
+                        )
+                        .await
+                        .unwrap();
+                    assert_eq!(response.blocks.len(), 1);
+                    assert!(response.blocks[0]
+                        .verbose_data
+                        .as_ref()
+                        .unwrap()
+                        .transaction_ids
+                        .is_empty());
+
+                    // A verbosity without blocks is rejected
+                    assert!(rpc_client
+                        .get_blocks_call(GetBlocksRequest {
+                            verbosity: Some(RpcBlockVerbosity::High),
+                            ..GetBlocksRequest::new(None, false, false)
+                        })
+                        .await
+                        .is_err());
                 })
             }
 
@@ -267,6 +356,7 @@ async fn sanity_test() {
                             transaction_id: 0.into(),
                             include_orphan_pool: true,
                             filter_transaction_pool: false,
+                            resolve_previous_outputs: false,
                         })
                         .await;
                     // Test Get Mempool Entry:
@@ -279,13 +369,29 @@ async fn sanity_test() {
                 let rpc_client = client.clone();
                 tst!(op, {
                     let response = rpc_client
-                        .get_mempool_entries_call(GetMempoolEntriesRequest {
-                            include_orphan_pool: true,
-                            filter_transaction_pool: false,
-                        })
+                        .get_mempool_entries_call(GetMempoolEntriesRequest::new(true, false))
                         .await
                         .unwrap();
                     assert!(response.mempool_entries.is_empty());
+                    assert!(response.next_cursor.is_none());
+
+                    let request = GetMempoolEntriesRequest::new(true, false).with_pagination();
+                    let response = rpc_client
+                        .get_mempool_entries_call(request.clone())
+                        .await
+                        .unwrap();
+                    assert!(request.next_page(&response).is_none());
+
+                    // A cursor must be a transaction id
+                    let request = GetMempoolEntriesRequest {
+                        cursor: Some("not-a-cursor".to_string()),
+                        ..request
+                    };
+                    let err = rpc_client
+                        .get_mempool_entries_call(request)
+                        .await
+                        .unwrap_err();
+                    assert_eq!(err.code(), codes::INVALID_CURSOR);
                 })
             }
 
@@ -438,11 +544,21 @@ async fn sanity_test() {
                 let rpc_client = client.clone();
                 tst!(op, {
                     let addresses = vec![Address::new(Prefix::Simnet, Version::PubKey, &[0u8; 32])];
+                    let request = GetUtxosByAddressesRequest::new(addresses);
                     let response = rpc_client
-                        .get_utxos_by_addresses_call(GetUtxosByAddressesRequest { addresses })
+                        .get_utxos_by_addresses_call(request.clone())
                         .await
                         .unwrap();
                     assert!(response.entries.is_empty());
+                    assert!(response.next_cursor.is_none());
+
+                    let request = request.with_pagination();
+                    let response = rpc_client
+                        .get_utxos_by_addresses_call(request.clone())
+                        .await
+                        .unwrap();
+                    assert!(response.entries.is_empty());
+                    assert!(request.next_page(&response).is_none());
                 })
             }
 
@@ -481,6 +597,21 @@ async fn sanity_test() {
                 })
             }
 
+            KarlsendPayloadOps::GetBalanceByAddressesAt => {
+                let rpc_client = client.clone();
+                tst!(op, {
+                    let addresses = vec![Address::new(Prefix::Simnet, Version::PubKey, &[1u8; 32])];
+                    let response = rpc_client
+                        .get_balance_by_addresses_at(addresses.clone(), 0)
+                        .await
+                        .unwrap();
+                    assert_eq!(response.entries.len(), 1);
+                    assert_eq!(response.entries[0].address, addresses[0]);
+                    assert_eq!(response.entries[0].balance, Some(0));
+                    assert_eq!(response.daa_score, 0);
+                })
+            }
+
             KarlsendPayloadOps::GetSinkBlueScore => {
                 let rpc_client = client.clone();
                 tst!(op, {
@@ -624,6 +755,16 @@ async fn sanity_test() {
                 })
             }
 
+            KarlsendPayloadOps::GetMinerStats => {
+                let rpc_client = client.clone();
+                tst!(op, {
+                    // No block was submitted to the node
+                    let response = rpc_client.get_miner_stats().await.unwrap();
+                    assert_eq!(response.accepted_blocks, 0);
+                    assert_eq!(response.expected_seconds_to_block, None);
+                })
+            }
+
             KarlsendPayloadOps::GetDaaScoreTimestampEstimate => {
                 let rpc_client = client.clone();
                 tst!(op, {
@@ -653,6 +794,311 @@ async fn sanity_test() {
                 })
             }
 
+            KarlsendPayloadOps::GetServerCapabilities => {
+                let rpc_client = client.clone();
+                tst!(op, {
+                    let response = rpc_client
+                        .get_server_capabilities_call(GetServerCapabilitiesRequest {})
+                        .await
+                        .unwrap();
+                    assert_eq!(response.rpc_api_version, RPC_API_VERSION);
+                    assert_eq!(response.protowire_api_version, PROTOWIRE_API_VERSION);
+                    assert!(response.supports_method("GetServerCapabilities"));
+                    assert!(response.supports_method("SubmitBlock"));
+                    assert!(!response.supports_method("NotifyBlockAdded"));
+                    assert!(!response.supports_method("BlockAddedNotification"));
+                    assert!(response.supports_notification("BlockAdded"));
+                    assert!(response.supports_notification("UtxosChanged"));
+                })
+            }
+
+            KarlsendPayloadOps::GetNetworkInfo => {
+                let rpc_client = client.clone();
+                tst!(op, {
+                    let response = rpc_client
+                        .get_network_info_call(GetNetworkInfoRequest {})
+                        .await
+                        .unwrap();
+                    assert_eq!(response.network_id, network_id);
+                    assert_eq!(response.address_prefix, Prefix::Simnet.to_string());
+
+                    // Addresses of another network must be rejected
+                    let foreign_address =
+                        Address::new(Prefix::Mainnet, Version::PubKey, &[0u8; 32]);
+                    assert!(rpc_client
+                        .get_balance_by_address_call(GetBalanceByAddressRequest {
+                            address: foreign_address.clone()
+                        })
+                        .await
+                        .is_err());
+                    assert!(rpc_client
+                        .get_mempool_entries_by_addresses_call(
+                            GetMempoolEntriesByAddressesRequest::new(
+                                vec![foreign_address],
+                                false,
+                                false
+                            )
+                        )
+                        .await
+                        .is_err());
+                })
+            }
+
+            KarlsendPayloadOps::TestMempoolAccept => {
+                let rpc_client = client.clone();
+                tst!(op, {
+                    // An erroneous transaction...
+                    let transaction =
+                        Transaction::new(0, vec![], vec![], 0, SubnetworkId::default(), 0, vec![]);
+                    let response = rpc_client
+                        .test_mempool_accept((&transaction).into())
+                        .await
+                        .unwrap();
+                    // ...gets rejected with a reason but no call error
+                    assert!(!response.accepted);
+                    assert!(response.reject_reason.is_some());
+                    assert_eq!(response.transaction_id, transaction.id());
+                    assert_eq!(response.feerate, 0.0);
+                })
+            }
+
+            KarlsendPayloadOps::DebugScript => {
+                let rpc_client = client.clone();
+                tst!(op, {
+                    // An empty signature script spending an anyone-can-spend output
+                    let input = TransactionInput::new(
+                        TransactionOutpoint::new(Hash::from_u64_word(1), 0),
+                        vec![],
+                        0,
+                        0,
+                    );
+                    let transaction = Transaction::new(
+                        0,
+                        vec![input],
+                        vec![],
+                        0,
+                        SubnetworkId::default(),
+                        0,
+                        vec![],
+                    );
+                    let entry =
+                        UtxoEntry::new(1_000, ScriptPublicKey::from_vec(0, vec![OpTrue]), 0, false);
+                    let response = rpc_client
+                        .debug_script((&transaction).into(), 0, vec![entry])
+                        .await
+                        .unwrap();
+                    assert!(response.success);
+                    assert_eq!(response.steps.len(), 1);
+                    assert_eq!(response.steps[0].opcode_name, "OpTrue");
+                    assert_eq!(response.steps[0].stack, vec!["01".to_string()]);
+
+                    // Every input must come with its UTXO entry
+                    let result = rpc_client
+                        .debug_script((&transaction).into(), 0, vec![])
+                        .await;
+                    // The error code survives the transport
+                    let err = result.unwrap_err();
+                    assert_eq!(err.code(), codes::UTXO_ENTRIES_COUNT_MISMATCH);
+                    assert_eq!(err.category(), RpcErrorCategory::InvalidRequest);
+                })
+            }
+
+            KarlsendPayloadOps::GetDifficultyInfo => {
+                let rpc_client = client.clone();
+                tst!(op, {
+                    // A fresh simnet node may not have filled its DAA window yet
+                    if let Ok(response) = rpc_client.get_difficulty_info(10).await {
+                        assert!(!response.chain_blocks.is_empty());
+                        assert!(response.chain_blocks.len() <= 10);
+                        assert!(response.next_difficulty > 0.0);
+                    }
+                })
+            }
+
+            KarlsendPayloadOps::GetDagSlice => {
+                let rpc_client = client.clone();
+                tst!(op, {
+                    let response = rpc_client.get_dag_slice(0, 100).await.unwrap();
+                    // The DAG holds at least the genesis, which is a chain block
+                    assert!(!response.blocks.is_empty());
+                    assert!(response.blocks.iter().any(|block| block.is_chain_block));
+                    assert!(response
+                        .blocks
+                        .windows(2)
+                        .all(|pair| pair[0].daa_score <= pair[1].daa_score));
+                    assert_eq!(
+                        response.next_daa_score,
+                        response.blocks.last().unwrap().daa_score + 1
+                    );
+                })
+            }
+
+            KarlsendPayloadOps::GetAcceptanceEventsSince => {
+                let rpc_client = client.clone();
+                tst!(op, {
+                    let response = rpc_client.get_acceptance_events_since(0, 10).await.unwrap();
+                    assert!(!response.gap);
+                    assert!(response.events.len() <= 10);
+                    assert!(response
+                        .events
+                        .windows(2)
+                        .all(|pair| pair[0].sequence + 1 == pair[1].sequence));
+                })
+            }
+
+            KarlsendPayloadOps::GetTemplateDiff => {
+                let rpc_client = client.clone();
+                tst!(op, {
+                    let pay_address = Address::new(Prefix::Simnet, Version::PubKey, &[0u8; 32]);
+                    let first = rpc_client
+                        .get_template_diff(pay_address.clone(), Vec::new(), 0)
+                        .await
+                        .unwrap();
+                    assert_eq!(first.base_template_id, 0);
+                    assert_eq!(first.added_transactions.len(), first.transaction_ids.len());
+                    assert!(first.removed_transaction_ids.is_empty());
+                    RpcWorkBinding::verify_job(
+                        &first.coinbase_transaction.payload,
+                        &pay_to_address_script(&pay_address),
+                        None,
+                        WORK_BINDING_SECRET.as_bytes(),
+                    )
+                    .unwrap();
+
+                    // Applying the diff to the first template yields the second one
+                    let second = rpc_client
+                        .get_template_diff(pay_address, Vec::new(), first.template_id)
+                        .await
+                        .unwrap();
+                    assert!(second.template_id > first.template_id);
+                    assert_eq!(second.base_template_id, first.template_id);
+                    assert_eq!(
+                        second.transaction_ids.len() + second.removed_transaction_ids.len(),
+                        first.transaction_ids.len() + second.added_transactions.len()
+                    );
+                })
+            }
+
+            KarlsendPayloadOps::GetMetadata => {
+                let rpc_client = client.clone();
+                tst!(op, {
+                    let entries = rpc_client
+                        .get_metadata("rpc-tests".to_string(), vec!["absent".to_string()])
+                        .await
+                        .unwrap();
+                    assert!(entries.is_empty());
+                })
+            }
+
+            KarlsendPayloadOps::UpdateMetadata => {
+                let rpc_client = client.clone();
+                tst!(op, {
+                    let namespace = "rpc-tests-update".to_string();
+                    let entry = RpcMetadataEntry::new("cursor".to_string(), vec![1, 2, 3]);
+                    rpc_client
+                        .update_metadata(
+                            namespace.clone(),
+                            vec![],
+                            vec![entry.key.clone()],
+                            vec![entry.clone()],
+                            vec![],
+                        )
+                        .await
+                        .unwrap();
+                    let entries = rpc_client
+                        .get_metadata(namespace.clone(), vec![entry.key.clone()])
+                        .await
+                        .unwrap();
+                    assert_eq!(entries, vec![entry.clone()]);
+
+                    // The key is no longer absent, so the same update is rejected
+                    let result = rpc_client
+                        .update_metadata(
+                            namespace,
+                            vec![],
+                            vec![entry.key.clone()],
+                            vec![entry],
+                            vec![],
+                        )
+                        .await;
+                    assert!(result.is_err());
+                })
+            }
+
+            KarlsendPayloadOps::GetTransactionStatus => {
+                let rpc_client = client.clone();
+                tst!(op, {
+                    // A transaction never submitted to the node is neither in the mempool nor tracked
+                    let transaction_id = Hash::from_u64_word(1);
+                    let response = rpc_client
+                        .get_transaction_status(transaction_id)
+                        .await
+                        .unwrap();
+                    assert_eq!(response.transaction_id, transaction_id);
+                    assert!(!response.in_mempool);
+                    assert!(!response.is_orphan);
+                    assert!(!response.is_tracked);
+                    assert_eq!(response.announced_peers, 0);
+                    assert!(response.accepting_block_hash.is_none());
+                    assert_eq!(response.confirmations, 0);
+                })
+            }
+            KarlsendPayloadOps::GetUnconfirmedTxRisk => {
+                let rpc_client = client.clone();
+                tst!(op, {
+                    // An unknown transaction has no risk indicators
+                    let transaction_id = Hash::from_u64_word(1);
+                    let response = rpc_client
+                        .get_unconfirmed_tx_risk(transaction_id)
+                        .await
+                        .unwrap();
+                    assert_eq!(response.transaction_id, transaction_id);
+                    assert!(!response.in_mempool);
+                    assert!(!response.is_orphan);
+                    assert!(!response.is_accepted);
+                    assert_eq!(response.fee_rate, 0.0);
+                    assert!(response.conflicting_transaction_ids.is_empty());
+                    assert!(response.input_ages.is_empty());
+                    assert_eq!(response.unconfirmed_input_count, 0);
+                    assert_eq!(response.announcing_peer_count, 0);
+                })
+            }
+
+            KarlsendPayloadOps::GetBlockFilterHeaders => {
+                let rpc_client = client.clone();
+                tst!(op, {
+                    // The index has no filter for a block outside of the chain
+                    let result = rpc_client
+                        .get_block_filter_headers(Hash::from_u64_word(1), 10)
+                        .await;
+                    assert_eq!(result.unwrap_err().code(), codes::BLOCK_NOT_IN_FILTER_INDEX);
+                })
+            }
+
+            KarlsendPayloadOps::GetBlockFilters => {
+                let rpc_client = client.clone();
+                tst!(op, {
+                    let result = rpc_client
+                        .get_block_filters(Hash::from_u64_word(1), 10)
+                        .await;
+                    assert_eq!(result.unwrap_err().code(), codes::BLOCK_NOT_IN_FILTER_INDEX);
+                })
+            }
+
+            KarlsendPayloadOps::GetAddressCluster => {
+                let rpc_client = client.clone();
+                tst!(op, {
+                    // The test node is built without the analytics feature
+                    let result = rpc_client
+                        .get_address_cluster(
+                            Address::new(Prefix::Simnet, Version::PubKey, &[0u8; 32]),
+                            10,
+                        )
+                        .await;
+                    assert_eq!(result.unwrap_err().code(), codes::NO_ADDRESS_CLUSTER_INDEX);
+                })
+            }
+
             KarlsendPayloadOps::NotifyBlockAdded => {
                 let rpc_client = client.clone();
                 let id = listener_id;
@@ -741,6 +1187,26 @@ async fn sanity_test() {
                         .unwrap();
                 })
             }
+            KarlsendPayloadOps::NotifyTransactionStatusChanged => {
+                let rpc_client = client.clone();
+                let id = listener_id;
+                tst!(op, {
+                    rpc_client
+                        .start_notify(id, TransactionStatusChangedScope {}.into())
+                        .await
+                        .unwrap();
+                })
+            }
+            KarlsendPayloadOps::NotifyDoubleSpendDetected => {
+                let rpc_client = client.clone();
+                let id = listener_id;
+                tst!(op, {
+                    rpc_client
+                        .start_notify(id, DoubleSpendDetectedScope {}.into())
+                        .await
+                        .unwrap();
+                })
+            }
             KarlsendPayloadOps::StopNotifyingUtxosChanged => {
                 let rpc_client = client.clone();
                 let id = listener_id;
@@ -115,9 +115,12 @@ impl Task for BlockMinerTask {
                 }
 
                 // Read the most up-to-date block template
-                let mut block = template.lock().block.clone();
-                // Use index as nonce to avoid duplicate blocks
-                block.header.nonce = i as u64;
+                let (mut block, nonce) = {
+                    let template = template.lock();
+                    (template.block.clone(), template.nonce_at(i as u64))
+                };
+                // Use the index-th nonce of the recommended range to avoid duplicate blocks
+                block.header.nonce = nonce;
 
                 let c_template = template.clone();
                 let c_client = client.clone();
@@ -7,7 +7,7 @@ use async_trait::async_trait;
 use karlsen_consensus_core::tx::Transaction;
 use karlsen_core::{error, warn};
 use karlsen_grpc_client::ClientPool;
-use karlsen_rpc_core::{api::rpc::RpcApi, RpcError};
+use karlsen_rpc_core::{api::rpc::RpcApi, error_code::codes};
 use karlsen_utils::triggers::SingleTrigger;
 use std::{sync::Arc, time::Duration};
 use tokio::{task::JoinHandle, time::sleep};
@@ -63,7 +63,11 @@ impl Task for TransactionSubmitterTask {
                         Ok(_) => {
                             return false;
                         }
-                        Err(RpcError::General(msg)) if msg.contains("orphan") => {
+                        Err(err)
+                            if err.code() == codes::REJECTED_TRANSACTION
+                                && err.to_string().contains("orphan") =>
+                        {
+                            let msg = err.to_string();
                             error!("Transaction {i}: submit attempt #{attempt} failed");
                             error!("\n\n\n{msg}\n\n");
                             sleep(Duration::from_millis(50)).await;
@@ -5,6 +5,7 @@ pub mod channel;
 pub mod hashmap;
 pub mod hex;
 pub mod iter;
+pub mod mem_budget;
 pub mod mem_size;
 pub mod networking;
 pub mod option;
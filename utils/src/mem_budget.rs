@@ -0,0 +1,232 @@
+//!
+//! Process wide memory budget.
+//!
+//! The unbounded-by-nature memory consumers of the node (caches, pools, buffers) register
+//! here along with an optional cap. The budget is disabled until a memory limit is set. Once
+//! enabled, it is periodically enforced against the resident set size of the process:
+//! consumers above their cap are always shrunk, and when the process exceeds the memory limit,
+//! every consumer gives back a share of the excess proportional to its current usage.
+//!
+
+use parking_lot::Mutex;
+use std::sync::{
+    atomic::{AtomicU64, AtomicUsize, Ordering},
+    Arc, OnceLock, Weak,
+};
+
+/// A memory consumer which can be shrunk on demand
+pub trait MemoryConsumer: Send + Sync {
+    /// Estimated memory usage, in bytes
+    fn memory_usage(&self) -> usize;
+
+    /// Releases memory until the usage is no more than `target` bytes, on a best effort basis
+    fn shrink_to(&self, target: usize);
+}
+
+/// Memory usage of a registered consumer
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MemoryUsage {
+    pub name: &'static str,
+    pub usage: usize,
+    pub cap: Option<usize>,
+}
+
+/// Pressure statistics of the budget
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct MemoryBudgetStats {
+    /// Memory limit of the process in bytes, 0 if the budget is disabled
+    pub limit: usize,
+    /// Estimated usage of the registered consumers, in bytes
+    pub usage: usize,
+    /// Count of enforcements which found the process above the limit
+    pub pressure_events: u64,
+    /// Estimated count of bytes released by the consumers since startup
+    pub released_bytes: u64,
+}
+
+struct Registration {
+    name: &'static str,
+    cap: Option<usize>,
+    consumer: Weak<dyn MemoryConsumer>,
+}
+
+#[derive(Default)]
+pub struct MemoryBudget {
+    /// Memory limit of the process in bytes, 0 meaning the budget is disabled
+    limit: AtomicUsize,
+    registrations: Mutex<Vec<Registration>>,
+    pressure_events: AtomicU64,
+    released_bytes: AtomicU64,
+}
+
+impl MemoryBudget {
+    pub fn new(limit: usize) -> Self {
+        Self {
+            limit: AtomicUsize::new(limit),
+            ..Default::default()
+        }
+    }
+
+    pub fn limit(&self) -> usize {
+        self.limit.load(Ordering::Relaxed)
+    }
+
+    pub fn set_limit(&self, limit: usize) {
+        self.limit.store(limit, Ordering::Relaxed);
+    }
+
+    /// Registers a consumer. The budget only keeps a weak reference, so the consumer is
+    /// unregistered when dropped.
+    pub fn register(
+        &self,
+        name: &'static str,
+        cap: Option<usize>,
+        consumer: Arc<dyn MemoryConsumer>,
+    ) {
+        self.registrations.lock().push(Registration {
+            name,
+            cap,
+            consumer: Arc::downgrade(&consumer),
+        });
+    }
+
+    /// Returns the current usage of the registered consumers
+    pub fn snapshot(&self) -> Vec<MemoryUsage> {
+        self.consumers()
+            .into_iter()
+            .map(|(name, cap, consumer)| MemoryUsage {
+                name,
+                usage: consumer.memory_usage(),
+                cap,
+            })
+            .collect()
+    }
+
+    pub fn stats(&self) -> MemoryBudgetStats {
+        MemoryBudgetStats {
+            limit: self.limit(),
+            usage: self.snapshot().iter().map(|x| x.usage).sum(),
+            pressure_events: self.pressure_events.load(Ordering::Relaxed),
+            released_bytes: self.released_bytes.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Shrinks the consumers according to their caps and to the resident set size of the
+    /// process. Returns the estimated count of bytes released, always 0 if the budget is
+    /// disabled.
+    pub fn enforce(&self, resident_set_size: usize) -> usize {
+        let limit = self.limit();
+        if limit == 0 {
+            return 0;
+        }
+        let consumers = self
+            .consumers()
+            .into_iter()
+            .map(|(_, cap, consumer)| (cap, consumer.memory_usage(), consumer))
+            .collect::<Vec<_>>();
+        let excess = resident_set_size.saturating_sub(limit);
+        if excess > 0 {
+            self.pressure_events.fetch_add(1, Ordering::Relaxed);
+        }
+        let total_usage = consumers.iter().map(|(_, usage, _)| *usage).sum::<usize>();
+
+        let mut released = 0;
+        for (cap, usage, consumer) in consumers {
+            let share = if total_usage > 0 {
+                (excess as u128 * usage as u128 / total_usage as u128) as usize
+            } else {
+                0
+            };
+            let target = usage.saturating_sub(share).min(cap.unwrap_or(usize::MAX));
+            if target < usage {
+                consumer.shrink_to(target);
+                released += usage.saturating_sub(consumer.memory_usage());
+            }
+        }
+        self.released_bytes
+            .fetch_add(released as u64, Ordering::Relaxed);
+        released
+    }
+
+    fn consumers(&self) -> Vec<(&'static str, Option<usize>, Arc<dyn MemoryConsumer>)> {
+        let mut registrations = self.registrations.lock();
+        registrations.retain(|x| x.consumer.strong_count() > 0);
+        registrations
+            .iter()
+            .filter_map(|x| {
+                x.consumer
+                    .upgrade()
+                    .map(|consumer| (x.name, x.cap, consumer))
+            })
+            .collect()
+    }
+}
+
+static MEMORY_BUDGET: OnceLock<MemoryBudget> = OnceLock::new();
+
+/// The memory budget of the process, disabled until a limit is set
+pub fn memory_budget() -> &'static MemoryBudget {
+    MEMORY_BUDGET.get_or_init(MemoryBudget::default)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Consumer(Mutex<usize>);
+
+    impl MemoryConsumer for Consumer {
+        fn memory_usage(&self) -> usize {
+            *self.0.lock()
+        }
+
+        fn shrink_to(&self, target: usize) {
+            let mut usage = self.0.lock();
+            *usage = (*usage).min(target);
+        }
+    }
+
+    #[test]
+    fn test_memory_budget() {
+        let budget = MemoryBudget::new(0);
+        let a = Arc::new(Consumer(Mutex::new(300)));
+        let b = Arc::new(Consumer(Mutex::new(100)));
+        budget.register("a", Some(250), a.clone());
+        budget.register("b", None, b.clone());
+
+        // Without limit the budget is disabled
+        assert_eq!(budget.enforce(10_000), 0);
+        assert_eq!(a.memory_usage(), 300);
+
+        // Within the limit only the caps apply
+        budget.set_limit(1_000);
+        assert_eq!(budget.enforce(1_000), 50);
+        assert_eq!(a.memory_usage(), 250);
+        assert_eq!(b.memory_usage(), 100);
+
+        // The excess is shared proportionally to the usage
+        assert_eq!(budget.enforce(1_070), 70);
+        assert_eq!(a.memory_usage(), 200);
+        assert_eq!(b.memory_usage(), 80);
+        assert_eq!(
+            budget.stats(),
+            MemoryBudgetStats {
+                limit: 1_000,
+                usage: 280,
+                pressure_events: 1,
+                released_bytes: 120
+            }
+        );
+
+        // Dropped consumers are unregistered
+        drop(b);
+        assert_eq!(
+            budget.snapshot(),
+            vec![MemoryUsage {
+                name: "a",
+                usage: 200,
+                cap: Some(250)
+            }]
+        );
+    }
+}
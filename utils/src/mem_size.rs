@@ -47,6 +47,7 @@ pub trait MemSizeEstimator {
     }
 }
 
+impl MemSizeEstimator for () {}
 impl MemSizeEstimator for u64 {}
 impl MemSizeEstimator for u32 {}
 impl MemSizeEstimator for u16 {}
@@ -105,10 +105,15 @@ impl<T: Clone> Environment<T> {
             .push(Event::new(self.now + timeout, dest, None))
     }
 
-    pub fn broadcast(&mut self, _sender: u64, msg: T) {
+    pub fn broadcast(&mut self, sender: u64, msg: T) {
+        self.broadcast_after(0, sender, msg)
+    }
+
+    /// Broadcasts `msg` once `delay` elapsed, on top of the broadcast delay
+    pub fn broadcast_after(&mut self, delay: u64, _sender: u64, msg: T) {
         for &id in self.process_ids.iter() {
             self.event_queue.push(Event::new(
-                self.now + self.broadcast_delay,
+                self.now + delay + self.broadcast_delay,
                 id,
                 Some(msg.clone()),
             ));
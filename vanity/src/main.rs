@@ -0,0 +1,235 @@
+use clap::{Arg, ArgAction, Command};
+use karlsen_addresses::{Address, Prefix, Version};
+use karlsen_bip32::{DerivationPath, ExtendedPrivateKey, Language, Mnemonic, SecretKey, WordCount};
+use karlsen_consensus_core::network::NetworkId;
+use karlsen_core::karlsend_env::version;
+use secp256k1::{rand::thread_rng, Keypair};
+use std::{
+    io::{BufRead, IsTerminal, Write},
+    str::FromStr,
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        Mutex,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+mod pattern;
+
+use pattern::Pattern;
+
+/// Derivation path of the first receive address of the first account of a BIP32 wallet
+const RECEIVE_ADDRESS_PATH: &str = "m/44'/121337'/0'/0/0";
+
+/// Interval between two progress reports
+const PROGRESS_INTERVAL: Duration = Duration::from_secs(5);
+
+pub struct Args {
+    pub pattern: String,
+    pub network: NetworkId,
+    pub ignore_case: bool,
+    pub threads: usize,
+    pub mnemonic: bool,
+}
+
+impl Args {
+    fn parse() -> Self {
+        let m = cli().get_matches();
+        Args {
+            pattern: m.get_one::<String>("pattern").cloned().unwrap(),
+            network: m.get_one::<NetworkId>("network").cloned().unwrap(),
+            ignore_case: m.get_one::<bool>("ignore-case").cloned().unwrap_or(false),
+            threads: m.get_one::<usize>("threads").cloned().unwrap(),
+            mnemonic: m.get_one::<bool>("mnemonic").cloned().unwrap_or(false),
+        }
+    }
+}
+
+pub fn cli() -> Command {
+    Command::new("karlsen-vanity")
+        .about(format!("{} (karlsen-vanity) v{}", env!("CARGO_PKG_DESCRIPTION"), version()))
+        .version(env!("CARGO_PKG_VERSION"))
+        .arg(
+            Arg::new("pattern")
+                .required(true)
+                .value_name("pattern")
+                .help("Pattern of the address without its network prefix, made of address characters and '.' wildcards, optionally anchored with '^' and '$'"),
+        )
+        .arg(
+            Arg::new("network")
+                .long("network")
+                .short('n')
+                .value_name("network")
+                .default_value("mainnet")
+                .value_parser(|s: &str| NetworkId::from_str(s).map_err(|err| err.to_string()))
+                .help("Network of the address (mainnet, testnet-<suffix>, devnet or simnet)"),
+        )
+        .arg(
+            Arg::new("ignore-case")
+                .long("ignore-case")
+                .short('i')
+                .action(ArgAction::SetTrue)
+                .help("Match the pattern regardless of its case"),
+        )
+        .arg(
+            Arg::new("threads")
+                .long("threads")
+                .short('t')
+                .default_value("0")
+                .value_parser(clap::value_parser!(usize))
+                .help("Number of search threads. Set to 0 to use 1 thread per core."),
+        )
+        .arg(
+            Arg::new("mnemonic")
+                .long("mnemonic")
+                .action(ArgAction::SetTrue)
+                .help("Search for a 24 word mnemonic whose first receive address matches, instead of a private key. Much slower."),
+        )
+}
+
+/// The key of a matching address
+enum Secret {
+    PrivateKey(SecretKey),
+    Mnemonic(Mnemonic),
+}
+
+struct Candidate {
+    address: Address,
+    secret: Secret,
+}
+
+impl Candidate {
+    fn random(prefix: Prefix) -> Self {
+        let keypair = Keypair::new(secp256k1::SECP256K1, &mut thread_rng());
+        Self::new(prefix, &keypair, Secret::PrivateKey(keypair.secret_key()))
+    }
+
+    fn random_mnemonic(prefix: Prefix, path: &DerivationPath) -> Self {
+        let mnemonic = Mnemonic::random(WordCount::Words24, Language::English).unwrap();
+        let xprv = ExtendedPrivateKey::<SecretKey>::new(mnemonic.to_seed(""))
+            .and_then(|xprv| xprv.derive_path(path))
+            .unwrap();
+        let keypair = Keypair::from_secret_key(secp256k1::SECP256K1, xprv.private_key());
+        Self::new(prefix, &keypair, Secret::Mnemonic(mnemonic))
+    }
+
+    fn new(prefix: Prefix, keypair: &Keypair, secret: Secret) -> Self {
+        let address = Address::new(
+            prefix,
+            Version::PubKey,
+            &keypair.x_only_public_key().0.serialize(),
+        );
+        Self { address, secret }
+    }
+}
+
+/// Generates random keys on `threads` threads until the address of one matches `pattern`
+fn search(args: &Args, pattern: &Pattern) -> Candidate {
+    let prefix = Prefix::from(args.network);
+    let prefix_len = prefix.to_string().len() + 1;
+    let path = RECEIVE_ADDRESS_PATH.parse::<DerivationPath>().unwrap();
+    let threads = if args.threads == 0 {
+        num_cpus::get()
+    } else {
+        args.threads
+    };
+    let expected_attempts = pattern.expected_attempts();
+    println!(
+        "Searching with {} threads, expecting about {:.0} attempts",
+        threads, expected_attempts
+    );
+
+    let found: Mutex<Option<Candidate>> = Mutex::new(None);
+    let done = AtomicBool::new(false);
+    let attempts = AtomicU64::new(0);
+    thread::scope(|scope| {
+        for _ in 0..threads {
+            scope.spawn(|| {
+                while !done.load(Ordering::Relaxed) {
+                    let candidate = if args.mnemonic {
+                        Candidate::random_mnemonic(prefix, &path)
+                    } else {
+                        Candidate::random(prefix)
+                    };
+                    attempts.fetch_add(1, Ordering::Relaxed);
+                    let address = candidate.address.to_string();
+                    if pattern.matches(&address.as_bytes()[prefix_len..]) {
+                        found.lock().unwrap().get_or_insert(candidate);
+                        done.store(true, Ordering::Relaxed);
+                    }
+                }
+            });
+        }
+
+        let start = Instant::now();
+        let mut last_report = Instant::now();
+        while !done.load(Ordering::Relaxed) {
+            thread::sleep(Duration::from_millis(100));
+            if last_report.elapsed() >= PROGRESS_INTERVAL {
+                last_report = Instant::now();
+                let attempts = attempts.load(Ordering::Relaxed);
+                let rate = attempts as f64 / start.elapsed().as_secs_f64();
+                println!(
+                    "{} attempts, {:.0}/s, {:.0}% of the expected attempts",
+                    attempts,
+                    rate,
+                    100.0 * attempts as f64 / expected_attempts
+                );
+            }
+        }
+    });
+    found.into_inner().unwrap().unwrap()
+}
+
+/// Asks for a confirmation on the terminal before revealing the key of the found address
+fn confirm_reveal() -> bool {
+    if !std::io::stdin().is_terminal() {
+        println!("Not revealing the key without a confirmation from a terminal");
+        return false;
+    }
+    print!("Reveal the key of this address on the terminal? [y/N]: ");
+    std::io::stdout().flush().unwrap();
+    let mut answer = String::new();
+    if std::io::stdin().lock().read_line(&mut answer).is_err() {
+        return false;
+    }
+    matches!(answer.trim().to_lowercase().as_str(), "y" | "yes")
+}
+
+fn main() {
+    let args = Args::parse();
+    let pattern = match Pattern::parse(&args.pattern, args.ignore_case) {
+        Ok(pattern) => pattern,
+        Err(err) => {
+            eprintln!("Invalid pattern: {}", err);
+            std::process::exit(1);
+        }
+    };
+
+    let start = Instant::now();
+    let candidate = search(&args, &pattern);
+    println!(
+        "Found {} in {:.1}s",
+        candidate.address,
+        start.elapsed().as_secs_f64()
+    );
+
+    // The key is generated and kept in memory only, and written out on explicit request
+    if !confirm_reveal() {
+        println!("The key was discarded");
+        return;
+    }
+    match candidate.secret {
+        Secret::PrivateKey(secret_key) => {
+            println!(
+                "Private key: {}",
+                faster_hex::hex_string(&secret_key.secret_bytes())
+            );
+        }
+        Secret::Mnemonic(mnemonic) => {
+            println!("Mnemonic: {}", mnemonic.phrase());
+            println!("Derivation path: {}", RECEIVE_ADDRESS_PATH);
+        }
+    }
+}
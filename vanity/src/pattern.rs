@@ -0,0 +1,281 @@
+//! Patterns matched against the addresses, a minimal regex syntax over the bech32 charset.
+//!
+//! A pattern is made of address characters and `.` wildcards, optionally anchored at the start
+//! of the address with `^` and at its end with `$`. Unanchored patterns match anywhere in the
+//! address. The `karlsen:` prefix is not part of the matched string, so `^qqkas` matches the
+//! addresses starting with `karlsen:qqkas`.
+
+use std::ops::RangeInclusive;
+
+/// The characters of the bech32 charset, in the order of their 5-bit values
+const CHARSET: &[u8] = b"qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+
+/// The characters of the bech32 charset with an even 5-bit value
+const EVEN_CHARS: &[u8] = b"qzyxg2vwsj5kc6u7";
+
+/// Count of characters following the `karlsen:` prefix in a PubKey address
+pub const ADDRESS_LENGTH: usize = 61;
+
+/// Position of the last payload character: the version byte and the 32 bytes of the public key
+/// fill 52 characters and the 4 high bits of this one, its low bit being zero padding
+const PADDED_POSITION: usize = 52;
+
+/// Returns the characters the encoding produces at `position` of a PubKey address
+fn allowed_chars(position: usize) -> &'static [u8] {
+    match position {
+        // The version byte of PubKey addresses is zero, so they start with 'q' followed by
+        // one of the 4 characters whose 3 high bits are zero
+        0 => b"q",
+        1 => b"qpzr",
+        PADDED_POSITION => EVEN_CHARS,
+        // The remaining payload characters and the checksum take any value
+        _ => CHARSET,
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Token {
+    Char(u8),
+    Any,
+}
+
+impl Token {
+    fn matches(&self, c: u8) -> bool {
+        match self {
+            Token::Char(expected) => *expected == c,
+            Token::Any => true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Pattern {
+    tokens: Vec<Token>,
+    anchored_start: bool,
+    anchored_end: bool,
+}
+
+impl Pattern {
+    /// Parses `pattern`, lowercasing it first if `ignore_case` is set since addresses are
+    /// always lowercase
+    pub fn parse(pattern: &str, ignore_case: bool) -> Result<Self, String> {
+        let pattern = if ignore_case {
+            pattern.to_lowercase()
+        } else {
+            pattern.to_owned()
+        };
+        let mut body = pattern.as_str();
+        let anchored_start = body.starts_with('^');
+        if anchored_start {
+            body = &body[1..];
+        }
+        let anchored_end = body.ends_with('$');
+        if anchored_end {
+            body = &body[..body.len() - 1];
+        }
+
+        let tokens = body
+            .bytes()
+            .map(|c| match c {
+                b'.' => Ok(Token::Any),
+                c if CHARSET.contains(&c) => Ok(Token::Char(c)),
+                c if c.is_ascii_uppercase() => Err(format!(
+                    "addresses are lowercase, '{}' never matches (see --ignore-case)",
+                    c as char
+                )),
+                c => Err(format!(
+                    "'{}' is not a character of addresses, which only use \"{}\"",
+                    c as char,
+                    std::str::from_utf8(CHARSET).unwrap()
+                )),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        if tokens.iter().all(|token| *token == Token::Any) {
+            return Err("the pattern matches any address".to_owned());
+        }
+        if tokens.len() > ADDRESS_LENGTH {
+            return Err(format!(
+                "the pattern is longer than the {} characters of an address",
+                ADDRESS_LENGTH
+            ));
+        }
+
+        if anchored_start && anchored_end && tokens.len() != ADDRESS_LENGTH {
+            return Err(format!(
+                "a pattern anchored at both ends must span the {} characters of an address",
+                ADDRESS_LENGTH
+            ));
+        }
+
+        let pattern = Self {
+            tokens,
+            anchored_start,
+            anchored_end,
+        };
+        let starts = pattern.starts();
+        if starts
+            .clone()
+            .all(|start| pattern.probability_at(start) == 0.0)
+        {
+            return Err(match pattern.misplaced_char(*starts.start()) {
+                Some((position, c)) if starts.start() == starts.end() => format!(
+                    "'{}' never appears at position {} of an address, which only holds one of \"{}\"",
+                    c as char,
+                    position,
+                    std::str::from_utf8(allowed_chars(position)).unwrap()
+                ),
+                _ => "the pattern cannot appear anywhere in an address".to_owned(),
+            });
+        }
+        Ok(pattern)
+    }
+
+    /// Returns the positions in the address where the pattern may start
+    fn starts(&self) -> RangeInclusive<usize> {
+        let last_start = ADDRESS_LENGTH - self.tokens.len();
+        match (self.anchored_start, self.anchored_end) {
+            (true, _) => 0..=0,
+            (false, true) => last_start..=last_start,
+            (false, false) => 0..=last_start,
+        }
+    }
+
+    /// Returns the position and the character of the first token the encoding cannot produce
+    /// with the pattern placed at `start`
+    fn misplaced_char(&self, start: usize) -> Option<(usize, u8)> {
+        self.tokens
+            .iter()
+            .enumerate()
+            .find_map(|(i, token)| match token {
+                Token::Char(c) if !allowed_chars(start + i).contains(c) => Some((start + i, *c)),
+                _ => None,
+            })
+    }
+
+    /// Returns the probability that a random address matches the pattern placed at `start`
+    fn probability_at(&self, start: usize) -> f64 {
+        self.tokens
+            .iter()
+            .enumerate()
+            .map(|(i, token)| match token {
+                Token::Char(c) => {
+                    let allowed = allowed_chars(start + i);
+                    if allowed.contains(c) {
+                        1.0 / allowed.len() as f64
+                    } else {
+                        0.0
+                    }
+                }
+                Token::Any => 1.0,
+            })
+            .product()
+    }
+
+    /// Returns true if the address string `address`, stripped of its prefix, matches
+    pub fn matches(&self, address: &[u8]) -> bool {
+        if self.tokens.len() > address.len() {
+            return false;
+        }
+        let last_start = address.len() - self.tokens.len();
+        match (self.anchored_start, self.anchored_end) {
+            (true, true) => last_start == 0 && self.matches_at(address, 0),
+            (true, false) => self.matches_at(address, 0),
+            (false, true) => self.matches_at(address, last_start),
+            (false, false) => (0..=last_start).any(|start| self.matches_at(address, start)),
+        }
+    }
+
+    fn matches_at(&self, address: &[u8], start: usize) -> bool {
+        self.tokens
+            .iter()
+            .zip(&address[start..])
+            .all(|(token, c)| token.matches(*c))
+    }
+
+    /// Returns the expected count of random addresses to generate before one matches
+    pub fn expected_attempts(&self) -> f64 {
+        let probability = self
+            .starts()
+            .map(|start| self.probability_at(start))
+            .sum::<f64>();
+        (1.0 / probability).max(1.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pattern() {
+        let address = b"qqkas3l7rs9xt9kvsdeqmy7x4hhh4e9edj9jzptlvfvrgq0k3kw2gs7dj2asv";
+        assert_eq!(address.len(), ADDRESS_LENGTH);
+
+        for (pattern, expected) in [
+            ("kas", true),
+            ("^qqkas", true),
+            ("^qqk.s3", true),
+            ("dj2asv$", true),
+            ("dj2as$", false),
+            (
+                "^qqkas3l7rs9xt9kvsdeqmy7x4hhh4e9edj9jzptlvfvrgq0k3kw2gs7dj2asv$",
+                true,
+            ),
+            ("mmm", false),
+            // The last payload character only takes even values
+            ("g........$", true),
+            ("k........$", false),
+        ] {
+            let parsed = Pattern::parse(pattern, false).unwrap();
+            assert_eq!(parsed.matches(address), expected, "pattern {}", pattern);
+        }
+
+        // Case insensitivity
+        assert!(Pattern::parse("KAS", false).is_err());
+        assert!(Pattern::parse("KAS", true).unwrap().matches(address));
+
+        // Characters outside of the charset, wildcards only and characters the encoding never
+        // produces at their position are rejected
+        let impossible_padding = format!("^{}a", ".".repeat(52));
+        let unanchored_full = format!("p{}", ".".repeat(60));
+        for pattern in [
+            "b",
+            "kas1",
+            "...",
+            "^$",
+            "^kas",
+            "^p",
+            "^qy",
+            "^qqkas$",
+            "a........$",
+            &impossible_padding,
+            &unanchored_full,
+        ] {
+            assert!(
+                Pattern::parse(pattern, true).is_err(),
+                "pattern {}",
+                pattern
+            );
+        }
+
+        // Difficulty
+        assert_eq!(
+            Pattern::parse("^qqkas", false).unwrap().expected_attempts(),
+            32f64.powi(4) / 8.0
+        );
+        assert_eq!(
+            Pattern::parse("^q.kas", false).unwrap().expected_attempts(),
+            32f64.powi(3)
+        );
+        assert_eq!(
+            Pattern::parse("kas$", false).unwrap().expected_attempts(),
+            32f64.powi(3)
+        );
+        // Among the 59 placements, the 2 at the start never match, "a" never appears at the
+        // padded position and "s" or "k" match there with a probability of 1/16
+        assert_eq!(
+            Pattern::parse("kas", false).unwrap().expected_attempts(),
+            32f64.powi(3) / 58.0
+        );
+    }
+}
@@ -75,6 +75,10 @@ pub enum Error {
     #[error("Maximum derivation depth exceeded")]
     Depth,
 
+    /// Master key (depth 0) with a parent fingerprint or a child number.
+    #[error("Master key with a non-zero parent fingerprint or child number")]
+    MasterKeyAttrs,
+
     /// Seed length invalid.
     #[error("Invalid seed length")]
     SeedLength,
@@ -100,6 +100,14 @@ impl FromStr for ExtendedKey {
             chain_code,
         };
 
+        // keys at any depth are accepted, but a master key has no parent
+        if attrs.depth == 0
+            && (attrs.parent_fingerprint != [0u8; 4]
+                || attrs.child_number != ChildNumber::default())
+        {
+            return Err(Error::MasterKeyAttrs);
+        }
+
         Ok(ExtendedKey {
             prefix,
             attrs,
@@ -177,4 +185,33 @@ mod tests {
         );
         assert_eq!(&xpub.to_string(), xpub_base58);
     }
+
+    #[test]
+    fn bip32_test_vector_1_xprv_at_depth() {
+        // chain m/0H/1/2H/2
+        let xprv_base58 = "xprvA2JDeKCSNNZky6uBCviVfJSKyQ1mDYahRjijr5idH2WwLsEd4Hsb\
+            2Tyh8RfQMuPh7f7RtyzTtdrbdqqsunu5Mm3wDvUAKRHSC34sJ7in334";
+
+        let xprv = xprv_base58
+            .parse::<ExtendedKey>()
+            .expect("Could not parse key");
+        assert_eq!(xprv.prefix.as_str(), "xprv");
+        assert_eq!(xprv.attrs.depth, 4);
+        assert_eq!(xprv.attrs.parent_fingerprint, hex!("ee7ab90c"));
+        assert_eq!(xprv.attrs.child_number.0, 2);
+        assert_eq!(&xprv.to_string(), xprv_base58);
+    }
+
+    #[test]
+    fn bip32_test_vector_5_invalid_master_keys() {
+        // zero depth with non-zero parent fingerprint
+        let xprv_base58 = "xprv9s2SPatNQ9Vc6GTbVMFPFo7jsaZySyzk7L8n2uqKXJen3KUmvQNT\
+            uLh3fhZMBoG3G4ZW1N2kZuHEPY53qmbZzCHshoQnNf4GvELZfqTUrcv";
+        assert!(xprv_base58.parse::<ExtendedKey>().is_err());
+
+        // zero depth with non-zero index
+        let xprv_base58 = "xprv9s21ZrQH4r4TsiLvyLXqM9P7k1K3EYhA1kkD6xuquB5i39AU8KF4\
+            2acDyL3qsDbU9NmZn6MsGSUYZEsuoePmjzsB3eFKSUEh3Gu1N3cqVUN";
+        assert!(xprv_base58.parse::<ExtendedKey>().is_err());
+    }
 }
@@ -5,10 +5,12 @@
 
 pub mod descriptor;
 pub mod kind;
+pub mod policy;
 pub mod variants;
 pub use kind::*;
 pub use variants::*;
 
+use crate::account::policy::{policy_secret_hash, SpendingPolicy};
 use crate::derivation::build_derivate_paths;
 use crate::derivation::AddressDerivationManagerTrait;
 use crate::imports::*;
@@ -140,6 +142,10 @@ pub trait Account: AnySync + Send + Sync + 'static {
         self.context().settings.name.clone()
     }
 
+    fn policy(&self) -> Option<SpendingPolicy> {
+        self.context().settings.policy.clone()
+    }
+
     fn name_or_id(&self) -> String {
         if let Some(name) = self.name() {
             if name.is_empty() {
@@ -181,6 +187,46 @@ pub trait Account: AnySync + Send + Sync + 'static {
         Ok(())
     }
 
+    /// Sets or clears the spending policy enforced on the spends of the account.
+    ///
+    /// The policy is guarded by `policy_secret`, which must differ from the wallet secret. The
+    /// first policy set on the account establishes it, and it is required to change or clear the
+    /// policy afterwards.
+    async fn set_policy(
+        &self,
+        wallet_secret: &Secret,
+        policy_secret: &Secret,
+        policy: Option<SpendingPolicy>,
+    ) -> Result<()> {
+        if policy_secret.as_ref() == wallet_secret.as_ref() {
+            return Err(Error::PolicySecretNotSeparate);
+        }
+        let hash = policy_secret_hash(self.id(), policy_secret)?;
+        {
+            let mut context = self.context();
+            if context
+                .settings
+                .policy_secret_hash
+                .as_ref()
+                .is_some_and(|stored_hash| *stored_hash != hash)
+            {
+                return Err(Error::InvalidPolicySecret);
+            }
+            context.settings.policy_secret_hash = policy.is_some().then_some(hash);
+            context.settings.policy = policy;
+        }
+
+        let account = self.to_storage()?;
+        self.wallet()
+            .store()
+            .as_account_store()?
+            .store_single(&account, None)
+            .await?;
+
+        self.wallet().store().commit(wallet_secret).await?;
+        Ok(())
+    }
+
     fn get_list_string(&self) -> Result<String> {
         let name = style(self.name_with_id()).blue();
         let balance = self.balance_as_strings(None)?;
@@ -330,6 +376,8 @@ pub trait Account: AnySync + Send + Sync + 'static {
 
     /// Aggregate all account UTXOs into the change address.
     /// Also known as "compounding".
+    ///
+    /// The sweep is subject to the account [`SpendingPolicy`], though its funds stay within the account.
     async fn sweep(
         self: Arc<Self>,
         wallet_secret: Secret,
@@ -343,31 +391,52 @@ pub trait Account: AnySync + Send + Sync + 'static {
             keydata,
             payment_secret,
         ));
-        let settings = GeneratorSettings::try_new_with_account(
-            self.clone().as_dyn_arc(),
-            PaymentDestination::Change,
-            Fees::None,
-            None,
-        )?;
-        let generator = Generator::try_new(settings, Some(signer), Some(abortable))?;
 
-        let mut stream = generator.stream();
+        let wallet = self.wallet();
+        let guard = wallet.spending_guard();
+        let destination = PaymentDestination::Change;
+        let authorized = guard
+            .authorize(&self.clone().as_dyn_arc(), &destination, None)
+            .await?;
+
         let mut ids = vec![];
-        while let Some(transaction) = stream.try_next().await? {
-            transaction.try_sign()?;
-            ids.push(transaction.try_submit(&self.wallet().rpc_api()).await?);
+        let result = async {
+            let settings = GeneratorSettings::try_new_with_account(
+                self.clone().as_dyn_arc(),
+                destination,
+                Fees::None,
+                None,
+            )?;
+            let generator = Generator::try_new(settings, Some(signer), Some(abortable))?;
 
-            if let Some(notifier) = notifier.as_ref() {
-                notifier(&transaction);
+            let mut stream = generator.stream();
+            while let Some(transaction) = stream.try_next().await? {
+                transaction.try_sign()?;
+                ids.push(transaction.try_submit(&wallet.rpc_api()).await?);
+
+                if let Some(notifier) = notifier.as_ref() {
+                    notifier(&transaction);
+                }
+                yield_executor().await;
             }
-            yield_executor().await;
+
+            Ok(generator.summary())
         }
+        .await;
 
-        Ok((generator.summary(), ids))
+        let submitted = !ids.is_empty();
+        guard
+            .settle(self.id(), authorized, result, submitted)
+            .await
+            .map(|summary| (summary, ids))
     }
 
     /// Send funds to a [`PaymentDestination`] comprised of one or multiple [`PaymentOutputs`](crate::tx::PaymentOutputs)
     /// or [`PaymentDestination::Change`] variant that will forward funds to the change address.
+    ///
+    /// The send is subject to the account [`SpendingPolicy`]; a send requiring a confirmation
+    /// fails with [`Error::SendApprovalRequired`] and goes through once repeated with the
+    /// returned `approval_token`, after it was approved with the policy secret.
     async fn send(
         self: Arc<Self>,
         destination: PaymentDestination,
@@ -375,6 +444,7 @@ pub trait Account: AnySync + Send + Sync + 'static {
         payload: Option<Vec<u8>>,
         wallet_secret: Secret,
         payment_secret: Option<Secret>,
+        approval_token: Option<String>,
         abortable: &Abortable,
         notifier: Option<GenerationNotifier>,
     ) -> Result<(GeneratorSummary, Vec<karlsen_hashes::Hash>)> {
@@ -385,31 +455,53 @@ pub trait Account: AnySync + Send + Sync + 'static {
             payment_secret,
         ));
 
-        let settings = GeneratorSettings::try_new_with_account(
-            self.clone().as_dyn_arc(),
-            destination,
-            priority_fee_sompi,
-            payload,
-        )?;
-
-        let generator = Generator::try_new(settings, Some(signer), Some(abortable))?;
+        let wallet = self.wallet();
+        let guard = wallet.spending_guard();
+        let authorized = guard
+            .authorize(
+                &self.clone().as_dyn_arc(),
+                &destination,
+                approval_token.as_deref(),
+            )
+            .await?;
 
-        let mut stream = generator.stream();
         let mut ids = vec![];
-        while let Some(transaction) = stream.try_next().await? {
-            transaction.try_sign()?;
-            ids.push(transaction.try_submit(&self.wallet().rpc_api()).await?);
+        let result = async {
+            let settings = GeneratorSettings::try_new_with_account(
+                self.clone().as_dyn_arc(),
+                destination,
+                priority_fee_sompi,
+                payload,
+            )?;
 
-            if let Some(notifier) = notifier.as_ref() {
-                notifier(&transaction);
+            let generator = Generator::try_new(settings, Some(signer), Some(abortable))?;
+
+            let mut stream = generator.stream();
+            while let Some(transaction) = stream.try_next().await? {
+                transaction.try_sign()?;
+                ids.push(transaction.try_submit(&wallet.rpc_api()).await?);
+
+                if let Some(notifier) = notifier.as_ref() {
+                    notifier(&transaction);
+                }
+                yield_executor().await;
             }
-            yield_executor().await;
+
+            Ok(generator.summary())
         }
+        .await;
 
-        Ok((generator.summary(), ids))
+        let submitted = !ids.is_empty();
+        guard
+            .settle(self.id(), authorized, result, submitted)
+            .await
+            .map(|summary| (summary, ids))
     }
 
     /// Execute a transfer to another wallet account.
+    ///
+    /// The transfer is subject to the account [`SpendingPolicy`], just like a send to the
+    /// receive address of the destination account.
     async fn transfer(
         self: Arc<Self>,
         destination_account_id: AccountId,
@@ -417,6 +509,7 @@ pub trait Account: AnySync + Send + Sync + 'static {
         priority_fee_sompi: Fees,
         wallet_secret: Secret,
         payment_secret: Option<Secret>,
+        approval_token: Option<String>,
         abortable: &Abortable,
         notifier: Option<GenerationNotifier>,
     ) -> Result<(GeneratorSummary, Vec<karlsen_hashes::Hash>)> {
@@ -427,8 +520,8 @@ pub trait Account: AnySync + Send + Sync + 'static {
             payment_secret,
         ));
 
-        let destination_account = self
-            .wallet()
+        let wallet = self.wallet();
+        let destination_account = wallet
             .get_account_by_id(&destination_account_id)
             .await?
             .ok_or_else(|| Error::AccountNotFound(destination_account_id))?;
@@ -440,29 +533,47 @@ pub trait Account: AnySync + Send + Sync + 'static {
         ));
         let final_transaction_payload = None;
 
-        let settings = GeneratorSettings::try_new_with_account(
-            self.clone().as_dyn_arc(),
-            final_transaction_destination,
-            priority_fee_sompi,
-            final_transaction_payload,
-        )?
-        .utxo_context_transfer(destination_account.utxo_context());
-
-        let generator = Generator::try_new(settings, Some(signer), Some(abortable))?;
+        let guard = wallet.spending_guard();
+        let authorized = guard
+            .authorize(
+                &self.clone().as_dyn_arc(),
+                &final_transaction_destination,
+                approval_token.as_deref(),
+            )
+            .await?;
 
-        let mut stream = generator.stream();
         let mut ids = vec![];
-        while let Some(transaction) = stream.try_next().await? {
-            transaction.try_sign()?;
-            ids.push(transaction.try_submit(&self.wallet().rpc_api()).await?);
-
-            if let Some(notifier) = notifier.as_ref() {
-                notifier(&transaction);
+        let result = async {
+            let settings = GeneratorSettings::try_new_with_account(
+                self.clone().as_dyn_arc(),
+                final_transaction_destination,
+                priority_fee_sompi,
+                final_transaction_payload,
+            )?
+            .utxo_context_transfer(destination_account.utxo_context());
+
+            let generator = Generator::try_new(settings, Some(signer), Some(abortable))?;
+
+            let mut stream = generator.stream();
+            while let Some(transaction) = stream.try_next().await? {
+                transaction.try_sign()?;
+                ids.push(transaction.try_submit(&wallet.rpc_api()).await?);
+
+                if let Some(notifier) = notifier.as_ref() {
+                    notifier(&transaction);
+                }
+                yield_executor().await;
             }
-            yield_executor().await;
+
+            Ok(generator.summary())
         }
+        .await;
 
-        Ok((generator.summary(), ids))
+        let submitted = !ids.is_empty();
+        guard
+            .settle(self.id(), authorized, result, submitted)
+            .await
+            .map(|summary| (summary, ids))
     }
 
     async fn estimate(
@@ -0,0 +1,424 @@
+//!
+//! Spending policies of the accounts, guarding their sends against operator errors
+//! and compromised wallet secrets.
+//!
+//! A policy limits the amount sent within any 24 hours, restricts the destinations to
+//! whitelisted addresses and requires large sends to be confirmed. It applies to every
+//! spend of the account, be it a send, a transfer to another account or a sweep.
+//!
+//! The policy is guarded by a policy secret distinct from the wallet secret, so that a
+//! compromised wallet secret can neither change the policy nor approve a send. A send
+//! requiring a confirmation fails with an approval token. Once the token is approved with
+//! the policy secret, it confirms the very same send passed along with it, within
+//! [`APPROVAL_TOKEN_LIFETIME_MSEC`].
+//!
+
+use crate::encryption::argon2_sha256iv_hash;
+use crate::imports::*;
+use crate::storage::{Binding, TransactionData};
+use crate::tx::PaymentDestination;
+use std::collections::VecDeque;
+use workflow_core::time::unixtime_as_millis_u64;
+
+/// Period covered by the daily spend limit
+pub const SPENDING_PERIOD_MSEC: u64 = 24 * 60 * 60 * 1000;
+
+/// Period within which an approval token confirms its send
+pub const APPROVAL_TOKEN_LIFETIME_MSEC: u64 = 10 * 60 * 1000;
+
+/// Spending policy of an account, each of its rules applying only when set.
+#[derive(
+    Default, Debug, Clone, PartialEq, Eq, Serialize, Deserialize, BorshSerialize, BorshDeserialize,
+)]
+#[serde(rename_all = "camelCase")]
+pub struct SpendingPolicy {
+    /// Maximum amount in sompi sent within any 24 hours
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub daily_limit_sompi: Option<u64>,
+    /// Addresses the account may send to
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub whitelist: Option<Vec<Address>>,
+    /// Amount in sompi above which a send must be confirmed with an approval token
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub approval_threshold_sompi: Option<u64>,
+}
+
+impl SpendingPolicy {
+    pub fn check_destination(&self, destination: &PaymentDestination) -> Result<()> {
+        if let (Some(whitelist), PaymentDestination::PaymentOutputs(payment_outputs)) =
+            (self.whitelist.as_ref(), destination)
+        {
+            if let Some(output) = payment_outputs
+                .outputs
+                .iter()
+                .find(|output| !whitelist.contains(&output.address))
+            {
+                return Err(Error::DestinationNotWhitelisted(output.address.to_string()));
+            }
+        }
+        Ok(())
+    }
+
+    /// Checks that sending `amount` on top of the `spent` amount of the last 24 hours stays within the daily limit
+    pub fn check_daily_limit(&self, spent: u64, amount: u64) -> Result<()> {
+        match self.daily_limit_sompi {
+            Some(limit) if spent.saturating_add(amount) > limit => {
+                Err(Error::DailySpendLimitExceeded { limit, spent })
+            }
+            _ => Ok(()),
+        }
+    }
+
+    pub fn requires_approval(&self, amount: u64) -> bool {
+        self.approval_threshold_sompi
+            .is_some_and(|threshold| amount > threshold)
+    }
+}
+
+/// Hashes the policy secret of an account, salted with the account id
+pub fn policy_secret_hash(account_id: &AccountId, policy_secret: &Secret) -> Result<Vec<u8>> {
+    let data = [account_id.0.as_bytes().as_slice(), policy_secret.as_ref()].concat();
+    Ok(argon2_sha256iv_hash(&data, 32)?.as_ref().to_vec())
+}
+
+/// A send awaiting its confirmation
+struct PendingApproval {
+    account_id: AccountId,
+    /// Destination of the send, rendered as its payment outputs
+    destination: String,
+    expiration_msec: u64,
+    /// Whether the send was approved with the policy secret
+    approved: bool,
+}
+
+/// Sends of an account within the last 24 hours
+struct SpendingLedger {
+    /// Unix time in milliseconds and amount of every send, oldest first
+    sends: VecDeque<(u64, u64)>,
+}
+
+impl SpendingLedger {
+    fn spent(&mut self, now_msec: u64) -> u64 {
+        while self
+            .sends
+            .front()
+            .is_some_and(|(time, _)| time + SPENDING_PERIOD_MSEC <= now_msec)
+        {
+            self.sends.pop_front();
+        }
+        self.sends.iter().map(|(_, amount)| amount).sum()
+    }
+}
+
+/// Enforces the spending policies of the accounts of a wallet, tracking their
+/// recent sends and the sends awaiting a confirmation.
+#[derive(Default)]
+pub struct SpendingGuard {
+    ledgers: AsyncMutex<HashMap<AccountId, SpendingLedger>>,
+    approvals: Mutex<HashMap<String, PendingApproval>>,
+}
+
+impl SpendingGuard {
+    /// Authorizes a send of `account` to `destination` under the account policy, recording it as
+    /// spent. The returned record must be passed to [`SpendingGuard::cancel`] if the send fails.
+    pub async fn authorize(
+        &self,
+        account: &Arc<dyn Account>,
+        destination: &PaymentDestination,
+        approval_token: Option<&str>,
+    ) -> Result<Option<(u64, u64)>> {
+        let Some(policy) = account.policy() else {
+            return Ok(None);
+        };
+        let amount = destination.amount().unwrap_or_default();
+        policy.check_destination(destination)?;
+
+        let now_msec = unixtime_as_millis_u64();
+        let mut ledgers = self.ledgers.lock().await;
+        let ledger = match ledgers.entry(*account.id()) {
+            std::collections::hash_map::Entry::Occupied(entry) => entry.into_mut(),
+            std::collections::hash_map::Entry::Vacant(entry) => {
+                entry.insert(Self::load_ledger(account, now_msec).await?)
+            }
+        };
+        policy.check_daily_limit(ledger.spent(now_msec), amount)?;
+
+        if policy.requires_approval(amount) {
+            self.check_approval(account.id(), destination, approval_token, now_msec)?;
+        }
+
+        let send = (now_msec, amount);
+        ledger.sends.push_back(send);
+        Ok(Some(send))
+    }
+
+    /// Removes a send recorded by [`SpendingGuard::authorize`] which did not go through
+    pub async fn cancel(&self, account_id: &AccountId, send: (u64, u64)) {
+        if let Some(ledger) = self.ledgers.lock().await.get_mut(account_id) {
+            if let Some(position) = ledger.sends.iter().position(|entry| *entry == send) {
+                ledger.sends.remove(position);
+            }
+        }
+    }
+
+    /// Settles the outcome of a send recorded by [`SpendingGuard::authorize`]. A send which
+    /// failed before submitting any of its transactions does not count against the daily limit.
+    pub async fn settle<T>(
+        &self,
+        account_id: &AccountId,
+        authorized: Option<(u64, u64)>,
+        result: Result<T>,
+        submitted: bool,
+    ) -> Result<T> {
+        if let (Err(_), Some(send), false) = (&result, authorized, submitted) {
+            self.cancel(account_id, send).await;
+        }
+        result
+    }
+
+    /// Approves the send awaiting a confirmation with `approval_token`, provided the policy secret
+    /// of the account
+    pub fn approve_send(
+        &self,
+        account: &Arc<dyn Account>,
+        approval_token: &str,
+        policy_secret: &Secret,
+    ) -> Result<()> {
+        let stored_hash = account.context().settings.policy_secret_hash.clone();
+        match stored_hash {
+            Some(hash) if hash == policy_secret_hash(account.id(), policy_secret)? => {
+                self.approve_pending(account.id(), approval_token, unixtime_as_millis_u64())
+            }
+            _ => Err(Error::InvalidPolicySecret),
+        }
+    }
+
+    /// Forgets the sends and the approvals, to be called when the wallet is closed
+    pub async fn clear(&self) {
+        self.ledgers.lock().await.clear();
+        self.approvals.lock().unwrap().clear();
+    }
+
+    /// Marks the send awaiting a confirmation with `approval_token` as approved
+    fn approve_pending(
+        &self,
+        account_id: &AccountId,
+        approval_token: &str,
+        now_msec: u64,
+    ) -> Result<()> {
+        let mut approvals = self.approvals.lock().unwrap();
+        approvals.retain(|_, approval| approval.expiration_msec > now_msec);
+        match approvals.get_mut(approval_token) {
+            Some(approval) if approval.account_id == *account_id => {
+                approval.approved = true;
+                Ok(())
+            }
+            _ => Err(Error::InvalidApprovalToken),
+        }
+    }
+
+    /// Consumes the approved token of the send, or issues one if none is given
+    fn check_approval(
+        &self,
+        account_id: &AccountId,
+        destination: &PaymentDestination,
+        approval_token: Option<&str>,
+        now_msec: u64,
+    ) -> Result<()> {
+        let destination = destination_key(destination);
+        let mut approvals = self.approvals.lock().unwrap();
+        approvals.retain(|_, approval| approval.expiration_msec > now_msec);
+        match approval_token {
+            Some(token) => match approvals.get(token) {
+                Some(approval)
+                    if approval.account_id == *account_id
+                        && approval.destination == destination =>
+                {
+                    if !approval.approved {
+                        return Err(Error::SendNotApproved(token.to_string()));
+                    }
+                    approvals.remove(token);
+                    Ok(())
+                }
+                _ => Err(Error::InvalidApprovalToken),
+            },
+            None => {
+                let token = faster_hex::hex_string(&rand::random::<[u8; 16]>());
+                approvals.insert(
+                    token.clone(),
+                    PendingApproval {
+                        account_id: *account_id,
+                        destination,
+                        expiration_msec: now_msec + APPROVAL_TOKEN_LIFETIME_MSEC,
+                        approved: false,
+                    },
+                );
+                Err(Error::SendApprovalRequired(token))
+            }
+        }
+    }
+
+    /// Reads the sends of the last 24 hours from the outgoing transaction records of the account
+    async fn load_ledger(account: &Arc<dyn Account>, now_msec: u64) -> Result<SpendingLedger> {
+        let wallet = account.wallet();
+        let mut stream = wallet
+            .store()
+            .as_transaction_record_store()?
+            .transaction_data_iter(&Binding::Account(*account.id()), &wallet.network_id()?)
+            .await?;
+        let mut sends = Vec::new();
+        while let Some(record) = stream.try_next().await? {
+            if let (
+                Some(time),
+                TransactionData::Outgoing {
+                    payment_value: Some(amount),
+                    ..
+                },
+            ) = (record.unixtime_msec, &record.transaction_data)
+            {
+                if time + SPENDING_PERIOD_MSEC > now_msec {
+                    sends.push((time, *amount));
+                }
+            }
+        }
+        sends.sort_unstable();
+        Ok(SpendingLedger {
+            sends: sends.into(),
+        })
+    }
+}
+
+fn destination_key(destination: &PaymentDestination) -> String {
+    match destination {
+        PaymentDestination::Change => "change".to_string(),
+        PaymentDestination::PaymentOutputs(payment_outputs) => payment_outputs
+            .outputs
+            .iter()
+            .map(|output| format!("{}:{}", output.address, output.amount))
+            .collect::<Vec<_>>()
+            .join(","),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tx::PaymentOutputs;
+
+    fn address(byte: u8) -> Address {
+        Address::new(
+            Prefix::Testnet,
+            karlsen_addresses::Version::PubKey,
+            &[byte; 32],
+        )
+    }
+
+    #[test]
+    fn test_spending_policy() {
+        let policy = SpendingPolicy {
+            daily_limit_sompi: Some(100),
+            whitelist: Some(vec![address(1)]),
+            approval_threshold_sompi: Some(50),
+        };
+        let whitelisted = PaymentDestination::from(PaymentOutputs::from((address(1), 10)));
+        let other = PaymentDestination::from(PaymentOutputs::from((address(2), 10)));
+        assert!(policy.check_destination(&whitelisted).is_ok());
+        assert!(policy
+            .check_destination(&PaymentDestination::Change)
+            .is_ok());
+        assert!(matches!(
+            policy.check_destination(&other),
+            Err(Error::DestinationNotWhitelisted(_))
+        ));
+
+        assert!(policy.check_daily_limit(60, 40).is_ok());
+        assert!(matches!(
+            policy.check_daily_limit(60, 41),
+            Err(Error::DailySpendLimitExceeded {
+                limit: 100,
+                spent: 60
+            })
+        ));
+        assert!(!policy.requires_approval(50));
+        assert!(policy.requires_approval(51));
+        assert!(!SpendingPolicy::default().requires_approval(u64::MAX));
+    }
+
+    #[test]
+    fn test_spending_approval() {
+        let guard = SpendingGuard::default();
+        let account_id = AccountId(karlsen_hashes::Hash::from_u64_word(1));
+        let destination = PaymentDestination::from(PaymentOutputs::from((address(1), 60)));
+        let other = PaymentDestination::from(PaymentOutputs::from((address(1), 61)));
+
+        let Err(Error::SendApprovalRequired(token)) =
+            guard.check_approval(&account_id, &destination, None, 0)
+        else {
+            panic!("the send should require an approval");
+        };
+        // A token confirms nothing until approved with the policy secret
+        assert!(matches!(
+            guard.check_approval(&account_id, &destination, Some(&token), 0),
+            Err(Error::SendNotApproved(_))
+        ));
+        assert!(guard
+            .approve_pending(
+                &AccountId(karlsen_hashes::Hash::from_u64_word(2)),
+                &token,
+                0
+            )
+            .is_err());
+        guard.approve_pending(&account_id, &token, 0).unwrap();
+
+        // An approved token only confirms the send it was issued for, once and before it expires
+        assert!(matches!(
+            guard.check_approval(&account_id, &other, Some(&token), 0),
+            Err(Error::InvalidApprovalToken)
+        ));
+        assert!(guard
+            .check_approval(&account_id, &destination, Some(&token), 1)
+            .is_ok());
+        assert!(guard
+            .check_approval(&account_id, &destination, Some(&token), 1)
+            .is_err());
+        let Err(Error::SendApprovalRequired(token)) =
+            guard.check_approval(&account_id, &destination, None, 0)
+        else {
+            panic!("the send should require an approval");
+        };
+        guard.approve_pending(&account_id, &token, 0).unwrap();
+        assert!(guard
+            .check_approval(
+                &account_id,
+                &destination,
+                Some(&token),
+                APPROVAL_TOKEN_LIFETIME_MSEC
+            )
+            .is_err());
+
+        let mut ledger = SpendingLedger {
+            sends: VecDeque::from([(0, 10), (1000, 20)]),
+        };
+        assert_eq!(ledger.spent(999), 30);
+        assert_eq!(ledger.spent(SPENDING_PERIOD_MSEC + 1), 20);
+    }
+
+    #[test]
+    fn test_policy_secret_hash() {
+        let account_id = AccountId(karlsen_hashes::Hash::from_u64_word(1));
+        let other_account_id = AccountId(karlsen_hashes::Hash::from_u64_word(2));
+        let hash = policy_secret_hash(&account_id, &Secret::from("policy")).unwrap();
+        assert_eq!(
+            hash,
+            policy_secret_hash(&account_id, &Secret::from("policy")).unwrap()
+        );
+        assert_ne!(
+            hash,
+            policy_secret_hash(&account_id, &Secret::from("wallet")).unwrap()
+        );
+        assert_ne!(
+            hash,
+            policy_secret_hash(&other_account_id, &Secret::from("policy")).unwrap()
+        );
+    }
+}
@@ -195,6 +195,10 @@ impl Bip32 {
         })
     }
 
+    pub fn xpub_keys(&self) -> &ExtendedPublicKeys {
+        &self.xpub_keys
+    }
+
     pub fn get_address_range_for_scan(&self, range: std::ops::Range<u32>) -> Result<Vec<Address>> {
         let receive_addresses = self
             .derivation
@@ -0,0 +1,438 @@
+//!
+//! Multi-wallet host allowing a single process to serve several
+//! independent wallet files concurrently.
+//!
+//! Each hosted wallet runs its own [`Wallet`] instance with its own storage,
+//! sharing the RPC connection of the hub. Wallet API calls are scoped to
+//! a wallet by its name and serialized per wallet, so the calls made on
+//! different wallets never contend with each other. [`WalletHub::codec`]
+//! exposes a hosted wallet as a transport [`Codec`], so a
+//! [`WalletClient`](super::transport::WalletClient) drives it like a wallet
+//! served on its own.
+//!
+
+use super::transport::{BorshCodec, Codec, EventHandler, SerdeCodec, WalletServer};
+use crate::imports::*;
+use convert_case::{Case, Casing};
+use xxhash_rust::xxh3::xxh3_64;
+
+/// Wallet API methods that can not be invoked on a hosted wallet since they
+/// change the wallet file it is bound to or affect the RPC connection shared
+/// by all the hosted wallets. Wallets are opened and closed through the hub.
+const HUB_RESTRICTED_METHODS: &[&str] = &[
+    "Connect",
+    "Disconnect",
+    "ChangeNetworkId",
+    "WalletCreate",
+    "WalletOpen",
+    "WalletClose",
+    "WalletRename",
+    "WalletImport",
+];
+
+/// Receives the events of the hosted wallets, tagged with the name of the
+/// wallet emitting them.
+#[async_trait]
+pub trait HubEventHandler: Send + Sync {
+    async fn handle_event(&self, wallet_name: &str, event: &Events);
+}
+
+/// Forwards the events of a single hosted wallet to the [`HubEventHandler`]
+struct ScopedEventHandler {
+    wallet_name: String,
+    handler: Arc<dyn HubEventHandler>,
+}
+
+#[async_trait]
+impl EventHandler for ScopedEventHandler {
+    async fn handle_event(&self, event: &Events) {
+        self.handler.handle_event(&self.wallet_name, event).await;
+    }
+}
+
+struct HostedWallet {
+    server: Arc<WalletServer>,
+    /// Serializes the API calls made on the wallet
+    lock: AsyncMutex<()>,
+    /// Set under `lock` once the wallet is closed, rejecting the calls
+    /// that were waiting on it
+    closed: AtomicBool,
+}
+
+#[derive(Default)]
+struct HubState {
+    wallets: HashMap<String, Arc<HostedWallet>>,
+    /// Names reserved by the wallets being opened
+    opening: HashSet<String>,
+}
+
+/// [`WalletHub`] hosts multiple wallets, opened, closed and listed by name,
+/// and dispatches the serialized Wallet API calls to the wallet they are
+/// scoped to.
+pub struct WalletHub {
+    rpc: Option<Rpc>,
+    network_id: Option<NetworkId>,
+    event_handler: Arc<dyn HubEventHandler>,
+    storage_folder: Option<String>,
+    /// Never held across an `.await`
+    state: Mutex<HubState>,
+}
+
+impl WalletHub {
+    pub fn new(
+        rpc: Option<Rpc>,
+        network_id: Option<NetworkId>,
+        event_handler: Arc<dyn HubEventHandler>,
+    ) -> Self {
+        Self {
+            rpc,
+            network_id,
+            event_handler,
+            storage_folder: None,
+            state: Mutex::new(HubState::default()),
+        }
+    }
+
+    /// Opens the wallet files from `folder` instead of the default storage folder
+    pub fn with_storage_folder(mut self, folder: &str) -> Self {
+        self.storage_folder = Some(folder.to_string());
+        self
+    }
+
+    fn store(&self) -> Result<Arc<dyn Interface>> {
+        match self.storage_folder.as_deref() {
+            Some(folder) => Wallet::local_store_with_folder(folder),
+            None => Wallet::local_store(),
+        }
+    }
+
+    /// Opens the wallet stored under `name` and starts hosting it.
+    pub async fn open(
+        &self,
+        name: &str,
+        wallet_secret: &Secret,
+        args: WalletOpenArgs,
+    ) -> Result<Option<Vec<AccountDescriptor>>> {
+        {
+            let mut state = self.state.lock().unwrap();
+            if state.wallets.contains_key(name) || !state.opening.insert(name.to_string()) {
+                return Err(Error::WalletAlreadyOpen(name.to_string()));
+            }
+        }
+
+        let result = self.open_impl(name, wallet_secret, args).await;
+        let mut state = self.state.lock().unwrap();
+        state.opening.remove(name);
+        let (hosted, account_descriptors) = result?;
+        state.wallets.insert(name.to_string(), hosted);
+        Ok(account_descriptors)
+    }
+
+    async fn open_impl(
+        &self,
+        name: &str,
+        wallet_secret: &Secret,
+        args: WalletOpenArgs,
+    ) -> Result<(Arc<HostedWallet>, Option<Vec<AccountDescriptor>>)> {
+        let wallet = Arc::new(Wallet::try_with_rpc(
+            self.rpc.clone(),
+            self.store()?,
+            self.network_id,
+        )?);
+        wallet.start().await?;
+        let account_descriptors = match wallet
+            .open(wallet_secret, Some(name.to_string()), args)
+            .await
+        {
+            Ok(account_descriptors) => account_descriptors,
+            Err(err) => {
+                wallet.stop().await?;
+                return Err(err);
+            }
+        };
+
+        let server = Arc::new(WalletServer::new(
+            wallet,
+            Arc::new(ScopedEventHandler {
+                wallet_name: name.to_string(),
+                handler: self.event_handler.clone(),
+            }),
+        ));
+        server.start();
+        let hosted = Arc::new(HostedWallet {
+            server,
+            lock: AsyncMutex::new(()),
+            closed: AtomicBool::new(false),
+        });
+        Ok((hosted, account_descriptors))
+    }
+
+    /// Closes the wallet `name` and stops hosting it, once the calls in
+    /// progress on it complete. The wallet stays hosted if it fails to close.
+    pub async fn close(&self, name: &str) -> Result<()> {
+        let hosted = self.hosted(name)?;
+        let _guard = hosted.lock.lock().await;
+        if hosted.closed.load(Ordering::SeqCst) {
+            return Err(Error::WalletNotHosted(name.to_string()));
+        }
+        hosted.server.wallet.close().await?;
+        hosted.server.wallet.stop().await?;
+        hosted.server.stop_task().await?;
+        hosted.closed.store(true, Ordering::SeqCst);
+        self.state.lock().unwrap().wallets.remove(name);
+        Ok(())
+    }
+
+    /// Closes all the hosted wallets, returning the first error encountered
+    /// once every wallet was attempted.
+    pub async fn close_all(&self) -> Result<()> {
+        let mut result = Ok(());
+        for name in self.list() {
+            if let Err(err) = self.close(&name).await {
+                log_error!("WalletHub: unable to close wallet '{name}': {err}");
+                if result.is_ok() {
+                    result = Err(err);
+                }
+            }
+        }
+        result
+    }
+
+    /// Returns the names of the hosted wallets, sorted
+    pub fn list(&self) -> Vec<String> {
+        let mut names = self
+            .state
+            .lock()
+            .unwrap()
+            .wallets
+            .keys()
+            .cloned()
+            .collect::<Vec<_>>();
+        names.sort();
+        names
+    }
+
+    /// Returns the hosted wallet `name`
+    pub fn wallet(&self, name: &str) -> Result<Arc<Wallet>> {
+        Ok(self.hosted(name)?.server.wallet.clone())
+    }
+
+    fn hosted(&self, name: &str) -> Result<Arc<HostedWallet>> {
+        self.state
+            .lock()
+            .unwrap()
+            .wallets
+            .get(name)
+            .cloned()
+            .ok_or_else(|| Error::WalletNotHosted(name.to_string()))
+    }
+
+    /// Returns a Borsh transport [`Codec`] scoped to the hosted wallet `name`,
+    /// to be used by a [`WalletClient`](super::transport::WalletClient). The wallet is resolved on each call,
+    /// so the codec fails with [`Error::WalletNotHosted`] once it is closed.
+    pub fn codec(self: &Arc<Self>, name: &str) -> Codec {
+        Codec::Borsh(Arc::new(HubCodec {
+            hub: self.clone(),
+            wallet_name: name.to_string(),
+        }))
+    }
+
+    /// Returns a Serde JSON transport [`Codec`] scoped to the hosted wallet `name`
+    pub fn serde_codec(self: &Arc<Self>, name: &str) -> Codec {
+        Codec::Serde(Arc::new(HubCodec {
+            hub: self.clone(),
+            wallet_name: name.to_string(),
+        }))
+    }
+
+    /// Dispatches a Borsh serialized Wallet API call to the hosted wallet `name`
+    pub async fn call_with_borsh(&self, name: &str, op: u64, request: &[u8]) -> Result<Vec<u8>> {
+        if let Some(method) = HUB_RESTRICTED_METHODS
+            .iter()
+            .find(|method| xxh3_64(method.as_bytes()) == op)
+        {
+            return Err(Error::WalletHubMethodNotAllowed(method.to_string()));
+        }
+        let hosted = self.hosted(name)?;
+        let _guard = hosted.lock.lock().await;
+        if hosted.closed.load(Ordering::SeqCst) {
+            return Err(Error::WalletNotHosted(name.to_string()));
+        }
+        hosted.server.call_with_borsh(op, request).await
+    }
+
+    /// Dispatches a Serde JSON serialized Wallet API call to the hosted wallet `name`
+    pub async fn call_with_serde(&self, name: &str, op: &str, request: &str) -> Result<String> {
+        if let Some(method) = HUB_RESTRICTED_METHODS
+            .iter()
+            .find(|method| method.to_case(Case::Kebab) == op)
+        {
+            return Err(Error::WalletHubMethodNotAllowed(method.to_string()));
+        }
+        let hosted = self.hosted(name)?;
+        let _guard = hosted.lock.lock().await;
+        if hosted.closed.load(Ordering::SeqCst) {
+            return Err(Error::WalletNotHosted(name.to_string()));
+        }
+        hosted.server.call_with_serde(op, request).await
+    }
+}
+
+/// Transport codec dispatching the calls of a
+/// [`WalletClient`](super::transport::WalletClient) to a hosted wallet
+struct HubCodec {
+    hub: Arc<WalletHub>,
+    wallet_name: String,
+}
+
+#[async_trait]
+impl BorshCodec for HubCodec {
+    async fn call(&self, op: u64, request: Vec<u8>) -> Result<Vec<u8>> {
+        self.hub
+            .call_with_borsh(&self.wallet_name, op, &request)
+            .await
+    }
+}
+
+#[async_trait]
+impl SerdeCodec for HubCodec {
+    async fn call(&self, op: &str, request: &str) -> Result<String> {
+        self.hub
+            .call_with_serde(&self.wallet_name, op, request)
+            .await
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::api::transport::WalletClient;
+    use crate::api::WalletApi;
+    use crate::tests::RpcCoreMock;
+
+    struct NoopEventHandler;
+
+    #[async_trait]
+    impl HubEventHandler for NoopEventHandler {
+        async fn handle_event(&self, _wallet_name: &str, _event: &Events) {}
+    }
+
+    async fn create_wallet(
+        rpc: &Rpc,
+        folder: &str,
+        name: &str,
+        wallet_secret: &Secret,
+    ) -> Result<()> {
+        let wallet = Arc::new(Wallet::try_with_rpc(
+            Some(rpc.clone()),
+            Wallet::local_store_with_folder(folder)?,
+            Some(NetworkId::new(NetworkType::Mainnet)),
+        )?);
+        wallet
+            .create_wallet(
+                wallet_secret,
+                WalletCreateArgs {
+                    title: Some(name.to_string()),
+                    filename: Some(name.to_string()),
+                    encryption_kind: EncryptionKind::XChaCha20Poly1305,
+                    user_hint: None,
+                    overwrite_wallet_storage: false,
+                },
+            )
+            .await?;
+        wallet.close().await
+    }
+
+    async fn hosted_filename(client: Arc<WalletClient>) -> Result<Option<String>> {
+        Ok(client
+            .get_status_call(GetStatusRequest { name: None })
+            .await?
+            .wallet_descriptor
+            .map(|descriptor| descriptor.filename))
+    }
+
+    #[tokio::test]
+    async fn test_wallet_hub() -> Result<()> {
+        let folder =
+            std::env::temp_dir().join(format!("karlsen-wallet-hub-{}", rand::random::<u64>()));
+        std::fs::create_dir_all(&folder)?;
+        let folder = folder.to_str().unwrap().to_string();
+
+        let rpc: Rpc = Arc::new(RpcCoreMock::new()).into();
+        let wallet_secret = Secret::new(b"secret".to_vec());
+        create_wallet(&rpc, &folder, "alpha", &wallet_secret).await?;
+        create_wallet(&rpc, &folder, "beta", &wallet_secret).await?;
+
+        let hub = Arc::new(
+            WalletHub::new(
+                Some(rpc),
+                Some(NetworkId::new(NetworkType::Mainnet)),
+                Arc::new(NoopEventHandler),
+            )
+            .with_storage_folder(&folder),
+        );
+
+        // A failed open leaves the name free for a retry
+        let wrong_secret = Secret::new(b"wrong".to_vec());
+        assert!(hub
+            .open("alpha", &wrong_secret, WalletOpenArgs::default())
+            .await
+            .is_err());
+        assert!(hub.list().is_empty());
+
+        hub.open("alpha", &wallet_secret, WalletOpenArgs::default())
+            .await?;
+        hub.open("beta", &wallet_secret, WalletOpenArgs::default())
+            .await?;
+        assert!(matches!(
+            hub.open("alpha", &wallet_secret, WalletOpenArgs::default())
+                .await,
+            Err(Error::WalletAlreadyOpen(_))
+        ));
+        assert_eq!(hub.list(), vec!["alpha".to_string(), "beta".to_string()]);
+
+        // The calls made through each codec are scoped to their own wallet
+        let alpha = Arc::new(WalletClient::new(hub.codec("alpha")));
+        let beta = Arc::new(WalletClient::new(hub.serde_codec("beta")));
+        assert_eq!(
+            hosted_filename(alpha.clone()).await?.as_deref(),
+            Some("alpha")
+        );
+        assert_eq!(
+            hosted_filename(beta.clone()).await?.as_deref(),
+            Some("beta")
+        );
+
+        // Methods rebinding the hosted wallet are rejected on both transports
+        assert!(matches!(
+            alpha.clone().wallet_close_call(WalletCloseRequest {}).await,
+            Err(Error::WalletHubMethodNotAllowed(_))
+        ));
+        assert!(matches!(
+            beta.clone().wallet_close_call(WalletCloseRequest {}).await,
+            Err(Error::WalletHubMethodNotAllowed(_))
+        ));
+
+        // Closing a wallet leaves the other one hosted
+        hub.close("alpha").await?;
+        assert_eq!(hub.list(), vec!["beta".to_string()]);
+        assert!(matches!(
+            hosted_filename(alpha).await,
+            Err(Error::WalletNotHosted(_))
+        ));
+        assert!(matches!(
+            hub.close("alpha").await,
+            Err(Error::WalletNotHosted(_))
+        ));
+        assert_eq!(hosted_filename(beta).await?.as_deref(), Some("beta"));
+
+        // A closed wallet can be hosted again
+        hub.open("alpha", &wallet_secret, WalletOpenArgs::default())
+            .await?;
+        hub.close_all().await?;
+        assert!(hub.list().is_empty());
+
+        std::fs::remove_dir_all(&folder)?;
+        Ok(())
+    }
+}
@@ -5,6 +5,7 @@
 //! `XxxRequest` and `XxxResponse` message.
 //!
 
+use crate::account::policy::SpendingPolicy;
 use crate::imports::*;
 use crate::tx::{Fees, GeneratorSummary, PaymentDestination};
 use karlsen_addresses::Address;
@@ -291,6 +292,32 @@ pub struct AccountsRenameRequest {
 #[serde(rename_all = "camelCase")]
 pub struct AccountsRenameResponse {}
 
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountsSetPolicyRequest {
+    pub account_id: AccountId,
+    pub policy: Option<SpendingPolicy>,
+    pub wallet_secret: Secret,
+    /// Secret guarding the policy, distinct from the wallet secret
+    pub policy_secret: Secret,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountsSetPolicyResponse {}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountsApproveSendRequest {
+    pub account_id: AccountId,
+    pub approval_token: String,
+    pub policy_secret: Secret,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountsApproveSendResponse {}
+
 /// @category Wallet API
 #[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize, CastFromJs)]
 #[serde(rename_all = "camelCase")]
@@ -451,6 +478,8 @@ pub struct AccountsSendRequest {
     pub destination: PaymentDestination,
     pub priority_fee_sompi: Fees,
     pub payload: Option<Vec<u8>>,
+    /// Token confirming a send which requires an approval under the account spending policy
+    pub approval_token: Option<String>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
@@ -470,6 +499,8 @@ pub struct AccountsTransferRequest {
     pub transfer_amount_sompi: u64,
     pub priority_fee_sompi: Option<Fees>,
     // pub priority_fee_sompi: Fees,
+    /// Token confirming a transfer which requires an approval under the account spending policy
+    pub approval_token: Option<String>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
@@ -9,3 +9,5 @@ pub mod traits;
 pub use traits::*;
 
 pub mod transport;
+
+pub mod hub;
@@ -7,6 +7,7 @@
 //! is implemented by the [`Wallet`] struct.
 //!
 
+use crate::account::policy::SpendingPolicy;
 use crate::api::message::*;
 use crate::imports::*;
 use crate::storage::{PrvKeyData, PrvKeyDataId, PrvKeyDataInfo, WalletDescriptor};
@@ -381,6 +382,61 @@ pub trait WalletApi: Send + Sync + AnySync {
         request: AccountsRenameRequest,
     ) -> Result<AccountsRenameResponse>;
 
+    /// Wrapper around [`accounts_set_policy_call()`](Self::accounts_set_policy_call)
+    async fn accounts_set_policy(
+        self: Arc<Self>,
+        account_id: AccountId,
+        policy: Option<SpendingPolicy>,
+        wallet_secret: Secret,
+        policy_secret: Secret,
+    ) -> Result<()> {
+        self.accounts_set_policy_call(AccountsSetPolicyRequest {
+            account_id,
+            policy,
+            wallet_secret,
+            policy_secret,
+        })
+        .await?;
+        Ok(())
+    }
+    /// Set or clear the account spending policy, limiting the daily amount spent,
+    /// restricting the destinations to whitelisted addresses and requiring large
+    /// spends to be confirmed with an approval token. The policy is guarded by
+    /// a policy secret distinct from the wallet secret, established by the first
+    /// policy set on the account.
+    ///
+    /// See [`accounts_set_policy`](Self::accounts_set_policy) for a convenience wrapper
+    /// around this call.
+    async fn accounts_set_policy_call(
+        self: Arc<Self>,
+        request: AccountsSetPolicyRequest,
+    ) -> Result<AccountsSetPolicyResponse>;
+
+    /// Wrapper around [`accounts_approve_send_call()`](Self::accounts_approve_send_call)
+    async fn accounts_approve_send(
+        self: Arc<Self>,
+        account_id: AccountId,
+        approval_token: String,
+        policy_secret: Secret,
+    ) -> Result<()> {
+        self.accounts_approve_send_call(AccountsApproveSendRequest {
+            account_id,
+            approval_token,
+            policy_secret,
+        })
+        .await?;
+        Ok(())
+    }
+    /// Approve with the policy secret the spend awaiting a confirmation with
+    /// `approval_token`, which then goes through once repeated with the token.
+    ///
+    /// See [`accounts_approve_send`](Self::accounts_approve_send) for a convenience wrapper
+    /// around this call.
+    async fn accounts_approve_send_call(
+        self: Arc<Self>,
+        request: AccountsApproveSendRequest,
+    ) -> Result<AccountsApproveSendResponse>;
+
     async fn accounts_select(self: Arc<Self>, account_id: Option<AccountId>) -> Result<()> {
         self.accounts_select_call(AccountsSelectRequest { account_id })
             .await?;
@@ -568,9 +624,12 @@ pub trait WalletApi: Send + Sync + AnySync {
     ) -> Result<AccountsTransferResponse>;
 
     /// Performs a transaction estimate, returning [`AccountsEstimateResponse`]
-    /// that contains [`GeneratorSummary`]. This call will estimate the total
-    /// amount of fees that will be required by the transaction as well as
-    /// the number of UTXOs that will be consumed by the transaction. If this
+    /// that contains [`GeneratorSummary`]. This call runs the full transaction
+    /// builder without signing or submitting, estimating the total amount of
+    /// fees that will be required by the transaction, the number of UTXOs
+    /// that will be consumed by the transaction as well as the mass and the
+    /// change of the final transaction, so that an over-mass transaction is
+    /// reported before submission. If this
     /// call is invoked while the previous instance of this call is already
     /// running for the same account, the previous call will be aborted returning
     /// an error.
@@ -92,6 +92,8 @@ impl WalletApi for WalletClient {
         PrvKeyDataRemove,
         PrvKeyDataGet,
         AccountsRename,
+        AccountsSetPolicy,
+        AccountsApproveSend,
         AccountsSelect,
         AccountsEnumerate,
         AccountsDiscovery,
@@ -172,6 +174,8 @@ impl WalletServer {
         PrvKeyDataRemove,
         PrvKeyDataGet,
         AccountsRename,
+        AccountsSetPolicy,
+        AccountsApproveSend,
         AccountsSelect,
         AccountsEnumerate,
         AccountsDiscovery,
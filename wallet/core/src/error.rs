@@ -72,6 +72,36 @@ pub enum Error {
     #[error("Wallet is not open")]
     WalletNotOpen,
 
+    #[error("Wallet '{0}' is already open")]
+    WalletAlreadyOpen(String),
+
+    #[error("No open wallet named '{0}'")]
+    WalletNotHosted(String),
+
+    #[error("Method '{0}' is not available on a hosted wallet")]
+    WalletHubMethodNotAllowed(String),
+
+    #[error("Destination address '{0}' is not whitelisted by the account spending policy")]
+    DestinationNotWhitelisted(String),
+
+    #[error("The send exceeds the daily spend limit of {limit} sompi, {spent} sompi were already sent within the last 24 hours")]
+    DailySpendLimitExceeded { limit: u64, spent: u64 },
+
+    #[error("The send requires a confirmation, approve the token {0} with the policy secret and repeat the send with it")]
+    SendApprovalRequired(String),
+
+    #[error("The approval token {0} was not approved with the policy secret")]
+    SendNotApproved(String),
+
+    #[error("Invalid or expired approval token")]
+    InvalidApprovalToken,
+
+    #[error("Invalid policy secret")]
+    InvalidPolicySecret,
+
+    #[error("The policy secret must differ from the wallet secret")]
+    PolicySecretNotSeparate,
+
     #[error("Wallet is not connected")]
     NotConnected,
 
@@ -275,6 +305,9 @@ pub enum Error {
     #[error("Transaction exceeds the maximum allowed mass")]
     GeneratorTransactionIsTooHeavy,
 
+    #[error("Memo of {size} bytes exceeds the maximum of {max} bytes")]
+    MemoTooLarge { size: usize, max: usize },
+
     #[error("Storage mass exceeds maximum")]
     StorageMassExceedsMaximumTransactionMass { storage_mass: u64 },
 
@@ -287,6 +320,12 @@ pub enum Error {
     #[error(transparent)]
     TxScriptError(#[from] karlsen_txscript_errors::TxScriptError),
 
+    #[error(transparent)]
+    AddressMessageError(#[from] karlsen_txscript::message::Error),
+
+    #[error("The private key does not match the address {0}")]
+    AddressKeyMismatch(karlsen_addresses::Address),
+
     #[error("Legacy account is not initialized")]
     LegacyAccountNotInitialized,
 
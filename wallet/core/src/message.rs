@@ -2,9 +2,12 @@
 //! Message signing and verification functions.
 //!
 
+use karlsen_addresses::{Address, Version};
 use karlsen_hashes::{Hash, PersonalMessageSigningHash};
 use secp256k1::{Error, XOnlyPublicKey};
 
+pub use karlsen_txscript::message::{calc_address_message_hash, verify_address_message};
+
 #[derive(Clone)]
 pub struct PersonalMessage<'a>(pub &'a str);
 
@@ -41,6 +44,39 @@ pub fn verify_message(
     sig.verify(&msg, pubkey)
 }
 
+/// Signs a message with the private key of `address`, proving the ownership of the address.
+///
+/// The message is signed under the `KarlsenSignedMessage` domain, with Schnorr for PubKey
+/// addresses and ECDSA for PubKeyECDSA addresses, and can be checked against the address alone
+/// with [`verify_address_message`].
+pub fn sign_address_message(
+    msg: &str,
+    privkey: &[u8; 32],
+    address: &Address,
+) -> crate::result::Result<Vec<u8>> {
+    let hash = calc_address_message_hash(msg.as_bytes());
+    let msg = secp256k1::Message::from_digest_slice(hash.as_bytes().as_slice())?;
+    let keypair = secp256k1::Keypair::from_seckey_slice(secp256k1::SECP256K1, privkey)?;
+    match address.version {
+        Version::PubKey => {
+            if keypair.x_only_public_key().0.serialize().as_slice() != address.payload.as_slice() {
+                return Err(crate::error::Error::AddressKeyMismatch(address.clone()));
+            }
+            Ok(keypair.sign_schnorr(msg).as_ref().to_vec())
+        }
+        Version::PubKeyECDSA => {
+            if keypair.public_key().serialize().as_slice() != address.payload.as_slice() {
+                return Err(crate::error::Error::AddressKeyMismatch(address.clone()));
+            }
+            Ok(secp256k1::SECP256K1
+                .sign_ecdsa(&msg, &keypair.secret_key())
+                .serialize_compact()
+                .to_vec())
+        }
+        version => Err(karlsen_txscript::message::Error::UnsupportedAddress(version).into()),
+    }
+}
+
 fn calc_personal_message_hash(msg: &PersonalMessage) -> Hash {
     let mut hasher = PersonalMessageSigningHash::new();
     hasher.write(msg);
@@ -50,6 +86,7 @@ fn calc_personal_message_hash(msg: &PersonalMessage) -> Hash {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use karlsen_addresses::Prefix;
 
     /// Sign message equivalent that's only used for tests
     /// Necessary only because of KIP test vectors
@@ -165,6 +202,40 @@ Ut omnis magnam et accusamus earum rem impedit provident eum commodi repellat qu
         assert!(verify_result.is_err());
     }
 
+    #[test]
+    fn test_sign_and_verify_address_message() {
+        let privkey = [3u8; 32];
+        let keypair =
+            secp256k1::Keypair::from_seckey_slice(secp256k1::SECP256K1, &privkey).unwrap();
+        let message = "I own this address";
+
+        for address in [
+            Address::new(
+                Prefix::Mainnet,
+                Version::PubKey,
+                &keypair.x_only_public_key().0.serialize(),
+            ),
+            Address::new(
+                Prefix::Mainnet,
+                Version::PubKeyECDSA,
+                &keypair.public_key().serialize(),
+            ),
+        ] {
+            let signature = sign_address_message(message, &privkey, &address)
+                .expect("sign_address_message failed");
+            verify_address_message(message.as_bytes(), &signature, &address)
+                .expect("verify_address_message failed");
+            assert!(verify_address_message(b"Another message", &signature, &address).is_err());
+        }
+
+        // A key cannot sign for an address it does not own
+        let address = Address::new(Prefix::Mainnet, Version::PubKey, &[1u8; 32]);
+        assert!(matches!(
+            sign_address_message(message, &privkey, &address),
+            Err(crate::error::Error::AddressKeyMismatch(_))
+        ));
+    }
+
     #[test]
     fn test_sign_and_verify_test_case_0() {
         let pm = PersonalMessage("Hello Karlsen!");
@@ -2,9 +2,10 @@
 //! Storage wrapper for account data.
 //!
 
+use crate::account::policy::SpendingPolicy;
 use crate::imports::*;
 
-const ACCOUNT_SETTINGS_VERSION: u32 = 0;
+const ACCOUNT_SETTINGS_VERSION: u32 = 2;
 
 #[derive(Default, Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
@@ -13,6 +14,11 @@ pub struct AccountSettings {
     pub name: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub meta: Option<Vec<u8>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub policy: Option<SpendingPolicy>,
+    /// Hash of the secret guarding the policy, see [`policy_secret_hash`](crate::account::policy::policy_secret_hash)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub policy_secret_hash: Option<Vec<u8>>,
 }
 
 impl BorshSerialize for AccountSettings {
@@ -20,6 +26,8 @@ impl BorshSerialize for AccountSettings {
         BorshSerialize::serialize(&ACCOUNT_SETTINGS_VERSION, writer)?;
         BorshSerialize::serialize(&self.name, writer)?;
         BorshSerialize::serialize(&self.meta, writer)?;
+        BorshSerialize::serialize(&self.policy, writer)?;
+        BorshSerialize::serialize(&self.policy_secret_hash, writer)?;
 
         Ok(())
     }
@@ -27,11 +35,26 @@ impl BorshSerialize for AccountSettings {
 
 impl BorshDeserialize for AccountSettings {
     fn deserialize(buf: &mut &[u8]) -> IoResult<Self> {
-        let _version: u32 = BorshDeserialize::deserialize(buf)?;
+        let version: u32 = BorshDeserialize::deserialize(buf)?;
         let name = BorshDeserialize::deserialize(buf)?;
         let meta = BorshDeserialize::deserialize(buf)?;
+        let policy = if version > 0 {
+            BorshDeserialize::deserialize(buf)?
+        } else {
+            None
+        };
+        let policy_secret_hash = if version > 1 {
+            BorshDeserialize::deserialize(buf)?
+        } else {
+            None
+        };
 
-        Ok(Self { name, meta })
+        Ok(Self {
+            name,
+            meta,
+            policy,
+            policy_secret_hash,
+        })
     }
 }
 
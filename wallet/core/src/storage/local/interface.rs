@@ -346,8 +346,12 @@ pub(crate) struct LocalStore {
 
 impl LocalStore {
     pub fn try_new(is_resident: bool) -> Result<Self> {
+        Self::try_with_location(is_resident, Location::default())
+    }
+
+    pub fn try_with_location(is_resident: bool, location: Location) -> Result<Self> {
         Ok(Self {
-            location: Arc::new(Mutex::new(Some(Arc::new(Location::default())))),
+            location: Arc::new(Mutex::new(Some(Arc::new(location)))),
             inner: Arc::new(Mutex::new(None)),
             is_resident,
             batch: Arc::new(AtomicBool::new(false)),
@@ -139,6 +139,21 @@ impl TransactionData {
         }
     }
 
+    /// Returns the transaction itself for the variants carrying it, ie. those issued by the wallet.
+    pub fn transaction(&self) -> Option<&Transaction> {
+        match self {
+            TransactionData::Reorg { .. }
+            | TransactionData::Incoming { .. }
+            | TransactionData::Stasis { .. }
+            | TransactionData::External { .. } => None,
+            TransactionData::Outgoing { transaction, .. }
+            | TransactionData::Batch { transaction, .. }
+            | TransactionData::TransferIncoming { transaction, .. }
+            | TransactionData::TransferOutgoing { transaction, .. }
+            | TransactionData::Change { transaction, .. } => Some(transaction),
+        }
+    }
+
     pub fn has_address(&self, address: &Address) -> bool {
         match self {
             TransactionData::Reorg { utxo_entries, .. } => utxo_entries
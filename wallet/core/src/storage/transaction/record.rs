@@ -282,6 +282,11 @@ export interface ITransactionRecord {
      * Transaction data type.
      */
     type: string;
+    /**
+     * Optional transaction payload (memo) of the transactions issued
+     * by the wallet and of the incoming transactions.
+     */
+    payload?: HexString;
 }
 "#;
 
@@ -336,11 +341,16 @@ pub struct TransactionRecord {
     #[serde(skip_serializing_if = "Option::is_none")]
     #[wasm_bindgen(getter_with_clone)]
     pub metadata: Option<String>,
+    /// Payload of an incoming transaction, resolved from the node
+    /// since UTXO change notifications do not convey it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[wasm_bindgen(skip)]
+    pub incoming_payload: Option<Vec<u8>>,
 }
 
 impl TransactionRecord {
     const STORAGE_MAGIC: u32 = 0x5854414b;
-    const STORAGE_VERSION: u32 = 0;
+    const STORAGE_VERSION: u32 = 1;
 
     pub fn id(&self) -> &TransactionId {
         &self.id
@@ -430,6 +440,31 @@ impl TransactionRecord {
         &self.transaction_data
     }
 
+    /// Payload (memo) attached to the transaction, if any.
+    ///
+    /// The payload is taken from the transaction for the transactions issued by the wallet,
+    /// including transfers between its accounts, and from the payload resolved on receipt
+    /// for the incoming transactions issued by third parties.
+    pub fn payload(&self) -> Option<&[u8]> {
+        self.transaction_data
+            .transaction()
+            .map(|transaction| transaction.payload.as_slice())
+            .or(self.incoming_payload.as_deref())
+            .filter(|payload| !payload.is_empty())
+    }
+
+    /// Attaches the payload of an incoming transaction to the record.
+    pub fn with_incoming_payload(mut self, payload: Option<Vec<u8>>) -> Self {
+        self.incoming_payload = payload;
+        self
+    }
+
+    /// Payload of the transaction as a human-readable memo, if it is valid UTF-8.
+    pub fn memo(&self) -> Option<&str> {
+        self.payload()
+            .and_then(|payload| std::str::from_utf8(payload).ok())
+    }
+
     // Transaction maturity ignores the stasis period and provides
     // a progress value based on the pending period. It is assumed
     // that transactions in stasis are not visible to the user.
@@ -563,6 +598,7 @@ impl TransactionRecord {
                 .expect("network expected for transaction record generation"),
             metadata: None,
             note: None,
+            incoming_payload: None,
         }
     }
 
@@ -598,6 +634,7 @@ impl TransactionRecord {
                 .expect("network expected for transaction record generation"),
             metadata: None,
             note: None,
+            incoming_payload: None,
         }
     }
 
@@ -657,6 +694,7 @@ impl TransactionRecord {
                 .expect("network expected for transaction record generation"),
             metadata: None,
             note: None,
+            incoming_payload: None,
         })
     }
 
@@ -716,6 +754,7 @@ impl TransactionRecord {
                 .expect("network expected for transaction record generation"),
             metadata: None,
             note: None,
+            incoming_payload: None,
         })
     }
 
@@ -774,6 +813,7 @@ impl TransactionRecord {
                 .expect("network expected for transaction record generation"),
             metadata: None,
             note: None,
+            incoming_payload: None,
         })
     }
 
@@ -832,6 +872,7 @@ impl TransactionRecord {
                 .expect("network expected for transaction record generation"),
             metadata: None,
             note: None,
+            incoming_payload: None,
         })
     }
 
@@ -885,6 +926,7 @@ impl TransactionRecord {
                 .expect("network expected for transaction record generation"),
             metadata: None,
             note: None,
+            incoming_payload: None,
         })
     }
 }
@@ -910,6 +952,11 @@ impl TransactionRecord {
         self.transaction_data.kind().to_string()
     }
 
+    #[wasm_bindgen(getter, js_name = "payload")]
+    pub fn payload_as_hex(&self) -> Option<String> {
+        self.payload().map(|payload| payload.to_hex())
+    }
+
     /// Check if the transaction record has the given address within the associated UTXO set.
     #[wasm_bindgen(js_name = hasAddress)]
     pub fn has_address(&self, address: &Address) -> bool {
@@ -942,6 +989,7 @@ impl BorshSerialize for TransactionRecord {
         BorshSerialize::serialize(&self.transaction_data, writer)?;
         BorshSerialize::serialize(&self.note, writer)?;
         BorshSerialize::serialize(&self.metadata, writer)?;
+        BorshSerialize::serialize(&self.incoming_payload, writer)?;
 
         Ok(())
     }
@@ -949,7 +997,7 @@ impl BorshSerialize for TransactionRecord {
 
 impl BorshDeserialize for TransactionRecord {
     fn deserialize(buf: &mut &[u8]) -> IoResult<Self> {
-        let StorageHeader { version: _, .. } = StorageHeader::deserialize(buf)?
+        let StorageHeader { version, .. } = StorageHeader::deserialize(buf)?
             .try_magic(Self::STORAGE_MAGIC)?
             .try_version(Self::STORAGE_VERSION)?;
 
@@ -962,6 +1010,11 @@ impl BorshDeserialize for TransactionRecord {
         let transaction_data = BorshDeserialize::deserialize(buf)?;
         let note = BorshDeserialize::deserialize(buf)?;
         let metadata = BorshDeserialize::deserialize(buf)?;
+        let incoming_payload = if version > 0 {
+            BorshDeserialize::deserialize(buf)?
+        } else {
+            None
+        };
 
         Ok(Self {
             id,
@@ -973,6 +1026,7 @@ impl BorshDeserialize for TransactionRecord {
             transaction_data,
             note,
             metadata,
+            incoming_payload,
         })
     }
 }
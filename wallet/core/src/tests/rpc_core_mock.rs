@@ -25,6 +25,8 @@ pub struct RpcCoreMock {
     ctl: RpcCtl,
     core_notifier: Arc<RpcCoreNotifier>,
     _sync_receiver: Receiver<()>,
+    /// Selected chain served by the block queries, ordered from the oldest block to the sink
+    chain: Mutex<Vec<RpcBlock>>,
 }
 
 impl RpcCoreMock {
@@ -45,9 +47,15 @@ impl RpcCoreMock {
             core_notifier,
             _sync_receiver: sync_receiver,
             ctl: RpcCtl::new(),
+            chain: Mutex::new(vec![]),
         }
     }
 
+    /// Sets the selected chain served by `get_sink`, `get_block` and `get_blocks`
+    pub fn set_chain(&self, chain: Vec<RpcBlock>) {
+        *self.chain.lock().unwrap() = chain;
+    }
+
     pub fn core_notifier(&self) -> Arc<RpcCoreNotifier> {
         self.core_notifier.clone()
     }
@@ -102,6 +110,9 @@ impl RpcApi for RpcCoreMock {
             is_synced: false,
             has_notify_command: false,
             has_message_id: false,
+            peer_clock_offset: 0,
+            header_clock_offset: 0,
+            is_clock_skewed: false,
         })
     }
 
@@ -156,7 +167,11 @@ impl RpcApi for RpcCoreMock {
     }
 
     async fn get_sink_call(&self, _request: GetSinkRequest) -> RpcResult<GetSinkResponse> {
-        Err(RpcError::NotImplemented)
+        let chain = self.chain.lock().unwrap();
+        let sink = chain.last().ok_or(RpcError::NotImplemented)?;
+        Ok(GetSinkResponse {
+            sink: sink.header.hash,
+        })
     }
 
     async fn get_mempool_entry_call(
@@ -191,8 +206,15 @@ impl RpcApi for RpcCoreMock {
         Err(RpcError::NotImplemented)
     }
 
-    async fn get_block_call(&self, _request: GetBlockRequest) -> RpcResult<GetBlockResponse> {
-        Err(RpcError::NotImplemented)
+    async fn get_block_call(&self, request: GetBlockRequest) -> RpcResult<GetBlockResponse> {
+        let chain = self.chain.lock().unwrap();
+        let block = chain
+            .iter()
+            .find(|block| block.header.hash == request.hash)
+            .ok_or_else(|| RpcError::General(format!("block {} not found", request.hash)))?;
+        Ok(GetBlockResponse {
+            block: block.clone(),
+        })
     }
 
     async fn get_subnetwork_call(
@@ -209,8 +231,15 @@ impl RpcApi for RpcCoreMock {
         Err(RpcError::NotImplemented)
     }
 
-    async fn get_blocks_call(&self, _request: GetBlocksRequest) -> RpcResult<GetBlocksResponse> {
-        Err(RpcError::NotImplemented)
+    async fn get_blocks_call(&self, request: GetBlocksRequest) -> RpcResult<GetBlocksResponse> {
+        let chain = self.chain.lock().unwrap();
+        let low = request
+            .low_hash
+            .and_then(|low_hash| chain.iter().position(|block| block.header.hash == low_hash))
+            .unwrap_or_default();
+        let blocks = chain[low..].to_vec();
+        let block_hashes = blocks.iter().map(|block| block.header.hash).collect();
+        Ok(GetBlocksResponse::new(block_hashes, blocks, None))
     }
 
     async fn get_block_count_call(
@@ -306,6 +335,125 @@ impl RpcApi for RpcCoreMock {
         Err(RpcError::NotImplemented)
     }
 
+    async fn get_server_capabilities_call(
+        &self,
+        _request: GetServerCapabilitiesRequest,
+    ) -> RpcResult<GetServerCapabilitiesResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn get_network_info_call(
+        &self,
+        _request: GetNetworkInfoRequest,
+    ) -> RpcResult<GetNetworkInfoResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn test_mempool_accept_call(
+        &self,
+        _request: TestMempoolAcceptRequest,
+    ) -> RpcResult<TestMempoolAcceptResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn get_difficulty_info_call(
+        &self,
+        _request: GetDifficultyInfoRequest,
+    ) -> RpcResult<GetDifficultyInfoResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn debug_script_call(
+        &self,
+        _request: DebugScriptRequest,
+    ) -> RpcResult<DebugScriptResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn get_transaction_status_call(
+        &self,
+        _request: GetTransactionStatusRequest,
+    ) -> RpcResult<GetTransactionStatusResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn get_block_filter_headers_call(
+        &self,
+        _request: GetBlockFilterHeadersRequest,
+    ) -> RpcResult<GetBlockFilterHeadersResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn get_block_filters_call(
+        &self,
+        _request: GetBlockFiltersRequest,
+    ) -> RpcResult<GetBlockFiltersResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn get_balance_by_addresses_at_call(
+        &self,
+        _request: GetBalanceByAddressesAtRequest,
+    ) -> RpcResult<GetBalanceByAddressesAtResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn get_unconfirmed_tx_risk_call(
+        &self,
+        _request: GetUnconfirmedTxRiskRequest,
+    ) -> RpcResult<GetUnconfirmedTxRiskResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn get_dag_slice_call(
+        &self,
+        _request: GetDagSliceRequest,
+    ) -> RpcResult<GetDagSliceResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn get_acceptance_events_since_call(
+        &self,
+        _request: GetAcceptanceEventsSinceRequest,
+    ) -> RpcResult<GetAcceptanceEventsSinceResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn get_template_diff_call(
+        &self,
+        _request: GetTemplateDiffRequest,
+    ) -> RpcResult<GetTemplateDiffResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn get_metadata_call(
+        &self,
+        _request: GetMetadataRequest,
+    ) -> RpcResult<GetMetadataResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn update_metadata_call(
+        &self,
+        _request: UpdateMetadataRequest,
+    ) -> RpcResult<UpdateMetadataResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn get_miner_stats_call(
+        &self,
+        _request: GetMinerStatsRequest,
+    ) -> RpcResult<GetMinerStatsResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn get_address_cluster_call(
+        &self,
+        _request: GetAddressClusterRequest,
+    ) -> RpcResult<GetAddressClusterResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
     // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
     // Notification API
 
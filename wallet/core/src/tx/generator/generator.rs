@@ -108,6 +108,10 @@ struct Context {
     utxo_stash: VecDeque<UtxoEntryReference>,
     /// final transaction id
     final_transaction_id: Option<TransactionId>,
+    /// final transaction mass
+    final_transaction_mass: Option<u64>,
+    /// final transaction change output value
+    final_transaction_change: Option<u64>,
     /// signifies that the generator is finished
     /// no more items will be produced in the
     /// iterator or a stream
@@ -463,6 +467,8 @@ impl Generator {
             stage: Some(Box::default()),
             utxo_stash: VecDeque::default(),
             final_transaction_id: None,
+            final_transaction_mass: None,
+            final_transaction_change: None,
             is_done: false,
         });
 
@@ -1136,6 +1142,8 @@ impl Generator {
                 );
 
                 context.final_transaction_id = Some(tx.id());
+                context.final_transaction_mass = Some(aggregate_mass);
+                context.final_transaction_change = Some(change_output_value);
                 context.number_of_transactions += 1;
 
                 Ok(Some(PendingTransaction::try_new(
@@ -1245,6 +1253,8 @@ impl Generator {
             aggregated_fees: context.aggregate_fees,
             final_transaction_amount: self.final_transaction_value_no_fees(),
             final_transaction_id: context.final_transaction_id,
+            final_transaction_mass: context.final_transaction_mass,
+            final_transaction_change: context.final_transaction_change,
             number_of_generated_transactions: context.number_of_transactions,
         }
     }
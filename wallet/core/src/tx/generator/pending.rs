@@ -152,6 +152,10 @@ impl PendingTransaction {
         self.inner.payment_value
     }
 
+    pub fn mass(&self) -> u64 {
+        self.inner.mass
+    }
+
     pub fn change_value(&self) -> u64 {
         self.inner.change_output_value
     }
@@ -12,7 +12,7 @@ use karlsen_consensus_core::tx::TransactionId;
 use serde::{Deserialize, Serialize};
 use std::fmt;
 
-#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct GeneratorSummary {
     pub network_id: NetworkId,
     pub aggregated_utxos: usize,
@@ -20,9 +20,14 @@ pub struct GeneratorSummary {
     pub number_of_generated_transactions: usize,
     pub final_transaction_amount: Option<u64>,
     pub final_transaction_id: Option<TransactionId>,
+    pub final_transaction_mass: Option<u64>,
+    pub final_transaction_change: Option<u64>,
 }
 
 impl GeneratorSummary {
+    /// Version 1 added `final_transaction_mass` and `final_transaction_change`
+    const BORSH_VERSION: u16 = 1;
+
     pub fn network_type(&self) -> NetworkType {
         self.network_id.into()
     }
@@ -50,6 +55,69 @@ impl GeneratorSummary {
     pub fn final_transaction_id(&self) -> Option<TransactionId> {
         self.final_transaction_id
     }
+
+    pub fn final_transaction_mass(&self) -> Option<u64> {
+        self.final_transaction_mass
+    }
+
+    pub fn final_transaction_change(&self) -> Option<u64> {
+        self.final_transaction_change
+    }
+}
+
+impl BorshSerialize for GeneratorSummary {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        BorshSerialize::serialize(&Self::BORSH_VERSION, writer)?;
+        BorshSerialize::serialize(&self.network_id, writer)?;
+        BorshSerialize::serialize(&self.aggregated_utxos, writer)?;
+        BorshSerialize::serialize(&self.aggregated_fees, writer)?;
+        BorshSerialize::serialize(&self.number_of_generated_transactions, writer)?;
+        BorshSerialize::serialize(&self.final_transaction_amount, writer)?;
+        BorshSerialize::serialize(&self.final_transaction_id, writer)?;
+        BorshSerialize::serialize(&self.final_transaction_mass, writer)?;
+        BorshSerialize::serialize(&self.final_transaction_change, writer)?;
+        Ok(())
+    }
+}
+
+impl BorshDeserialize for GeneratorSummary {
+    fn deserialize(buf: &mut &[u8]) -> std::io::Result<Self> {
+        let version: u16 = BorshDeserialize::deserialize(buf)?;
+        if version > Self::BORSH_VERSION {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!(
+                    "GeneratorSummary version {version} is newer than the supported version {}",
+                    Self::BORSH_VERSION
+                ),
+            ));
+        }
+        let network_id = BorshDeserialize::deserialize(buf)?;
+        let aggregated_utxos = BorshDeserialize::deserialize(buf)?;
+        let aggregated_fees = BorshDeserialize::deserialize(buf)?;
+        let number_of_generated_transactions = BorshDeserialize::deserialize(buf)?;
+        let final_transaction_amount = BorshDeserialize::deserialize(buf)?;
+        let final_transaction_id = BorshDeserialize::deserialize(buf)?;
+        let (final_transaction_mass, final_transaction_change) = if version > 0 {
+            (
+                BorshDeserialize::deserialize(buf)?,
+                BorshDeserialize::deserialize(buf)?,
+            )
+        } else {
+            (None, None)
+        };
+
+        Ok(Self {
+            network_id,
+            aggregated_utxos,
+            aggregated_fees,
+            number_of_generated_transactions,
+            final_transaction_amount,
+            final_transaction_id,
+            final_transaction_mass,
+            final_transaction_change,
+        })
+    }
 }
 
 impl fmt::Display for GeneratorSummary {
@@ -63,15 +131,25 @@ impl fmt::Display for GeneratorSummary {
             )
         };
 
+        let final_transaction = match (self.final_transaction_mass, self.final_transaction_change) {
+            (Some(mass), Some(change)) => format!(
+                "Mass: {}  Change: {}  ",
+                mass,
+                sompi_to_karlsen_string_with_suffix(change, &self.network_id)
+            ),
+            _ => "".to_string(),
+        };
+
         if let Some(final_transaction_amount) = self.final_transaction_amount {
             let total = final_transaction_amount + self.aggregated_fees;
             write!(
                 f,
-                "Amount: {}  Fees: {}  Total: {}  UTXOs: {}  {}",
+                "Amount: {}  Fees: {}  Total: {}  UTXOs: {}  {}{}",
                 sompi_to_karlsen_string_with_suffix(final_transaction_amount, &self.network_id),
                 sompi_to_karlsen_string_with_suffix(self.aggregated_fees, &self.network_id),
                 sompi_to_karlsen_string_with_suffix(total, &self.network_id),
                 self.aggregated_utxos,
+                final_transaction,
                 transactions
             )?;
         } else {
@@ -5,6 +5,7 @@ use crate::result::Result;
 use crate::tx::{Fees, MassCalculator, PaymentDestination};
 use crate::utxo::UtxoEntryReference;
 use crate::{tx::PaymentOutputs, utils::karlsen_to_sompi};
+use borsh::{BorshDeserialize, BorshSerialize};
 use karlsen_addresses::Address;
 use karlsen_consensus_core::network::{NetworkId, NetworkType};
 use karlsen_consensus_core::tx::Transaction;
@@ -116,6 +117,27 @@ impl GeneratorSummaryExtension for GeneratorSummary {
         );
         let aggregated_fees = accumulator.list.iter().map(|pt| pt.fees()).sum::<u64>();
         assert_eq!(self.aggregated_fees, aggregated_fees, "aggregated fees");
+        if let Some(final_transaction) = accumulator.list.last().filter(|pt| pt.is_final()) {
+            assert_eq!(
+                self.final_transaction_mass,
+                Some(final_transaction.mass()),
+                "final transaction mass"
+            );
+            assert_eq!(
+                self.final_transaction_change,
+                Some(final_transaction.change_value()),
+                "final transaction change"
+            );
+        }
+        let stored = GeneratorSummary::try_from_slice(&self.try_to_vec().unwrap()).unwrap();
+        assert_eq!(
+            (
+                stored.final_transaction_mass,
+                stored.final_transaction_change
+            ),
+            (self.final_transaction_mass, self.final_transaction_change),
+            "stored final transaction mass and change"
+        );
         self
     }
 }
@@ -30,6 +30,22 @@ pub(crate) const MINIMUM_RELAY_TRANSACTION_FEE: u64 = 1000;
 /// are considered standard and will therefore be relayed and considered for mining.
 pub const MAXIMUM_STANDARD_TRANSACTION_MASS: u64 = 100_000;
 
+/// MAXIMUM_MEMO_SIZE is the maximum size in bytes of a memo attached as the payload of a
+/// wallet transaction. Its mass stays at 1% of the maximum standard transaction mass, leaving
+/// the rest to the inputs and outputs of the payment.
+pub const MAXIMUM_MEMO_SIZE: usize = (MAXIMUM_STANDARD_TRANSACTION_MASS / 100) as usize;
+
+/// validate_memo checks that the memo fits within [`MAXIMUM_MEMO_SIZE`].
+pub fn validate_memo(memo: &[u8]) -> crate::result::Result<()> {
+    if memo.len() > MAXIMUM_MEMO_SIZE {
+        return Err(crate::error::Error::MemoTooLarge {
+            size: memo.len(),
+            max: MAXIMUM_MEMO_SIZE,
+        });
+    }
+    Ok(())
+}
+
 /// minimum_required_transaction_relay_fee returns the minimum transaction fee required
 /// for a transaction with the passed mass to be accepted into the mempool and relayed.
 pub fn calc_minimum_required_transaction_relay_fee(mass: u64) -> u64 {
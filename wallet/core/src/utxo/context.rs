@@ -105,6 +105,9 @@ pub struct Context {
     /// Confirmation occurs when the transaction UTXOs are
     /// removed from the context by the UTXO change notification.
     pub(crate) outgoing: AHashMap<TransactionId, OutgoingTransaction>,
+    /// Payloads of the pending incoming transactions, carried
+    /// over to their records once they reach maturity.
+    pub(crate) payloads: AHashMap<TransactionId, Vec<u8>>,
     /// Total balance of all UTXOs in this context (mature, pending)
     balance: Option<Balance>,
     /// Addresses monitored by this UTXO context
@@ -119,6 +122,7 @@ impl Default for Context {
             stasis: AHashMap::default(),
             map: AHashMap::default(),
             outgoing: AHashMap::default(),
+            payloads: AHashMap::default(),
             balance: None,
             addresses: Arc::new(DashSet::new()),
         }
@@ -486,7 +490,9 @@ impl UtxoContext {
                 unreachable!("Error: promotion of the outgoing transaction!");
             }
 
-            let record = TransactionRecord::new_incoming(self, txid, &utxos);
+            let payload = self.context().payloads.remove(&txid);
+            let record =
+                TransactionRecord::new_incoming(self, txid, &utxos).with_incoming_payload(payload);
             self.processor().notify(Events::Maturity { record }).await?;
         }
 
@@ -708,7 +714,12 @@ impl UtxoContext {
                 }
             } else if !is_coinbase_stasis {
                 // do not notify if coinbase transaction is in stasis
-                let record = TransactionRecord::new_incoming(self, txid, &utxos);
+                let payload = self.fetch_incoming_payload(txid, &utxos).await;
+                if let Some(payload) = payload.as_ref() {
+                    self.context().payloads.insert(txid, payload.clone());
+                }
+                let record = TransactionRecord::new_incoming(self, txid, &utxos)
+                    .with_incoming_payload(payload);
                 self.processor().notify(Events::Pending { record }).await?;
             }
         }
@@ -720,6 +731,26 @@ impl UtxoContext {
         Ok(())
     }
 
+    /// Fetches the payload of a third-party transaction paying to this context. Coinbase
+    /// payloads carry miner data rather than memos and are not fetched.
+    async fn fetch_incoming_payload(
+        &self,
+        txid: TransactionId,
+        utxos: &[UtxoEntryReference],
+    ) -> Option<Vec<u8>> {
+        let utxo = utxos.first()?;
+        if utxo.is_coinbase() {
+            return None;
+        }
+        self.processor()
+            .fetch_transaction_payload(txid, utxo.block_daa_score())
+            .await
+            .unwrap_or_else(|err| {
+                log_warn!("Unable to fetch the payload of transaction {txid}: {err}");
+                None
+            })
+    }
+
     pub(crate) async fn handle_utxo_removed(
         &self,
         mut utxos: Vec<UtxoEntryReference>,
@@ -808,7 +839,9 @@ impl UtxoContext {
         }
 
         for (txid, utxos) in pending.into_iter() {
-            let record = TransactionRecord::new_reorg(self, txid, &utxos);
+            let payload = self.context().payloads.remove(&txid);
+            let record =
+                TransactionRecord::new_reorg(self, txid, &utxos).with_incoming_payload(payload);
             self.processor().notify(Events::Reorg { record }).await?;
         }
 
@@ -37,6 +37,10 @@ use karlsen_rpc_core::{
 // use workflow_core::task;
 // use karlsen_metrics_core::{Metrics,Metric};
 
+/// Maximum number of chain blocks walked back from the sink when fetching the payload of an
+/// incoming transaction
+pub const PAYLOAD_LOOKUP_MAX_CHAIN_BLOCKS: usize = 1_000;
+
 pub struct Inner {
     /// Coinbase UTXOs in stasis
     stasis: DashMap<UtxoEntryId, PendingUtxoEntryReference>,
@@ -167,6 +171,51 @@ impl UtxoProcessor {
             .ok()
     }
 
+    /// Fetches the payload of the transaction `id` accepted by the chain block at `accepting_daa_score`.
+    ///
+    /// The selected chain is walked back from the sink to the selected parent of the accepting
+    /// block, the first batch of blocks above it then holding the mergeset of the accepting block.
+    /// Returns `None` if the transaction is not found within [`PAYLOAD_LOOKUP_MAX_CHAIN_BLOCKS`].
+    pub async fn fetch_transaction_payload(
+        &self,
+        id: TransactionId,
+        accepting_daa_score: u64,
+    ) -> Result<Option<Vec<u8>>> {
+        let rpc = self.rpc_api();
+        let mut hash = rpc.get_sink().await?.sink;
+        let mut reached = false;
+        for _ in 0..PAYLOAD_LOOKUP_MAX_CHAIN_BLOCKS {
+            let block = rpc.get_block(hash, false).await?;
+            if block.header.daa_score < accepting_daa_score {
+                reached = true;
+                break;
+            }
+            match block.verbose_data {
+                Some(verbose_data) => hash = verbose_data.selected_parent_hash,
+                None => return Ok(None),
+            }
+        }
+        if !reached {
+            return Ok(None);
+        }
+
+        let payload = rpc
+            .get_blocks(Some(hash), true, true)
+            .await?
+            .blocks
+            .into_iter()
+            .flat_map(|block| block.transactions)
+            .find(|transaction| {
+                transaction
+                    .verbose_data
+                    .as_ref()
+                    .is_some_and(|verbose_data| verbose_data.transaction_id == id)
+            })
+            .map(|transaction| transaction.payload)
+            .filter(|payload| !payload.is_empty());
+        Ok(payload)
+    }
+
     pub async fn bind_rpc(&self, rpc: Option<Rpc>) -> Result<()> {
         self.inner.rpc.lock().unwrap().clone_from(&rpc);
         let rpc_api = rpc.as_ref().map(|rpc| rpc.rpc_api().clone());
@@ -1,10 +1,14 @@
 use crate::imports::*;
 use crate::result::Result;
+use crate::storage::TransactionRecord;
 use crate::tests::RpcCoreMock;
 use crate::tx::generator::test::*;
 use crate::tx::*;
 use crate::utils::*;
 use crate::utxo::*;
+use karlsen_consensus_core::{header::Header, subnets::SUBNETWORK_ID_NATIVE};
+use karlsen_hashes::Hash;
+use karlsen_rpc_core::{RpcBlock, RpcBlockVerboseData, RpcTransaction, RpcTransactionVerboseData};
 
 #[tokio::test]
 async fn test_utxo_subsystem_bootstrap() -> Result<()> {
@@ -45,3 +49,122 @@ fn test_utxo_generator_empty_utxo_noop() -> Result<()> {
     // assert!(tx.is_none());
     Ok(())
 }
+
+fn chain_block(index: u64, transactions: Vec<RpcTransaction>) -> RpcBlock {
+    let hash = Hash::from_u64_word(index + 1);
+    let selected_parent_hash = Hash::from_u64_word(index);
+    let mut header = Header::from_precomputed_hash(hash, vec![selected_parent_hash]);
+    header.daa_score = 10 + index;
+    RpcBlock {
+        header,
+        verbose_data: Some(RpcBlockVerboseData {
+            hash,
+            difficulty: 1.0,
+            selected_parent_hash,
+            transaction_ids: vec![],
+            is_header_only: false,
+            blue_score: 10 + index,
+            children_hashes: vec![],
+            merge_set_blues_hashes: vec![],
+            merge_set_reds_hashes: vec![],
+            is_chain_block: true,
+        }),
+        transactions,
+    }
+}
+
+fn transaction_with_payload(id: TransactionId, payload: &[u8]) -> RpcTransaction {
+    RpcTransaction {
+        version: 0,
+        inputs: vec![],
+        outputs: vec![],
+        lock_time: 0,
+        subnetwork_id: SUBNETWORK_ID_NATIVE,
+        gas: 0,
+        payload: payload.to_vec(),
+        mass: 0,
+        verbose_data: Some(RpcTransactionVerboseData {
+            transaction_id: id,
+            hash: id,
+            mass: 0,
+            block_hash: Hash::default(),
+            block_time: 0,
+        }),
+    }
+}
+
+#[tokio::test]
+async fn test_incoming_transaction_payload() -> Result<()> {
+    let network_id = NetworkId::with_suffix(NetworkType::Testnet, 1);
+    let rpc_api_mock = Arc::new(RpcCoreMock::new());
+    let processor = UtxoProcessor::new(
+        Some(rpc_api_mock.clone().into()),
+        Some(network_id),
+        None,
+        None,
+    );
+    let context = UtxoContext::new(&processor, UtxoContextBinding::default());
+
+    // The transaction is merged by the chain block at DAA score 13
+    let id = TransactionId::from_u64_word(42);
+    let other = TransactionId::from_u64_word(43);
+    rpc_api_mock.set_chain(
+        (0..5)
+            .map(|index| match index {
+                3 => chain_block(
+                    index,
+                    vec![
+                        transaction_with_payload(other, &[]),
+                        transaction_with_payload(id, b"deposit-42"),
+                    ],
+                ),
+                _ => chain_block(index, vec![]),
+            })
+            .collect(),
+    );
+    assert_eq!(
+        processor.fetch_transaction_payload(id, 13).await?,
+        Some(b"deposit-42".to_vec())
+    );
+    // Empty payloads and unknown transactions resolve to no payload
+    assert_eq!(processor.fetch_transaction_payload(other, 13).await?, None);
+    assert_eq!(
+        processor
+            .fetch_transaction_payload(TransactionId::from_u64_word(44), 13)
+            .await?,
+        None
+    );
+
+    // The payload of an incoming record is surfaced as its memo and survives storage
+    let utxos = vec![UtxoEntryReference::simulated(karlsen_to_sompi(1.0))];
+    let record = TransactionRecord::new_incoming(&context, id, &utxos)
+        .with_incoming_payload(Some(b"deposit-42".to_vec()));
+    assert_eq!(record.payload(), Some(b"deposit-42".as_slice()));
+    assert_eq!(record.memo(), Some("deposit-42"));
+    let stored = TransactionRecord::try_from_slice(&record.try_to_vec()?)?;
+    assert_eq!(stored.memo(), Some("deposit-42"));
+
+    let record = TransactionRecord::new_incoming(&context, id, &utxos)
+        .with_incoming_payload(Some(vec![0xff, 0xfe]));
+    assert_eq!(record.payload(), Some([0xff, 0xfe].as_slice()));
+    assert_eq!(record.memo(), None);
+
+    // Records stored before the payload was kept still load
+    let record = TransactionRecord::new_incoming(&context, id, &utxos);
+    let mut bytes = record.try_to_vec()?;
+    bytes[4..8].copy_from_slice(&0u32.to_le_bytes());
+    assert_eq!(bytes.pop(), Some(0));
+    let stored = TransactionRecord::try_from_slice(&bytes)?;
+    assert_eq!(stored.payload(), None);
+
+    Ok(())
+}
+
+#[test]
+fn test_memo_size() {
+    assert!(validate_memo(&[0; MAXIMUM_MEMO_SIZE]).is_ok());
+    assert!(matches!(
+        validate_memo(&[0; MAXIMUM_MEMO_SIZE + 1]),
+        Err(Error::MemoTooLarge { size, max }) if size == MAXIMUM_MEMO_SIZE + 1 && max == MAXIMUM_MEMO_SIZE
+    ));
+}
@@ -378,6 +378,48 @@ impl WalletApi for super::Wallet {
         Ok(AccountsRenameResponse {})
     }
 
+    async fn accounts_set_policy_call(
+        self: Arc<Self>,
+        request: AccountsSetPolicyRequest,
+    ) -> Result<AccountsSetPolicyResponse> {
+        let AccountsSetPolicyRequest {
+            account_id,
+            policy,
+            wallet_secret,
+            policy_secret,
+        } = request;
+
+        let account = self
+            .get_account_by_id(&account_id)
+            .await?
+            .ok_or(Error::AccountNotFound(account_id))?;
+        account
+            .set_policy(&wallet_secret, &policy_secret, policy)
+            .await?;
+
+        Ok(AccountsSetPolicyResponse {})
+    }
+
+    async fn accounts_approve_send_call(
+        self: Arc<Self>,
+        request: AccountsApproveSendRequest,
+    ) -> Result<AccountsApproveSendResponse> {
+        let AccountsApproveSendRequest {
+            account_id,
+            approval_token,
+            policy_secret,
+        } = request;
+
+        let account = self
+            .get_account_by_id(&account_id)
+            .await?
+            .ok_or(Error::AccountNotFound(account_id))?;
+        self.spending_guard()
+            .approve_send(&account, &approval_token, &policy_secret)?;
+
+        Ok(AccountsApproveSendResponse {})
+    }
+
     async fn accounts_select_call(
         self: Arc<Self>,
         request: AccountsSelectRequest,
@@ -577,6 +619,7 @@ impl WalletApi for super::Wallet {
             destination,
             priority_fee_sompi,
             payload,
+            approval_token,
         } = request;
 
         let account = self
@@ -592,6 +635,7 @@ impl WalletApi for super::Wallet {
                 payload,
                 wallet_secret,
                 payment_secret,
+                approval_token,
                 &abortable,
                 None,
             )
@@ -614,6 +658,7 @@ impl WalletApi for super::Wallet {
             payment_secret,
             priority_fee_sompi,
             transfer_amount_sompi,
+            approval_token,
         } = request;
 
         let source_account = self
@@ -629,6 +674,7 @@ impl WalletApi for super::Wallet {
                 priority_fee_sompi.unwrap_or(Fees::SenderPays(0)),
                 wallet_secret,
                 payment_secret,
+                approval_token,
                 &abortable,
                 None,
             )
@@ -6,6 +6,7 @@ pub mod args;
 pub mod maps;
 pub use args::*;
 
+use crate::account::policy::SpendingGuard;
 use crate::account::ScanNotifier;
 use crate::compat::gen1::decrypt_mnemonic;
 use crate::error::Error::Custom;
@@ -13,7 +14,7 @@ use crate::factory::try_load_account;
 use crate::imports::*;
 use crate::settings::{SettingsStore, WalletSettings};
 use crate::storage::interface::{OpenArgs, StorageDescriptor};
-use crate::storage::local::interface::LocalStore;
+use crate::storage::local::interface::{LocalStore, Location};
 use crate::storage::local::Storage;
 use crate::wallet::maps::ActiveAccountMap;
 use karlsen_bip32::{ExtendedKey, Language, Mnemonic, Prefix as KeyPrefix, WordCount};
@@ -89,6 +90,7 @@ pub struct Inner {
     multiplexer: Multiplexer<Box<Events>>,
     wallet_bus: Channel<WalletBusMessage>,
     estimation_abortables: Mutex<HashMap<AccountId, Abortable>>,
+    spending_guard: SpendingGuard,
     retained_contexts: Mutex<HashMap<String, Arc<Vec<u8>>>>,
 }
 
@@ -109,6 +111,14 @@ impl Wallet {
         Ok(Arc::new(LocalStore::try_new(false)?))
     }
 
+    /// Local store keeping the wallet files in `folder` instead of the default storage folder
+    pub fn local_store_with_folder(folder: &str) -> Result<Arc<dyn Interface>> {
+        Ok(Arc::new(LocalStore::try_with_location(
+            false,
+            Location::new(folder),
+        )?))
+    }
+
     pub fn resident_store() -> Result<Arc<dyn Interface>> {
         Ok(Arc::new(LocalStore::try_new(true)?))
     }
@@ -175,6 +185,7 @@ impl Wallet {
                 utxo_processor: utxo_processor.clone(),
                 wallet_bus,
                 estimation_abortables: Mutex::new(HashMap::new()),
+                spending_guard: SpendingGuard::default(),
                 retained_contexts: Mutex::new(HashMap::new()),
             }),
         };
@@ -209,6 +220,11 @@ impl Wallet {
         &self.inner.legacy_accounts
     }
 
+    /// Enforces the spending policies of the wallet accounts.
+    pub fn spending_guard(&self) -> &SpendingGuard {
+        &self.inner.spending_guard
+    }
+
     pub async fn reset(self: &Arc<Self>, clear_legacy_cache: bool) -> Result<()> {
         self.utxo_processor().cleanup().await?;
 
@@ -278,6 +294,7 @@ impl Wallet {
     pub async fn close(self: &Arc<Wallet>) -> Result<()> {
         if self.is_open() {
             self.reset(true).await?;
+            self.spending_guard().clear().await;
             self.store().close().await?;
             self.notify(Events::WalletClose).await?;
         }
@@ -1373,6 +1373,10 @@ declare! {
          * If not supplied, the destination will be the change address resulting in a UTXO compound transaction.
          */
         destination? : IPaymentOutput[];
+        /**
+         * Token confirming a send which requires an approval under the account spending policy.
+         */
+        approvalToken? : string;
     }
     "#,
 }
@@ -1387,8 +1391,9 @@ try_from! ( args: IAccountsSendRequest, AccountsSendRequest, {
     let outputs = args.get_value("destination")?;
     let destination: PaymentDestination =
         if outputs.is_undefined() { PaymentDestination::Change } else { PaymentOutputs::try_owned_from(outputs)?.into() };
+    let approval_token = args.try_get_string("approvalToken")?;
 
-    Ok(AccountsSendRequest { account_id, wallet_secret, payment_secret, priority_fee_sompi, destination, payload })
+    Ok(AccountsSendRequest { account_id, wallet_secret, payment_secret, priority_fee_sompi, destination, payload, approval_token })
 });
 
 declare! {
@@ -1437,6 +1442,10 @@ declare! {
         paymentSecret? : string;
         priorityFeeSompi? : IFees | bigint;
         transferAmountSompi : bigint;
+        /**
+         * Token confirming a transfer which requires an approval under the account spending policy.
+         */
+        approvalToken? : string;
     }
     "#,
 }
@@ -1448,6 +1457,7 @@ try_from! ( args: IAccountsTransferRequest, AccountsTransferRequest, {
     let payment_secret = args.try_get_secret("paymentSecret")?;
     let priority_fee_sompi = args.try_get::<IFees>("priorityFeeSompi")?.map(Fees::try_from).transpose()?;
     let transfer_amount_sompi = args.get_u64("transferAmountSompi")?;
+    let approval_token = args.try_get_string("approvalToken")?;
 
     Ok(AccountsTransferRequest {
         source_account_id,
@@ -1456,6 +1466,7 @@ try_from! ( args: IAccountsTransferRequest, AccountsTransferRequest, {
         payment_secret,
         priority_fee_sompi,
         transfer_amount_sompi,
+        approval_token,
     })
 });
 
@@ -84,3 +84,80 @@ pub fn js_verify_message(value: IVerifyMessage) -> Result<bool, Error> {
         Err(Error::custom("Failed to parse input"))
     }
 }
+
+#[wasm_bindgen(typescript_custom_section)]
+const TS_MESSAGE_TYPES: &'static str = r#"
+/**
+ * Interface declaration for {@link signAddressMessage} function arguments.
+ * 
+ * @category Message Signing
+ */
+export interface ISignAddressMessage {
+    message: string;
+    privateKey: PrivateKey | string;
+    address: Address | string;
+}
+"#;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(extends = js_sys::Object, typescript_type = "ISignAddressMessage")]
+    pub type ISignAddressMessage;
+}
+
+/// Signs a message with the private key of the given address, proving the ownership
+/// of the address. The signature can be checked with {@link verifyAddressMessage}.
+/// @category Message Signing
+#[wasm_bindgen(js_name = signAddressMessage)]
+pub fn js_sign_address_message(value: ISignAddressMessage) -> Result<HexString, Error> {
+    if let Some(object) = Object::try_from(&value) {
+        let private_key = object.get_cast::<PrivateKey>("privateKey")?;
+        let address = object.get_cast::<Address>("address")?.into_owned();
+        let raw_msg = object.get_string("message")?;
+        let mut privkey_bytes = [0u8; 32];
+        privkey_bytes.copy_from_slice(&private_key.as_ref().secret_bytes());
+        let sig_result = sign_address_message(&raw_msg, &privkey_bytes, &address);
+        privkey_bytes.zeroize();
+        Ok(faster_hex::hex_string(sig_result?.as_slice()).into())
+    } else {
+        Err(Error::custom("Failed to parse input"))
+    }
+}
+
+#[wasm_bindgen(typescript_custom_section)]
+const TS_MESSAGE_TYPES: &'static str = r#"
+/**
+ * Interface declaration for {@link verifyAddressMessage} function arguments.
+ * 
+ * @category Message Signing
+ */
+export interface IVerifyAddressMessage {
+    message: string;
+    signature: HexString;
+    address: Address | string;
+}
+"#;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(extends = js_sys::Object, typescript_type = "IVerifyAddressMessage")]
+    pub type IVerifyAddressMessage;
+}
+
+/// Verifies that the signature of the given message was made with the key of the address
+/// @category Message Signing
+#[wasm_bindgen(js_name = verifyAddressMessage, skip_jsdoc)]
+pub fn js_verify_address_message(value: IVerifyAddressMessage) -> Result<bool, Error> {
+    if let Some(object) = Object::try_from(&value) {
+        let address = object.get_cast::<Address>("address")?.into_owned();
+        let raw_msg = object.get_string("message")?;
+        let signature = object.get_string("signature")?;
+
+        let mut signature_bytes = [0u8; 64];
+        faster_hex::hex_decode(signature.as_bytes(), &mut signature_bytes)?;
+
+        Ok(verify_address_message(raw_msg.as_bytes(), &signature_bytes, &address).is_ok())
+    } else {
+        Err(Error::custom("Failed to parse input"))
+    }
+}
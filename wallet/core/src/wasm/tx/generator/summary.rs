@@ -5,7 +5,8 @@ use crate::tx::generator as core;
 /// A class containing a summary produced by transaction {@link Generator}.
 /// This class contains the number of transactions, the aggregated fees,
 /// the aggregated UTXOs and the final transaction amount that includes
-/// both network and QoS (priority) fees.
+/// both network and QoS (priority) fees, along with the mass and the
+/// change of the final transaction.
 ///
 /// @see {@link createTransactions}, {@link IGeneratorSettingsObject}, {@link Generator}
 /// @category Wallet SDK
@@ -46,6 +47,16 @@ impl GeneratorSummary {
     pub fn final_transaction_id(&self) -> Option<String> {
         self.inner.final_transaction_id().map(|id| id.to_string())
     }
+
+    #[wasm_bindgen(getter, js_name = finalMass)]
+    pub fn final_transaction_mass(&self) -> Option<BigInt> {
+        self.inner.final_transaction_mass().map(BigInt::from)
+    }
+
+    #[wasm_bindgen(getter, js_name = finalChange)]
+    pub fn final_transaction_change(&self) -> Option<BigInt> {
+        self.inner.final_transaction_change().map(BigInt::from)
+    }
 }
 
 impl From<core::GeneratorSummary> for GeneratorSummary {
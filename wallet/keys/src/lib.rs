@@ -11,5 +11,6 @@ pub mod publickey;
 pub mod result;
 pub mod secret;
 pub mod types;
+pub mod xkey;
 pub mod xprv;
 pub mod xpub;
@@ -6,5 +6,6 @@ pub use crate::pubkeygen::*;
 pub use crate::publickey::*;
 pub use crate::secret::*;
 pub use crate::types::*;
+pub use crate::xkey::*;
 pub use crate::xprv::*;
 pub use crate::xpub::*;
@@ -0,0 +1,85 @@
+//!
+//! Network-specific version bytes of the extended keys.
+//!
+//! Karlsen extended keys are serialized in the standard BIP32 base58check
+//! format, using `kprv`/`kpub` prefixes on mainnet and `ktrv`/`ktub` on the
+//! test networks.
+//!
+
+use karlsen_bip32::Prefix;
+use karlsen_consensus_core::network::NetworkType;
+
+/// Extended private key [`Prefix`] of the network.
+pub fn xprv_prefix(network_type: NetworkType) -> Prefix {
+    match network_type {
+        NetworkType::Mainnet => Prefix::KPRV,
+        NetworkType::Testnet | NetworkType::Devnet | NetworkType::Simnet => Prefix::KTRV,
+    }
+}
+
+/// Extended public key [`Prefix`] of the network.
+pub fn xpub_prefix(network_type: NetworkType) -> Prefix {
+    match network_type {
+        NetworkType::Mainnet => Prefix::KPUB,
+        NetworkType::Testnet | NetworkType::Devnet | NetworkType::Simnet => Prefix::KTUB,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::derivation::gen1::WalletDerivationManager;
+    use crate::derivation::traits::*;
+    use karlsen_bip32::{ChildNumber, ExtendedPrivateKey, ExtendedPublicKey, SecretKey};
+    use std::str::FromStr;
+
+    const MASTER_XPRV: &str = "kprv5y2qurMHCsXYrpeDB395BY2DPKYHUGaCMpFAYRi1cmhwin1bWRyUXVbtTyy54FCGxPnnEvbK9WaiaQgkGS9ngGxmHy1bubZYY6MTokeYP2Q";
+
+    #[test]
+    fn test_xkey_network_roundtrip() {
+        let master = ExtendedPrivateKey::<SecretKey>::from_str(MASTER_XPRV).unwrap();
+        let account = master
+            .derive_child(ChildNumber::new(44, true).unwrap())
+            .and_then(|key| key.derive_child(ChildNumber::new(121337, true).unwrap()))
+            .and_then(|key| key.derive_child(ChildNumber::new(0, true).unwrap()))
+            .unwrap();
+
+        for network_type in [NetworkType::Mainnet, NetworkType::Testnet] {
+            // an xprv imported at the account depth keeps its attributes
+            let xprv = account.to_string(xprv_prefix(network_type));
+            let imported = ExtendedPrivateKey::<SecretKey>::from_str(&xprv).unwrap();
+            assert_eq!(imported, account);
+            assert_eq!(imported.attrs().depth, 3);
+
+            let xpub = imported
+                .public_key()
+                .to_string(Some(xpub_prefix(network_type)));
+            let imported = ExtendedPublicKey::<secp256k1::PublicKey>::from_str(&xpub).unwrap();
+            assert_eq!(imported, account.public_key());
+        }
+        assert!(account
+            .to_string(xprv_prefix(NetworkType::Mainnet))
+            .starts_with("kprv"));
+        assert!(account
+            .to_string(xprv_prefix(NetworkType::Simnet))
+            .starts_with("ktrv"));
+
+        // the account xpub matches the one of the wallet derivation
+        let wallet =
+            WalletDerivationManager::from_master_xprv(MASTER_XPRV, false, 0, None).unwrap();
+        assert_eq!(
+            account.public_key().to_string(Some(Prefix::KPUB)),
+            wallet.to_string(Some(Prefix::KPUB)).to_string()
+        );
+
+        // non-hardened children derive the same from either key, hardened ones only from the xprv
+        let child = ChildNumber::new(7, false).unwrap();
+        assert_eq!(
+            account.derive_child(child).unwrap().public_key(),
+            account.public_key().derive_child(child).unwrap()
+        );
+        let hardened = ChildNumber::new(7, true).unwrap();
+        assert!(account.derive_child(hardened).is_ok());
+        assert!(account.public_key().derive_child(hardened).is_err());
+    }
+}
@@ -1,4 +1,7 @@
+use crate::derivation::gen1::WalletDerivationManager;
 use crate::imports::*;
+use crate::xkey::xprv_prefix;
+use karlsen_bip32::SecretKeyExt;
 
 ///
 /// Extended private key (XPrv).
@@ -68,11 +71,62 @@ impl XPrv {
         Ok(str)
     }
 
+    /// Serialize using the version bytes of the network: `kprv` on mainnet, `ktrv` on the test networks.
+    #[wasm_bindgen(js_name = toNetworkString)]
+    pub fn to_network_string(&self, network: &NetworkTypeT) -> Result<String> {
+        let prefix = xprv_prefix(network.try_into()?);
+        Ok(self.inner.to_extended_key(prefix).to_string())
+    }
+
     #[wasm_bindgen(js_name = toXPub)]
     pub fn to_xpub(&self) -> Result<XPub> {
         let public_key = self.inner.public_key();
         Ok(public_key.into())
     }
+
+    /// Create the {@link XPub} of the wallet account `accountIndex`, derived from
+    /// this master key along the same path as the wallet accounts.
+    #[wasm_bindgen(js_name = toAccountXPub)]
+    pub fn to_account_xpub(&self, account_index: u64) -> Result<XPub> {
+        if self.inner.attrs().depth != 0 {
+            return Err(Error::custom(
+                "Account XPub can only be derived from a master XPrv",
+            ));
+        }
+        let (private_key, attrs) = WalletDerivationManager::derive_extended_key_from_master_key(
+            self.inner.clone(),
+            false,
+            account_index,
+        )?;
+        Ok(ExtendedPublicKey::from_public_key(private_key.get_public_key(), &attrs).into())
+    }
+
+    #[wasm_bindgen(js_name = toPrivateKey)]
+    pub fn to_private_key(&self) -> PrivateKey {
+        self.inner.private_key().into()
+    }
+
+    /// Depth of the key in the derivation hierarchy, `0` for a master key.
+    #[wasm_bindgen(getter)]
+    pub fn depth(&self) -> u8 {
+        self.inner.attrs().depth
+    }
+
+    #[wasm_bindgen(getter, js_name = parentFingerprint)]
+    pub fn parent_fingerprint(&self) -> String {
+        self.inner.attrs().parent_fingerprint.to_vec().to_hex()
+    }
+
+    /// Child number of the key, including the hardened flag.
+    #[wasm_bindgen(getter, js_name = childNumber)]
+    pub fn child_number(&self) -> u32 {
+        self.inner.attrs().child_number.0
+    }
+
+    #[wasm_bindgen(getter, js_name = chainCode)]
+    pub fn chain_code(&self) -> String {
+        self.inner.attrs().chain_code.to_vec().to_hex()
+    }
 }
 
 impl<'a> From<&'a XPrv> for &'a ExtendedPrivateKey<SecretKey> {
@@ -1,4 +1,5 @@
 use crate::imports::*;
+use crate::xkey::xpub_prefix;
 
 ///
 /// Extended public key (XPub).
@@ -51,10 +52,38 @@ impl XPub {
         Ok(self.inner.to_string(Some(prefix.try_into()?)))
     }
 
+    /// Serialize using the version bytes of the network: `kpub` on mainnet, `ktub` on the test networks.
+    #[wasm_bindgen(js_name = toNetworkString)]
+    pub fn to_network_string(&self, network: &NetworkTypeT) -> Result<String> {
+        Ok(self.inner.to_string(Some(xpub_prefix(network.try_into()?))))
+    }
+
     #[wasm_bindgen(js_name = toPublicKey)]
     pub fn public_key(&self) -> PublicKey {
         self.inner.public_key().into()
     }
+
+    /// Depth of the key in the derivation hierarchy, `0` for a master key.
+    #[wasm_bindgen(getter)]
+    pub fn depth(&self) -> u8 {
+        self.inner.attrs().depth
+    }
+
+    #[wasm_bindgen(getter, js_name = parentFingerprint)]
+    pub fn parent_fingerprint(&self) -> String {
+        self.inner.attrs().parent_fingerprint.to_vec().to_hex()
+    }
+
+    /// Child number of the key, including the hardened flag.
+    #[wasm_bindgen(getter, js_name = childNumber)]
+    pub fn child_number(&self) -> u32 {
+        self.inner.attrs().child_number.0
+    }
+
+    #[wasm_bindgen(getter, js_name = chainCode)]
+    pub fn chain_code(&self) -> String {
+        self.inner.attrs().chain_code.to_vec().to_hex()
+    }
 }
 
 impl From<ExtendedPublicKey<secp256k1::PublicKey>> for XPub {
@@ -4,6 +4,8 @@ let {
     PublicKey,
     signMessage,
     verifyMessage,
+    signAddressMessage,
+    verifyAddressMessage,
 } = karlsen;
 
 karlsen.initConsolePanicHook();
@@ -28,3 +30,14 @@ function runDemo(message, privateKey, publicKey) {
 runDemo(message, privkey, pubkey);
 // Using Objects:
 runDemo(message, new PrivateKey(privkey), new PublicKey(pubkey));
+
+
+// Proving the ownership of an address:
+let address = new PrivateKey(privkey).toAddress('mainnet');
+let signature = signAddressMessage({message, privateKey: privkey, address});
+console.info(`Address: ${address} => Signature: ${signature}`);
+if (verifyAddressMessage({message, signature, address})) {
+    console.info('Address ownership verified!');
+} else {
+    console.info('Signature is invalid!');
+}